@@ -79,7 +79,7 @@ mod tests {
         ExchangeService, ExchangeServiceServer,
     };
     use risingwave_pb::task_service::{
-        GetDataRequest, GetDataResponse, GetStreamRequest, GetStreamResponse,
+        ExchangeOptions, GetDataRequest, GetDataResponse, GetStreamRequest, GetStreamResponse,
     };
     use risingwave_rpc_client::{ExchangeSource, GrpcExchangeSource};
     use tokio_stream::wrappers::ReceiverStream;
@@ -104,6 +104,7 @@ mod tests {
                 tx.send(Ok(GetDataResponse {
                     status: None,
                     record_batch: Some(DataChunk::default()),
+                    ..Default::default()
                 }))
                 .await
                 .unwrap();
@@ -151,6 +152,7 @@ mod tests {
                 task_id: Some(TaskId::default()),
                 ..Default::default()
             },
+            ExchangeOptions::default(),
         )
         .await
         .unwrap();
@@ -168,7 +170,9 @@ mod tests {
     #[tokio::test]
     async fn test_unconnectable_node() {
         let addr = "127.0.0.1:1001".parse().unwrap();
-        let res = GrpcExchangeSource::create(addr, TaskOutputId::default()).await;
+        let res =
+            GrpcExchangeSource::create(addr, TaskOutputId::default(), ExchangeOptions::default())
+                .await;
         assert!(res.is_err());
     }
 }
@@ -16,10 +16,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use risingwave_common::array::DataChunk;
-use risingwave_common::catalog::{ColumnId, Schema, TableId};
+use risingwave_common::catalog::{ColumnDesc, Schema, TableId};
 use risingwave_common::error::ErrorCode::{InternalError, ProtocolError};
 use risingwave_common::error::{Result, RwError};
-use risingwave_common::types::DataType;
 use risingwave_pb::plan::plan_node::NodeBody;
 use risingwave_pb::plan::RowFormatType;
 use risingwave_source::parser::JSONParser;
@@ -94,15 +93,11 @@ impl BoxedExecutorBuilder for CreateSourceExecutor {
             .get_column_descs()
             .iter()
             .enumerate()
-            .map(|(idx, c)| {
-                Ok(SourceColumnDesc {
-                    name: c.name.clone(),
-                    data_type: DataType::from(c.get_column_type()?),
-                    column_id: ColumnId::from(c.column_id),
-                    skip_parse: idx as i32 == row_id_index,
-                })
+            .map(|(idx, c)| SourceColumnDesc {
+                skip_parse: idx as i32 == row_id_index,
+                ..(&ColumnDesc::from(c)).into()
             })
-            .collect::<Result<Vec<SourceColumnDesc>>>()?;
+            .collect::<Vec<SourceColumnDesc>>();
 
         let format = match info.get_row_format().unwrap() {
             RowFormatType::Json => SourceFormat::Json,
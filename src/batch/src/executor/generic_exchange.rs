@@ -18,6 +18,7 @@ use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::Result;
 use risingwave_common::util::addr::{is_local_address, HostAddr};
+use risingwave_common::util::exchange::exchange_options;
 use risingwave_pb::plan::plan_node::NodeBody;
 use risingwave_pb::plan::{ExchangeSource as ProstExchangeSource, Field as NodeField};
 use risingwave_rpc_client::{ExchangeSource, GrpcExchangeSource};
@@ -78,7 +79,12 @@ impl CreateSource for DefaultCreateSource {
             value.get_task_output_id()
         );
         Ok(Box::new(
-            GrpcExchangeSource::create(peer_addr, value.get_task_output_id()?.clone()).await?,
+            GrpcExchangeSource::create(
+                peer_addr,
+                value.get_task_output_id()?.clone(),
+                exchange_options(&env.config().exchange),
+            )
+            .await?,
         ))
     }
 }
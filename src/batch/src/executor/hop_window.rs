@@ -0,0 +1,272 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+
+use num_traits::CheckedSub;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
+use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
+use risingwave_expr::expr::{BoxedExpression, Expression, InputRefExpression, LiteralExpression};
+use risingwave_pb::expr::expr_node;
+use risingwave_pb::plan::plan_node::NodeBody;
+
+use crate::executor::{BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder};
+
+/// Emits each input row once for every hop window containing its time column, appending the
+/// `window_start` and `window_end` columns. Rows of the same window are emitted in one chunk.
+pub(super) struct HopWindowExecutor {
+    child: BoxedExecutor,
+    /// Evaluates the start of the first window containing each row.
+    hop_start: BoxedExpression,
+    /// `(window_start, window_end)` of each window, evaluated on a chunk of `hop_start`.
+    window_exprs: Vec<(BoxedExpression, BoxedExpression)>,
+    /// Output chunks of the last input chunk which are not returned yet.
+    pending: VecDeque<DataChunk>,
+
+    schema: Schema,
+    identity: String,
+}
+
+impl HopWindowExecutor {
+    fn new(
+        child: BoxedExecutor,
+        time_col_idx: usize,
+        window_slide: IntervalUnit,
+        window_size: IntervalUnit,
+        identity: String,
+    ) -> Result<Self> {
+        let units = window_size
+            .exact_div(&window_slide)
+            .and_then(|x| NonZeroUsize::new(usize::try_from(x).ok()?))
+            .ok_or_else(|| {
+                InternalError(format!(
+                    "window_size {} cannot be divided by window_slide {}",
+                    window_size, window_slide
+                ))
+            })?
+            .get();
+
+        let interval_literal = |interval: IntervalUnit| {
+            LiteralExpression::new(DataType::Interval, Some(ScalarImpl::Interval(interval))).boxed()
+        };
+        let checked_mul = |i: usize| {
+            window_slide.checked_mul_int(i).ok_or_else(|| {
+                RwError::from(InternalError(format!(
+                    "window_slide {} cannot be multiplied by {}",
+                    window_slide, i
+                )))
+            })
+        };
+
+        // The first window containing a row starts at
+        // `tumble_start(time_col - (window_size - window_slide), window_slide)`.
+        let window_size_sub_slide = window_size.checked_sub(&window_slide).ok_or_else(|| {
+            InternalError(format!(
+                "window_size {} cannot be subtracted by window_slide {}",
+                window_size, window_slide
+            ))
+        })?;
+        let time_col_type = child.schema().fields()[time_col_idx].data_type();
+        let hop_start = new_binary_expr(
+            expr_node::Type::TumbleStart,
+            DataType::Timestamp,
+            new_binary_expr(
+                expr_node::Type::Subtract,
+                DataType::Timestamp,
+                InputRefExpression::new(time_col_type, time_col_idx).boxed(),
+                interval_literal(window_size_sub_slide),
+            ),
+            interval_literal(window_slide),
+        );
+
+        let window_exprs = (0..units)
+            .map(|i| {
+                let offset_expr = |offset| {
+                    new_binary_expr(
+                        expr_node::Type::Add,
+                        DataType::Timestamp,
+                        InputRefExpression::new(DataType::Timestamp, 0).boxed(),
+                        interval_literal(offset),
+                    )
+                };
+                Ok((
+                    offset_expr(checked_mul(i)?),
+                    offset_expr(checked_mul(i + units)?),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut fields = child.schema().fields().to_vec();
+        fields.push(Field::with_name(DataType::Timestamp, "window_start"));
+        fields.push(Field::with_name(DataType::Timestamp, "window_end"));
+
+        Ok(Self {
+            child,
+            hop_start,
+            window_exprs,
+            pending: VecDeque::new(),
+            schema: Schema { fields },
+            identity,
+        })
+    }
+
+    fn expand_chunk(&mut self, chunk: DataChunk) -> Result<()> {
+        let chunk = chunk.compact()?;
+        let hop_start = self.hop_start.eval(&chunk)?;
+        let hop_start_chunk = DataChunk::new(vec![Column::new(hop_start)], None);
+        let (columns, _) = chunk.into_parts();
+        for (window_start_expr, window_end_expr) in &self.window_exprs {
+            let window_start = window_start_expr.eval(&hop_start_chunk)?;
+            let window_end = window_end_expr.eval(&hop_start_chunk)?;
+            let mut new_columns = columns.clone();
+            new_columns.push(Column::new(window_start));
+            new_columns.push(Column::new(window_end));
+            self.pending.push_back(DataChunk::new(new_columns, None));
+        }
+        Ok(())
+    }
+}
+
+impl BoxedExecutorBuilder for HopWindowExecutor {
+    fn new_boxed_executor(source: &ExecutorBuilder) -> Result<BoxedExecutor> {
+        ensure!(source.plan_node().get_children().len() == 1);
+
+        let hop_window_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::HopWindow
+        )?;
+
+        let child = source
+            .clone_for_plan(&source.plan_node().get_children()[0])
+            .build()?;
+        let time_col_idx = hop_window_node.get_time_col()?.column_idx as usize;
+        let window_slide = hop_window_node.get_window_slide()?.into();
+        let window_size = hop_window_node.get_window_size()?.into();
+
+        Ok(Box::new(
+            Self::new(
+                child,
+                time_col_idx,
+                window_slide,
+                window_size,
+                source.plan_node().get_identity().clone(),
+            )?
+            .fuse(),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Executor for HopWindowExecutor {
+    async fn open(&mut self) -> Result<()> {
+        self.child.open().await
+    }
+
+    async fn next(&mut self) -> Result<Option<DataChunk>> {
+        loop {
+            if let Some(chunk) = self.pending.pop_front() {
+                return Ok(Some(chunk));
+            }
+            match self.child.next().await? {
+                Some(chunk) => self.expand_chunk(chunk)?,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.child.close().await
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use risingwave_common::array::{Array, I32Array, NaiveDateTimeArray};
+    use risingwave_common::column_nonnull;
+    use risingwave_common::types::NaiveDateTimeWrapper;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_hop_window_executor() {
+        let t = |hours, minutes| {
+            let date = NaiveDate::from_ymd(2022, 2, 2);
+            let time = NaiveTime::from_hms(hours, minutes, 0);
+            NaiveDateTimeWrapper(NaiveDateTime::new(date, time))
+        };
+
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Timestamp),
+            ],
+        };
+        let chunk = DataChunk::builder()
+            .columns(vec![
+                column_nonnull! { I32Array, [1, 2] },
+                column_nonnull! { NaiveDateTimeArray, [t(10, 5), t(10, 20)] },
+            ])
+            .build();
+        let child = Box::new(MockExecutor::with_chunk(chunk, schema));
+
+        let mut executor = HopWindowExecutor::new(
+            child,
+            1,
+            IntervalUnit::from_minutes(15),
+            IntervalUnit::from_minutes(30),
+            "HopWindowExecutor".to_string(),
+        )
+        .unwrap();
+        assert_eq!(executor.schema().len(), 4);
+
+        executor.open().await.unwrap();
+        let expected = [
+            [(t(9, 45), t(10, 15)), (t(10, 0), t(10, 30))],
+            [(t(10, 0), t(10, 30)), (t(10, 15), t(10, 45))],
+        ];
+        for window in expected {
+            let chunk = executor.next().await.unwrap().unwrap();
+            assert_eq!(chunk.cardinality(), 2);
+            let starts = chunk.column_at(2).array();
+            let ends = chunk.column_at(3).array();
+            for (row, (start, end)) in window.into_iter().enumerate() {
+                assert_eq!(
+                    starts.as_naivedatetime().value_at(row),
+                    Some(start),
+                    "row {}",
+                    row
+                );
+                assert_eq!(ends.as_naivedatetime().value_at(row), Some(end));
+            }
+        }
+        assert!(executor.next().await.unwrap().is_none());
+        executor.close().await.unwrap();
+    }
+}
@@ -17,6 +17,7 @@ use drop_table::*;
 use filter::*;
 use generic_exchange::*;
 use hash_agg::*;
+use hop_window::*;
 use limit::*;
 use merge_sort_exchange::*;
 use order_by::*;
@@ -41,6 +42,7 @@ use crate::executor::join::nested_loop_join::NestedLoopJoinExecutor;
 use crate::executor::join::sort_merge_join::SortMergeJoinExecutor;
 use crate::executor::join::HashJoinExecutorBuilder;
 pub use crate::executor::stream_scan::StreamScanExecutor;
+use crate::executor::table_function::TableFunctionExecutor;
 use crate::executor::trace::TraceExecutor;
 use crate::executor::values::ValuesExecutor;
 use crate::task::{BatchEnvironment, TaskId};
@@ -55,6 +57,7 @@ mod fuse;
 mod generate_series;
 mod generic_exchange;
 mod hash_agg;
+mod hop_window;
 mod insert;
 mod join;
 mod limit;
@@ -65,6 +68,7 @@ mod projection;
 mod row_seq_scan;
 mod sort_agg;
 mod stream_scan;
+mod table_function;
 #[cfg(test)]
 mod test_utils;
 mod top_n;
@@ -181,7 +185,9 @@ impl<'a> ExecutorBuilder<'a> {
             NodeBody::DropSource => DropStreamExecutor,
             NodeBody::HashAgg => HashAggExecutorBuilder,
             NodeBody::MergeSortExchange => MergeSortExchangeExecutor,
-            NodeBody::GenerateInt32Series => GenerateSeriesI32Executor
+            NodeBody::GenerateInt32Series => GenerateSeriesI32Executor,
+            NodeBody::TableFunction => TableFunctionExecutor,
+            NodeBody::HopWindow => HopWindowExecutor
         }?;
         let input_desc = real_executor.identity().to_string();
         Ok(Box::new(TraceExecutor::new(real_executor, input_desc)))
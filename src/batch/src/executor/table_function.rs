@@ -0,0 +1,95 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::column::Column;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::Result;
+use risingwave_expr::table_function::{build_from_prost, BoxedTableFunction};
+use risingwave_pb::plan::plan_node::NodeBody;
+
+use crate::executor::{BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder};
+
+/// Evaluates a table function with constant arguments, e.g. `SELECT * FROM
+/// jsonb_array_elements('[1, 2]')`.
+pub(super) struct TableFunctionExecutor {
+    table_function: BoxedTableFunction,
+    /// Whether the table function has been evaluated.
+    done: bool,
+
+    schema: Schema,
+    identity: String,
+}
+
+impl BoxedExecutorBuilder for TableFunctionExecutor {
+    fn new_boxed_executor(source: &ExecutorBuilder) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::TableFunction
+        )?;
+
+        let table_function = build_from_prost(node.get_table_function()?)?;
+        let schema = Schema::new(vec![Field::unnamed(table_function.return_type())]);
+
+        Ok(Box::new(
+            Self {
+                table_function,
+                done: false,
+                schema,
+                identity: source.plan_node().get_identity().clone(),
+            }
+            .fuse(),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Executor for TableFunctionExecutor {
+    async fn open(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Result<Option<DataChunk>> {
+        if self.done {
+            return Ok(None);
+        }
+        self.done = true;
+
+        let array = self
+            .table_function
+            .eval(&DataChunk::new_dummy(1))?
+            .into_iter()
+            .next()
+            .unwrap();
+        if array.len() == 0 {
+            return Ok(None);
+        }
+        let chunk = DataChunk::builder()
+            .columns(vec![Column::new(array)])
+            .build();
+        Ok(Some(chunk))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
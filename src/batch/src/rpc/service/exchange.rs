@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use risingwave_common::error::{Result, ToRwResult};
-use risingwave_pb::task_service::GetDataResponse;
+use risingwave_common::util::exchange::{encode_batch, ExchangeBatcher};
+use risingwave_pb::data::DataChunk as ProstDataChunk;
+use risingwave_pb::task_service::{DataChunkBatch, ExchangeOptions, GetDataResponse};
 use tonic::Status;
 
 type ExchangeDataSender = tokio::sync::mpsc::Sender<std::result::Result<GetDataResponse, Status>>;
@@ -21,21 +23,36 @@ type ExchangeDataSender = tokio::sync::mpsc::Sender<std::result::Result<GetDataR
 #[async_trait::async_trait]
 pub trait ExchangeWriter: Send {
     async fn write(&mut self, resp: GetDataResponse) -> Result<()>;
+
+    /// Sends the data buffered by the writer, if any. Called when all data is written.
+    async fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
 
 pub struct GrpcExchangeWriter {
     sender: ExchangeDataSender,
     written_chunks: usize,
+    /// Coalesces and compresses the chunks, if negotiated.
+    batcher: Option<ExchangeBatcher<ProstDataChunk>>,
 }
 
 impl GrpcExchangeWriter {
-    pub fn new(sender: ExchangeDataSender) -> Self {
+    pub fn new(sender: ExchangeDataSender, options: &ExchangeOptions) -> Self {
         Self {
             sender,
             written_chunks: 0,
+            batcher: ExchangeBatcher::new(options),
         }
     }
 
+    async fn send(&mut self, data: GetDataResponse) -> Result<()> {
+        self.sender
+            .send(Ok(data))
+            .await
+            .to_rw_result_with(|| "failed to write data to ExchangeWriter".into())
+    }
+
     pub fn written_chunks(&self) -> usize {
         self.written_chunks
     }
@@ -43,34 +60,95 @@ impl GrpcExchangeWriter {
 
 #[async_trait::async_trait]
 impl ExchangeWriter for GrpcExchangeWriter {
-    async fn write(&mut self, data: GetDataResponse) -> Result<()> {
+    async fn write(&mut self, mut data: GetDataResponse) -> Result<()> {
         self.written_chunks += 1;
-        self.sender
-            .send(Ok(data))
-            .await
-            .to_rw_result_with(|| "failed to write data to ExchangeWriter".into())
+        let batcher = match &mut self.batcher {
+            Some(batcher) => batcher,
+            None => return self.send(data).await,
+        };
+        // The time bound is only checked on writes, as the task output is drained as fast as
+        // possible and flushed in the end.
+        let should_flush = match data.record_batch.take() {
+            Some(chunk) => batcher.push(chunk),
+            None => false,
+        };
+        if should_flush {
+            self.flush().await
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        let (compression, chunks) = match &mut self.batcher {
+            Some(batcher) if !batcher.is_empty() => (batcher.compression(), batcher.take()),
+            _ => return Ok(()),
+        };
+        let batch = encode_batch(compression, &DataChunkBatch { chunks })?;
+        self.send(GetDataResponse {
+            compression: compression as i32,
+            batch,
+            ..Default::default()
+        })
+        .await
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use risingwave_pb::task_service::GetDataResponse;
+    use risingwave_common::util::exchange::decode_batch;
+    use risingwave_pb::data::DataChunk as ProstDataChunk;
+    use risingwave_pb::task_service::{
+        DataChunkBatch, ExchangeCompression, ExchangeOptions, GetDataResponse,
+    };
 
     use crate::rpc::service::exchange::{ExchangeWriter, GrpcExchangeWriter};
 
     #[tokio::test]
     async fn test_exchange_writer() {
         let (tx, _rx) = tokio::sync::mpsc::channel(10);
-        let mut writer = GrpcExchangeWriter::new(tx);
+        let mut writer = GrpcExchangeWriter::new(tx, &ExchangeOptions::default());
         writer.write(GetDataResponse::default()).await.unwrap();
         assert_eq!(writer.written_chunks(), 1);
     }
 
+    #[tokio::test]
+    async fn test_exchange_writer_coalesce() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let chunk = ProstDataChunk {
+            cardinality: 1,
+            ..Default::default()
+        };
+        let options = ExchangeOptions {
+            compression: ExchangeCompression::Lz4 as i32,
+            batch_max_bytes: 1 << 20,
+            batch_timeout_ms: 1000,
+        };
+        let mut writer = GrpcExchangeWriter::new(tx, &options);
+        for _ in 0..3 {
+            writer
+                .write(GetDataResponse {
+                    record_batch: Some(chunk.clone()),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+        assert!(rx.try_recv().is_err());
+
+        writer.flush().await.unwrap();
+        let resp = rx.recv().await.unwrap().unwrap();
+        assert!(resp.record_batch.is_none());
+        let batch: DataChunkBatch = decode_batch(resp.compression(), &resp.batch).unwrap();
+        assert_eq!(batch.chunks, vec![chunk; 3]);
+        assert_eq!(writer.written_chunks(), 3);
+    }
+
     #[tokio::test]
     async fn test_write_to_closed_channel() {
         let (tx, rx) = tokio::sync::mpsc::channel(10);
         drop(rx);
-        let mut writer = GrpcExchangeWriter::new(tx);
+        let mut writer = GrpcExchangeWriter::new(tx, &ExchangeOptions::default());
         let res = writer.write(GetDataResponse::default()).await;
         assert!(res.is_err());
     }
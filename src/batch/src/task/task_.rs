@@ -129,6 +129,7 @@ impl TaskOutput {
                 }
                 // Reached EOF
                 Ok(None) => {
+                    writer.flush().await?;
                     break;
                 }
                 // Error happened
@@ -19,8 +19,8 @@ use itertools::Itertools;
 
 use crate::array::DataChunk;
 use crate::types::{
-    deserialize_datum_from, deserialize_datum_not_null_from, serialize_datum_into,
-    serialize_datum_not_null_into, DataType, Datum, DatumRef, ToOwnedDatum,
+    deserialize_datum_from, deserialize_datum_not_null_from, estimated_datum_size,
+    serialize_datum_into, serialize_datum_not_null_into, DataType, Datum, DatumRef, ToOwnedDatum,
 };
 use crate::util::sort_util::OrderType;
 
@@ -196,6 +196,11 @@ impl Row {
     pub fn size(&self) -> usize {
         self.0.len()
     }
+
+    /// Estimates the memory held by the row in bytes.
+    pub fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.0.iter().map(estimated_datum_size).sum::<usize>()
+    }
 }
 
 /// Deserializer of the `Row`.
@@ -0,0 +1,490 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::mem::size_of;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use risingwave_pb::data::buffer::CompressionType;
+use risingwave_pb::data::{Array as ProstArray, ArrayType, Buffer};
+use serde_json::Value;
+
+use super::{Array, ArrayBuilder, ArrayBuilderImpl, ArrayIterator, ArrayMeta, NULL_VAL_FOR_HASH};
+use crate::array::value_reader::Utf8ValueReader;
+use crate::array::{read_string_array, ArrayImpl, Utf8ArrayBuilder};
+use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::error::ErrorCode::InvalidInputSyntax;
+use crate::error::{Result, RwError};
+use crate::types::Scalar;
+
+/// An owned JSONB value.
+///
+/// Object keys are always kept sorted, so the textual form of a value is canonical. Values are
+/// ordered by this canonical text, which keeps the order consistent with the memcomparable
+/// encoding. Note that this differs from the ordering of `jsonb` in PostgreSQL.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonbVal(Box<Value>);
+
+/// A reference to a JSONB value, either owned by a [`JsonbVal`] or stored in a [`JsonbArray`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct JsonbRef<'a>(&'a Value);
+
+impl JsonbVal {
+    pub fn new(value: Value) -> Self {
+        Self(Box::new(value))
+    }
+
+    pub fn into_value(self) -> Value {
+        *self.0
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Estimates the heap memory held by the value in bytes.
+    pub fn estimated_heap_size(&self) -> usize {
+        fn nested_size(value: &Value) -> usize {
+            match value {
+                Value::String(s) => s.capacity(),
+                Value::Array(elements) => elements
+                    .iter()
+                    .map(|v| size_of::<Value>() + nested_size(v))
+                    .sum(),
+                Value::Object(fields) => fields
+                    .iter()
+                    .map(|(k, v)| k.capacity() + size_of::<Value>() + nested_size(v))
+                    .sum(),
+                _ => 0,
+            }
+        }
+        size_of::<Value>() + nested_size(&self.0)
+    }
+}
+
+impl FromStr for JsonbVal {
+    type Err = RwError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = serde_json::from_str(s).map_err(|e| {
+            RwError::from(InvalidInputSyntax(format!(
+                "invalid input syntax for type jsonb: {}",
+                e
+            )))
+        })?;
+        Ok(Self::new(value))
+    }
+}
+
+impl<'a> JsonbRef<'a> {
+    pub fn new(value: &'a Value) -> Self {
+        Self(value)
+    }
+
+    pub fn value(&self) -> &'a Value {
+        self.0
+    }
+
+    /// Get the field of an object by `key`, i.e. `jsonb -> 'key'`.
+    pub fn access_object_field(&self, key: &str) -> Option<JsonbRef<'a>> {
+        self.0.as_object()?.get(key).map(JsonbRef)
+    }
+
+    /// Get the element of an array by `idx`, where negative indexes count from the end, i.e.
+    /// `jsonb -> 1`.
+    pub fn access_array_element(&self, idx: i64) -> Option<JsonbRef<'a>> {
+        let array = self.0.as_array()?;
+        let idx = if idx < 0 {
+            array.len().checked_sub(idx.unsigned_abs() as usize)?
+        } else {
+            idx as usize
+        };
+        array.get(idx).map(JsonbRef)
+    }
+
+    /// Follow `path` from this value, where each element is either an object key or an array
+    /// index, i.e. `jsonb #> '{a,0,b}'`.
+    pub fn access_path<'b>(&self, path: impl IntoIterator<Item = &'b str>) -> Option<JsonbRef<'a>> {
+        path.into_iter()
+            .try_fold(*self, |value, key| match value.0 {
+                Value::Object(_) => value.access_object_field(key),
+                Value::Array(_) => value.access_array_element(key.parse().ok()?),
+                _ => None,
+            })
+    }
+
+    /// Iterate over the elements of an array, or `None` if this value is not an array.
+    pub fn array_elements(&self) -> Option<impl Iterator<Item = JsonbRef<'a>>> {
+        Some(self.0.as_array()?.iter().map(JsonbRef))
+    }
+
+    /// Whether this value contains `other` at the top level, i.e. `jsonb @> jsonb`, following
+    /// the containment rules of PostgreSQL.
+    pub fn contains(&self, other: JsonbRef<'_>) -> bool {
+        fn contains(left: &Value, right: &Value) -> bool {
+            match (left, right) {
+                (Value::Object(l), Value::Object(r)) => r
+                    .iter()
+                    .all(|(k, rv)| l.get(k).map_or(false, |lv| contains(lv, rv))),
+                (Value::Array(l), Value::Array(r)) => {
+                    r.iter().all(|rv| l.iter().any(|lv| contains(lv, rv)))
+                }
+                // A top-level array contains a primitive value.
+                (Value::Array(l), r) if !r.is_object() && !r.is_array() => l.contains(r),
+                (l, r) => l == r,
+            }
+        }
+        contains(self.0, other.0)
+    }
+
+    /// Convert to text, where a string is returned without quotes, i.e. `jsonb ->> 'key'`.
+    pub fn to_text(&self) -> Option<String> {
+        match self.0 {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            _ => Some(self.to_string()),
+        }
+    }
+}
+
+impl Hash for JsonbVal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_scalar_ref().hash(state)
+    }
+}
+
+impl Hash for JsonbRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+            match value {
+                Value::Null => 0u8.hash(state),
+                Value::Bool(b) => (1u8, b).hash(state),
+                Value::Number(n) => (2u8, n.to_string()).hash(state),
+                Value::String(s) => (3u8, s).hash(state),
+                Value::Array(array) => {
+                    (4u8, array.len()).hash(state);
+                    array.iter().for_each(|v| hash_value(v, state));
+                }
+                Value::Object(object) => {
+                    (5u8, object.len()).hash(state);
+                    object.iter().for_each(|(k, v)| {
+                        k.hash(state);
+                        hash_value(v, state);
+                    });
+                }
+            }
+        }
+        hash_value(self.0, state)
+    }
+}
+
+impl PartialOrd for JsonbVal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbVal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_scalar_ref().cmp(&other.as_scalar_ref())
+    }
+}
+
+impl PartialOrd for JsonbRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.0 == other.0 {
+            return Ordering::Equal;
+        }
+        self.0.to_string().cmp(&other.0.to_string())
+    }
+}
+
+impl Display for JsonbVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_scalar_ref(), f)
+    }
+}
+
+/// Formats a JSONB value the same way as PostgreSQL, e.g. `{"a": 1, "b": [1, 2]}`.
+impl Display for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct PgFormatter;
+
+        impl serde_json::ser::Formatter for PgFormatter {
+            fn begin_array_value<W: ?Sized + io::Write>(
+                &mut self,
+                writer: &mut W,
+                first: bool,
+            ) -> io::Result<()> {
+                if first {
+                    Ok(())
+                } else {
+                    writer.write_all(b", ")
+                }
+            }
+
+            fn begin_object_key<W: ?Sized + io::Write>(
+                &mut self,
+                writer: &mut W,
+                first: bool,
+            ) -> io::Result<()> {
+                if first {
+                    Ok(())
+                } else {
+                    writer.write_all(b", ")
+                }
+            }
+
+            fn begin_object_value<W: ?Sized + io::Write>(
+                &mut self,
+                writer: &mut W,
+            ) -> io::Result<()> {
+                writer.write_all(b": ")
+            }
+        }
+
+        let mut buf = Vec::new();
+        let mut ser = serde_json::Serializer::with_formatter(&mut buf, PgFormatter);
+        serde::Serialize::serialize(self.0, &mut ser).map_err(|_| fmt::Error)?;
+        f.write_str(std::str::from_utf8(&buf).map_err(|_| fmt::Error)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonbArray {
+    bitmap: Bitmap,
+    data: Vec<Value>,
+}
+
+impl JsonbArray {
+    pub fn from_protobuf(array: &ProstArray, cardinality: usize) -> Result<ArrayImpl> {
+        // JSONB values are transferred in their textual form.
+        let strings =
+            read_string_array::<Utf8ArrayBuilder, Utf8ValueReader>(array, cardinality)?.into_utf8();
+        let mut builder = JsonbArrayBuilder::new(cardinality)?;
+        for s in strings.iter() {
+            match s {
+                Some(s) => builder.append(Some(JsonbVal::from_str(s)?.as_scalar_ref()))?,
+                None => builder.append(None)?,
+            }
+        }
+        Ok(builder.finish()?.into())
+    }
+}
+
+impl Array for JsonbArray {
+    type Builder = JsonbArrayBuilder;
+    type Iter<'a> = ArrayIterator<'a, Self>;
+    type OwnedItem = JsonbVal;
+    type RefItem<'a> = JsonbRef<'a>;
+
+    fn value_at(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null(idx) {
+            Some(JsonbRef(&self.data[idx]))
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ArrayIterator::new(self)
+    }
+
+    fn to_protobuf(&self) -> ProstArray {
+        let mut offset_buffer = Vec::<u8>::with_capacity(self.data.len() * size_of::<usize>());
+        let mut data_buffer = Vec::<u8>::new();
+        let mut offset = 0usize;
+        for (v, not_null) in self.data.iter().zip_eq(self.null_bitmap().iter()) {
+            if not_null {
+                let s = v.to_string();
+                let b = s.as_bytes();
+                offset_buffer.extend_from_slice(&offset.to_be_bytes());
+                data_buffer.extend_from_slice(b);
+                offset += b.len();
+            }
+        }
+        offset_buffer.extend_from_slice(&offset.to_be_bytes());
+
+        let values = vec![
+            Buffer {
+                compression: CompressionType::None as i32,
+                body: offset_buffer,
+            },
+            Buffer {
+                compression: CompressionType::None as i32,
+                body: data_buffer,
+            },
+        ];
+        let null_bitmap = self.null_bitmap().to_protobuf();
+        ProstArray {
+            null_bitmap: Some(null_bitmap),
+            values,
+            array_type: ArrayType::Jsonb as i32,
+            struct_array_data: None,
+            list_array_data: None,
+        }
+    }
+
+    fn null_bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    fn set_bitmap(&mut self, bitmap: Bitmap) {
+        self.bitmap = bitmap;
+    }
+
+    fn hash_at<H: Hasher>(&self, idx: usize, state: &mut H) {
+        if !self.is_null(idx) {
+            JsonbRef(&self.data[idx]).hash(state);
+        } else {
+            NULL_VAL_FOR_HASH.hash(state);
+        }
+    }
+
+    fn create_builder(&self, capacity: usize) -> Result<ArrayBuilderImpl> {
+        let array_builder = JsonbArrayBuilder::new(capacity)?;
+        Ok(ArrayBuilderImpl::Jsonb(array_builder))
+    }
+}
+
+/// `JsonbArrayBuilder` constructs a `JsonbArray` from `Option<JsonbRef>`.
+#[derive(Debug)]
+pub struct JsonbArrayBuilder {
+    bitmap: BitmapBuilder,
+    data: Vec<Value>,
+}
+
+impl ArrayBuilder for JsonbArrayBuilder {
+    type ArrayType = JsonbArray;
+
+    fn new_with_meta(capacity: usize, _meta: ArrayMeta) -> Result<Self> {
+        Ok(Self {
+            bitmap: BitmapBuilder::with_capacity(capacity),
+            data: Vec::with_capacity(capacity),
+        })
+    }
+
+    fn append(&mut self, value: Option<JsonbRef<'_>>) -> Result<()> {
+        match value {
+            Some(x) => {
+                self.bitmap.append(true);
+                self.data.push(x.0.clone());
+            }
+            None => {
+                self.bitmap.append(false);
+                self.data.push(Value::Null);
+            }
+        }
+        Ok(())
+    }
+
+    fn append_array(&mut self, other: &JsonbArray) -> Result<()> {
+        for bit in other.bitmap.iter() {
+            self.bitmap.append(bit);
+        }
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<JsonbArray> {
+        Ok(JsonbArray {
+            bitmap: self.bitmap.finish(),
+            data: self.data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jsonb(s: &str) -> JsonbVal {
+        JsonbVal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_jsonb_array_protobuf() {
+        let values = [
+            Some(jsonb(r#"{"a": [1, "b", null]}"#)),
+            None,
+            Some(jsonb("1.5")),
+        ];
+        let mut builder = JsonbArrayBuilder::new(values.len()).unwrap();
+        for v in &values {
+            builder
+                .append(v.as_ref().map(|v| v.as_scalar_ref()))
+                .unwrap();
+        }
+        let array: ArrayImpl = builder.finish().unwrap().into();
+        let decoded = JsonbArray::from_protobuf(&array.to_protobuf(), values.len()).unwrap();
+        assert_eq!(array, decoded);
+    }
+
+    #[test]
+    fn test_jsonb_display() {
+        assert_eq!(
+            jsonb(r#"{"b":[1,2],"a":{"c":"d"}}"#).to_string(),
+            r#"{"a": {"c": "d"}, "b": [1, 2]}"#
+        );
+    }
+
+    #[test]
+    fn test_jsonb_access() {
+        let v = jsonb(r#"{"a": [1, {"b": "x"}], "c": null}"#);
+        let v = v.as_scalar_ref();
+        assert_eq!(
+            v.access_object_field("a").unwrap().to_string(),
+            r#"[1, {"b": "x"}]"#
+        );
+        assert!(v.access_object_field("z").is_none());
+        let a = v.access_object_field("a").unwrap();
+        assert_eq!(
+            a.access_array_element(-1).unwrap().to_string(),
+            r#"{"b": "x"}"#
+        );
+        assert!(a.access_array_element(2).is_none());
+        assert!(a.access_array_element(-3).is_none());
+        assert_eq!(
+            v.access_path(["a", "1", "b"]).unwrap().to_text().unwrap(),
+            "x"
+        );
+        assert_eq!(v.access_object_field("c").unwrap().to_text(), None);
+        assert_eq!(a.array_elements().unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_jsonb_contains() {
+        let check = |l: &str, r: &str| jsonb(l).as_scalar_ref().contains(jsonb(r).as_scalar_ref());
+        assert!(check(r#"{"a": 1, "b": [1, 2]}"#, r#"{"b": [2]}"#));
+        assert!(!check(r#"{"a": 1}"#, r#"{"a": 2}"#));
+        assert!(check(r#"[1, 2, [3]]"#, r#"[[3], 1]"#));
+        assert!(check(r#"["foo", "bar"]"#, r#""foo""#));
+        assert!(!check(r#""foo""#, r#"["foo"]"#));
+    }
+}
@@ -259,8 +259,8 @@ pub struct ListValue {
 }
 
 impl fmt::Display for ListValue {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_scalar_ref(), f)
     }
 }
 
@@ -280,6 +280,14 @@ impl ListValue {
     pub fn new(values: Vec<Datum>) -> Self {
         Self { values }
     }
+
+    pub fn values(&self) -> &[Datum] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<Datum> {
+        self.values
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -360,8 +368,19 @@ impl Debug for ListRef<'_> {
 }
 
 impl Display for ListRef<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!()
+    /// Formats the list in the PostgreSQL text form, e.g. `{1,2,NULL}`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, v) in self.values_ref().iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            match v {
+                Some(s) => Display::fmt(s, f)?,
+                None => write!(f, "NULL")?,
+            }
+        }
+        write!(f, "}}")
     }
 }
 
@@ -624,4 +643,11 @@ mod tests {
             ListValue::new(vec![Some(1.into()), None]),
         );
     }
+
+    #[test]
+    fn test_list_display() {
+        let value = ListValue::new(vec![Some(1.into()), None, Some(3.into())]);
+        assert_eq!(value.to_string(), "{1,NULL,3}");
+        assert_eq!(ListValue::new(vec![]).to_string(), "{}");
+    }
 }
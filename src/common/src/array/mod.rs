@@ -23,6 +23,7 @@ pub mod data_chunk_iter;
 mod decimal_array;
 pub mod interval_array;
 mod iterator;
+pub mod jsonb_array;
 pub mod list_array;
 mod macros;
 mod primitive_array;
@@ -47,6 +48,7 @@ pub use data_chunk_iter::{Row, RowDeserializer, RowRef};
 pub use decimal_array::{DecimalArray, DecimalArrayBuilder};
 pub use interval_array::{IntervalArray, IntervalArrayBuilder};
 pub use iterator::ArrayIterator;
+pub use jsonb_array::{JsonbArray, JsonbArrayBuilder, JsonbRef, JsonbVal};
 pub use list_array::{ListArray, ListArrayBuilder, ListRef, ListValue};
 use paste::paste;
 pub use primitive_array::{PrimitiveArray, PrimitiveArrayBuilder, PrimitiveArrayItemType};
@@ -248,7 +250,8 @@ macro_rules! for_all_variants {
             { NaiveDateTime, naivedatetime, NaiveDateTimeArray, NaiveDateTimeArrayBuilder },
             { NaiveTime, naivetime, NaiveTimeArray, NaiveTimeArrayBuilder },
             { Struct, struct, StructArray, StructArrayBuilder },
-            { List, list, ListArray, ListArrayBuilder }
+            { List, list, ListArray, ListArrayBuilder },
+            { Jsonb, jsonb, JsonbArray, JsonbArrayBuilder }
         }
     };
 }
@@ -324,6 +327,12 @@ impl From<ListArray> for ArrayImpl {
     }
 }
 
+impl From<JsonbArray> for ArrayImpl {
+    fn from(arr: JsonbArray) -> Self {
+        Self::Jsonb(arr)
+    }
+}
+
 for_all_variants! { array_impl_enum }
 
 /// `impl_convert` implements several conversions for `Array` and `ArrayBuilder`.
@@ -574,6 +583,7 @@ impl ArrayImpl {
             ProstArrayType::Interval => read_interval_unit_array(array, cardinality)?,
             ProstArrayType::Struct => StructArray::from_protobuf(array)?,
             ProstArrayType::List => ListArray::from_protobuf(array)?,
+            ProstArrayType::Jsonb => JsonbArray::from_protobuf(array, cardinality)?,
         };
         Ok(array)
     }
@@ -262,8 +262,8 @@ pub struct StructValue {
 }
 
 impl fmt::Display for StructValue {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_scalar_ref())
     }
 }
 
@@ -283,6 +283,10 @@ impl StructValue {
     pub fn new(fields: Vec<Datum>) -> Self {
         Self { fields }
     }
+
+    pub fn fields(&self) -> &[Datum] {
+        &self.fields
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -304,6 +308,14 @@ impl<'a> StructRef<'a> {
                 .collect::<Vec<DatumRef<'a>>>(),
         }
     }
+
+    /// Returns the `i`-th field without collecting the other ones.
+    pub fn field_at(&self, i: usize) -> DatumRef<'a> {
+        match self {
+            StructRef::Indexed { arr, idx } => arr.children[i].value_at(*idx),
+            StructRef::ValueRef { val } => val.fields[i].as_ref().map(|s| s.as_scalar_ref_impl()),
+        }
+    }
 }
 
 impl Hash for StructRef<'_> {
@@ -365,9 +377,18 @@ impl Debug for StructRef<'_> {
     }
 }
 
+/// Formats the struct in the textual form of a Postgres record, e.g. `(1,abc,)`. Null fields are
+/// left empty.
 impl Display for StructRef<'_> {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({})",
+            self.fields_ref()
+                .into_iter()
+                .map(|d| d.map(|s| s.to_string()).unwrap_or_default())
+                .join(",")
+        )
     }
 }
 
@@ -463,6 +484,16 @@ mod tests {
         assert_eq!(arr.array_meta(), arr2.array_meta());
     }
 
+    #[test]
+    fn test_struct_display() {
+        let value = StructValue::new(vec![
+            Some(ScalarImpl::Int32(1)),
+            None,
+            Some(ScalarImpl::Utf8("abc".to_string())),
+        ]);
+        assert_eq!(value.to_string(), "(1,,abc)");
+    }
+
     #[test]
     fn test_struct_value_cmp() {
         // (1, 2.0) > (1, 1.0)
@@ -102,6 +102,12 @@ impl ColumnDesc {
         }
     }
 
+    /// Short name of the column, i.e. the last segment of the fully-qualified name of a nested
+    /// field like `country.city`.
+    pub fn field_name(&self) -> &str {
+        self.name.rsplit('.').next().unwrap_or_default()
+    }
+
     /// Get all column descs under `field_descs`.
     pub fn get_column_descs(&self) -> Vec<ColumnDesc> {
         let mut descs = vec![self.clone()];
@@ -129,13 +135,10 @@ impl ColumnDesc {
         type_name: &str,
         fields: Vec<ColumnDesc>,
     ) -> Self {
-        let data_type = DataType::Struct {
-            fields: fields
-                .iter()
-                .map(|f| f.data_type.clone())
-                .collect_vec()
-                .into(),
-        };
+        let data_type = DataType::new_struct(
+            fields.iter().map(|f| f.data_type.clone()).collect(),
+            fields.iter().map(|f| f.field_name().to_string()).collect(),
+        );
         Self {
             data_type,
             column_id: ColumnId::new(column_id),
@@ -147,38 +150,19 @@ impl ColumnDesc {
 }
 
 impl From<ProstColumnDesc> for ColumnDesc {
-    // Since the prost DataType struct doesn't have field, so it need to be reinit when into
-    // ColumnDesc
     fn from(prost: ProstColumnDesc) -> Self {
-        if let DataType::Struct { .. } = DataType::from(prost.column_type.as_ref().unwrap()) {
-            let descs: Vec<ColumnDesc> = prost
+        // The prost data type of a struct carries its field types and names, so the field descs
+        // are only needed by the nested columns themselves.
+        Self {
+            data_type: DataType::from(prost.column_type.as_ref().unwrap()),
+            column_id: ColumnId::new(prost.column_id),
+            name: prost.name,
+            type_name: prost.type_name,
+            field_descs: prost
                 .field_descs
                 .into_iter()
                 .map(ColumnDesc::from)
-                .collect();
-            let date_type = DataType::Struct {
-                fields: descs
-                    .clone()
-                    .into_iter()
-                    .map(|c| c.data_type)
-                    .collect_vec()
-                    .into(),
-            };
-            Self {
-                data_type: date_type,
-                column_id: ColumnId::new(prost.column_id),
-                name: prost.name,
-                type_name: prost.type_name,
-                field_descs: descs,
-            }
-        } else {
-            Self {
-                data_type: DataType::from(prost.column_type.as_ref().unwrap()),
-                column_id: ColumnId::new(prost.column_id),
-                name: prost.name,
-                type_name: prost.type_name,
-                field_descs: vec![],
-            }
+                .collect(),
         }
     }
 }
@@ -59,6 +59,34 @@ impl<K: Hash + Eq, V> EvictableHashMap<K, V> {
         self.inner.resize(usize::MAX);
     }
 
+    /// Evict the least recently used items until the total size of the remaining items, measured by
+    /// `size_of`, is no more than `capacity`.
+    pub fn evict_to_size(&mut self, capacity: usize, size_of: impl Fn(&K, &V) -> usize) {
+        let mut size: usize = self.inner.iter().map(|(k, v)| size_of(k, v)).sum();
+        while size > capacity {
+            match self.inner.pop_lru() {
+                Some((k, v)) => size -= size_of(&k, &v),
+                None => break,
+            }
+        }
+    }
+
+    /// Removes all items whose key does not satisfy `f`.
+    pub fn retain_keys(&mut self, mut f: impl FnMut(&K) -> bool)
+    where
+        K: Clone,
+    {
+        let to_remove = self
+            .inner
+            .iter()
+            .filter(|(k, _v)| !f(k))
+            .map(|(k, _v)| k.clone())
+            .collect::<Vec<_>>();
+        for key in to_remove {
+            self.inner.pop(&key);
+        }
+    }
+
     /// An iterator visiting all values in most-recently used order. The iterator element type is
     /// &V.
     pub fn values(&self) -> impl Iterator<Item = &V> {
@@ -107,4 +135,32 @@ mod tests {
         assert!(map.get(&(items_count - target_cap - 1)).is_none());
         assert!(map.get(&(items_count - target_cap)).is_some());
     }
+
+    #[test]
+    fn test_evict_to_size() {
+        let mut map = EvictableHashMap::new(10);
+        for i in 1..=10 {
+            map.put(i, ());
+        }
+        map.get(&1);
+
+        // Each item takes the size of its key, so 1 and 10 are kept.
+        map.evict_to_size(11, |k, _| *k);
+        assert_eq!(map.len(), 2);
+        assert!(map.get(&1).is_some());
+        assert!(map.get(&10).is_some());
+    }
+
+    #[test]
+    fn test_retain_keys() {
+        let mut map = EvictableHashMap::new(10);
+        for i in 0..10 {
+            map.put(i, ());
+        }
+
+        map.retain_keys(|k| *k >= 4);
+        assert_eq!(map.len(), 6);
+        assert!(map.get(&3).is_none());
+        assert!(map.get(&4).is_some());
+    }
 }
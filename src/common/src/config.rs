@@ -64,6 +64,9 @@ impl Default for ServerConfig {
 pub struct BatchConfig {
     #[serde(default = "default::chunk_size")]
     pub chunk_size: u32,
+
+    #[serde(default)]
+    pub exchange: ExchangeConfig,
 }
 
 impl Default for BatchConfig {
@@ -76,6 +79,25 @@ impl Default for BatchConfig {
 pub struct StreamingConfig {
     #[serde(default = "default::chunk_size")]
     pub chunk_size: u32,
+
+    /// Memory budget in bytes of the join states cached by each hash join actor. The states
+    /// beyond the budget are evicted, and will be read from the state store when needed.
+    #[serde(default = "default::join_cache_capacity")]
+    pub join_cache_capacity: usize,
+
+    /// Memory budget in bytes of the caches of all streaming executors on a compute node. Once the
+    /// usage exceeds most of the budget, executors are asked to evict their caches on barriers.
+    #[serde(default = "default::memory_budget")]
+    pub memory_budget: usize,
+
+    /// Whether to keep the barriers flowing while a new materialized view consumes the snapshot
+    /// of its upstream. Otherwise the barriers are held until the snapshot is consumed, so the
+    /// checkpoints are stalled and the progress of the creation can not be reported.
+    #[serde(default = "default::rearranged_chain_enabled")]
+    pub rearranged_chain_enabled: bool,
+
+    #[serde(default)]
+    pub exchange: ExchangeConfig,
 }
 
 impl Default for StreamingConfig {
@@ -84,6 +106,37 @@ impl Default for StreamingConfig {
     }
 }
 
+/// Compression of the chunks sent to other nodes through the exchange service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExchangeCompression {
+    None,
+    Lz4,
+    Zstd,
+}
+
+/// Configurations of the exchange channels to other nodes. They are proposed by the receiver of a
+/// channel, and only take effect if the sender also enables them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExchangeConfig {
+    #[serde(default = "default::exchange_compression")]
+    pub compression: ExchangeCompression,
+
+    /// Small chunks are coalesced up to this size in bytes before sent. 0 disables coalescing.
+    #[serde(default = "default::exchange_batch_max_bytes")]
+    pub batch_max_bytes: usize,
+
+    /// The longest time in milliseconds that a chunk is held for coalescing.
+    #[serde(default = "default::exchange_batch_timeout_ms")]
+    pub batch_timeout_ms: u64,
+}
+
+impl Default for ExchangeConfig {
+    fn default() -> Self {
+        toml::from_str("").unwrap()
+    }
+}
+
 /// Currently all configurations are server before they can be specified with DDL syntaxes.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StorageConfig {
@@ -169,6 +222,32 @@ mod default {
         1024
     }
 
+    pub fn join_cache_capacity() -> usize {
+        // 64 MB
+        67108864
+    }
+
+    pub fn memory_budget() -> usize {
+        // 2 GB
+        2147483648
+    }
+
+    pub fn rearranged_chain_enabled() -> bool {
+        true
+    }
+
+    pub fn exchange_compression() -> super::ExchangeCompression {
+        super::ExchangeCompression::None
+    }
+
+    pub fn exchange_batch_max_bytes() -> usize {
+        0
+    }
+
+    pub fn exchange_batch_timeout_ms() -> u64 {
+        10
+    }
+
     pub fn sst_size() -> u32 {
         // 256MB
         268435456
@@ -231,6 +310,10 @@ mod tests {
         
         [streaming]
         
+        [streaming.exchange]
+        compression = "lz4"
+        batch_max_bytes = 65536
+
         [storage]
         sstable_size = 1024
         data_directory = "test"
@@ -239,6 +322,13 @@ mod tests {
         let cfg: ComputeNodeConfig = toml::from_str(partial_toml_str).unwrap();
         assert_eq!(cfg.server.heartbeat_interval, 10);
         assert_eq!(cfg.batch.chunk_size, 256);
+        assert_eq!(cfg.batch.exchange.compression, ExchangeCompression::None);
+        assert_eq!(cfg.streaming.exchange.compression, ExchangeCompression::Lz4);
+        assert_eq!(cfg.streaming.exchange.batch_max_bytes, 65536);
+        assert_eq!(
+            cfg.streaming.exchange.batch_timeout_ms,
+            default::exchange_batch_timeout_ms()
+        );
         assert_eq!(cfg.storage.sstable_size, 1024);
         assert_eq!(cfg.storage.block_size, default::block_size());
         assert_eq!(
@@ -19,6 +19,7 @@ use std::ops::{Add, Sub};
 use byteorder::{BigEndian, WriteBytesExt};
 use bytes::BytesMut;
 use num_traits::{CheckedAdd, CheckedSub};
+use risingwave_pb::data::IntervalUnit as ProstIntervalUnit;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
@@ -144,6 +145,36 @@ impl IntervalUnit {
         Some(IntervalUnit { months, days, ms })
     }
 
+    /// Adjusts the interval so that 24-hour time periods are represented as days and 30-day time
+    /// periods are represented as months, with the signs of all units made consistent.
+    /// Same as `justify_interval` in PG.
+    #[must_use]
+    pub fn justify_interval(&self) -> Self {
+        const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+        let mut months = self.months;
+        let mut days = self.days + (self.ms / DAY_MS) as i32;
+        let mut ms = self.ms % DAY_MS;
+        months += days / 30;
+        days %= 30;
+
+        if months > 0 && (days < 0 || (days == 0 && ms < 0)) {
+            days += 30;
+            months -= 1;
+        } else if months < 0 && (days > 0 || (days == 0 && ms > 0)) {
+            days -= 30;
+            months += 1;
+        }
+        if days > 0 && ms < 0 {
+            ms += DAY_MS;
+            days -= 1;
+        } else if days < 0 && ms > 0 {
+            ms -= DAY_MS;
+            days += 1;
+        }
+
+        IntervalUnit { months, days, ms }
+    }
+
     /// Performs an exact division, returns [`None`] if for any unit, lhs % rhs != 0.
     pub fn exact_div(&self, rhs: &Self) -> Option<i64> {
         let mut res = None;
@@ -177,6 +208,26 @@ impl IntervalUnit {
     }
 }
 
+impl From<&ProstIntervalUnit> for IntervalUnit {
+    fn from(p: &ProstIntervalUnit) -> Self {
+        Self {
+            months: p.months,
+            days: p.days,
+            ms: p.ms,
+        }
+    }
+}
+
+impl From<IntervalUnit> for ProstIntervalUnit {
+    fn from(i: IntervalUnit) -> Self {
+        Self {
+            months: i.months,
+            days: i.days,
+            ms: i.ms,
+        }
+    }
+}
+
 impl Add for IntervalUnit {
     type Output = Self;
 
@@ -257,6 +308,22 @@ mod tests {
         assert_eq!(interval.to_string(), "-1 years -2 mons 3 days 11:45:14.233");
     }
 
+    #[test]
+    fn test_justify_interval() {
+        let cases = [
+            ((0, 0, 25 * 3600 * 1000), (0, 1, 3600 * 1000)),
+            ((0, 35, 0), (1, 5, 0)),
+            ((1, -1, 0), (0, 29, 0)),
+            ((0, 1, -3600 * 1000), (0, 0, 23 * 3600 * 1000)),
+            ((-1, 0, 3600 * 1000), (0, -29, -23 * 3600 * 1000)),
+        ];
+        for (input, expected) in cases {
+            let input = IntervalUnit::new(input.0, input.1, input.2);
+            let expected = IntervalUnit::new(expected.0, expected.1, expected.2);
+            assert_eq!(input.justify_interval(), expected);
+        }
+    }
+
     #[test]
     fn test_exact_div() {
         let cases = [
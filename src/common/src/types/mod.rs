@@ -42,7 +42,8 @@ pub use ordered_float::IntoOrdered;
 use paste::paste;
 
 use crate::array::{
-    ArrayBuilderImpl, ListRef, ListValue, PrimitiveArrayItemType, StructRef, StructValue,
+    ArrayBuilderImpl, JsonbRef, JsonbVal, ListRef, ListValue, PrimitiveArrayItemType, StructRef,
+    StructValue,
 };
 
 pub type OrderedF32 = ordered_float::OrderedFloat<f32>;
@@ -63,8 +64,14 @@ pub enum DataType {
     Timestamp,
     Timestampz,
     Interval,
-    Struct { fields: Arc<[DataType]> },
-    List { datatype: Box<DataType> },
+    Struct {
+        fields: Arc<[DataType]>,
+        field_names: Arc<[String]>,
+    },
+    List {
+        datatype: Box<DataType>,
+    },
+    Jsonb,
 }
 
 const DECIMAL_DEFAULT_PRECISION: u32 = 20;
@@ -99,11 +106,21 @@ impl From<&ProstDataType> for DataType {
             TypeName::Interval => DataType::Interval,
             TypeName::Symbol => DataType::Varchar,
             TypeName::Struct => DataType::Struct {
-                fields: Arc::new([]),
+                fields: proto.field_type.iter().map(Into::into).collect(),
+                field_names: proto.field_names.iter().cloned().collect(),
             },
             TypeName::List => DataType::List {
-                datatype: Box::new(DataType::Int32),
+                // The element type is expected to be the only field type. Fall back to `Int32`
+                // for plans serialized without it.
+                datatype: Box::new(
+                    proto
+                        .field_type
+                        .first()
+                        .map(Into::into)
+                        .unwrap_or(DataType::Int32),
+                ),
             },
+            TypeName::Jsonb => DataType::Jsonb,
         }
     }
 }
@@ -125,9 +142,13 @@ impl DataType {
             DataType::Timestamp => NaiveDateTimeArrayBuilder::new(capacity)?.into(),
             DataType::Timestampz => PrimitiveArrayBuilder::<i64>::new(capacity)?.into(),
             DataType::Interval => IntervalArrayBuilder::new(capacity)?.into(),
-            DataType::Struct { .. } => {
-                todo!()
-            }
+            DataType::Struct { fields, .. } => StructArrayBuilder::new_with_meta(
+                capacity,
+                ArrayMeta::Struct {
+                    children: fields.clone(),
+                },
+            )?
+            .into(),
             DataType::List { datatype } => ListArrayBuilder::new_with_meta(
                 capacity,
                 ArrayMeta::List {
@@ -135,6 +156,7 @@ impl DataType {
                 },
             )?
             .into(),
+            DataType::Jsonb => JsonbArrayBuilder::new(capacity)?.into(),
         })
     }
 
@@ -155,13 +177,25 @@ impl DataType {
             DataType::Interval => TypeName::Interval,
             DataType::Struct { .. } => TypeName::Struct,
             DataType::List { .. } => TypeName::List,
+            DataType::Jsonb => TypeName::Jsonb,
         }
     }
 
     pub fn to_protobuf(&self) -> ProstDataType {
+        let field_type = match self {
+            DataType::Struct { fields, .. } => fields.iter().map(|f| f.to_protobuf()).collect(),
+            DataType::List { datatype } => vec![datatype.to_protobuf()],
+            _ => vec![],
+        };
+        let field_names = match self {
+            DataType::Struct { field_names, .. } => field_names.to_vec(),
+            _ => vec![],
+        };
         ProstDataType {
             type_name: self.prost_type_name() as i32,
             is_nullable: true,
+            field_type,
+            field_names,
             ..Default::default()
         }
     }
@@ -184,6 +218,15 @@ impl DataType {
             DataType::Interval => DataSize::Variable,
             DataType::Struct { .. } => DataSize::Variable,
             DataType::List { .. } => DataSize::Variable,
+            DataType::Jsonb => DataSize::Variable,
+        }
+    }
+
+    pub fn new_struct(fields: Vec<DataType>, field_names: Vec<String>) -> Self {
+        debug_assert_eq!(fields.len(), field_names.len());
+        DataType::Struct {
+            fields: fields.into(),
+            field_names: field_names.into(),
         }
     }
 
@@ -274,7 +317,8 @@ macro_rules! for_all_scalar_variants {
             { NaiveDateTime, naivedatetime, NaiveDateTimeWrapper, NaiveDateTimeWrapper },
             { NaiveTime, naivetime, NaiveTimeWrapper, NaiveTimeWrapper },
             { Struct, struct, StructValue, StructRef<'scalar> },
-            { List, list, ListValue, ListRef<'scalar> }
+            { List, list, ListValue, ListRef<'scalar> },
+            { Jsonb, jsonb, JsonbVal, JsonbRef<'scalar> }
         }
     };
 }
@@ -302,6 +346,11 @@ for_all_scalar_variants! { scalar_impl_enum }
 pub type Datum = Option<ScalarImpl>;
 pub type DatumRef<'a> = Option<ScalarRefImpl<'a>>;
 
+/// Estimates the memory held by the datum in bytes, including the heap memory of the scalar.
+pub fn estimated_datum_size(datum: &Datum) -> usize {
+    std::mem::size_of::<Datum>() + datum.as_ref().map_or(0, ScalarImpl::estimated_heap_size)
+}
+
 // TODO: specify `NULL FIRST` or `NULL LAST`.
 pub fn serialize_datum_ref_into(
     datum_ref: &DatumRef,
@@ -539,6 +588,7 @@ impl std::hash::Hash for ScalarImpl {
                     Self::NaiveTime(naivetime) => naivetime.hash(state),
                     Self::Struct(v) => v.hash(state),
                     Self::List(v) => v.hash(state),
+                    Self::Jsonb(v) => v.hash(state),
                 }
             };
         }
@@ -603,6 +653,35 @@ impl ScalarRefImpl<'_> {
             &Self::NaiveTime(v) => {
                 ser.serialize_naivetime(v.0.num_seconds_from_midnight(), v.0.nanosecond())?
             }
+            // JSONB is encoded in its canonical textual form, which `JsonbRef` is ordered by.
+            Self::Jsonb(v) => v.value().to_string().serialize(ser)?,
+            // Each element is prefixed with a tag, and the list is terminated with `0`. Nulls are
+            // tagged with `2` so that they sort after all other values, as `ListRef` does.
+            Self::List(v) => {
+                for datum in v.values_ref() {
+                    match datum {
+                        Some(scalar) => {
+                            1u8.serialize(&mut *ser)?;
+                            scalar.serialize(ser)?;
+                        }
+                        None => 2u8.serialize(&mut *ser)?,
+                    }
+                }
+                0u8.serialize(ser)?;
+            }
+            // Struct fields are tagged in the same way as list elements. The number of fields is
+            // known from the type, so no terminator is needed.
+            Self::Struct(v) => {
+                for datum in v.fields_ref() {
+                    match datum {
+                        Some(scalar) => {
+                            1u8.serialize(&mut *ser)?;
+                            scalar.serialize(ser)?;
+                        }
+                        None => 2u8.serialize(&mut *ser)?,
+                    }
+                }
+            }
             _ => {
                 panic!("Type is unable to be serialized.")
             }
@@ -612,6 +691,17 @@ impl ScalarRefImpl<'_> {
 }
 
 impl ScalarImpl {
+    /// Estimates the heap memory held by the scalar in bytes.
+    pub fn estimated_heap_size(&self) -> usize {
+        match self {
+            Self::Utf8(s) => s.capacity(),
+            Self::Struct(v) => v.fields().iter().map(estimated_datum_size).sum(),
+            Self::List(v) => v.values().iter().map(estimated_datum_size).sum(),
+            Self::Jsonb(v) => v.estimated_heap_size(),
+            _ => 0,
+        }
+    }
+
     /// Serialize the scalar.
     pub fn serialize(
         &self,
@@ -657,6 +747,35 @@ impl ScalarImpl {
                 let days = de.deserialize_naivedate()?;
                 NaiveDateWrapper::new_with_days(days)?
             }),
+            Ty::Jsonb => Self::Jsonb(JsonbVal::new(
+                serde_json::from_str(&String::deserialize(de)?)
+                    .map_err(|e| memcomparable::Error::Message(e.to_string()))?,
+            )),
+            Ty::List { datatype } => {
+                let mut values = vec![];
+                loop {
+                    let tag = u8::deserialize(&mut *de)?;
+                    match tag {
+                        0 => break,
+                        1 => values.push(Some(Self::deserialize(*datatype.clone(), de)?)),
+                        2 => values.push(None),
+                        _ => return Err(memcomparable::Error::InvalidTagEncoding(tag as _)),
+                    }
+                }
+                Self::List(ListValue::new(values))
+            }
+            Ty::Struct { fields, .. } => {
+                let mut values = Vec::with_capacity(fields.len());
+                for field in fields.iter() {
+                    let tag = u8::deserialize(&mut *de)?;
+                    match tag {
+                        1 => values.push(Some(Self::deserialize(field.clone(), de)?)),
+                        2 => values.push(None),
+                        _ => return Err(memcomparable::Error::InvalidTagEncoding(tag as _)),
+                    }
+                }
+                Self::Struct(StructValue::new(values))
+            }
             _ => {
                 panic!("Type is unable to be deserialized.")
             }
@@ -744,4 +863,79 @@ mod tests {
         assert!(decoded_floats.is_sorted());
         assert_eq!(floats, decoded_floats);
     }
+
+    #[test]
+    fn test_list_memcomparable() {
+        fn serialize(values: Vec<Datum>) -> Vec<u8> {
+            let mut serializer = memcomparable::Serializer::new(vec![]);
+            serialize_datum_not_null_into(
+                &Some(ScalarImpl::List(ListValue::new(values))),
+                &mut serializer,
+            )
+            .unwrap();
+            serializer.into_inner()
+        }
+
+        let lists = vec![
+            vec![],
+            vec![Some(ScalarImpl::Int32(1))],
+            vec![Some(ScalarImpl::Int32(1)), Some(ScalarImpl::Int32(2))],
+            vec![Some(ScalarImpl::Int32(1)), None],
+            vec![Some(ScalarImpl::Int32(2))],
+            vec![None],
+        ];
+        let memcomparables = lists.iter().cloned().map(serialize).collect_vec();
+        assert!(memcomparables.is_sorted());
+
+        let ty = DataType::List {
+            datatype: Box::new(DataType::Int32),
+        };
+        for (list, bytes) in lists.into_iter().zip_eq(memcomparables) {
+            let mut deserializer = memcomparable::Deserializer::new(bytes.as_slice());
+            let datum = deserialize_datum_not_null_from(ty.clone(), &mut deserializer).unwrap();
+            assert_eq!(datum, Some(ScalarImpl::List(ListValue::new(list))));
+        }
+    }
+
+    #[test]
+    fn test_struct_memcomparable() {
+        fn serialize(values: Vec<Datum>) -> Vec<u8> {
+            let mut serializer = memcomparable::Serializer::new(vec![]);
+            serialize_datum_not_null_into(
+                &Some(ScalarImpl::Struct(StructValue::new(values))),
+                &mut serializer,
+            )
+            .unwrap();
+            serializer.into_inner()
+        }
+
+        let structs = vec![
+            vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Utf8("a".to_string())),
+            ],
+            vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Utf8("b".to_string())),
+            ],
+            vec![Some(ScalarImpl::Int32(1)), None],
+            vec![
+                Some(ScalarImpl::Int32(2)),
+                Some(ScalarImpl::Utf8("a".to_string())),
+            ],
+            vec![None, Some(ScalarImpl::Utf8("a".to_string()))],
+        ];
+        let memcomparables = structs.iter().cloned().map(serialize).collect_vec();
+        assert!(memcomparables.is_sorted());
+
+        let ty = DataType::new_struct(
+            vec![DataType::Int32, DataType::Varchar],
+            vec!["a".to_string(), "b".to_string()],
+        );
+        for (fields, bytes) in structs.into_iter().zip_eq(memcomparables) {
+            let mut deserializer = memcomparable::Deserializer::new(bytes.as_slice());
+            let datum = deserialize_datum_not_null_from(ty.clone(), &mut deserializer).unwrap();
+            assert_eq!(datum, Some(ScalarImpl::Struct(StructValue::new(fields))));
+        }
+    }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use crate::array::jsonb_array::{JsonbRef, JsonbVal};
 use crate::array::list_array::{ListRef, ListValue};
 use crate::array::struct_array::{StructRef, StructValue};
 use crate::{for_all_native_types, for_all_scalar_variants};
@@ -94,6 +95,19 @@ impl Scalar for ListValue {
     }
 }
 
+/// Implement `Scalar` for `JsonbVal`.
+impl Scalar for JsonbVal {
+    type ScalarRefType<'a> = JsonbRef<'a>;
+
+    fn as_scalar_ref(&self) -> JsonbRef<'_> {
+        JsonbRef::new(self.value())
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Jsonb(self)
+    }
+}
+
 /// Implement `ScalarRef` for `String`.
 /// `String` could be converted to `&str`.
 impl<'a> ScalarRef<'a> for &'a str {
@@ -116,6 +130,12 @@ impl ScalarPartialOrd for String {
     }
 }
 
+impl ScalarPartialOrd for JsonbVal {
+    fn scalar_cmp(&self, other: JsonbRef<'_>) -> Option<std::cmp::Ordering> {
+        self.as_scalar_ref().partial_cmp(&other)
+    }
+}
+
 impl<T: PrimitiveArrayItemType + Scalar> ScalarPartialOrd for T {
     fn scalar_cmp(&self, other: Self) -> Option<std::cmp::Ordering> {
         self.partial_cmp(&other)
@@ -313,6 +333,15 @@ impl<'a> ScalarRef<'a> for ListRef<'a> {
     }
 }
 
+/// Implement `ScalarRef` for `JsonbRef`.
+impl<'a> ScalarRef<'a> for JsonbRef<'a> {
+    type ScalarType = JsonbVal;
+
+    fn to_owned_scalar(&self) -> JsonbVal {
+        JsonbVal::new(self.value().clone())
+    }
+}
+
 impl ScalarImpl {
     pub fn get_ident(&self) -> &'static str {
         macro_rules! impl_all_get_ident {
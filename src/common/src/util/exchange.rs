@@ -0,0 +1,262 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compression and coalescing of the messages sent through the exchange service.
+
+use std::time::{Duration, Instant};
+
+use prost::Message;
+use risingwave_pb::task_service::{
+    ExchangeCompression as ProstExchangeCompression, ExchangeOptions,
+};
+
+use crate::config::{ExchangeCompression, ExchangeConfig};
+use crate::error::ErrorCode::InternalError;
+use crate::error::{Result, RwError};
+
+/// Exchange favors speed over compression ratio.
+const ZSTD_LEVEL: i32 = 1;
+
+impl From<ExchangeCompression> for ProstExchangeCompression {
+    fn from(compression: ExchangeCompression) -> Self {
+        match compression {
+            ExchangeCompression::None => Self::None,
+            ExchangeCompression::Lz4 => Self::Lz4,
+            ExchangeCompression::Zstd => Self::Zstd,
+        }
+    }
+}
+
+/// The options proposed by the receiver of an exchange channel.
+pub fn exchange_options(config: &ExchangeConfig) -> ExchangeOptions {
+    ExchangeOptions {
+        compression: ProstExchangeCompression::from(config.compression) as i32,
+        batch_max_bytes: config.batch_max_bytes as u64,
+        batch_timeout_ms: config.batch_timeout_ms,
+    }
+}
+
+/// Negotiates the options of an exchange channel on the sender side. Compression is used only if
+/// both sides enable it, with the algorithm chosen by the receiver. Coalescing is used only if both
+/// sides enable it, with the tighter bounds.
+pub fn negotiate_exchange_options(
+    config: &ExchangeConfig,
+    requested: Option<&ExchangeOptions>,
+) -> ExchangeOptions {
+    let requested = requested.cloned().unwrap_or_default();
+    let compression = if config.compression == ExchangeCompression::None {
+        ProstExchangeCompression::None
+    } else {
+        requested.compression()
+    };
+    let batch_max_bytes = if config.batch_max_bytes == 0 || requested.batch_max_bytes == 0 {
+        0
+    } else {
+        requested.batch_max_bytes.min(config.batch_max_bytes as u64)
+    };
+    ExchangeOptions {
+        compression: compression as i32,
+        batch_max_bytes,
+        batch_timeout_ms: requested.batch_timeout_ms.min(config.batch_timeout_ms),
+    }
+}
+
+/// Encodes a batch of messages, and compresses it with `compression`.
+pub fn encode_batch(
+    compression: ProstExchangeCompression,
+    batch: &impl Message,
+) -> Result<Vec<u8>> {
+    let encoded = batch.encode_to_vec();
+    match compression {
+        ProstExchangeCompression::None => Ok(encoded),
+        ProstExchangeCompression::Lz4 => lz4::block::compress(&encoded, None, true),
+        ProstExchangeCompression::Zstd => zstd::encode_all(encoded.as_slice(), ZSTD_LEVEL),
+    }
+    .map_err(|e| {
+        RwError::from(InternalError(format!(
+            "failed to compress exchange data: {}",
+            e
+        )))
+    })
+}
+
+/// Decompresses a batch of messages with `compression`, and decodes it.
+pub fn decode_batch<M: Message + Default>(
+    compression: ProstExchangeCompression,
+    bytes: &[u8],
+) -> Result<M> {
+    let decompressed = match compression {
+        ProstExchangeCompression::None => Ok(bytes.to_vec()),
+        ProstExchangeCompression::Lz4 => lz4::block::decompress(bytes, None),
+        ProstExchangeCompression::Zstd => zstd::decode_all(bytes),
+    }
+    .map_err(|e| {
+        RwError::from(InternalError(format!(
+            "failed to decompress exchange data: {}",
+            e
+        )))
+    })?;
+    Ok(M::decode(decompressed.as_slice())?)
+}
+
+/// Coalesces the messages of an exchange channel into batches, bounded by the size and the time
+/// that the first message of a batch has been held.
+pub struct ExchangeBatcher<T> {
+    compression: ProstExchangeCompression,
+    max_bytes: usize,
+    timeout: Duration,
+
+    buffer: Vec<T>,
+    buffered_bytes: usize,
+    deadline: Option<Instant>,
+}
+
+impl<T: Message> ExchangeBatcher<T> {
+    /// Returns `None` if the options neither compress nor coalesce, in which case the messages
+    /// should be sent alone without encoding.
+    pub fn new(options: &ExchangeOptions) -> Option<Self> {
+        if options.compression() == ProstExchangeCompression::None && options.batch_max_bytes == 0 {
+            return None;
+        }
+        Some(Self {
+            compression: options.compression(),
+            max_bytes: options.batch_max_bytes as usize,
+            timeout: Duration::from_millis(options.batch_timeout_ms),
+            buffer: vec![],
+            buffered_bytes: 0,
+            deadline: None,
+        })
+    }
+
+    pub fn compression(&self) -> ProstExchangeCompression {
+        self.compression
+    }
+
+    /// The time when the buffered messages should be sent.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Buffers a message, and returns whether the buffered messages should be sent.
+    pub fn push(&mut self, message: T) -> bool {
+        if self.buffer.is_empty() {
+            self.deadline = Some(Instant::now() + self.timeout);
+        }
+        self.buffered_bytes += message.encoded_len();
+        self.buffer.push(message);
+        self.buffered_bytes >= self.max_bytes || Instant::now() >= self.deadline.unwrap()
+    }
+
+    /// Takes the buffered messages.
+    pub fn take(&mut self) -> Vec<T> {
+        self.buffered_bytes = 0;
+        self.deadline = None;
+        std::mem::take(&mut self.buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::data::DataChunk;
+    use risingwave_pb::task_service::DataChunkBatch;
+
+    use super::*;
+
+    fn config(compression: ExchangeCompression, batch_max_bytes: usize) -> ExchangeConfig {
+        ExchangeConfig {
+            compression,
+            batch_max_bytes,
+            batch_timeout_ms: 10,
+        }
+    }
+
+    #[test]
+    fn test_negotiate_exchange_options() {
+        let requested = exchange_options(&config(ExchangeCompression::Zstd, 1024));
+
+        let options =
+            negotiate_exchange_options(&config(ExchangeCompression::Lz4, 4096), Some(&requested));
+        assert_eq!(options.compression(), ProstExchangeCompression::Zstd);
+        assert_eq!(options.batch_max_bytes, 1024);
+
+        let options =
+            negotiate_exchange_options(&config(ExchangeCompression::None, 0), Some(&requested));
+        assert_eq!(options.compression(), ProstExchangeCompression::None);
+        assert_eq!(options.batch_max_bytes, 0);
+        assert!(ExchangeBatcher::<DataChunk>::new(&options).is_none());
+
+        // Receivers that propose nothing get the uncompressed messages.
+        let options = negotiate_exchange_options(&config(ExchangeCompression::Lz4, 4096), None);
+        assert_eq!(options.compression(), ProstExchangeCompression::None);
+        assert!(ExchangeBatcher::<DataChunk>::new(&options).is_none());
+    }
+
+    #[test]
+    fn test_encode_decode_batch() {
+        let batch = DataChunkBatch {
+            chunks: (0..10)
+                .map(|i| DataChunk {
+                    cardinality: i,
+                    ..Default::default()
+                })
+                .collect(),
+        };
+        for compression in [
+            ProstExchangeCompression::None,
+            ProstExchangeCompression::Lz4,
+            ProstExchangeCompression::Zstd,
+        ] {
+            let bytes = encode_batch(compression, &batch).unwrap();
+            let decoded: DataChunkBatch = decode_batch(compression, &bytes).unwrap();
+            assert_eq!(decoded, batch);
+        }
+        assert!(
+            decode_batch::<DataChunkBatch>(ProstExchangeCompression::Zstd, b"garbage").is_err()
+        );
+    }
+
+    #[test]
+    fn test_exchange_batcher() {
+        let chunk = DataChunk {
+            cardinality: 1024,
+            ..Default::default()
+        };
+        let options = ExchangeOptions {
+            compression: ProstExchangeCompression::None as i32,
+            batch_max_bytes: 2 * chunk.encoded_len() as u64,
+            batch_timeout_ms: 1000,
+        };
+        let mut batcher = ExchangeBatcher::new(&options).unwrap();
+        assert!(batcher.deadline().is_none());
+        assert!(!batcher.push(chunk.clone()));
+        assert!(batcher.deadline().is_some());
+        assert!(batcher.push(chunk.clone()));
+        assert_eq!(batcher.take().len(), 2);
+        assert!(batcher.is_empty());
+        assert!(batcher.deadline().is_none());
+
+        // Compressed but not coalesced.
+        let options = ExchangeOptions {
+            compression: ProstExchangeCompression::Lz4 as i32,
+            batch_max_bytes: 0,
+            batch_timeout_ms: 1000,
+        };
+        let mut batcher = ExchangeBatcher::new(&options).unwrap();
+        assert!(batcher.push(chunk));
+    }
+}
@@ -24,6 +24,7 @@ pub mod bit_util;
 pub mod chunk_coalesce;
 pub mod encoding_for_comparison;
 pub mod env_var;
+pub mod exchange;
 pub mod hash_util;
 pub mod ordered;
 pub mod prost;
@@ -21,7 +21,7 @@ use OrderedDatum::{NormalOrder, ReversedOrder};
 
 pub use self::serde::*;
 use crate::array::{Row, RowRef};
-use crate::types::{serialize_datum_into, Datum};
+use crate::types::{estimated_datum_size, serialize_datum_into, Datum};
 use crate::util::sort_util::OrderType;
 
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -75,6 +75,19 @@ impl OrderedRow {
         RowRef(datum_refs)
     }
 
+    /// Estimates the memory held by the row in bytes.
+    pub fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self
+                .0
+                .iter()
+                .map(|ordered_datum| match ordered_datum {
+                    NormalOrder(datum) => estimated_datum_size(datum),
+                    ReversedOrder(datum) => estimated_datum_size(&datum.0),
+                })
+                .sum::<usize>()
+    }
+
     /// Serialize the row into a memcomparable bytes.
     ///
     /// All values are nullable. Each value will have 1 extra byte to indicate whether it is null.
@@ -189,7 +189,8 @@ pub fn compare_two_row(
                 Interval,
                 NaiveDate,
                 NaiveDateTime,
-                NaiveTime
+                NaiveTime,
+                Jsonb
             ]
         );
         if res != Ordering::Equal {
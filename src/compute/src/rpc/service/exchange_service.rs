@@ -17,13 +17,20 @@ use std::sync::Arc;
 
 use futures::channel::mpsc::Receiver;
 use futures::StreamExt;
+use prometheus::IntGauge;
 use risingwave_batch::rpc::service::exchange::GrpcExchangeWriter;
 use risingwave_batch::task::{BatchManager, TaskOutputId};
+use risingwave_common::config::ExchangeConfig;
 use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::util::exchange::{
+    encode_batch, negotiate_exchange_options, ExchangeBatcher,
+};
+use risingwave_pb::data::StreamMessage;
 use risingwave_pb::plan::TaskOutputId as ProtoTaskOutputId;
 use risingwave_pb::task_service::exchange_service_server::ExchangeService;
 use risingwave_pb::task_service::{
-    GetDataRequest, GetDataResponse, GetStreamRequest, GetStreamResponse,
+    ExchangeOptions, GetDataRequest, GetDataResponse, GetStreamRequest, GetStreamResponse,
+    StreamMessageBatch,
 };
 use risingwave_stream::executor::Message;
 use risingwave_stream::task::LocalStreamManager;
@@ -37,6 +44,8 @@ const EXCHANGE_BUFFER_SIZE: usize = 1024;
 pub struct ExchangeServiceImpl {
     batch_mgr: Arc<BatchManager>,
     stream_mgr: Arc<LocalStreamManager>,
+    batch_exchange_config: ExchangeConfig,
+    stream_exchange_config: ExchangeConfig,
 }
 
 type ExchangeDataStream = ReceiverStream<std::result::Result<GetDataResponse, Status>>;
@@ -57,10 +66,12 @@ impl ExchangeService for ExchangeServiceImpl {
             .remote_addr()
             .ok_or_else(|| Status::unavailable("connection unestablished"))?;
         let req = request.into_inner();
+        let options = negotiate_exchange_options(&self.batch_exchange_config, req.options.as_ref());
         match self
             .get_data_impl(
                 peer_addr,
                 req.get_task_output_id().map_err(tonic_err)?.clone(),
+                options,
             )
             .await
         {
@@ -81,11 +92,17 @@ impl ExchangeService for ExchangeServiceImpl {
             .ok_or_else(|| Status::unavailable("get_stream connection unestablished"))?;
         let req = request.into_inner();
         let up_down_ids = (req.up_fragment_id, req.down_fragment_id);
+        let options =
+            negotiate_exchange_options(&self.stream_exchange_config, req.options.as_ref());
         let receiver = self
             .stream_mgr
             .take_receiver(up_down_ids)
             .map_err(|e| e.to_grpc_status())?;
-        match self.get_stream_impl(peer_addr, receiver).await {
+        let occupancy = self.stream_mgr.channel_metrics(up_down_ids).occupancy;
+        match self
+            .get_stream_impl(peer_addr, receiver, occupancy, options)
+            .await
+        {
             Ok(resp) => Ok(resp),
             Err(e) => {
                 error!(
@@ -99,10 +116,17 @@ impl ExchangeService for ExchangeServiceImpl {
 }
 
 impl ExchangeServiceImpl {
-    pub fn new(mgr: Arc<BatchManager>, stream_mgr: Arc<LocalStreamManager>) -> Self {
+    pub fn new(
+        mgr: Arc<BatchManager>,
+        stream_mgr: Arc<LocalStreamManager>,
+        batch_exchange_config: ExchangeConfig,
+        stream_exchange_config: ExchangeConfig,
+    ) -> Self {
         ExchangeServiceImpl {
             batch_mgr: mgr,
             stream_mgr,
+            batch_exchange_config,
+            stream_exchange_config,
         }
     }
 
@@ -111,6 +135,7 @@ impl ExchangeServiceImpl {
         &self,
         peer_addr: SocketAddr,
         pb_tsid: ProtoTaskOutputId,
+        options: ExchangeOptions,
     ) -> Result<Response<<Self as ExchangeService>::GetDataStream>> {
         let (tx, rx) = tokio::sync::mpsc::channel(EXCHANGE_BUFFER_SIZE);
 
@@ -118,7 +143,7 @@ impl ExchangeServiceImpl {
         tracing::trace!(target: "events::compute::exchange", peer_addr = %peer_addr, from = ?tsid, "serve exchange RPC");
         let mut task_output = self.batch_mgr.take_output(&pb_tsid)?;
         tokio::spawn(async move {
-            let mut writer = GrpcExchangeWriter::new(tx.clone());
+            let mut writer = GrpcExchangeWriter::new(tx.clone(), &options);
             match task_output.take_data(&mut writer).await {
                 Ok(_) => {
                     tracing::debug!(
@@ -139,9 +164,17 @@ impl ExchangeServiceImpl {
         &self,
         peer_addr: SocketAddr,
         mut receiver: Receiver<Message>,
+        occupancy: IntGauge,
+        options: ExchangeOptions,
     ) -> Result<Response<<Self as ExchangeService>::GetStreamStream>> {
         let (tx, rx) = tokio::sync::mpsc::channel(EXCHANGE_BUFFER_SIZE);
         tracing::trace!(target: "events::compute::exchange", peer_addr = %peer_addr, "serve stream exchange RPC");
+        if let Some(batcher) = ExchangeBatcher::new(&options) {
+            tokio::spawn(Self::forward_stream_batches(
+                receiver, occupancy, tx, batcher,
+            ));
+            return Ok(Response::new(ReceiverStream::new(rx)));
+        }
         tokio::spawn(async move {
             loop {
                 let msg = receiver.next().await;
@@ -149,9 +182,11 @@ impl ExchangeServiceImpl {
                     // the sender is closed, we close the receiver and stop forwarding message
                     None => break,
                     Some(msg) => {
+                        occupancy.dec();
                         let res = match msg.to_protobuf() {
                             Ok(stream_msg) => Ok(GetStreamResponse {
                                 message: Some(stream_msg),
+                                ..Default::default()
                             }),
                             Err(e) => Err(e.to_grpc_status()),
                         };
@@ -170,4 +205,77 @@ impl ExchangeServiceImpl {
         });
         Ok(Response::new(ReceiverStream::new(rx)))
     }
+
+    /// Coalesces the stream messages into compressed batches. A batch is sent once it reaches the
+    /// size bound or the time bound, or ends with a barrier or a watermark.
+    async fn forward_stream_batches(
+        mut receiver: Receiver<Message>,
+        occupancy: IntGauge,
+        tx: tokio::sync::mpsc::Sender<std::result::Result<GetStreamResponse, Status>>,
+        mut batcher: ExchangeBatcher<StreamMessage>,
+    ) {
+        loop {
+            let msg = match batcher.deadline() {
+                Some(deadline) => {
+                    let deadline = tokio::time::Instant::from_std(deadline);
+                    match tokio::time::timeout_at(deadline, receiver.next()).await {
+                        Ok(msg) => msg,
+                        // The time bound is reached. Send the buffered messages.
+                        Err(_) => {
+                            if !Self::send_stream_batch(&tx, &mut batcher).await {
+                                break;
+                            }
+                            continue;
+                        }
+                    }
+                }
+                None => receiver.next().await,
+            };
+            let should_send = match msg {
+                // The sender is closed. Send the remaining messages and stop forwarding.
+                None => {
+                    Self::send_stream_batch(&tx, &mut batcher).await;
+                    break;
+                }
+                Some(msg) => {
+                    occupancy.dec();
+                    let is_chunk = matches!(msg, Message::Chunk(_));
+                    match msg.to_protobuf() {
+                        Ok(stream_msg) => batcher.push(stream_msg) || !is_chunk,
+                        Err(e) => {
+                            let _ = tx.send(Err(e.to_grpc_status())).await;
+                            break;
+                        }
+                    }
+                }
+            };
+            if should_send && !Self::send_stream_batch(&tx, &mut batcher).await {
+                break;
+            }
+        }
+    }
+
+    /// Sends the buffered messages as a batch. Returns false if the channel is closed.
+    async fn send_stream_batch(
+        tx: &tokio::sync::mpsc::Sender<std::result::Result<GetStreamResponse, Status>>,
+        batcher: &mut ExchangeBatcher<StreamMessage>,
+    ) -> bool {
+        if batcher.is_empty() {
+            return true;
+        }
+        let compression = batcher.compression();
+        let res = encode_batch(
+            compression,
+            &StreamMessageBatch {
+                messages: batcher.take(),
+            },
+        )
+        .map(|batch| GetStreamResponse {
+            message: None,
+            compression: compression as i32,
+            batch,
+        })
+        .map_err(|e| e.to_grpc_status());
+        tx.send(res).await.is_ok()
+    }
 }
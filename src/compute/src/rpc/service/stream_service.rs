@@ -101,9 +101,12 @@ impl StreamService for StreamServiceImpl {
     ) -> std::result::Result<Response<DropActorsResponse>, Status> {
         let req = request.into_inner();
         let actors = req.actor_ids;
-        self.mgr
-            .drop_actor(&actors)
-            .map_err(|e| e.to_grpc_status())?;
+        if req.discard_unsynced_states {
+            self.mgr.drop_actor_and_discard_states(&actors).await
+        } else {
+            self.mgr.drop_actor(&actors)
+        }
+        .map_err(|e| e.to_grpc_status())?;
         Ok(Response::new(DropActorsResponse {
             request_id: req.request_id,
             status: None,
@@ -141,7 +144,12 @@ impl StreamService for StreamServiceImpl {
 
         let collect_result = self
             .mgr
-            .send_and_collect_barrier(&barrier, req.actor_ids_to_send, req.actor_ids_to_collect)
+            .send_and_collect_barrier(
+                &barrier,
+                req.actor_ids_to_send,
+                req.actor_ids_to_collect,
+                req.checkpoint,
+            )
             .await
             .map_err(|e| e.to_grpc_status())?;
 
@@ -150,10 +158,18 @@ impl StreamService for StreamServiceImpl {
             .into_iter()
             .map(Into::into)
             .collect();
+        let create_mview_progress = collect_result
+            .create_mview_progress
+            .into_iter()
+            .map(Into::into)
+            .collect();
 
         Ok(Response::new(InjectBarrierResponse {
             request_id: req.request_id,
             finished_create_mviews,
+            failed_actor_ids: collect_result.failed_actors,
+            create_mview_progress,
+            table_row_count_deltas: collect_result.table_row_count_deltas,
             status: None,
         }))
     }
@@ -35,7 +35,7 @@ use risingwave_storage::hummock::hummock_meta_client::MonitoredHummockMetaClient
 use risingwave_storage::monitor::{HummockMetrics, StateStoreMetrics};
 use risingwave_storage::StateStoreImpl;
 use risingwave_stream::executor::monitor::StreamingMetrics;
-use risingwave_stream::task::{LocalStreamManager, StreamEnvironment};
+use risingwave_stream::task::{LocalStreamManager, MemoryManager, StreamEnvironment};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 use tower::make::Shared;
@@ -147,17 +147,27 @@ pub async fn compute_node_serve(
 
     // Initialize the streaming environment.
     let stream_config = Arc::new(config.streaming.clone());
+    let memory_manager = Arc::new(MemoryManager::new(
+        config.streaming.memory_budget,
+        streaming_metrics.clone(),
+    ));
     let stream_env = StreamEnvironment::new(
         source_mgr,
         client_addr.clone(),
         stream_config,
         worker_id,
         state_store,
+        memory_manager,
     );
 
     // Boot the runtime gRPC services.
     let batch_srv = BatchServiceImpl::new(batch_mgr.clone(), batch_env);
-    let exchange_srv = ExchangeServiceImpl::new(batch_mgr, stream_mgr.clone());
+    let exchange_srv = ExchangeServiceImpl::new(
+        batch_mgr,
+        stream_mgr.clone(),
+        config.batch.exchange.clone(),
+        config.streaming.exchange.clone(),
+    );
     let stream_srv = StreamServiceImpl::new(stream_mgr, stream_env.clone());
 
     let (shutdown_send, mut shutdown_recv) = tokio::sync::mpsc::unbounded_channel();
@@ -37,10 +37,10 @@ use risingwave_storage::table::cell_based_table::CellBasedTable;
 // use risingwave_storage::table::mview::MViewTable;
 use risingwave_storage::{Keyspace, StateStore, StateStoreImpl};
 use risingwave_stream::executor::{
-    Barrier, Executor as StreamExecutor, Message, PkIndices, SourceExecutor, StreamingMetrics,
+    Barrier, Executor as StreamExecutor, Message, PkIndices, StreamingMetrics,
 };
 use risingwave_stream::executor_v2::{
-    Executor as ExecutorV2, MaterializeExecutor as MaterializeExecutorV2,
+    Executor as ExecutorV2, MaterializeExecutor as MaterializeExecutorV2, SourceExecutor,
 };
 use tokio::sync::mpsc::unbounded_channel;
 
@@ -158,22 +158,24 @@ async fn test_table_v2_materialize() -> Result<()> {
         PkIndices::from([1]),
         barrier_rx,
         1,
+        0,
         1,
-        "SourceExecutor".to_string(),
         Arc::new(StreamingMetrics::unused()),
         vec![],
-    )?;
+        vec![],
+        None,
+    );
 
     // Create a `Materialize` to write the changes to storage
     let keyspace = Keyspace::table_root(memory_state_store.clone(), &source_table_id);
-    let mut materialize = Box::new(MaterializeExecutorV2::new_from_v1(
+    let mut materialize = Box::new(MaterializeExecutorV2::new(
         Box::new(stream_source),
         keyspace.clone(),
         vec![OrderPair::new(1, OrderType::Ascending)],
         all_column_ids.clone(),
         2,
-        "MaterializeExecutor".to_string(),
         vec![],
+        None,
     ))
     .v1();
 
@@ -243,7 +245,7 @@ async fn test_table_v2_materialize() -> Result<()> {
             assert_eq!(col_row_id.value_at(0).unwrap(), 0);
             assert_eq!(col_row_id.value_at(1).unwrap(), 1);
         }
-        Message::Barrier(_) => panic!(),
+        Message::Barrier(_) | Message::Watermark(_) => panic!(),
     }
 
     // Send a barrier and poll again, should write changes to storage
@@ -311,7 +313,7 @@ async fn test_table_v2_materialize() -> Result<()> {
             let col_row_id = c.columns()[1].array_ref().as_int64();
             assert_eq!(col_row_id.value_at(0).unwrap(), 0);
         }
-        Message::Barrier(_) => panic!(),
+        Message::Barrier(_) | Message::Watermark(_) => panic!(),
     }
 
     // Send a barrier and poll again, should write changes to storage
@@ -30,8 +30,8 @@ pub enum SourceOffset {
 }
 
 use crate::kafka::KafkaSplit;
-use crate::kinesis::split::KinesisSplit;
-use crate::pulsar::{PulsarSplit, PulsarSplitEnumerator};
+use crate::kinesis::split::{KinesisOffset, KinesisSplit};
+use crate::pulsar::{PulsarOffset, PulsarSplit, PulsarSplitEnumerator};
 use crate::{kafka, kinesis, pulsar};
 
 const UPSTREAM_SOURCE_KEY: &str = "connector";
@@ -72,6 +72,13 @@ pub trait SourceReader {
     async fn new(config: HashMap<String, String>, state: Option<ConnectorState>) -> Result<Self>
     where
         Self: Sized;
+
+    /// Replaces the splits to read. Each split is read from its start offset.
+    async fn assign_splits(&mut self, _splits: Vec<SplitImpl>) -> Result<()> {
+        Err(anyhow!(
+            "split assignment is not supported by this connector"
+        ))
+    }
 }
 
 #[async_trait]
@@ -86,7 +93,7 @@ pub enum SplitEnumeratorImpl {
     Kinesis(kinesis::enumerator::client::KinesisSplitEnumerator),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SplitImpl {
     Kafka(kafka::KafkaSplit),
     Pulsar(pulsar::PulsarSplit),
@@ -118,6 +125,17 @@ impl SplitImpl {
         }
     }
 
+    /// Moves the start offset of the split past `offset`, which is the offset of the last message
+    /// read from it, so that the split resumes after that message.
+    pub fn update_offset(&mut self, offset: String) -> Result<()> {
+        match self {
+            SplitImpl::Kafka(k) => k.start_offset = Some(offset.parse::<i64>()? + 1),
+            SplitImpl::Pulsar(p) => p.start_offset = PulsarOffset::MessageID(offset.parse()?),
+            SplitImpl::Kinesis(k) => k.start_position = KinesisOffset::SequenceNumber(offset),
+        }
+        Ok(())
+    }
+
     pub fn restore_from_bytes(split_type: String, bytes: &[u8]) -> Result<Self> {
         match split_type.as_str() {
             kafka::KAFKA_SPLIT_TYPE => KafkaSplit::restore_from_bytes(bytes).map(SplitImpl::Kafka),
@@ -20,14 +20,15 @@ use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use futures::StreamExt;
 use rdkafka::config::RDKafkaLogLevel;
-use rdkafka::consumer::{DefaultConsumerContext, StreamConsumer};
-use rdkafka::ClientConfig;
+use rdkafka::consumer::{Consumer, DefaultConsumerContext, StreamConsumer};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
 use risingwave_common::error::ErrorCode::{InternalError, ProtocolError};
 use risingwave_common::error::RwError;
 
 use crate::base::{InnerMessage, SourceReader};
 use crate::kafka::split::KafkaSplit;
 use crate::kafka::KAFKA_CONFIG_BROKER_KEY;
+use crate::SplitImpl;
 
 const KAFKA_MAX_FETCH_MESSAGES: usize = 1024;
 
@@ -101,4 +102,29 @@ impl SourceReader for KafkaSplitReader {
             assigned_splits: HashMap::new(),
         })
     }
+
+    async fn assign_splits(&mut self, splits: Vec<SplitImpl>) -> Result<()> {
+        let mut assigned_splits: HashMap<String, Vec<KafkaSplit>> = HashMap::new();
+        let mut topic_partitions = TopicPartitionList::with_capacity(splits.len());
+
+        for split in splits {
+            let split = match split {
+                SplitImpl::Kafka(split) => split,
+                other => return Err(anyhow!("expect kafka split, got {:?}", other)),
+            };
+            let offset = match split.start_offset {
+                Some(offset) => Offset::Offset(offset),
+                None => Offset::Beginning,
+            };
+            topic_partitions.add_partition_offset(&split.topic, split.partition, offset)?;
+            assigned_splits
+                .entry(split.topic.clone())
+                .or_default()
+                .push(split);
+        }
+
+        self.consumer.assign(&topic_partitions)?;
+        self.assigned_splits = assigned_splits;
+        Ok(())
+    }
 }
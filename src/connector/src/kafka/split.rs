@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::base::SourceSplit;
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct KafkaSplit {
     pub(crate) topic: String,
     pub(crate) partition: i32,
@@ -28,7 +28,7 @@ pub enum KinesisOffset {
 
 pub const KINESIS_SPLIT_TYPE: &str = "kinesis";
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct KinesisSplit {
     pub(crate) shard_id: String,
     pub(crate) start_position: KinesisOffset,
@@ -26,7 +26,7 @@ pub enum PulsarOffset {
 
 pub const PULSAR_SPLIT_TYPE: &str = "pulsar";
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PulsarSplit {
     pub(crate) sub_topic: String,
     pub(crate) start_offset: PulsarOffset,
@@ -20,6 +20,8 @@ use log::error;
 use risingwave_storage::storage_value::StorageValue;
 use risingwave_storage::{Keyspace, StateStore};
 
+use crate::SplitImpl;
+
 /// `SourceState` Represents an abstraction of state,
 /// e.g. if the Kafka Source state consists of `topic` `partition_id` and `offset`.
 pub trait SourceState: Debug + Clone {
@@ -31,6 +33,21 @@ pub trait SourceState: Debug + Clone {
     fn decode(&self, values: Bytes) -> Self;
 }
 
+/// The state of a split is the split itself, whose start offset is moved past the messages read.
+impl SourceState for SplitImpl {
+    fn identifier(&self) -> String {
+        self.id()
+    }
+
+    fn encode(&self) -> Bytes {
+        Bytes::from(self.to_string().unwrap())
+    }
+
+    fn decode(&self, values: Bytes) -> Self {
+        SplitImpl::restore_from_bytes(self.get_type(), &values).unwrap()
+    }
+}
+
 #[derive(Clone)]
 pub struct SourceStateHandler<S: StateStore> {
     keyspace: Keyspace<S>,
@@ -158,6 +175,37 @@ impl<S: StateStore> SourceStateHandler<S> {
             Err(e) => Err(anyhow!(e)),
         }
     }
+
+    /// Persists the states under their identifiers, overwriting the previous ones. Unlike
+    /// [`SourceStateHandler::take_snapshot`], only the latest state of each identifier is kept.
+    pub async fn put_latest_states<SS>(&self, states: Vec<SS>, epoch: u64) -> Result<()>
+    where
+        SS: SourceState,
+    {
+        let mut write_batch = self.keyspace.state_store().start_write_batch();
+        let mut local_batch = write_batch.prefixify(&self.keyspace);
+        for state in states {
+            local_batch.put(
+                state.identifier(),
+                StorageValue::new_default_put(state.encode()),
+            );
+        }
+        write_batch.ingest(epoch).await.map_err(|e| anyhow!(e))
+    }
+
+    /// Returns the latest state of the same identifier as `state` persisted by
+    /// [`SourceStateHandler::put_latest_states`], or `None` if it has never been persisted.
+    pub async fn get_latest_state<SS>(&self, state: &SS, epoch: u64) -> Result<Option<SS>>
+    where
+        SS: SourceState,
+    {
+        let value = self
+            .keyspace
+            .get(state.identifier(), epoch)
+            .await
+            .map_err(|e| anyhow!(e))?;
+        Ok(value.map(|value| state.decode(value)))
+    }
 }
 
 #[cfg(test)]
@@ -308,6 +356,34 @@ mod tests {
         assert_eq!(0, list_states.len())
     }
 
+    #[tokio::test]
+    async fn test_latest_state() {
+        let keyspace = new_test_keyspace();
+        let state_store_handler = SourceStateHandler::new(keyspace.clone());
+        let state = TestSourceState::new("p0".to_string(), 0);
+        assert!(state_store_handler
+            .get_latest_state(&state, u64::MAX)
+            .await
+            .unwrap()
+            .is_none());
+
+        for (epoch, offset) in [(1000, 10), (1001, 20), (1002, 30)] {
+            state_store_handler
+                .put_latest_states(vec![TestSourceState::new("p0".to_string(), offset)], epoch)
+                .await
+                .unwrap();
+        }
+        let latest = state_store_handler
+            .get_latest_state(&state, u64::MAX)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(30, latest.offset);
+
+        // The previous states are overwritten.
+        assert_eq!(1, keyspace.scan(None, u64::MAX).await.unwrap().len());
+    }
+
     #[tokio::test]
     async fn test_state_restore() {
         let state_store_handler = SourceStateHandler::new(new_test_keyspace());
@@ -13,3 +13,4 @@
 // limitations under the License.
 
 pub mod hummock;
+pub mod meta;
@@ -31,6 +31,9 @@ enum Commands {
     /// Commands for Hummock
     #[clap(subcommand)]
     Hummock(HummockCommands),
+    /// Commands for Meta
+    #[clap(subcommand)]
+    Meta(MetaCommands),
 }
 
 #[derive(Subcommand)]
@@ -41,11 +44,21 @@ enum HummockCommands {
     ListKv,
 }
 
+#[derive(Subcommand)]
+enum MetaCommands {
+    /// pause the data sources of all streaming jobs
+    Pause,
+    /// resume the data sources of all streaming jobs
+    Resume,
+}
+
 pub async fn start(opts: CliOpts) {
     match &opts.command {
         Commands::Hummock(HummockCommands::ListVersion) => {
             cmd_impl::hummock::list_version().await.unwrap()
         }
         Commands::Hummock(HummockCommands::ListKv) => cmd_impl::hummock::list_kv().await.unwrap(),
+        Commands::Meta(MetaCommands::Pause) => cmd_impl::meta::pause().await.unwrap(),
+        Commands::Meta(MetaCommands::Resume) => cmd_impl::meta::resume().await.unwrap(),
     }
 }
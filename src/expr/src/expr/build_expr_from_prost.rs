@@ -15,20 +15,27 @@
 use risingwave_common::array::DataChunk;
 use risingwave_common::ensure;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
 use risingwave_pb::expr::expr_node::RexNode;
 use risingwave_pb::expr::{expr_node, ExprNode};
 
+use crate::expr::expr_array::ArrayFunctionExpression;
 use crate::expr::expr_binary_bytes::new_substr_start;
 use crate::expr::expr_binary_nonnull::{new_binary_expr, new_like_default};
 use crate::expr::expr_binary_nullable::new_nullable_binary_expr;
 use crate::expr::expr_case::{CaseExpression, WhenClause};
 use crate::expr::expr_in::InExpression;
+use crate::expr::expr_jsonb::{
+    new_jsonb_access_expr, new_jsonb_contains_expr, JsonbExtractPathExpression,
+};
+use crate::expr::expr_now::NowExpression;
+use crate::expr::expr_struct::{FieldExpression, RowExpression};
+use crate::expr::expr_ternary::new_ternary_expr;
 use crate::expr::expr_ternary_bytes::{new_replace_expr, new_substr_start_end, new_translate_expr};
 use crate::expr::expr_unary::{
     new_length_default, new_ltrim_expr, new_rtrim_expr, new_trim_expr, new_unary_expr,
 };
-use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression};
+use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression, LiteralExpression};
 
 fn get_return_type_and_children(prost: &ExprNode) -> Result<(Vec<ExprNode>, DataType)> {
     let ret_type = DataType::from(prost.get_return_type()?);
@@ -74,6 +81,96 @@ pub fn build_nullable_binary_expr_prost(prost: &ExprNode) -> Result<BoxedExpress
     ))
 }
 
+pub fn build_jsonb_access_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let left_expr = expr_build_from_prost(&children[0])?;
+    let right_expr = expr_build_from_prost(&children[1])?;
+    new_jsonb_access_expr(prost.get_expr_type()?, ret_type, left_expr, right_expr)
+}
+
+pub fn build_jsonb_contains_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let left_expr = expr_build_from_prost(&children[0])?;
+    let right_expr = expr_build_from_prost(&children[1])?;
+    Ok(new_jsonb_contains_expr(ret_type, left_expr, right_expr))
+}
+
+pub fn build_jsonb_extract_path_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(!children.is_empty());
+    let input = expr_build_from_prost(&children[0])?;
+    let path = children[1..]
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(JsonbExtractPathExpression::new(
+        input, path, ret_type,
+    )))
+}
+
+pub fn build_array_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    let children = children
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(ArrayFunctionExpression::new(
+        prost.get_expr_type()?,
+        children,
+        ret_type,
+    )?))
+}
+
+pub fn build_field_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 2);
+    let input = expr_build_from_prost(&children[0])?;
+    let index = match LiteralExpression::try_from(&children[1])?.literal() {
+        Some(ScalarImpl::Int32(index)) if index >= 0 => index as usize,
+        index => {
+            return Err(ErrorCode::InternalError(format!(
+                "field index must be a non-negative int32 constant, got {:?}",
+                index
+            ))
+            .into())
+        }
+    };
+    Ok(Box::new(FieldExpression::new(ret_type, input, index)?))
+}
+
+pub fn build_row_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    let children = children
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(RowExpression::new(ret_type, children)?))
+}
+
+pub fn build_ternary_expr_prost(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.len() == 3);
+    let first_expr = expr_build_from_prost(&children[0])?;
+    let second_expr = expr_build_from_prost(&children[1])?;
+    let third_expr = expr_build_from_prost(&children[2])?;
+    new_ternary_expr(
+        prost.get_expr_type()?,
+        ret_type,
+        first_expr,
+        second_expr,
+        third_expr,
+    )
+}
+
+pub fn build_now_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_return_type_and_children(prost)?;
+    ensure!(children.is_empty());
+    ensure!(ret_type == DataType::Timestampz);
+    Ok(Box::new(NowExpression::new(ret_type)))
+}
+
 pub fn build_substr_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     let (children, ret_type) = get_return_type_and_children(prost)?;
     let child = expr_build_from_prost(&children[0])?;
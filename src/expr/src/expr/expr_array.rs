@@ -0,0 +1,157 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Expressions on `list` values, e.g. `ARRAY[...]`, subscripting and `array_cat`.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{ArrayRef, DataChunk};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, Datum, DatumRef};
+use risingwave_pb::expr::expr_node::Type;
+
+use crate::expr::{BoxedExpression, Expression};
+use crate::vector_op::array::*;
+
+type ArrayFunction = fn(&[DatumRef<'_>]) -> Result<Datum>;
+
+/// Evaluates an array function row by row. Unlike the typed templates, the arguments and the
+/// result can be of any (element) type, which is only known at runtime.
+#[derive(Debug)]
+pub struct ArrayFunctionExpression {
+    children: Vec<BoxedExpression>,
+    return_type: DataType,
+    func: ArrayFunction,
+}
+
+impl ArrayFunctionExpression {
+    pub fn new(
+        expr_type: Type,
+        children: Vec<BoxedExpression>,
+        return_type: DataType,
+    ) -> Result<Self> {
+        let func: ArrayFunction = match expr_type {
+            Type::Array => array_constructor,
+            Type::ArrayAccess => array_access,
+            Type::ArrayLength => array_length,
+            Type::ArrayCat => array_cat,
+            Type::ArrayAppend => array_append,
+            Type::ArrayPosition => array_position,
+            Type::ArrayAnyEqual => array_any_equal,
+            _ => {
+                return Err(ErrorCode::InternalError(format!(
+                    "{:?} is not an array function",
+                    expr_type
+                ))
+                .into())
+            }
+        };
+        let arity_matches = match expr_type {
+            Type::Array => true,
+            Type::ArrayLength => matches!(children.len(), 1 | 2),
+            _ => children.len() == 2,
+        };
+        if !arity_matches {
+            return Err(ErrorCode::InternalError(format!(
+                "{:?} does not accept {} arguments",
+                expr_type,
+                children.len()
+            ))
+            .into());
+        }
+        Ok(Self {
+            children,
+            return_type,
+            func,
+        })
+    }
+}
+
+impl Expression for ArrayFunctionExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let arrays = self
+            .children
+            .iter()
+            .map(|e| e.eval(input))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder = self.return_type.create_array_builder(input.capacity())?;
+        let mut args = Vec::with_capacity(arrays.len());
+        for idx in 0..input.capacity() {
+            if let Some(visibility) = input.get_visibility_ref() {
+                if !visibility.is_set(idx)? {
+                    continue;
+                }
+            }
+            args.clear();
+            args.extend(arrays.iter().map(|a| a.value_at(idx)));
+            builder.append_datum(&(self.func)(&args)?)?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::ListValue;
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::expr::LiteralExpression;
+
+    fn literal(ty: DataType, v: ScalarImpl) -> BoxedExpression {
+        Box::new(LiteralExpression::new(ty, Some(v)))
+    }
+
+    #[test]
+    fn test_array_constructor_and_access() {
+        let list_type = DataType::List {
+            datatype: Box::new(DataType::Int32),
+        };
+        let array = ArrayFunctionExpression::new(
+            Type::Array,
+            vec![
+                literal(DataType::Int32, ScalarImpl::Int32(1)),
+                literal(DataType::Int32, ScalarImpl::Int32(2)),
+            ],
+            list_type.clone(),
+        )
+        .unwrap();
+        let res = array.eval(&DataChunk::new_dummy(2)).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(
+            res.datum_at(1),
+            Some(ScalarImpl::List(ListValue::new(vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Int32(2))
+            ])))
+        );
+
+        let access = ArrayFunctionExpression::new(
+            Type::ArrayAccess,
+            vec![
+                Box::new(array),
+                literal(DataType::Int32, ScalarImpl::Int32(2)),
+            ],
+            DataType::Int32,
+        )
+        .unwrap();
+        let res = access.eval(&DataChunk::new_dummy(1)).unwrap();
+        assert_eq!(res.datum_at(0), Some(ScalarImpl::Int32(2)));
+    }
+}
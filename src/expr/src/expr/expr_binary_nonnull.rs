@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use risingwave_common::array::{
-    Array, BoolArray, DecimalArray, I32Array, IntervalArray, NaiveDateArray, NaiveDateTimeArray,
-    Utf8Array,
+    Array, BoolArray, DecimalArray, I32Array, I64Array, IntervalArray, NaiveDateArray,
+    NaiveDateTimeArray, Utf8Array,
 };
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::Result;
@@ -23,12 +23,16 @@ use risingwave_pb::expr::expr_node::Type;
 
 use crate::expr::template::BinaryExpression;
 use crate::expr::BoxedExpression;
+use crate::vector_op::age::age_timestamp_timestamp;
 use crate::vector_op::arithmetic_op::*;
 use crate::vector_op::cmp::*;
+use crate::vector_op::date_trunc::{date_trunc_timestamp, date_trunc_timestampz};
 use crate::vector_op::extract::{extract_from_date, extract_from_timestamp};
 use crate::vector_op::like::like_default;
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
+use crate::vector_op::to_char::{to_char_timestamp, to_char_timestampz};
+use crate::vector_op::to_timestamp::to_timestamp;
 use crate::vector_op::tumble::{tumble_start_date, tumble_start_date_time};
 
 /// A placeholder function that returns bool in [`gen_binary_expr_atm`]
@@ -164,6 +168,7 @@ macro_rules! gen_binary_expr_cmp {
                     { float32, decimal, float64, $general_f },
                     { float64, decimal, float64, $general_f },
                     { timestamp, timestamp, timestamp, $general_f },
+                    { timestampz, timestampz, timestampz, $general_f },
                     { date, date, date, $general_f },
                     { boolean, boolean, boolean, $general_f },
                     { timestamp, date, timestamp, $general_f },
@@ -192,6 +197,9 @@ macro_rules! gen_binary_expr_atm {
             $((Timestamp, Timestamp) => $timestamp_timestamp_f:ident,)?
             $((Timestamp, Interval) => $timestamp_interval_f:ident,)?
             $((Interval, Timestamp) => $interval_timestamp_f:ident,)?
+            $((Timestampz, Timestampz) => $timestampz_timestampz_f:ident,)?
+            $((Timestampz, Interval) => $timestampz_interval_f:ident,)?
+            $((Interval, Timestampz) => $interval_timestampz_f:ident,)?
             $((Date, Date) => $date_date_f:ident,)?
             $((Interval, Date) => $interval_date_f:ident,)?
             $((Date, Interval) => $date_interval_f:ident,)?
@@ -241,9 +249,13 @@ macro_rules! gen_binary_expr_atm {
             $({ timestamp, timestamp, interval, $timestamp_timestamp_f },)?
             $({ timestamp, interval, timestamp, $timestamp_interval_f },)?
             $({ interval, timestamp, timestamp, $interval_timestamp_f },)?
+            $({ timestampz, timestampz, interval, $timestampz_timestampz_f },)?
+            $({ timestampz, interval, timestampz, $timestampz_interval_f },)?
+            $({ interval, timestampz, timestampz, $interval_timestampz_f },)?
             $({ date, date, int32, $date_date_f },)?
             $({ date, interval, timestamp, $date_interval_f },)?
             $({ interval, date, timestamp, $interval_date_f },)?
+            $({ interval, interval, interval, $interval_interval_f },)?
             $({ interval, int16, interval, $interval_int_f },)?
             $({ interval, int32, interval, $interval_int_f },)?
             $({ interval, int64, interval, $interval_int_f },)?
@@ -276,6 +288,50 @@ fn build_extract_expr(ret: DataType, l: BoxedExpression, r: BoxedExpression) ->
     }
 }
 
+fn build_date_trunc_expr(ret: DataType, l: BoxedExpression, r: BoxedExpression) -> BoxedExpression {
+    match r.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            Utf8Array,
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, date_trunc_timestamp)),
+        DataType::Timestampz => {
+            Box::new(BinaryExpression::<Utf8Array, I64Array, I64Array, _>::new(
+                l,
+                r,
+                ret,
+                date_trunc_timestampz,
+            ))
+        }
+        _ => {
+            unimplemented!("DateTrunc ( {:?} ) is not supported yet!", r.return_type())
+        }
+    }
+}
+
+fn build_to_char_expr(ret: DataType, l: BoxedExpression, r: BoxedExpression) -> BoxedExpression {
+    match l.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            NaiveDateTimeArray,
+            Utf8Array,
+            Utf8Array,
+            _,
+        >::new(l, r, ret, to_char_timestamp)),
+        DataType::Timestampz => {
+            Box::new(BinaryExpression::<I64Array, Utf8Array, Utf8Array, _>::new(
+                l,
+                r,
+                ret,
+                to_char_timestampz,
+            ))
+        }
+        _ => {
+            unimplemented!("ToChar ( {:?} ) is not supported yet!", l.return_type())
+        }
+    }
+}
+
 pub fn new_binary_expr(
     expr_type: Type,
     ret: DataType,
@@ -311,6 +367,8 @@ pub fn new_binary_expr(
                     General => general_add,
                     (Timestamp, Interval) => timestamp_interval_add,
                     (Interval, Timestamp) => interval_timestamp_add,
+                    (Timestampz, Interval) => timestampz_interval_add,
+                    (Interval, Timestampz) => interval_timestampz_add,
                     (Interval, Date) => interval_date_add,
                     (Date, Interval) => date_interval_add,
                     (Interval, Interval) => general_add,
@@ -325,6 +383,8 @@ pub fn new_binary_expr(
                     General => general_sub,
                     (Timestamp, Timestamp) => timestamp_timestamp_sub,
                     (Timestamp, Interval) => timestamp_interval_sub,
+                    (Timestampz, Timestampz) => timestampz_timestampz_sub,
+                    (Timestampz, Interval) => timestampz_interval_sub,
                     (Date, Date) => date_date_sub,
                     (Date, Interval) => date_interval_sub,
                     (Interval, Interval) => general_sub,
@@ -373,6 +433,20 @@ pub fn new_binary_expr(
             l, r, ret, position,
         )),
         Type::TumbleStart => new_tumble_start(l, r, ret),
+        Type::DateTrunc => build_date_trunc_expr(ret, l, r),
+        Type::ToChar => build_to_char_expr(ret, l, r),
+        Type::ToTimestamp => Box::new(BinaryExpression::<Utf8Array, Utf8Array, I64Array, _>::new(
+            l,
+            r,
+            ret,
+            to_timestamp,
+        )),
+        Type::Age => Box::new(BinaryExpression::<
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            IntervalArray,
+            _,
+        >::new(l, r, ret, age_timestamp_timestamp)),
         tp => {
             unimplemented!(
                 "The expression {:?} using vectorized expression framework is not supported yet!",
@@ -0,0 +1,187 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Expressions on `jsonb` values, e.g. `->`, `->>`, `@>` and `jsonb_extract_path`.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{
+    Array, ArrayBuilder, ArrayRef, BoolArray, DataChunk, I32Array, JsonbArray, JsonbArrayBuilder,
+    Utf8Array,
+};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, Scalar, ScalarRef};
+use risingwave_pb::expr::expr_node::Type;
+
+use crate::expr::template::{BinaryExpression, BinaryNullableExpression};
+use crate::expr::{BoxedExpression, Expression};
+use crate::vector_op::jsonb::*;
+
+/// Builds `->` or `->>`. The accessor is either an object key (`varchar`) or an array index
+/// (`int`).
+pub fn new_jsonb_access_expr(
+    expr_type: Type,
+    return_type: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match (expr_type, r.return_type()) {
+        (Type::JsonbAccessInner, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                JsonbArray,
+                _,
+            >::new(l, r, return_type, jsonb_object_field))
+        }
+        (Type::JsonbAccessInner, DataType::Int32) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                I32Array,
+                JsonbArray,
+                _,
+            >::new(l, r, return_type, jsonb_array_element))
+        }
+        (Type::JsonbAccessStr, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                Utf8Array,
+                _,
+            >::new(
+                l, r, return_type, jsonb_object_field_text
+            ))
+        }
+        (Type::JsonbAccessStr, DataType::Int32) => {
+            Box::new(
+                BinaryNullableExpression::<JsonbArray, I32Array, Utf8Array, _>::new(
+                    l,
+                    r,
+                    return_type,
+                    jsonb_array_element_text,
+                ),
+            )
+        }
+        (expr_type, ty) => {
+            return Err(ErrorCode::NotImplemented(
+                format!("{:?} with {:?} is not supported", expr_type, ty),
+                None.into(),
+            )
+            .into())
+        }
+    };
+    Ok(expr)
+}
+
+pub fn new_jsonb_contains_expr(
+    return_type: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> BoxedExpression {
+    Box::new(
+        BinaryExpression::<JsonbArray, JsonbArray, BoolArray, _>::new(
+            l,
+            r,
+            return_type,
+            jsonb_contains,
+        ),
+    )
+}
+
+/// `jsonb_extract_path(from_json jsonb, VARIADIC path_elems text[])`, which is also what
+/// `jsonb #> text[]` is bound to. Each path element is an object key or, if the current value is
+/// an array, an array index. Returns NULL if any element of the path does not exist.
+#[derive(Debug)]
+pub struct JsonbExtractPathExpression {
+    input: BoxedExpression,
+    path: Vec<BoxedExpression>,
+    return_type: DataType,
+}
+
+impl JsonbExtractPathExpression {
+    pub fn new(input: BoxedExpression, path: Vec<BoxedExpression>, return_type: DataType) -> Self {
+        Self {
+            input,
+            path,
+            return_type,
+        }
+    }
+}
+
+impl Expression for JsonbExtractPathExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let json_array = self.input.eval(input)?;
+        let json_array: &JsonbArray = json_array.as_ref().into();
+        let path_arrays = self
+            .path
+            .iter()
+            .map(|e| e.eval(input))
+            .collect::<Result<Vec<_>>>()?;
+        let path_arrays = path_arrays
+            .iter()
+            .map(|a| a.as_ref().into())
+            .collect::<Vec<&Utf8Array>>();
+
+        let mut builder = JsonbArrayBuilder::new(input.capacity())?;
+        for idx in 0..input.capacity() {
+            if let Some(visibility) = input.get_visibility_ref() {
+                if !visibility.is_set(idx)? {
+                    continue;
+                }
+            }
+            let path: Option<Vec<&str>> = path_arrays.iter().map(|a| a.value_at(idx)).collect();
+            let output = match (json_array.value_at(idx), path) {
+                (Some(json), Some(path)) => json.access_path(path).map(|v| v.to_owned_scalar()),
+                _ => None,
+            };
+            builder.append(output.as_ref().map(|v| v.as_scalar_ref()))?;
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::expr::LiteralExpression;
+    use crate::vector_op::jsonb::parse_jsonb_path;
+
+    fn literal(ty: DataType, v: ScalarImpl) -> BoxedExpression {
+        Box::new(LiteralExpression::new(ty, Some(v)))
+    }
+
+    #[test]
+    fn test_jsonb_extract_path() {
+        let json = r#"{"a": [1, {"b": "foo"}]}"#.parse().unwrap();
+        let path = parse_jsonb_path("{a, 1, b}").unwrap();
+        let expr = JsonbExtractPathExpression::new(
+            literal(DataType::Jsonb, ScalarImpl::Jsonb(json)),
+            path.into_iter()
+                .map(|p| literal(DataType::Varchar, ScalarImpl::Utf8(p)))
+                .collect(),
+            DataType::Jsonb,
+        );
+        let res = expr.eval(&DataChunk::new_dummy(1)).unwrap();
+        assert_eq!(
+            res.to_datum(),
+            Some(ScalarImpl::Jsonb(r#""foo""#.parse().unwrap()))
+        );
+    }
+}
@@ -24,6 +24,7 @@ use risingwave_common::array::{
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::types::{DataType, Datum, Decimal, IntervalUnit, Scalar, ScalarImpl};
+use risingwave_common::util::value_encoding::deserialize_cell_not_null;
 use risingwave_common::{ensure, for_all_variants};
 use risingwave_pb::data::data_type::IntervalType::*;
 use risingwave_pb::data::data_type::{IntervalType, TypeName};
@@ -103,6 +104,9 @@ fn literal_type_match(return_type: &DataType, literal: Option<&ScalarImpl>) -> b
                     | (DataType::Timestamp, ScalarImpl::NaiveDateTime(_))
                     | (DataType::Decimal, ScalarImpl::Decimal(_))
                     | (DataType::Interval, ScalarImpl::Interval(_))
+                    | (DataType::Jsonb, ScalarImpl::Jsonb(_))
+                    | (DataType::List { .. }, ScalarImpl::List(_))
+                    | (DataType::Struct { .. }, ScalarImpl::Struct(_))
             )
         }
         None => true,
@@ -220,6 +224,19 @@ impl<'a> TryFrom<&'a ExprNode> for LiteralExpression {
                             InternalError(format!("Failed to deserialize decimal, reason: {:?}", e))
                         })?,
                 ),
+                TypeName::Jsonb => ScalarImpl::Jsonb(
+                    std::str::from_utf8(prost_value.get_body())
+                        .map_err(|e| {
+                            InternalError(format!("Failed to deserialize jsonb, reason: {:?}", e))
+                        })?
+                        .parse()?,
+                ),
+                TypeName::Struct | TypeName::List => {
+                    let mut deserializer =
+                        value_encoding::Deserializer::new(prost_value.get_body().as_slice());
+                    deserialize_cell_not_null(&mut deserializer, ret_type.clone())?
+                        .ok_or_else(|| InternalError("Unexpected null literal".to_string()))?
+                }
                 TypeName::Interval => {
                     let bytes = prost_value.get_body();
                     ScalarImpl::Interval(make_interval(
@@ -352,6 +369,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_struct_literal_from() {
+        use risingwave_common::array::StructValue;
+        use risingwave_common::util::value_encoding::serialize_cell_not_null;
+
+        let ty = DataType::new_struct(
+            vec![DataType::Int32, DataType::Varchar],
+            vec!["a".to_string(), "b".to_string()],
+        );
+        let v = Some(ScalarImpl::Struct(StructValue::new(vec![
+            Some(ScalarImpl::Int32(1)),
+            None,
+        ])));
+        let prost = ExprNode {
+            expr_type: Type::ConstantValue as i32,
+            return_type: Some(ty.to_protobuf()),
+            rex_node: Some(RexNode::Constant(ConstantValue {
+                body: serialize_cell_not_null(&v).unwrap(),
+            })),
+        };
+        let expr = LiteralExpression::try_from(&prost).unwrap();
+        assert_eq!(expr.return_type(), ty);
+        assert_eq!(expr.literal(), v);
+    }
+
     fn make_expression(bytes: Option<Vec<u8>>, data_type: TypeName) -> ExprNode {
         ExprNode {
             expr_type: Type::ConstantValue as i32,
@@ -0,0 +1,122 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `NOW()` and `CURRENT_TIMESTAMP`.
+//!
+//! In batch queries, `NOW()` is the time when the expression is built, so all rows of a query see
+//! the same value. In streaming, `NOW()` is bound to the epoch of the latest barrier seen by the
+//! actor, so that the results are deterministic when the same epochs are replayed on recovery.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use risingwave_common::array::{ArrayBuilder, ArrayRef, DataChunk, I64ArrayBuilder};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+
+use crate::expr::Expression;
+
+/// Must be consistent with the epoch layout generated by meta, where the physical time in
+/// milliseconds occupies the higher bits.
+const EPOCH_PHYSICAL_SHIFT_BITS: u8 = 16;
+
+tokio::task_local! {
+    /// The epoch of the latest barrier seen by the current streaming actor.
+    static STREAM_EPOCH: Cell<u64>;
+}
+
+/// Runs `f`, usually a streaming actor, with an epoch context that `NOW()` is bound to.
+pub async fn scope_stream_epoch<F: Future>(f: F) -> F::Output {
+    STREAM_EPOCH.scope(Cell::new(0), f).await
+}
+
+/// Updates the epoch that `NOW()` is bound to. It's a no-op outside [`scope_stream_epoch`].
+pub fn set_stream_epoch(epoch: u64) {
+    let _ = STREAM_EPOCH.try_with(|e| e.set(epoch));
+}
+
+fn stream_epoch() -> Option<u64> {
+    STREAM_EPOCH.try_with(|e| e.get()).ok().filter(|e| *e != 0)
+}
+
+#[derive(Debug)]
+pub struct NowExpression {
+    return_type: DataType,
+    /// Microseconds since the Unix epoch when the expression is built.
+    build_time: i64,
+}
+
+impl NowExpression {
+    pub fn new(return_type: DataType) -> Self {
+        let build_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_micros() as i64;
+        Self {
+            return_type,
+            build_time,
+        }
+    }
+
+    fn now(&self) -> i64 {
+        match stream_epoch() {
+            Some(epoch) => ((epoch >> EPOCH_PHYSICAL_SHIFT_BITS) * 1000) as i64,
+            None => self.build_time,
+        }
+    }
+}
+
+impl Expression for NowExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let now = self.now();
+        let mut builder = I64ArrayBuilder::new(input.cardinality())?;
+        for _ in 0..input.cardinality() {
+            builder.append(Some(now))?;
+        }
+        Ok(Arc::new(builder.finish()?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::ScalarRefImpl;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_now_bound_to_epoch() {
+        let expr = NowExpression::new(DataType::Timestampz);
+        let chunk = DataChunk::new_dummy(2);
+        let batch_now = expr.eval(&chunk).unwrap();
+        assert_eq!(batch_now.len(), 2);
+        assert_eq!(batch_now.value_at(0), batch_now.value_at(1));
+
+        scope_stream_epoch(async {
+            // 2022-05-18 13:45:21 UTC
+            set_stream_epoch(1_652_881_521_000 << EPOCH_PHYSICAL_SHIFT_BITS);
+            let stream_now = expr.eval(&chunk).unwrap();
+            assert_eq!(
+                stream_now.value_at(0),
+                Some(ScalarRefImpl::Int64(1_652_881_521_000_000))
+            );
+        })
+        .await;
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Expressions on `struct` values, i.e. field access and `ROW(...)`.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{ArrayRef, DataChunk, StructValue};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
+
+use crate::expr::{BoxedExpression, Expression};
+
+/// Extracts the `index`-th field of a struct. The result is null if the struct itself is null.
+#[derive(Debug)]
+pub struct FieldExpression {
+    return_type: DataType,
+    input: BoxedExpression,
+    index: usize,
+}
+
+impl FieldExpression {
+    pub fn new(return_type: DataType, input: BoxedExpression, index: usize) -> Result<Self> {
+        match input.return_type() {
+            DataType::Struct { fields, .. } if index < fields.len() => Ok(Self {
+                return_type,
+                input,
+                index,
+            }),
+            ty => Err(ErrorCode::InternalError(format!(
+                "cannot access field {} of type {:?}",
+                index, ty
+            ))
+            .into()),
+        }
+    }
+}
+
+impl Expression for FieldExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let array = self.input.eval(input)?;
+        let array = array.as_struct();
+
+        let mut builder = self.return_type.create_array_builder(input.capacity())?;
+        for idx in 0..input.capacity() {
+            if let Some(visibility) = input.get_visibility_ref() {
+                if !visibility.is_set(idx)? {
+                    continue;
+                }
+            }
+            let datum = array.value_at(idx).and_then(|s| s.field_at(self.index));
+            builder.append_datum_ref(datum)?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+/// Builds a struct from its fields, i.e. `ROW(a, b, ...)`.
+#[derive(Debug)]
+pub struct RowExpression {
+    return_type: DataType,
+    children: Vec<BoxedExpression>,
+}
+
+impl RowExpression {
+    pub fn new(return_type: DataType, children: Vec<BoxedExpression>) -> Result<Self> {
+        match &return_type {
+            DataType::Struct { fields, .. } if fields.len() == children.len() => Ok(Self {
+                return_type,
+                children,
+            }),
+            ty => Err(ErrorCode::InternalError(format!(
+                "cannot build {:?} from {} fields",
+                ty,
+                children.len()
+            ))
+            .into()),
+        }
+    }
+}
+
+impl Expression for RowExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let arrays = self
+            .children
+            .iter()
+            .map(|e| e.eval(input))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder = self.return_type.create_array_builder(input.capacity())?;
+        for idx in 0..input.capacity() {
+            if let Some(visibility) = input.get_visibility_ref() {
+                if !visibility.is_set(idx)? {
+                    continue;
+                }
+            }
+            let fields = arrays
+                .iter()
+                .map(|a| a.value_at(idx).to_owned_datum())
+                .collect();
+            builder.append_datum(&Some(ScalarImpl::Struct(StructValue::new(fields))))?;
+        }
+        Ok(Arc::new(builder.finish()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::LiteralExpression;
+
+    #[test]
+    fn test_row_and_field() {
+        let struct_type = DataType::new_struct(
+            vec![DataType::Int32, DataType::Varchar],
+            vec!["a".to_string(), "b".to_string()],
+        );
+        let row = RowExpression::new(
+            struct_type.clone(),
+            vec![
+                Box::new(LiteralExpression::new(
+                    DataType::Int32,
+                    Some(ScalarImpl::Int32(1)),
+                )),
+                Box::new(LiteralExpression::new(DataType::Varchar, None)),
+            ],
+        )
+        .unwrap();
+        let res = row.eval(&DataChunk::new_dummy(2)).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(
+            res.datum_at(0),
+            Some(ScalarImpl::Struct(StructValue::new(vec![
+                Some(ScalarImpl::Int32(1)),
+                None
+            ])))
+        );
+
+        let field = FieldExpression::new(DataType::Int32, Box::new(row), 0).unwrap();
+        let res = field.eval(&DataChunk::new_dummy(2)).unwrap();
+        assert_eq!(res.datum_at(1), Some(ScalarImpl::Int32(1)));
+
+        let null_struct = LiteralExpression::new(struct_type, None);
+        let field = FieldExpression::new(DataType::Varchar, Box::new(null_struct), 1).unwrap();
+        let res = field.eval(&DataChunk::new_dummy(1)).unwrap();
+        assert_eq!(res.datum_at(0), None);
+
+        assert!(FieldExpression::new(
+            DataType::Int32,
+            Box::new(LiteralExpression::new(DataType::Int32, None)),
+            0
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! For expression that accept 3 arguments as input.
+
+use risingwave_common::array::{I32Array, NaiveDateArray};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::expr_node::Type;
+
+use crate::expr::template::TernaryExpression;
+use crate::expr::BoxedExpression;
+use crate::vector_op::make_date::make_date;
+
+pub fn new_ternary_expr(
+    expr_type: Type,
+    return_type: DataType,
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    expr_ia3: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match expr_type {
+        Type::MakeDate => Box::new(TernaryExpression::<
+            I32Array,
+            I32Array,
+            I32Array,
+            NaiveDateArray,
+            _,
+        >::new(
+            expr_ia1, expr_ia2, expr_ia3, return_type, make_date
+        )),
+        tp => return Err(ErrorCode::NotImplemented(
+            format!(
+                "The expression {:?} using vectorized expression framework is not supported yet!",
+                tp
+            ),
+            112.into(),
+        )
+        .into()),
+    };
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::DataChunk;
+    use risingwave_common::types::{NaiveDateWrapper, ScalarRefImpl};
+
+    use super::*;
+    use crate::expr::InputRefExpression;
+
+    #[test]
+    fn test_make_date() {
+        let expr = new_ternary_expr(
+            Type::MakeDate,
+            DataType::Date,
+            Box::new(InputRefExpression::new(DataType::Int32, 0)),
+            Box::new(InputRefExpression::new(DataType::Int32, 1)),
+            Box::new(InputRefExpression::new(DataType::Int32, 2)),
+        )
+        .unwrap();
+        let columns = [
+            [Some(2022), Some(2022)],
+            [Some(5), None],
+            [Some(18), Some(1)],
+        ]
+        .iter()
+        .map(|values| {
+            Column::new(
+                I32Array::from_slice(values)
+                    .map(|x| Arc::new(x.into()))
+                    .unwrap(),
+            )
+        })
+        .collect();
+        let chunk = DataChunk::builder().columns(columns).build();
+        let result = expr.eval(&chunk).unwrap();
+        assert_eq!(
+            result.value_at(0),
+            Some(ScalarRefImpl::NaiveDate(NaiveDateWrapper::new(
+                NaiveDate::from_ymd(2022, 5, 18)
+            )))
+        );
+        assert_eq!(result.value_at(1), None);
+    }
+}
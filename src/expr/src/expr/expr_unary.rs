@@ -29,6 +29,8 @@ use crate::vector_op::ascii::ascii;
 use crate::vector_op::cast::*;
 use crate::vector_op::cmp::{is_false, is_not_false, is_not_true, is_true};
 use crate::vector_op::conjunction;
+use crate::vector_op::jsonb::{jsonb_to_str, str_to_jsonb};
+use crate::vector_op::justify_interval::justify_interval;
 use crate::vector_op::length::length_default;
 use crate::vector_op::lower::lower;
 use crate::vector_op::ltrim::ltrim;
@@ -103,6 +105,9 @@ macro_rules! gen_cast {
 
             { boolean, varchar, bool_to_str },
 
+            { varchar, jsonb, str_to_jsonb },
+            { jsonb, varchar, jsonb_to_str },
+
             { int16, int32, general_cast },
             { int16, int64, general_cast },
             { int16, float32, general_cast },
@@ -248,6 +253,13 @@ pub fn new_unary_expr(
         (ProstType::Neg, _, _) => {
             gen_neg! { child_expr, return_type }
         }
+        (ProstType::JustifyInterval, _, DataType::Interval) => {
+            Box::new(UnaryExpression::<IntervalArray, IntervalArray, _>::new(
+                child_expr,
+                return_type,
+                justify_interval,
+            ))
+        }
         (ProstType::PgSleep, _, DataType::Decimal) => Box::new(PgSleepExpression::new(child_expr)),
 
         (expr, ret, child) => {
@@ -15,6 +15,7 @@
 mod agg;
 pub mod build_expr_from_prost;
 pub mod data_types;
+mod expr_array;
 mod expr_binary_bytes;
 pub mod expr_binary_nonnull;
 pub mod expr_binary_nullable;
@@ -22,7 +23,11 @@ mod expr_case;
 mod expr_in;
 mod expr_input_ref;
 mod expr_is_null;
+mod expr_jsonb;
 mod expr_literal;
+mod expr_now;
+mod expr_struct;
+mod expr_ternary;
 mod expr_ternary_bytes;
 pub mod expr_unary;
 mod pg_sleep;
@@ -35,6 +40,7 @@ use std::sync::Arc;
 pub use agg::AggKind;
 pub use expr_input_ref::InputRefExpression;
 pub use expr_literal::*;
+pub use expr_now::{scope_stream_epoch, set_stream_epoch};
 use risingwave_common::array::{ArrayRef, DataChunk, Row};
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::Result;
@@ -71,12 +77,23 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
 
     match prost.get_expr_type()? {
         Cast | Upper | Lower | Not | PgSleep | IsTrue | IsNotTrue | IsFalse | IsNotFalse
-        | IsNull | IsNotNull | Neg | Ascii => build_unary_expr_prost(prost),
+        | IsNull | IsNotNull | Neg | Ascii | JustifyInterval => build_unary_expr_prost(prost),
         Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual => {
             build_binary_expr_prost(prost)
         }
         Add | Subtract | Multiply | Divide | Modulus => build_binary_expr_prost(prost),
-        Extract | RoundDigit | TumbleStart | Position => build_binary_expr_prost(prost),
+        Extract | RoundDigit | TumbleStart | Position | DateTrunc | ToChar | ToTimestamp | Age => {
+            build_binary_expr_prost(prost)
+        }
+        MakeDate => build_ternary_expr_prost(prost),
+        Now => build_now_expr(prost),
+        Array | ArrayAccess | ArrayLength | ArrayCat | ArrayAppend | ArrayPosition
+        | ArrayAnyEqual => build_array_expr(prost),
+        Field => build_field_expr(prost),
+        Row => build_row_expr(prost),
+        JsonbAccessInner | JsonbAccessStr => build_jsonb_access_expr(prost),
+        JsonbContains => build_jsonb_contains_expr(prost),
+        JsonbExtractPath => build_jsonb_extract_path_expr(prost),
         StreamNullByRowCount | And | Or => build_nullable_binary_expr_prost(prost),
         Substr => build_substr_expr(prost),
         Length => build_length_expr(prost),
@@ -0,0 +1,93 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{
+    Array, ArrayBuilder, ArrayRef, DataChunk, JsonbArray, JsonbArrayBuilder,
+};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+
+use super::TableFunction;
+use crate::expr::BoxedExpression;
+
+/// `jsonb_array_elements(jsonb)`: expands the top-level JSON array into a set of JSON values.
+/// Produces no rows if the input is NULL or not an array.
+#[derive(Debug)]
+pub struct JsonbArrayElements {
+    arg: BoxedExpression,
+    return_type: DataType,
+}
+
+impl JsonbArrayElements {
+    pub fn new(arg: BoxedExpression, return_type: DataType) -> Self {
+        Self { arg, return_type }
+    }
+}
+
+impl TableFunction for JsonbArrayElements {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<Vec<ArrayRef>> {
+        let arg = self.arg.eval(input)?;
+        let arg: &JsonbArray = arg.as_ref().into();
+
+        let mut output = Vec::with_capacity(input.cardinality());
+        for idx in 0..input.capacity() {
+            if let Some(visibility) = input.get_visibility_ref() {
+                if !visibility.is_set(idx)? {
+                    continue;
+                }
+            }
+            let mut builder = JsonbArrayBuilder::new(0)?;
+            if let Some(elements) = arg.value_at(idx).and_then(|j| j.array_elements()) {
+                for element in elements {
+                    builder.append(Some(element))?;
+                }
+            }
+            output.push(Arc::new(builder.finish()?.into()));
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::expr::LiteralExpression;
+
+    #[test]
+    fn test_jsonb_array_elements() {
+        let json = r#"[1, "a", {"b": null}]"#.parse().unwrap();
+        let func = JsonbArrayElements::new(
+            Box::new(LiteralExpression::new(
+                DataType::Jsonb,
+                Some(ScalarImpl::Jsonb(json)),
+            )),
+            DataType::Jsonb,
+        );
+        let output = func.eval(&DataChunk::new_dummy(2)).unwrap();
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[0].len(), 3);
+        assert_eq!(
+            output[0].datum_at(2),
+            Some(ScalarImpl::Jsonb(r#"{"b": null}"#.parse().unwrap()))
+        );
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Table functions, i.e. set-returning functions which produce zero or more rows for each input
+//! row.
+
+use risingwave_common::array::{ArrayRef, DataChunk};
+use risingwave_common::ensure;
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::table_function::Type;
+use risingwave_pb::expr::TableFunction as TableFunctionProst;
+
+use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression};
+
+mod jsonb_array_elements;
+mod unnest;
+
+pub use jsonb_array_elements::JsonbArrayElements;
+pub use unnest::Unnest;
+
+/// Instance of a table function.
+pub trait TableFunction: std::fmt::Debug + Sync + Send {
+    fn return_type(&self) -> DataType;
+
+    /// Evaluates the table function. Returns one array for each visible row of `input`,
+    /// containing the rows generated from that row.
+    fn eval(&self, input: &DataChunk) -> Result<Vec<ArrayRef>>;
+
+    fn boxed(self) -> BoxedTableFunction
+    where
+        Self: Sized + Send + 'static,
+    {
+        Box::new(self)
+    }
+}
+
+pub type BoxedTableFunction = Box<dyn TableFunction>;
+
+pub fn build_from_prost(prost: &TableFunctionProst) -> Result<BoxedTableFunction> {
+    let return_type = DataType::from(prost.get_return_type()?);
+    let args = prost
+        .get_args()
+        .iter()
+        .map(expr_build_from_prost)
+        .collect::<Result<Vec<BoxedExpression>>>()?;
+
+    match prost.get_function_type()? {
+        Type::JsonbArrayElements => {
+            ensure!(args.len() == 1);
+            let arg = args.into_iter().next().unwrap();
+            Ok(JsonbArrayElements::new(arg, return_type).boxed())
+        }
+        Type::Unnest => {
+            ensure!(args.len() == 1);
+            let arg = args.into_iter().next().unwrap();
+            Ok(Unnest::new(arg, return_type).boxed())
+        }
+        Type::Unspecified => Err(InternalError("unspecified table function".to_string()).into()),
+    }
+}
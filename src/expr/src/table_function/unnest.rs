@@ -0,0 +1,101 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{ArrayRef, DataChunk};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, ScalarRefImpl};
+
+use super::TableFunction;
+use crate::expr::BoxedExpression;
+
+/// `unnest(anyarray)`: expands an array into a set of rows, one for each element. Produces no rows
+/// if the input is NULL.
+#[derive(Debug)]
+pub struct Unnest {
+    arg: BoxedExpression,
+    return_type: DataType,
+}
+
+impl Unnest {
+    pub fn new(arg: BoxedExpression, return_type: DataType) -> Self {
+        Self { arg, return_type }
+    }
+}
+
+impl TableFunction for Unnest {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<Vec<ArrayRef>> {
+        let arg = self.arg.eval(input)?;
+
+        let mut output = Vec::with_capacity(input.cardinality());
+        for idx in 0..input.capacity() {
+            if let Some(visibility) = input.get_visibility_ref() {
+                if !visibility.is_set(idx)? {
+                    continue;
+                }
+            }
+            let mut builder = self.return_type.create_array_builder(0)?;
+            match arg.value_at(idx) {
+                Some(ScalarRefImpl::List(list)) => {
+                    for element in list.values_ref() {
+                        builder.append_datum_ref(element)?;
+                    }
+                }
+                None => {}
+                Some(other) => {
+                    return Err(ErrorCode::InternalError(format!(
+                        "unnest expects a list, got {:?}",
+                        other
+                    ))
+                    .into())
+                }
+            }
+            output.push(Arc::new(builder.finish()?));
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::ListValue;
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::expr::LiteralExpression;
+
+    #[test]
+    fn test_unnest() {
+        let list = ListValue::new(vec![Some(ScalarImpl::Int32(1)), None]);
+        let func = Unnest::new(
+            Box::new(LiteralExpression::new(
+                DataType::List {
+                    datatype: Box::new(DataType::Int32),
+                },
+                Some(ScalarImpl::List(list)),
+            )),
+            DataType::Int32,
+        );
+        let output = func.eval(&DataChunk::new_dummy(1)).unwrap();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].len(), 2);
+        assert_eq!(output[0].datum_at(0), Some(ScalarImpl::Int32(1)));
+        assert_eq!(output[0].datum_at(1), None);
+    }
+}
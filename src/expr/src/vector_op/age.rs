@@ -0,0 +1,95 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, NaiveDateTime};
+use risingwave_common::error::Result;
+use risingwave_common::types::{IntervalUnit, NaiveDateTimeWrapper};
+
+use super::arithmetic_op::get_mouth_days;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Subtracts `r` from `l`, producing a "symbolic" result that uses years and months, rather than
+/// just days. Same as `age(timestamp, timestamp)` in PG.
+#[inline(always)]
+pub fn age_timestamp_timestamp(
+    l: NaiveDateTimeWrapper,
+    r: NaiveDateTimeWrapper,
+) -> Result<IntervalUnit> {
+    // Always subtract the earlier one from the later one, and negate the result afterwards.
+    let (later, earlier, sign) = if l.0 >= r.0 {
+        (l.0, r.0, 1)
+    } else {
+        (r.0, l.0, -1)
+    };
+    let (months, days, ms) = age_positive(later, earlier);
+    Ok(IntervalUnit::new(
+        sign * months,
+        sign * days,
+        sign as i64 * ms,
+    ))
+}
+
+fn age_positive(later: NaiveDateTime, earlier: NaiveDateTime) -> (i32, i32, i64) {
+    let mut years = later.year() - earlier.year();
+    let mut months = later.month() as i32 - earlier.month() as i32;
+    let mut days = later.day() as i32 - earlier.day() as i32;
+    let mut ms = (later.time() - earlier.time()).num_milliseconds();
+
+    // Borrow from the larger units. A borrowed month has as many days as the month of the earlier
+    // timestamp, as in PG.
+    if ms < 0 {
+        ms += DAY_MS;
+        days -= 1;
+    }
+    if days < 0 {
+        days += get_mouth_days(earlier.year(), earlier.month() as usize);
+        months -= 1;
+    }
+    if months < 0 {
+        months += 12;
+        years -= 1;
+    }
+    (years * 12 + months, days, ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> NaiveDateTimeWrapper {
+        NaiveDateTimeWrapper::new(NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap())
+    }
+
+    #[test]
+    fn test_age() {
+        let l = parse("2001-04-10 00:00:00");
+        let r = parse("1957-06-13 00:00:00");
+        assert_eq!(
+            age_timestamp_timestamp(l, r).unwrap(),
+            IntervalUnit::new(43 * 12 + 9, 27, 0)
+        );
+        assert_eq!(
+            age_timestamp_timestamp(r, l).unwrap(),
+            IntervalUnit::new(-(43 * 12 + 9), -27, 0)
+        );
+
+        let l = parse("2022-03-01 01:00:00");
+        let r = parse("2022-02-27 02:00:00");
+        assert_eq!(
+            age_timestamp_timestamp(l, r).unwrap(),
+            IntervalUnit::new(0, 1, 23 * 3600 * 1000)
+        );
+    }
+}
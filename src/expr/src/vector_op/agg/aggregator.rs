@@ -18,11 +18,14 @@ use risingwave_common::types::*;
 use risingwave_pb::expr::AggCall;
 
 use crate::expr::AggKind;
+use crate::vector_op::agg::array_agg::ArrayAgg;
 use crate::vector_op::agg::count_star::CountStar;
+use crate::vector_op::agg::first_last_value::FirstLastValue;
 use crate::vector_op::agg::functions::*;
 use crate::vector_op::agg::general_agg::*;
 use crate::vector_op::agg::general_distinct_agg::*;
 use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+use crate::vector_op::agg::variance::VarianceAgg;
 
 /// An `Aggregator` supports `update` data and `output` result.
 pub trait Aggregator: Send + 'static {
@@ -60,6 +63,8 @@ pub struct AggStateFactory {
     // When agg func is count(*), the args is empty and input type is None.
     input_type: Option<DataType>,
     input_col_idx: usize,
+    /// The ordering key column of `first_value` and `last_value`.
+    order_col_idx: Option<usize>,
     agg_kind: AggKind,
     return_type: DataType,
     distinct: bool,
@@ -77,6 +82,19 @@ impl AggStateFactory {
                 Ok(Self {
                     input_type: Some(input_type),
                     input_col_idx,
+                    order_col_idx: None,
+                    agg_kind,
+                    return_type,
+                    distinct,
+                })
+            }
+            [ref value, ref key]
+                if matches!(agg_kind, AggKind::FirstValue | AggKind::LastValue) && !distinct =>
+            {
+                Ok(Self {
+                    input_type: Some(DataType::from(value.get_type()?)),
+                    input_col_idx: value.get_input()?.get_column_idx() as usize,
+                    order_col_idx: Some(key.get_input()?.get_column_idx() as usize),
                     agg_kind,
                     return_type,
                     distinct,
@@ -86,6 +104,7 @@ impl AggStateFactory {
                 (AggKind::Count, DataType::Int64) => Ok(Self {
                     input_type: None,
                     input_col_idx: 0,
+                    order_col_idx: None,
                     agg_kind,
                     return_type,
                     distinct,
@@ -103,7 +122,14 @@ impl AggStateFactory {
     }
 
     pub fn create_agg_state(&self) -> Result<Box<dyn Aggregator>> {
-        if let Some(input_type) = self.input_type.clone() {
+        if let Some(order_col_idx) = self.order_col_idx {
+            Ok(Box::new(FirstLastValue::new(
+                self.return_type.clone(),
+                self.input_col_idx,
+                order_col_idx,
+                self.agg_kind == AggKind::FirstValue,
+            )))
+        } else if let Some(input_type) = self.input_type.clone() {
             create_agg_state_unary(
                 input_type,
                 self.input_col_idx,
@@ -130,6 +156,45 @@ pub fn create_agg_state_unary(
 ) -> Result<Box<dyn Aggregator>> {
     use crate::expr::data_types::*;
 
+    // `array_agg` works on any input type, so it is not generated from the typed arms below.
+    if *agg_type == AggKind::ArrayAgg {
+        if distinct {
+            return Err(ErrorCode::NotImplemented(
+                "array_agg with DISTINCT".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+        return Ok(Box::new(ArrayAgg::new(return_type, input_col_idx)?));
+    }
+
+    // Variance aggregations accept any numeric input and always compute in double precision.
+    if matches!(
+        agg_type,
+        AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp
+    ) {
+        if distinct {
+            return Err(ErrorCode::NotImplemented(
+                format!("{} with DISTINCT", agg_type),
+                None.into(),
+            )
+            .into());
+        }
+        if !input_type.is_numeric() {
+            return Err(ErrorCode::InternalError(format!(
+                "unsupported aggregator: type={:?} input={:?}",
+                agg_type, input_type
+            ))
+            .into());
+        }
+        return Ok(Box::new(VarianceAgg::new(
+            agg_type.clone(),
+            &input_type,
+            return_type,
+            input_col_idx,
+        )?));
+    }
+
     macro_rules! gen_arms {
         [$(($agg:ident, $fn:expr, $in:tt, $ret:tt)),* $(,)?] => {
             match (
@@ -198,6 +263,14 @@ pub fn create_agg_state_unary(
         (Max, max, decimal, decimal),
         (Max, max, boolean, boolean), // TODO(#359): remove once unnecessary
         (Max, max_str, varchar, varchar),
+        (BoolAnd, bool_and, boolean, boolean),
+        (BoolOr, bool_or, boolean, boolean),
+        (BitAnd, bit_and, int16, int16),
+        (BitAnd, bit_and, int32, int32),
+        (BitAnd, bit_and, int64, int64),
+        (BitOr, bit_or, int16, int16),
+        (BitOr, bit_or, int32, int32),
+        (BitOr, bit_or, int64, int64),
         // Global Agg
         (Sum, sum, int64, int64),
         // We remark that SingleValue does not produce a runtime error when it receives zero row.
@@ -267,5 +340,10 @@ mod tests {
         test_create! { decimal_type, SingleValue, decimal_type, is_ok }
         test_create! { bool_type, SingleValue, bool_type, is_ok }
         test_create! { char_type, SingleValue, char_type, is_ok }
+
+        test_create! { bool_type, BoolAnd, bool_type, is_ok }
+        test_create! { int64_type, BoolOr, bool_type, is_err }
+        test_create! { int64_type, BitAnd, int64_type, is_ok }
+        test_create! { char_type, BitOr, char_type, is_err }
     }
 }
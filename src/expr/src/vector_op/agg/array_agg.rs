@@ -0,0 +1,148 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::*;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::*;
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// `array_agg(x)`: collects the input values, including NULLs, into a list. Produces NULL rather
+/// than an empty list when there is no input row.
+pub struct ArrayAgg {
+    return_type: DataType,
+    input_col_idx: usize,
+    result: Option<Vec<Datum>>,
+}
+
+impl ArrayAgg {
+    pub fn new(return_type: DataType, input_col_idx: usize) -> Result<Self> {
+        if !matches!(return_type, DataType::List { .. }) {
+            return Err(ErrorCode::InternalError(format!(
+                "array_agg should return a list, got {:?}",
+                return_type
+            ))
+            .into());
+        }
+        Ok(Self {
+            return_type,
+            input_col_idx,
+            result: None,
+        })
+    }
+
+    fn push(&mut self, datum: DatumRef<'_>) {
+        self.result
+            .get_or_insert_with(Vec::new)
+            .push(datum.to_owned_datum());
+    }
+
+    fn is_visible(input: &DataChunk, row_id: usize) -> Result<bool> {
+        match input.visibility() {
+            Some(visibility) => visibility.is_set(row_id),
+            None => Ok(true),
+        }
+    }
+
+    fn output_and_reset(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        let result = self
+            .result
+            .take()
+            .map(|v| ScalarImpl::List(ListValue::new(v)));
+        builder.append_datum(&result)
+    }
+}
+
+impl Aggregator for ArrayAgg {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        if Self::is_visible(input, row_id)? {
+            let array = input.column_at(self.input_col_idx).array_ref();
+            self.push(array.value_at(row_id));
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        for row_id in 0..input.capacity() {
+            self.update_with_row(input, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        let result = self
+            .result
+            .clone()
+            .map(|v| ScalarImpl::List(ListValue::new(v)));
+        builder.append_datum(&result)
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let mut groups_iter = groups.get_starting_indices().iter().peekable();
+        for row_id in 0..input.capacity() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                self.output_and_reset(builder)?;
+            }
+            self.update_with_row(input, row_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_common::array::column::Column;
+
+    use super::*;
+
+    #[test]
+    fn test_array_agg() -> Result<()> {
+        let return_type = DataType::List {
+            datatype: Box::new(DataType::Int32),
+        };
+        let input = I32Array::from_slice(&[Some(1), None, Some(3)])?;
+        let input_chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(input.into()))])
+            .build();
+        let mut agg = ArrayAgg::new(return_type.clone(), 0)?;
+        let mut builder = return_type.create_array_builder(0)?;
+        agg.output(&mut builder)?;
+        agg.update(&input_chunk)?;
+        agg.output(&mut builder)?;
+        let output = builder.finish()?;
+        assert_eq!(output.datum_at(0), None);
+        assert_eq!(
+            output.datum_at(1),
+            Some(ScalarImpl::List(ListValue::new(vec![
+                Some(ScalarImpl::Int32(1)),
+                None,
+                Some(ScalarImpl::Int32(3)),
+            ])))
+        );
+        Ok(())
+    }
+}
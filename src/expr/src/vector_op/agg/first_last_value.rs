@@ -0,0 +1,170 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use risingwave_common::array::*;
+use risingwave_common::error::Result;
+use risingwave_common::types::*;
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// Compares ordering keys of `first_value` and `last_value`, where NULLs sort after all other
+/// values as in an ascending `ORDER BY`.
+pub fn cmp_nulls_last(lhs: &Datum, rhs: &Datum) -> Ordering {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => lhs.cmp(rhs),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// `first_value(x ORDER BY k)` and `last_value(x ORDER BY k)`: the value of the input row with the
+/// smallest, resp. largest, ordering key. The inputs are the value and the ordering key columns.
+pub struct FirstLastValue {
+    return_type: DataType,
+    value_col_idx: usize,
+    key_col_idx: usize,
+    is_first: bool,
+    /// The ordering key and value of the chosen row so far.
+    result: Option<(Datum, Datum)>,
+}
+
+impl FirstLastValue {
+    pub fn new(
+        return_type: DataType,
+        value_col_idx: usize,
+        key_col_idx: usize,
+        is_first: bool,
+    ) -> Self {
+        Self {
+            return_type,
+            value_col_idx,
+            key_col_idx,
+            is_first,
+            result: None,
+        }
+    }
+
+    fn is_visible(input: &DataChunk, row_id: usize) -> Result<bool> {
+        match input.visibility() {
+            Some(visibility) => visibility.is_set(row_id),
+            None => Ok(true),
+        }
+    }
+
+    fn output_and_reset(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        self.output(builder)?;
+        self.result = None;
+        Ok(())
+    }
+}
+
+impl Aggregator for FirstLastValue {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        if !Self::is_visible(input, row_id)? {
+            return Ok(());
+        }
+        let key = input
+            .column_at(self.key_col_idx)
+            .array_ref()
+            .datum_at(row_id);
+        let replace = match &self.result {
+            None => true,
+            // On ties, `first_value` keeps the earliest row and `last_value` takes the latest one.
+            Some((cur_key, _)) => match cmp_nulls_last(&key, cur_key) {
+                Ordering::Less => self.is_first,
+                Ordering::Equal | Ordering::Greater => !self.is_first,
+            },
+        };
+        if replace {
+            let value = input
+                .column_at(self.value_col_idx)
+                .array_ref()
+                .datum_at(row_id);
+            self.result = Some((key, value));
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        for row_id in 0..input.capacity() {
+            self.update_with_row(input, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        let value = self.result.as_ref().and_then(|(_, value)| value.clone());
+        builder.append_datum(&value)
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let mut groups_iter = groups.get_starting_indices().iter().peekable();
+        for row_id in 0..input.capacity() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                self.output_and_reset(builder)?;
+            }
+            self.update_with_row(input, row_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_common::array::column::Column;
+
+    use super::*;
+
+    #[test]
+    fn test_first_last_value() -> Result<()> {
+        let values = I32Array::from_slice(&[Some(1), Some(2), Some(3), Some(4)])?;
+        let keys = I64Array::from_slice(&[Some(20), None, Some(10), Some(20)])?;
+        let input_chunk = DataChunk::builder()
+            .columns(vec![
+                Column::new(Arc::new(values.into())),
+                Column::new(Arc::new(keys.into())),
+            ])
+            .build();
+        let mut builder = DataType::Int32.create_array_builder(0)?;
+        for is_first in [true, false] {
+            let mut agg = FirstLastValue::new(DataType::Int32, 0, 1, is_first);
+            agg.output(&mut builder)?;
+            agg.update(&input_chunk)?;
+            agg.output(&mut builder)?;
+        }
+        let output = builder.finish()?;
+        assert_eq!(output.datum_at(0), None);
+        assert_eq!(output.datum_at(1), Some(ScalarImpl::Int32(3)));
+        assert_eq!(output.datum_at(2), None);
+        // The NULL key sorts last.
+        assert_eq!(output.datum_at(3), Some(ScalarImpl::Int32(2)));
+        Ok(())
+    }
+}
@@ -52,7 +52,7 @@ where
 }
 
 use std::convert::From;
-use std::ops::Add;
+use std::ops::{Add, BitAnd, BitOr};
 
 use risingwave_common::types::ScalarRef;
 
@@ -119,6 +119,48 @@ pub fn count_str(r: Option<i64>, i: Option<&str>) -> Result<Option<i64>> {
     count(r, i)
 }
 
+pub fn bool_and(result: Option<bool>, input: Option<bool>) -> Result<Option<bool>> {
+    let res = match (result, input) {
+        (_, None) => result,
+        (None, Some(i)) => Some(i),
+        (Some(r), Some(i)) => Some(r && i),
+    };
+    Ok(res)
+}
+
+pub fn bool_or(result: Option<bool>, input: Option<bool>) -> Result<Option<bool>> {
+    let res = match (result, input) {
+        (_, None) => result,
+        (None, Some(i)) => Some(i),
+        (Some(r), Some(i)) => Some(r || i),
+    };
+    Ok(res)
+}
+
+pub fn bit_and<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitAnd<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (_, None) => result,
+        (None, Some(i)) => Some(i),
+        (Some(r), Some(i)) => Some(r & i),
+    };
+    Ok(res)
+}
+
+pub fn bit_or<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitOr<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (_, None) => result,
+        (None, Some(i)) => Some(i),
+        (Some(r), Some(i)) => Some(r | i),
+    };
+    Ok(res)
+}
+
 pub struct SingleValue {
     count: usize,
 }
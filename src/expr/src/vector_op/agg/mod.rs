@@ -13,11 +13,16 @@
 // limitations under the License.
 
 mod aggregator;
+mod array_agg;
 mod count_star;
+mod first_last_value;
 mod functions;
 mod general_agg;
 mod general_distinct_agg;
 mod general_sorted_grouper;
+mod variance;
 
 pub use aggregator::{AggStateFactory, BoxedAggState};
+pub use first_last_value::cmp_nulls_last;
 pub use general_sorted_grouper::{create_sorted_grouper, BoxedSortedGrouper, EqGroups};
+pub use variance::{VarianceState, VarianceSums};
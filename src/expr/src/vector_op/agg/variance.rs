@@ -0,0 +1,394 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub};
+use risingwave_common::array::*;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::*;
+
+use crate::expr::AggKind;
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::general_sorted_grouper::EqGroups;
+
+/// Running count, sum and sum of squares of the non-null inputs, from which `var_pop`, `var_samp`,
+/// `stddev_pop` and `stddev_samp` are derived. Inputs can be retracted, so streaming aggregation
+/// shares this state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VarianceState {
+    pub count: i64,
+    pub sums: VarianceSums,
+}
+
+/// The sum and sum of squares of the inputs. Integer and decimal inputs are summed exactly, so
+/// that retractions leave no rounding errors behind, and are only converted to `f64` at output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VarianceSums {
+    Int { sum: i128, sum_sq: i128 },
+    Decimal { sum: Decimal, sum_sq: Decimal },
+    Float { sum: f64, sum_sq: f64 },
+}
+
+impl VarianceState {
+    /// Creates an empty state for inputs of `input_type`, which must be numeric.
+    pub fn new(input_type: &DataType) -> Self {
+        let sums = match input_type {
+            DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+                VarianceSums::Int { sum: 0, sum_sq: 0 }
+            }
+            DataType::Decimal => VarianceSums::Decimal {
+                sum: Decimal::zero(),
+                sum_sq: Decimal::zero(),
+            },
+            _ => VarianceSums::Float {
+                sum: 0.0,
+                sum_sq: 0.0,
+            },
+        };
+        Self { count: 0, sums }
+    }
+
+    /// Removes all inputs, keeping the kind of the sums.
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.sums = match self.sums {
+            VarianceSums::Int { .. } => VarianceSums::Int { sum: 0, sum_sq: 0 },
+            VarianceSums::Decimal { .. } => VarianceSums::Decimal {
+                sum: Decimal::zero(),
+                sum_sq: Decimal::zero(),
+            },
+            VarianceSums::Float { .. } => VarianceSums::Float {
+                sum: 0.0,
+                sum_sq: 0.0,
+            },
+        };
+    }
+
+    pub fn accumulate(&mut self, value: ScalarRefImpl<'_>) -> Result<()> {
+        self.update(value, false)
+    }
+
+    pub fn retract(&mut self, value: ScalarRefImpl<'_>) -> Result<()> {
+        self.update(value, true)
+    }
+
+    fn update(&mut self, value: ScalarRefImpl<'_>, retract: bool) -> Result<()> {
+        match (&mut self.sums, value) {
+            (
+                VarianceSums::Int { sum, sum_sq },
+                ScalarRefImpl::Int16(_) | ScalarRefImpl::Int32(_) | ScalarRefImpl::Int64(_),
+            ) => {
+                let v = match value {
+                    ScalarRefImpl::Int16(v) => v as i128,
+                    ScalarRefImpl::Int32(v) => v as i128,
+                    ScalarRefImpl::Int64(v) => v as i128,
+                    _ => unreachable!(),
+                };
+                // The square of an `i64` always fits in an `i128`.
+                let (new_sum, new_sum_sq) = if retract {
+                    (sum.checked_sub(v), sum_sq.checked_sub(v * v))
+                } else {
+                    (sum.checked_add(v), sum_sq.checked_add(v * v))
+                };
+                match (new_sum, new_sum_sq) {
+                    (Some(new_sum), Some(new_sum_sq)) => {
+                        *sum = new_sum;
+                        *sum_sq = new_sum_sq;
+                    }
+                    _ => return Err(ErrorCode::NumericValueOutOfRange.into()),
+                }
+            }
+            (VarianceSums::Decimal { sum, sum_sq }, ScalarRefImpl::Decimal(v)) => {
+                let square = v.checked_mul(&v);
+                let (new_sum, new_sum_sq) = if retract {
+                    (
+                        sum.checked_sub(&v),
+                        square.and_then(|sq| sum_sq.checked_sub(&sq)),
+                    )
+                } else {
+                    (
+                        sum.checked_add(&v),
+                        square.and_then(|sq| sum_sq.checked_add(&sq)),
+                    )
+                };
+                match (new_sum, new_sum_sq) {
+                    (Some(new_sum), Some(new_sum_sq)) => {
+                        *sum = new_sum;
+                        *sum_sq = new_sum_sq;
+                    }
+                    _ => return Err(ErrorCode::NumericValueOutOfRange.into()),
+                }
+            }
+            (
+                VarianceSums::Float { sum, sum_sq },
+                ScalarRefImpl::Float32(_) | ScalarRefImpl::Float64(_),
+            ) => {
+                let v = match value {
+                    ScalarRefImpl::Float32(v) => v.0 as f64,
+                    ScalarRefImpl::Float64(v) => v.0,
+                    _ => unreachable!(),
+                };
+                if retract && self.count == 1 {
+                    // Avoid accumulating floating-point errors once all inputs are gone.
+                    *sum = 0.0;
+                    *sum_sq = 0.0;
+                } else if retract {
+                    *sum -= v;
+                    *sum_sq -= v * v;
+                } else {
+                    *sum += v;
+                    *sum_sq += v * v;
+                }
+            }
+            (sums, value) => {
+                return Err(ErrorCode::InternalError(format!(
+                    "unexpected input {:?} of variance aggregation with {:?}",
+                    value, sums
+                ))
+                .into())
+            }
+        }
+        self.count += if retract { -1 } else { 1 };
+        Ok(())
+    }
+
+    /// Computes the result of `kind`, which must be one of the variance or standard deviation
+    /// aggregations. Returns `None` when there are too few inputs.
+    pub fn output(&self, kind: &AggKind) -> Result<Option<f64>> {
+        let min_count = match kind {
+            AggKind::VarPop | AggKind::StddevPop => 1,
+            AggKind::VarSamp | AggKind::StddevSamp => 2,
+            _ => unreachable!("not a variance aggregation: {:?}", kind),
+        };
+        if self.count < min_count {
+            return Ok(None);
+        }
+        // `count * sum_sq - sum * sum`, computed exactly whenever the inputs are exact.
+        let numerator = match self.sums {
+            VarianceSums::Int { sum, sum_sq } => (self.count as i128)
+                .checked_mul(sum_sq)
+                .zip(sum.checked_mul(sum))
+                .and_then(|(n, sq)| n.checked_sub(sq))
+                .map(|n| n as f64),
+            VarianceSums::Decimal { sum, sum_sq } => Decimal::from(self.count)
+                .checked_mul(&sum_sq)
+                .zip(sum.checked_mul(&sum))
+                .and_then(|(n, sq)| n.checked_sub(&sq))
+                .map(decimal_to_f64)
+                .transpose()?,
+            VarianceSums::Float { .. } => None,
+        };
+        let numerator = match numerator {
+            Some(numerator) => numerator,
+            None => {
+                let (sum, sum_sq) = self.sums_as_f64()?;
+                self.count as f64 * sum_sq - sum * sum
+            }
+        };
+        let count = self.count as f64;
+        let divisor = count * (count - (min_count - 1) as f64);
+        // The subtraction may produce a tiny negative number due to rounding.
+        let variance = (numerator / divisor).max(0.0);
+        match kind {
+            AggKind::StddevPop | AggKind::StddevSamp => Ok(Some(variance.sqrt())),
+            _ => Ok(Some(variance)),
+        }
+    }
+
+    fn sums_as_f64(&self) -> Result<(f64, f64)> {
+        match self.sums {
+            VarianceSums::Int { sum, sum_sq } => Ok((sum as f64, sum_sq as f64)),
+            VarianceSums::Decimal { sum, sum_sq } => {
+                Ok((decimal_to_f64(sum)?, decimal_to_f64(sum_sq)?))
+            }
+            VarianceSums::Float { sum, sum_sq } => Ok((sum, sum_sq)),
+        }
+    }
+}
+
+fn decimal_to_f64(v: Decimal) -> Result<f64> {
+    f64::try_from(v).map_err(|e| -> RwError {
+        ErrorCode::InternalError(format!("failed to convert {}: {}", v, e)).into()
+    })
+}
+
+/// `var_pop(x)`, `var_samp(x)`, `stddev_pop(x)` and `stddev_samp(x)` over numeric inputs, returned
+/// in double precision.
+pub struct VarianceAgg {
+    kind: AggKind,
+    input_col_idx: usize,
+    state: VarianceState,
+}
+
+impl VarianceAgg {
+    pub fn new(
+        kind: AggKind,
+        input_type: &DataType,
+        return_type: DataType,
+        input_col_idx: usize,
+    ) -> Result<Self> {
+        if return_type != DataType::Float64 {
+            return Err(ErrorCode::InternalError(format!(
+                "{} should return double precision, got {:?}",
+                kind, return_type
+            ))
+            .into());
+        }
+        Ok(Self {
+            kind,
+            input_col_idx,
+            state: VarianceState::new(input_type),
+        })
+    }
+
+    fn is_visible(input: &DataChunk, row_id: usize) -> Result<bool> {
+        match input.visibility() {
+            Some(visibility) => visibility.is_set(row_id),
+            None => Ok(true),
+        }
+    }
+
+    fn output_and_reset(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        self.output(builder)?;
+        self.state.reset();
+        Ok(())
+    }
+}
+
+impl Aggregator for VarianceAgg {
+    fn return_type(&self) -> DataType {
+        DataType::Float64
+    }
+
+    fn update_with_row(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        if Self::is_visible(input, row_id)? {
+            let array = input.column_at(self.input_col_idx).array_ref();
+            if let Some(scalar) = array.value_at(row_id) {
+                self.state.accumulate(scalar)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, input: &DataChunk) -> Result<()> {
+        for row_id in 0..input.capacity() {
+            self.update_with_row(input, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        let result = self
+            .state
+            .output(&self.kind)?
+            .map(|v| ScalarImpl::Float64(v.into()));
+        builder.append_datum(&result)
+    }
+
+    fn update_and_output_with_sorted_groups(
+        &mut self,
+        input: &DataChunk,
+        builder: &mut ArrayBuilderImpl,
+        groups: &EqGroups,
+    ) -> Result<()> {
+        let mut groups_iter = groups.get_starting_indices().iter().peekable();
+        for row_id in 0..input.capacity() {
+            if groups_iter.peek() == Some(&&row_id) {
+                groups_iter.next();
+                self.output_and_reset(builder)?;
+            }
+            self.update_with_row(input, row_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_common::array::column::Column;
+
+    use super::*;
+
+    fn eval(kind: AggKind, input: &[Option<i32>]) -> Result<Datum> {
+        let input = I32Array::from_slice(input)?;
+        let input_chunk = DataChunk::builder()
+            .columns(vec![Column::new(Arc::new(input.into()))])
+            .build();
+        let mut agg = VarianceAgg::new(kind, &DataType::Int32, DataType::Float64, 0)?;
+        let mut builder = DataType::Float64.create_array_builder(0)?;
+        agg.update(&input_chunk)?;
+        agg.output(&mut builder)?;
+        Ok(builder.finish()?.datum_at(0))
+    }
+
+    #[test]
+    fn test_variance_agg() -> Result<()> {
+        let input = [
+            Some(2),
+            Some(4),
+            None,
+            Some(4),
+            Some(4),
+            Some(5),
+            Some(5),
+            Some(7),
+            Some(9),
+        ];
+        let float = |v: f64| Some(ScalarImpl::Float64(v.into()));
+        assert_eq!(eval(AggKind::VarPop, &input)?, float(4.0));
+        assert_eq!(eval(AggKind::StddevPop, &input)?, float(2.0));
+        assert_eq!(eval(AggKind::VarSamp, &input)?, float(32.0 / 7.0));
+        assert_eq!(eval(AggKind::VarPop, &[Some(1)])?, float(0.0));
+        assert_eq!(eval(AggKind::VarSamp, &[Some(1)])?, None);
+        assert_eq!(eval(AggKind::StddevPop, &[None])?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_variance_state_retract() -> Result<()> {
+        for input_type in [DataType::Int64, DataType::Decimal, DataType::Float64] {
+            let value = |v: i64| match input_type {
+                DataType::Int64 => ScalarImpl::Int64(v),
+                DataType::Decimal => ScalarImpl::Decimal(Decimal::from(v)),
+                _ => ScalarImpl::Float64((v as f64).into()),
+            };
+            let mut state = VarianceState::new(&input_type);
+            state.accumulate(value(1).as_scalar_ref_impl())?;
+            state.accumulate(value(3).as_scalar_ref_impl())?;
+            state.retract(value(1).as_scalar_ref_impl())?;
+            assert_eq!(state.output(&AggKind::VarPop)?, Some(0.0));
+            state.retract(value(3).as_scalar_ref_impl())?;
+            assert_eq!(state, VarianceState::new(&input_type));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_variance_state_exact() -> Result<()> {
+        // Large integers whose squares lose precision as `f64`, retracted one by one.
+        let mut state = VarianceState::new(&DataType::Int64);
+        let large = 1i64 << 40;
+        for v in [large + 1, large + 3, large + 100] {
+            state.accumulate(ScalarRefImpl::Int64(v))?;
+        }
+        state.retract(ScalarRefImpl::Int64(large + 100))?;
+        assert_eq!(state.output(&AggKind::VarSamp)?, Some(2.0));
+
+        let mut state = VarianceState::new(&DataType::Int64);
+        state.accumulate(ScalarRefImpl::Int64(i64::MAX))?;
+        assert!(state.accumulate(ScalarRefImpl::Int64(i64::MAX)).is_err());
+        Ok(())
+    }
+}
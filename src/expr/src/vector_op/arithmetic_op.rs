@@ -23,7 +23,7 @@ use risingwave_common::error::ErrorCode::{InternalError, NumericValueOutOfRange}
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper};
 
-use super::cast::date_to_timestamp;
+use super::cast::{date_to_timestamp, timestampz_to_utc_datetime, utc_datetime_to_timestampz};
 
 #[inline(always)]
 pub fn general_add<T1, T2, T3>(l: T1, r: T2) -> Result<T3>
@@ -131,7 +131,7 @@ fn is_leap_year(year: i32) -> bool {
 }
 
 /// return the days of the `year-month`
-fn get_mouth_days(year: i32, month: usize) -> i32 {
+pub(super) fn get_mouth_days(year: i32, month: usize) -> i32 {
     if is_leap_year(year) {
         LEAP_DAYS[month]
     } else {
@@ -239,6 +239,33 @@ pub fn timestamp_interval_sub<T1, T2, T3>(
     interval_timestamp_add::<T1, T2, T3>(r.negative(), l)
 }
 
+#[inline(always)]
+pub fn timestampz_timestampz_sub<T1, T2, T3>(l: i64, r: i64) -> Result<IntervalUnit> {
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+    let ms = l
+        .checked_sub(r)
+        .ok_or_else(|| RwError::from(NumericValueOutOfRange))?
+        / 1000;
+    Ok(IntervalUnit::new(0, (ms / DAY_MS) as i32, ms % DAY_MS))
+}
+
+#[inline(always)]
+pub fn interval_timestampz_add<T1, T2, T3>(l: IntervalUnit, r: i64) -> Result<i64> {
+    let datetime = NaiveDateTimeWrapper::new(timestampz_to_utc_datetime(r)?);
+    let result = interval_timestamp_add::<T1, T2, T3>(l, datetime)?;
+    Ok(utc_datetime_to_timestampz(result.0))
+}
+
+#[inline(always)]
+pub fn timestampz_interval_add<T1, T2, T3>(l: i64, r: IntervalUnit) -> Result<i64> {
+    interval_timestampz_add::<T1, T2, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn timestampz_interval_sub<T1, T2, T3>(l: i64, r: IntervalUnit) -> Result<i64> {
+    interval_timestampz_add::<T1, T2, T3>(r.negative(), l)
+}
+
 #[inline(always)]
 pub fn interval_int_mul<T1, T2, T3>(l: IntervalUnit, r: T2) -> Result<IntervalUnit>
 where
@@ -260,9 +287,9 @@ where
 mod tests {
     use std::str::FromStr;
 
-    use risingwave_common::types::Decimal;
+    use risingwave_common::types::{Decimal, IntervalUnit};
 
-    use crate::vector_op::arithmetic_op::general_add;
+    use crate::vector_op::arithmetic_op::*;
 
     #[test]
     fn test() {
@@ -271,4 +298,29 @@ mod tests {
             Decimal::from_str("2").unwrap()
         );
     }
+
+    #[test]
+    fn test_timestampz_interval() {
+        // 2022-01-31 12:00:00 UTC
+        let timestampz = 1_643_630_400_000_000;
+        // 2022-02-28 12:00:00 UTC
+        let expected = 1_646_049_600_000_000;
+        let one_month = IntervalUnit::from_month(1);
+        assert_eq!(
+            timestampz_interval_add::<i64, IntervalUnit, i64>(timestampz, one_month).unwrap(),
+            expected
+        );
+        assert_eq!(
+            timestampz_interval_sub::<i64, IntervalUnit, i64>(
+                timestampz,
+                IntervalUnit::from_days(1)
+            )
+            .unwrap(),
+            timestampz - 24 * 3600 * 1_000_000
+        );
+        assert_eq!(
+            timestampz_timestampz_sub::<i64, i64, IntervalUnit>(expected, timestampz).unwrap(),
+            IntervalUnit::from_days(28)
+        );
+    }
 }
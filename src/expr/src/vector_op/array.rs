@@ -0,0 +1,216 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functions on `list` values. The element type is only known at runtime, so each function
+//! takes the row's argument datums and is evaluated by `ArrayFunctionExpression`.
+
+use risingwave_common::array::{ListRef, ListValue};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{Datum, DatumRef, ScalarImpl, ScalarRefImpl, ToOwnedDatum};
+
+fn as_list<'a>(datum: DatumRef<'a>) -> Result<Option<ListRef<'a>>> {
+    match datum {
+        None => Ok(None),
+        Some(ScalarRefImpl::List(list)) => Ok(Some(list)),
+        Some(other) => {
+            Err(ErrorCode::InternalError(format!("expect list argument, got {:?}", other)).into())
+        }
+    }
+}
+
+fn as_index(datum: DatumRef<'_>) -> Result<Option<i32>> {
+    match datum {
+        None => Ok(None),
+        Some(ScalarRefImpl::Int32(i)) => Ok(Some(i)),
+        Some(other) => {
+            Err(ErrorCode::InternalError(format!("expect int32 argument, got {:?}", other)).into())
+        }
+    }
+}
+
+/// `ARRAY[e1, e2, ...]`.
+pub fn array_constructor(args: &[DatumRef<'_>]) -> Result<Datum> {
+    Ok(Some(ScalarImpl::List(ListValue::new(
+        args.iter().map(|d| d.to_owned_datum()).collect(),
+    ))))
+}
+
+/// `array[index]`. The index is 1-based; out-of-range subscripts yield NULL.
+pub fn array_access(args: &[DatumRef<'_>]) -> Result<Datum> {
+    let (list, index) = match (as_list(args[0])?, as_index(args[1])?) {
+        (Some(list), Some(index)) => (list, index),
+        _ => return Ok(None),
+    };
+    if index < 1 {
+        return Ok(None);
+    }
+    Ok(list
+        .values_ref()
+        .get(index as usize - 1)
+        .and_then(|d| d.to_owned_datum()))
+}
+
+/// `array_length(array [, dimension])`. Only the first dimension is supported. Like PostgreSQL,
+/// an empty array has no dimension and yields NULL.
+pub fn array_length(args: &[DatumRef<'_>]) -> Result<Datum> {
+    let list = match as_list(args[0])? {
+        Some(list) => list,
+        None => return Ok(None),
+    };
+    if let Some(dim) = args.get(1) {
+        if as_index(*dim)? != Some(1) {
+            return Ok(None);
+        }
+    }
+    let len = list.values_ref().len();
+    Ok((len > 0).then(|| ScalarImpl::Int32(len as i32)))
+}
+
+/// `array_cat(array, array)`. A NULL argument is treated as an empty array.
+pub fn array_cat(args: &[DatumRef<'_>]) -> Result<Datum> {
+    let (left, right) = (as_list(args[0])?, as_list(args[1])?);
+    if left.is_none() && right.is_none() {
+        return Ok(None);
+    }
+    let values = left
+        .into_iter()
+        .chain(right)
+        .flat_map(|list| list.values_ref())
+        .map(|d| d.to_owned_datum())
+        .collect();
+    Ok(Some(ScalarImpl::List(ListValue::new(values))))
+}
+
+/// `array_append(array, element)`. A NULL array is treated as an empty array.
+pub fn array_append(args: &[DatumRef<'_>]) -> Result<Datum> {
+    let mut values: Vec<Datum> = match as_list(args[0])? {
+        Some(list) => list
+            .values_ref()
+            .into_iter()
+            .map(|d| d.to_owned_datum())
+            .collect(),
+        None => vec![],
+    };
+    values.push(args[1].to_owned_datum());
+    Ok(Some(ScalarImpl::List(ListValue::new(values))))
+}
+
+/// `array_position(array, element)`. Returns the 1-based position of the first occurrence of the
+/// element, compared with `IS NOT DISTINCT FROM` semantics, or NULL if it is not found.
+pub fn array_position(args: &[DatumRef<'_>]) -> Result<Datum> {
+    let list = match as_list(args[0])? {
+        Some(list) => list,
+        None => return Ok(None),
+    };
+    Ok(list
+        .values_ref()
+        .iter()
+        .position(|d| *d == args[1])
+        .map(|pos| ScalarImpl::Int32(pos as i32 + 1)))
+}
+
+/// `element = ANY(array)`. Follows the three-valued logic of PostgreSQL: the result is NULL if
+/// no element is equal but some comparison yields NULL.
+pub fn array_any_equal(args: &[DatumRef<'_>]) -> Result<Datum> {
+    let list = match as_list(args[1])? {
+        Some(list) => list,
+        None => return Ok(None),
+    };
+    let mut has_null = false;
+    for value in list.values_ref() {
+        match (args[0], value) {
+            (Some(l), Some(r)) if l == r => return Ok(Some(ScalarImpl::Bool(true))),
+            (Some(_), Some(_)) => {}
+            _ => has_null = true,
+        }
+    }
+    Ok((!has_null).then(|| ScalarImpl::Bool(false)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: Vec<Option<i32>>) -> ScalarImpl {
+        ScalarImpl::List(ListValue::new(
+            values
+                .into_iter()
+                .map(|v| v.map(ScalarImpl::Int32))
+                .collect(),
+        ))
+    }
+
+    fn int(v: i32) -> ScalarImpl {
+        ScalarImpl::Int32(v)
+    }
+
+    fn refs(args: &[Datum]) -> Vec<DatumRef<'_>> {
+        args.iter()
+            .map(|d| d.as_ref().map(|s| s.as_scalar_ref_impl()))
+            .collect()
+    }
+
+    #[test]
+    fn test_array_access_and_length() {
+        let arr = list(vec![Some(1), None, Some(3)]);
+        let args = [Some(arr.clone()), Some(int(3))];
+        assert_eq!(array_access(&refs(&args)).unwrap(), Some(int(3)));
+        let args = [Some(arr.clone()), Some(int(4))];
+        assert_eq!(array_access(&refs(&args)).unwrap(), None);
+        let args = [Some(arr.clone()), Some(int(0))];
+        assert_eq!(array_access(&refs(&args)).unwrap(), None);
+
+        assert_eq!(array_length(&refs(&[Some(arr)])).unwrap(), Some(int(3)));
+        assert_eq!(array_length(&refs(&[Some(list(vec![]))])).unwrap(), None);
+    }
+
+    #[test]
+    fn test_array_cat_append_position() {
+        let args = [Some(list(vec![Some(1)])), Some(list(vec![Some(2), None]))];
+        assert_eq!(
+            array_cat(&refs(&args)).unwrap(),
+            Some(list(vec![Some(1), Some(2), None]))
+        );
+        let args = [None, Some(int(2))];
+        assert_eq!(
+            array_append(&refs(&args)).unwrap(),
+            Some(list(vec![Some(2)]))
+        );
+        let args = [Some(list(vec![Some(1), None, Some(1)])), None];
+        assert_eq!(array_position(&refs(&args)).unwrap(), Some(int(2)));
+        let args = [Some(list(vec![Some(1), None, Some(1)])), Some(int(1))];
+        assert_eq!(array_position(&refs(&args)).unwrap(), Some(int(1)));
+    }
+
+    #[test]
+    fn test_array_any_equal() {
+        let args = [Some(int(2)), Some(list(vec![Some(1), Some(2)]))];
+        assert_eq!(
+            array_any_equal(&refs(&args)).unwrap(),
+            Some(ScalarImpl::Bool(true))
+        );
+        let args = [Some(int(3)), Some(list(vec![Some(1), Some(2)]))];
+        assert_eq!(
+            array_any_equal(&refs(&args)).unwrap(),
+            Some(ScalarImpl::Bool(false))
+        );
+        let args = [Some(int(3)), Some(list(vec![Some(1), None]))];
+        assert_eq!(array_any_equal(&refs(&args)).unwrap(), None);
+        let args = [None, Some(list(vec![]))];
+        assert_eq!(
+            array_any_equal(&refs(&args)).unwrap(),
+            Some(ScalarImpl::Bool(false))
+        );
+    }
+}
@@ -84,6 +84,26 @@ pub fn str_to_timestampz(elem: &str) -> Result<i64> {
         .map_err(|e| RwError::from(ParseError(Box::new(e))))
 }
 
+/// Converts a `timestamp with time zone`, represented as microseconds since the Unix epoch, into
+/// the corresponding date time in UTC.
+#[inline(always)]
+pub fn timestampz_to_utc_datetime(elem: i64) -> Result<NaiveDateTime> {
+    let secs = elem.div_euclid(1_000_000);
+    let nsecs = elem.rem_euclid(1_000_000) * 1000;
+    NaiveDateTime::from_timestamp_opt(secs, nsecs as u32).ok_or_else(|| {
+        RwError::from(InternalError(format!(
+            "timestamp with time zone {} out of range",
+            elem
+        )))
+    })
+}
+
+/// Inverse of [`timestampz_to_utc_datetime`].
+#[inline(always)]
+pub fn utc_datetime_to_timestampz(elem: NaiveDateTime) -> i64 {
+    elem.timestamp() * 1_000_000 + elem.timestamp_subsec_micros() as i64
+}
+
 #[inline(always)]
 pub fn str_parse<T>(elem: &str) -> Result<T>
 where
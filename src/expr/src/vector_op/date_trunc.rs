@@ -0,0 +1,126 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::Result;
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+use super::cast::{timestampz_to_utc_datetime, utc_datetime_to_timestampz};
+
+fn truncate(field: &str, time: NaiveDateTime) -> Result<NaiveDateTime> {
+    let date = time.date();
+    let year = date.year();
+    let first_day_of_year = |year: i32| NaiveDate::from_ymd(year, 1, 1);
+    let truncated = match field.to_lowercase().as_str() {
+        "microseconds" => NaiveDateTime::new(
+            date,
+            NaiveTime::from_hms_nano(
+                time.hour(),
+                time.minute(),
+                time.second(),
+                time.nanosecond() / 1_000 * 1_000,
+            ),
+        ),
+        "milliseconds" => NaiveDateTime::new(
+            date,
+            NaiveTime::from_hms_nano(
+                time.hour(),
+                time.minute(),
+                time.second(),
+                time.nanosecond() / 1_000_000 * 1_000_000,
+            ),
+        ),
+        "second" => date.and_hms(time.hour(), time.minute(), time.second()),
+        "minute" => date.and_hms(time.hour(), time.minute(), 0),
+        "hour" => date.and_hms(time.hour(), 0, 0),
+        "day" => date.and_hms(0, 0, 0),
+        // Weeks start on Monday, as in ISO 8601.
+        "week" => {
+            (date - Duration::days(date.weekday().num_days_from_monday() as i64)).and_hms(0, 0, 0)
+        }
+        "month" => NaiveDate::from_ymd(year, date.month(), 1).and_hms(0, 0, 0),
+        "quarter" => NaiveDate::from_ymd(year, (date.month() - 1) / 3 * 3 + 1, 1).and_hms(0, 0, 0),
+        "year" => first_day_of_year(year).and_hms(0, 0, 0),
+        "decade" => first_day_of_year(year - year.rem_euclid(10)).and_hms(0, 0, 0),
+        // Centuries and millenniums start with year 1 rather than year 0.
+        "century" => first_day_of_year((year - 1).div_euclid(100) * 100 + 1).and_hms(0, 0, 0),
+        "millennium" => first_day_of_year((year - 1).div_euclid(1000) * 1000 + 1).and_hms(0, 0, 0),
+        _ => {
+            return Err(
+                InvalidInputSyntax(format!("timestamp units \"{}\" not recognized", field)).into(),
+            )
+        }
+    };
+    Ok(truncated)
+}
+
+/// Truncates `timestamp` to the precision specified by `field`, e.g. `date_trunc('hour', ts)`.
+#[inline(always)]
+pub fn date_trunc_timestamp(
+    field: &str,
+    timestamp: NaiveDateTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    truncate(field, timestamp.0).map(NaiveDateTimeWrapper::new)
+}
+
+/// Same as [`date_trunc_timestamp`], but for `timestamp with time zone`. The truncation is done
+/// in UTC.
+#[inline(always)]
+pub fn date_trunc_timestampz(field: &str, timestampz: i64) -> Result<i64> {
+    truncate(field, timestampz_to_utc_datetime(timestampz)?).map(utc_datetime_to_timestampz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trunc(field: &str, time: &str) -> String {
+        let time = NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S%.f").unwrap();
+        date_trunc_timestamp(field, NaiveDateTimeWrapper::new(time))
+            .unwrap()
+            .0
+            .format("%Y-%m-%d %H:%M:%S%.f")
+            .to_string()
+    }
+
+    #[test]
+    fn test_date_trunc() {
+        let time = "2022-05-18 13:45:21.123456";
+        assert_eq!(trunc("milliseconds", time), "2022-05-18 13:45:21.123");
+        assert_eq!(trunc("second", time), "2022-05-18 13:45:21");
+        assert_eq!(trunc("minute", time), "2022-05-18 13:45:00");
+        assert_eq!(trunc("HOUR", time), "2022-05-18 13:00:00");
+        assert_eq!(trunc("day", time), "2022-05-18 00:00:00");
+        assert_eq!(trunc("week", time), "2022-05-16 00:00:00");
+        assert_eq!(trunc("month", time), "2022-05-01 00:00:00");
+        assert_eq!(trunc("quarter", time), "2022-04-01 00:00:00");
+        assert_eq!(trunc("year", time), "2022-01-01 00:00:00");
+        assert_eq!(trunc("decade", time), "2020-01-01 00:00:00");
+        assert_eq!(trunc("century", time), "2001-01-01 00:00:00");
+        assert_eq!(trunc("millennium", time), "2001-01-01 00:00:00");
+        assert!(date_trunc_timestamp("fortnight", NaiveDateTimeWrapper::default()).is_err());
+    }
+
+    #[test]
+    fn test_date_trunc_timestampz() {
+        // 2022-05-18 13:45:21 UTC
+        let timestampz = 1_652_881_521_000_000;
+        // 2022-05-18 13:00:00 UTC
+        assert_eq!(
+            date_trunc_timestampz("hour", timestampz).unwrap(),
+            1_652_878_800_000_000
+        );
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::array::{JsonbRef, JsonbVal};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::ScalarRef;
+
+#[inline(always)]
+pub fn str_to_jsonb(elem: &str) -> Result<JsonbVal> {
+    elem.parse()
+}
+
+#[inline(always)]
+pub fn jsonb_to_str(elem: JsonbRef<'_>) -> Result<String> {
+    Ok(elem.to_string())
+}
+
+/// `jsonb -> text`. Returns NULL if the jsonb is not an object or the key does not exist.
+#[inline(always)]
+pub fn jsonb_object_field(j: Option<JsonbRef<'_>>, key: Option<&str>) -> Result<Option<JsonbVal>> {
+    Ok(match (j, key) {
+        (Some(j), Some(key)) => j.access_object_field(key).map(|v| v.to_owned_scalar()),
+        _ => None,
+    })
+}
+
+/// `jsonb -> int`. Negative indexes count from the end of the array.
+#[inline(always)]
+pub fn jsonb_array_element(j: Option<JsonbRef<'_>>, idx: Option<i32>) -> Result<Option<JsonbVal>> {
+    Ok(match (j, idx) {
+        (Some(j), Some(idx)) => j
+            .access_array_element(idx as i64)
+            .map(|v| v.to_owned_scalar()),
+        _ => None,
+    })
+}
+
+/// `jsonb ->> text`. Same as `->` but the result is converted to text.
+#[inline(always)]
+pub fn jsonb_object_field_text(
+    j: Option<JsonbRef<'_>>,
+    key: Option<&str>,
+) -> Result<Option<String>> {
+    Ok(match (j, key) {
+        (Some(j), Some(key)) => j.access_object_field(key).and_then(|v| v.to_text()),
+        _ => None,
+    })
+}
+
+/// `jsonb ->> int`.
+#[inline(always)]
+pub fn jsonb_array_element_text(
+    j: Option<JsonbRef<'_>>,
+    idx: Option<i32>,
+) -> Result<Option<String>> {
+    Ok(match (j, idx) {
+        (Some(j), Some(idx)) => j.access_array_element(idx as i64).and_then(|v| v.to_text()),
+        _ => None,
+    })
+}
+
+/// `jsonb @> jsonb`.
+#[inline(always)]
+pub fn jsonb_contains(left: JsonbRef<'_>, right: JsonbRef<'_>) -> Result<bool> {
+    Ok(left.contains(right))
+}
+
+/// Parses a `text[]` literal of `#>` such as `'{a,0,b}'` into path elements.
+pub fn parse_jsonb_path(path: &str) -> Result<Vec<String>> {
+    let inner = path
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| {
+            ErrorCode::InvalidInputSyntax(format!("malformed array literal: \"{}\"", path))
+        })?;
+    if inner.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    Ok(inner
+        .split(',')
+        .map(|s| s.trim().trim_matches('"').to_string())
+        .collect_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::Scalar;
+
+    use super::*;
+
+    #[test]
+    fn test_jsonb_access() {
+        let j: JsonbVal = r#"{"a": [1, {"b": "foo"}], "c": null}"#.parse().unwrap();
+        let j = Some(j.as_scalar_ref());
+
+        let a = jsonb_object_field(j, Some("a")).unwrap().unwrap();
+        assert_eq!(a.to_string(), r#"[1, {"b": "foo"}]"#);
+        let last = jsonb_array_element(Some(a.as_scalar_ref()), Some(-1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            jsonb_object_field_text(Some(last.as_scalar_ref()), Some("b")).unwrap(),
+            Some("foo".to_string())
+        );
+        assert_eq!(
+            jsonb_array_element_text(Some(a.as_scalar_ref()), Some(0)).unwrap(),
+            Some("1".to_string())
+        );
+        assert_eq!(jsonb_object_field_text(j, Some("c")).unwrap(), None);
+        assert_eq!(jsonb_object_field(j, Some("d")).unwrap(), None);
+        assert_eq!(jsonb_array_element(j, Some(0)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_jsonb_path() {
+        assert_eq!(parse_jsonb_path("{a,0}").unwrap(), vec!["a", "0"]);
+        assert!(parse_jsonb_path("{}").unwrap().is_empty());
+        assert!(parse_jsonb_path("a,0").is_err());
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::error::Result;
+use risingwave_common::types::IntervalUnit;
+
+/// Adjusts `interval` using 30-day months and 24-hour days. See
+/// [`IntervalUnit::justify_interval`].
+#[inline(always)]
+pub fn justify_interval(interval: IntervalUnit) -> Result<IntervalUnit> {
+    Ok(interval.justify_interval())
+}
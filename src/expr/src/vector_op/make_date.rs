@@ -0,0 +1,50 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::NaiveDate;
+use risingwave_common::error::ErrorCode::InvalidInputSyntax;
+use risingwave_common::error::Result;
+use risingwave_common::types::NaiveDateWrapper;
+
+/// Creates a date from year, month and day fields, e.g. `make_date(2022, 5, 18)`.
+#[inline(always)]
+pub fn make_date(year: i32, month: i32, day: i32) -> Result<NaiveDateWrapper> {
+    u32::try_from(month)
+        .ok()
+        .zip(u32::try_from(day).ok())
+        .and_then(|(month, day)| NaiveDate::from_ymd_opt(year, month, day))
+        .map(NaiveDateWrapper::new)
+        .ok_or_else(|| {
+            InvalidInputSyntax(format!(
+                "date field value out of range: {}-{}-{}",
+                year, month, day
+            ))
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_date() {
+        assert_eq!(
+            make_date(2022, 5, 18).unwrap(),
+            NaiveDateWrapper::new(NaiveDate::from_ymd(2022, 5, 18))
+        );
+        assert!(make_date(2022, 2, 30).is_err());
+        assert!(make_date(2022, -1, 1).is_err());
+    }
+}
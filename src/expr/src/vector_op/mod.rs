@@ -12,22 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod age;
 pub mod agg;
 pub mod arithmetic_op;
+pub mod array;
 pub mod ascii;
 pub mod cast;
 pub mod cmp;
 pub mod conjunction;
+pub mod date_trunc;
 pub mod extract;
+pub mod jsonb;
+pub mod justify_interval;
 pub mod length;
 pub mod like;
 pub mod lower;
 pub mod ltrim;
+pub mod make_date;
 pub mod position;
 pub mod replace;
 pub mod round;
 pub mod rtrim;
 pub mod substr;
+pub mod to_char;
+pub mod to_timestamp;
 pub mod translate;
 pub mod trim;
 pub mod tumble;
@@ -0,0 +1,121 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::error::Result;
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+use super::cast::timestampz_to_utc_datetime;
+
+/// PG template patterns and their `chrono` counterparts. Longer patterns that share a prefix with
+/// shorter ones must come first.
+///
+/// See <https://www.postgresql.org/docs/current/functions-formatting.html>.
+const PATTERNS: &[(&str, &str)] = &[
+    ("HH24", "%H"),
+    ("HH12", "%I"),
+    ("HH", "%I"),
+    ("MI", "%M"),
+    ("SS", "%S"),
+    ("MS", "%3f"),
+    ("US", "%6f"),
+    ("YYYY", "%Y"),
+    ("YY", "%y"),
+    ("MM", "%m"),
+    ("DDD", "%j"),
+    ("DD", "%d"),
+    ("Month", "%B"),
+    ("Mon", "%b"),
+    ("Day", "%A"),
+    ("Dy", "%a"),
+    ("AM", "%p"),
+    ("PM", "%p"),
+];
+
+/// Compiles a PG formatting template, e.g. `YYYY-MM-DD HH24:MI:SS`, into a `chrono` format
+/// string. Text in double quotes is copied literally.
+pub fn compile_pattern_to_chrono(tmpl: &str) -> String {
+    let mut chrono_fmt = String::with_capacity(tmpl.len() * 2);
+    let mut rest = tmpl;
+    'outer: while let Some(c) = rest.chars().next() {
+        for (pattern, replacement) in PATTERNS {
+            if let Some(remaining) = rest.strip_prefix(pattern) {
+                chrono_fmt.push_str(replacement);
+                rest = remaining;
+                continue 'outer;
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '"' => {
+                let end = rest.find('"').unwrap_or(rest.len());
+                chrono_fmt.push_str(&rest[..end].replace('%', "%%"));
+                rest = rest.get(end + 1..).unwrap_or_default();
+            }
+            '%' => chrono_fmt.push_str("%%"),
+            c => chrono_fmt.push(c),
+        }
+    }
+    chrono_fmt
+}
+
+/// Formats `timestamp` according to the PG template `tmpl`.
+#[inline(always)]
+pub fn to_char_timestamp(timestamp: NaiveDateTimeWrapper, tmpl: &str) -> Result<String> {
+    let chrono_fmt = compile_pattern_to_chrono(tmpl);
+    Ok(timestamp.0.format(&chrono_fmt).to_string())
+}
+
+/// Same as [`to_char_timestamp`], but for `timestamp with time zone`. The result is in UTC.
+#[inline(always)]
+pub fn to_char_timestampz(timestampz: i64, tmpl: &str) -> Result<String> {
+    to_char_timestamp(
+        NaiveDateTimeWrapper::new(timestampz_to_utc_datetime(timestampz)?),
+        tmpl,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    #[test]
+    fn test_compile_pattern() {
+        assert_eq!(
+            compile_pattern_to_chrono("YYYY-MM-DD HH24:MI:SS.MS"),
+            "%Y-%m-%d %H:%M:%S.%3f"
+        );
+        assert_eq!(
+            compile_pattern_to_chrono("Dy, DD Mon YYYY \"at\" HH12 AM 100%"),
+            "%a, %d %b %Y at %I %p 100%%"
+        );
+    }
+
+    #[test]
+    fn test_to_char() {
+        let time = NaiveDateTimeWrapper::new(
+            NaiveDateTime::parse_from_str("2022-05-18 13:45:21.123", "%Y-%m-%d %H:%M:%S%.f")
+                .unwrap(),
+        );
+        assert_eq!(
+            to_char_timestamp(time, "YYYY/MM/DD HH24:MI:SS.MS").unwrap(),
+            "2022/05/18 13:45:21.123"
+        );
+        assert_eq!(
+            to_char_timestamp(time, "Day, Month DDD HH12PM").unwrap(),
+            "Wednesday, May 138 01PM"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use risingwave_common::error::ErrorCode::ParseError;
+use risingwave_common::error::{Result, RwError};
+
+use super::cast::utc_datetime_to_timestampz;
+use super::to_char::compile_pattern_to_chrono;
+
+/// Parses `s` with the PG template `tmpl` into a `timestamp with time zone`. The input is
+/// interpreted in UTC. Templates without time fields, e.g. `YYYY-MM-DD`, yield midnight.
+#[inline(always)]
+pub fn to_timestamp(s: &str, tmpl: &str) -> Result<i64> {
+    let chrono_fmt = compile_pattern_to_chrono(tmpl);
+    let datetime = match NaiveDateTime::parse_from_str(s, &chrono_fmt) {
+        Ok(datetime) => datetime,
+        Err(e) => NaiveDate::parse_from_str(s, &chrono_fmt)
+            .map(|date| date.and_hms(0, 0, 0))
+            .map_err(|_| RwError::from(ParseError(Box::new(e))))?,
+    };
+    Ok(utc_datetime_to_timestampz(datetime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_timestamp() {
+        assert_eq!(
+            to_timestamp("2022-05-18 13:45:21", "YYYY-MM-DD HH24:MI:SS").unwrap(),
+            1_652_881_521_000_000
+        );
+        assert_eq!(
+            to_timestamp("18/05/2022", "DD/MM/YYYY").unwrap(),
+            1_652_832_000_000_000
+        );
+        assert!(to_timestamp("2022-05-18", "HH24:MI").is_err());
+    }
+}
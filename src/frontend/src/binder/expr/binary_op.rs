@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_expr::vector_op::jsonb::parse_jsonb_path;
 use risingwave_sqlparser::ast::{BinaryOperator, Expr};
 
 use crate::binder::Binder;
-use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall};
+use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall, Literal};
 
 impl Binder {
     pub(super) fn bind_binary_op(
@@ -25,6 +28,9 @@ impl Binder {
         op: BinaryOperator,
         right: Expr,
     ) -> Result<FunctionCall> {
+        if let Expr::AnyOp(array) = right {
+            return self.bind_any(left, op, *array);
+        }
         let bound_left = self.bind_expr(left)?;
         let bound_right = self.bind_expr(right)?;
         let func_type = match op {
@@ -43,6 +49,14 @@ impl Binder {
             BinaryOperator::Or => ExprType::Or,
             BinaryOperator::Like => ExprType::Like,
             BinaryOperator::NotLike => return self.bind_not_like(bound_left, bound_right),
+            BinaryOperator::PGJsonAccess => ExprType::JsonbAccessInner,
+            BinaryOperator::PGJsonAccessAsText => ExprType::JsonbAccessStr,
+            BinaryOperator::PGJsonPathAccess => {
+                return self.bind_jsonb_path_access(bound_left, bound_right)
+            }
+            BinaryOperator::PGJsonContains => {
+                return self.bind_jsonb_contains(bound_left, bound_right)
+            }
             _ => return Err(ErrorCode::NotImplemented(format!("{:?}", op), 112.into()).into()),
         };
         FunctionCall::new_or_else(func_type, vec![bound_left, bound_right], |inputs| {
@@ -50,6 +64,31 @@ impl Binder {
         })
     }
 
+    /// Binds `element = ANY(array)`. Other comparison operators are not supported yet.
+    fn bind_any(&mut self, left: Expr, op: BinaryOperator, array: Expr) -> Result<FunctionCall> {
+        if op != BinaryOperator::Eq {
+            return Err(ErrorCode::NotImplemented(format!("{:?} ANY", op), None.into()).into());
+        }
+        let left = self.bind_expr(left)?;
+        let array = self.bind_expr(array)?;
+        let element_type = match array.return_type() {
+            DataType::List { datatype } => *datatype,
+            t => {
+                return Err(ErrorCode::BindError(format!(
+                    "op ANY/ALL (array) requires array on right side, got {:?}",
+                    t
+                ))
+                .into())
+            }
+        };
+        let left = left.cast_implicit(element_type)?;
+        Ok(FunctionCall::new_with_return_type(
+            ExprType::ArrayAnyEqual,
+            vec![left, array],
+            DataType::Boolean,
+        ))
+    }
+
     /// Apply a NOT on top of LIKE.
     fn bind_not_like(&mut self, left: ExprImpl, right: ExprImpl) -> Result<FunctionCall> {
         Ok(FunctionCall::new(
@@ -64,6 +103,65 @@ impl Binder {
         .unwrap())
     }
 
+    /// Binds `jsonb #> text[]` to `jsonb_extract_path`. As there's no array type yet, the path must
+    /// be a string literal in array form, e.g. `'{a,0}'`.
+    fn bind_jsonb_path_access(&mut self, left: ExprImpl, right: ExprImpl) -> Result<FunctionCall> {
+        let path = match &right {
+            ExprImpl::Literal(literal) if right.return_type() == DataType::Varchar => {
+                match literal.get_data() {
+                    Some(ScalarImpl::Utf8(path)) => parse_jsonb_path(path)?
+                        .into_iter()
+                        .map(|p| Literal::new(Some(ScalarImpl::Utf8(p)), DataType::Varchar).into())
+                        .collect(),
+                    _ => vec![right],
+                }
+            }
+            _ => {
+                return Err(ErrorCode::NotImplemented(
+                    "#> with a non-constant path".to_string(),
+                    None.into(),
+                )
+                .into())
+            }
+        };
+        Self::bind_jsonb_extract_path(std::iter::once(left).chain(path).collect())
+    }
+
+    /// `jsonb_extract_path(from_json jsonb, VARIADIC path_elems text[])`.
+    pub(super) fn bind_jsonb_extract_path(inputs: Vec<ExprImpl>) -> Result<FunctionCall> {
+        let valid = inputs.first().map(|j| j.return_type()) == Some(DataType::Jsonb)
+            && inputs[1..]
+                .iter()
+                .all(|p| p.return_type() == DataType::Varchar);
+        if !valid {
+            return Err(ErrorCode::BindError(format!(
+                "jsonb_extract_path does not support {:?}",
+                inputs.iter().map(|i| i.return_type()).collect_vec()
+            ))
+            .into());
+        }
+        Ok(FunctionCall::new_with_return_type(
+            ExprType::JsonbExtractPath,
+            inputs,
+            DataType::Jsonb,
+        ))
+    }
+
+    /// Binds `jsonb @> jsonb`. A string literal on either side is taken as jsonb, like an
+    /// `unknown`-typed literal in PG.
+    fn bind_jsonb_contains(&mut self, left: ExprImpl, right: ExprImpl) -> Result<FunctionCall> {
+        let to_jsonb = |e: ExprImpl| match e {
+            ExprImpl::Literal(_) if e.return_type() == DataType::Varchar => {
+                e.cast_explicit(DataType::Jsonb)
+            }
+            _ => Ok(e),
+        };
+        let inputs = vec![to_jsonb(left)?, to_jsonb(right)?];
+        FunctionCall::new_or_else(ExprType::JsonbContains, inputs, |inputs| {
+            Self::err_unsupported_binary_op(BinaryOperator::PGJsonContains, inputs)
+        })
+    }
+
     fn err_unsupported_binary_op(op: BinaryOperator, inputs: &[ExprImpl]) -> RwError {
         let bound_left = inputs.get(0).unwrap();
         let bound_right = inputs.get(1).unwrap();
@@ -14,16 +14,25 @@
 
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
 use risingwave_expr::expr::AggKind;
-use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr};
+use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, OrderByExpr};
 
 use crate::binder::bind_context::Clause;
 use crate::binder::Binder;
 use crate::expr::{AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal};
 
+/// Arguments of `make_interval`, in positional order.
+const MAKE_INTERVAL_FIELDS: [&str; 7] =
+    ["years", "months", "weeks", "days", "hours", "mins", "secs"];
+
 impl Binder {
     pub(super) fn bind_function(&mut self, f: Function) -> Result<ExprImpl> {
+        if f.name.0.len() == 1 && f.name.0[0].value.eq_ignore_ascii_case("make_interval") {
+            // `make_interval` is the only function accepting named arguments.
+            return self.bind_make_interval(f.args);
+        }
+
         let mut inputs = f
             .args
             .into_iter()
@@ -40,10 +49,51 @@ impl Binder {
                 "min" => Some(AggKind::Min),
                 "max" => Some(AggKind::Max),
                 "avg" => Some(AggKind::Avg),
+                "array_agg" => Some(AggKind::ArrayAgg),
+                "stddev_pop" => Some(AggKind::StddevPop),
+                "stddev_samp" | "stddev" => Some(AggKind::StddevSamp),
+                "var_pop" => Some(AggKind::VarPop),
+                "var_samp" | "variance" => Some(AggKind::VarSamp),
+                "bool_and" | "every" => Some(AggKind::BoolAnd),
+                "bool_or" => Some(AggKind::BoolOr),
+                "bit_and" => Some(AggKind::BitAnd),
+                "bit_or" => Some(AggKind::BitOr),
+                "first_value" => Some(AggKind::FirstValue),
+                "last_value" => Some(AggKind::LastValue),
                 _ => None,
             };
-            if let Some(kind) = agg_kind {
+            if let Some(mut kind) = agg_kind {
                 self.ensure_aggregate_allowed()?;
+                if matches!(kind, AggKind::FirstValue | AggKind::LastValue) {
+                    // The ordering key is passed as the second input. A descending order turns
+                    // `first_value` into `last_value` and vice versa.
+                    let [order_by]: [OrderByExpr; 1] = f.order_by.try_into().map_err(|_| {
+                        ErrorCode::InvalidInputSyntax(format!(
+                            "{} requires exactly one ORDER BY expression",
+                            kind
+                        ))
+                    })?;
+                    if order_by.nulls_first.is_some() {
+                        return Err(ErrorCode::NotImplemented(
+                            format!("NULLS FIRST/LAST in {}", kind),
+                            None.into(),
+                        )
+                        .into());
+                    }
+                    if order_by.asc == Some(false) {
+                        kind = match kind {
+                            AggKind::FirstValue => AggKind::LastValue,
+                            _ => AggKind::FirstValue,
+                        };
+                    }
+                    inputs.push(self.bind_expr(order_by.expr)?);
+                } else if !f.order_by.is_empty() {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("ORDER BY in {}", kind),
+                        None.into(),
+                    )
+                    .into());
+                }
                 return Ok(ExprImpl::AggCall(Box::new(AggCall::new(kind, inputs)?)));
             }
             let function_type = match function_name.as_str() {
@@ -68,6 +118,27 @@ impl Binder {
                     inputs = Self::rewrite_round_args(inputs);
                     ExprType::RoundDigit
                 }
+                "date_trunc" | "to_char" | "age" => {
+                    inputs = Self::rewrite_date_args(inputs);
+                    match function_name.as_str() {
+                        "date_trunc" => ExprType::DateTrunc,
+                        "to_char" => ExprType::ToChar,
+                        _ => ExprType::Age,
+                    }
+                }
+                "date_part" => {
+                    inputs = Self::rewrite_date_part_args(inputs);
+                    ExprType::Extract
+                }
+                "to_timestamp" => ExprType::ToTimestamp,
+                "make_date" => ExprType::MakeDate,
+                "make_timestamp" => return self.bind_make_timestamp(inputs),
+                "justify_interval" => ExprType::JustifyInterval,
+                "now" | "current_timestamp" => ExprType::Now,
+                "jsonb_extract_path" => return Ok(Self::bind_jsonb_extract_path(inputs)?.into()),
+                "array_length" | "array_cat" | "array_append" | "array_position" => {
+                    return Ok(Self::bind_array_function(&function_name, inputs)?.into())
+                }
                 _ => {
                     return Err(ErrorCode::NotImplemented(
                         format!("unsupported function: {:?}", function_name),
@@ -101,6 +172,41 @@ impl Binder {
         .into()
     }
 
+    /// Bind functions on arrays. They accept arrays of any element type, so their signatures are
+    /// checked here instead of through the function signature map.
+    fn bind_array_function(function_name: &str, inputs: Vec<ExprImpl>) -> Result<FunctionCall> {
+        let element_type = match inputs.first().map(|i| i.return_type()) {
+            Some(DataType::List { datatype }) => *datatype,
+            _ => return Err(Self::err_unsupported_func(function_name, &inputs)),
+        };
+        let array_type = DataType::List {
+            datatype: Box::new(element_type.clone()),
+        };
+        // The type the second argument (if any) is casted to.
+        let (func_type, arg_type, return_type) = match (function_name, inputs.len()) {
+            ("array_length", 1) => (ExprType::ArrayLength, None, DataType::Int32),
+            ("array_length", 2) => (
+                ExprType::ArrayLength,
+                Some(DataType::Int32),
+                DataType::Int32,
+            ),
+            ("array_cat", 2) => (ExprType::ArrayCat, Some(array_type.clone()), array_type),
+            ("array_append", 2) => (ExprType::ArrayAppend, Some(element_type), array_type),
+            ("array_position", 2) => (ExprType::ArrayPosition, Some(element_type), DataType::Int32),
+            _ => return Err(Self::err_unsupported_func(function_name, &inputs)),
+        };
+        let mut inputs = inputs;
+        if let Some(arg_type) = arg_type {
+            let arg = inputs.pop().unwrap();
+            inputs.push(arg.cast_implicit(arg_type)?);
+        }
+        Ok(FunctionCall::new_with_return_type(
+            func_type,
+            inputs,
+            return_type,
+        ))
+    }
+
     /// Rewrite the arguments to be consistent with the `round` signature:
     /// - round(Decimal, Int32) -> Decimal
     /// - round(Decimal) -> Decimal
@@ -131,6 +237,153 @@ impl Binder {
         }
     }
 
+    /// Rewrite `date` arguments to `timestamp`, as date/time functions are only implemented for
+    /// timestamps.
+    fn rewrite_date_args(inputs: Vec<ExprImpl>) -> Vec<ExprImpl> {
+        inputs
+            .into_iter()
+            .map(|input| {
+                if input.return_type() == DataType::Date {
+                    input.cast_implicit(DataType::Timestamp).unwrap()
+                } else {
+                    input
+                }
+            })
+            .collect()
+    }
+
+    /// Rewrite the arguments of `date_part(field, source)` to be consistent with `extract`, which
+    /// expects the field in upper case.
+    fn rewrite_date_part_args(mut inputs: Vec<ExprImpl>) -> Vec<ExprImpl> {
+        if let Some(field) = inputs.first_mut() {
+            if let ExprImpl::Literal(literal) = field
+                && let Some(ScalarImpl::Utf8(s)) = literal.get_data()
+            {
+                *field = Literal::new(
+                    Some(ScalarImpl::Utf8(s.to_uppercase())),
+                    DataType::Varchar,
+                )
+                .into();
+            } else if let Some(upper) =
+                FunctionCall::new(ExprType::Upper, vec![field.clone()])
+            {
+                *field = upper.into();
+            }
+        }
+        inputs
+    }
+
+    /// Bind `make_timestamp(year, month, day, hour, min, sec)` as
+    /// `make_date(year, month, day) + make_interval(hours => hour, mins => min, secs => sec)`.
+    fn bind_make_timestamp(&mut self, mut inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        if inputs.len() != 6 {
+            return Err(Self::err_unsupported_func("make_timestamp", &inputs));
+        }
+        let time_fields = inputs.split_off(3);
+        let date = FunctionCall::new_or_else(ExprType::MakeDate, inputs, |args| {
+            Self::err_unsupported_func("make_date", args)
+        })?;
+        let mut interval_fields: [Option<ExprImpl>; 7] = Default::default();
+        for (field, input) in interval_fields[4..].iter_mut().zip_eq(time_fields) {
+            *field = Some(input);
+        }
+        let time = Self::build_make_interval(interval_fields)?;
+        Ok(
+            FunctionCall::new_or_else(ExprType::Add, vec![date.into(), time], |args| {
+                Self::err_unsupported_func("make_timestamp", args)
+            })?
+            .into(),
+        )
+    }
+
+    /// Bind `make_interval(years, months, weeks, days, hours, mins, secs)`, where all arguments
+    /// are optional and can be passed by name, e.g. `make_interval(days => 10)`.
+    fn bind_make_interval(&mut self, args: Vec<FunctionArg>) -> Result<ExprImpl> {
+        let mut fields: [Option<ExprImpl>; 7] = Default::default();
+        for (i, arg) in args.into_iter().enumerate() {
+            let (idx, arg) = match arg {
+                FunctionArg::Unnamed(arg) => (i, arg),
+                FunctionArg::Named { name, arg } => {
+                    let name = name.value.to_lowercase();
+                    let idx = MAKE_INTERVAL_FIELDS
+                        .iter()
+                        .position(|field| *field == name)
+                        .ok_or_else(|| {
+                            ErrorCode::BindError(format!(
+                                "make_interval has no argument named \"{}\"",
+                                name
+                            ))
+                        })?;
+                    (idx, arg)
+                }
+            };
+            if idx >= fields.len() {
+                return Err(ErrorCode::BindError(
+                    "too many arguments for make_interval".to_string(),
+                )
+                .into());
+            }
+            let [input]: [ExprImpl; 1] =
+                self.bind_function_expr_arg(arg)?.try_into().map_err(|_| {
+                    ErrorCode::BindError("invalid argument for make_interval".to_string())
+                })?;
+            fields[idx] = Some(input);
+        }
+        Self::build_make_interval(fields)
+    }
+
+    /// Build the sum of `field * unit` for each given field of `make_interval`.
+    fn build_make_interval(fields: [Option<ExprImpl>; 7]) -> Result<ExprImpl> {
+        let units = [
+            IntervalUnit::from_month(12),
+            IntervalUnit::from_month(1),
+            IntervalUnit::from_days(7),
+            IntervalUnit::from_days(1),
+            IntervalUnit::from_minutes(60),
+            IntervalUnit::from_minutes(1),
+            IntervalUnit::from_millis(1),
+        ];
+        let mut result: Option<ExprImpl> = None;
+        for ((name, field), unit) in MAKE_INTERVAL_FIELDS.iter().zip_eq(fields).zip_eq(units) {
+            let Some(field) = field else {
+                continue;
+            };
+            let count = if *name == "secs" {
+                // Seconds may be fractional, so count in whole milliseconds instead.
+                let ms = FunctionCall::new_or_else(
+                    ExprType::Multiply,
+                    vec![
+                        field,
+                        Literal::new(Some(1000.into()), DataType::Int32).into(),
+                    ],
+                    |args| Self::err_unsupported_func("make_interval", args),
+                )?;
+                ExprImpl::from(ms).cast_explicit(DataType::Int64)?
+            } else {
+                field.cast_implicit(DataType::Int64)?
+            };
+            let term: ExprImpl = FunctionCall::new_or_else(
+                ExprType::Multiply,
+                vec![
+                    count,
+                    Literal::new(Some(unit.into()), DataType::Interval).into(),
+                ],
+                |args| Self::err_unsupported_func("make_interval", args),
+            )?
+            .into();
+            result = Some(match result {
+                None => term,
+                Some(sum) => FunctionCall::new_or_else(ExprType::Add, vec![sum, term], |args| {
+                    Self::err_unsupported_func("make_interval", args)
+                })?
+                .into(),
+            });
+        }
+        Ok(result.unwrap_or_else(|| {
+            Literal::new(Some(IntervalUnit::default().into()), DataType::Interval).into()
+        }))
+    }
+
     fn ensure_aggregate_allowed(&self) -> Result<()> {
         if let Some(clause) = self.context.clause {
             if clause == Clause::Values || clause == Clause::Where {
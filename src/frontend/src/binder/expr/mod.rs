@@ -16,7 +16,8 @@ use itertools::zip_eq;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{
-    BinaryOperator, DataType as AstDataType, DateTimeField, Expr, TrimWhereField, UnaryOperator,
+    BinaryOperator, DataType as AstDataType, DateTimeField, Expr, Ident, TrimWhereField,
+    UnaryOperator,
 };
 
 use crate::binder::Binder;
@@ -88,6 +89,10 @@ impl Binder {
                 self.bind_between(*expr, negated, *low, *high)?,
             ))),
             Expr::Extract { field, expr } => self.bind_extract(field, *expr),
+            Expr::Array(exprs) => Ok(ExprImpl::FunctionCall(Box::new(self.bind_array(exprs)?))),
+            Expr::MapAccess { column, keys } => self.bind_array_access(*column, keys),
+            Expr::FieldIdentifier(expr, fields) => self.bind_field_access(*expr, fields),
+            Expr::Row(exprs) => Ok(ExprImpl::FunctionCall(Box::new(self.bind_row(exprs)?))),
             _ => Err(ErrorCode::NotImplemented(
                 format!("unsupported expression {:?}", expr),
                 112.into(),
@@ -282,6 +287,119 @@ impl Binder {
         ))
     }
 
+    /// Bind `ARRAY[e1, e2, ...]`. The element type is the least restrictive type of the elements.
+    pub(super) fn bind_array(&mut self, exprs: Vec<Expr>) -> Result<FunctionCall> {
+        let exprs: Vec<ExprImpl> = exprs
+            .into_iter()
+            .map(|e| self.bind_expr(e))
+            .collect::<Result<_>>()?;
+        if exprs.is_empty() {
+            return Err(
+                ErrorCode::BindError("cannot determine type of empty array".to_string()).into(),
+            );
+        }
+        // NULLs are untyped, so they don't take part in the type inference.
+        let mut element_type: Option<DataType> = None;
+        for e in exprs.iter().filter(|e| !e.is_null()) {
+            element_type = Some(match element_type {
+                None => e.return_type(),
+                Some(t) => least_restrictive(t, e.return_type())?,
+            });
+        }
+        let element_type = element_type.unwrap_or(DataType::Varchar);
+        let exprs = exprs
+            .into_iter()
+            .map(|e| e.cast_implicit(element_type.clone()))
+            .collect::<Result<_>>()?;
+        Ok(FunctionCall::new_with_return_type(
+            ExprType::Array,
+            exprs,
+            DataType::List {
+                datatype: Box::new(element_type),
+            },
+        ))
+    }
+
+    /// Bind array subscripts, e.g. `arr[1]` or `arr[i][j]`. Subscripts are 1-based.
+    pub(super) fn bind_array_access(&mut self, array: Expr, keys: Vec<Expr>) -> Result<ExprImpl> {
+        let mut array = self.bind_expr(array)?;
+        for key in keys {
+            let element_type = match array.return_type() {
+                DataType::List { datatype } => *datatype,
+                t => {
+                    return Err(ErrorCode::BindError(format!(
+                        "cannot subscript type {:?} because it is not an array",
+                        t
+                    ))
+                    .into())
+                }
+            };
+            let index = self.bind_expr(key)?;
+            let index = index.cast_implicit(DataType::Int32).map_err(|_| {
+                ErrorCode::BindError("array subscript must have type integer".to_string())
+            })?;
+            array = FunctionCall::new_with_return_type(
+                ExprType::ArrayAccess,
+                vec![array, index],
+                element_type,
+            )
+            .into();
+        }
+        Ok(array)
+    }
+
+    /// Bind struct field accesses, e.g. `(country).city.address`.
+    pub(super) fn bind_field_access(&mut self, expr: Expr, fields: Vec<Ident>) -> Result<ExprImpl> {
+        let mut expr = self.bind_expr(expr)?;
+        for field in fields {
+            let index = match expr.return_type() {
+                DataType::Struct { field_names, .. } => field_names
+                    .iter()
+                    .position(|name| *name == field.value)
+                    .ok_or_else(|| {
+                        ErrorCode::BindError(format!(
+                            "field \"{}\" not found in {:?}",
+                            field.value,
+                            expr.return_type()
+                        ))
+                    })?,
+                t => {
+                    return Err(ErrorCode::BindError(format!(
+                        "cannot access field \"{}\" of type {:?} because it is not a struct",
+                        field.value, t
+                    ))
+                    .into())
+                }
+            };
+            expr = FunctionCall::new_field(expr, index).into();
+        }
+        Ok(expr)
+    }
+
+    /// Bind `ROW(a, b, ...)`. As in Postgres, the fields are named `f1`, `f2`, etc.
+    pub(super) fn bind_row(&mut self, exprs: Vec<Expr>) -> Result<FunctionCall> {
+        let exprs: Vec<ExprImpl> = exprs
+            .into_iter()
+            .map(|e| {
+                let e = self.bind_expr(e)?;
+                // NULLs are untyped, so they are taken as strings, as in `ARRAY[...]`.
+                match e.is_null() {
+                    true => e.cast_implicit(DataType::Varchar),
+                    false => Ok(e),
+                }
+            })
+            .collect::<Result<_>>()?;
+        let return_type = DataType::new_struct(
+            exprs.iter().map(|e| e.return_type()).collect(),
+            (1..=exprs.len()).map(|i| format!("f{}", i)).collect(),
+        );
+        Ok(FunctionCall::new_with_return_type(
+            ExprType::Row,
+            exprs,
+            return_type,
+        ))
+    }
+
     pub(super) fn bind_is_operator(
         &mut self,
         func_type: ExprType,
@@ -314,9 +432,17 @@ pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
         AstDataType::Timestamp(false) => DataType::Timestamp,
         AstDataType::Timestamp(true) => DataType::Timestampz,
         AstDataType::Interval => DataType::Interval,
+        AstDataType::Jsonb => DataType::Jsonb,
         AstDataType::Array(datatype) => DataType::List {
             datatype: Box::new(bind_data_type(datatype)?),
         },
+        AstDataType::Struct(fields) => DataType::new_struct(
+            fields
+                .iter()
+                .map(|f| bind_data_type(&f.data_type))
+                .collect::<Result<_>>()?,
+            fields.iter().map(|f| f.name.value.clone()).collect(),
+        ),
         _ => {
             return Err(ErrorCode::NotImplemented(
                 format!("unsupported data type: {:?}", data_type),
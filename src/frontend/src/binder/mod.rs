@@ -25,6 +25,7 @@ mod select;
 mod set_expr;
 mod statement;
 mod values;
+mod watermark;
 mod window_table_function;
 
 pub use bind_context::BindContext;
@@ -32,10 +33,11 @@ pub use delete::BoundDelete;
 pub use insert::BoundInsert;
 pub use query::BoundQuery;
 pub use relation::{BoundBaseTable, BoundJoin, BoundSource, BoundTableSource, Relation};
-pub use select::BoundSelect;
+pub use select::{BoundDistinct, BoundSelect};
 pub use set_expr::BoundSetExpr;
 pub use statement::BoundStatement;
 pub use values::BoundValues;
+pub use watermark::BoundWatermark;
 pub use window_table_function::{BoundWindowTableFunction, WindowTableFunctionKind};
 
 use crate::catalog::catalog_service::CatalogReadGuard;
@@ -45,6 +45,18 @@ impl BoundQuery {
     pub fn is_correlated(&self) -> bool {
         self.body.is_correlated()
     }
+
+    /// Whether this [`BoundQuery`] returns at most one row, i.e. it is an aggregation without
+    /// `GROUP BY`.
+    pub fn max_one_row(&self) -> bool {
+        match &self.body {
+            BoundSetExpr::Select(select) => {
+                select.group_by.is_empty()
+                    && select.select_items.iter().any(|expr| expr.has_agg_call())
+            }
+            BoundSetExpr::Values(_) => false,
+        }
+    }
 }
 
 impl Binder {
@@ -21,7 +21,8 @@ use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::types::DataType;
 use risingwave_pb::plan::JoinType;
 use risingwave_sqlparser::ast::{
-    JoinConstraint, JoinOperator, ObjectName, Query, TableAlias, TableFactor, TableWithJoins,
+    FunctionArg, JoinConstraint, JoinOperator, ObjectName, Query, TableAlias, TableFactor,
+    TableWithJoins,
 };
 
 use super::bind_context::ColumnBinding;
@@ -30,7 +31,7 @@ use crate::binder::Binder;
 use crate::catalog::source_catalog::SourceCatalog;
 use crate::catalog::table_catalog::TableCatalog;
 use crate::catalog::{CatalogError, TableId};
-use crate::expr::{Expr, ExprImpl};
+use crate::expr::{Expr, ExprImpl, TableFunction, TableFunctionType};
 
 /// A validated item that refers to a table-like entity, including base table, subquery, join, etc.
 /// It is usually part of the `from` clause.
@@ -41,6 +42,7 @@ pub enum Relation {
     Subquery(Box<BoundSubquery>),
     Join(Box<BoundJoin>),
     WindowTableFunction(Box<BoundWindowTableFunction>),
+    TableFunction(Box<TableFunction>),
 }
 
 #[derive(Debug)]
@@ -56,6 +58,8 @@ pub struct BoundBaseTable {
     pub name: String, // explain-only
     pub table_id: TableId,
     pub table_catalog: TableCatalog,
+    /// Whether the table is the dimension table of a temporal join.
+    pub for_system_time_as_of_proctime: bool,
 }
 
 impl From<&TableCatalog> for BoundBaseTable {
@@ -64,6 +68,7 @@ impl From<&TableCatalog> for BoundBaseTable {
             name: t.name.clone(),
             table_id: t.id,
             table_catalog: t.clone(),
+            for_system_time_as_of_proctime: false,
         }
     }
 }
@@ -116,8 +121,16 @@ impl Binder {
     }
 
     fn bind_table_with_joins(&mut self, table: TableWithJoins) -> Result<Relation> {
+        if Self::is_temporal_table_factor(&table.relation) {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "FOR SYSTEM_TIME AS OF PROCTIME() can only be used on the right side of a join"
+                    .into(),
+            )
+            .into());
+        }
         let mut root = self.bind_table_factor(table.relation)?;
         for join in table.joins {
+            let is_temporal = Self::is_temporal_table_factor(&join.relation);
             let right = self.bind_table_factor(join.relation)?;
             let (constraint, join_type) = match join.join_operator {
                 JoinOperator::Inner(constraint) => (constraint, JoinType::Inner),
@@ -127,6 +140,13 @@ impl Binder {
                 // Cross join equals to inner join with with no constraint.
                 JoinOperator::CrossJoin => (JoinConstraint::None, JoinType::Inner),
             };
+            if is_temporal && !matches!(join_type, JoinType::Inner | JoinType::LeftOuter) {
+                return Err(ErrorCode::NotImplemented(
+                    format!("temporal join of type {:?}", join_type),
+                    None.into(),
+                )
+                .into());
+            }
             let cond = self.bind_join_constraint(constraint)?;
             let join = BoundJoin {
                 join_type,
@@ -140,6 +160,16 @@ impl Binder {
         Ok(root)
     }
 
+    fn is_temporal_table_factor(table_factor: &TableFactor) -> bool {
+        matches!(
+            table_factor,
+            TableFactor::Table {
+                for_system_time_as_of_proctime: true,
+                ..
+            }
+        )
+    }
+
     fn bind_join_constraint(&mut self, constraint: JoinConstraint) -> Result<ExprImpl> {
         Ok(match constraint {
             JoinConstraint::None => ExprImpl::literal_bool(true),
@@ -165,10 +195,42 @@ impl Binder {
 
     pub(super) fn bind_table_factor(&mut self, table_factor: TableFactor) -> Result<Relation> {
         match table_factor {
-            TableFactor::Table { name, alias, args } => {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                for_system_time_as_of_proctime: true,
+            } => {
+                if !args.is_empty() {
+                    return Err(ErrorCode::InvalidInputSyntax(
+                        "FOR SYSTEM_TIME AS OF PROCTIME() can not be used on table functions"
+                            .into(),
+                    )
+                    .into());
+                }
+                let (schema_name, table_name) = Self::resolve_table_name(name)?;
+                match self.bind_table_or_source(&schema_name, &table_name, alias)? {
+                    Relation::BaseTable(mut table) => {
+                        table.for_system_time_as_of_proctime = true;
+                        Ok(Relation::BaseTable(table))
+                    }
+                    _ => Err(ErrorCode::NotImplemented(
+                        "FOR SYSTEM_TIME AS OF PROCTIME() on sources, please use a table or a \
+                         materialized view instead"
+                            .into(),
+                        None.into(),
+                    )
+                    .into()),
+                }
+            }
+            TableFactor::Table {
+                name, alias, args, ..
+            } => {
                 if args.is_empty() {
                     let (schema_name, table_name) = Self::resolve_table_name(name)?;
                     self.bind_table_or_source(&schema_name, &table_name, alias)
+                } else if let Ok(function_type) = TableFunctionType::from_str(&name.0[0].value) {
+                    self.bind_table_function(function_type, args, alias)
                 } else {
                     let kind =
                         WindowTableFunctionKind::from_str(&name.0[0].value).map_err(|_| {
@@ -203,6 +265,42 @@ impl Binder {
         }
     }
 
+    /// Binds a table function in `FROM`, e.g. `jsonb_array_elements('[1, 2]')`. Only constant
+    /// arguments are supported for now.
+    fn bind_table_function(
+        &mut self,
+        function_type: TableFunctionType,
+        args: Vec<FunctionArg>,
+        alias: Option<TableAlias>,
+    ) -> Result<Relation> {
+        let args: Vec<ExprImpl> = args
+            .into_iter()
+            .map(|arg| self.bind_function_arg(arg))
+            .flatten_ok()
+            .try_collect()?;
+        if args
+            .iter()
+            .any(|arg| arg.has_input_ref() || arg.has_subquery() || arg.has_agg_call())
+        {
+            return Err(ErrorCode::NotImplemented(
+                "table function with non-constant arguments".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+        let table_function = TableFunction::new(function_type, args)?;
+        self.bind_context(
+            [(
+                function_type.column_name().to_string(),
+                table_function.return_type.clone(),
+                false,
+            )],
+            table_function.name().to_string(),
+            alias,
+        )?;
+        Ok(Relation::TableFunction(Box::new(table_function)))
+    }
+
     pub(super) fn bind_table_or_source(
         &mut self,
         schema_name: &str,
@@ -225,6 +323,18 @@ impl Binder {
             .into());
         }
 
+        if let Ok(table) = self
+            .catalog
+            .get_table_by_name(&self.db_name, schema_name, table_name)
+            && table.is_creating()
+        {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "materialized view {} is still being created",
+                table_name
+            ))
+            .into());
+        }
+
         let (ret, columns) = {
             let catalog = &self.catalog;
 
@@ -281,6 +391,7 @@ impl Binder {
             name: table_name.to_string(),
             table_id,
             table_catalog,
+            for_system_time_as_of_proctime: false,
         })
     }
 
@@ -17,7 +17,7 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
-use risingwave_sqlparser::ast::{Expr, Select, SelectItem};
+use risingwave_sqlparser::ast::{Distinct, Expr, Select, SelectItem};
 
 use super::bind_context::{Clause, ColumnBinding};
 use super::UNNAMED_COLUMN;
@@ -27,7 +27,7 @@ use crate::expr::{Expr as _, ExprImpl, InputRef};
 
 #[derive(Debug)]
 pub struct BoundSelect {
-    pub distinct: bool,
+    pub distinct: BoundDistinct,
     pub select_items: Vec<ExprImpl>,
     pub aliases: Vec<Option<String>>,
     pub from: Option<Relation>,
@@ -35,6 +35,25 @@ pub struct BoundSelect {
     pub group_by: Vec<ExprImpl>,
 }
 
+/// The bound `DISTINCT` clause of a [`BoundSelect`].
+#[derive(Debug)]
+pub enum BoundDistinct {
+    All,
+    Distinct,
+    /// `DISTINCT ON (<expr>, ...)`, whose exprs are bound against the `FROM` clause.
+    DistinctOn(Vec<ExprImpl>),
+}
+
+impl BoundDistinct {
+    /// The exprs of `DISTINCT ON`, or an empty slice otherwise.
+    pub fn exprs(&self) -> &[ExprImpl] {
+        match self {
+            BoundDistinct::DistinctOn(exprs) => exprs,
+            BoundDistinct::All | BoundDistinct::Distinct => &[],
+        }
+    }
+}
+
 impl BoundSelect {
     /// The names returned by this [`BoundSelect`].
     pub fn names(&self) -> Vec<String> {
@@ -58,6 +77,7 @@ impl BoundSelect {
             .iter()
             .chain(self.group_by.iter())
             .chain(self.where_clause.iter())
+            .chain(self.distinct.exprs())
             .any(|expr| expr.has_correlated_input_ref())
     }
 }
@@ -93,11 +113,23 @@ impl Binder {
             .map(|expr| self.bind_expr(expr))
             .try_collect()?;
 
+        // Bind DISTINCT clause.
+        let distinct = match select.distinct {
+            Distinct::All => BoundDistinct::All,
+            Distinct::Distinct => BoundDistinct::Distinct,
+            Distinct::DistinctOn(exprs) => BoundDistinct::DistinctOn(
+                exprs
+                    .into_iter()
+                    .map(|expr| self.bind_expr(expr))
+                    .try_collect()?,
+            ),
+        };
+
         // Bind SELECT clause.
         let (select_items, aliases) = self.bind_project(select.projection)?;
 
         Ok(BoundSelect {
-            distinct: select.distinct,
+            distinct,
             select_items,
             aliases,
             from,
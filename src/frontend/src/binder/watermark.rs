@@ -0,0 +1,80 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_sqlparser::ast::SourceWatermark;
+
+use super::Binder;
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::expr::{Expr, ExprImpl};
+
+#[derive(Debug)]
+pub struct BoundWatermark {
+    /// Index of the watermark column in the source.
+    pub column_idx: usize,
+    /// Expression to generate the watermark from a row, e.g. `ts - INTERVAL '5' SECOND`.
+    pub expr: ExprImpl,
+}
+
+impl Binder {
+    /// Binds the `WATERMARK FOR <column> AS <expr>` clauses of `CREATE SOURCE` against the columns
+    /// of the source.
+    pub fn bind_source_watermarks(
+        &mut self,
+        source_name: String,
+        columns: &[ColumnCatalog],
+        watermarks: Vec<SourceWatermark>,
+    ) -> Result<Vec<BoundWatermark>> {
+        if watermarks.len() > 1 {
+            return Err(ErrorCode::NotImplemented(
+                "more than one watermark on a source".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+
+        self.bind_context(
+            columns
+                .iter()
+                .map(|c| (c.name().to_string(), c.data_type().clone(), c.is_hidden())),
+            source_name,
+            None,
+        )?;
+
+        watermarks
+            .into_iter()
+            .map(|watermark| {
+                let column_name = watermark.column.value;
+                let column_idx = columns
+                    .iter()
+                    .position(|c| c.name() == column_name)
+                    .ok_or_else(|| {
+                        ErrorCode::ItemNotFound(format!("Invalid column: {}", column_name))
+                    })?;
+                let expr = self.bind_expr(watermark.expr)?;
+                let column_type = columns[column_idx].data_type();
+                if &expr.return_type() != column_type {
+                    return Err(ErrorCode::BindError(format!(
+                        "the watermark expression of column \"{}\" should be of type {:?}, but got {:?}",
+                        column_name,
+                        column_type,
+                        expr.return_type()
+                    ))
+                    .into());
+                }
+                Ok(BoundWatermark { column_idx, expr })
+            })
+            .collect()
+    }
+}
@@ -22,6 +22,7 @@ use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
 };
+use risingwave_pb::ddl_service::alter_rate_limit_request::Relation;
 use risingwave_pb::stream_plan::StreamNode;
 use risingwave_rpc_client::MetaClient;
 use tokio::sync::watch::Receiver;
@@ -53,7 +54,14 @@ pub trait CatalogWriter: Send + Sync {
 
     async fn create_schema(&self, db_id: DatabaseId, schema_name: &str) -> Result<()>;
 
-    async fn create_materialized_view(&self, table: ProstTable, plan: StreamNode) -> Result<()>;
+    /// Create a materialized view. If `background`, it returns once the materialized view is
+    /// added to the catalog in creating status, without waiting for the backfill.
+    async fn create_materialized_view(
+        &self,
+        table: ProstTable,
+        plan: StreamNode,
+        background: bool,
+    ) -> Result<()>;
 
     async fn create_materialized_source(
         &self,
@@ -69,6 +77,14 @@ pub trait CatalogWriter: Send + Sync {
     async fn drop_materialized_view(&self, table_id: TableId) -> Result<()>;
 
     async fn drop_source(&self, source_id: u32) -> Result<()>;
+
+    async fn alter_source_rate_limit(&self, source_id: u32, rate_limit: Option<u32>) -> Result<()>;
+
+    async fn alter_materialized_view_rate_limit(
+        &self,
+        table_id: TableId,
+        rate_limit: Option<u32>,
+    ) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -103,10 +119,15 @@ impl CatalogWriter for CatalogWriterImpl {
     }
 
     // TODO: maybe here to pass a materialize plan node
-    async fn create_materialized_view(&self, table: ProstTable, plan: StreamNode) -> Result<()> {
+    async fn create_materialized_view(
+        &self,
+        table: ProstTable,
+        plan: StreamNode,
+        background: bool,
+    ) -> Result<()> {
         let (_, version) = self
             .meta_client
-            .create_materialized_view(table, plan)
+            .create_materialized_view(table, plan, background)
             .await?;
         self.wait_version(version).await
     }
@@ -146,6 +167,28 @@ impl CatalogWriter for CatalogWriterImpl {
         let version = self.meta_client.drop_source(source_id).await?;
         self.wait_version(version).await
     }
+
+    async fn alter_source_rate_limit(&self, source_id: u32, rate_limit: Option<u32>) -> Result<()> {
+        let version = self
+            .meta_client
+            .alter_rate_limit(Relation::SourceId(source_id), rate_limit.unwrap_or(0))
+            .await?;
+        self.wait_version(version).await
+    }
+
+    async fn alter_materialized_view_rate_limit(
+        &self,
+        table_id: TableId,
+        rate_limit: Option<u32>,
+    ) -> Result<()> {
+        self.meta_client
+            .alter_rate_limit(
+                Relation::TableId(table_id.table_id()),
+                rate_limit.unwrap_or(0),
+            )
+            .await?;
+        Ok(())
+    }
 }
 
 impl CatalogWriterImpl {
@@ -44,6 +44,11 @@ impl SchemaCatalog {
         self.table_name_by_id.try_insert(id, name).unwrap();
     }
 
+    pub fn update_table(&mut self, prost: &ProstTable) {
+        self.drop_table(prost.id.into());
+        self.create_table(prost);
+    }
+
     pub fn drop_table(&mut self, id: TableId) {
         let name = self.table_name_by_id.remove(&id).unwrap();
         self.table_by_name.remove(&name).unwrap();
@@ -59,6 +64,11 @@ impl SchemaCatalog {
         self.source_name_by_id.try_insert(id, name).unwrap();
     }
 
+    pub fn update_source(&mut self, prost: ProstSource) {
+        self.drop_source(prost.id);
+        self.create_source(prost);
+    }
+
     pub fn drop_source(&mut self, id: SourceId) {
         let name = self.source_name_by_id.remove(&id).unwrap();
         self.source_by_name.remove(&name).unwrap();
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use risingwave_pb::catalog::source::Info;
-use risingwave_pb::catalog::Source as ProstSource;
+use risingwave_pb::catalog::{RowFormatType, Source as ProstSource, WatermarkDesc};
 use risingwave_pb::stream_plan::source_node::SourceType;
 
 use super::column_catalog::ColumnCatalog;
@@ -26,28 +26,43 @@ pub struct SourceCatalog {
     pub columns: Vec<ColumnCatalog>,
     pub pk_col_ids: Vec<ColumnId>,
     pub source_type: SourceType,
+    pub watermark_descs: Vec<WatermarkDesc>,
+    /// Whether the source only produces inserts. Connector sources are append-only unless their
+    /// rows carry changes, e.g. `ROW FORMAT DEBEZIUM_JSON`, while table sources are not.
+    pub append_only: bool,
+    /// The maximum number of rows per second each source actor reads, if limited.
+    pub rate_limit: Option<u32>,
 }
 
 impl From<&ProstSource> for SourceCatalog {
     fn from(prost: &ProstSource) -> Self {
         let id = prost.id;
         let name = prost.name.clone();
-        let (source_type, prost_columns, pk_col_ids) = match &prost.info {
-            Some(Info::StreamSource(source)) => (
-                SourceType::Source,
-                source.columns.clone(),
-                source
-                    .pk_column_ids
-                    .iter()
-                    .map(|id| ColumnId::new(*id))
-                    .collect(),
-            ),
-            Some(Info::TableSource(source)) => (
-                SourceType::Table,
-                source.columns.clone(),
-                vec![TABLE_SOURCE_PK_COLID],
-            ),
-            None => unreachable!(),
+        let (source_type, prost_columns, pk_col_ids, watermark_descs, append_only) =
+            match &prost.info {
+                Some(Info::StreamSource(source)) => (
+                    SourceType::Source,
+                    source.columns.clone(),
+                    source
+                        .pk_column_ids
+                        .iter()
+                        .map(|id| ColumnId::new(*id))
+                        .collect(),
+                    source.watermark_descs.clone(),
+                    source.row_format != RowFormatType::DebeziumJson as i32,
+                ),
+                Some(Info::TableSource(source)) => (
+                    SourceType::Table,
+                    source.columns.clone(),
+                    vec![TABLE_SOURCE_PK_COLID],
+                    vec![],
+                    false,
+                ),
+                None => unreachable!(),
+            };
+        let rate_limit = match &prost.info {
+            Some(Info::StreamSource(source)) if source.rate_limit > 0 => Some(source.rate_limit),
+            _ => None,
         };
         let columns = prost_columns.into_iter().map(ColumnCatalog::from).collect();
         Self {
@@ -56,6 +71,9 @@ impl From<&ProstSource> for SourceCatalog {
             columns,
             pk_col_ids,
             source_type,
+            watermark_descs,
+            append_only,
+            rate_limit,
         }
     }
 }
@@ -17,7 +17,7 @@ use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 use risingwave_common::catalog::{ColumnDesc, OrderedColumnDesc, TableDesc};
 use risingwave_common::util::sort_util::OrderType;
-use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+use risingwave_pb::catalog::table::{OptionalAssociatedSourceId, StreamJobStatus};
 use risingwave_pb::catalog::Table as ProstTable;
 use risingwave_pb::plan::OrderType as ProstOrderType;
 
@@ -32,6 +32,8 @@ pub struct TableCatalog {
     pub name: String,
     pub columns: Vec<ColumnCatalog>,
     pub pk_desc: Vec<OrderedColumnDesc>,
+    /// Whether the materialized view is still being created in background.
+    pub is_creating: bool,
 }
 
 impl TableCatalog {
@@ -70,6 +72,11 @@ impl TableCatalog {
         self.name.as_ref()
     }
 
+    /// Whether the materialized view is still being created in background.
+    pub fn is_creating(&self) -> bool {
+        self.is_creating
+    }
+
     pub fn to_prost(&self, schema_id: SchemaId, database_id: DatabaseId) -> ProstTable {
         let (pk_column_ids, pk_orders) = self
             .pk_desc()
@@ -94,6 +101,11 @@ impl TableCatalog {
             optional_associated_source_id: self
                 .associated_source_id
                 .map(|source_id| OptionalAssociatedSourceId::AssociatedSourceId(source_id.into())),
+            stream_job_status: if self.is_creating {
+                StreamJobStatus::Creating
+            } else {
+                StreamJobStatus::Created
+            } as i32,
         }
     }
 }
@@ -105,6 +117,7 @@ impl From<ProstTable> for TableCatalog {
             OptionalAssociatedSourceId::AssociatedSourceId(id) => id,
         });
         let name = tb.name.clone();
+        let is_creating = tb.stream_job_status() == StreamJobStatus::Creating;
         let mut col_names = HashSet::new();
         let mut col_descs: HashMap<i32, ColumnDesc> = HashMap::new();
         let columns: Vec<ColumnCatalog> = tb.columns.into_iter().map(ColumnCatalog::from).collect();
@@ -139,6 +152,7 @@ impl From<ProstTable> for TableCatalog {
             name,
             pk_desc,
             columns,
+            is_creating,
         }
     }
 }
@@ -154,7 +168,7 @@ mod tests {
     use risingwave_common::catalog::{ColumnDesc, ColumnId, OrderedColumnDesc, TableId};
     use risingwave_common::types::*;
     use risingwave_common::util::sort_util::OrderType;
-    use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+    use risingwave_pb::catalog::table::{OptionalAssociatedSourceId, StreamJobStatus};
     use risingwave_pb::catalog::Table as ProstTable;
     use risingwave_pb::plan::{ColumnCatalog as ProstColumnCatalog, ColumnDesc as ProstColumnDesc};
 
@@ -200,6 +214,7 @@ mod tests {
             dependent_relations: vec![],
             optional_associated_source_id: OptionalAssociatedSourceId::AssociatedSourceId(233)
                 .into(),
+            stream_job_status: StreamJobStatus::Creating as i32,
         }
         .into();
 
@@ -213,9 +228,10 @@ mod tests {
                     ColumnCatalog::row_id_column(),
                     ColumnCatalog {
                         column_desc: ColumnDesc {
-                            data_type: DataType::Struct {
-                                fields: vec![DataType::Varchar, DataType::Varchar].into()
-                            },
+                            data_type: DataType::new_struct(
+                                vec![DataType::Varchar, DataType::Varchar],
+                                vec!["address".to_string(), "zipcode".to_string()]
+                            ),
                             column_id: ColumnId::new(1),
                             name: "country".to_string(),
                             field_descs: vec![
@@ -242,7 +258,8 @@ mod tests {
                 pk_desc: vec![OrderedColumnDesc {
                     column_desc: row_id_column_desc(),
                     order: OrderType::Ascending
-                }]
+                }],
+                is_creating: true,
             }
         );
     }
@@ -70,6 +70,35 @@ impl AggCall {
                 _ => return None,
             },
             (AggKind::Count, _) => DataType::Int64,
+            // Unlike postgres, which returns `numeric` for exact numeric inputs, variances are
+            // always computed in double precision.
+            (
+                AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp,
+                [input],
+            ) if input.is_numeric() => DataType::Float64,
+            (AggKind::BoolAnd | AggKind::BoolOr, [DataType::Boolean]) => DataType::Boolean,
+            (
+                AggKind::BitAnd | AggKind::BitOr,
+                [input @ (DataType::Int16 | DataType::Int32 | DataType::Int64)],
+            ) => input.clone(),
+            // The second input is the ordering key.
+            (AggKind::FirstValue | AggKind::LastValue, [input, _]) => input.clone(),
+            (
+                AggKind::StddevPop
+                | AggKind::StddevSamp
+                | AggKind::VarPop
+                | AggKind::VarSamp
+                | AggKind::BoolAnd
+                | AggKind::BoolOr
+                | AggKind::BitAnd
+                | AggKind::BitOr
+                | AggKind::FirstValue
+                | AggKind::LastValue,
+                _,
+            ) => return None,
+            (AggKind::ArrayAgg, [input]) => DataType::List {
+                datatype: Box::new(input.clone()),
+            },
             (other_kind, other_inputs) => {
                 todo!(
                     "Unsupported aggregate function: {:?} with {} inputs",
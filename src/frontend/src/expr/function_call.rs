@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 
 use super::{cast_ok, infer_type, CastContext, Expr, ExprImpl, Literal};
 use crate::expr::ExprType;
@@ -113,6 +114,8 @@ impl FunctionCall {
             Ok(Literal::new(None, target).into())
         } else if source == target {
             Ok(child)
+        } else if let (DataType::Struct { .. }, DataType::Struct { .. }) = (&source, &target) {
+            Self::new_struct_cast(child, target, allows)
         } else if cast_ok(&source, &target, &allows) {
             Ok(Self {
                 func_type: ExprType::Cast,
@@ -129,6 +132,52 @@ impl FunctionCall {
         }
     }
 
+    /// Casts a struct field by field, and rebuilds it with `ROW`. The fields are matched by
+    /// position, so this also renames them.
+    ///
+    /// Note that a null struct which is not a `ROW` constructor is cast to a struct of nulls.
+    fn new_struct_cast(child: ExprImpl, target: DataType, allows: CastContext) -> Result<ExprImpl> {
+        let source = child.return_type();
+        let (source_fields, target_fields) = match (&source, &target) {
+            (DataType::Struct { fields: s, .. }, DataType::Struct { fields: t, .. }) => (s, t),
+            _ => unreachable!(),
+        };
+        if source_fields.len() != target_fields.len() {
+            return Err(ErrorCode::BindError(format!(
+                "cannot cast type {:?} to {:?}: the number of fields does not match",
+                source, target
+            ))
+            .into());
+        }
+        let fields = match child {
+            ExprImpl::FunctionCall(func) if func.func_type == ExprType::Row => func.inputs,
+            child => (0..source_fields.len())
+                .map(|i| Self::new_field(child.clone(), i).into())
+                .collect(),
+        };
+        let fields = fields
+            .into_iter()
+            .zip_eq(target_fields.iter())
+            .map(|(field, ty)| Self::new_cast(field, ty.clone(), allows))
+            .collect::<Result<_>>()?;
+        Ok(Self::new_with_return_type(ExprType::Row, fields, target).into())
+    }
+
+    /// Create an access to the `index`-th field of the struct `input`.
+    pub fn new_field(input: ExprImpl, index: usize) -> Self {
+        let DataType::Struct { fields, .. } = input.return_type() else {
+            panic!("field access on non-struct type {:?}", input.return_type())
+        };
+        Self::new_with_return_type(
+            ExprType::Field,
+            vec![
+                input,
+                Literal::new(Some(ScalarImpl::Int32(index as i32)), DataType::Int32).into(),
+            ],
+            fields[index].clone(),
+        )
+    }
+
     /// used for expressions like cast
     pub fn new_with_return_type(
         func_type: ExprType,
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::value_encoding::serialize_cell_not_null;
 use risingwave_pb::expr::expr_node::RexNode;
 
 use super::Expr;
@@ -89,11 +90,14 @@ fn literal_to_protobuf(d: &Datum) -> Option<RexNode> {
         ScalarImpl::Bool(v) => (*v as i8).to_be_bytes().to_vec(),
         ScalarImpl::Decimal(v) => v.to_string().as_bytes().to_vec(),
         ScalarImpl::Interval(v) => v.to_protobuf_owned(),
+        ScalarImpl::Jsonb(v) => v.to_string().as_bytes().to_vec(),
         ScalarImpl::NaiveDate(_) => todo!(),
         ScalarImpl::NaiveDateTime(_) => todo!(),
         ScalarImpl::NaiveTime(_) => todo!(),
-        ScalarImpl::Struct(_) => todo!(),
-        ScalarImpl::List(_) => todo!(),
+        // Nested values are stored with the value encoding.
+        ScalarImpl::Struct(_) | ScalarImpl::List(_) => {
+            serialize_cell_not_null(&Some(d.clone())).unwrap()
+        }
     };
     Some(RexNode::Constant(ConstantValue { body }))
 }
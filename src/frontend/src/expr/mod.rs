@@ -26,6 +26,7 @@ mod function_call;
 mod input_ref;
 mod literal;
 mod subquery;
+mod table_function;
 
 mod expr_rewriter;
 mod expr_visitor;
@@ -38,6 +39,7 @@ pub use function_call::FunctionCall;
 pub use input_ref::{as_alias_display, input_ref_to_column_indices, InputRef, InputRefDisplay};
 pub use literal::Literal;
 pub use subquery::{Subquery, SubqueryKind};
+pub use table_function::{TableFunction, TableFunctionType};
 
 pub type ExprType = risingwave_pb::expr::expr_node::Type;
 
@@ -171,6 +173,7 @@ impl ExprImpl {
                         .iter()
                         .chain(select.group_by.iter())
                         .chain(select.where_clause.iter())
+                        .chain(select.distinct.exprs())
                         .for_each(|expr| self.visit_expr(expr)),
                     BoundSetExpr::Values(_) => {}
                 }
@@ -0,0 +1,157 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use itertools::Itertools;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::table_function::Type;
+use risingwave_pb::expr::TableFunction as TableFunctionProst;
+
+use super::{Expr, ExprImpl};
+
+/// A table function takes a row as input and returns a table. It is also known as Set-Returning
+/// Function.
+///
+/// See also [`TableFunction`](risingwave_expr::table_function::TableFunction) trait in expr
+/// crate.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct TableFunction {
+    pub args: Vec<ExprImpl>,
+    pub return_type: DataType,
+    pub function_type: TableFunctionType,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TableFunctionType {
+    JsonbArrayElements,
+    Unnest,
+}
+
+impl TableFunctionType {
+    fn to_protobuf(self) -> Type {
+        match self {
+            TableFunctionType::JsonbArrayElements => Type::JsonbArrayElements,
+            TableFunctionType::Unnest => Type::Unnest,
+        }
+    }
+
+    /// The name of the only output column, aligned with PG.
+    pub fn column_name(self) -> &'static str {
+        match self {
+            TableFunctionType::JsonbArrayElements => "value",
+            TableFunctionType::Unnest => "unnest",
+        }
+    }
+}
+
+impl FromStr for TableFunctionType {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("jsonb_array_elements") {
+            Ok(TableFunctionType::JsonbArrayElements)
+        } else if s.eq_ignore_ascii_case("unnest") {
+            Ok(TableFunctionType::Unnest)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl TableFunction {
+    /// Create a table function and infer the return type. String literal arguments are taken as
+    /// the expected type, like `unknown`-typed literals in PG.
+    pub fn new(function_type: TableFunctionType, args: Vec<ExprImpl>) -> Result<Self> {
+        let (args, return_type) = match function_type {
+            TableFunctionType::JsonbArrayElements => {
+                let [arg]: [ExprImpl; 1] = args.try_into().map_err(|args: Vec<ExprImpl>| {
+                    ErrorCode::BindError(format!(
+                        "jsonb_array_elements expects 1 argument, got {}",
+                        args.len()
+                    ))
+                })?;
+                let arg = match arg.return_type() {
+                    DataType::Jsonb => arg,
+                    DataType::Varchar if matches!(arg, ExprImpl::Literal(_)) => {
+                        arg.cast_explicit(DataType::Jsonb)?
+                    }
+                    t => {
+                        return Err(ErrorCode::BindError(format!(
+                            "function jsonb_array_elements({:?}) does not exist",
+                            t
+                        ))
+                        .into())
+                    }
+                };
+                (vec![arg], DataType::Jsonb)
+            }
+            TableFunctionType::Unnest => {
+                let [arg]: [ExprImpl; 1] = args.try_into().map_err(|args: Vec<ExprImpl>| {
+                    ErrorCode::BindError(format!("unnest expects 1 argument, got {}", args.len()))
+                })?;
+                let return_type = match arg.return_type() {
+                    DataType::List { datatype } => *datatype,
+                    t => {
+                        return Err(ErrorCode::BindError(format!(
+                            "function unnest({:?}) does not exist",
+                            t
+                        ))
+                        .into())
+                    }
+                };
+                (vec![arg], return_type)
+            }
+        };
+        Ok(TableFunction {
+            args,
+            return_type,
+            function_type,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.function_type {
+            TableFunctionType::JsonbArrayElements => "jsonb_array_elements",
+            TableFunctionType::Unnest => "unnest",
+        }
+    }
+
+    pub fn to_protobuf(&self) -> TableFunctionProst {
+        TableFunctionProst {
+            function_type: self.function_type.to_protobuf() as i32,
+            args: self.args.iter().map(|c| c.to_protobuf()).collect_vec(),
+            return_type: Some(self.return_type.to_protobuf()),
+        }
+    }
+}
+
+impl std::fmt::Debug for TableFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.debug_struct("TableFunction")
+                .field("function_type", &self.function_type)
+                .field("return_type", &self.return_type)
+                .field("args", &self.args)
+                .finish()
+        } else {
+            let mut builder = f.debug_tuple(self.name());
+            self.args.iter().for_each(|arg| {
+                builder.field(arg);
+            });
+            builder.finish()
+        }
+    }
+}
@@ -41,6 +41,7 @@ enum DataTypeName {
     Timestamp,
     Timestampz,
     Interval,
+    Jsonb,
     Struct,
     List,
 }
@@ -60,6 +61,7 @@ fn name_of(ty: &DataType) -> DataTypeName {
         DataType::Timestampz => DataTypeName::Timestampz,
         DataType::Decimal => DataTypeName::Decimal,
         DataType::Interval => DataTypeName::Interval,
+        DataType::Jsonb => DataTypeName::Jsonb,
         DataType::Struct { .. } => DataTypeName::Struct,
         DataType::List { .. } => DataTypeName::List,
     }
@@ -85,8 +87,10 @@ pub fn infer_type(func_type: ExprType, inputs_type: Vec<DataType>) -> Option<Dat
         DataTypeName::Timestampz => DataType::Timestampz,
         DataTypeName::Decimal => DataType::Decimal,
         DataTypeName::Interval => DataType::Interval,
+        DataTypeName::Jsonb => DataType::Jsonb,
         DataTypeName::Struct => DataType::Struct {
             fields: Arc::new([]),
+            field_names: Arc::new([]),
         },
         DataTypeName::List => DataType::List {
             datatype: Box::new(DataType::Int32),
@@ -270,6 +274,13 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &[T::Date, T::Timestamp],
         T::Boolean,
     );
+    build_binary_funcs(
+        &mut map,
+        &cmp_exprs,
+        &[T::Timestampz],
+        &[T::Timestampz],
+        T::Boolean,
+    );
     // Date/Timestamp/Interval arithmetic
     build_commutative_binary_funcs(
         &mut map,
@@ -285,6 +296,34 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &[T::Interval],
         T::Timestamp,
     );
+    build_commutative_binary_funcs(
+        &mut map,
+        &[E::Add],
+        &[T::Timestampz],
+        &[T::Interval],
+        T::Timestampz,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::Subtract],
+        &[T::Timestampz],
+        &[T::Interval],
+        T::Timestampz,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::Subtract],
+        &[T::Timestampz],
+        &[T::Timestampz],
+        T::Interval,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::Add, E::Subtract],
+        &[T::Interval],
+        &[T::Interval],
+        T::Interval,
+    );
     build_commutative_binary_funcs(
         &mut map,
         &[E::Multiply],
@@ -365,6 +404,74 @@ fn build_type_derive_map() -> HashMap<FuncSign, DataTypeName> {
         &[T::Interval],
         T::Timestamp,
     );
+    build_binary_funcs(
+        &mut map,
+        &[E::DateTrunc],
+        &[T::Varchar], // Time field, "year", "day", etc
+        &[T::Timestamp],
+        T::Timestamp,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::DateTrunc],
+        &[T::Varchar],
+        &[T::Timestampz],
+        T::Timestampz,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::ToChar],
+        &[T::Timestamp, T::Timestampz],
+        &[T::Varchar],
+        T::Varchar,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::ToTimestamp],
+        &[T::Varchar],
+        &[T::Varchar],
+        T::Timestampz,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::Age],
+        &[T::Timestamp],
+        &[T::Timestamp],
+        T::Interval,
+    );
+    build_ternary_funcs(
+        &mut map,
+        &[E::MakeDate],
+        &[T::Int32],
+        &[T::Int32],
+        &[T::Int32],
+        T::Date,
+    );
+    build_unary_funcs(&mut map, &[E::JustifyInterval], &[T::Interval], T::Interval);
+    map.insert(FuncSign::new_no_input(E::Now), T::Timestampz);
+
+    // Jsonb operators. `jsonb_extract_path` is variadic and checked when binding.
+    build_binary_funcs(
+        &mut map,
+        &[E::JsonbAccessInner],
+        &[T::Jsonb],
+        &[T::Varchar, T::Int32],
+        T::Jsonb,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::JsonbAccessStr],
+        &[T::Jsonb],
+        &[T::Varchar, T::Int32],
+        T::Varchar,
+    );
+    build_binary_funcs(
+        &mut map,
+        &[E::JsonbContains],
+        &[T::Jsonb],
+        &[T::Jsonb],
+        T::Boolean,
+    );
     map
 }
 
@@ -392,7 +499,7 @@ pub fn least_restrictive(lhs: DataType, rhs: DataType) -> Result<DataType> {
 /// The context a cast operation is invoked in. An implicit cast operation is allowed in a context
 /// that allows explicit casts, but not vice versa. See details in
 /// [PG](https://www.postgresql.org/docs/current/catalog-pg-cast.html).
-#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum CastContext {
     Implicit,
     Assign,
@@ -452,6 +559,7 @@ fn build_cast_map() -> HashMap<(DataTypeName, DataTypeName), CastContext> {
         T::Timestampz,
         T::Time,
         T::Interval,
+        T::Jsonb,
     ] {
         m.insert((t, T::Varchar), CastContext::Assign);
         // Casting from string is explicit-only in PG.
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use fixedbitset::FixedBitSet;
 use risingwave_common::types::ScalarImpl;
 use risingwave_pb::expr::expr_node::Type;
@@ -259,13 +261,66 @@ impl CollectInputRef {
     }
 }
 
+/// Collect the nested field paths through which each `InputRef` is read, e.g. `((a).b).c` is
+/// recorded as the path `[b, c]` of `a`. A bare `InputRef` is recorded as an empty path, meaning
+/// the whole column is used.
+#[derive(Default)]
+pub struct CollectFieldAccess {
+    paths: HashMap<usize, Vec<Vec<usize>>>,
+}
+
+impl ExprVisitor for CollectFieldAccess {
+    fn visit_input_ref(&mut self, expr: &InputRef) {
+        self.paths.entry(expr.index()).or_default().push(vec![]);
+    }
+
+    fn visit_function_call(&mut self, func_call: &FunctionCall) {
+        if let Some((index, path)) = Self::field_path(func_call) {
+            self.paths.entry(index).or_default().push(path);
+        } else {
+            func_call
+                .inputs()
+                .iter()
+                .for_each(|expr| self.visit_expr(expr))
+        }
+    }
+}
+
+impl CollectFieldAccess {
+    /// Returns the input column and field path if `func_call` is a chain of field accesses on an
+    /// `InputRef`.
+    fn field_path(func_call: &FunctionCall) -> Option<(usize, Vec<usize>)> {
+        if func_call.get_expr_type() != ExprType::Field {
+            return None;
+        }
+        let field = match func_call.inputs()[1].as_literal()?.get_data() {
+            Some(ScalarImpl::Int32(field)) => *field as usize,
+            _ => return None,
+        };
+        match &func_call.inputs()[0] {
+            ExprImpl::InputRef(input) => Some((input.index(), vec![field])),
+            ExprImpl::FunctionCall(inner) => {
+                let (index, mut path) = Self::field_path(inner)?;
+                path.push(field);
+                Some((index, path))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the collected field paths, keyed by the index of the `InputRef`.
+    pub fn collect(self) -> HashMap<usize, Vec<Vec<usize>>> {
+        self.paths
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_pb::expr::expr_node::Type;
 
-    use super::{fold_boolean_constant, push_down_not};
-    use crate::expr::{ExprImpl, FunctionCall, InputRef};
+    use super::{fold_boolean_constant, push_down_not, CollectFieldAccess};
+    use crate::expr::{ExprImpl, ExprVisitor, FunctionCall, InputRef};
 
     #[test]
     fn constant_boolean_folding_basic_and() {
@@ -450,4 +505,47 @@ mod tests {
         assert_eq!(rhs_type, Type::Not);
         assert!(rhs_input.as_input_ref().is_some());
     }
+
+    #[test]
+    fn collect_field_access() {
+        let inner = DataType::new_struct(
+            vec![DataType::Varchar, DataType::Int32],
+            vec!["x".to_string(), "y".to_string()],
+        );
+        let outer = DataType::new_struct(
+            vec![inner, DataType::Int32],
+            vec!["a".to_string(), "b".to_string()],
+        );
+        // expr := ((#0).a).y = 1 AND #1 > 1
+        let field: ExprImpl = FunctionCall::new_field(
+            FunctionCall::new_field(InputRef::new(0, outer).into(), 0).into(),
+            1,
+        )
+        .into();
+        let expr: ExprImpl = FunctionCall::new(
+            Type::And,
+            vec![
+                FunctionCall::new(Type::Equal, vec![field, ExprImpl::literal_int(1)])
+                    .unwrap()
+                    .into(),
+                FunctionCall::new(
+                    Type::GreaterThan,
+                    vec![
+                        InputRef::new(1, DataType::Int32).into(),
+                        ExprImpl::literal_int(1),
+                    ],
+                )
+                .unwrap()
+                .into(),
+            ],
+        )
+        .unwrap()
+        .into();
+
+        let mut visitor = CollectFieldAccess::default();
+        visitor.visit_expr(&expr);
+        let paths = visitor.collect();
+        assert_eq!(paths[&0], vec![vec![0, 1]]);
+        assert_eq!(paths[&1], vec![Vec::<usize>::new()]);
+    }
 }
@@ -0,0 +1,85 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_sqlparser::ast::{AlterMaterializedViewOperation, ObjectName};
+
+use super::alter_source::bind_rate_limit;
+use crate::binder::Binder;
+use crate::session::OptimizerContext;
+
+/// Changes how many snapshot rows per second each chain actor of the materialized view backfills.
+pub(super) async fn handle_alter_mv(
+    context: OptimizerContext,
+    name: ObjectName,
+    operation: AlterMaterializedViewOperation,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, table_name) = Binder::resolve_table_name(name)?;
+
+    let table_id = {
+        let reader = session.env().catalog_reader().read_guard();
+        let table = reader.get_table_by_name(session.database(), &schema_name, &table_name)?;
+        if table.associated_source_id().is_some() {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "\"{}\" is a table, only materialized views can be altered",
+                table_name
+            ))));
+        }
+        table.id()
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    match operation {
+        AlterMaterializedViewOperation::SetRateLimit(rate_limit) => {
+            let rate_limit = bind_rate_limit(rate_limit)?;
+            catalog_writer
+                .alter_materialized_view_rate_limit(table_id, rate_limit)
+                .await?
+        }
+    }
+
+    Ok(PgResponse::empty_result(
+        StatementType::ALTER_MATERIALIZED_VIEW,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_alter_mv_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int)").await.unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1 from t")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter materialized view mv set rate_limit = 100")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter materialized view mv set rate_limit = default")
+            .await
+            .unwrap();
+
+        // Tables cannot be altered as materialized views.
+        assert!(frontend
+            .run_sql("alter materialized view t set rate_limit = 100")
+            .await
+            .is_err());
+    }
+}
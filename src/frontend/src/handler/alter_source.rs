@@ -0,0 +1,116 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_pb::stream_plan::source_node::SourceType;
+use risingwave_sqlparser::ast::{AlterSourceOperation, ObjectName};
+
+use crate::binder::Binder;
+use crate::session::OptimizerContext;
+
+/// Validates the rate limit of `SET RATE_LIMIT`, where `None` stands for `DEFAULT`, i.e. no limit.
+pub(super) fn bind_rate_limit(rate_limit: Option<u64>) -> Result<Option<u32>> {
+    rate_limit
+        .map(|rate_limit| {
+            u32::try_from(rate_limit)
+                .ok()
+                .filter(|rate_limit| *rate_limit > 0)
+                .ok_or_else(|| {
+                    RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                        "rate limit must be a positive 32-bit integer, got {}",
+                        rate_limit
+                    )))
+                })
+        })
+        .transpose()
+}
+
+/// Pauses or resumes the actors reading the source, or changes how many rows per second each of
+/// them reads. Barriers keep flowing through the paused actors, so the materialized views on the
+/// source are still checkpointed.
+pub(super) async fn handle_alter_source(
+    context: OptimizerContext,
+    source_name: ObjectName,
+    operation: AlterSourceOperation,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, source_name) = Binder::resolve_table_name(source_name)?;
+
+    let source_id = {
+        let reader = session.env().catalog_reader().read_guard();
+        let source = reader.get_source_by_name(session.database(), &schema_name, &source_name)?;
+        if source.source_type == SourceType::Table {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "\"{}\" is a table, only sources can be altered",
+                source_name
+            ))));
+        }
+        source.id
+    };
+
+    let client = session.env().meta_client();
+    match operation {
+        AlterSourceOperation::Pause => client.pause_sources(vec![source_id]).await?,
+        AlterSourceOperation::Resume => client.resume_sources(vec![source_id]).await?,
+        AlterSourceOperation::SetRateLimit(rate_limit) => {
+            let rate_limit = bind_rate_limit(rate_limit)?;
+            session
+                .env()
+                .catalog_writer()
+                .alter_source_rate_limit(source_id, rate_limit)
+                .await?
+        }
+    }
+
+    Ok(PgResponse::empty_result(StatementType::ALTER_SOURCE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::handler::create_source::tests::create_proto_file;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_alter_source_handler() {
+        let proto_file = create_proto_file();
+        let sql = format!(
+            r#"CREATE SOURCE s
+    WITH ('kafka.topic' = 'abc', 'kafka.servers' = 'localhost:1001')
+    ROW FORMAT PROTOBUF MESSAGE '.test.TestRecord' ROW SCHEMA LOCATION 'file://{}'"#,
+            proto_file.path().to_str().unwrap()
+        );
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql(sql).await.unwrap();
+        frontend.run_sql("alter source s pause").await.unwrap();
+        frontend.run_sql("alter source s resume").await.unwrap();
+        frontend
+            .run_sql("alter source s set rate_limit = 1000")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter source s set rate_limit = default")
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql("alter source s set rate_limit = 0")
+            .await
+            .is_err());
+
+        // Tables cannot be paused.
+        frontend.run_sql("create table t (v1 int)").await.unwrap();
+        assert!(frontend.run_sql("alter source t pause").await.is_err());
+        // Unknown sources are rejected.
+        assert!(frontend.run_sql("alter source s2 pause").await.is_err());
+    }
+}
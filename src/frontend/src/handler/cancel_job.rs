@@ -0,0 +1,100 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::Result;
+
+use crate::session::OptimizerContext;
+
+/// Cancels a materialized view being created in background. The job id is the table id shown
+/// by `SHOW JOBS`.
+pub(super) async fn handle_cancel_job(
+    context: OptimizerContext,
+    job_id: u32,
+) -> Result<PgResponse> {
+    let client = context.session_ctx.env().meta_client();
+    client.cancel_job(job_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::CANCEL_JOB))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_cancel_job() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v int)").await.unwrap();
+        frontend
+            .run_sql("create materialized view mv with (background = true) as select v from t")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv2 as select v from t")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let table_id = |name: &str| {
+            session
+                .env()
+                .catalog_reader()
+                .read_guard()
+                .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, name)
+                .unwrap()
+                .id()
+                .table_id
+        };
+        let (mv_id, mv2_id) = (table_id("mv"), table_id("mv2"));
+
+        // Only the materialized views being created can be cancelled.
+        for job_id in [mv2_id, 10000] {
+            let err = frontend
+                .run_sql(format!("cancel job {}", job_id))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("not being created"), "{}", err);
+        }
+        assert!(frontend.run_sql("cancel job mv").await.is_err());
+
+        let jobs = frontend.query_formatted_result("show jobs").await;
+        assert_eq!(jobs.len(), 1);
+        assert!(jobs[0].contains("\"mv\""), "{:?}", jobs);
+
+        frontend
+            .run_sql(format!("cancel job {}", mv_id))
+            .await
+            .unwrap();
+        // The cancelled materialized view is dropped from both the catalog and the jobs listed.
+        assert!(session
+            .env()
+            .catalog_reader()
+            .read_guard()
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "mv")
+            .is_err());
+        assert!(frontend
+            .query_formatted_result("show jobs")
+            .await
+            .is_empty());
+        let err = frontend.run_sql("select * from mv").await.unwrap_err();
+        assert!(!err.to_string().contains("still being created"), "{}", err);
+        assert!(frontend
+            .run_sql(format!("cancel job {}", mv_id))
+            .await
+            .is_err());
+    }
+}
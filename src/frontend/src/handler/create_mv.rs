@@ -13,22 +13,61 @@
 // limitations under the License.
 
 use pgwire::pg_response::{PgResponse, StatementType};
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_pb::catalog::Table as ProstTable;
-use risingwave_sqlparser::ast::{ObjectName, Query};
+use risingwave_sqlparser::ast::{ObjectName, Query, SqlOption, Value};
 
+use super::create_source::{parse_rate_limit, RATE_LIMIT_OPTION};
 use crate::binder::Binder;
 use crate::optimizer::property::Distribution;
 use crate::optimizer::PlanRef;
 use crate::planner::Planner;
 use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
 
+/// Extracts the backfill rate limit from the `WITH` options of a materialized view.
+pub fn get_rate_limit(with_options: &[SqlOption]) -> Result<Option<u32>> {
+    with_options
+        .iter()
+        .find(|option| option.name.value.eq_ignore_ascii_case(RATE_LIMIT_OPTION))
+        .map(|option| match &option.value {
+            Value::Number(value, _) | Value::SingleQuotedString(value) => parse_rate_limit(value),
+            value => Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "{} must be a positive integer, got {}",
+                RATE_LIMIT_OPTION, value
+            )))),
+        })
+        .transpose()
+}
+
+pub const BACKGROUND_OPTION: &str = "background";
+
+/// Extracts whether the materialized view should be created in background from the `WITH`
+/// options. Defaults to `false`.
+pub fn get_background(with_options: &[SqlOption]) -> Result<bool> {
+    with_options
+        .iter()
+        .find(|option| option.name.value.eq_ignore_ascii_case(BACKGROUND_OPTION))
+        .map(|option| match &option.value {
+            Value::Boolean(value) => Ok(*value),
+            Value::SingleQuotedString(value) if value.eq_ignore_ascii_case("true") => Ok(true),
+            Value::SingleQuotedString(value) if value.eq_ignore_ascii_case("false") => Ok(false),
+            value => Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "{} must be a boolean, got {}",
+                BACKGROUND_OPTION, value
+            )))),
+        })
+        .transpose()
+        .map(|background| background.unwrap_or(false))
+}
+
 /// Generate create MV plan, return plan and mv table info.
 pub fn gen_create_mv_plan(
     session: &SessionImpl,
     context: OptimizerContextRef,
     query: Box<Query>,
     name: ObjectName,
+    emit_on_window_close: bool,
+    rate_limit: Option<u32>,
 ) -> Result<(PlanRef, ProstTable)> {
     let (schema_name, table_name) = Binder::resolve_table_name(name)?;
     let (database_id, schema_id) = session
@@ -47,6 +86,8 @@ pub fn gen_create_mv_plan(
 
     let mut plan_root = Planner::new(context).plan_query(bound)?;
     plan_root.set_required_dist(Distribution::any().clone());
+    plan_root.set_emit_on_window_close(emit_on_window_close);
+    plan_root.set_rate_limit(rate_limit);
     let materialize = plan_root.gen_create_mv_plan(table_name)?;
     let table = materialize.table().to_prost(schema_id, database_id);
     let plan: PlanRef = materialize.into();
@@ -58,21 +99,193 @@ pub async fn handle_create_mv(
     context: OptimizerContext,
     name: ObjectName,
     query: Box<Query>,
+    emit_on_window_close: bool,
+    with_options: Vec<SqlOption>,
 ) -> Result<PgResponse> {
     let session = context.session_ctx.clone();
+    let rate_limit = get_rate_limit(&with_options)?;
+    let background = get_background(&with_options)?;
 
     let (table, stream_plan) = {
-        let (plan, table) = gen_create_mv_plan(&session, context.into(), query, name)?;
+        let (plan, table) = gen_create_mv_plan(
+            &session,
+            context.into(),
+            query,
+            name,
+            emit_on_window_close,
+            rate_limit,
+        )?;
         let stream_plan = plan.to_stream_prost();
         (table, stream_plan)
     };
 
     let catalog_writer = session.env().catalog_writer();
     catalog_writer
-        .create_materialized_view(table, stream_plan)
+        .create_materialized_view(table, stream_plan, background)
         .await?;
 
     Ok(PgResponse::empty_result(
         StatementType::CREATE_MATERIALIZED_VIEW,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_mv_emit_on_window_close() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql(
+                "create source s (v int, ts timestamp, watermark for ts as ts - interval '5' second)
+    with ('kafka.topic' = 'abc', 'kafka.servers' = 'localhost:1001') row format json",
+            )
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table t (v int, ts timestamp)")
+            .await
+            .unwrap();
+
+        let plan = frontend
+            .query_formatted_result(
+                "explain create materialized view mv as select ts, count(*) from s group by ts emit on window close",
+            )
+            .await
+            .join("\n");
+        assert!(plan.contains("emit_on_window_close: true"), "{}", plan);
+
+        let err = frontend
+            .run_sql("create materialized view mv2 as select v from s emit on window close")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("EMIT ON WINDOW CLOSE"), "{}", err);
+
+        // The windows are never closed without a watermark on the group keys.
+        for sql in [
+            "create materialized view mv3 as select v, count(*) from s group by v emit on window close",
+            "create materialized view mv4 as select ts, count(*) from t group by ts emit on window close",
+        ] {
+            let err = frontend.run_sql(sql).await.unwrap_err();
+            assert!(err.to_string().contains("watermark"), "{}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_mv_with_rate_limit() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v int)").await.unwrap();
+
+        let plan = frontend
+            .query_formatted_result(
+                "explain create materialized view mv with (rate_limit = 100) as select v from t",
+            )
+            .await
+            .join("\n");
+        assert!(plan.contains("rate_limit: 100"), "{}", plan);
+
+        frontend
+            .run_sql("create materialized view mv with (rate_limit = 100) as select v from t")
+            .await
+            .unwrap();
+        let err = frontend
+            .run_sql("create materialized view mv2 with (rate_limit = 0) as select v from t")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("rate_limit"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_create_mv_in_background() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v int)").await.unwrap();
+
+        frontend
+            .run_sql("create materialized view mv with (background = true) as select v from t")
+            .await
+            .unwrap();
+        let err = frontend.run_sql("select * from mv").await.unwrap_err();
+        assert!(err.to_string().contains("still being created"), "{}", err);
+
+        frontend
+            .run_sql("create materialized view mv2 with (background = 'false') as select v from t")
+            .await
+            .unwrap();
+        frontend.run_sql("select * from mv2").await.unwrap();
+
+        let err = frontend
+            .run_sql("create materialized view mv3 with (background = 1) as select v from t")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("background"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_create_mv_distinct_on() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql(
+                "create source s (k int, v int) with ('kafka.topic' = 'abc', 'kafka.servers' = 'localhost:1001') row format json",
+            )
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table t (k int, v int)")
+            .await
+            .unwrap();
+
+        let plan = frontend
+            .query_formatted_result(
+                "explain create materialized view mv as select distinct on (k) k, v from s",
+            )
+            .await
+            .join("\n");
+        assert!(
+            plan.contains("StreamAppendOnlyDedup { dedup_cols: [$0] }"),
+            "{}",
+            plan
+        );
+
+        // Tables are not append-only.
+        let err = frontend
+            .run_sql("create materialized view mv2 as select distinct on (k) k, v from t")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not append-only"), "{}", err);
+
+        let err = frontend
+            .run_sql("select distinct on (k) k, v from t")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("DISTINCT ON"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_create_mv_dynamic_filter() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (k int, v int)")
+            .await
+            .unwrap();
+
+        let plan = frontend
+            .query_formatted_result(
+                "explain create materialized view mv as select * from t where v > (select avg(v) from t)",
+            )
+            .await
+            .join("\n");
+        assert!(plan.contains("StreamDynamicFilter"), "{}", plan);
+        assert!(
+            plan.contains("StreamExchange { dist: Broadcast }"),
+            "{}",
+            plan
+        );
+
+        let err = frontend
+            .run_sql("select * from t where v > (select avg(v) from t)")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("scalar subquery"), "{}", err);
+    }
+}
@@ -16,10 +16,10 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
-use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::ErrorCode::{InvalidInputSyntax, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::source::Info;
-use risingwave_pb::catalog::{Source as ProstSource, StreamSourceInfo};
+use risingwave_pb::catalog::{Source as ProstSource, StreamSourceInfo, WatermarkDesc};
 use risingwave_pb::plan::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
 use risingwave_source::ProtobufParser;
 use risingwave_sqlparser::ast::{
@@ -29,6 +29,7 @@ use risingwave_sqlparser::ast::{
 use super::create_table::{bind_sql_columns, gen_materialized_source_plan};
 use crate::binder::Binder;
 use crate::catalog::column_catalog::ColumnCatalog;
+use crate::expr::Expr;
 use crate::session::{OptimizerContext, SessionImpl};
 
 pub(crate) fn make_prost_source(
@@ -79,12 +80,26 @@ fn handle_source_with_properties(options: Vec<SqlOption>) -> Result<HashMap<Stri
         .collect()
 }
 
+/// The `WITH` option limiting the rows per second read by each source actor, or backfilled by each
+/// chain actor of a materialized view.
+pub(super) const RATE_LIMIT_OPTION: &str = "rate_limit";
+
+pub(super) fn parse_rate_limit(value: &str) -> Result<u32> {
+    match value.parse::<u32>() {
+        Ok(rate_limit) if rate_limit > 0 => Ok(rate_limit),
+        _ => Err(RwError::from(InvalidInputSyntax(format!(
+            "{} must be a positive integer, got \"{}\"",
+            RATE_LIMIT_OPTION, value
+        )))),
+    }
+}
+
 pub(super) async fn handle_create_source(
     context: OptimizerContext,
     is_materialized: bool,
     stmt: CreateSourceStatement,
 ) -> Result<PgResponse> {
-    let source = match &stmt.source_schema {
+    let mut source = match &stmt.source_schema {
         SourceSchema::Protobuf(protobuf_schema) => {
             let mut columns = vec![ColumnCatalog::row_id_column().to_protobuf()];
             columns.extend(extract_protobuf_table_schema(protobuf_schema)?.into_iter());
@@ -95,6 +110,8 @@ pub(super) async fn handle_create_source(
                 row_id_index: 0,
                 columns,
                 pk_column_ids: vec![0],
+                watermark_descs: vec![],
+                rate_limit: 0,
             }
         }
         SourceSchema::Json => StreamSourceInfo {
@@ -104,10 +121,41 @@ pub(super) async fn handle_create_source(
             row_id_index: 0,
             columns: bind_sql_columns(stmt.columns)?,
             pk_column_ids: vec![0],
+            watermark_descs: vec![],
+            rate_limit: 0,
         },
     };
 
+    if let Some(rate_limit) = source.properties.remove(RATE_LIMIT_OPTION) {
+        source.rate_limit = parse_rate_limit(&rate_limit)?;
+    }
+
     let session = context.session_ctx.clone();
+    if !stmt.source_watermarks.is_empty() {
+        let columns = source
+            .columns
+            .iter()
+            .cloned()
+            .map(ColumnCatalog::from)
+            .collect_vec();
+        let mut binder = Binder::new(
+            session.env().catalog_reader().read_guard(),
+            session.database().to_string(),
+        );
+        source.watermark_descs = binder
+            .bind_source_watermarks(
+                stmt.source_name.to_string(),
+                &columns,
+                stmt.source_watermarks,
+            )?
+            .into_iter()
+            .map(|watermark| WatermarkDesc {
+                watermark_idx: watermark.column_idx as u32,
+                expr: Some(watermark.expr.to_protobuf()),
+            })
+            .collect();
+    }
+
     let source = make_prost_source(&session, stmt.source_name, Info::StreamSource(source))?;
     let catalog_writer = session.env().catalog_writer();
     if is_materialized {
@@ -205,9 +253,18 @@ pub mod tests {
             .map(|col| (col.name.as_str(), col.data_type.clone()))
             .collect::<HashMap<&str, DataType>>();
 
-        let city_type = DataType::Struct {
-            fields: vec![DataType::Varchar, DataType::Varchar].into(),
-        };
+        let city_type = DataType::new_struct(
+            vec![DataType::Varchar, DataType::Varchar],
+            vec!["address".to_string(), "zipcode".to_string()],
+        );
+        let country_type = DataType::new_struct(
+            vec![DataType::Varchar, city_type.clone(), DataType::Varchar],
+            vec![
+                "address".to_string(),
+                "city".to_string(),
+                "zipcode".to_string(),
+            ],
+        );
         let row_id_col_name = gen_row_id_column_name(0);
         let expected_columns = maplit::hashmap! {
             row_id_col_name.as_str() => DataType::Int64,
@@ -219,8 +276,74 @@ pub mod tests {
             "country.city" => city_type.clone(),
             "country.city.zipcode" => DataType::Varchar,
             "rate" => DataType::Float32,
-            "country" => DataType::Struct {fields:vec![DataType::Varchar,city_type,DataType::Varchar].into()},
+            "country" => country_type,
         };
         assert_eq!(columns, expected_columns);
     }
+    #[tokio::test]
+    async fn test_create_source_with_watermark() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql(
+                "CREATE SOURCE t (v INT, ts TIMESTAMP, WATERMARK FOR ts AS ts - INTERVAL '5' SECOND)
+    WITH ('kafka.topic' = 'abc', 'kafka.servers' = 'localhost:1001') ROW FORMAT JSON",
+            )
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let source = session
+            .env()
+            .catalog_reader()
+            .read_guard()
+            .get_source_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "t")
+            .unwrap()
+            .clone();
+        assert!(source.append_only);
+        // The hidden row id column comes first.
+        assert_eq!(source.watermark_descs.len(), 1);
+        assert_eq!(source.watermark_descs[0].watermark_idx, 2);
+
+        let err = frontend
+            .run_sql(
+                "CREATE SOURCE t2 (v INT, ts TIMESTAMP, WATERMARK FOR ts AS v)
+    WITH ('kafka.topic' = 'abc', 'kafka.servers' = 'localhost:1001') ROW FORMAT JSON",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("watermark expression"));
+    }
+
+    #[tokio::test]
+    async fn test_create_source_with_rate_limit() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql(
+                "CREATE SOURCE t (v INT)
+    WITH ('kafka.topic' = 'abc', 'kafka.servers' = 'localhost:1001', 'rate_limit' = '1000')
+    ROW FORMAT JSON",
+            )
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let source = session
+            .env()
+            .catalog_reader()
+            .read_guard()
+            .get_source_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "t")
+            .unwrap()
+            .clone();
+        assert_eq!(source.rate_limit, Some(1000));
+
+        let err = frontend
+            .run_sql(
+                "CREATE SOURCE t2 (v INT)
+    WITH ('kafka.topic' = 'abc', 'kafka.servers' = 'localhost:1001', 'rate_limit' = '0')
+    ROW FORMAT JSON",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("rate_limit"), "{}", err);
+    }
 }
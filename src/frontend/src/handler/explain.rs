@@ -18,7 +18,7 @@ use pgwire::types::Row;
 use risingwave_common::error::Result;
 use risingwave_sqlparser::ast::Statement;
 
-use super::create_mv::gen_create_mv_plan;
+use super::create_mv::{gen_create_mv_plan, get_rate_limit};
 use super::create_table::gen_create_table_plan;
 use crate::binder::Binder;
 use crate::planner::Planner;
@@ -39,8 +39,20 @@ pub(super) fn handle_explain(
             materialized: true,
             query,
             name,
+            emit_on_window_close,
+            with_options,
             ..
-        } => gen_create_mv_plan(&*session, planner.ctx(), query, name)?.0,
+        } => {
+            gen_create_mv_plan(
+                &*session,
+                planner.ctx(),
+                query,
+                name,
+                emit_on_window_close,
+                get_rate_limit(&with_options)?,
+            )?
+            .0
+        }
 
         Statement::CreateTable { name, columns, .. } => {
             gen_create_table_plan(&*session, planner.ctx(), name, columns)?.0
@@ -55,7 +67,7 @@ pub(super) fn handle_explain(
                 binder.bind(stmt)?
             };
             let logical = planner.plan(bound)?;
-            logical.gen_batch_query_plan()
+            logical.gen_batch_query_plan()?
         }
     };
 
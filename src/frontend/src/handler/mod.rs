@@ -20,6 +20,9 @@ use risingwave_sqlparser::ast::{DropStatement, ObjectName, ObjectType, Statement
 
 use crate::session::{OptimizerContext, SessionImpl};
 
+mod alter_mv;
+mod alter_source;
+mod cancel_job;
 pub mod create_mv;
 mod create_source;
 pub mod create_table;
@@ -87,9 +90,21 @@ pub(super) async fn handle(session: Arc<SessionImpl>, stmt: Statement) -> Result
             or_replace: false,
             name,
             query,
+            emit_on_window_close,
+            with_options,
             ..
-        } => create_mv::handle_create_mv(context, name, query).await,
+        } => {
+            create_mv::handle_create_mv(context, name, query, emit_on_window_close, with_options)
+                .await
+        }
         Statement::Flush => flush::handle_flush(context).await,
+        Statement::CancelJob { job_id } => cancel_job::handle_cancel_job(context, job_id).await,
+        Statement::AlterSource { name, operation } => {
+            alter_source::handle_alter_source(context, name, operation).await
+        }
+        Statement::AlterMaterializedView { name, operation } => {
+            alter_mv::handle_alter_mv(context, name, operation).await
+        }
         Statement::SetVariable {
             local: _,
             variable,
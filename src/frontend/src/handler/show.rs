@@ -32,6 +32,10 @@ pub async fn handle_show_object(
     context: OptimizerContext,
     command: ShowObject,
 ) -> Result<PgResponse> {
+    if command == ShowObject::Jobs {
+        return handle_show_jobs(context).await;
+    }
+
     let session = context.session_ctx;
     let catalog_reader = session.env().catalog_reader().read_guard();
 
@@ -60,6 +64,7 @@ pub async fn handle_show_object(
             .iter_materialized_source()
             .map(|t| t.name.clone())
             .collect(),
+        ShowObject::Jobs => unreachable!(),
     };
 
     let rows = names
@@ -75,8 +80,53 @@ pub async fn handle_show_object(
     ))
 }
 
+/// Shows the materialized views being created, with the progress of consuming the snapshots of
+/// their upstream materialized views, and the ones whose creations failed.
+async fn handle_show_jobs(context: OptimizerContext) -> Result<PgResponse> {
+    let jobs = context.session_ctx.env().meta_client().list_jobs().await?;
+
+    let rows = jobs
+        .into_iter()
+        .map(|job| {
+            Row::new(vec![
+                Some(job.table_id.to_string()),
+                Some(job.name),
+                Some(if job.failure.is_empty() {
+                    format_progress(job.consumed_rows, job.estimated_rows)
+                } else {
+                    format!("failed: {}", job.failure)
+                }),
+            ])
+        })
+        .collect_vec();
+
+    Ok(PgResponse::new(
+        StatementType::SHOW_COMMAND,
+        rows.len() as i32,
+        rows,
+        vec![
+            PgFieldDescriptor::new("Id".to_owned(), TypeOid::Int),
+            PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Progress".to_owned(), TypeOid::Varchar),
+        ],
+    ))
+}
+
+fn format_progress(consumed_rows: u64, estimated_rows: u64) -> String {
+    let percentage = if estimated_rows == 0 {
+        0.0
+    } else {
+        consumed_rows as f64 * 100.0 / estimated_rows as f64
+    };
+    format!(
+        "{:.2}% ({}/{} rows)",
+        percentage, consumed_rows, estimated_rows
+    )
+}
+
 #[cfg(test)]
 mod tests {
+    use super::format_progress;
     use crate::test_utils::LocalFrontend;
 
     #[tokio::test]
@@ -108,4 +158,15 @@ mod tests {
             .await;
         assert_eq!(rows, vec!["Row([Some(\"t2\")])".to_string()]);
     }
+
+    #[tokio::test]
+    async fn test_show_jobs() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let rows = frontend.query_formatted_result("SHOW JOBS").await;
+        assert!(rows.is_empty());
+
+        assert_eq!(format_progress(4520, 10000), "45.20% (4520/10000 rows)");
+        assert_eq!(format_progress(0, 0), "0.00% (0/0 rows)");
+    }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use risingwave_common::error::Result;
+use risingwave_pb::meta::list_jobs_response::Job;
 use risingwave_rpc_client::{HummockMetaClient, MetaClient};
 
 /// A wrapper around the `MetaClient` that only provides a minor set of meta rpc.
@@ -26,6 +27,14 @@ pub trait FrontendMetaClient: Send + Sync {
 
     async fn flush(&self) -> Result<()>;
 
+    async fn pause_sources(&self, source_ids: Vec<u32>) -> Result<()>;
+
+    async fn resume_sources(&self, source_ids: Vec<u32>) -> Result<()>;
+
+    async fn list_jobs(&self) -> Result<Vec<Job>>;
+
+    async fn cancel_job(&self, table_id: u32) -> Result<()>;
+
     async fn unpin_snapshot(&self, epoch: u64) -> Result<()>;
 }
 
@@ -41,6 +50,22 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
         self.0.flush().await
     }
 
+    async fn pause_sources(&self, source_ids: Vec<u32>) -> Result<()> {
+        self.0.pause(source_ids).await
+    }
+
+    async fn resume_sources(&self, source_ids: Vec<u32>) -> Result<()> {
+        self.0.resume(source_ids).await
+    }
+
+    async fn list_jobs(&self) -> Result<Vec<Job>> {
+        self.0.list_jobs().await
+    }
+
+    async fn cancel_job(&self, table_id: u32) -> Result<()> {
+        self.0.cancel_job(table_id).await
+    }
+
     async fn unpin_snapshot(&self, epoch: u64) -> Result<()> {
         self.0.unpin_snapshot(&[epoch]).await
     }
@@ -136,6 +136,7 @@ impl ObserverManager {
                 Operation::Delete => {
                     catalog_guard.drop_table(table.database_id, table.schema_id, table.id.into())
                 }
+                Operation::Update => catalog_guard.update_table(table),
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
             Some(Info::Source(source)) => match resp.operation() {
@@ -143,6 +144,7 @@ impl ObserverManager {
                 Operation::Delete => {
                     catalog_guard.drop_source(source.database_id, source.schema_id, source.id)
                 }
+                Operation::Update => catalog_guard.update_source(source.clone()),
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
             Some(Info::FeSnapshot(_)) => {
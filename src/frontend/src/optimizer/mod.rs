@@ -25,12 +25,17 @@ use fixedbitset::FixedBitSet;
 use itertools::Itertools as _;
 use property::{Distribution, Order};
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
 
 use self::heuristic::{ApplyOrder, HeuristicOptimizer};
-use self::plan_node::{Convention, LogicalProject, StreamMaterialize};
+use self::plan_node::{
+    Convention, LogicalDedup, LogicalDynamicFilter, LogicalProject, PlanTreeNode,
+    PlanTreeNodeUnary, StreamDedup, StreamHashAgg, StreamMaterialize, StreamTableScan,
+};
+use self::plan_rewriter::PlanRewriter;
+use self::plan_visitor::PlanVisitor;
 use self::rule::*;
-use crate::expr::InputRef;
+use crate::expr::{ExprImpl, ExprType, InputRef};
 
 /// `PlanRoot` is used to describe a plan. planner will construct a `PlanRoot` with `LogicalNode`.
 /// and required distribution and order. And `PlanRoot` can generate corresponding streaming or
@@ -49,6 +54,8 @@ pub struct PlanRoot {
     required_order: Order,
     out_fields: FixedBitSet,
     schema: Schema,
+    emit_on_window_close: bool,
+    rate_limit: Option<u32>,
 }
 
 impl PlanRoot {
@@ -73,6 +80,8 @@ impl PlanRoot {
             required_order,
             out_fields,
             schema,
+            emit_on_window_close: false,
+            rate_limit: None,
         }
     }
 
@@ -142,9 +151,19 @@ impl PlanRoot {
     }
 
     /// optimize and generate a batch query plan
-    pub fn gen_batch_query_plan(&self) -> PlanRef {
+    pub fn gen_batch_query_plan(&self) -> Result<PlanRef> {
         let mut plan = self.gen_optimized_logical_plan();
 
+        let mut finder = StreamOnlyNodeFinder::default();
+        finder.visit(plan.clone());
+        if let Some(feature) = finder.feature {
+            return Err(ErrorCode::NotImplemented(
+                format!("{} in batch queries", feature),
+                None.into(),
+            )
+            .into());
+        }
+
         // Convert to physical plan node
         plan = plan.to_batch_with_order_required(&self.required_order);
 
@@ -154,16 +173,16 @@ impl PlanRoot {
         // TODO: do a final column pruning after add the batch project, but now the column
         // pruning is not used in batch node, need to think.
 
-        plan
+        Ok(plan)
     }
 
     /// Optimize and generate a batch query plan.
     /// Currently only used by test runner (Have distributed plan but not schedule yet).
     /// Will be removed after dist execution.
-    pub fn gen_dist_batch_query_plan(&self) -> PlanRef {
-        let plan = self.gen_batch_query_plan();
+    pub fn gen_dist_batch_query_plan(&self) -> Result<PlanRef> {
+        let plan = self.gen_batch_query_plan()?;
 
-        plan.to_distributed_with_required(&self.required_order, &self.required_dist)
+        Ok(plan.to_distributed_with_required(&self.required_order, &self.required_dist))
     }
 
     /// Optimize and generate a create materialize view plan.
@@ -191,6 +210,42 @@ impl PlanRoot {
             _ => panic!(),
         };
 
+        let stream_plan = if self.emit_on_window_close {
+            let mut rewriter = EmitOnWindowCloseRewriter::default();
+            let stream_plan = rewriter.rewrite(stream_plan);
+            if !rewriter.has_hash_agg {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "EMIT ON WINDOW CLOSE requires an aggregation with GROUP BY".to_string(),
+                )
+                .into());
+            }
+            if rewriter.missing_watermark {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "EMIT ON WINDOW CLOSE requires a watermark on one of the GROUP BY columns"
+                        .to_string(),
+                )
+                .into());
+            }
+            stream_plan
+        } else {
+            stream_plan
+        };
+
+        let stream_plan = match self.rate_limit {
+            Some(rate_limit) => RateLimitRewriter { rate_limit }.rewrite(stream_plan),
+            None => stream_plan,
+        };
+
+        let mut finder = RetractableDedupFinder::default();
+        finder.visit(stream_plan.clone());
+        if finder.has_retractable_dedup {
+            return Err(ErrorCode::NotImplemented(
+                "DISTINCT ON on a stream which is not append-only".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+
         // Ignore the required_dist and required_order, as they are provided by user now.
         // TODO: need more thinking and refactor.
 
@@ -209,6 +264,122 @@ impl PlanRoot {
     pub fn set_required_dist(&mut self, required_dist: Distribution) {
         self.required_dist = required_dist;
     }
+
+    /// Set whether the aggregations in the stream plan should `EMIT ON WINDOW CLOSE`.
+    pub fn set_emit_on_window_close(&mut self, emit_on_window_close: bool) {
+        self.emit_on_window_close = emit_on_window_close;
+    }
+
+    /// Set the rate limit, in rows per second, of the backfill of the tables scanned by the stream
+    /// plan.
+    pub fn set_rate_limit(&mut self, rate_limit: Option<u32>) {
+        self.rate_limit = rate_limit;
+    }
+}
+
+/// Marks all the [`StreamHashAgg`]s in the plan to `EMIT ON WINDOW CLOSE`. The nodes above them
+/// are rebuilt, so that they know their input becomes append-only.
+#[derive(Default)]
+struct EmitOnWindowCloseRewriter {
+    has_hash_agg: bool,
+    /// Whether any of the aggregations has no watermark on its group keys, whose windows are
+    /// never closed.
+    missing_watermark: bool,
+}
+
+impl PlanRewriter for EmitOnWindowCloseRewriter {
+    fn rewrite_stream_hash_agg(&mut self, plan: &StreamHashAgg) -> PlanRef {
+        self.has_hash_agg = true;
+        let watermark_columns = watermark_columns(&plan.input());
+        if !plan
+            .distribution_keys()
+            .iter()
+            .any(|&key| watermark_columns.contains(key))
+        {
+            self.missing_watermark = true;
+        }
+        let input = self.rewrite(plan.input());
+        plan.clone_with_input(input)
+            .with_emit_on_window_close()
+            .into()
+    }
+}
+
+/// Returns the output columns of the stream plan with watermarks. Only the derivations done by the
+/// executors are followed, i.e. the watermarks generated by the sources, and passed through the
+/// filters, the exchanges, and the input references or `TUMBLE_START` of the projections.
+fn watermark_columns(plan: &PlanRef) -> FixedBitSet {
+    if let Some(source) = plan.as_stream_source() {
+        return source.watermark_columns();
+    }
+    if plan.as_stream_filter().is_some() || plan.as_stream_exchange().is_some() {
+        return watermark_columns(&plan.inputs()[0]);
+    }
+
+    let mut columns = FixedBitSet::with_capacity(plan.schema().len());
+    if let Some(project) = plan.as_stream_project() {
+        let input_columns = watermark_columns(&project.input());
+        let input_ref_idx = |expr: &ExprImpl| match expr {
+            ExprImpl::InputRef(input_ref) => Some(input_ref.index()),
+            _ => None,
+        };
+        for (idx, expr) in project.exprs().iter().enumerate() {
+            let input_idx = match expr {
+                ExprImpl::FunctionCall(call) if call.get_expr_type() == ExprType::TumbleStart => {
+                    call.inputs().first().and_then(input_ref_idx)
+                }
+                _ => input_ref_idx(expr),
+            };
+            if let Some(input_idx) = input_idx && input_columns.contains(input_idx) {
+                columns.insert(idx);
+            }
+        }
+    }
+    columns
+}
+
+/// Sets the rate limit of all the [`StreamTableScan`]s in the plan.
+struct RateLimitRewriter {
+    rate_limit: u32,
+}
+
+impl PlanRewriter for RateLimitRewriter {
+    fn rewrite_stream_table_scan(&mut self, plan: &StreamTableScan) -> PlanRef {
+        plan.with_rate_limit(Some(self.rate_limit)).into()
+    }
+}
+
+/// Finds the logical nodes which can only be converted to a stream plan for now, and records the
+/// feature they are planned for.
+#[derive(Default)]
+struct StreamOnlyNodeFinder {
+    feature: Option<&'static str>,
+}
+
+impl PlanVisitor<()> for StreamOnlyNodeFinder {
+    fn visit_logical_dedup(&mut self, _plan: &LogicalDedup) {
+        self.feature = Some("DISTINCT ON");
+    }
+
+    fn visit_logical_dynamic_filter(&mut self, _plan: &LogicalDynamicFilter) {
+        self.feature = Some("comparison with a scalar subquery");
+    }
+}
+
+/// Finds [`StreamDedup`]s whose input is not append-only, as only the first-seen row of each key
+/// is kept and a retraction can not be handled.
+#[derive(Default)]
+struct RetractableDedupFinder {
+    has_retractable_dedup: bool,
+}
+
+impl PlanVisitor<()> for RetractableDedupFinder {
+    fn visit_stream_dedup(&mut self, plan: &StreamDedup) {
+        if !plan.input().append_only() {
+            self.has_retractable_dedup = true;
+        }
+        self.visit(plan.input());
+    }
 }
 
 #[cfg(test)]
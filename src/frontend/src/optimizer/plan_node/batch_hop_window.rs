@@ -0,0 +1,81 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::expr::InputRefExpr;
+use risingwave_pb::plan::plan_node::NodeBody;
+use risingwave_pb::plan::HopWindowNode;
+
+use super::{LogicalHopWindow, PlanRef, PlanTreeNodeUnary, ToBatchProst, ToDistributedBatch};
+use crate::optimizer::plan_node::PlanBase;
+use crate::optimizer::property::Order;
+
+/// `BatchHopWindow` implements [`super::LogicalHopWindow`]
+#[derive(Debug, Clone)]
+pub struct BatchHopWindow {
+    pub base: PlanBase,
+    logical: LogicalHopWindow,
+}
+
+impl BatchHopWindow {
+    pub fn new(logical: LogicalHopWindow) -> Self {
+        let ctx = logical.base.ctx.clone();
+        // Each input chunk is emitted once per window, so the input order is not preserved.
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            logical.input().distribution().clone(),
+            Order::any().clone(),
+        );
+        BatchHopWindow { base, logical }
+    }
+}
+
+impl fmt::Display for BatchHopWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchHopWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for BatchHopWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { BatchHopWindow }
+
+impl ToDistributedBatch for BatchHopWindow {
+    fn to_distributed(&self) -> PlanRef {
+        let new_input = self.input().to_distributed();
+        self.clone_with_input(new_input).into()
+    }
+}
+
+impl ToBatchProst for BatchHopWindow {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::HopWindow(HopWindowNode {
+            time_col: Some(InputRefExpr {
+                column_idx: self.logical.time_col().index() as i32,
+            }),
+            window_slide: Some(self.logical.window_slide().into()),
+            window_size: Some(self.logical.window_size().into()),
+        })
+    }
+}
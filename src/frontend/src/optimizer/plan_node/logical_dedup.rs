@@ -0,0 +1,209 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+
+use super::{
+    ColPrunable, LogicalProject, PlanBase, PlanNode, PlanRef, PlanTreeNodeUnary, StreamDedup,
+    ToBatch, ToStream,
+};
+use crate::optimizer::property::Distribution;
+use crate::utils::ColIndexMapping;
+
+/// `LogicalDedup` only keeps the first row of each distinct value of `dedup_cols`, which is used
+/// to plan `SELECT DISTINCT ON`.
+///
+/// It is only supported in streaming on an append-only input, where the first row of a key is the
+/// first one arrived.
+#[derive(Debug, Clone)]
+pub struct LogicalDedup {
+    pub base: PlanBase,
+    input: PlanRef,
+    dedup_cols: Vec<usize>,
+}
+
+impl LogicalDedup {
+    pub fn new(input: PlanRef, dedup_cols: Vec<usize>) -> Self {
+        let ctx = input.ctx();
+        let schema = input.schema().clone();
+        let pk_indices = input.pk_indices().to_vec();
+        for idx in &dedup_cols {
+            assert!(*idx < schema.len());
+        }
+        let base = PlanBase::new_logical(ctx, schema, pk_indices);
+        LogicalDedup {
+            base,
+            input,
+            dedup_cols,
+        }
+    }
+
+    pub fn create(input: PlanRef, dedup_cols: Vec<usize>) -> PlanRef {
+        Self::new(input, dedup_cols).into()
+    }
+
+    pub fn dedup_cols(&self) -> &[usize] {
+        &self.dedup_cols
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ dedup_cols: [{}] }}",
+            name,
+            self.dedup_cols
+                .iter()
+                .map(|idx| format!("${}", idx))
+                .join(", ")
+        )
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalDedup {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.dedup_cols.clone())
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let dedup_cols = self
+            .dedup_cols
+            .iter()
+            .map(|idx| input_col_change.map(*idx))
+            .collect();
+        (Self::new(input, dedup_cols), input_col_change)
+    }
+}
+impl_plan_tree_node_for_unary! {LogicalDedup}
+
+impl fmt::Display for LogicalDedup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalDedup")
+    }
+}
+
+impl ColPrunable for LogicalDedup {
+    fn prune_col(&self, required_cols: &FixedBitSet) -> PlanRef {
+        self.must_contain_columns(required_cols);
+
+        let mut input_required_cols = required_cols.clone();
+        input_required_cols.extend(self.dedup_cols.iter().copied());
+
+        let mapping = ColIndexMapping::with_remaining_columns(&input_required_cols);
+        let dedup_cols = self
+            .dedup_cols
+            .iter()
+            .map(|idx| mapping.map(*idx))
+            .collect();
+        let dedup = Self::new(self.input.prune_col(&input_required_cols), dedup_cols);
+
+        if *required_cols == input_required_cols {
+            dedup.into()
+        } else {
+            let mut remaining_columns = FixedBitSet::with_capacity(dedup.schema().fields().len());
+            remaining_columns.extend(required_cols.ones().map(|i| mapping.map(i)));
+            LogicalProject::with_mapping(
+                dedup.into(),
+                ColIndexMapping::with_remaining_columns(&remaining_columns),
+            )
+        }
+    }
+}
+
+impl ToBatch for LogicalDedup {
+    fn to_batch(&self) -> PlanRef {
+        unreachable!("`DISTINCT ON` should have been rejected before the batch plan is generated")
+    }
+}
+
+impl ToStream for LogicalDedup {
+    fn to_stream(&self) -> PlanRef {
+        let input = self
+            .input()
+            .to_stream_with_dist_required(&Distribution::HashShard(self.dedup_cols.clone()));
+        StreamDedup::new(self.clone_with_input(input)).into()
+    }
+
+    fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream();
+        let (dedup, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        (dedup.into(), out_col_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::expr::assert_eq_input_ref;
+    use crate::optimizer::plan_node::LogicalValues;
+    use crate::session::OptimizerContext;
+
+    #[tokio::test]
+    /// Pruning
+    /// ```text
+    /// Dedup(dedup_cols: [$2])
+    ///   TableScan(v1, v2, v3)
+    /// ```
+    /// with required columns [0] will result in
+    /// ```text
+    /// Project($0)
+    ///   Dedup(dedup_cols: [$1])
+    ///     TableScan(v1, v3)
+    /// ```
+    async fn test_prune_dedup() {
+        let ctx = OptimizerContext::mock().await;
+        let fields: Vec<Field> = vec![
+            Field::with_name(DataType::Int32, "v1"),
+            Field::with_name(DataType::Int32, "v2"),
+            Field::with_name(DataType::Int32, "v3"),
+        ];
+        let values = LogicalValues::new(
+            vec![],
+            Schema {
+                fields: fields.clone(),
+            },
+            ctx,
+        );
+        let dedup = LogicalDedup::new(values.into(), vec![2]);
+
+        let mut required_cols = FixedBitSet::with_capacity(3);
+        required_cols.insert(0);
+        let plan = dedup.prune_col(&required_cols);
+
+        let project = plan.as_logical_project().unwrap();
+        assert_eq!(project.exprs().len(), 1);
+        assert_eq_input_ref!(&project.exprs()[0], 0);
+
+        let dedup = project.input();
+        let dedup = dedup.as_logical_dedup().unwrap();
+        assert_eq!(dedup.dedup_cols(), &[1]);
+        assert_eq!(dedup.schema().fields().len(), 2);
+        assert_eq!(dedup.schema().fields()[0], fields[0]);
+        assert_eq!(dedup.schema().fields()[1], fields[2]);
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+
+use super::{
+    ColPrunable, LogicalProject, PlanBase, PlanNode, PlanRef, PlanTreeNodeBinary,
+    StreamDynamicFilter, ToBatch, ToStream,
+};
+use crate::expr::{ExprType, FunctionCall, InputRef};
+use crate::optimizer::property::Distribution;
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalDynamicFilter` keeps the rows of `left` for which `left_index` compared with the
+/// `right_index` column of `right` is true. `right` has at most one row, whose changes will change
+/// the rows passing the filter, so it is used to plan a comparison with a scalar subquery.
+///
+/// The output schema is the same as `left`.
+#[derive(Debug, Clone)]
+pub struct LogicalDynamicFilter {
+    pub base: PlanBase,
+    left: PlanRef,
+    right: PlanRef,
+    left_index: usize,
+    right_index: usize,
+    comparator: ExprType,
+}
+
+impl LogicalDynamicFilter {
+    pub fn new(
+        left: PlanRef,
+        left_index: usize,
+        comparator: ExprType,
+        right: PlanRef,
+        right_index: usize,
+    ) -> Self {
+        assert!(
+            matches!(
+                comparator,
+                ExprType::LessThan
+                    | ExprType::LessThanOrEqual
+                    | ExprType::GreaterThan
+                    | ExprType::GreaterThanOrEqual
+            ),
+            "Invalid comparator {:?} for LogicalDynamicFilter",
+            comparator
+        );
+        assert!(left_index < left.schema().len());
+        assert!(right_index < right.schema().len());
+        let ctx = left.ctx();
+        let schema = left.schema().clone();
+        let pk_indices = left.pk_indices().to_vec();
+        let base = PlanBase::new_logical(ctx, schema, pk_indices);
+        LogicalDynamicFilter {
+            base,
+            left,
+            right,
+            left_index,
+            right_index,
+            comparator,
+        }
+    }
+
+    pub fn create(
+        left: PlanRef,
+        left_index: usize,
+        comparator: ExprType,
+        right: PlanRef,
+        right_index: usize,
+    ) -> PlanRef {
+        Self::new(left, left_index, comparator, right, right_index).into()
+    }
+
+    pub fn left_index(&self) -> usize {
+        self.left_index
+    }
+
+    pub fn right_index(&self) -> usize {
+        self.right_index
+    }
+
+    pub fn comparator(&self) -> ExprType {
+        self.comparator
+    }
+
+    /// The filter predicate, where the right column is indexed after the left columns.
+    pub fn predicate(&self) -> Condition {
+        let left_fields = self.left.schema().fields();
+        let right_field = &self.right.schema().fields()[self.right_index];
+        let cmp = FunctionCall::new(
+            self.comparator,
+            vec![
+                InputRef::new(
+                    self.left_index,
+                    left_fields[self.left_index].data_type.clone(),
+                )
+                .into(),
+                InputRef::new(
+                    left_fields.len() + self.right_index,
+                    right_field.data_type.clone(),
+                )
+                .into(),
+            ],
+        )
+        .unwrap();
+        Condition::with_expr(cmp.into())
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(f, "{} {{ predicate: {} }}", name, self.predicate())
+    }
+
+    fn rewrite_with_left_right(
+        &self,
+        left: PlanRef,
+        left_col_change: ColIndexMapping,
+        right: PlanRef,
+        right_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let filter = Self::new(
+            left,
+            left_col_change.map(self.left_index),
+            self.comparator,
+            right,
+            right_col_change.map(self.right_index),
+        );
+        (filter, left_col_change)
+    }
+}
+
+impl PlanTreeNodeBinary for LogicalDynamicFilter {
+    fn left(&self) -> PlanRef {
+        self.left.clone()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.right.clone()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            left,
+            self.left_index,
+            self.comparator,
+            right,
+            self.right_index,
+        )
+    }
+}
+
+impl_plan_tree_node_for_binary! { LogicalDynamicFilter }
+
+impl fmt::Display for LogicalDynamicFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalDynamicFilter")
+    }
+}
+
+impl ColPrunable for LogicalDynamicFilter {
+    fn prune_col(&self, required_cols: &FixedBitSet) -> PlanRef {
+        self.must_contain_columns(required_cols);
+
+        let mut left_required_cols = required_cols.clone();
+        left_required_cols.insert(self.left_index);
+        let mut right_required_cols = FixedBitSet::with_capacity(self.right.schema().len());
+        right_required_cols.insert(self.right_index);
+
+        let mapping = ColIndexMapping::with_remaining_columns(&left_required_cols);
+        let filter = Self::new(
+            self.left.prune_col(&left_required_cols),
+            mapping.map(self.left_index),
+            self.comparator,
+            self.right.prune_col(&right_required_cols),
+            0,
+        );
+
+        if *required_cols == left_required_cols {
+            filter.into()
+        } else {
+            let mut remaining_columns = FixedBitSet::with_capacity(filter.schema().fields().len());
+            remaining_columns.extend(required_cols.ones().map(|i| mapping.map(i)));
+            LogicalProject::with_mapping(
+                filter.into(),
+                ColIndexMapping::with_remaining_columns(&remaining_columns),
+            )
+        }
+    }
+}
+
+impl ToBatch for LogicalDynamicFilter {
+    fn to_batch(&self) -> PlanRef {
+        unreachable!(
+            "comparison with a scalar subquery should have been rejected before the batch plan is \
+             generated"
+        )
+    }
+}
+
+impl ToStream for LogicalDynamicFilter {
+    fn to_stream(&self) -> PlanRef {
+        let left = self.left().to_stream();
+        // Every parallel unit of the left side compares with the single row of the right side.
+        let right = self
+            .right()
+            .to_stream_with_dist_required(&Distribution::Broadcast);
+        StreamDynamicFilter::new(self.clone_with_left_right(left, right)).into()
+    }
+
+    fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
+        let (left, left_col_change) = self.left.logical_rewrite_for_stream();
+        let (right, right_col_change) = self.right.logical_rewrite_for_stream();
+        let (filter, out_col_change) =
+            self.rewrite_with_left_right(left, left_col_change, right, right_col_change);
+        (filter.into(), out_col_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::expr::assert_eq_input_ref;
+    use crate::optimizer::plan_node::{LogicalValues, PlanTreeNodeUnary};
+    use crate::session::OptimizerContext;
+
+    #[tokio::test]
+    /// Pruning
+    /// ```text
+    /// DynamicFilter($2 > $4)
+    ///   TableScan(v1, v2, v3)
+    ///   TableScan(v4, v5)
+    /// ```
+    /// with required columns [0] will result in
+    /// ```text
+    /// Project($0)
+    ///   DynamicFilter($1 > $2)
+    ///     TableScan(v1, v3)
+    ///     TableScan(v5)
+    /// ```
+    async fn test_prune_dynamic_filter() {
+        let ctx = OptimizerContext::mock().await;
+        let fields: Vec<Field> = (1..6)
+            .map(|i| Field::with_name(DataType::Int32, format!("v{}", i)))
+            .collect();
+        let left = LogicalValues::new(
+            vec![],
+            Schema {
+                fields: fields[0..3].to_vec(),
+            },
+            ctx.clone(),
+        );
+        let right = LogicalValues::new(
+            vec![],
+            Schema {
+                fields: fields[3..5].to_vec(),
+            },
+            ctx,
+        );
+        let filter =
+            LogicalDynamicFilter::new(left.into(), 2, ExprType::GreaterThan, right.into(), 1);
+
+        let mut required_cols = FixedBitSet::with_capacity(3);
+        required_cols.insert(0);
+        let plan = filter.prune_col(&required_cols);
+
+        let project = plan.as_logical_project().unwrap();
+        assert_eq!(project.exprs().len(), 1);
+        assert_eq_input_ref!(&project.exprs()[0], 0);
+
+        let filter = project.input();
+        let filter = filter.as_logical_dynamic_filter().unwrap();
+        assert_eq!(filter.left_index(), 1);
+        assert_eq!(filter.right_index(), 0);
+        assert_eq!(
+            filter.left().schema().fields(),
+            &[fields[0].clone(), fields[2].clone()]
+        );
+        assert_eq!(filter.right().schema().fields(), &[fields[4].clone()]);
+    }
+}
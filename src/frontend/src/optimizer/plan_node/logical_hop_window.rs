@@ -0,0 +1,254 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use risingwave_common::catalog::Field;
+use risingwave_common::types::{DataType, IntervalUnit};
+
+use super::{
+    BatchHopWindow, ColPrunable, LogicalProject, PlanBase, PlanNode, PlanRef, PlanTreeNodeUnary,
+    StreamHopWindow, ToBatch, ToStream,
+};
+use crate::expr::{Expr, InputRef};
+use crate::utils::ColIndexMapping;
+
+/// `LogicalHopWindow` implements the `HOP` window table function. Each input row is emitted once
+/// for every window containing its `time_col`, with `window_start` and `window_end` appended.
+#[derive(Debug, Clone)]
+pub struct LogicalHopWindow {
+    pub base: PlanBase,
+    input: PlanRef,
+    time_col: InputRef,
+    window_slide: IntervalUnit,
+    window_size: IntervalUnit,
+}
+
+impl LogicalHopWindow {
+    pub fn new(
+        input: PlanRef,
+        time_col: InputRef,
+        window_slide: IntervalUnit,
+        window_size: IntervalUnit,
+    ) -> Self {
+        let ctx = input.ctx();
+        let mut schema = input.schema().clone();
+        schema
+            .fields
+            .push(Field::with_name(DataType::Timestamp, "window_start"));
+        schema
+            .fields
+            .push(Field::with_name(DataType::Timestamp, "window_end"));
+        // The same input row appears in several windows, so `window_start` is part of the pk.
+        let mut pk_indices = input.pk_indices().to_vec();
+        pk_indices.push(input.schema().len());
+        let base = PlanBase::new_logical(ctx, schema, pk_indices);
+        LogicalHopWindow {
+            base,
+            input,
+            time_col,
+            window_slide,
+            window_size,
+        }
+    }
+
+    pub fn create(
+        input: PlanRef,
+        time_col: InputRef,
+        window_slide: IntervalUnit,
+        window_size: IntervalUnit,
+    ) -> PlanRef {
+        Self::new(input, time_col, window_slide, window_size).into()
+    }
+
+    pub fn time_col(&self) -> &InputRef {
+        &self.time_col
+    }
+
+    pub fn window_slide(&self) -> IntervalUnit {
+        self.window_slide
+    }
+
+    pub fn window_size(&self) -> IntervalUnit {
+        self.window_size
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ time_col: {}, slide: {}, size: {} }}",
+            name, self.time_col, self.window_slide, self.window_size
+        )
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalHopWindow {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(
+            input,
+            self.time_col.clone(),
+            self.window_slide,
+            self.window_size,
+        )
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let time_col = InputRef::new(
+            input_col_change.map(self.time_col.index()),
+            self.time_col.return_type(),
+        );
+        let new_input_len = input.schema().len();
+        // The window columns are always appended after the input columns.
+        let (mut map, _) = input_col_change.into_parts();
+        map.push(Some(new_input_len));
+        map.push(Some(new_input_len + 1));
+        let hop = Self::new(input, time_col, self.window_slide, self.window_size);
+        (
+            hop,
+            ColIndexMapping::with_target_size(map, new_input_len + 2),
+        )
+    }
+}
+
+impl_plan_tree_node_for_unary! {LogicalHopWindow}
+
+impl fmt::Display for LogicalHopWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalHopWindow")
+    }
+}
+
+impl ColPrunable for LogicalHopWindow {
+    fn prune_col(&self, required_cols: &FixedBitSet) -> PlanRef {
+        self.must_contain_columns(required_cols);
+
+        let input_len = self.input.schema().len();
+        let mut input_required_cols = FixedBitSet::with_capacity(input_len);
+        input_required_cols.extend(required_cols.ones().filter(|i| *i < input_len));
+        input_required_cols.insert(self.time_col.index());
+        let mapping = ColIndexMapping::with_remaining_columns(&input_required_cols);
+
+        let time_col = InputRef::new(
+            mapping.map(self.time_col.index()),
+            self.time_col.return_type(),
+        );
+        let hop = Self::new(
+            self.input.prune_col(&input_required_cols),
+            time_col,
+            self.window_slide,
+            self.window_size,
+        );
+
+        let new_input_len = input_required_cols.count_ones(..);
+        let mut remaining_columns = FixedBitSet::with_capacity(new_input_len + 2);
+        remaining_columns.extend(required_cols.ones().map(|i| {
+            if i < input_len {
+                mapping.map(i)
+            } else {
+                i - input_len + new_input_len
+            }
+        }));
+        if remaining_columns.count_ones(..) == new_input_len + 2 {
+            hop.into()
+        } else {
+            LogicalProject::with_mapping(
+                hop.into(),
+                ColIndexMapping::with_remaining_columns(&remaining_columns),
+            )
+        }
+    }
+}
+
+impl ToBatch for LogicalHopWindow {
+    fn to_batch(&self) -> PlanRef {
+        let new_input = self.input().to_batch();
+        let new_logical = self.clone_with_input(new_input);
+        BatchHopWindow::new(new_logical).into()
+    }
+}
+
+impl ToStream for LogicalHopWindow {
+    fn to_stream(&self) -> PlanRef {
+        let new_input = self.input().to_stream();
+        let new_logical = self.clone_with_input(new_input);
+        StreamHopWindow::new(new_logical).into()
+    }
+
+    fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream();
+        let (hop, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        (hop.into(), out_col_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::Schema;
+
+    use super::*;
+    use crate::optimizer::plan_node::LogicalValues;
+    use crate::session::OptimizerContext;
+
+    #[tokio::test]
+    /// Pruning
+    /// ```text
+    /// HopWindow(time_col: $1)
+    ///   Values(v1, v2, v3)
+    /// ```
+    /// with required columns [0, 3] will result in
+    /// ```text
+    /// Project($0, $2)
+    ///   HopWindow(time_col: $1)
+    ///     Values(v1, v2)
+    /// ```
+    async fn test_prune_hop_window() {
+        let ctx = OptimizerContext::mock().await;
+        let fields: Vec<Field> = vec![
+            Field::with_name(DataType::Int32, "v1"),
+            Field::with_name(DataType::Timestamp, "v2"),
+            Field::with_name(DataType::Int32, "v3"),
+        ];
+        let values = LogicalValues::new(vec![], Schema { fields }, ctx);
+        let hop = LogicalHopWindow::new(
+            values.into(),
+            InputRef::new(1, DataType::Timestamp),
+            IntervalUnit::from_minutes(15),
+            IntervalUnit::from_minutes(30),
+        );
+        assert_eq!(hop.schema().len(), 5);
+
+        let mut required_cols = FixedBitSet::with_capacity(5);
+        required_cols.insert(0);
+        required_cols.insert(3);
+        let plan = hop.prune_col(&required_cols);
+
+        let project = plan.as_logical_project().unwrap();
+        assert_eq!(project.exprs().len(), 2);
+        let hop = project.input();
+        let hop = hop.as_logical_hop_window().unwrap();
+        assert_eq!(hop.time_col().index(), 1);
+        assert_eq!(hop.schema().len(), 4);
+        assert_eq!(hop.input().schema().len(), 2);
+    }
+}
@@ -19,8 +19,8 @@ use risingwave_common::catalog::Schema;
 use risingwave_pb::plan::JoinType;
 
 use super::{
-    ColPrunable, LogicalProject, PlanBase, PlanNode, PlanRef, PlanTreeNodeBinary, StreamHashJoin,
-    ToBatch, ToStream,
+    ColPrunable, LogicalProject, LogicalScan, PlanBase, PlanNode, PlanRef, PlanTreeNodeBinary,
+    StreamHashJoin, StreamTemporalJoin, ToBatch, ToStream,
 };
 use crate::expr::ExprImpl;
 use crate::optimizer::plan_node::{
@@ -225,6 +225,41 @@ impl LogicalJoin {
     pub fn clone_with_cond(&self, cond: Condition) -> Self {
         Self::new(self.left.clone(), self.right.clone(), self.join_type, cond)
     }
+
+    /// Convert to a temporal join which looks up the dimension table `right_scan` for each row
+    /// from the left side. The join keys have been checked by the planner to be a prefix of the
+    /// primary key of the table.
+    fn to_stream_temporal_join(&self, right_scan: &LogicalScan) -> PlanRef {
+        let predicate = EqJoinPredicate::create(
+            self.left.schema().len(),
+            self.right.schema().len(),
+            self.on.clone(),
+        );
+        let left = self
+            .left()
+            .to_stream_with_dist_required(&Distribution::HashShard(predicate.left_eq_indexes()));
+        let right = self
+            .right()
+            .to_stream_with_dist_required(&Distribution::HashShard(predicate.right_eq_indexes()));
+        let logical_join = self.clone_with_left_right(left, right);
+
+        // The lookup only evaluates the equal conditions. Non-equal conditions are only allowed in
+        // inner joins, and are pulled to a filter operator on top of it.
+        let eq_cond = EqJoinPredicate::new(
+            Condition::true_cond(),
+            predicate.eq_keys().to_vec(),
+            self.left.schema().len(),
+        );
+        let logical_join = logical_join.clone_with_cond(eq_cond.eq_cond());
+        let temporal_join = StreamTemporalJoin::new(logical_join, eq_cond, right_scan.clone());
+        if predicate.has_non_eq() {
+            assert_eq!(self.join_type, JoinType::Inner);
+            let logical_filter = LogicalFilter::new(temporal_join.into(), predicate.non_eq_cond());
+            StreamFilter::new(logical_filter).into()
+        } else {
+            temporal_join.into()
+        }
+    }
 }
 
 impl PlanTreeNodeBinary for LogicalJoin {
@@ -358,6 +393,10 @@ impl ToBatch for LogicalJoin {
 
 impl ToStream for LogicalJoin {
     fn to_stream(&self) -> PlanRef {
+        if let Some(scan) = self.right.as_logical_scan() && scan.for_system_time_as_of_proctime() {
+            return self.to_stream_temporal_join(scan);
+        }
+
         let predicate = EqJoinPredicate::create(
             self.left.schema().len(),
             self.right.schema().len(),
@@ -373,8 +412,11 @@ impl ToStream for LogicalJoin {
 
         if predicate.has_eq() {
             // Convert to Hash Join for equal joins
-            // For inner joins, pull non-equal conditions to a filter operator on top of it
-            let pull_filter = self.join_type == JoinType::Inner && predicate.has_non_eq();
+            // For inner joins, pull non-equal conditions to a filter operator on top of it,
+            // unless they bound the time columns of an interval join.
+            let pull_filter = self.join_type == JoinType::Inner
+                && predicate.has_non_eq()
+                && StreamHashJoin::derive_interval_bound(self, &predicate).is_none();
             if pull_filter {
                 let eq_cond = EqJoinPredicate::new(
                     Condition::true_cond(),
@@ -23,7 +23,8 @@ use super::{
     ToBatch, ToStream,
 };
 use crate::expr::{
-    as_alias_display, assert_input_ref, Expr, ExprImpl, ExprRewriter, ExprVisitor, InputRef,
+    as_alias_display, assert_input_ref, CollectFieldAccess, Expr, ExprImpl, ExprRewriter,
+    ExprVisitor, InputRef,
 };
 use crate::optimizer::plan_node::CollectInputRef;
 use crate::optimizer::property::{Distribution, Order};
@@ -257,13 +258,25 @@ impl ColPrunable for LogicalProject {
             })
             .unzip();
 
+        // Skip parsing the nested fields of a source which are never accessed.
+        let input = match self.input.as_logical_source() {
+            Some(source) => {
+                let mut visitor = CollectFieldAccess::default();
+                required_cols.ones().for_each(|id| {
+                    visitor.visit_expr(&self.exprs[id]);
+                });
+                let pruned_field_ids = source.unused_field_ids(&visitor.collect());
+                if pruned_field_ids.is_empty() {
+                    self.input.clone()
+                } else {
+                    source.with_pruned_fields(pruned_field_ids).into()
+                }
+            }
+            None => self.input.clone(),
+        };
+
         // Reconstruct the LogicalProject.
-        LogicalProject::new(
-            self.input.prune_col(&child_required_cols),
-            exprs,
-            expr_alias,
-        )
-        .into()
+        LogicalProject::new(input.prune_col(&child_required_cols), exprs, expr_alias).into()
     }
 }
 
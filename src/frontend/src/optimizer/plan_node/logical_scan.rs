@@ -33,6 +33,9 @@ pub struct LogicalScan {
     table_name: String, // explain-only
     required_col_idx: Vec<usize>,
     table_desc: Rc<TableDesc>,
+    /// Whether the table is scanned with `FOR SYSTEM_TIME AS OF PROCTIME()`, i.e. as the
+    /// dimension table of a temporal join.
+    for_system_time_as_of_proctime: bool,
 }
 
 impl LogicalScan {
@@ -74,6 +77,7 @@ impl LogicalScan {
             table_name,
             required_col_idx,
             table_desc,
+            for_system_time_as_of_proctime: false,
         }
     }
 
@@ -81,15 +85,29 @@ impl LogicalScan {
     pub fn create(
         table_name: String, // explain-only
         table_desc: Rc<TableDesc>,
+        for_system_time_as_of_proctime: bool,
         ctx: OptimizerContextRef,
     ) -> Result<PlanRef> {
-        Ok(Self::new(
+        let mut scan = Self::new(
             table_name,
             (0..table_desc.columns.len()).into_iter().collect(),
             table_desc,
             ctx,
-        )
-        .into())
+        );
+        scan.for_system_time_as_of_proctime = for_system_time_as_of_proctime;
+        Ok(scan.into())
+    }
+
+    /// Create a new [`LogicalScan`] on the same table with different required columns.
+    fn clone_with_required_cols(&self, required_col_idx: Vec<usize>) -> Self {
+        let mut scan = Self::new(
+            self.table_name.clone(),
+            required_col_idx,
+            self.table_desc.clone(),
+            self.base.ctx.clone(),
+        );
+        scan.for_system_time_as_of_proctime = self.for_system_time_as_of_proctime;
+        scan
     }
 
     pub(super) fn column_names(&self) -> Vec<String> {
@@ -110,6 +128,10 @@ impl LogicalScan {
         self.table_desc.as_ref()
     }
 
+    pub fn for_system_time_as_of_proctime(&self) -> bool {
+        self.for_system_time_as_of_proctime
+    }
+
     /// Get a reference to the logical scan's table desc.
     #[must_use]
     pub fn column_descs(&self) -> Vec<ColumnDesc> {
@@ -126,10 +148,14 @@ impl fmt::Display for LogicalScan {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "LogicalScan {{ table: {}, columns: [{}] }}",
+            "LogicalScan {{ table: {}, columns: [{}]",
             self.table_name,
             self.column_names().join(", ")
-        )
+        )?;
+        if self.for_system_time_as_of_proctime {
+            write!(f, ", for_system_time_as_of_proctime: true")?;
+        }
+        write!(f, " }}")
     }
 }
 
@@ -141,13 +167,7 @@ impl ColPrunable for LogicalScan {
             .map(|i| self.required_col_idx[i])
             .collect();
 
-        Self::new(
-            self.table_name.clone(),
-            required_col_idx,
-            self.table_desc.clone(),
-            self.base.ctx.clone(),
-        )
-        .into()
+        self.clone_with_required_cols(required_col_idx).into()
     }
 }
 
@@ -184,13 +204,7 @@ impl ToStream for LogicalScan {
                 let mut required_col_idx = self.required_col_idx.clone();
                 required_col_idx.extend(col_need_to_add);
                 (
-                    Self::new(
-                        self.table_name.clone(),
-                        required_col_idx,
-                        self.table_desc.clone(),
-                        self.base.ctx.clone(),
-                    )
-                    .into(),
+                    self.clone_with_required_cols(required_col_idx).into(),
                     ColIndexMapping::identity(self.schema().len()),
                 )
             }
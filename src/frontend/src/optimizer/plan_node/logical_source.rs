@@ -17,7 +17,8 @@ use std::fmt;
 use std::rc::Rc;
 
 use fixedbitset::FixedBitSet;
-use risingwave_common::catalog::Schema;
+use itertools::Itertools;
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema};
 
 use super::{
     ColPrunable, LogicalProject, PlanBase, PlanNode, PlanRef, StreamSource, ToBatch, ToStream,
@@ -31,6 +32,9 @@ use crate::utils::ColIndexMapping;
 pub struct LogicalSource {
     pub base: PlanBase,
     pub source_catalog: Rc<SourceCatalog>,
+    /// Column ids of the nested struct fields that are not used by the parent, which the source
+    /// executor can skip parsing.
+    pub pruned_field_ids: Vec<ColumnId>,
 }
 
 impl LogicalSource {
@@ -56,9 +60,68 @@ impl LogicalSource {
         LogicalSource {
             base,
             source_catalog,
+            pruned_field_ids: vec![],
         }
     }
 
+    /// Returns a copy of the source that skips the given nested struct fields.
+    pub fn with_pruned_fields(&self, pruned_field_ids: Vec<ColumnId>) -> Self {
+        Self {
+            pruned_field_ids,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the ids of the nested struct fields that none of the `accessed` field paths
+    /// (keyed by column index) reach. Columns without recorded paths are left untouched.
+    pub fn unused_field_ids(&self, accessed: &HashMap<usize, Vec<Vec<usize>>>) -> Vec<ColumnId> {
+        fn collect(desc: &ColumnDesc, paths: &[&[usize]], unused: &mut Vec<ColumnId>) {
+            if paths.iter().any(|path| path.is_empty()) {
+                return;
+            }
+            for (i, field) in desc.field_descs.iter().enumerate() {
+                let sub_paths = paths
+                    .iter()
+                    .filter(|path| path[0] == i)
+                    .map(|path| &path[1..])
+                    .collect_vec();
+                if sub_paths.is_empty() {
+                    unused.push(field.column_id);
+                } else {
+                    collect(field, &sub_paths, unused);
+                }
+            }
+        }
+
+        let mut unused = vec![];
+        for (idx, paths) in accessed {
+            let paths = paths.iter().map(Vec::as_slice).collect_vec();
+            collect(
+                &self.source_catalog.columns[*idx].column_desc,
+                &paths,
+                &mut unused,
+            );
+        }
+        unused.sort_by_key(ColumnId::get_id);
+        unused
+    }
+
+    /// Formats the names of the pruned nested fields, or nothing if no field is pruned.
+    pub(super) fn fmt_pruned_fields(&self) -> String {
+        if self.pruned_field_ids.is_empty() {
+            return String::new();
+        }
+        let names = self
+            .source_catalog
+            .columns
+            .iter()
+            .flat_map(|c| c.column_desc.get_column_descs())
+            .filter(|desc| self.pruned_field_ids.contains(&desc.column_id))
+            .map(|desc| desc.name)
+            .join(", ");
+        format!(", pruned_fields: [{}]", names)
+    }
+
     pub(super) fn column_names(&self) -> Vec<String> {
         self.schema()
             .fields()
@@ -74,9 +137,10 @@ impl fmt::Display for LogicalSource {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "LogicalSource {{ source: {}, columns: [{}] }}",
+            "LogicalSource {{ source: {}, columns: [{}]{} }}",
             self.source_catalog.name,
-            self.column_names().join(", ")
+            self.column_names().join(", "),
+            self.fmt_pruned_fields()
         )
     }
 }
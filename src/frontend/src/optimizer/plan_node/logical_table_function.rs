@@ -0,0 +1,85 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use risingwave_common::catalog::{Field, Schema};
+
+use super::{
+    BatchTableFunction, ColPrunable, LogicalProject, PlanBase, PlanRef, ToBatch, ToStream,
+};
+use crate::expr::TableFunction;
+use crate::session::OptimizerContextRef;
+use crate::utils::ColIndexMapping;
+
+/// `LogicalTableFunction` is a scalar/table function used as a relation (in the `FROM` clause).
+#[derive(Debug, Clone)]
+pub struct LogicalTableFunction {
+    pub base: PlanBase,
+    pub table_function: TableFunction,
+}
+
+impl LogicalTableFunction {
+    /// Create a [`LogicalTableFunction`] node. Used internally by optimizer.
+    pub fn new(table_function: TableFunction, ctx: OptimizerContextRef) -> Self {
+        let schema = Schema {
+            fields: vec![Field::with_name(
+                table_function.return_type.clone(),
+                table_function.function_type.column_name(),
+            )],
+        };
+        let base = PlanBase::new_logical(ctx, schema, vec![]);
+        Self {
+            base,
+            table_function,
+        }
+    }
+
+    pub fn table_function(&self) -> &TableFunction {
+        &self.table_function
+    }
+}
+
+impl_plan_tree_node_for_leaf! { LogicalTableFunction }
+
+impl fmt::Display for LogicalTableFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LogicalTableFunction {{ {:?} }}", self.table_function)
+    }
+}
+
+impl ColPrunable for LogicalTableFunction {
+    fn prune_col(&self, required_cols: &FixedBitSet) -> PlanRef {
+        self.must_contain_columns(required_cols);
+        let mapping = ColIndexMapping::with_remaining_columns(required_cols);
+        LogicalProject::with_mapping(self.clone().into(), mapping)
+    }
+}
+
+impl ToBatch for LogicalTableFunction {
+    fn to_batch(&self) -> PlanRef {
+        BatchTableFunction::new(self.clone()).into()
+    }
+}
+
+impl ToStream for LogicalTableFunction {
+    fn to_stream(&self) -> PlanRef {
+        unimplemented!("stream table function is unimplemented!")
+    }
+
+    fn logical_rewrite_for_stream(&self) -> (PlanRef, ColIndexMapping) {
+        unimplemented!("stream table function is unimplemented!")
+    }
+}
@@ -213,68 +213,88 @@ mod batch_exchange;
 mod batch_filter;
 mod batch_hash_agg;
 mod batch_hash_join;
+mod batch_hop_window;
 mod batch_insert;
 mod batch_limit;
 mod batch_project;
 mod batch_seq_scan;
 mod batch_simple_agg;
 mod batch_sort;
+mod batch_table_function;
 mod batch_values;
 mod logical_agg;
 mod logical_apply;
+mod logical_dedup;
 mod logical_delete;
+mod logical_dynamic_filter;
 mod logical_filter;
+mod logical_hop_window;
 mod logical_insert;
 mod logical_join;
 mod logical_limit;
 mod logical_project;
 mod logical_scan;
 mod logical_source;
+mod logical_table_function;
 mod logical_topn;
 mod logical_values;
+mod stream_dedup;
+mod stream_dynamic_filter;
 mod stream_exchange;
 mod stream_filter;
 mod stream_hash_agg;
 mod stream_hash_join;
+mod stream_hop_window;
 mod stream_materialize;
 mod stream_project;
 mod stream_simple_agg;
 mod stream_source;
 mod stream_table_scan;
+mod stream_temporal_join;
 
 pub use batch_delete::BatchDelete;
 pub use batch_exchange::BatchExchange;
 pub use batch_filter::BatchFilter;
 pub use batch_hash_agg::BatchHashAgg;
 pub use batch_hash_join::BatchHashJoin;
+pub use batch_hop_window::BatchHopWindow;
 pub use batch_insert::BatchInsert;
 pub use batch_limit::BatchLimit;
 pub use batch_project::BatchProject;
 pub use batch_seq_scan::BatchSeqScan;
 pub use batch_simple_agg::BatchSimpleAgg;
 pub use batch_sort::BatchSort;
+pub use batch_table_function::BatchTableFunction;
 pub use batch_values::BatchValues;
 pub use logical_agg::{LogicalAgg, PlanAggCall};
 pub use logical_apply::LogicalApply;
+pub use logical_dedup::LogicalDedup;
 pub use logical_delete::LogicalDelete;
+pub use logical_dynamic_filter::LogicalDynamicFilter;
 pub use logical_filter::LogicalFilter;
+pub use logical_hop_window::LogicalHopWindow;
 pub use logical_insert::LogicalInsert;
 pub use logical_join::LogicalJoin;
 pub use logical_limit::LogicalLimit;
 pub use logical_project::LogicalProject;
 pub use logical_scan::LogicalScan;
 pub use logical_source::LogicalSource;
+pub use logical_table_function::LogicalTableFunction;
 pub use logical_topn::LogicalTopN;
 pub use logical_values::LogicalValues;
+pub use stream_dedup::StreamDedup;
+pub use stream_dynamic_filter::StreamDynamicFilter;
 pub use stream_exchange::StreamExchange;
 pub use stream_filter::StreamFilter;
 pub use stream_hash_agg::StreamHashAgg;
-pub use stream_hash_join::StreamHashJoin;
+pub use stream_hash_join::{IntervalBound, StreamHashJoin};
+pub use stream_hop_window::StreamHopWindow;
 pub use stream_materialize::StreamMaterialize;
 pub use stream_project::StreamProject;
 pub use stream_simple_agg::StreamSimpleAgg;
 pub use stream_source::StreamSource;
 pub use stream_table_scan::StreamTableScan;
+pub use stream_temporal_join::StreamTemporalJoin;
 
 use crate::session::OptimizerContextRef;
 
@@ -307,6 +327,10 @@ macro_rules! for_all_plan_nodes {
             ,{ Logical, Values }
             ,{ Logical, Limit }
             ,{ Logical, TopN }
+            ,{ Logical, TableFunction }
+            ,{ Logical, HopWindow }
+            ,{ Logical, Dedup }
+            ,{ Logical, DynamicFilter }
             // ,{ Logical, Sort } we don't need a LogicalSort, just require the Order
             ,{ Batch, SimpleAgg }
             ,{ Batch, HashAgg }
@@ -320,6 +344,8 @@ macro_rules! for_all_plan_nodes {
             ,{ Batch, Sort }
             ,{ Batch, Exchange }
             ,{ Batch, Limit }
+            ,{ Batch, TableFunction }
+            ,{ Batch, HopWindow }
             ,{ Stream, Project }
             ,{ Stream, Filter }
             ,{ Stream, TableScan }
@@ -329,6 +355,10 @@ macro_rules! for_all_plan_nodes {
             ,{ Stream, HashAgg }
             ,{ Stream, SimpleAgg }
             ,{ Stream, Materialize }
+            ,{ Stream, HopWindow }
+            ,{ Stream, TemporalJoin }
+            ,{ Stream, Dedup }
+            ,{ Stream, DynamicFilter }
         }
     };
 }
@@ -350,6 +380,10 @@ macro_rules! for_logical_plan_nodes {
             ,{ Logical, Values }
             ,{ Logical, Limit }
             ,{ Logical, TopN }
+            ,{ Logical, TableFunction }
+            ,{ Logical, HopWindow }
+            ,{ Logical, Dedup }
+            ,{ Logical, DynamicFilter }
             // ,{ Logical, Sort} not sure if we will support Order by clause in subquery/view/MV
             // if we dont support thatk, we don't need LogicalSort, just require the Order at the top of query
         }
@@ -374,6 +408,8 @@ macro_rules! for_batch_plan_nodes {
             ,{ Batch, Exchange }
             ,{ Batch, Insert }
             ,{ Batch, Delete }
+            ,{ Batch, TableFunction }
+            ,{ Batch, HopWindow }
         }
     };
 }
@@ -393,6 +429,10 @@ macro_rules! for_stream_plan_nodes {
             ,{ Stream, HashAgg }
             ,{ Stream, SimpleAgg }
             ,{ Stream, Materialize }
+            ,{ Stream, HopWindow }
+            ,{ Stream, TemporalJoin }
+            ,{ Stream, Dedup }
+            ,{ Stream, DynamicFilter }
         }
     };
 }
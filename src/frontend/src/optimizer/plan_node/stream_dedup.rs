@@ -0,0 +1,75 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::stream_plan::stream_node::Node as ProstStreamNode;
+use risingwave_pb::stream_plan::AppendOnlyDedupNode;
+
+use super::{LogicalDedup, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+
+/// [`StreamDedup`] only keeps the first-seen row of each key on an append-only stream.
+#[derive(Debug, Clone)]
+pub struct StreamDedup {
+    pub base: PlanBase,
+    logical: LogicalDedup,
+}
+
+impl StreamDedup {
+    pub fn new(logical: LogicalDedup) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let input = logical.input();
+        let pk_indices = logical.base.pk_indices.to_vec();
+        // Rows are either passed through or dropped, so the output is append-only as long as the
+        // input is.
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            pk_indices,
+            input.distribution().clone(),
+            input.append_only(),
+        );
+        StreamDedup { base, logical }
+    }
+
+    pub fn dedup_cols(&self) -> &[usize] {
+        self.logical.dedup_cols()
+    }
+}
+
+impl fmt::Display for StreamDedup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamAppendOnlyDedup")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamDedup {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamDedup }
+
+impl ToStreamProst for StreamDedup {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::AppendOnlyDedupNode(AppendOnlyDedupNode {
+            dedup_column_indices: self.dedup_cols().iter().map(|idx| *idx as u32).collect(),
+        })
+    }
+}
@@ -15,12 +15,13 @@
 use std::fmt;
 
 use itertools::Itertools;
+use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
 use risingwave_pb::plan::JoinType;
 use risingwave_pb::stream_plan::stream_node::Node;
-use risingwave_pb::stream_plan::HashJoinNode;
+use risingwave_pb::stream_plan::{HashJoinNode, IntervalJoinBound};
 
 use super::{LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, ToStreamProst};
-use crate::expr::Expr;
+use crate::expr::{Expr, ExprImpl, ExprType};
 use crate::optimizer::plan_node::EqJoinPredicate;
 use crate::optimizer::property::Distribution;
 use crate::utils::ColIndexMapping;
@@ -36,6 +37,20 @@ pub struct StreamHashJoin {
     /// The join condition must be equivalent to `logical.on`, but separated into equal and
     /// non-equal parts to facilitate execution later
     eq_join_predicate: EqJoinPredicate,
+
+    /// Set if the non-equal condition bounds the time columns of both sides, i.e. `left.time_col
+    /// BETWEEN right.time_col + lower AND right.time_col + upper`. The join states can then be
+    /// cleaned by the watermarks of the time columns.
+    interval_bound: Option<IntervalBound>,
+}
+
+/// The time bound of an interval join. `right_time_col` is the index in the right input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalBound {
+    pub left_time_col: usize,
+    pub right_time_col: usize,
+    pub lower_bound: IntervalUnit,
+    pub upper_bound: IntervalUnit,
 }
 
 impl StreamHashJoin {
@@ -60,11 +75,13 @@ impl StreamHashJoin {
             dist,
             append_only,
         );
+        let interval_bound = Self::derive_interval_bound(&logical, &eq_join_predicate);
 
         Self {
             base,
             logical,
             eq_join_predicate,
+            interval_bound,
         }
     }
 
@@ -73,6 +90,129 @@ impl StreamHashJoin {
         &self.eq_join_predicate
     }
 
+    pub fn interval_bound(&self) -> Option<&IntervalBound> {
+        self.interval_bound.as_ref()
+    }
+
+    /// Find a pair of timestamp columns bounded from both directions in the non-equal condition.
+    /// Only inner joins are supported, as outer joins must emit the unmatched rows before cleaning
+    /// them.
+    pub(super) fn derive_interval_bound(
+        logical: &LogicalJoin,
+        predicate: &EqJoinPredicate,
+    ) -> Option<IntervalBound> {
+        if logical.join_type() != JoinType::Inner {
+            return None;
+        }
+        let left_col_num = logical.left().schema().len();
+
+        // `(left_col, right_col) -> (lower, upper)`
+        let mut bounds: Vec<((usize, usize), (Option<IntervalUnit>, Option<IntervalUnit>))> =
+            vec![];
+        for expr in &predicate.other_cond().conjunctions {
+            let call = match expr {
+                ExprImpl::FunctionCall(call) => call,
+                _ => continue,
+            };
+            let mut func_type = call.get_expr_type();
+            if !matches!(
+                func_type,
+                ExprType::GreaterThan
+                    | ExprType::GreaterThanOrEqual
+                    | ExprType::LessThan
+                    | ExprType::LessThanOrEqual
+            ) {
+                continue;
+            }
+            let (mut lhs, mut rhs) = match (
+                Self::time_col_with_offset(&call.inputs()[0]),
+                Self::time_col_with_offset(&call.inputs()[1]),
+            ) {
+                (Some(lhs), Some(rhs)) => (lhs, rhs),
+                _ => continue,
+            };
+            if lhs.0 >= left_col_num && rhs.0 < left_col_num {
+                std::mem::swap(&mut lhs, &mut rhs);
+                func_type = match func_type {
+                    ExprType::GreaterThan => ExprType::LessThan,
+                    ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
+                    ExprType::LessThan => ExprType::GreaterThan,
+                    _ => ExprType::GreaterThanOrEqual,
+                };
+            }
+            if !(lhs.0 < left_col_num && rhs.0 >= left_col_num) {
+                continue;
+            }
+            // `l + a OP r + b` => `l OP r + (b - a)`. Strictness is ignored, as the bound is only
+            // used to clean the states and the condition itself is still evaluated on each match.
+            let offset = rhs.1 - lhs.1;
+            let key = (lhs.0, rhs.0 - left_col_num);
+            let pos = match bounds.iter().position(|(k, _)| *k == key) {
+                Some(pos) => pos,
+                None => {
+                    bounds.push((key, (None, None)));
+                    bounds.len() - 1
+                }
+            };
+            let entry = &mut bounds[pos].1;
+            match func_type {
+                ExprType::GreaterThan | ExprType::GreaterThanOrEqual => {
+                    entry.0.get_or_insert(offset);
+                }
+                _ => {
+                    entry.1.get_or_insert(offset);
+                }
+            }
+        }
+
+        bounds.into_iter().find_map(|((l, r), bound)| match bound {
+            (Some(lower_bound), Some(upper_bound)) => Some(IntervalBound {
+                left_time_col: l,
+                right_time_col: r,
+                lower_bound,
+                upper_bound,
+            }),
+            _ => None,
+        })
+    }
+
+    /// Match `col`, `col + interval`, `interval + col` or `col - interval` where `col` is a
+    /// timestamp column.
+    fn time_col_with_offset(expr: &ExprImpl) -> Option<(usize, IntervalUnit)> {
+        let interval = |expr: &ExprImpl| match expr {
+            ExprImpl::Literal(literal) => match literal.get_data() {
+                Some(ScalarImpl::Interval(interval)) => Some(*interval),
+                _ => None,
+            },
+            _ => None,
+        };
+        match expr {
+            ExprImpl::InputRef(input_ref) if input_ref.return_type() == DataType::Timestamp => {
+                Some((input_ref.index(), IntervalUnit::default()))
+            }
+            ExprImpl::FunctionCall(call) if call.inputs().len() == 2 => {
+                let (lhs, rhs) = (&call.inputs()[0], &call.inputs()[1]);
+                match call.get_expr_type() {
+                    ExprType::Add => {
+                        if let Some((col, _)) = Self::time_col_with_offset(lhs) {
+                            interval(rhs).map(|i| (col, i))
+                        } else if let Some((col, _)) = Self::time_col_with_offset(rhs) {
+                            interval(lhs).map(|i| (col, i))
+                        } else {
+                            None
+                        }
+                    }
+                    ExprType::Subtract => {
+                        let (col, _) = Self::time_col_with_offset(lhs)?;
+                        interval(rhs).map(|i| (col, i.negative()))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     fn derive_dist(
         left: &Distribution,
         right: &Distribution,
@@ -95,10 +235,18 @@ impl fmt::Display for StreamHashJoin {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "StreamHashJoin {{ type: {:?}, predicate: {} }}",
+            "StreamHashJoin {{ type: {:?}, predicate: {}",
             self.logical.join_type(),
             self.eq_join_predicate()
-        )
+        )?;
+        if let Some(bound) = &self.interval_bound {
+            write!(
+                f,
+                ", interval_bound: [{}, {}]",
+                bound.lower_bound, bound.upper_bound
+            )?;
+        }
+        write!(f, " }}")
     }
 }
 
@@ -149,6 +297,12 @@ impl ToStreamProst for StreamHashJoin {
                 .iter()
                 .map(|idx| *idx as i32)
                 .collect_vec(),
+            interval_bound: self.interval_bound.as_ref().map(|bound| IntervalJoinBound {
+                left_time_col: bound.left_time_col as u32,
+                right_time_col: bound.right_time_col as u32,
+                lower_bound: Some(bound.lower_bound.into()),
+                upper_bound: Some(bound.upper_bound.into()),
+            }),
         })
     }
 }
@@ -0,0 +1,76 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::expr::InputRefExpr;
+use risingwave_pb::stream_plan::stream_node::Node as ProstStreamNode;
+use risingwave_pb::stream_plan::HopWindowNode;
+
+use super::{LogicalHopWindow, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::optimizer::plan_node::PlanBase;
+
+/// [`StreamHopWindow`] represents a hop window table function.
+#[derive(Debug, Clone)]
+pub struct StreamHopWindow {
+    pub base: PlanBase,
+    logical: LogicalHopWindow,
+}
+
+impl StreamHopWindow {
+    pub fn new(logical: LogicalHopWindow) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let input = logical.input();
+        let pk_indices = logical.base.pk_indices.to_vec();
+        // Input columns keep their positions, so the input distribution still holds.
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            pk_indices,
+            input.distribution().clone(),
+            input.append_only(),
+        );
+        Self { base, logical }
+    }
+}
+
+impl fmt::Display for StreamHopWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamHopWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamHopWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! {StreamHopWindow}
+
+impl ToStreamProst for StreamHopWindow {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::HopWindowNode(HopWindowNode {
+            time_col: Some(InputRefExpr {
+                column_idx: self.logical.time_col().index() as i32,
+            }),
+            window_slide: Some(self.logical.window_slide().into()),
+            window_size: Some(self.logical.window_size().into()),
+        })
+    }
+}
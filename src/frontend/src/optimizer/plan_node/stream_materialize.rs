@@ -151,6 +151,7 @@ impl StreamMaterialize {
             name: mv_name,
             columns,
             pk_desc,
+            is_creating: false,
         };
 
         Ok(Self { base, input, table })
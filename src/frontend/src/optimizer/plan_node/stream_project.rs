@@ -18,7 +18,7 @@ use risingwave_pb::stream_plan::stream_node::Node as ProstStreamNode;
 use risingwave_pb::stream_plan::ProjectNode;
 
 use super::{LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
-use crate::expr::Expr;
+use crate::expr::{Expr, ExprImpl};
 
 /// `StreamProject` implements [`super::LogicalProject`] to evaluate specified expressions on input
 /// rows.
@@ -35,6 +35,10 @@ impl fmt::Display for StreamProject {
 }
 
 impl StreamProject {
+    pub fn exprs(&self) -> &Vec<ExprImpl> {
+        self.logical.exprs()
+    }
+
     pub fn new(logical: LogicalProject) -> Self {
         let ctx = logical.base.ctx.clone();
         let input = logical.input();
@@ -86,6 +86,7 @@ impl ToStreamProst for StreamSimpleAgg {
                 .iter()
                 .map(|idx| *idx as i32)
                 .collect_vec(),
+            is_append_only: self.input().append_only(),
         })
     }
 }
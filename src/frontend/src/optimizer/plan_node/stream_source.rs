@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::plan::TableRefId;
 use risingwave_pb::stream_plan::stream_node::Node as ProstStreamNode;
 use risingwave_pb::stream_plan::SourceNode;
@@ -35,11 +36,20 @@ impl StreamSource {
             logical.schema().clone(),
             logical.pk_indices().to_vec(),
             Distribution::any().clone(),
-            false, // TODO: determine the `append-only` field of source
+            logical.source_catalog.append_only,
         );
         Self { base, logical }
     }
 
+    /// Returns the columns with watermarks generated by the source.
+    pub fn watermark_columns(&self) -> FixedBitSet {
+        let mut columns = FixedBitSet::with_capacity(self.schema().len());
+        for desc in &self.logical.source_catalog.watermark_descs {
+            columns.insert(desc.watermark_idx as usize);
+        }
+        columns
+    }
+
     pub fn column_names(&self) -> Vec<String> {
         self.schema()
             .fields()
@@ -55,9 +65,10 @@ impl fmt::Display for StreamSource {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "StreamSource {{ source: {},  columns: [{}] }}",
+            "StreamSource {{ source: {},  columns: [{}]{} }}",
             self.logical.source_catalog.name,
-            self.column_names().join(", ")
+            self.column_names().join(", "),
+            self.logical.fmt_pruned_fields()
         )
     }
 }
@@ -80,6 +91,14 @@ impl ToStreamProst for StreamSource {
                 .collect(),
             source_type: self.logical.source_catalog.source_type as i32,
             stream_source_state: None,
+            pruned_field_ids: self
+                .logical
+                .pruned_field_ids
+                .iter()
+                .map(|id| id.get_id())
+                .collect(),
+            watermark_descs: self.logical.source_catalog.watermark_descs.clone(),
+            rate_limit: self.logical.source_catalog.rate_limit.unwrap_or(0),
         })
     }
 }
@@ -30,6 +30,8 @@ pub struct StreamTableScan {
     pub base: PlanBase,
     logical: LogicalScan,
     batch_plan_id: PlanNodeId,
+    /// The maximum number of snapshot rows per second each chain actor backfills, if limited.
+    rate_limit: Option<u32>,
 }
 
 impl StreamTableScan {
@@ -49,6 +51,14 @@ impl StreamTableScan {
             base,
             logical,
             batch_plan_id,
+            rate_limit: None,
+        }
+    }
+
+    pub fn with_rate_limit(&self, rate_limit: Option<u32>) -> Self {
+        Self {
+            rate_limit,
+            ..self.clone()
         }
     }
 
@@ -62,11 +72,15 @@ impl fmt::Display for StreamTableScan {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "StreamTableScan {{ table: {}, columns: [{}], pk_indices: {:?} }}",
+            "StreamTableScan {{ table: {}, columns: [{}], pk_indices: {:?}",
             self.logical.table_name(),
             self.logical.column_names().join(", "),
             self.base.pk_indices
-        )
+        )?;
+        if let Some(rate_limit) = self.rate_limit {
+            write!(f, ", rate_limit: {}", rate_limit)?;
+        }
+        write!(f, " }}")
     }
 }
 
@@ -153,6 +167,7 @@ impl StreamTableScan {
                     .iter()
                     .map(|x| x.column_id.get_id())
                     .collect(),
+                rate_limit: self.rate_limit.unwrap_or(0),
             })),
             pk_indices,
             operator_id: if auto_fields {
@@ -0,0 +1,166 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::ColumnId;
+use risingwave_pb::expr::InputRefExpr;
+use risingwave_pb::plan::{ColumnOrder, TableRefId};
+use risingwave_pb::stream_plan::stream_node::Node;
+use risingwave_pb::stream_plan::LookupNode;
+
+use super::{LogicalJoin, LogicalScan, PlanBase, PlanRef, PlanTreeNodeBinary, ToStreamProst};
+use crate::optimizer::plan_node::EqJoinPredicate;
+use crate::optimizer::property::Distribution;
+
+/// `StreamTemporalJoin` joins the stream with a table scanned `FOR SYSTEM_TIME AS OF PROCTIME()`.
+/// Each row from the stream looks up the current state of the table, so the stream side is not
+/// kept in the states and the changes of the table won't affect the rows already joined.
+#[derive(Debug, Clone)]
+pub struct StreamTemporalJoin {
+    pub base: PlanBase,
+    logical: LogicalJoin,
+
+    /// The join condition must be equivalent to `logical.on`, but separated into equal and
+    /// non-equal parts to facilitate execution later
+    eq_join_predicate: EqJoinPredicate,
+
+    /// The scan of the dimension table, to lookup from its storage.
+    right_scan: LogicalScan,
+}
+
+impl StreamTemporalJoin {
+    pub fn new(
+        logical: LogicalJoin,
+        eq_join_predicate: EqJoinPredicate,
+        right_scan: LogicalScan,
+    ) -> Self {
+        assert!(eq_join_predicate.other_cond().always_true());
+        let ctx = logical.base.ctx.clone();
+        // The output only changes with the stream side.
+        let append_only = logical.left().append_only();
+        let dist = logical
+            .l2o_col_mapping()
+            .rewrite_provided_distribution(logical.left().distribution());
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            logical.base.pk_indices.to_vec(),
+            dist,
+            append_only,
+        );
+
+        Self {
+            base,
+            logical,
+            eq_join_predicate,
+            right_scan,
+        }
+    }
+
+    /// Get a reference to the temporal join's eq join predicate.
+    pub fn eq_join_predicate(&self) -> &EqJoinPredicate {
+        &self.eq_join_predicate
+    }
+
+    /// The index of the column in the scan's columns.
+    fn col_idx_in_scan(&self, column_id: ColumnId) -> usize {
+        self.right_scan
+            .column_descs()
+            .iter()
+            .position(|col| col.column_id == column_id)
+            .unwrap()
+    }
+}
+
+impl fmt::Display for StreamTemporalJoin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "StreamTemporalJoin {{ type: {:?}, predicate: {} }}",
+            self.logical.join_type(),
+            self.eq_join_predicate()
+        )
+    }
+}
+
+impl PlanTreeNodeBinary for StreamTemporalJoin {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            self.logical.clone_with_left_right(left, right),
+            self.eq_join_predicate.clone(),
+            self.right_scan.clone(),
+        )
+    }
+}
+
+impl_plan_tree_node_for_binary! { StreamTemporalJoin }
+
+impl ToStreamProst for StreamTemporalJoin {
+    fn to_stream_prost_body(&self) -> Node {
+        let table_desc = self.right_scan.table_desc();
+        let column_descs = self.right_scan.column_descs();
+
+        // The join keys are looked up as the prefix of the primary key of the table.
+        let arrange_key = self
+            .eq_join_predicate
+            .right_eq_indexes()
+            .into_iter()
+            .map(|idx| {
+                table_desc
+                    .pk
+                    .iter()
+                    .position(|col| col.column_desc.column_id == column_descs[idx].column_id)
+                    .unwrap() as i32
+            })
+            .collect();
+
+        Node::LookupNode(LookupNode {
+            arrange_key,
+            stream_key: self
+                .eq_join_predicate
+                .left_eq_indexes()
+                .iter()
+                .map(|v| *v as i32)
+                .collect(),
+            use_current_epoch: true,
+            join_type: self.logical.join_type() as i32,
+            arrangement_table_ref_id: Some(TableRefId {
+                table_id: table_desc.table_id.table_id as i32,
+                schema_ref_id: None, // TODO: fill schema ref id
+            }),
+            arrangement_column_descs: column_descs.iter().map(|col| col.to_protobuf()).collect(),
+            arrangement_column_orders: table_desc
+                .pk
+                .iter()
+                .map(|col| ColumnOrder {
+                    order_type: col.order.to_prost() as i32,
+                    input_ref: Some(InputRefExpr {
+                        column_idx: self.col_idx_in_scan(col.column_desc.column_id) as i32,
+                    }),
+                    return_type: Some(col.column_desc.data_type.to_protobuf()),
+                })
+                .collect_vec(),
+        })
+    }
+}
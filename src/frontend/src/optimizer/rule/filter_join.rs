@@ -53,6 +53,12 @@ impl Rule for FilterJoinRule {
         let join_type = join.join_type();
         let left_col_num = join.left().schema().len();
         let right_col_num = join.right().schema().len();
+        // The dimension table of a temporal join must be directly under the join, so that it can
+        // be looked up by the join.
+        let right_is_temporal = join
+            .right()
+            .as_logical_scan()
+            .map_or(false, |scan| scan.for_system_time_as_of_proctime());
 
         let mut new_filter_predicate = filter.predicate().clone();
 
@@ -61,7 +67,7 @@ impl Rule for FilterJoinRule {
             left_col_num,
             right_col_num,
             self.can_push_left_from_filter(join_type),
-            self.can_push_right_from_filter(join_type),
+            self.can_push_right_from_filter(join_type) && !right_is_temporal,
             self.can_push_on_from_filter(join_type),
         );
 
@@ -75,7 +81,7 @@ impl Rule for FilterJoinRule {
             left_col_num,
             right_col_num,
             self.can_push_left_from_on(join_type),
-            self.can_push_right_from_on(join_type),
+            self.can_push_right_from_on(join_type) && !right_is_temporal,
             false,
         );
         assert!(on.is_none(), "On-clause should not be pushed to on-clause.");
@@ -12,20 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
 use std::rc::Rc;
 
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
+use risingwave_pb::plan::JoinType;
 
 use crate::binder::{
     BoundBaseTable, BoundJoin, BoundSource, BoundWindowTableFunction, Relation,
     WindowTableFunctionKind,
 };
-use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef};
+use crate::expr::{Expr, ExprImpl, ExprType, FunctionCall, InputRef};
 use crate::optimizer::plan_node::{
-    LogicalJoin, LogicalProject, LogicalScan, LogicalSource, PlanRef,
+    EqJoinPredicate, LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSource,
+    LogicalTableFunction, PlanRef,
 };
 use crate::planner::Planner;
+use crate::utils::Condition;
 
 impl Planner {
     pub(super) fn plan_relation(&mut self, relation: Relation) -> Result<PlanRef> {
@@ -36,6 +40,7 @@ impl Planner {
             Relation::Join(join) => self.plan_join(*join),
             Relation::WindowTableFunction(tf) => self.plan_window_table_function(*tf),
             Relation::Source(s) => self.plan_source(*s),
+            Relation::TableFunction(tf) => Ok(LogicalTableFunction::new(*tf, self.ctx()).into()),
         }
     }
 
@@ -43,6 +48,7 @@ impl Planner {
         LogicalScan::create(
             base_table.name,
             Rc::new(base_table.table_catalog.table_desc()),
+            base_table.for_system_time_as_of_proctime,
             self.ctx(),
         )
     }
@@ -56,9 +62,62 @@ impl Planner {
         let right = self.plan_relation(join.right)?;
         let join_type = join.join_type;
         let on_clause = join.cond;
+        if let Some(scan) = right.as_logical_scan() && scan.for_system_time_as_of_proctime() {
+            Self::check_temporal_join(left.schema().len(), scan, join_type, &on_clause)?;
+        }
         Ok(LogicalJoin::create(left, right, join_type, on_clause))
     }
 
+    /// A temporal join looks up the dimension table by the join keys, which requires the join
+    /// keys to be a prefix of the primary key of the table.
+    fn check_temporal_join(
+        left_col_num: usize,
+        scan: &LogicalScan,
+        join_type: JoinType,
+        on_clause: &ExprImpl,
+    ) -> Result<()> {
+        let predicate = EqJoinPredicate::create(
+            left_col_num,
+            scan.schema().len(),
+            Condition::with_expr(on_clause.clone()),
+        );
+        if !predicate.has_eq() {
+            return Err(ErrorCode::NotImplemented(
+                "temporal join without equal conditions".into(),
+                None.into(),
+            )
+            .into());
+        }
+        if join_type == JoinType::LeftOuter && predicate.has_non_eq() {
+            return Err(ErrorCode::NotImplemented(
+                "non-equal conditions in temporal left join".into(),
+                None.into(),
+            )
+            .into());
+        }
+
+        let column_descs = scan.column_descs();
+        let pk = &scan.table_desc().pk;
+        let pk_positions: BTreeSet<_> = predicate
+            .right_eq_indexes()
+            .into_iter()
+            .map(|idx| {
+                let column_id = column_descs[idx].column_id;
+                pk.iter()
+                    .position(|col| col.column_desc.column_id == column_id)
+            })
+            .collect::<Option<_>>()
+            .unwrap_or_default();
+        if pk_positions.is_empty() || pk_positions.iter().copied().ne(0..pk_positions.len()) {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "the join keys of temporal join must be a prefix of the primary key of table {}",
+                scan.table_name()
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
     pub(super) fn plan_window_table_function(
         &mut self,
         table_function: BoundWindowTableFunction,
@@ -70,11 +129,11 @@ impl Planner {
                 table_function.time_col,
                 table_function.args,
             ),
-            Hop => Err(ErrorCode::NotImplemented(
-                "HOP window function is not implemented yet".to_string(),
-                1191.into(),
-            )
-            .into()),
+            Hop => self.plan_hop_window(
+                table_function.input,
+                table_function.time_col,
+                table_function.args,
+            ),
         }
     }
 
@@ -129,4 +188,54 @@ impl Planner {
             .into()),
         }
     }
+
+    fn plan_hop_window(
+        &mut self,
+        input: Relation,
+        time_col: InputRef,
+        args: Vec<ExprImpl>,
+    ) -> Result<PlanRef> {
+        let interval_arg = |arg: Option<ExprImpl>| -> Option<IntervalUnit> {
+            match arg? {
+                ExprImpl::Literal(literal) => match literal.get_data() {
+                    Some(ScalarImpl::Interval(interval)) => Some(*interval),
+                    _ => None,
+                },
+                _ => None,
+            }
+        };
+
+        let mut args = args.into_iter();
+        let (Some(window_slide), Some(window_size), None) =
+            (interval_arg(args.next()), interval_arg(args.next()), args.next()) else {
+            return Err(ErrorCode::BindError(
+                "Invalid arguments for HOP window function".to_string(),
+            )
+            .into());
+        };
+        if time_col.return_type() != DataType::Timestamp {
+            return Err(ErrorCode::BindError(format!(
+                "the time_col of HOP window function should be timestamp, but got {:?}",
+                time_col.return_type()
+            ))
+            .into());
+        }
+        if window_slide <= IntervalUnit::default()
+            || !matches!(window_size.exact_div(&window_slide), Some(units) if units > 0)
+        {
+            return Err(ErrorCode::BindError(format!(
+                "window_size {} must be a positive multiple of window_slide {}",
+                window_size, window_slide
+            ))
+            .into());
+        }
+
+        let base = self.plan_relation(input)?;
+        Ok(LogicalHopWindow::create(
+            base,
+            time_col,
+            window_slide,
+            window_size,
+        ))
+    }
 }
@@ -18,26 +18,27 @@ use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
 use risingwave_pb::plan::JoinType;
 
-use crate::binder::BoundSelect;
+use crate::binder::{BoundDistinct, BoundSelect};
 use crate::expr::{
     Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Subquery, SubqueryKind,
 };
 pub use crate::optimizer::plan_node::LogicalFilter;
 use crate::optimizer::plan_node::{
-    LogicalAgg, LogicalApply, LogicalJoin, LogicalProject, LogicalValues, PlanAggCall, PlanRef,
+    LogicalAgg, LogicalApply, LogicalDedup, LogicalDynamicFilter, LogicalJoin, LogicalProject,
+    LogicalValues, PlanAggCall, PlanRef,
 };
 use crate::planner::Planner;
-use crate::utils::Condition;
+use crate::utils::{ColIndexMapping, Condition};
 impl Planner {
     pub(super) fn plan_select(
         &mut self,
         BoundSelect {
+            distinct,
             from,
             where_clause,
             mut select_items,
             group_by,
-            aliases,
-            ..
+            mut aliases,
         }: BoundSelect,
     ) -> Result<PlanRef> {
         // Plan the FROM clause.
@@ -53,15 +54,62 @@ impl Planner {
         // TODO: select-agg, group-by, having can also contain subquery exprs.
         let has_agg_call = select_items.iter().any(|expr| expr.has_agg_call());
         if !group_by.is_empty() || has_agg_call {
+            if let BoundDistinct::DistinctOn(_) = distinct {
+                return Err(ErrorCode::NotImplemented(
+                    "DISTINCT ON with aggregation".to_string(),
+                    None.into(),
+                )
+                .into());
+            }
             LogicalAgg::create(select_items, aliases, group_by, root)
         } else {
+            let out_len = select_items.len();
+            let dedup_cols = match distinct {
+                BoundDistinct::DistinctOn(exprs) => Some(Self::append_distinct_on_exprs(
+                    &mut select_items,
+                    &mut aliases,
+                    exprs,
+                )),
+                BoundDistinct::All | BoundDistinct::Distinct => None,
+            };
             if select_items.iter().any(|e| e.has_subquery()) {
                 (root, select_items) = self.substitute_subqueries(root, select_items)?;
             }
-            Ok(LogicalProject::create(root, select_items, aliases))
+            root = LogicalProject::create(root, select_items, aliases);
+            if let Some(dedup_cols) = dedup_cols {
+                root = LogicalDedup::create(root, dedup_cols);
+                if root.schema().len() != out_len {
+                    // Remove the `DISTINCT ON` exprs which are not selected.
+                    let mapping = ColIndexMapping::identity_or_none(root.schema().len(), out_len);
+                    root = LogicalProject::with_mapping(root, mapping);
+                }
+            }
+            Ok(root)
         }
     }
 
+    /// Appends the `DISTINCT ON` exprs which are not in the select list to it, and returns the
+    /// positions of all of them in the select list.
+    fn append_distinct_on_exprs(
+        select_items: &mut Vec<ExprImpl>,
+        aliases: &mut Vec<Option<String>>,
+        exprs: Vec<ExprImpl>,
+    ) -> Vec<usize> {
+        exprs
+            .into_iter()
+            .map(
+                |expr| match select_items.iter().position(|item| *item == expr) {
+                    Some(idx) => idx,
+                    None => {
+                        select_items.push(expr);
+                        aliases.push(None);
+                        select_items.len() - 1
+                    }
+                },
+            )
+            .collect()
+    }
+
     /// Helper to create a dummy node as child of [`LogicalProject`].
     /// For example, `select 1+2, 3*4` will be `Project([1+2, 3+4]) - Values([[]])`.
     fn create_dummy_values(&self) -> PlanRef {
@@ -154,10 +202,18 @@ impl Planner {
             input = Self::create_apply_or_join(is_correlated, input, right, join_type);
         }
 
-        if others.always_true() {
+        let (dynamic_filters, others): (Vec<_>, Vec<_>) = others
+            .conjunctions
+            .into_iter()
+            .partition(Self::is_dynamic_filter);
+        for expr in dynamic_filters {
+            input = self.plan_dynamic_filter(input, expr)?;
+        }
+
+        if others.is_empty() {
             Ok(input)
         } else {
-            let (input, others) = self.substitute_subqueries(input, others.conjunctions)?;
+            let (input, others) = self.substitute_subqueries(input, others)?;
             Ok(LogicalFilter::create(
                 input,
                 Condition {
@@ -167,6 +223,110 @@ impl Planner {
         }
     }
 
+    /// Whether `expr` compares an expression with an uncorrelated scalar subquery returning at
+    /// most one row, which can be planned as a [`LogicalDynamicFilter`].
+    fn is_dynamic_filter(expr: &ExprImpl) -> bool {
+        let func_call = match expr {
+            ExprImpl::FunctionCall(func_call) => func_call,
+            _ => return false,
+        };
+        if !matches!(
+            func_call.get_expr_type(),
+            ExprType::LessThan
+                | ExprType::LessThanOrEqual
+                | ExprType::GreaterThan
+                | ExprType::GreaterThanOrEqual
+        ) {
+            return false;
+        }
+        let subquery = match func_call.inputs() {
+            [lhs, rhs] if !lhs.has_subquery() => rhs,
+            [lhs, rhs] if !rhs.has_subquery() => lhs,
+            _ => return false,
+        };
+        // The subquery may be implicitly casted to the type of the other side.
+        let subquery = match subquery {
+            ExprImpl::FunctionCall(cast) if cast.get_expr_type() == ExprType::Cast => {
+                &cast.inputs()[0]
+            }
+            _ => subquery,
+        };
+        match subquery {
+            ExprImpl::Subquery(subquery) => {
+                subquery.kind == SubqueryKind::Scalar
+                    && !subquery.is_correlated()
+                    && subquery.query.max_one_row()
+            }
+            _ => false,
+        }
+    }
+
+    /// Plans a comparison accepted by [`Self::is_dynamic_filter`] as a [`LogicalDynamicFilter`],
+    /// whose left side is `input` and right side is the planned subquery. The output schema is
+    /// the same as `input`.
+    fn plan_dynamic_filter(&mut self, input: PlanRef, expr: ExprImpl) -> Result<PlanRef> {
+        let (comparator, lhs, rhs) = expr.into_function_call().unwrap().decompose_as_binary();
+        let (comparator, expr, subquery_expr) = if lhs.has_subquery() {
+            let comparator = match comparator {
+                ExprType::LessThan => ExprType::GreaterThan,
+                ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
+                ExprType::GreaterThan => ExprType::LessThan,
+                ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
+                _ => unreachable!(),
+            };
+            (comparator, rhs, lhs)
+        } else {
+            (comparator, lhs, rhs)
+        };
+
+        // Plan the subquery as the right side, with the cast of it if any.
+        let (subquery, cast) = match subquery_expr {
+            ExprImpl::Subquery(subquery) => (*subquery, None),
+            ExprImpl::FunctionCall(cast) => {
+                let (expr_type, subquery, return_type) = cast.decompose();
+                let subquery = subquery.into_iter().exactly_one().unwrap();
+                (
+                    *subquery.into_subquery().unwrap(),
+                    Some((expr_type, return_type)),
+                )
+            }
+            _ => unreachable!(),
+        };
+        let subquery_type = subquery.return_type();
+        let mut right = self.plan_query(subquery.query)?.as_subplan();
+        if let Some((expr_type, return_type)) = cast {
+            let cast = FunctionCall::new_with_return_type(
+                expr_type,
+                vec![InputRef::new(0, subquery_type).into()],
+                return_type,
+            );
+            right = LogicalProject::create(right, vec![cast.into()], vec![None]);
+        }
+
+        let input_len = input.schema().len();
+        if let ExprImpl::InputRef(input_ref) = &expr {
+            let left_index = input_ref.index();
+            return Ok(LogicalDynamicFilter::create(
+                input, left_index, comparator, right, 0,
+            ));
+        }
+        // Compute the compared expression as an extra column, and remove it after the filter.
+        let mut exprs: Vec<ExprImpl> = input
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| InputRef::new(idx, field.data_type()).into())
+            .collect();
+        exprs.push(expr);
+        let left = LogicalProject::create(input, exprs, vec![None; input_len + 1]);
+        let filter = LogicalDynamicFilter::create(left, input_len, comparator, right, 0);
+        Ok(LogicalProject::with_mapping(
+            filter,
+            ColIndexMapping::identity_or_none(input_len + 1, input_len),
+        ))
+    }
+
     /// Substitutes all [`Subquery`] in `exprs`.
     ///
     /// Each time a [`Subquery`] is found, it is replaced by a new [`InputRef`]. And `root` is
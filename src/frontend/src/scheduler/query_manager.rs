@@ -22,6 +22,7 @@ use risingwave_common::array::DataChunk;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::common::HostAddress;
 use risingwave_pb::plan::{PlanNode as BatchPlanProst, TaskId, TaskOutputId};
+use risingwave_pb::task_service::ExchangeOptions;
 use risingwave_rpc_client::{ComputeClient, ExchangeSource};
 use uuid::Uuid;
 
@@ -164,7 +165,9 @@ impl QueryResultFetcher {
         );
         let compute_client: ComputeClient = ComputeClient::new((&self.task_host).into()).await?;
 
-        let mut source = compute_client.get_data(self.task_output_id).await?;
+        let mut source = compute_client
+            .get_data(self.task_output_id, ExchangeOptions::default())
+            .await?;
         while let Some(chunk) = source.take_data().await? {
             yield chunk;
         }
@@ -143,6 +143,7 @@ impl FrontendEnv {
 
         let catalog = Arc::new(RwLock::new(Catalog::default()));
         let catalog_writer = Arc::new(MockCatalogWriter::new(catalog.clone()));
+        let meta_client = Arc::new(MockFrontendMetaClient::new(catalog_writer.clone()));
         let catalog_reader = CatalogReader::new(catalog);
         let worker_node_manager = Arc::new(WorkerNodeManager::mock(vec![]));
         let query_manager = QueryManager::new(worker_node_manager.clone(), false);
@@ -150,7 +151,7 @@ impl FrontendEnv {
             catalog_writer,
             catalog_reader,
             worker_node_manager,
-            meta_client: Arc::new(MockFrontendMetaClient {}),
+            meta_client,
             query_manager,
         }
     }
@@ -21,11 +21,12 @@ use parking_lot::RwLock;
 use pgwire::pg_response::PgResponse;
 use pgwire::pg_server::{Session, SessionManager};
 use risingwave_common::catalog::{TableId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
-use risingwave_common::error::Result;
-use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::catalog::table::{OptionalAssociatedSourceId, StreamJobStatus};
 use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
 };
+use risingwave_pb::meta::list_jobs_response::Job;
 use risingwave_pb::stream_plan::StreamNode;
 use risingwave_sqlparser::ast::Statement;
 use risingwave_sqlparser::parser::Parser;
@@ -92,10 +93,10 @@ impl LocalFrontend {
                 );
                 binder.bind(Statement::Query(query.clone()))?
             };
-            Ok(Planner::new(OptimizerContext::new(session).into())
+            Planner::new(OptimizerContext::new(session).into())
                 .plan(bound)
                 .unwrap()
-                .gen_batch_query_plan())
+                .gen_batch_query_plan()
         } else {
             unreachable!()
         }
@@ -138,8 +139,12 @@ impl CatalogWriter for MockCatalogWriter {
         &self,
         mut table: ProstTable,
         _plan: StreamNode,
+        background: bool,
     ) -> Result<()> {
         table.id = self.gen_id();
+        if background {
+            table.set_stream_job_status(StreamJobStatus::Creating);
+        }
         self.catalog.write().create_table(&table);
         self.add_id(table.id, table.database_id, table.schema_id);
         Ok(())
@@ -154,7 +159,7 @@ impl CatalogWriter for MockCatalogWriter {
         let source_id = self.create_source_inner(source)?;
         table.optional_associated_source_id =
             Some(OptionalAssociatedSourceId::AssociatedSourceId(source_id));
-        self.create_materialized_view(table, plan).await?;
+        self.create_materialized_view(table, plan, false).await?;
         Ok(())
     }
 
@@ -190,6 +195,22 @@ impl CatalogWriter for MockCatalogWriter {
             .drop_table(database_id, schema_id, table_id);
         Ok(())
     }
+
+    async fn alter_source_rate_limit(
+        &self,
+        _source_id: u32,
+        _rate_limit: Option<u32>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn alter_materialized_view_rate_limit(
+        &self,
+        _table_id: TableId,
+        _rate_limit: Option<u32>,
+    ) -> Result<()> {
+        Ok(())
+    }
 }
 
 impl MockCatalogWriter {
@@ -224,6 +245,35 @@ impl MockCatalogWriter {
         self.id_to_schema_id.write().remove(&id).unwrap()
     }
 
+    /// The materialized views being created in background, without progress.
+    fn list_creating_jobs(&self) -> Vec<Job> {
+        let catalog = self.catalog.read();
+        let mut jobs = vec![];
+        for db_name in catalog.get_all_database_names() {
+            for schema_name in catalog.get_all_schema_names(&db_name).unwrap() {
+                let schema = catalog.get_schema_by_name(&db_name, &schema_name).unwrap();
+                jobs.extend(
+                    schema
+                        .iter_mv()
+                        .filter(|table| table.is_creating())
+                        .map(|table| Job {
+                            table_id: table.id().table_id,
+                            name: table.name().to_string(),
+                            ..Default::default()
+                        }),
+                );
+            }
+        }
+        jobs
+    }
+
+    /// Whether the materialized view is being created in background.
+    fn is_creating(&self, table_id: u32) -> bool {
+        self.list_creating_jobs()
+            .iter()
+            .any(|job| job.table_id == table_id)
+    }
+
     fn create_source_inner(&self, mut source: ProstSource) -> Result<u32> {
         source.id = self.gen_id();
         self.catalog.write().create_source(source.clone());
@@ -232,7 +282,15 @@ impl MockCatalogWriter {
     }
 }
 
-pub struct MockFrontendMetaClient {}
+pub struct MockFrontendMetaClient {
+    catalog_writer: Arc<MockCatalogWriter>,
+}
+
+impl MockFrontendMetaClient {
+    pub fn new(catalog_writer: Arc<MockCatalogWriter>) -> Self {
+        Self { catalog_writer }
+    }
+}
 
 #[async_trait::async_trait]
 impl FrontendMetaClient for MockFrontendMetaClient {
@@ -244,6 +302,33 @@ impl FrontendMetaClient for MockFrontendMetaClient {
         Ok(())
     }
 
+    async fn pause_sources(&self, _source_ids: Vec<u32>) -> Result<()> {
+        Ok(())
+    }
+
+    async fn resume_sources(&self, _source_ids: Vec<u32>) -> Result<()> {
+        Ok(())
+    }
+
+    async fn list_jobs(&self) -> Result<Vec<Job>> {
+        Ok(self.catalog_writer.list_creating_jobs())
+    }
+
+    async fn cancel_job(&self, table_id: u32) -> Result<()> {
+        // Like the meta service, only the materialized views being created can be cancelled, whose
+        // catalogs are dropped along with their actors.
+        if !self.catalog_writer.is_creating(table_id) {
+            return Err(ErrorCode::InternalError(format!(
+                "materialized view {} is not being created",
+                table_id
+            ))
+            .into());
+        }
+        self.catalog_writer
+            .drop_materialized_view(TableId::new(table_id))
+            .await
+    }
+
     async fn unpin_snapshot(&self, _epoch: u64) -> Result<()> {
         Ok(())
     }
@@ -179,9 +179,18 @@ impl TestCase {
                         or_replace: false,
                         name,
                         query,
+                        emit_on_window_close,
+                        with_options,
                         ..
                     } => {
-                        create_mv::handle_create_mv(context, name, query).await?;
+                        create_mv::handle_create_mv(
+                            context,
+                            name,
+                            query,
+                            emit_on_window_close,
+                            with_options,
+                        )
+                        .await?;
                     }
 
                     Statement::Drop(drop_statement) => {
@@ -240,7 +249,7 @@ impl TestCase {
         }
 
         if self.batch_plan.is_some() || self.batch_plan_proto.is_some() {
-            let batch_plan = logical_plan.gen_dist_batch_query_plan();
+            let batch_plan = logical_plan.gen_dist_batch_query_plan()?;
 
             // Only generate batch_plan if it is specified in test case
             if self.batch_plan.is_some() {
@@ -267,6 +276,8 @@ impl TestCase {
                 context,
                 Box::new(q),
                 ObjectName(vec!["test".into()]),
+                false,
+                None,
             )?;
 
             // Only generate stream_plan if it is specified in test case
@@ -19,15 +19,49 @@ use risingwave_common::catalog::TableId;
 use risingwave_common::error::{Result, RwError, ToRwResult};
 use risingwave_pb::common::ActorInfo;
 use risingwave_pb::data::barrier::Mutation;
-use risingwave_pb::data::{Actors, AddMutation, NothingMutation, StopMutation};
+use risingwave_pb::data::{
+    Actors, AddMutation, ConnectorSplit, ConnectorSplits, DispatcherUpdate, MergeUpdate,
+    NothingMutation, PauseMutation, RescheduleMutation, ResumeMutation, SourceChangeSplitMutation,
+    StopMutation, ThrottleMutation,
+};
+use risingwave_pb::stream_plan::ActorMapping;
 use risingwave_pb::stream_service::DropActorsRequest;
 use uuid::Uuid;
 
 use super::info::BarrierActorInfo;
-use crate::manager::StreamClientsRef;
-use crate::model::{ActorId, TableFragments};
+use crate::manager::{SourceId, StreamClientsRef};
+use crate::model::{ActorId, FragmentId, TableFragments};
 use crate::storage::MetaStore;
-use crate::stream::FragmentManagerRef;
+use crate::stream::{FragmentManagerRef, SplitAssignment};
+
+/// [`Reschedule`] describes how to add or remove actors of a hash-distributed fragment. Check
+/// [`Command::RescheduleFragment`] for details.
+#[derive(Debug, Clone)]
+pub struct Reschedule {
+    pub table_id: TableId,
+    pub fragment_id: FragmentId,
+
+    /// The actors to add, which have been built on compute nodes and marked as `Inactive`.
+    pub added_actors: Vec<ActorInfo>,
+    /// The actors to remove.
+    pub removed_actors: Vec<ActorId>,
+
+    /// The new mapping from virtual nodes to the actors of this fragment.
+    pub vnode_mapping: Vec<ActorId>,
+    /// The existing actors of this fragment whose virtual nodes are changed.
+    pub vnode_changed_actors: Vec<ActorId>,
+
+    /// The actors of the upstream fragments, which dispatch to this fragment by hash.
+    pub upstream_actors: Vec<ActorId>,
+    /// The actors of the downstream fragment, which merge the outputs of this fragment.
+    pub downstream_actors: Vec<ActorId>,
+}
+
+impl Reschedule {
+    pub fn added_actor_ids(&self) -> Vec<ActorId> {
+        self.added_actors.iter().map(|info| info.actor_id).collect()
+    }
+}
 
 /// [`Command`] is the action of [`crate::barrier::GlobalBarrierManager`]. For different commands,
 /// we'll build different barriers to send, and may do different stuffs after the barrier is
@@ -60,6 +94,45 @@ pub enum Command {
         table_sink_map: HashMap<TableId, Vec<ActorId>>,
         dispatches: HashMap<ActorId, Vec<ActorInfo>>,
     },
+
+    /// `RescheduleFragment` command generates a `Reschedule` barrier to add or remove actors of a
+    /// hash-distributed fragment, without recreating the materialized view.
+    ///
+    /// Barriers from the actors to be added, which are marked as `Inactive` at first, WILL be
+    /// collected, since they are connected to the upstream before the barrier is dispatched. The
+    /// actors to be removed are stopped after the barrier, and the remaining actors whose virtual
+    /// nodes are changed will evict their caches.
+    /// After the barrier is collected, it notifies compute nodes to drop the removed actors, and
+    /// then updates the table fragments info in meta store.
+    RescheduleFragment(Reschedule),
+
+    /// `SourceSplitAssignment` command generates a `Splits` barrier to change the splits read by
+    /// the given source actors, e.g., when the source has new splits discovered.
+    ///
+    /// The splits kept by an actor are never moved to another one in the same barrier. The states
+    /// of the splits moved must be committed by their previous actors before the barrier, from
+    /// which the actors newly assigned resume.
+    /// After the barrier is collected, it updates the splits of the actors in meta store.
+    SourceSplitAssignment(SplitAssignment),
+
+    /// `Throttle` command generates a `Throttle` barrier to change the rate limits (rows per
+    /// second) of the given source and chain actors, where 0 means unlimited.
+    ///
+    /// After the barrier is collected, it updates the rate limits of the actors in meta store, so
+    /// that they are kept when the actors are rebuilt.
+    Throttle(HashMap<ActorId, u32>),
+
+    /// `PauseSources` command generates a `Pause` barrier to pause the actors reading the given
+    /// stream sources. Barriers still flow through them.
+    ///
+    /// After the barrier is collected, the sources are recorded as paused, so that the actors
+    /// created later for them, by creating materialized views, rescheduling or recovery, start
+    /// paused as well.
+    PauseSources(HashSet<SourceId>),
+
+    /// `ResumeSources` command generates a `Resume` barrier to resume the actors reading the given
+    /// stream sources, or all paused sources if empty.
+    ResumeSources(HashSet<SourceId>),
 }
 
 impl Command {
@@ -67,6 +140,18 @@ impl Command {
         Self::Plain(Mutation::Nothing(NothingMutation {}))
     }
 
+    /// Applies the `PauseSources` or `ResumeSources` of this command to the set of paused sources.
+    pub fn update_paused_sources(&self, paused_sources: &mut HashSet<SourceId>) {
+        match self {
+            Command::PauseSources(source_ids) => paused_sources.extend(source_ids.iter().cloned()),
+            Command::ResumeSources(source_ids) if source_ids.is_empty() => paused_sources.clear(),
+            Command::ResumeSources(source_ids) => source_ids.iter().for_each(|source_id| {
+                paused_sources.remove(source_id);
+            }),
+            _ => {}
+        }
+    }
+
     pub fn creating_table_id(&self) -> Option<TableId> {
         match self {
             Command::CreateMaterializedView {
@@ -75,6 +160,27 @@ impl Command {
             _ => None,
         }
     }
+
+    /// Whether the barrier of this command must be a checkpoint. The commands changing the graph
+    /// require the states before them to be committed, e.g. the snapshot read by `Chain` and the
+    /// states read by the actors whose virtual nodes are changed.
+    pub fn need_checkpoint(&self) -> bool {
+        !matches!(
+            self,
+            Command::Plain(_)
+                | Command::Throttle(_)
+                | Command::PauseSources(_)
+                | Command::ResumeSources(_)
+        )
+    }
+
+    /// Returns the table whose `Inactive` actors should also be sent and collected the barrier.
+    pub fn changed_table_id(&self) -> Option<TableId> {
+        match self {
+            Command::RescheduleFragment(reschedule) => Some(reschedule.table_id),
+            _ => self.creating_table_id(),
+        }
+    }
 }
 
 /// [`CommandContext`] is used for generating barrier and doing post stuffs according to the given
@@ -91,16 +197,25 @@ pub struct CommandContext<'a, S> {
     pub prev_epoch: u64,
     pub curr_epoch: u64,
 
+    /// Whether to sync the states and commit `prev_epoch` after the barrier is collected.
+    pub checkpoint: bool,
+
+    /// The stream sources paused before this barrier. The actors added for them start paused.
+    paused_sources: HashSet<SourceId>,
+
     command: Command,
 }
 
 impl<'a, S> CommandContext<'a, S> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         fragment_manager: FragmentManagerRef<S>,
         clients: StreamClientsRef,
         info: &'a BarrierActorInfo,
         prev_epoch: u64,
         curr_epoch: u64,
+        checkpoint: bool,
+        paused_sources: HashSet<SourceId>,
         command: Command,
     ) -> Self {
         Self {
@@ -109,6 +224,8 @@ impl<'a, S> CommandContext<'a, S> {
             info,
             prev_epoch,
             curr_epoch,
+            checkpoint,
+            paused_sources,
             command,
         }
     }
@@ -128,7 +245,12 @@ where
                 Mutation::Stop(StopMutation { actors })
             }
 
-            Command::CreateMaterializedView { dispatches, .. } => {
+            Command::CreateMaterializedView {
+                table_fragments,
+                dispatches,
+                ..
+            } => {
+                let paused_actors = table_fragments.stream_source_actor_ids(&self.paused_sources);
                 let actors = dispatches
                     .iter()
                     .map(|(&up_actor_id, down_actor_infos)| {
@@ -140,7 +262,97 @@ where
                         )
                     })
                     .collect();
-                Mutation::Add(AddMutation { actors })
+                Mutation::Add(AddMutation {
+                    actors,
+                    paused_actors,
+                })
+            }
+
+            Command::RescheduleFragment(reschedule) => {
+                let source_id = self
+                    .fragment_manager
+                    .get_table_fragments(&reschedule.table_id)
+                    .await?
+                    .fragment_stream_source_id(reschedule.fragment_id);
+                let paused_actors = match source_id {
+                    Some(source_id) if self.paused_sources.contains(&source_id) => {
+                        reschedule.added_actor_ids()
+                    }
+                    _ => vec![],
+                };
+
+                let dispatcher_update = DispatcherUpdate {
+                    added_downstream_actors: reschedule.added_actors.clone(),
+                    removed_downstream_actor_id: reschedule.removed_actors.clone(),
+                    hash_mapping: reschedule.vnode_mapping.clone(),
+                };
+                let dispatchers = reschedule
+                    .upstream_actors
+                    .iter()
+                    .map(|&up_actor_id| (up_actor_id, dispatcher_update.clone()))
+                    .collect();
+
+                let merge_update = MergeUpdate {
+                    added_upstream_actors: reschedule.added_actors.clone(),
+                    removed_upstream_actor_id: reschedule.removed_actors.clone(),
+                };
+                let merges = reschedule
+                    .downstream_actors
+                    .iter()
+                    .map(|&down_actor_id| (down_actor_id, merge_update.clone()))
+                    .collect();
+
+                Mutation::Reschedule(RescheduleMutation {
+                    dispatchers,
+                    merges,
+                    stopped_actors: reschedule.removed_actors.clone(),
+                    vnode_changed_actors: reschedule.vnode_changed_actors.clone(),
+                    paused_actors,
+                })
+            }
+
+            Command::SourceSplitAssignment(split_assignment) => {
+                let actor_splits = split_assignment
+                    .iter()
+                    .map(|(&actor_id, splits)| {
+                        let splits = splits
+                            .iter()
+                            .map(|split| {
+                                Ok(ConnectorSplit {
+                                    split_type: split.get_type(),
+                                    encoded_split: split.to_string().to_rw_result()?.into_bytes(),
+                                })
+                            })
+                            .collect::<Result<_>>()?;
+                        Ok((actor_id, ConnectorSplits { splits }))
+                    })
+                    .collect::<Result<_>>()?;
+                Mutation::Splits(SourceChangeSplitMutation { actor_splits })
+            }
+
+            Command::Throttle(actor_rate_limits) => Mutation::Throttle(ThrottleMutation {
+                actor_throttle: actor_rate_limits.clone(),
+            }),
+
+            Command::PauseSources(source_ids) => {
+                let actors = self
+                    .fragment_manager
+                    .get_stream_source_actor_ids(source_ids)
+                    .await;
+                Mutation::Pause(PauseMutation { actors })
+            }
+
+            Command::ResumeSources(source_ids) => {
+                let source_ids = if source_ids.is_empty() {
+                    &self.paused_sources
+                } else {
+                    source_ids
+                };
+                let actors = self
+                    .fragment_manager
+                    .get_stream_source_actor_ids(source_ids)
+                    .await;
+                Mutation::Resume(ResumeMutation { actors })
             }
         };
 
@@ -163,7 +375,7 @@ where
     /// Do some stuffs after barriers are collected, for the given command.
     pub async fn post_collect(&self) -> Result<()> {
         match &self.command {
-            Command::Plain(_) => {}
+            Command::Plain(_) | Command::PauseSources(_) | Command::ResumeSources(_) => {}
 
             Command::DropMaterializedView(table_id) => {
                 // Tell compute nodes to drop actors.
@@ -178,6 +390,7 @@ where
                         let request = DropActorsRequest {
                             request_id,
                             actor_ids: actors.to_owned(),
+                            discard_unsynced_states: false,
                         };
                         client.drop_actors(request).await.to_rw_result()?;
 
@@ -220,6 +433,68 @@ where
                     )
                     .await?;
             }
+
+            Command::RescheduleFragment(reschedule) => {
+                // Tell compute nodes to drop the removed actors.
+                let removed_actors: HashSet<_> = reschedule.removed_actors.iter().collect();
+                let node_actors = self
+                    .fragment_manager
+                    .table_node_actors(&reschedule.table_id)
+                    .await?;
+                let futures = node_actors.iter().filter_map(|(node_id, actors)| {
+                    let actors = actors
+                        .iter()
+                        .filter(|actor_id| removed_actors.contains(actor_id))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if actors.is_empty() {
+                        return None;
+                    }
+                    let node = self.info.node_map.get(node_id).unwrap();
+                    let request_id = Uuid::new_v4().to_string();
+
+                    Some(async move {
+                        let mut client = self.clients.get(node).await?;
+                        tracing::debug!(request_id = %request_id, node = node_id, actors = ?actors, "drop actors");
+                        let request = DropActorsRequest {
+                            request_id,
+                            actor_ids: actors,
+                            discard_unsynced_states: false,
+                        };
+                        client.drop_actors(request).await.to_rw_result()?;
+
+                        Ok::<_, RwError>(())
+                    })
+                });
+
+                try_join_all(futures).await?;
+
+                // Update fragment info in meta store.
+                self.fragment_manager
+                    .apply_reschedule(
+                        &reschedule.table_id,
+                        &reschedule.upstream_actors,
+                        &reschedule.downstream_actors,
+                        &reschedule.removed_actors,
+                        &reschedule.added_actor_ids(),
+                        ActorMapping {
+                            hash_mapping: reschedule.vnode_mapping.clone(),
+                        },
+                    )
+                    .await?;
+            }
+
+            Command::SourceSplitAssignment(split_assignment) => {
+                self.fragment_manager
+                    .update_actor_splits(split_assignment)
+                    .await?;
+            }
+
+            Command::Throttle(actor_rate_limits) => {
+                self.fragment_manager
+                    .update_actor_rate_limits(actor_rate_limits)
+                    .await?;
+            }
         }
 
         Ok(())
@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::iter::once;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use futures::future::try_join_all;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
 use itertools::Itertools;
 use risingwave_common::catalog::TableId;
 use risingwave_common::error::{ErrorCode, Result, RwError, ToRwResult};
@@ -25,6 +27,7 @@ use risingwave_hummock_sdk::HummockEpoch;
 use risingwave_pb::common::worker_node::State::Running;
 use risingwave_pb::common::WorkerType;
 use risingwave_pb::data::Barrier;
+use risingwave_pb::meta::list_jobs_response::Job;
 use risingwave_pb::stream_service::{InjectBarrierRequest, InjectBarrierResponse};
 use smallvec::SmallVec;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -32,14 +35,15 @@ use tokio::sync::{oneshot, watch, RwLock};
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
-pub use self::command::Command;
 use self::command::CommandContext;
+pub use self::command::{Command, Reschedule};
 use self::info::BarrierActorInfo;
 use self::notifier::{Notifier, UnfinishedNotifiers};
-use crate::cluster::{ClusterManagerRef, META_NODE_ID};
+use self::progress::CreateMviewProgressTracker;
+use crate::cluster::{ClusterManagerRef, WorkerId, META_NODE_ID};
 use crate::hummock::HummockManagerRef;
 use crate::manager::{CatalogManagerRef, MetaSrvEnv, INVALID_EPOCH};
-use crate::model::BarrierManagerState;
+use crate::model::{ActorId, BarrierManagerState, TableRowCounts};
 use crate::rpc::metrics::MetaMetrics;
 use crate::storage::MetaStore;
 use crate::stream::FragmentManagerRef;
@@ -47,10 +51,14 @@ use crate::stream::FragmentManagerRef;
 mod command;
 mod info;
 mod notifier;
+mod progress;
 mod recovery;
 
 type Scheduled = (Command, SmallVec<[Notifier; 1]>);
 
+/// How long the failed jobs are kept in the jobs listed.
+const FAILED_JOB_TTL: Duration = Duration::from_secs(60 * 60);
+
 /// A buffer or queue for scheduling barriers.
 struct ScheduledBarriers {
     buffer: RwLock<VecDeque<Scheduled>>,
@@ -127,6 +135,37 @@ impl ScheduledBarriers {
     }
 }
 
+/// The failure of running a barrier. The actors reported failed by the compute nodes and the
+/// unreachable compute nodes are recorded, so that the recovery can be scoped to them.
+#[derive(Debug)]
+struct BarrierFailure {
+    err: RwError,
+
+    /// node_id => the actors failed before collecting the barrier. The barrier is not collected on
+    /// these nodes, including the ones not waited for after the failure, so the states on them are
+    /// not synced.
+    failed_actors: HashMap<WorkerId, Vec<ActorId>>,
+
+    /// The nodes that cannot be reached.
+    unreachable_nodes: HashSet<WorkerId>,
+}
+
+impl From<RwError> for BarrierFailure {
+    fn from(err: RwError) -> Self {
+        Self {
+            err,
+            failed_actors: Default::default(),
+            unreachable_nodes: Default::default(),
+        }
+    }
+}
+
+impl From<BarrierFailure> for RwError {
+    fn from(failure: BarrierFailure) -> Self {
+        failure.err
+    }
+}
+
 /// [`crate::barrier::GlobalBarrierManager`] sends barriers to all registered compute nodes and
 /// collect them, with monotonic increasing epoch numbers. On compute nodes, `LocalBarrierManager`
 /// in `risingwave_stream` crate will serve these requests and dispatch them to source actors.
@@ -140,6 +179,9 @@ pub struct GlobalBarrierManager<S: MetaStore> {
     /// The maximal interval for sending a barrier.
     interval: Duration,
 
+    /// Every `checkpoint_frequency` barriers, one is a checkpoint.
+    checkpoint_frequency: usize,
+
     /// Enable recovery or not when failover.
     enable_recovery: bool,
 
@@ -154,6 +196,16 @@ pub struct GlobalBarrierManager<S: MetaStore> {
 
     hummock_manager: HummockManagerRef<S>,
 
+    /// The progress of the materialized views being created.
+    create_mview_progress: RwLock<CreateMviewProgressTracker>,
+
+    /// The materialized views whose creations are aborted by the recovery, which are dropped and
+    /// reported as failed in the jobs listed for [`FAILED_JOB_TTL`], with the time they failed.
+    failed_jobs: RwLock<VecDeque<(Instant, Job)>>,
+
+    /// The estimated row counts of tables, loaded from meta store when the barrier manager starts.
+    table_row_counts: RwLock<TableRowCounts>,
+
     metrics: Arc<MetaMetrics>,
 
     env: MetaSrvEnv<S>,
@@ -172,19 +224,23 @@ where
         hummock_manager: HummockManagerRef<S>,
         metrics: Arc<MetaMetrics>,
     ) -> Self {
-        // TODO: make interval configurable.
         // TODO: when tracing is on, warn the developer on this short interval.
-        let interval = Duration::from_millis(100);
+        let interval = env.opts.barrier_interval;
+        let checkpoint_frequency = env.opts.checkpoint_frequency.max(1);
         let enable_recovery = env.opts.enable_recovery;
 
         Self {
             interval,
+            checkpoint_frequency,
             enable_recovery,
             cluster_manager,
             catalog_manager,
             fragment_manager,
             scheduled_barriers: ScheduledBarriers::new(),
             hummock_manager,
+            create_mview_progress: RwLock::new(CreateMviewProgressTracker::default()),
+            failed_jobs: RwLock::new(VecDeque::new()),
+            table_row_counts: RwLock::new(TableRowCounts::default()),
             metrics,
             env,
         }
@@ -207,6 +263,9 @@ where
         min_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
         let mut unfinished = UnfinishedNotifiers::default();
         let mut state = BarrierManagerState::create(self.env.meta_store()).await;
+        *self.table_row_counts.write().await = TableRowCounts::create(self.env.meta_store()).await;
+        // The number of barriers sent since the last checkpoint.
+        let mut uncheckpointed_barriers = 0;
 
         if self.enable_recovery {
             // handle init, here we simply trigger a recovery process to achieve the consistency. We
@@ -215,14 +274,15 @@ where
             assert!(new_epoch > state.prev_epoch);
             state.prev_epoch = new_epoch;
 
-            let (new_epoch, actors_to_finish, finished_create_mviews) =
-                self.recovery(state.prev_epoch, None).await;
+            self.clean_up_creating_tables().await;
+            let (new_epoch, actors_to_finish, responses) = self
+                .recovery(state.prev_epoch, None, &state.paused_sources)
+                .await;
             unfinished.add(new_epoch.into_inner(), actors_to_finish, vec![]);
-            for finished in finished_create_mviews {
-                unfinished.finish_actors(finished.epoch, once(finished.actor_id));
-            }
+            self.handle_responses(&mut unfinished, responses).await;
             state.prev_epoch = new_epoch.into_inner();
             state.update(self.env.meta_store()).await.unwrap();
+            self.update_table_row_counts().await;
         }
 
         loop {
@@ -240,15 +300,26 @@ where
             }
             // Get a barrier to send.
             let (command, notifiers) = self.scheduled_barriers.pop_or_default().await;
-            let info = self.resolve_actor_info(command.creating_table_id()).await;
+            let info = self.resolve_actor_info(command.changed_table_id()).await;
             let new_epoch = self.env.epoch_generator().generate().into_inner();
             assert!(new_epoch > state.prev_epoch);
+            // Checkpoint if it's required by the command, or someone is waiting for the barrier,
+            // e.g. `FLUSH` expects the written data to be readable afterwards.
+            uncheckpointed_barriers += 1;
+            let checkpoint = command.need_checkpoint()
+                || !notifiers.is_empty()
+                || uncheckpointed_barriers >= self.checkpoint_frequency;
+            if checkpoint {
+                uncheckpointed_barriers = 0;
+            }
             let command_ctx = CommandContext::new(
                 self.fragment_manager.clone(),
                 self.env.stream_clients_ref(),
                 &info,
                 state.prev_epoch,
                 new_epoch,
+                checkpoint,
+                state.paused_sources.clone(),
                 command.clone(),
             );
 
@@ -261,35 +332,101 @@ where
 
                     // Then try to finish the barrier for Create MVs.
                     let actors_to_finish = command_ctx.actors_to_finish();
+                    self.create_mview_progress
+                        .write()
+                        .await
+                        .add(new_epoch, &command);
                     unfinished.add(new_epoch, actors_to_finish, notifiers);
-                    for finished in responses.into_iter().flat_map(|r| r.finished_create_mviews) {
-                        unfinished.finish_actors(finished.epoch, once(finished.actor_id));
+                    self.handle_responses(&mut unfinished, responses).await;
+                    if let Command::DropMaterializedView(table_id) = &command {
+                        self.table_row_counts
+                            .write()
+                            .await
+                            .remove(table_id.table_id());
+                        // The materialized view may be dropped before its creation is finished.
+                        if let Some(epoch) =
+                            self.create_mview_progress.write().await.cancel(*table_id)
+                        {
+                            unfinished.cancel(epoch);
+                        }
                     }
 
                     state.prev_epoch = new_epoch;
+                    command.update_paused_sources(&mut state.paused_sources);
                 }
-                Err(e) => {
-                    notifiers
-                        .into_iter()
-                        .for_each(|notifier| notifier.notify_collection_failed(e.clone()));
-                    if self.enable_recovery {
+                Err(failure) => {
+                    notifiers.into_iter().for_each(|notifier| {
+                        notifier.notify_collection_failed(failure.err.clone())
+                    });
+                    if !self.enable_recovery {
+                        panic!("failed to execute barrier: {:?}", failure.err);
+                    }
+
+                    // Try to recover only the materialized views affected by the failure first.
+                    // The barrier is still collected by the actors unaffected, so the command
+                    // takes effect if it succeeds.
+                    let mut paused_sources = state.paused_sources.clone();
+                    command.update_paused_sources(&mut paused_sources);
+                    let partial = self
+                        .partial_recovery(&command_ctx, &command, &failure, &paused_sources)
+                        .await;
+                    if let Some((new_epoch, responses)) = partial {
+                        state.paused_sources = paused_sources;
+                        uncheckpointed_barriers = 0;
+                        self.handle_responses(&mut unfinished, responses).await;
+
+                        state.prev_epoch = new_epoch.into_inner();
+                    } else {
                         // If failed, enter recovery mode.
-                        let (new_epoch, actors_to_finish, finished_create_mviews) =
-                            self.recovery(state.prev_epoch, Some(command)).await;
+                        let (new_epoch, actors_to_finish, responses) = self
+                            .recovery(state.prev_epoch, Some(command), &state.paused_sources)
+                            .await;
                         unfinished = UnfinishedNotifiers::default();
+                        self.fail_jobs("aborted by recovery").await;
+                        self.create_mview_progress.write().await.clear();
+                        uncheckpointed_barriers = 0;
                         unfinished.add(new_epoch.into_inner(), actors_to_finish, vec![]);
-                        for finished in finished_create_mviews {
-                            unfinished.finish_actors(finished.epoch, once(finished.actor_id));
-                        }
+                        self.handle_responses(&mut unfinished, responses).await;
 
                         state.prev_epoch = new_epoch.into_inner();
-                    } else {
-                        panic!("failed to execute barrier: {:?}", e);
                     }
                 }
             }
 
             state.update(self.env.meta_store()).await.unwrap();
+            self.update_table_row_counts().await;
+        }
+    }
+
+    /// Handle the responses of the collected barrier: update the progress of the materialized views
+    /// being created and the row counts of tables, then finish the creations reported.
+    async fn handle_responses(
+        &self,
+        unfinished: &mut UnfinishedNotifiers,
+        responses: Vec<InjectBarrierResponse>,
+    ) {
+        let mut progress = self.create_mview_progress.write().await;
+        let mut row_counts = self.table_row_counts.write().await;
+        for response in responses {
+            row_counts.apply_deltas(response.table_row_count_deltas);
+            response
+                .create_mview_progress
+                .iter()
+                .for_each(|p| progress.update(p));
+            for finished in response.finished_create_mviews {
+                for epoch in unfinished.finish_actors(finished.epoch, once(finished.actor_id)) {
+                    progress.finish(epoch);
+                }
+            }
+        }
+    }
+
+    /// Persist the row counts of the tables changed. The failure is only logged, since the row
+    /// counts are estimations and will be persisted along with the next barrier.
+    async fn update_table_row_counts(&self) {
+        let mut row_counts = self.table_row_counts.write().await;
+        if let Err(err) = row_counts.update(self.env.meta_store()).await {
+            tracing::warn!("failed to persist table row counts: {}", err);
         }
     }
 
@@ -297,30 +434,22 @@ where
     async fn run_inner<'a>(
         &self,
         command_context: &CommandContext<'a, S>,
-    ) -> Result<Vec<InjectBarrierResponse>> {
+    ) -> std::result::Result<Vec<InjectBarrierResponse>, BarrierFailure> {
         let timer = self.metrics.barrier_latency.start_timer();
 
         // Wait for all barriers collected
-        let result = self.inject_barrier(command_context).await;
-        // Commit this epoch to Hummock
-        if command_context.prev_epoch != INVALID_EPOCH {
-            match result {
-                Ok(_) => {
-                    // We must ensure all epochs are committed in ascending order, because
-                    // the storage engine will query from new to old in the order in which
-                    // the L0 layer files are generated. see https://github.com/singularity-data/risingwave/issues/1251
-                    self.hummock_manager
-                        .commit_epoch(command_context.prev_epoch)
-                        .await?;
-                }
-                Err(_) => {
-                    self.hummock_manager
-                        .abort_epoch(command_context.prev_epoch)
-                        .await?;
-                }
-            };
+        let responses = self.inject_barrier(command_context).await?;
+        // Commit this epoch to Hummock. The epochs of the non-checkpoint barriers are committed
+        // along with the next checkpoint, as their states are synced together. If the collection
+        // failed, the epoch is committed or aborted by the recovery.
+        if command_context.prev_epoch != INVALID_EPOCH && command_context.checkpoint {
+            // We must ensure all epochs are committed in ascending order, because
+            // the storage engine will query from new to old in the order in which
+            // the L0 layer files are generated. see https://github.com/singularity-data/risingwave/issues/1251
+            self.hummock_manager
+                .commit_epoch(command_context.prev_epoch)
+                .await?;
         }
-        let responses = result?;
 
         timer.observe_duration();
         command_context.post_collect().await?; // do some post stuffs
@@ -332,7 +461,7 @@ where
     async fn inject_barrier<'a>(
         &self,
         command_context: &CommandContext<'a, S>,
-    ) -> Result<Vec<InjectBarrierResponse>> {
+    ) -> std::result::Result<Vec<InjectBarrierResponse>, BarrierFailure> {
         let mutation = command_context.to_mutation().await?;
         let info = command_context.info;
 
@@ -358,42 +487,97 @@ where
                 };
 
                 async move {
-                    let mut client = self.env.stream_clients().get(node).await?;
+                    let timer = self
+                        .metrics
+                        .barrier_collect_latency
+                        .with_label_values(&[&node_id.to_string()])
+                        .start_timer();
+                    let mut client = match self.env.stream_clients().get(node).await {
+                        Ok(client) => client,
+                        Err(err) => return (*node_id, Err(err)),
+                    };
 
                     let request = InjectBarrierRequest {
                         request_id,
                         barrier: Some(barrier),
                         actor_ids_to_send,
                         actor_ids_to_collect,
+                        checkpoint: command_context.checkpoint,
                     };
                     tracing::trace!(
                         target: "events::meta::barrier::inject_barrier",
                         "inject barrier request: {:?}", request
                     );
 
-                    // This RPC returns only if this worker node has collected this barrier.
-                    client
+                    // This RPC returns only if this worker node has collected this barrier, or
+                    // some actors on it failed.
+                    let response = client
                         .inject_barrier(request)
                         .await
                         .map(tonic::Response::<_>::into_inner)
-                        .to_rw_result()
+                        .to_rw_result();
+                    timer.observe_duration();
+                    (*node_id, response)
                 }
                 .into()
             }
         });
 
-        try_join_all(collect_futures).await
+        let mut collect_futures: FuturesUnordered<_> = collect_futures.collect();
+        let mut uncollected_nodes: HashSet<_> = info
+            .node_map
+            .keys()
+            .filter(|node_id| info.actor_ids_to_collect(node_id).next().is_some())
+            .cloned()
+            .collect();
+
+        let mut responses = vec![];
+        while let Some((node_id, response)) = collect_futures.next().await {
+            uncollected_nodes.remove(&node_id);
+            let mut failure = match response {
+                Ok(response) if response.failed_actor_ids.is_empty() => {
+                    responses.push(response);
+                    continue;
+                }
+                Ok(response) => {
+                    let mut failure =
+                        BarrierFailure::from(RwError::from(ErrorCode::InternalError(format!(
+                            "actors {:?} failed on node {}",
+                            response.failed_actor_ids, node_id
+                        ))));
+                    failure
+                        .failed_actors
+                        .insert(node_id, response.failed_actor_ids);
+                    failure
+                }
+                Err(err) => {
+                    let mut failure = BarrierFailure::from(err);
+                    failure.unreachable_nodes.insert(node_id);
+                    failure
+                }
+            };
+
+            // Stop waiting for the other nodes, since some actors on them may be waiting for the
+            // barrier from the failed ones. The barrier is collected from them again by the
+            // recovery.
+            for node_id in uncollected_nodes {
+                failure.failed_actors.insert(node_id, vec![]);
+            }
+            return Err(failure);
+        }
+
+        Ok(responses)
     }
 
     /// Resolve actor information from cluster and fragment manager.
-    async fn resolve_actor_info(&self, creating_table_id: Option<TableId>) -> BarrierActorInfo {
+    async fn resolve_actor_info(&self, changed_table_id: Option<TableId>) -> BarrierActorInfo {
         let all_nodes = self
             .cluster_manager
             .list_worker_node(WorkerType::ComputeNode, Some(Running))
             .await;
         let all_actor_infos = self
             .fragment_manager
-            .load_all_actors(creating_table_id)
+            .load_all_actors(changed_table_id)
             .await;
         BarrierActorInfo::resolve(all_nodes, all_actor_infos)
     }
@@ -435,6 +619,13 @@ where
 
     /// Run a command and return when it's completely finished.
     pub async fn run_command(&self, command: Command) -> Result<()> {
+        self.start_command(command).await?.await
+    }
+
+    /// Run a command and return when its barrier is collected, with a future completed when the
+    /// command is completely finished. The future fails if the command is not finished, e.g., the
+    /// materialized view being created is dropped, or the cluster is recovered.
+    pub async fn start_command(&self, command: Command) -> Result<BoxFuture<'static, Result<()>>> {
         let (collect_tx, collect_rx) = oneshot::channel();
         let (finish_tx, finish_rx) = oneshot::channel();
 
@@ -452,6 +643,15 @@ where
 
         collect_rx.await.unwrap()?; // Throw the error if it occurs when collecting this barrier.
 
+        // The notifier is dropped without notified if the command is not finished.
+        let finished = finish_rx.map(|result| {
+            result.map_err(|_| {
+                RwError::from(ErrorCode::InternalError(
+                    "command is cancelled or aborted by recovery".to_string(),
+                ))
+            })
+        });
+
         // TODO: refactor this
         if is_create_mv {
             // The snapshot ingestion may last for several epochs, we should pin the epoch here.
@@ -460,15 +660,95 @@ where
                 .hummock_manager
                 .pin_snapshot(META_NODE_ID, HummockEpoch::MAX)
                 .await?;
-            finish_rx.await.unwrap(); // Wait for this command to be finished.
-            self.hummock_manager
-                .unpin_snapshot(META_NODE_ID, [snapshot])
-                .await?;
+            let hummock_manager = self.hummock_manager.clone();
+            Ok(async move {
+                let result = finished.await; // Wait for this command to be finished.
+                hummock_manager
+                    .unpin_snapshot(META_NODE_ID, [snapshot])
+                    .await?;
+                result
+            }
+            .boxed())
         } else {
-            finish_rx.await.unwrap(); // Wait for this command to be finished.
+            Ok(finished.boxed())
         }
+    }
 
-        Ok(())
+    /// Whether the materialized view is being created, i.e., its `Chain` actors are not finished.
+    pub async fn is_creating(&self, table_id: TableId) -> bool {
+        self.create_mview_progress
+            .read()
+            .await
+            .contains_table(table_id)
+    }
+
+    /// Report the materialized views being created as failed, as their creations are aborted.
+    async fn fail_jobs(&self, failure: &str) {
+        let jobs = self.list_creating_jobs().await;
+        self.add_failed_jobs(jobs.into_iter().map(|job| Job {
+            failure: failure.to_string(),
+            ..job
+        }))
+        .await;
+    }
+
+    /// Record the failed jobs, and drop the ones failed before [`FAILED_JOB_TTL`] or with the same
+    /// names, since the materialized views may be created again.
+    async fn add_failed_jobs(&self, jobs: impl IntoIterator<Item = Job>) {
+        let now = Instant::now();
+        let mut failed_jobs = self.failed_jobs.write().await;
+        for job in jobs {
+            failed_jobs.retain(|(_, failed)| failed.name != job.name);
+            failed_jobs.push_back((now, job));
+        }
+        while let Some((failed_at, _)) = failed_jobs.front() {
+            if now.duration_since(*failed_at) < FAILED_JOB_TTL {
+                break;
+            }
+            failed_jobs.pop_front();
+        }
+    }
+
+    /// List the materialized views being created, with the progress of consuming the snapshots,
+    /// and the ones whose creations failed within [`FAILED_JOB_TTL`]. The failed ones are not
+    /// listed once materialized views with the same names are being created again.
+    pub async fn list_jobs(&self) -> Vec<Job> {
+        let mut jobs = self.list_creating_jobs().await;
+        let failed_jobs = self
+            .failed_jobs
+            .read()
+            .await
+            .iter()
+            .filter(|(failed_at, failed)| {
+                failed_at.elapsed() < FAILED_JOB_TTL
+                    && jobs.iter().all(|creating| creating.name != failed.name)
+            })
+            .map(|(_, failed)| failed.clone())
+            .collect_vec();
+        jobs.extend(failed_jobs);
+        jobs
+    }
+
+    async fn list_creating_jobs(&self) -> Vec<Job> {
+        let progress = {
+            let row_counts = self.table_row_counts.read().await;
+            self.create_mview_progress.read().await.list(&row_counts)
+        };
+
+        let catalog_guard = self.catalog_manager.get_catalog_core_guard().await;
+        progress
+            .into_iter()
+            .map(|p| Job {
+                table_id: p.table_id.table_id(),
+                name: catalog_guard
+                    .get_creating_table(p.table_id.table_id())
+                    .map(|table| table.name.clone())
+                    .unwrap_or_default(),
+                consumed_rows: p.consumed_rows,
+                estimated_rows: p.estimated_rows,
+                failure: String::new(),
+            })
+            .collect()
     }
 
     /// Wait for the next barrier to collect. Note that the barrier flowing in our stream graph is
@@ -14,6 +14,7 @@
 
 use std::collections::{HashMap, HashSet};
 
+use itertools::Itertools;
 use risingwave_common::error::{Result, RwError};
 use tokio::sync::oneshot;
 
@@ -99,9 +100,22 @@ impl UnfinishedNotifiers {
         }
     }
 
+    /// Cancel the command with `epoch`, e.g., the materialized view being created is dropped. Its
+    /// notifiers are dropped without notified.
+    pub fn cancel(&mut self, epoch: u64) {
+        if self.0.remove(&epoch).is_some() {
+            tracing::debug!("cancel DDL with epoch {}", epoch);
+        }
+    }
+
     /// Tell that the command with `epoch` has been reported to be finished on given `actors`. If
-    /// we've finished on all actors, [`Notifier::notify_finished`] will be called.
-    pub fn finish_actors(&mut self, epoch: u64, actors: impl IntoIterator<Item = ActorId>) {
+    /// we've finished on all actors, [`Notifier::notify_finished`] will be called. Returns the
+    /// epochs of the commands finished.
+    pub fn finish_actors(
+        &mut self,
+        epoch: u64,
+        actors: impl IntoIterator<Item = ActorId>,
+    ) -> Vec<u64> {
         use std::collections::hash_map::Entry;
 
         match self.0.entry(epoch) {
@@ -117,10 +131,39 @@ impl UnfinishedNotifiers {
 
                     let notifiers = o.remove().1;
                     notifiers.into_iter().for_each(Notifier::notify_finished);
+                    vec![epoch]
+                } else {
+                    vec![]
                 }
             }
 
-            Entry::Vacant(_) => todo!("handle finish report after meta recovery"),
+            // The DDL is not tracked with this epoch, which happens if the actors are recovered
+            // and report the epoch of the init barrier. Finish them for the DDLs still waiting.
+            Entry::Vacant(_) => {
+                let actors: HashSet<_> = actors.into_iter().collect();
+                tracing::debug!(
+                    "finish actors {:?} reported with untracked epoch {}",
+                    actors,
+                    epoch
+                );
+
+                for (actor_ids, _) in self.0.values_mut() {
+                    actor_ids.retain(|a| !actors.contains(a));
+                }
+                let finished_epochs = self
+                    .0
+                    .iter()
+                    .filter(|(_, (actor_ids, _))| actor_ids.is_empty())
+                    .map(|(&epoch, _)| epoch)
+                    .collect_vec();
+                for epoch in &finished_epochs {
+                    tracing::debug!("finish all actors for DDL with epoch {}!", epoch);
+
+                    let notifiers = self.0.remove(epoch).unwrap().1;
+                    notifiers.into_iter().for_each(Notifier::notify_finished);
+                }
+                finished_epochs
+            }
         }
     }
 }
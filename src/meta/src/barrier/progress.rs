@@ -0,0 +1,237 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+
+use risingwave_common::catalog::TableId;
+use risingwave_pb::stream_service::inject_barrier_response::CreateMviewProgress;
+
+use super::Command;
+use crate::model::{ActorId, TableRowCounts};
+
+/// The progress of a materialized view being created, i.e., how many rows its `Chain` actors have
+/// consumed from the snapshots of the upstream materialized views.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MviewProgress {
+    pub table_id: TableId,
+    pub consumed_rows: u64,
+    /// The total number of rows to consume, estimated by the row counts of the upstream tables.
+    pub estimated_rows: u64,
+}
+
+#[derive(Debug)]
+struct Progress {
+    table_id: TableId,
+
+    /// `Chain` actor => the upstream table it reads the snapshot from.
+    upstream_table_ids: HashMap<ActorId, TableId>,
+
+    /// `Chain` actor => the number of rows consumed from the snapshot.
+    consumed_rows: HashMap<ActorId, u64>,
+}
+
+/// Tracks the progress of the materialized views being created, which are added by their
+/// `CreateMaterializedView` commands and removed once all of their `Chain` actors are finished.
+#[derive(Debug, Default)]
+pub(super) struct CreateMviewProgressTracker {
+    /// The epoch of the DDL => the progress of the materialized view.
+    progress_map: HashMap<u64, Progress>,
+
+    /// `Chain` actor => the epoch of the DDL it belongs to. The progress is reported with the
+    /// actor id, since the recovered actors report the epoch of the init barrier instead.
+    actor_map: HashMap<ActorId, u64>,
+}
+
+impl CreateMviewProgressTracker {
+    /// Start tracking the materialized view created by `command` with `epoch`.
+    pub fn add(&mut self, epoch: u64, command: &Command) {
+        let (table_fragments, table_sink_map, dispatches) = match command {
+            Command::CreateMaterializedView {
+                table_fragments,
+                table_sink_map,
+                dispatches,
+            } => (table_fragments, table_sink_map, dispatches),
+            _ => return,
+        };
+
+        let upstream_actor_tables: HashMap<_, _> = table_sink_map
+            .iter()
+            .flat_map(|(&table_id, actor_ids)| {
+                actor_ids.iter().map(move |&actor_id| (actor_id, table_id))
+            })
+            .collect();
+        let upstream_table_ids: HashMap<_, _> = dispatches
+            .iter()
+            .filter_map(|(up_actor_id, down_actor_infos)| {
+                let table_id = *upstream_actor_tables.get(up_actor_id)?;
+                Some(
+                    down_actor_infos
+                        .iter()
+                        .map(move |info| (info.actor_id, table_id)),
+                )
+            })
+            .flatten()
+            .collect();
+        if upstream_table_ids.is_empty() {
+            return;
+        }
+
+        self.actor_map
+            .extend(upstream_table_ids.keys().map(|&actor_id| (actor_id, epoch)));
+        self.progress_map.insert(
+            epoch,
+            Progress {
+                table_id: table_fragments.table_id(),
+                upstream_table_ids,
+                consumed_rows: HashMap::new(),
+            },
+        );
+    }
+
+    /// Update the number of rows consumed by a `Chain` actor. The progress of the actors not
+    /// tracked is ignored.
+    pub fn update(&mut self, progress: &CreateMviewProgress) {
+        if let Some(epoch) = self.actor_map.get(&progress.actor_id)
+            && let Some(p) = self.progress_map.get_mut(epoch)
+        {
+            p.consumed_rows.insert(progress.actor_id, progress.consumed_rows);
+        }
+    }
+
+    /// Stop tracking the materialized view created with `epoch`, since it's finished.
+    pub fn finish(&mut self, epoch: u64) {
+        if let Some(progress) = self.progress_map.remove(&epoch) {
+            for actor_id in progress.upstream_table_ids.keys() {
+                self.actor_map.remove(actor_id);
+            }
+        }
+    }
+
+    /// Stop tracking the materialized view being dropped before finished. Returns the epoch of the
+    /// DDL if it's tracked.
+    pub fn cancel(&mut self, table_id: TableId) -> Option<u64> {
+        let epoch = self
+            .progress_map
+            .iter()
+            .find(|(_, p)| p.table_id == table_id)
+            .map(|(&epoch, _)| epoch)?;
+        self.finish(epoch);
+        Some(epoch)
+    }
+
+    pub fn contains_table(&self, table_id: TableId) -> bool {
+        self.progress_map.values().any(|p| p.table_id == table_id)
+    }
+
+    /// Stop tracking all materialized views, e.g., when the cluster is recovered.
+    pub fn clear(&mut self) {
+        self.progress_map.clear();
+        self.actor_map.clear();
+    }
+
+    /// List the progress of the materialized views being created, with the rows to consume
+    /// estimated by `row_counts`.
+    pub fn list(&self, row_counts: &TableRowCounts) -> Vec<MviewProgress> {
+        self.progress_map
+            .values()
+            .map(|p| {
+                let consumed_rows: u64 = p.consumed_rows.values().sum();
+                let estimated_rows: u64 = p
+                    .upstream_table_ids
+                    .values()
+                    .map(|table_id| row_counts.get(table_id.table_id()))
+                    .sum();
+                MviewProgress {
+                    table_id: p.table_id,
+                    consumed_rows,
+                    estimated_rows: estimated_rows.max(consumed_rows),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::common::ActorInfo;
+
+    use super::*;
+    use crate::model::TableFragments;
+
+    #[test]
+    fn test_create_mview_progress_tracker() {
+        let upstream_table_id = TableId::new(1);
+        let table_id = TableId::new(2);
+        let command = Command::CreateMaterializedView {
+            table_fragments: TableFragments::new(table_id, Default::default()),
+            table_sink_map: HashMap::from([(upstream_table_id, vec![1, 2])]),
+            dispatches: HashMap::from([
+                (
+                    1,
+                    vec![ActorInfo {
+                        actor_id: 3,
+                        ..Default::default()
+                    }],
+                ),
+                (
+                    2,
+                    vec![ActorInfo {
+                        actor_id: 4,
+                        ..Default::default()
+                    }],
+                ),
+            ]),
+        };
+
+        let mut row_counts = TableRowCounts::default();
+        row_counts.apply_deltas([(upstream_table_id.table_id(), 100)]);
+
+        let mut tracker = CreateMviewProgressTracker::default();
+        tracker.add(10, &command);
+        tracker.update(&CreateMviewProgress {
+            epoch: 10,
+            actor_id: 3,
+            consumed_rows: 30,
+        });
+        // Recovered actors report the epoch of the init barrier.
+        tracker.update(&CreateMviewProgress {
+            epoch: 20,
+            actor_id: 4,
+            consumed_rows: 40,
+        });
+        // Untracked actors are ignored.
+        tracker.update(&CreateMviewProgress {
+            epoch: 10,
+            actor_id: 5,
+            consumed_rows: 50,
+        });
+        assert_eq!(
+            tracker.list(&row_counts),
+            vec![MviewProgress {
+                table_id,
+                consumed_rows: 70,
+                estimated_rows: 200,
+            }]
+        );
+
+        tracker.finish(10);
+        assert!(tracker.list(&row_counts).is_empty());
+        assert!(tracker.actor_map.is_empty());
+
+        tracker.add(30, &command);
+        assert!(tracker.contains_table(table_id));
+        assert_eq!(tracker.cancel(upstream_table_id), None);
+        assert_eq!(tracker.cancel(table_id), Some(30));
+        assert!(!tracker.contains_table(table_id));
+    }
+}
@@ -12,36 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
-use std::iter::Map;
+use std::collections::{HashMap, HashSet};
+use std::iter::{once, Map};
 use std::time::Duration;
 
 use futures::future::try_join_all;
+use itertools::Itertools;
 use log::{debug, error};
+use risingwave_common::catalog::TableId;
 use risingwave_common::error::{ErrorCode, Result, RwError, ToRwResult};
 use risingwave_pb::common::ActorInfo;
-use risingwave_pb::data::Epoch as ProstEpoch;
-use risingwave_pb::stream_service::inject_barrier_response::FinishedCreateMview;
+use risingwave_pb::data::{Barrier, Epoch as ProstEpoch};
+use risingwave_pb::meta::list_jobs_response::Job;
+use risingwave_pb::stream_plan::StreamActor;
 use risingwave_pb::stream_service::{
-    BroadcastActorInfoTableRequest, BuildActorsRequest, ForceStopActorsRequest, SyncSourcesRequest,
-    UpdateActorsRequest,
+    BroadcastActorInfoTableRequest, BuildActorsRequest, DropActorsRequest, ForceStopActorsRequest,
+    InjectBarrierRequest, InjectBarrierResponse, SyncSourcesRequest, UpdateActorsRequest,
 };
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use uuid::Uuid;
 
 use crate::barrier::command::CommandContext;
 use crate::barrier::info::BarrierActorInfo;
-use crate::barrier::{Command, GlobalBarrierManager};
-use crate::manager::Epoch;
+use crate::barrier::{BarrierFailure, Command, GlobalBarrierManager};
+use crate::cluster::WorkerId;
+use crate::manager::{Epoch, SourceId, INVALID_EPOCH};
 use crate::model::ActorId;
 use crate::storage::MetaStore;
 
-pub type RecoveryResult = (Epoch, HashSet<ActorId>, Vec<FinishedCreateMview>);
+pub type RecoveryResult = (Epoch, HashSet<ActorId>, Vec<InjectBarrierResponse>);
 
 impl<S> GlobalBarrierManager<S>
 where
     S: MetaStore,
 {
+    // Retry times of waiting for the unreachable nodes in partial recovery.
+    const PARTIAL_RECOVERY_RETRY_TIMES: usize = 10;
     // Retry base interval in milliseconds.
     const RECOVERY_RETRY_BASE_INTERVAL: u64 = 100;
     // Retry max interval.
@@ -55,15 +61,32 @@ where
             .map(jitter)
     }
 
-    /// Recovery the whole cluster from the latest epoch.
+    /// The command of the init barrier after recovery, which pauses the rebuilt actors of the
+    /// paused sources.
+    fn init_command(paused_sources: &HashSet<SourceId>) -> Command {
+        if paused_sources.is_empty() {
+            Command::checkpoint()
+        } else {
+            Command::PauseSources(paused_sources.clone())
+        }
+    }
+
+    /// Recovery the whole cluster from the latest epoch. The actors of `paused_sources` are paused
+    /// again by the init barrier.
     pub(crate) async fn recovery(
         &self,
         prev_epoch: u64,
         prev_command: Option<Command>,
+        paused_sources: &HashSet<SourceId>,
     ) -> RecoveryResult {
         // Abort buffered schedules, they might be dirty already.
         self.scheduled_barriers.abort().await;
 
+        // Abort the epoch of the failed barrier, whose states may have been synced partially.
+        if let Err(err) = self.hummock_manager.abort_epoch(prev_epoch).await {
+            error!("abort_epoch failed: {}", err);
+        }
+
         // clean up the previous command dirty data.
         if let Some(prev_command) = prev_command {
             self.clean_up(prev_command).await;
@@ -86,7 +109,8 @@ where
             }
 
             // update and build all actors.
-            if let Err(err) = self.update_actors(&info).await {
+            let node_actors = self.fragment_manager.all_node_actors(false).await;
+            if let Err(err) = self.update_actors(&info, node_actors).await {
                 error!("update_actors failed: {}", err);
                 return Err(err);
             }
@@ -97,14 +121,17 @@ where
 
             let prev_epoch = new_epoch.into_inner();
             new_epoch = self.env.epoch_generator().generate();
-            // checkpoint, used as init barrier to initialize all executors.
+            // checkpoint, used as init barrier to initialize all executors. The rebuilt sources
+            // start running, so pause the ones that were paused before the recovery.
             let command_ctx = CommandContext::new(
                 self.fragment_manager.clone(),
                 self.env.stream_clients_ref(),
                 &info,
                 prev_epoch,
                 new_epoch.into_inner(),
-                Command::checkpoint(),
+                true,
+                paused_sources.clone(),
+                Self::init_command(paused_sources),
             );
 
             match self.inject_barrier(&command_ctx).await {
@@ -115,9 +142,9 @@ where
                     }
                     Ok((new_epoch, response))
                 }
-                Err(err) => {
-                    error!("inject_barrier failed: {}", err);
-                    Err(err)
+                Err(failure) => {
+                    error!("inject_barrier failed: {}", failure.err);
+                    Err(failure.into())
                 }
             }
         })
@@ -128,19 +155,276 @@ where
         return (
             new_epoch,
             self.fragment_manager.all_chain_actor_ids().await,
-            responses
-                .into_iter()
-                .flat_map(|r| r.finished_create_mviews)
-                .collect(),
+            responses,
         );
     }
 
+    /// Recover from the `failure` of the barrier in `command_ctx` by rebuilding only the affected
+    /// materialized views, i.e. the ones with failed actors or with actors on unreachable nodes,
+    /// and the ones connected with them. The other materialized views keep running, and the failed
+    /// barrier is collected from them again. The actors of `paused_sources` rebuilt are paused
+    /// again by the init barrier.
+    ///
+    /// Returns `None` if the failure cannot be recovered partially, in which case the whole cluster
+    /// should be recovered by [`Self::recovery`].
+    pub(super) async fn partial_recovery(
+        &self,
+        command_ctx: &CommandContext<'_, S>,
+        command: &Command,
+        failure: &BarrierFailure,
+        paused_sources: &HashSet<SourceId>,
+    ) -> Option<(Epoch, Vec<InjectBarrierResponse>)> {
+        // The dirty data of the commands changing the stream graph is cleaned up by the recovery of
+        // the whole cluster.
+        if command.need_checkpoint() {
+            return None;
+        }
+
+        let mut failed_actors: HashSet<_> =
+            failure.failed_actors.values().flatten().cloned().collect();
+        for node_id in &failure.unreachable_nodes {
+            failed_actors.extend(command_ctx.info.actor_ids_to_collect(node_id));
+        }
+        if failed_actors.is_empty() {
+            return None;
+        }
+
+        let table_ids = self
+            .fragment_manager
+            .connected_table_ids(&failed_actors)
+            .await;
+        let table_count = self
+            .fragment_manager
+            .list_table_fragments()
+            .await
+            .ok()?
+            .len();
+        if table_ids.len() == table_count {
+            return None;
+        }
+
+        // The unsynced states of the affected tables are discarded before the failed barrier is
+        // collected again. However, a checkpoint barrier collected on some nodes has synced their
+        // states of all uncommitted epochs, which would be committed along with the epoch of the
+        // failed barrier. The unreachable nodes may have synced as well before the failure.
+        if command_ctx.checkpoint {
+            if !failure.unreachable_nodes.is_empty() {
+                return None;
+            }
+            let affected_actors = self
+                .fragment_manager
+                .load_actors_of_tables(&table_ids)
+                .await;
+            let synced = affected_actors.actor_maps.iter().any(|(node_id, actors)| {
+                !actors.is_empty() && !failure.failed_actors.contains_key(node_id)
+            });
+            if synced {
+                return None;
+            }
+        }
+
+        debug!("partial recovery start! tables to recover: {:?}", table_ids);
+        match self
+            .partial_recovery_inner(
+                command_ctx,
+                failure,
+                &failed_actors,
+                &table_ids,
+                paused_sources,
+            )
+            .await
+        {
+            Ok(result) => {
+                debug!("partial recovery success");
+                Some(result)
+            }
+            Err(err) => {
+                error!("partial recovery failed: {}", err);
+                // The epoch of the failed barrier may have been synced by the init barrier.
+                if let Err(err) = self
+                    .hummock_manager
+                    .abort_epoch(command_ctx.curr_epoch)
+                    .await
+                {
+                    error!("abort_epoch failed: {}", err);
+                }
+                None
+            }
+        }
+    }
+
+    async fn partial_recovery_inner(
+        &self,
+        command_ctx: &CommandContext<'_, S>,
+        failure: &BarrierFailure,
+        failed_actors: &HashSet<ActorId>,
+        table_ids: &HashSet<TableId>,
+        paused_sources: &HashSet<SourceId>,
+    ) -> Result<(Epoch, Vec<InjectBarrierResponse>)> {
+        // Wait for the unreachable nodes to be deleted, and their actors to be moved to the other
+        // nodes by the rescheduler.
+        let retry_strategy = Self::get_retry_strategy().take(Self::PARTIAL_RECOVERY_RETRY_TIMES);
+        let info = tokio_retry::Retry::spawn(retry_strategy, || async {
+            let info = self.resolve_actor_info(None).await;
+            let evicted = failure
+                .unreachable_nodes
+                .iter()
+                .all(|node_id| !info.node_map.contains_key(node_id))
+                && info
+                    .actor_map
+                    .keys()
+                    .all(|node_id| info.node_map.contains_key(node_id));
+            if evicted {
+                Ok(info)
+            } else {
+                Err(RwError::from(ErrorCode::InternalError(
+                    "unreachable worker not evicted yet".to_string(),
+                )))
+            }
+        })
+        .await?;
+
+        // Drop the actors to rebuild on all nodes. The failed actors are dropped as well, since
+        // they may have been moved away from the nodes they failed on. Their writes not synced yet
+        // are discarded, so that only the states of the unaffected tables are committed below.
+        let actors_to_rebuild = self.fragment_manager.load_actors_of_tables(table_ids).await;
+        let actors_to_drop: HashSet<_> = actors_to_rebuild
+            .actor_maps
+            .values()
+            .flatten()
+            .chain(failed_actors)
+            .cloned()
+            .collect();
+        self.drop_actors(&info, &actors_to_drop).await?;
+
+        // The unaffected actors can collect the failed barrier now, then commit its previous epoch.
+        let mut responses = self
+            .recollect_barrier(command_ctx, failure, &actors_to_drop)
+            .await?;
+        if command_ctx.prev_epoch != INVALID_EPOCH && command_ctx.checkpoint {
+            self.hummock_manager
+                .commit_epoch(command_ctx.prev_epoch)
+                .await?;
+        }
+
+        // Rebuild the actors of the affected tables.
+        let rebuild_info =
+            BarrierActorInfo::resolve(info.node_map.values().cloned(), actors_to_rebuild);
+        let node_actors = self.fragment_manager.node_actors_of_tables(table_ids).await;
+        self.update_actors(&rebuild_info, node_actors).await?;
+        self.build_actors(&rebuild_info).await?;
+
+        // checkpoint, used as init barrier to initialize the executors rebuilt.
+        let new_epoch = self.env.epoch_generator().generate();
+        let init_command_ctx = CommandContext::new(
+            self.fragment_manager.clone(),
+            self.env.stream_clients_ref(),
+            &info,
+            command_ctx.curr_epoch,
+            new_epoch.into_inner(),
+            true,
+            paused_sources.clone(),
+            Self::init_command(paused_sources),
+        );
+        responses.extend(self.inject_barrier(&init_command_ctx).await?);
+        self.hummock_manager
+            .commit_epoch(init_command_ctx.prev_epoch)
+            .await?;
+        init_command_ctx.post_collect().await?;
+
+        Ok((new_epoch, responses))
+    }
+
+    /// Drop `actors` on all compute nodes, which skip the actors not existing, and discard their
+    /// states not synced yet.
+    async fn drop_actors(&self, info: &BarrierActorInfo, actors: &HashSet<ActorId>) -> Result<()> {
+        let actor_ids = actors.iter().cloned().collect_vec();
+        let futures = info.node_map.values().map(|node| {
+            let request = DropActorsRequest {
+                request_id: Uuid::new_v4().to_string(),
+                actor_ids: actor_ids.clone(),
+                discard_unsynced_states: true,
+            };
+            async move {
+                let mut client = self.env.stream_clients().get(node).await?;
+                debug!("drop actors on node {}: {:?}", node.id, request.actor_ids);
+                client.drop_actors(request).await.to_rw_result()?;
+
+                Ok::<_, RwError>(())
+            }
+        });
+
+        try_join_all(futures).await?;
+        Ok(())
+    }
+
+    /// Collect the barrier in `command_ctx` again on the nodes where some actors failed, from the
+    /// actors not dropped. The states on these nodes are synced after the collection.
+    async fn recollect_barrier(
+        &self,
+        command_ctx: &CommandContext<'_, S>,
+        failure: &BarrierFailure,
+        dropped_actors: &HashSet<ActorId>,
+    ) -> Result<Vec<InjectBarrierResponse>> {
+        let mutation = command_ctx.to_mutation().await?;
+        let info = command_ctx.info;
+
+        let futures = failure.failed_actors.keys().filter_map(|node_id| {
+            let node = info.node_map.get(node_id)?;
+            let actor_ids_to_collect = info
+                .actor_ids_to_collect(node_id)
+                .filter(|actor_id| !dropped_actors.contains(actor_id))
+                .collect_vec();
+            if actor_ids_to_collect.is_empty() {
+                // The states on this node are synced along with the next checkpoint.
+                return None;
+            }
+
+            let request = InjectBarrierRequest {
+                request_id: Uuid::new_v4().to_string(),
+                barrier: Some(Barrier {
+                    epoch: Some(ProstEpoch {
+                        curr: command_ctx.curr_epoch,
+                        prev: command_ctx.prev_epoch,
+                    }),
+                    mutation: Some(mutation.clone()),
+                    span: vec![],
+                }),
+                actor_ids_to_send: vec![],
+                actor_ids_to_collect,
+                checkpoint: command_ctx.checkpoint,
+            };
+            Some(async move {
+                let mut client = self.env.stream_clients().get(node).await?;
+                let response = client
+                    .inject_barrier(request)
+                    .await
+                    .to_rw_result()?
+                    .into_inner();
+                if !response.failed_actor_ids.is_empty() {
+                    return Err(RwError::from(ErrorCode::InternalError(format!(
+                        "actors {:?} failed on node {}",
+                        response.failed_actor_ids, node.id
+                    ))));
+                }
+
+                Ok::<_, RwError>(response)
+            })
+        });
+
+        try_join_all(futures).await
+    }
+
     /// Clean up previous command dirty data. Currently, we only need to handle table fragments info
-    /// for `CreateMaterializedView`. For `DropMaterializedView`, since we already response fail to
-    /// frontend and the actors will be rebuild by follow recovery process, it's okay to retain
-    /// it.
+    /// for `CreateMaterializedView` and the added actors for `RescheduleFragment`. For
+    /// `DropMaterializedView`, since we already response fail to frontend and the actors will be
+    /// rebuild by follow recovery process, it's okay to retain it.
     async fn clean_up(&self, prev_command: Command) {
         if let Some(table_id) = prev_command.creating_table_id() {
+            self.table_row_counts
+                .write()
+                .await
+                .remove(table_id.table_id());
             let retry_strategy = Self::get_retry_strategy();
             tokio_retry::Retry::spawn(retry_strategy, || async {
                 self.fragment_manager.drop_table_fragments(&table_id).await
@@ -148,6 +432,49 @@ where
             .await
             .expect("Retry clean up until success");
         }
+
+        if let Command::RescheduleFragment(reschedule) = prev_command {
+            let added_actors = reschedule.added_actor_ids();
+            let retry_strategy = Self::get_retry_strategy();
+            tokio_retry::Retry::spawn(retry_strategy, || async {
+                self.fragment_manager
+                    .drop_inactive_actors(&reschedule.table_id, &added_actors)
+                    .await
+            })
+            .await
+            .expect("Retry clean up until success");
+        }
+    }
+
+    /// Drop the materialized views left in `Creating` status by the previous meta node, since their
+    /// creations in background are not waited for anymore. Their actors are not rebuilt by the
+    /// following recovery, and they're reported as failed in the jobs listed.
+    pub(super) async fn clean_up_creating_tables(&self) {
+        let tables = match self.catalog_manager.list_creating_tables().await {
+            Ok(tables) => tables,
+            Err(err) => {
+                error!("list_creating_tables failed: {}", err);
+                return;
+            }
+        };
+
+        for table in tables {
+            debug!("drop materialized view {} left in creating", table.id);
+            let table_id = TableId::new(table.id);
+            if let Err(err) = self.fragment_manager.drop_table_fragments(&table_id).await {
+                error!("drop_table_fragments failed: {}", err);
+            }
+            if let Err(err) = self.catalog_manager.drop_table(table.id).await {
+                error!("drop_table failed: {}", err);
+            }
+            self.add_failed_jobs(once(Job {
+                table_id: table.id,
+                name: table.name,
+                failure: "aborted by the restart of meta".to_string(),
+                ..Default::default()
+            }))
+            .await;
+        }
     }
 
     /// Sync all sources in compute nodes, the local source manager in compute nodes may be dirty
@@ -178,8 +505,12 @@ where
         Ok(())
     }
 
-    /// Update all actors in compute nodes.
-    async fn update_actors(&self, info: &BarrierActorInfo) -> Result<()> {
+    /// Update the actors in `info` on compute nodes.
+    async fn update_actors(
+        &self,
+        info: &BarrierActorInfo,
+        node_actors: HashMap<WorkerId, Vec<StreamActor>>,
+    ) -> Result<()> {
         let mut actor_infos = vec![];
         for (node_id, actors) in &info.actor_map {
             let host = info
@@ -198,7 +529,6 @@ where
             }));
         }
 
-        let node_actors = self.fragment_manager.all_node_actors(false).await;
         for (node_id, actors) in &info.actor_map {
             let node = info.node_map.get(node_id).unwrap();
             let client = self.env.stream_clients().get(node).await?;
@@ -227,7 +557,7 @@ where
         Ok(())
     }
 
-    /// Build all actors in compute nodes.
+    /// Build the actors in `info` on compute nodes.
     async fn build_actors(&self, info: &BarrierActorInfo) -> Result<()> {
         for (node_id, actors) in &info.actor_map {
             let node = info.node_map.get(node_id).unwrap();
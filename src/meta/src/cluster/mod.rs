@@ -183,11 +183,17 @@ where
 
                 core.update_worker_node(worker.clone());
 
-                // Notify frontends of new compute node.
+                // Notify frontends and local subscribers of new compute node.
                 if worker.worker_node.r#type == WorkerType::ComputeNode as i32 {
                     self.env
                         .notification_manager()
-                        .notify_frontend(Operation::Add, &Info::Node(worker.worker_node))
+                        .notify_frontend(Operation::Add, &Info::Node(worker.worker_node.clone()))
+                        .await;
+                    self.env
+                        .notification_manager()
+                        .notify_local_subscribers(LocalNotification::WorkerActivation(
+                            worker.worker_node,
+                        ))
                         .await;
                 }
 
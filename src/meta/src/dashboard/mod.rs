@@ -28,6 +28,7 @@ use tower_http::add_extension::AddExtensionLayer;
 use tower_http::cors::{self, CorsLayer};
 use tower_http::services::ServeDir;
 
+use crate::barrier::BarrierManagerRef;
 use crate::cluster::ClusterManagerRef;
 use crate::storage::MetaStore;
 use crate::stream::FragmentManagerRef;
@@ -37,6 +38,7 @@ pub struct DashboardService<S: MetaStore> {
     pub dashboard_addr: SocketAddr,
     pub cluster_manager: ClusterManagerRef<S>,
     pub fragment_manager: FragmentManagerRef<S>,
+    pub barrier_manager: BarrierManagerRef<S>,
 
     // TODO: replace with catalog manager.
     pub meta_store: Arc<S>,
@@ -49,6 +51,7 @@ mod handlers {
     use axum::Json;
     use risingwave_pb::catalog::Table;
     use risingwave_pb::common::WorkerNode;
+    use risingwave_pb::meta::list_jobs_response::Job;
     use risingwave_pb::meta::ActorLocation;
     use risingwave_pb::stream_plan::StreamActor;
     use serde_json::json;
@@ -139,6 +142,13 @@ mod handlers {
 
         Ok(Json(table_fragments))
     }
+
+    pub async fn list_jobs<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<Vec<Job>>> {
+        let jobs = srv.barrier_manager.list_jobs().await;
+        Ok(Json(jobs))
+    }
 }
 
 impl<S> DashboardService<S>
@@ -154,6 +164,7 @@ where
             .route("/actors", get(list_actors::<S>))
             .route("/fragments", get(list_table_fragments::<S>))
             .route("/materialized_views", get(list_materialized_views::<S>))
+            .route("/jobs", get(list_jobs::<S>))
             .layer(
                 ServiceBuilder::new()
                     .layer(AddExtensionLayer::new(srv.clone()))
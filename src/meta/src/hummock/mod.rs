@@ -94,6 +94,7 @@ where
                             }).await.expect("Should retry until release_contexts succeeds");
                             compactor_manager.remove_compactor(worker_node.id);
                         }
+                        Some(LocalNotification::WorkerActivation(_)) => {}
                     }
                 }
                 _ = shutdown_rx.recv() => {
@@ -86,6 +86,20 @@ pub struct MetaNodeOpts {
     /// e2e tests.
     #[clap(long)]
     disable_recovery: bool,
+
+    /// The maximal interval in ms for sending a barrier.
+    #[clap(long, default_value = "100")]
+    barrier_interval_ms: u32,
+
+    /// Only every N-th barrier is a checkpoint, which syncs the states to the storage backend and
+    /// commits the epoch. The states of other barriers stay in the shared buffer until then.
+    #[clap(long, default_value = "1")]
+    checkpoint_frequency: usize,
+
+    /// The interval in ms of listing the splits of the stream sources, so that the new splits,
+    /// e.g., Kafka partitions, are assigned to the source actors.
+    #[clap(long, default_value = "10000")]
+    split_discovery_interval_ms: u64,
 }
 
 /// Start meta node
@@ -115,6 +129,9 @@ pub async fn start(opts: MetaNodeOpts) {
         opts.dashboard_ui_path,
         MetaOpts {
             enable_recovery: !opts.disable_recovery,
+            barrier_interval: Duration::from_millis(opts.barrier_interval_ms as u64),
+            checkpoint_frequency: opts.checkpoint_frequency,
+            split_discovery_interval: Duration::from_millis(opts.split_discovery_interval_ms),
         },
     )
     .await
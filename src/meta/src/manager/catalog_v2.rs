@@ -23,7 +23,8 @@ use risingwave_common::catalog::{CatalogVersion, DEFAULT_DATABASE_NAME, DEFAULT_
 use risingwave_common::ensure;
 use risingwave_common::error::ErrorCode::{CatalogError, InternalError};
 use risingwave_common::error::{Result, RwError};
-use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+use risingwave_pb::catalog::source::Info as SourceInfo;
+use risingwave_pb::catalog::table::{OptionalAssociatedSourceId, StreamJobStatus};
 use risingwave_pb::catalog::{Database, Schema, Source, Table};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use tokio::sync::{Mutex, MutexGuard};
@@ -200,6 +201,7 @@ where
         let key = (table.database_id, table.schema_id, table.name.clone());
         if !core.has_table(table) && !core.has_in_progress_creation(&key) {
             core.mark_creating(&key);
+            core.creating_tables.insert(table.id, table.clone());
             for &dependent_relation_id in &table.dependent_relations {
                 core.increase_ref_count(dependent_relation_id);
             }
@@ -216,6 +218,7 @@ where
         let key = (table.database_id, table.schema_id, table.name.clone());
         if !core.has_table(table) && core.has_in_progress_creation(&key) {
             core.unmark_creating(&key);
+            core.creating_tables.remove(&table.id);
             table.insert(self.env.meta_store()).await?;
             core.add_table(table);
 
@@ -239,6 +242,7 @@ where
         let key = (table.database_id, table.schema_id, table.name.clone());
         if !core.has_table(table) && core.has_in_progress_creation(&key) {
             core.unmark_creating(&key);
+            core.creating_tables.remove(&table.id);
             for &dependent_relation_id in &table.dependent_relations {
                 core.decrease_ref_count(dependent_relation_id);
             }
@@ -250,6 +254,66 @@ where
         }
     }
 
+    /// Add the table in creating procedure to the catalog with `Creating` status, when it's created
+    /// in background. It's finished by [`Self::finish_create_table_in_background`], or dropped by
+    /// [`Self::drop_table`] if the creation is cancelled.
+    pub async fn publish_creating_table(&self, table: &Table) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        let key = (table.database_id, table.schema_id, table.name.clone());
+        if !core.has_table(table) && core.has_in_progress_creation(&key) {
+            core.unmark_creating(&key);
+            let mut table = table.clone();
+            table.set_stream_job_status(StreamJobStatus::Creating);
+            table.insert(self.env.meta_store()).await?;
+            core.add_table(&table);
+
+            let version = self
+                .env
+                .notification_manager()
+                .notify_frontend(Operation::Add, &Info::TableV2(table))
+                .await
+                .into_inner();
+
+            Ok(version)
+        } else {
+            Err(RwError::from(InternalError(
+                "table already exist or not in creating procedure".to_string(),
+            )))
+        }
+    }
+
+    pub async fn finish_create_table_in_background(
+        &self,
+        table_id: TableId,
+    ) -> Result<CatalogVersion> {
+        let mut core = self.core.lock().await;
+        let mut table = Table::select(self.env.meta_store(), &table_id)
+            .await?
+            .ok_or_else(|| RwError::from(InternalError("table doesn't exist".to_string())))?;
+        core.creating_tables.remove(&table_id);
+        table.set_stream_job_status(StreamJobStatus::Created);
+        table.insert(self.env.meta_store()).await?;
+
+        let version = self
+            .env
+            .notification_manager()
+            .notify_frontend(Operation::Update, &Info::TableV2(table))
+            .await
+            .into_inner();
+
+        Ok(version)
+    }
+
+    /// List the tables in `Creating` status.
+    pub async fn list_creating_tables(&self) -> Result<Vec<Table>> {
+        let _core = self.core.lock().await;
+        let tables = Table::list(self.env.meta_store()).await?;
+        Ok(tables
+            .into_iter()
+            .filter(|table| table.stream_job_status() == StreamJobStatus::Creating)
+            .collect())
+    }
+
     pub async fn create_table(&self, table: &Table) -> Result<CatalogVersion> {
         let mut core = self.core.lock().await;
         if !core.has_table(table) {
@@ -291,6 +355,7 @@ where
                 None => {
                     Table::delete(self.env.meta_store(), &table_id).await?;
                     core.drop_table(&table);
+                    core.creating_tables.remove(&table_id);
                     for &dependent_relation_id in &table.dependent_relations {
                         core.decrease_ref_count(dependent_relation_id);
                     }
@@ -417,6 +482,38 @@ where
         }
     }
 
+    /// Updates the rate limit recorded in the catalog of a stream source, so that the frontend
+    /// picks it up for materialized views created later on. `0` removes the limit.
+    pub async fn alter_source_rate_limit(
+        &self,
+        source_id: SourceId,
+        rate_limit: u32,
+    ) -> Result<CatalogVersion> {
+        let _core = self.core.lock().await;
+        let mut source = Source::select(self.env.meta_store(), &source_id)
+            .await?
+            .ok_or_else(|| RwError::from(InternalError("source doesn't exist".to_string())))?;
+        match source.info.as_mut() {
+            Some(SourceInfo::StreamSource(info)) => info.rate_limit = rate_limit,
+            _ => {
+                return Err(RwError::from(InternalError(format!(
+                    "source `{}` is not a stream source",
+                    source.name
+                ))))
+            }
+        }
+        source.insert(self.env.meta_store()).await?;
+
+        let version = self
+            .env
+            .notification_manager()
+            .notify_frontend(Operation::Update, &Info::Source(source))
+            .await
+            .into_inner();
+
+        Ok(version)
+    }
+
     pub async fn start_create_materialized_source_procedure(
         &self,
         source: &Source,
@@ -608,6 +705,8 @@ pub struct CatalogManagerCore<S: MetaStore> {
 
     // In-progress creation tracker
     in_progress_creation_tracker: HashSet<RelationKey>,
+    /// The tables in creating procedure, by table id.
+    creating_tables: HashMap<TableId, Table>,
 }
 
 impl<S> CatalogManagerCore<S>
@@ -650,6 +749,7 @@ where
             tables,
             relation_ref_count,
             in_progress_creation_tracker,
+            creating_tables: HashMap::new(),
         })
     }
 
@@ -666,6 +766,11 @@ where
         Source::list(self.env.meta_store()).await
     }
 
+    /// Get the table in creating procedure with the given id.
+    pub fn get_creating_table(&self, id: TableId) -> Option<&Table> {
+        self.creating_tables.get(&id)
+    }
+
     fn has_database(&self, database: &Database) -> bool {
         self.databases.contains(database.get_name())
     }
@@ -14,6 +14,7 @@
 
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::{StreamClients, StreamClientsRef};
 #[cfg(any(test, feature = "test"))]
@@ -53,9 +54,30 @@ where
 }
 
 /// Options shared by all meta service instances
-#[derive(Default)]
 pub struct MetaOpts {
     pub enable_recovery: bool,
+
+    /// The maximal interval for sending a barrier.
+    pub barrier_interval: Duration,
+
+    /// Every `checkpoint_frequency` barriers, one is a checkpoint that syncs the state store and
+    /// commits the epoch to Hummock.
+    pub checkpoint_frequency: usize,
+
+    /// The interval of listing the splits of the stream sources, to reassign the splits once they
+    /// are changed.
+    pub split_discovery_interval: Duration,
+}
+
+impl Default for MetaOpts {
+    fn default() -> Self {
+        Self {
+            enable_recovery: false,
+            barrier_interval: Duration::from_millis(100),
+            checkpoint_frequency: 1,
+            split_discovery_interval: Duration::from_secs(10),
+        }
+    }
 }
 
 impl<S> MetaSrvEnv<S>
@@ -32,6 +32,7 @@ pub type Notification = std::result::Result<SubscribeResponse, Status>;
 #[derive(Clone)]
 pub enum LocalNotification {
     WorkerDeletion(WorkerNode),
+    WorkerActivation(WorkerNode),
 }
 
 /// Interval before retry when notify fail.
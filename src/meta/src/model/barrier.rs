@@ -12,16 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashMap, HashSet};
+
 use risingwave_common::error::Result;
 
-use crate::manager::INVALID_EPOCH;
+use crate::manager::{SourceId, INVALID_EPOCH};
 use crate::storage;
-use crate::storage::{MetaStore, DEFAULT_COLUMN_FAMILY};
+use crate::storage::{MetaStore, Transaction, DEFAULT_COLUMN_FAMILY};
+
+/// Column family name for the row counts of tables.
+/// `cf(table_row_count)`: `table_id` -> `table_id` ++ `row_count`
+const TABLE_ROW_COUNT_CF_NAME: &str = "cf/table_row_count";
 
 /// `BarrierManagerState` defines the necessary state of `GlobalBarrierManager`, this will be stored
 /// persistently to meta store. Add more states when needed.
 pub struct BarrierManagerState {
     pub prev_epoch: u64,
+
+    /// The stream sources paused and not resumed yet. The actors created for them later start
+    /// paused. It's kept in memory only, so the sources will be resumed if the meta node restarts.
+    pub paused_sources: HashSet<SourceId>,
 }
 
 impl BarrierManagerState {
@@ -35,9 +45,11 @@ impl BarrierManagerState {
         {
             Ok(byte_vec) => BarrierManagerState {
                 prev_epoch: u64::from_be_bytes(byte_vec.as_slice().try_into().unwrap()),
+                paused_sources: HashSet::new(),
             },
             Err(storage::Error::ItemNotFound(_)) => BarrierManagerState {
                 prev_epoch: INVALID_EPOCH,
+                paused_sources: HashSet::new(),
             },
             Err(e) => panic!("{:?}", e),
         }
@@ -57,3 +69,88 @@ impl BarrierManagerState {
             .map_err(Into::into)
     }
 }
+
+/// `TableRowCounts` tracks the number of rows in each table, accumulated from the deltas reported
+/// by the `Materialize` executors when barriers are collected. It's an estimation, since the deltas
+/// of the epochs aborted by recovery are not reverted. Only the changed tables are persisted by
+/// [`Self::update`].
+#[derive(Debug, Default)]
+pub struct TableRowCounts {
+    row_counts: HashMap<u32, i64>,
+
+    /// The tables changed since the last update.
+    dirty: HashSet<u32>,
+}
+
+impl TableRowCounts {
+    pub async fn create<S>(store: &S) -> Self
+    where
+        S: MetaStore,
+    {
+        let values = store
+            .list_cf(TABLE_ROW_COUNT_CF_NAME)
+            .await
+            .unwrap_or_else(|e| panic!("{:?}", e));
+        let row_counts = values
+            .iter()
+            .map(|value| {
+                let (table_id, row_count) = value.split_at(4);
+                (
+                    u32::from_be_bytes(table_id.try_into().unwrap()),
+                    i64::from_be_bytes(row_count.try_into().unwrap()),
+                )
+            })
+            .collect();
+
+        Self {
+            row_counts,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// The estimated number of rows in the table.
+    pub fn get(&self, table_id: u32) -> u64 {
+        self.row_counts.get(&table_id).cloned().unwrap_or(0).max(0) as u64
+    }
+
+    /// Apply the row count deltas of tables reported in a barrier.
+    pub fn apply_deltas(&mut self, deltas: impl IntoIterator<Item = (u32, i64)>) {
+        for (table_id, delta) in deltas {
+            *self.row_counts.entry(table_id).or_default() += delta;
+            self.dirty.insert(table_id);
+        }
+    }
+
+    /// Remove the row count of a dropped table.
+    pub fn remove(&mut self, table_id: u32) {
+        if self.row_counts.remove(&table_id).is_some() {
+            self.dirty.insert(table_id);
+        }
+    }
+
+    pub async fn update<S>(&mut self, store: &S) -> Result<()>
+    where
+        S: MetaStore,
+    {
+        if self.dirty.is_empty() {
+            return Ok(());
+        }
+
+        let mut txn = Transaction::default();
+        for &table_id in &self.dirty {
+            let key = table_id.to_be_bytes().to_vec();
+            match self.row_counts.get(&table_id) {
+                Some(row_count) => {
+                    let value =
+                        [&table_id.to_be_bytes()[..], &row_count.to_be_bytes()[..]].concat();
+                    txn.put(TABLE_ROW_COUNT_CF_NAME.to_string(), key, value);
+                }
+                None => txn.delete(TABLE_ROW_COUNT_CF_NAME.to_string(), key),
+            }
+        }
+        store.txn(txn).await?;
+        self.dirty.clear();
+
+        Ok(())
+    }
+}
@@ -16,13 +16,16 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 
 use itertools::Itertools;
 use risingwave_common::catalog::TableId;
-use risingwave_common::error::Result;
+use risingwave_common::error::{Result, ToRwResult};
+use risingwave_connector::SplitImpl;
 use risingwave_pb::meta::table_fragments::fragment::FragmentType;
 use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus, Fragment};
 use risingwave_pb::meta::TableFragments as ProstTableFragments;
 use risingwave_pb::stream_plan::source_node::SourceType;
 use risingwave_pb::stream_plan::stream_node::Node;
-use risingwave_pb::stream_plan::{StreamActor, StreamNode};
+use risingwave_pb::stream_plan::{
+    ActorMapping, SourceNode, StreamActor, StreamNode, StreamSourceState,
+};
 
 use super::{ActorId, FragmentId};
 use crate::cluster::WorkerId;
@@ -105,6 +108,229 @@ impl TableFragments {
         }
     }
 
+    /// Update state of the given actors.
+    pub fn update_actors_state_of(&mut self, actor_ids: &[ActorId], state: ActorState) {
+        for actor_id in actor_ids {
+            if let Some(actor_status) = self.actor_status.get_mut(actor_id) {
+                actor_status.set_state(state);
+            }
+        }
+    }
+
+    /// Returns the status of actors.
+    pub fn actor_status(&self) -> &BTreeMap<ActorId, ActorStatus> {
+        &self.actor_status
+    }
+
+    /// Add actors to the given fragment with their status.
+    pub fn add_actors(
+        &mut self,
+        fragment_id: FragmentId,
+        actors: Vec<StreamActor>,
+        actor_status: BTreeMap<ActorId, ActorStatus>,
+    ) {
+        let fragment = self.fragments.get_mut(&fragment_id).unwrap();
+        fragment.actors.extend(actors);
+        self.actor_status.extend(actor_status);
+    }
+
+    /// Remove the given actors and their status.
+    pub fn remove_actors(&mut self, actor_ids: &HashSet<ActorId>) {
+        for fragment in self.fragments.values_mut() {
+            fragment
+                .actors
+                .retain(|actor| !actor_ids.contains(&actor.actor_id));
+        }
+        self.actor_status
+            .retain(|actor_id, _| !actor_ids.contains(actor_id));
+    }
+
+    /// Move the given actors to other nodes: `actor_id` => `node_id`.
+    pub fn migrate_actors(&mut self, migrations: &HashMap<ActorId, WorkerId>) {
+        for (actor_id, &node_id) in migrations {
+            if let Some(actor_status) = self.actor_status.get_mut(actor_id) {
+                actor_status.node_id = node_id;
+            }
+        }
+    }
+
+    /// Update the downstream actors and the hash mapping of the dispatchers of the given actors,
+    /// e.g., when their downstream fragment is rescheduled.
+    pub fn update_dispatchers(
+        &mut self,
+        actor_ids: &HashSet<ActorId>,
+        removed_downstream_actors: &HashSet<ActorId>,
+        added_downstream_actors: &[ActorId],
+        hash_mapping: Option<ActorMapping>,
+    ) {
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                if !actor_ids.contains(&actor.actor_id) {
+                    continue;
+                }
+                let dispatcher = &mut actor.dispatcher[0];
+                dispatcher
+                    .downstream_actor_id
+                    .retain(|id| !removed_downstream_actors.contains(id));
+                dispatcher
+                    .downstream_actor_id
+                    .extend_from_slice(added_downstream_actors);
+                if hash_mapping.is_some() {
+                    dispatcher.hash_mapping = hash_mapping.clone();
+                }
+            }
+        }
+    }
+
+    fn update_merge_node(
+        stream_node: &mut StreamNode,
+        removed_upstream_actors: &HashSet<ActorId>,
+        added_upstream_actors: &[ActorId],
+    ) {
+        if let Some(Node::MergeNode(merge)) = stream_node.node.as_mut() {
+            merge
+                .upstream_actor_id
+                .retain(|id| !removed_upstream_actors.contains(id));
+            merge
+                .upstream_actor_id
+                .extend_from_slice(added_upstream_actors);
+        }
+
+        for child in &mut stream_node.input {
+            Self::update_merge_node(child, removed_upstream_actors, added_upstream_actors);
+        }
+    }
+
+    /// Update the upstream actors of the given actors and their `Merge` nodes, e.g., when their
+    /// upstream fragment is rescheduled. Note that the given actors should have exactly one `Merge`
+    /// node.
+    pub fn update_merges(
+        &mut self,
+        actor_ids: &HashSet<ActorId>,
+        removed_upstream_actors: &HashSet<ActorId>,
+        added_upstream_actors: &[ActorId],
+    ) {
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                if !actor_ids.contains(&actor.actor_id) {
+                    continue;
+                }
+                actor
+                    .upstream_actor_id
+                    .retain(|id| !removed_upstream_actors.contains(id));
+                actor
+                    .upstream_actor_id
+                    .extend_from_slice(added_upstream_actors);
+                Self::update_merge_node(
+                    actor.nodes.as_mut().unwrap(),
+                    removed_upstream_actors,
+                    added_upstream_actors,
+                );
+            }
+        }
+    }
+
+    fn update_source_node(stream_node: &mut StreamNode, splits: &[SplitImpl]) {
+        if let Some(Node::SourceNode(source)) = stream_node.node.as_mut() {
+            source.stream_source_state = if splits.is_empty() {
+                None
+            } else {
+                Some(StreamSourceState {
+                    split_type: splits[0].get_type(),
+                    stream_source_splits: splits
+                        .iter()
+                        .map(|split| split.to_string().unwrap().into_bytes())
+                        .collect(),
+                })
+            };
+        }
+
+        for child in &mut stream_node.input {
+            Self::update_source_node(child, splits);
+        }
+    }
+
+    /// Update the splits read by the given stream source actors, which are persisted in their
+    /// `Source` nodes.
+    pub fn update_actor_splits(&mut self, split_assignment: &HashMap<ActorId, Vec<SplitImpl>>) {
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                if let Some(splits) = split_assignment.get(&actor.actor_id) {
+                    Self::update_source_node(actor.nodes.as_mut().unwrap(), splits);
+                }
+            }
+        }
+    }
+
+    fn update_rate_limit(stream_node: &mut StreamNode, rate_limit: u32) {
+        match stream_node.node.as_mut() {
+            Some(Node::SourceNode(source)) => source.rate_limit = rate_limit,
+            Some(Node::ChainNode(chain)) => chain.rate_limit = rate_limit,
+            _ => {}
+        }
+
+        for child in &mut stream_node.input {
+            Self::update_rate_limit(child, rate_limit);
+        }
+    }
+
+    /// Update the rate limits of the given source and chain actors, which are persisted in their
+    /// `Source` and `Chain` nodes. A rate limit of 0 means unlimited.
+    pub fn update_actor_rate_limits(&mut self, actor_rate_limits: &HashMap<ActorId, u32>) {
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                if let Some(&rate_limit) = actor_rate_limits.get(&actor.actor_id) {
+                    Self::update_rate_limit(actor.nodes.as_mut().unwrap(), rate_limit);
+                }
+            }
+        }
+    }
+
+    fn fetch_source_node(stream_node: &StreamNode) -> Option<&SourceNode> {
+        if let Some(Node::SourceNode(source)) = stream_node.node.as_ref() {
+            return Some(source);
+        }
+
+        stream_node.input.iter().find_map(Self::fetch_source_node)
+    }
+
+    /// Returns the splits read by the actors of each stream source fragment, along with the id of
+    /// the source.
+    pub fn stream_source_splits(
+        &self,
+    ) -> Result<Vec<(SourceId, BTreeMap<ActorId, Vec<SplitImpl>>)>> {
+        let mut source_splits = vec![];
+        for fragment in self.fragments.values() {
+            let mut source_id = None;
+            let mut actor_splits = BTreeMap::new();
+            for actor in &fragment.actors {
+                let stream_node = actor.nodes.as_ref().unwrap();
+                if let Some(id) = Self::fetch_stream_source_id(stream_node) {
+                    let splits = match &Self::fetch_source_node(stream_node)
+                        .unwrap()
+                        .stream_source_state
+                    {
+                        Some(state) => state
+                            .stream_source_splits
+                            .iter()
+                            .map(|split| {
+                                SplitImpl::restore_from_bytes(state.split_type.clone(), split)
+                            })
+                            .collect::<anyhow::Result<Vec<_>>>()
+                            .to_rw_result()?,
+                        None => vec![],
+                    };
+                    source_id = Some(id);
+                    actor_splits.insert(actor.actor_id, splits);
+                }
+            }
+            if let Some(source_id) = source_id {
+                source_splits.push((source_id, actor_splits));
+            }
+        }
+        Ok(source_splits)
+    }
+
     /// Returns actor ids associated with this table.
     pub fn actor_ids(&self) -> Vec<ActorId> {
         self.fragments
@@ -135,6 +361,43 @@ impl TableFragments {
         Self::filter_actor_ids(self, FragmentType::Source)
     }
 
+    /// Returns the ids of the actors reading the given stream sources. The actors of table sources
+    /// are not included.
+    pub fn stream_source_actor_ids(&self, source_ids: &HashSet<SourceId>) -> Vec<ActorId> {
+        self.fragments
+            .values()
+            .filter(|fragment| fragment.fragment_type == FragmentType::Source as i32)
+            .flat_map(|fragment| fragment.actors.iter())
+            .filter(|actor| {
+                Self::fetch_stream_source_id(actor.nodes.as_ref().unwrap())
+                    .map_or(false, |source_id| source_ids.contains(&source_id))
+            })
+            .map(|actor| actor.actor_id)
+            .collect()
+    }
+
+    /// Returns the ids of the stream sources read by this materialized view.
+    pub fn stream_source_ids(&self) -> HashSet<SourceId> {
+        self.fragments
+            .values()
+            .filter(|fragment| fragment.fragment_type == FragmentType::Source as i32)
+            .flat_map(|fragment| fragment.actors.iter())
+            .filter_map(|actor| Self::fetch_stream_source_id(actor.nodes.as_ref().unwrap()))
+            .collect()
+    }
+
+    /// Returns the id of the stream source read by the given fragment, if any.
+    pub fn fragment_stream_source_id(&self, fragment_id: FragmentId) -> Option<SourceId> {
+        let fragment = self.fragments.get(&fragment_id)?;
+        if fragment.fragment_type != FragmentType::Source as i32 {
+            return None;
+        }
+        fragment
+            .actors
+            .first()
+            .and_then(|actor| Self::fetch_stream_source_id(actor.nodes.as_ref().unwrap()))
+    }
+
     /// Returns sink actor ids.
     pub fn sink_actor_ids(&self) -> Vec<ActorId> {
         Self::filter_actor_ids(self, FragmentType::Sink)
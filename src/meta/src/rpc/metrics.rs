@@ -40,6 +40,8 @@ pub struct MetaMetrics {
     pub grpc_latency: HistogramVec,
     /// latency of each barrier
     pub barrier_latency: Histogram,
+    /// latency of collecting each barrier from each compute node
+    pub barrier_collect_latency: HistogramVec,
     /// max committed epoch
     pub max_committed_epoch: IntGauge,
     /// num of uncommitted SSTs,
@@ -84,6 +86,14 @@ impl MetaMetrics {
         );
         let barrier_latency = register_histogram_with_registry!(opts, registry).unwrap();
 
+        let opts = histogram_opts!(
+            "meta_barrier_collect_duration_seconds",
+            "latency of collecting barrier from each compute node",
+            buckets.to_vec()
+        );
+        let barrier_collect_latency =
+            register_histogram_vec_with_registry!(opts, &["worker_id"], registry).unwrap();
+
         let max_committed_epoch = register_int_gauge_with_registry!(
             "storage_max_committed_epoch",
             "max committed epoch",
@@ -174,6 +184,7 @@ impl MetaMetrics {
             registry,
             grpc_latency,
             barrier_latency,
+            barrier_collect_latency,
             max_committed_epoch,
             uncommitted_sst_num,
             level_sst_num,
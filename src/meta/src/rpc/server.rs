@@ -133,18 +133,6 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             .unwrap(),
     );
 
-    if let Some(dashboard_addr) = dashboard_addr {
-        let dashboard_service = DashboardService {
-            dashboard_addr,
-            cluster_manager: cluster_manager.clone(),
-            fragment_manager: fragment_manager.clone(),
-            meta_store: env.meta_store_ref(),
-            has_test_data: Arc::new(std::sync::atomic::AtomicBool::new(false)),
-        };
-        // TODO: join dashboard service back to local thread.
-        tokio::spawn(dashboard_service.serve(ui_path));
-    }
-
     let catalog_manager = Arc::new(
         StoredCatalogManager::new(meta_store, env.notification_manager_ref())
             .await
@@ -161,12 +149,26 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         meta_metrics.clone(),
     ));
 
+    if let Some(dashboard_addr) = dashboard_addr {
+        let dashboard_service = DashboardService {
+            dashboard_addr,
+            cluster_manager: cluster_manager.clone(),
+            fragment_manager: fragment_manager.clone(),
+            barrier_manager: barrier_manager.clone(),
+            meta_store: env.meta_store_ref(),
+            has_test_data: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        };
+        // TODO: join dashboard service back to local thread.
+        tokio::spawn(dashboard_service.serve(ui_path));
+    }
+
     let source_manager = Arc::new(
         SourceManager::new(
             env.clone(),
             cluster_manager.clone(),
             barrier_manager.clone(),
             catalog_manager_v2.clone(),
+            fragment_manager.clone(),
         )
         .await
         .unwrap(),
@@ -210,7 +212,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
     let cluster_srv = ClusterServiceImpl::<S>::new(cluster_manager.clone());
     let stream_srv = StreamServiceImpl::<S>::new(
         env.clone(),
-        stream_manager,
+        stream_manager.clone(),
         fragment_manager.clone(),
         cluster_manager.clone(),
     );
@@ -233,12 +235,15 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             hummock_manager,
             compactor_manager,
             vacuum_trigger,
-            notification_manager,
+            notification_manager.clone(),
         )
         .await,
     );
     #[cfg(not(test))]
     {
+        sub_tasks.push(
+            GlobalStreamManager::start_rescheduler(stream_manager, notification_manager).await,
+        );
         sub_tasks.push(
             ClusterManager::start_heartbeat_checker(cluster_manager, Duration::from_secs(1)).await,
         );
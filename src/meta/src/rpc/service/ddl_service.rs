@@ -15,6 +15,7 @@
 #![allow(dead_code)]
 use std::collections::HashSet;
 
+use futures::future::BoxFuture;
 use risingwave_common::catalog::CatalogVersion;
 use risingwave_common::error::{tonic_err, Result as RwResult};
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
@@ -279,7 +280,63 @@ where
             .map_err(tonic_err)?;
 
         // 3. Create mview in stream manager. The id in stream node will be filled.
-        if let Err(e) = self.create_mview_on_compute_node(stream_node, id).await {
+        let finished = match self
+            .start_create_mview_on_compute_node(stream_node, id)
+            .await
+        {
+            Ok(finished) => finished,
+            Err(e) => {
+                self.catalog_manager
+                    .cancel_create_table_procedure(&mview)
+                    .await
+                    .map_err(tonic_err)?;
+                return Err(e.to_grpc_status());
+            }
+        };
+
+        if req.background {
+            // 4. Add mview to the catalog in `Creating` status, and finish it in background once
+            // the backfill is finished.
+            let version = self
+                .catalog_manager
+                .publish_creating_table(&mview)
+                .await
+                .map_err(tonic_err)?;
+
+            let catalog_manager = self.catalog_manager.clone();
+            let stream_manager = self.stream_manager.clone();
+            let fragment_manager = self.fragment_manager.clone();
+            tokio::spawn(async move {
+                let result = match finished.await {
+                    Ok(()) => catalog_manager
+                        .finish_create_table_in_background(id)
+                        .await
+                        .map(|_| ()),
+                    Err(e) => {
+                        tracing::warn!("creating materialized view {} is aborted: {}", id, e);
+                        Self::drop_aborted_mview(&stream_manager, &fragment_manager, id).await;
+                        catalog_manager.drop_table(id).await.map(|_| ())
+                    }
+                };
+                if let Err(e) = result {
+                    tracing::error!(
+                        "failed to update materialized view {} created in background: {}",
+                        id,
+                        e
+                    );
+                }
+            });
+
+            return Ok(Response::new(CreateMaterializedViewResponse {
+                status: None,
+                table_id: id,
+                version,
+            }));
+        }
+
+        // 4. Wait for the backfill to finish.
+        if let Err(e) = finished.await {
+            Self::drop_aborted_mview(&self.stream_manager, &self.fragment_manager, id).await;
             self.catalog_manager
                 .cancel_create_table_procedure(&mview)
                 .await
@@ -287,7 +344,7 @@ where
             return Err(e.to_grpc_status());
         }
 
-        // 4. Finally, update the catalog.
+        // 5. Finally, update the catalog.
         let version = self
             .catalog_manager
             .finish_create_table_procedure(&mview)
@@ -367,6 +424,61 @@ where
             version,
         }))
     }
+
+    async fn alter_rate_limit(
+        &self,
+        request: Request<AlterRateLimitRequest>,
+    ) -> Result<Response<AlterRateLimitResponse>, Status> {
+        use risingwave_common::catalog::TableId;
+
+        let request = request.into_inner();
+        let rate_limit = request.rate_limit;
+
+        let version = match request.relation.unwrap() {
+            alter_rate_limit_request::Relation::SourceId(source_id) => {
+                // Update the catalog first, so that a failed throttle still takes effect on the
+                // next recovery of the source actors.
+                let version = self
+                    .catalog_manager
+                    .alter_source_rate_limit(source_id, rate_limit)
+                    .await
+                    .map_err(tonic_err)?;
+                self.stream_manager
+                    .throttle_source(source_id, rate_limit)
+                    .await
+                    .map_err(tonic_err)?;
+                version
+            }
+            alter_rate_limit_request::Relation::TableId(table_id) => {
+                self.stream_manager
+                    .throttle_materialized_view(&TableId::new(table_id), rate_limit)
+                    .await
+                    .map_err(tonic_err)?;
+                0
+            }
+        };
+
+        Ok(Response::new(AlterRateLimitResponse {
+            status: None,
+            version,
+        }))
+    }
+
+    async fn cancel_job(
+        &self,
+        request: Request<CancelJobRequest>,
+    ) -> Result<Response<CancelJobResponse>, Status> {
+        use risingwave_common::catalog::TableId;
+
+        let table_id = request.into_inner().table_id;
+        // The catalog is cleaned up by the creation, which fails after its actors are dropped.
+        self.stream_manager
+            .cancel_materialized_view(&TableId::new(table_id))
+            .await
+            .map_err(tonic_err)?;
+
+        Ok(Response::new(CancelJobResponse { status: None }))
+    }
 }
 
 impl<S> DdlServiceImpl<S>
@@ -375,9 +487,21 @@ where
 {
     async fn create_mview_on_compute_node(
         &self,
-        mut stream_node: StreamNode,
+        stream_node: StreamNode,
         id: TableId,
     ) -> RwResult<()> {
+        self.start_create_mview_on_compute_node(stream_node, id)
+            .await?
+            .await
+    }
+
+    /// Create mview on compute nodes, and return once its actors are built, with a future completed
+    /// when its backfill is finished.
+    async fn start_create_mview_on_compute_node(
+        &self,
+        mut stream_node: StreamNode,
+        id: TableId,
+    ) -> RwResult<BoxFuture<'static, RwResult<()>>> {
         use risingwave_common::catalog::TableId;
 
         use crate::stream::CreateMaterializedViewContext;
@@ -415,10 +539,30 @@ where
 
         // Create on compute node.
         self.stream_manager
-            .create_materialized_view(table_fragments, ctx)
-            .await?;
+            .start_create_materialized_view(table_fragments, ctx)
+            .await
+    }
+
+    /// Drop the actors of the mview whose creation is aborted after they are built. They are
+    /// dropped already if the creation is cancelled, but rebuilt if aborted by the recovery.
+    async fn drop_aborted_mview(
+        stream_manager: &GlobalStreamManagerRef<S>,
+        fragment_manager: &FragmentManagerRef<S>,
+        id: TableId,
+    ) {
+        use risingwave_common::catalog::TableId;
 
-        Ok(())
+        let table_id = TableId::new(id);
+        if fragment_manager
+            .get_table_fragments(&table_id)
+            .await
+            .is_err()
+        {
+            return;
+        }
+        if let Err(e) = stream_manager.drop_materialized_view(&table_id).await {
+            tracing::warn!("failed to drop aborted materialized view {}: {}", id, e);
+        }
     }
 
     async fn create_materialized_source_inner(
@@ -130,4 +130,37 @@ where
             .map_err(|e| e.to_grpc_status())?;
         Ok(Response::new(FlushResponse { status: None }))
     }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn pause(&self, request: Request<PauseRequest>) -> TonicResponse<PauseResponse> {
+        let req = request.into_inner();
+
+        self.global_stream_manager
+            .pause_sources(req.source_ids)
+            .await
+            .map_err(|e| e.to_grpc_status())?;
+        Ok(Response::new(PauseResponse { status: None }))
+    }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn resume(&self, request: Request<ResumeRequest>) -> TonicResponse<ResumeResponse> {
+        let req = request.into_inner();
+
+        self.global_stream_manager
+            .resume_sources(req.source_ids)
+            .await
+            .map_err(|e| e.to_grpc_status())?;
+        Ok(Response::new(ResumeResponse { status: None }))
+    }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn list_jobs(
+        &self,
+        request: Request<ListJobsRequest>,
+    ) -> TonicResponse<ListJobsResponse> {
+        let _req = request.into_inner();
+
+        let jobs = self.global_stream_manager.list_jobs().await;
+        Ok(Response::new(ListJobsResponse { status: None, jobs }))
+    }
 }
@@ -20,14 +20,17 @@ use risingwave_common::catalog::TableId;
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::try_match_expand;
+use risingwave_connector::SplitImpl;
 use risingwave_pb::meta::table_fragments::fragment::FragmentType;
-use risingwave_pb::meta::table_fragments::ActorState;
-use risingwave_pb::stream_plan::StreamActor;
+use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus};
+use risingwave_pb::stream_plan::{ActorMapping, StreamActor};
 use tokio::sync::RwLock;
 
 use crate::cluster::WorkerId;
-use crate::model::{ActorId, MetadataModel, TableFragments, Transactional};
+use crate::manager::SourceId;
+use crate::model::{ActorId, FragmentId, MetadataModel, TableFragments, Transactional};
 use crate::storage::{MetaStore, Transaction};
+use crate::stream::SplitAssignment;
 
 struct FragmentManagerCore {
     table_fragments: HashMap<TableId, TableFragments>,
@@ -95,6 +98,120 @@ where
         }
     }
 
+    pub async fn get_table_fragments(&self, table_id: &TableId) -> Result<TableFragments> {
+        let map = &self.core.read().await.table_fragments;
+        match map.get(table_id) {
+            Some(table_fragments) => Ok(table_fragments.clone()),
+            None => Err(RwError::from(InternalError(format!(
+                "table_fragment not exist: id={}",
+                table_id
+            )))),
+        }
+    }
+
+    /// Apply `f` to a copy of the table fragments, then persist it into meta store.
+    async fn mutate_table_fragments<F>(&self, table_id: &TableId, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut TableFragments),
+    {
+        let map = &mut self.core.write().await.table_fragments;
+
+        match map.get(table_id) {
+            Some(table_fragments) => {
+                let mut table_fragments = table_fragments.clone();
+                f(&mut table_fragments);
+                table_fragments.insert(&*self.meta_store).await?;
+                map.insert(*table_id, table_fragments);
+
+                Ok(())
+            }
+            None => Err(RwError::from(InternalError(format!(
+                "table_fragment not exist: id={}",
+                table_id
+            )))),
+        }
+    }
+
+    /// Add the actors to be rescheduled into the fragment, currently their state is
+    /// `ActorState::Inactive`.
+    pub async fn add_inactive_actors(
+        &self,
+        table_id: &TableId,
+        fragment_id: FragmentId,
+        actors: Vec<StreamActor>,
+        actor_status: BTreeMap<ActorId, ActorStatus>,
+    ) -> Result<()> {
+        self.mutate_table_fragments(table_id, |table_fragments| {
+            table_fragments.add_actors(fragment_id, actors, actor_status)
+        })
+        .await
+    }
+
+    /// Drop the given actors which are still `ActorState::Inactive`, used to clean up a failed
+    /// reschedule.
+    pub async fn drop_inactive_actors(
+        &self,
+        table_id: &TableId,
+        actor_ids: &[ActorId],
+    ) -> Result<()> {
+        self.mutate_table_fragments(table_id, |table_fragments| {
+            let inactive_actors = actor_ids
+                .iter()
+                .filter(|actor_id| {
+                    table_fragments
+                        .actor_status()
+                        .get(actor_id)
+                        .map_or(false, |status| status.state == ActorState::Inactive as i32)
+                })
+                .cloned()
+                .collect();
+            table_fragments.remove_actors(&inactive_actors);
+        })
+        .await
+    }
+
+    /// Finish rescheduling a fragment: remove the removed actors, update the added actors' state
+    /// to `ActorState::Running`, and update the dispatchers of upstream actors and the merges of
+    /// downstream actors.
+    pub async fn apply_reschedule(
+        &self,
+        table_id: &TableId,
+        upstream_actors: &[ActorId],
+        downstream_actors: &[ActorId],
+        removed_actors: &[ActorId],
+        added_actors: &[ActorId],
+        hash_mapping: ActorMapping,
+    ) -> Result<()> {
+        let upstream_actors = upstream_actors.iter().cloned().collect();
+        let downstream_actors = downstream_actors.iter().cloned().collect();
+        let removed_actors = removed_actors.iter().cloned().collect();
+
+        self.mutate_table_fragments(table_id, |table_fragments| {
+            table_fragments.remove_actors(&removed_actors);
+            table_fragments.update_actors_state_of(added_actors, ActorState::Running);
+            table_fragments.update_dispatchers(
+                &upstream_actors,
+                &removed_actors,
+                added_actors,
+                Some(hash_mapping),
+            );
+            table_fragments.update_merges(&downstream_actors, &removed_actors, added_actors);
+        })
+        .await
+    }
+
+    /// Move actors to other nodes without changing the stream graph: `actor_id` => `node_id`.
+    pub async fn migrate_actors(
+        &self,
+        table_id: &TableId,
+        migrations: &HashMap<ActorId, WorkerId>,
+    ) -> Result<()> {
+        self.mutate_table_fragments(table_id, |table_fragments| {
+            table_fragments.migrate_actors(migrations)
+        })
+        .await
+    }
+
     /// Start create a new `TableFragments` and insert it into meta store, currently the actors'
     /// state is `ActorState::Inactive`.
     pub async fn start_create_table_fragments(&self, table_fragment: TableFragments) -> Result<()> {
@@ -222,11 +339,29 @@ where
 
     /// Used in [`crate::barrier::GlobalBarrierManager`]
     pub async fn load_all_actors(&self, with_creating_table: Option<TableId>) -> ActorInfos {
+        let map = &self.core.read().await.table_fragments;
+        Self::resolve_actor_infos(map.values(), with_creating_table)
+    }
+
+    /// Load the running actors of the given tables, used in the partial recovery of
+    /// [`crate::barrier::GlobalBarrierManager`].
+    pub async fn load_actors_of_tables(&self, table_ids: &HashSet<TableId>) -> ActorInfos {
+        let map = &self.core.read().await.table_fragments;
+        Self::resolve_actor_infos(
+            map.values()
+                .filter(|fragments| table_ids.contains(&fragments.table_id())),
+            None,
+        )
+    }
+
+    fn resolve_actor_infos<'a>(
+        table_fragments: impl Iterator<Item = &'a TableFragments>,
+        with_creating_table: Option<TableId>,
+    ) -> ActorInfos {
         let mut actor_maps = HashMap::new();
         let mut source_actor_ids = HashMap::new();
 
-        let map = &self.core.read().await.table_fragments;
-        for fragments in map.values() {
+        for fragments in table_fragments {
             let include_inactive = with_creating_table.contains(&fragments.table_id());
             let check_state = |s: ActorState| {
                 s == ActorState::Running || include_inactive && s == ActorState::Inactive
@@ -262,6 +397,40 @@ where
         }
     }
 
+    /// Returns the tables of the given actors, along with the tables connected with them by
+    /// materialized views on materialized views in either direction. The actors of these tables
+    /// exchange data with each other, so they have to be recovered together.
+    pub async fn connected_table_ids(&self, actor_ids: &HashSet<ActorId>) -> HashSet<TableId> {
+        let map = &self.core.read().await.table_fragments;
+        let dependencies = map
+            .values()
+            .map(|fragments| (fragments.table_id(), fragments.dependent_table_ids()))
+            .collect::<HashMap<_, _>>();
+
+        let mut table_ids = map
+            .values()
+            .filter(|fragments| {
+                fragments
+                    .actor_status()
+                    .keys()
+                    .any(|actor_id| actor_ids.contains(actor_id))
+            })
+            .map(|fragments| fragments.table_id())
+            .collect::<HashSet<_>>();
+        let mut to_visit = table_ids.iter().cloned().collect::<Vec<_>>();
+        while let Some(table_id) = to_visit.pop() {
+            for (&other, other_dependencies) in &dependencies {
+                let connected = other_dependencies.contains(&table_id)
+                    || dependencies[&table_id].contains(&other);
+                if connected && table_ids.insert(other) {
+                    to_visit.push(other);
+                }
+            }
+        }
+
+        table_ids
+    }
+
     pub async fn all_node_actors(
         &self,
         include_inactive: bool,
@@ -279,6 +448,28 @@ where
         actor_maps
     }
 
+    pub async fn node_actors_of_tables(
+        &self,
+        table_ids: &HashSet<TableId>,
+    ) -> HashMap<WorkerId, Vec<StreamActor>> {
+        let mut actor_maps = HashMap::new();
+
+        let map = &self.core.read().await.table_fragments;
+        for fragments in map
+            .values()
+            .filter(|fragments| table_ids.contains(&fragments.table_id()))
+        {
+            for (node_id, actors) in fragments.node_actors(false) {
+                actor_maps
+                    .entry(node_id)
+                    .or_insert_with(Vec::new)
+                    .extend(actors);
+            }
+        }
+
+        actor_maps
+    }
+
     pub async fn all_chain_actor_ids(&self) -> HashSet<ActorId> {
         let map = &self.core.read().await.table_fragments;
 
@@ -312,6 +503,102 @@ where
         }
     }
 
+    /// Returns the ids of the actors reading the given stream sources.
+    pub async fn get_stream_source_actor_ids(
+        &self,
+        source_ids: &HashSet<SourceId>,
+    ) -> Vec<ActorId> {
+        let map = &self.core.read().await.table_fragments;
+
+        map.values()
+            .flat_map(|table_fragment| table_fragment.stream_source_actor_ids(source_ids))
+            .collect()
+    }
+
+    /// Returns the ids of the stream sources read by any materialized view.
+    pub async fn get_stream_source_ids(&self) -> HashSet<SourceId> {
+        let map = &self.core.read().await.table_fragments;
+
+        map.values()
+            .flat_map(|table_fragment| table_fragment.stream_source_ids())
+            .collect()
+    }
+
+    /// Returns the splits read by the actors of each stream source fragment, along with the id of
+    /// the source. The materialized views being created or rescheduled are skipped, as their
+    /// actors are not all running.
+    pub async fn get_stream_source_splits(
+        &self,
+    ) -> Result<Vec<(SourceId, BTreeMap<ActorId, Vec<SplitImpl>>)>> {
+        let map = &self.core.read().await.table_fragments;
+
+        let mut source_splits = vec![];
+        for table_fragments in map.values() {
+            if table_fragments
+                .actor_status()
+                .values()
+                .all(|status| status.state == ActorState::Running as i32)
+            {
+                source_splits.extend(table_fragments.stream_source_splits()?);
+            }
+        }
+        Ok(source_splits)
+    }
+
+    /// Update the splits read by the stream source actors, after they are assigned by a barrier.
+    pub async fn update_actor_splits(&self, split_assignment: &SplitAssignment) -> Result<()> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        for table_fragments in map.values_mut() {
+            if table_fragments
+                .actor_ids()
+                .iter()
+                .any(|actor_id| split_assignment.contains_key(actor_id))
+            {
+                let mut new_table_fragments = table_fragments.clone();
+                new_table_fragments.update_actor_splits(split_assignment);
+                new_table_fragments.insert(&*self.meta_store).await?;
+                *table_fragments = new_table_fragments;
+            }
+        }
+        Ok(())
+    }
+
+    /// Update the rate limits of the source and chain actors, after they are changed by a barrier.
+    pub async fn update_actor_rate_limits(
+        &self,
+        actor_rate_limits: &HashMap<ActorId, u32>,
+    ) -> Result<()> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        for table_fragments in map.values_mut() {
+            if table_fragments
+                .actor_ids()
+                .iter()
+                .any(|actor_id| actor_rate_limits.contains_key(actor_id))
+            {
+                let mut new_table_fragments = table_fragments.clone();
+                new_table_fragments.update_actor_rate_limits(actor_rate_limits);
+                new_table_fragments.insert(&*self.meta_store).await?;
+                *table_fragments = new_table_fragments;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the actors of the materialized view which read the snapshots of the upstream
+    /// materialized views with `Chain`.
+    pub async fn get_table_chain_actor_ids(&self, table_id: &TableId) -> Result<Vec<ActorId>> {
+        let map = &self.core.read().await.table_fragments;
+        match map.get(table_id) {
+            Some(table_fragment) => Ok(table_fragment.chain_actor_ids()),
+            None => Err(RwError::from(InternalError(format!(
+                "table_fragment not exist: id={}",
+                table_id
+            )))),
+        }
+    }
+
     pub async fn get_table_sink_actor_ids(&self, table_id: &TableId) -> Result<Vec<ActorId>> {
         let map = &self.core.read().await.table_fragments;
         match map.get(table_id) {
@@ -15,6 +15,7 @@
 mod fragmenter;
 mod graph;
 mod meta;
+mod scale;
 mod scheduler;
 mod source_manager;
 mod stream_manager;
@@ -24,6 +25,7 @@ mod test_fragmenter;
 
 pub use fragmenter::*;
 pub use meta::*;
+pub use scale::*;
 pub use scheduler::*;
 pub use source_manager::*;
 pub use stream_manager::*;
@@ -0,0 +1,318 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use itertools::Itertools;
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::common::ActorInfo;
+use risingwave_pb::meta::table_fragments::fragment::{FragmentDistributionType, FragmentType};
+use risingwave_pb::meta::table_fragments::Fragment;
+use risingwave_pb::stream_plan::stream_node::Node;
+use risingwave_pb::stream_plan::{DispatcherType, StreamActor, StreamNode};
+
+use crate::barrier::Reschedule;
+use crate::model::{ActorId, FragmentId, TableFragments};
+
+/// Returns whether the executors of the stream node can hand over the ownership of virtual nodes
+/// online. Stateless executors and hash joins keep their states in a keyspace shared by all actors
+/// of the fragment, so the ownership can be handed over by evicting the caches. Other stateful
+/// executors like hash agg and top-n keep their states in per-actor keyspaces, which cannot be
+/// handed over for now.
+fn is_reschedulable_node(stream_node: &StreamNode) -> bool {
+    let reschedulable = matches!(
+        stream_node.node,
+        Some(
+            Node::ProjectNode(_)
+                | Node::FilterNode(_)
+                | Node::HopWindowNode(_)
+                | Node::HashJoinNode(_)
+                | Node::MergeNode(_)
+        )
+    );
+
+    reschedulable && stream_node.input.iter().all(is_reschedulable_node)
+}
+
+fn count_merge_nodes(stream_node: &StreamNode) -> usize {
+    let count = matches!(stream_node.node, Some(Node::MergeNode(_))) as usize;
+
+    count
+        + stream_node
+            .input
+            .iter()
+            .map(count_merge_nodes)
+            .sum::<usize>()
+}
+
+/// The actors connected with a reschedulable fragment.
+struct Neighbours {
+    /// The actors of the upstream fragments, which dispatch to this fragment by hash.
+    upstream_actors: Vec<ActorId>,
+    /// The actors of the downstream fragment, which merge the outputs of this fragment.
+    downstream_actors: Vec<ActorId>,
+    /// The current mapping from virtual nodes to the actors of this fragment.
+    vnode_mapping: Vec<ActorId>,
+}
+
+/// Resolve the neighbours of the fragment, or returns an error if the fragment cannot be
+/// rescheduled online. A reschedulable fragment should:
+/// 1. be hash-distributed with at least 2 actors, and be neither the source nor the sink of the
+/// materialized view.
+/// 2. only consist of the executors described in [`is_reschedulable_node`].
+/// 3. be the only downstream of its upstream actors, which dispatch to it by hash.
+/// 4. be the only upstream of its downstream actors, which merge from it with one `Merge` node.
+fn resolve_neighbours(table_fragments: &TableFragments, fragment: &Fragment) -> Result<Neighbours> {
+    let not_reschedulable = |reason: &str| {
+        Err(RwError::from(InternalError(format!(
+            "fragment {} cannot be rescheduled: {}",
+            fragment.fragment_id, reason
+        ))))
+    };
+
+    if fragment.fragment_type != FragmentType::Others as i32
+        || fragment.distribution_type != FragmentDistributionType::Hash as i32
+        || fragment.actors.len() < 2
+    {
+        return not_reschedulable("not a hash-distributed fragment in the middle");
+    }
+    let template = &fragment.actors[0];
+    if !is_reschedulable_node(template.get_nodes()?) {
+        return not_reschedulable("the state of executors cannot be handed over");
+    }
+
+    let actor_map = table_fragments.actor_map();
+    let fragment_actors: HashSet<_> = fragment.actors.iter().map(|actor| actor.actor_id).collect();
+
+    let upstream_actors = template.upstream_actor_id.clone();
+    if upstream_actors.is_empty() {
+        return not_reschedulable("no upstream actors");
+    }
+    let mut vnode_mapping = None;
+    for up_id in &upstream_actors {
+        let dispatcher = match actor_map
+            .get(up_id)
+            .and_then(|actor| actor.dispatcher.first())
+        {
+            Some(dispatcher) => dispatcher,
+            None => return not_reschedulable("upstream actors are not in the same table"),
+        };
+        if dispatcher.r#type != DispatcherType::Hash as i32
+            || !dispatcher
+                .downstream_actor_id
+                .iter()
+                .all(|down_id| fragment_actors.contains(down_id))
+        {
+            return not_reschedulable("upstream actors do not dispatch to it by hash");
+        }
+        if let Some(hash_mapping) = &dispatcher.hash_mapping {
+            vnode_mapping.get_or_insert_with(|| hash_mapping.hash_mapping.clone());
+        }
+    }
+    let vnode_mapping = match vnode_mapping {
+        Some(vnode_mapping) => vnode_mapping,
+        None => return not_reschedulable("no hash mapping in upstream dispatchers"),
+    };
+
+    let downstream_actors = template
+        .dispatcher
+        .iter()
+        .flat_map(|dispatcher| dispatcher.downstream_actor_id.iter().cloned())
+        .collect_vec();
+    for down_id in &downstream_actors {
+        match actor_map.get(down_id) {
+            Some(actor) if count_merge_nodes(actor.get_nodes()?) == 1 => {}
+            _ => return not_reschedulable("downstream actors merge from multiple fragments"),
+        }
+    }
+
+    Ok(Neighbours {
+        upstream_actors,
+        downstream_actors,
+        vnode_mapping,
+    })
+}
+
+/// Returns whether the actors of the fragment can be added or removed online.
+pub fn is_reschedulable(table_fragments: &TableFragments, fragment: &Fragment) -> bool {
+    resolve_neighbours(table_fragments, fragment).is_ok()
+}
+
+/// Rebalance the virtual nodes among the actors after `removed_actors` are removed and
+/// `added_actors` are added. Only the virtual nodes of the removed actors and the overloaded actors
+/// are moved, so that the caches of the other actors remain valid.
+pub fn rebalance_vnode_mapping(
+    vnode_mapping: &[ActorId],
+    removed_actors: &[ActorId],
+    added_actors: &[ActorId],
+) -> Vec<ActorId> {
+    let mut actor_vnodes: BTreeMap<ActorId, Vec<usize>> = BTreeMap::new();
+    let mut orphan_vnodes = vec![];
+    for (vnode, actor_id) in vnode_mapping.iter().enumerate() {
+        if removed_actors.contains(actor_id) {
+            orphan_vnodes.push(vnode);
+        } else {
+            actor_vnodes.entry(*actor_id).or_default().push(vnode);
+        }
+    }
+    for actor_id in added_actors {
+        actor_vnodes.insert(*actor_id, vec![]);
+    }
+    assert!(
+        !actor_vnodes.is_empty(),
+        "no actors left to own virtual nodes"
+    );
+
+    // The actors owning more virtual nodes are preferred to own the remainder, which makes fewer
+    // virtual nodes moved.
+    let base = vnode_mapping.len() / actor_vnodes.len();
+    let mut remainder = vnode_mapping.len() % actor_vnodes.len();
+    let targets: HashMap<_, _> = actor_vnodes
+        .iter()
+        .sorted_by_key(|(_, vnodes)| Reverse(vnodes.len()))
+        .map(|(&actor_id, _)| {
+            let extra = if remainder > 0 {
+                remainder -= 1;
+                1
+            } else {
+                0
+            };
+            (actor_id, base + extra)
+        })
+        .collect();
+
+    // Take the excess virtual nodes away from the overloaded actors, and assign them with the
+    // virtual nodes of the removed actors to the underloaded actors.
+    for (actor_id, vnodes) in &mut actor_vnodes {
+        let target = targets[actor_id];
+        if vnodes.len() > target {
+            orphan_vnodes.extend(vnodes.drain(target..));
+        }
+    }
+    let mut new_mapping = vnode_mapping.to_vec();
+    for (actor_id, vnodes) in &mut actor_vnodes {
+        while vnodes.len() < targets[actor_id] {
+            let vnode = orphan_vnodes.pop().unwrap();
+            vnodes.push(vnode);
+            new_mapping[vnode] = *actor_id;
+        }
+    }
+    assert!(orphan_vnodes.is_empty());
+
+    new_mapping
+}
+
+/// Plan to reschedule the fragment by removing `removed_actors` and adding `added_actors`.
+pub fn plan_reschedule(
+    table_fragments: &TableFragments,
+    fragment_id: FragmentId,
+    removed_actors: Vec<ActorId>,
+    added_actors: Vec<ActorInfo>,
+) -> Result<Reschedule> {
+    let fragment = table_fragments.fragments.get(&fragment_id).ok_or_else(|| {
+        RwError::from(InternalError(format!(
+            "fragment not exist: id={}",
+            fragment_id
+        )))
+    })?;
+    let neighbours = resolve_neighbours(table_fragments, fragment)?;
+
+    if fragment.actors.len() + added_actors.len() < removed_actors.len() + 2 {
+        return Err(RwError::from(InternalError(format!(
+            "fragment {} should have at least 2 actors after rescheduling",
+            fragment_id
+        ))));
+    }
+
+    let added_actor_ids = added_actors.iter().map(|info| info.actor_id).collect_vec();
+    let vnode_mapping =
+        rebalance_vnode_mapping(&neighbours.vnode_mapping, &removed_actors, &added_actor_ids);
+
+    // The remaining actors whose virtual nodes are taken away or assigned.
+    let vnode_changed_actors = neighbours
+        .vnode_mapping
+        .iter()
+        .zip_eq(vnode_mapping.iter())
+        .filter(|(old, new)| old != new)
+        .flat_map(|(&old, &new)| [old, new])
+        .filter(|actor_id| {
+            !removed_actors.contains(actor_id) && !added_actor_ids.contains(actor_id)
+        })
+        .unique()
+        .collect();
+
+    Ok(Reschedule {
+        table_id: table_fragments.table_id(),
+        fragment_id,
+        added_actors,
+        removed_actors,
+        vnode_mapping,
+        vnode_changed_actors,
+        upstream_actors: neighbours.upstream_actors,
+        downstream_actors: neighbours.downstream_actors,
+    })
+}
+
+/// Build the actors to add to the fragment, which are copies of an existing actor since all actors
+/// of a hash-distributed fragment share the same plan, upstreams and downstreams.
+pub fn build_added_actors(fragment: &Fragment, actor_ids: &[ActorId]) -> Vec<StreamActor> {
+    actor_ids
+        .iter()
+        .map(|&actor_id| StreamActor {
+            actor_id,
+            ..fragment.actors[0].clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::hash::VIRTUAL_KEY_COUNT;
+
+    use super::*;
+
+    fn vnode_counts(vnode_mapping: &[ActorId]) -> HashMap<ActorId, usize> {
+        vnode_mapping.iter().cloned().counts()
+    }
+
+    #[test]
+    fn test_rebalance_vnode_mapping() {
+        let vnode_mapping = (1..=3)
+            .flat_map(|actor_id| vec![actor_id; VIRTUAL_KEY_COUNT / 3])
+            .chain(std::iter::repeat(3))
+            .take(VIRTUAL_KEY_COUNT)
+            .collect_vec();
+
+        // Scale out.
+        let scaled_out = rebalance_vnode_mapping(&vnode_mapping, &[], &[4, 5]);
+        let counts = vnode_counts(&scaled_out);
+        assert_eq!(counts.len(), 5);
+        assert!(counts.values().max().unwrap() - counts.values().min().unwrap() <= 1);
+        // The existing actors only give away virtual nodes.
+        for (old, new) in vnode_mapping.iter().zip_eq(scaled_out.iter()) {
+            assert!(old == new || [4, 5].contains(new));
+        }
+
+        // Scale in.
+        let scaled_in = rebalance_vnode_mapping(&scaled_out, &[2, 4], &[]);
+        let counts = vnode_counts(&scaled_in);
+        assert_eq!(counts.keys().sorted().collect_vec(), vec![&1, &3, &5]);
+        assert!(counts.values().max().unwrap() - counts.values().min().unwrap() <= 1);
+        // Only the virtual nodes of the removed actors are moved.
+        for (old, new) in scaled_out.iter().zip_eq(scaled_in.iter()) {
+            assert!(old == new || [2, 4].contains(old));
+        }
+    }
+}
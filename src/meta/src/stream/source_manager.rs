@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
 use futures::future::try_join_all;
@@ -29,19 +30,24 @@ use risingwave_pb::stream_service::{
     DropSourceRequest as ComputeNodeDropSourceRequest,
 };
 
-use crate::barrier::BarrierManagerRef;
+use crate::barrier::{BarrierManagerRef, Command};
 use crate::cluster::ClusterManagerRef;
 use crate::manager::{CatalogManagerRef, MetaSrvEnv, SourceId, StreamClient};
 use crate::model::ActorId;
 use crate::storage::MetaStore;
+use crate::stream::FragmentManagerRef;
 
 pub type SourceManagerRef<S> = Arc<SourceManager<S>>;
 
-#[allow(dead_code)]
+/// The splits assigned to each source actor.
+pub type SplitAssignment = HashMap<ActorId, Vec<SplitImpl>>;
+
 pub struct SourceManager<S: MetaStore> {
     env: MetaSrvEnv<S>,
     cluster_manager: ClusterManagerRef<S>,
+    barrier_manager: BarrierManagerRef<S>,
     catalog_manager: CatalogManagerRef<S>,
+    fragment_manager: FragmentManagerRef<S>,
 }
 
 impl<S> SourceManager<S>
@@ -51,13 +57,16 @@ where
     pub async fn new(
         env: MetaSrvEnv<S>,
         cluster_manager: ClusterManagerRef<S>,
-        _barrier_manager: BarrierManagerRef<S>,
+        barrier_manager: BarrierManagerRef<S>,
         catalog_manager: CatalogManagerRef<S>,
+        fragment_manager: FragmentManagerRef<S>,
     ) -> Result<Self> {
         Ok(Self {
             env,
             cluster_manager,
+            barrier_manager,
             catalog_manager,
+            fragment_manager,
         })
     }
 
@@ -93,7 +102,7 @@ where
     pub async fn schedule_split_for_actors(
         &self,
         actors: HashMap<SourceId, Vec<Vec<ActorId>>>,
-    ) -> Result<HashMap<ActorId, Vec<SplitImpl>>> {
+    ) -> Result<SplitAssignment> {
         let source_splits = try_join_all(
             actors
                 .keys()
@@ -175,8 +184,168 @@ where
         Ok(())
     }
 
+    /// Diff the splits discovered with the splits assigned to the actors of a fragment. The splits
+    /// no longer discovered are removed, while the new ones, including those assigned to the
+    /// actors removed, are assigned to the actors with the fewest splits. The splits kept are
+    /// never moved among the actors. Returns the new splits of the changed actors, or `None` if
+    /// nothing is changed.
+    fn reassign_splits(
+        actor_splits: &BTreeMap<ActorId, Vec<SplitImpl>>,
+        discovered_splits: &[SplitImpl],
+    ) -> Option<SplitAssignment> {
+        if actor_splits.is_empty() {
+            return None;
+        }
+
+        let discovered_ids: HashSet<_> = discovered_splits.iter().map(|split| split.id()).collect();
+        let mut new_actor_splits: BTreeMap<_, _> = actor_splits
+            .iter()
+            .map(|(&actor_id, splits)| {
+                let splits = splits
+                    .iter()
+                    .filter(|split| discovered_ids.contains(&split.id()))
+                    .cloned()
+                    .collect_vec();
+                (actor_id, splits)
+            })
+            .collect();
+
+        let assigned_ids: HashSet<_> = new_actor_splits
+            .values()
+            .flatten()
+            .map(|split| split.id())
+            .collect();
+        for split in discovered_splits {
+            if !assigned_ids.contains(&split.id()) {
+                let (_, splits) = new_actor_splits
+                    .iter_mut()
+                    .min_by_key(|(_, splits)| splits.len())
+                    .unwrap();
+                splits.push(split.clone());
+            }
+        }
+
+        let split_ids = |splits: &[SplitImpl]| splits.iter().map(|split| split.id()).collect_vec();
+        let changed: SplitAssignment = new_actor_splits
+            .into_iter()
+            .filter(|(actor_id, splits)| split_ids(splits) != split_ids(&actor_splits[actor_id]))
+            .collect();
+        if changed.is_empty() {
+            None
+        } else {
+            Some(changed)
+        }
+    }
+
+    /// List the splits of the stream sources, and reassign the splits to the source actors through
+    /// a barrier if they are changed.
+    async fn discover_splits(&self) -> Result<()> {
+        let source_splits = self.fragment_manager.get_stream_source_splits().await?;
+
+        let mut discovered_splits = HashMap::new();
+        let mut split_assignment = SplitAssignment::new();
+        for (source_id, actor_splits) in source_splits {
+            let splits = match discovered_splits.entry(source_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => match self.fetch_splits_for_source(source_id).await {
+                    Ok(splits) => entry.insert(splits),
+                    Err(e) => {
+                        log::warn!("failed to list splits of source {}: {}", source_id, e);
+                        continue;
+                    }
+                },
+            };
+            if let Some(changed) = Self::reassign_splits(&actor_splits, splits) {
+                split_assignment.extend(changed);
+            }
+        }
+
+        if split_assignment.is_empty() {
+            return Ok(());
+        }
+        // The splits newly assigned may have been read by other actors, which persist the offsets
+        // on the barriers before they lose the splits. Wait for a checkpoint barrier to commit
+        // those offsets, so that the actors newly assigned resume from them.
+        self.barrier_manager
+            .wait_for_next_barrier_to_collect()
+            .await?;
+        log::info!("reassign splits of source actors: {:?}", split_assignment);
+        self.barrier_manager
+            .run_command(Command::SourceSplitAssignment(split_assignment))
+            .await
+    }
+
+    /// Periodically discover the changes of the splits of the stream sources, e.g., new Kafka
+    /// partitions, and push them to the source actors.
     pub async fn run(&self) -> Result<()> {
-        // todo: in the future, split change will be pushed as a long running service
-        Ok(())
+        let mut ticker = tokio::time::interval(self.env.opts.split_discovery_interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.discover_splits().await {
+                log::warn!("failed to discover splits: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kafka_split(partition: i32) -> SplitImpl {
+        let split = format!(
+            r#"{{"topic":"t","partition":{},"start_offset":null,"stop_offset":null}}"#,
+            partition
+        );
+        SplitImpl::restore_from_bytes("kafka".to_string(), split.as_bytes()).unwrap()
+    }
+
+    fn kafka_splits(partitions: &[i32]) -> Vec<SplitImpl> {
+        partitions.iter().map(|&p| kafka_split(p)).collect()
+    }
+
+    fn split_ids(assignment: &SplitAssignment) -> BTreeMap<ActorId, Vec<String>> {
+        assignment
+            .iter()
+            .map(|(&actor_id, splits)| (actor_id, splits.iter().map(|s| s.id()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_reassign_splits() {
+        let reassign = SourceManager::<crate::storage::MemStore>::reassign_splits;
+
+        // New splits go to the actors with the fewest splits.
+        let actor_splits = BTreeMap::from([(1, kafka_splits(&[0])), (2, kafka_splits(&[1]))]);
+        let changed = reassign(&actor_splits, &kafka_splits(&[0, 1, 2, 3])).unwrap();
+        assert_eq!(
+            split_ids(&changed),
+            BTreeMap::from([
+                (1, vec!["0".to_string(), "2".to_string()]),
+                (2, vec!["1".to_string(), "3".to_string()]),
+            ])
+        );
+
+        // Nothing changes.
+        assert!(reassign(&actor_splits, &kafka_splits(&[1, 0])).is_none());
+
+        // The splits no longer discovered are removed, and only the changed actors are returned.
+        let actor_splits = BTreeMap::from([(1, kafka_splits(&[0, 1])), (2, kafka_splits(&[2]))]);
+        let changed = reassign(&actor_splits, &kafka_splits(&[0, 2])).unwrap();
+        assert_eq!(
+            split_ids(&changed),
+            BTreeMap::from([(1, vec!["0".to_string()])])
+        );
+
+        // The splits of the removed actors are reassigned.
+        let actor_splits = BTreeMap::from([(1, kafka_splits(&[0])), (3, vec![])]);
+        let changed = reassign(&actor_splits, &kafka_splits(&[0, 1, 2])).unwrap();
+        assert_eq!(
+            split_ids(&changed),
+            BTreeMap::from([
+                (3, vec!["1".to_string()]),
+                (1, vec!["0".to_string(), "2".to_string()])
+            ])
+        );
     }
 }
@@ -12,31 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Instant;
 
+use futures::future::BoxFuture;
 use itertools::Itertools;
 use log::{debug, info};
 use risingwave_common::catalog::TableId;
 use risingwave_common::error::ErrorCode::InternalError;
-use risingwave_common::error::{Result, ToRwResult};
-use risingwave_pb::common::{ActorInfo, WorkerType};
+use risingwave_common::error::{Result, RwError, ToRwResult};
+use risingwave_pb::common::{ActorInfo, ParallelUnit, ParallelUnitType, WorkerNode, WorkerType};
+use risingwave_pb::meta::list_jobs_response::Job;
 use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus};
-use risingwave_pb::stream_plan::stream_node::Node;
-use risingwave_pb::stream_plan::StreamSourceState;
+use risingwave_pb::stream_plan::ActorMapping;
 use risingwave_pb::stream_service::{
     BroadcastActorInfoTableRequest, BuildActorsRequest, HangingChannel, UpdateActorsRequest,
 };
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use super::ScheduledLocations;
 use crate::barrier::{BarrierManagerRef, Command};
-use crate::cluster::{ClusterManagerRef, WorkerId};
-use crate::manager::{MetaSrvEnv, StreamClientsRef};
-use crate::model::{ActorId, TableFragments};
+use crate::cluster::{ClusterManagerRef, WorkerId, WorkerLocations};
+use crate::manager::{
+    IdCategory, IdGeneratorManagerRef, LocalNotification, MetaSrvEnv, NotificationManagerRef,
+    SourceId, StreamClientsRef,
+};
+use crate::model::{ActorId, FragmentId, TableFragments};
 use crate::storage::MetaStore;
-use crate::stream::{FragmentManagerRef, Scheduler, SourceManagerRef};
+use crate::stream::{
+    build_added_actors, is_reschedulable, plan_reschedule, FragmentManagerRef, Scheduler,
+    SourceManagerRef,
+};
 
 pub type GlobalStreamManagerRef<S> = Arc<GlobalStreamManager<S>>;
 
@@ -70,6 +80,12 @@ pub struct GlobalStreamManager<S: MetaStore> {
 
     /// Clients to stream service on compute nodes
     clients: StreamClientsRef,
+
+    /// Generates ids of the actors added by rescheduling
+    id_gen_manager: IdGeneratorManagerRef<S>,
+
+    /// Serializes the rescheduling with the creation and dropping of materialized views
+    reschedule_lock: Mutex<()>,
 }
 
 impl<S> GlobalStreamManager<S>
@@ -90,20 +106,40 @@ where
             cluster_manager,
             clients: env.stream_clients_ref(),
             source_manager,
+            id_gen_manager: env.id_gen_manager_ref(),
+            reschedule_lock: Mutex::new(()),
         })
     }
 
+    /// Create materialized view and return when its creation is finished. Check
+    /// [`Self::start_create_materialized_view`] for details.
+    pub async fn create_materialized_view(
+        &self,
+        table_fragments: TableFragments,
+        ctx: CreateMaterializedViewContext,
+    ) -> Result<()> {
+        self.start_create_materialized_view(table_fragments, ctx)
+            .await?
+            .await
+    }
+
     /// Create materialized view, it works as follows:
     /// 1. schedule the actors to nodes in the cluster.
     /// 2. broadcast the actor info table.
     /// (optional) get the split information of the `StreamSource` via source manager and patch
     /// actors 3. notify related nodes to update and build the actors.
     /// 4. store related meta data.
-    pub async fn create_materialized_view(
+    ///
+    /// It returns once the actors are built and connected with the upstream, with a future
+    /// completed when the backfill is finished. The future fails if the creation is cancelled by
+    /// [`Self::cancel_materialized_view`] or aborted by the recovery.
+    pub async fn start_create_materialized_view(
         &self,
         mut table_fragments: TableFragments,
         ctx: CreateMaterializedViewContext,
-    ) -> Result<()> {
+    ) -> Result<BoxFuture<'static, Result<()>>> {
+        let _reschedule_guard = self.reschedule_lock.lock().await;
+
         let nodes = self
             .cluster_manager
             .list_worker_node(
@@ -139,7 +175,6 @@ where
             .collect();
 
         table_fragments.set_actor_status(actor_info);
-        let mut actor_map = table_fragments.actor_map();
 
         let mut source_actors_group_by_fragment = HashMap::new();
         for fragment in table_fragments.fragments() {
@@ -168,33 +203,11 @@ where
             .schedule_split_for_actors(source_actors_group_by_fragment)
             .await?;
 
-        // patch source actors with splits
-        for (actor_id, actor) in &mut actor_map {
-            if let Some(splits) = split_assignment.get(actor_id) {
-                let mut node = actor.nodes.as_mut().unwrap();
-                while !node.input.is_empty() {
-                    node = node.input.first_mut().unwrap();
-                }
-
-                if let Some(Node::SourceNode(s)) = node.node.as_mut() {
-                    log::debug!(
-                        "patching source node #{} with splits {:?}",
-                        actor_id,
-                        splits
-                    );
-
-                    if !splits.is_empty() {
-                        s.stream_source_state = Some(StreamSourceState {
-                            split_type: splits.first().unwrap().get_type(),
-                            stream_source_splits: splits
-                                .iter()
-                                .map(|split| split.to_string().unwrap().as_bytes().to_vec())
-                                .collect(),
-                        });
-                    }
-                }
-            }
-        }
+        // Patch source actors with splits. They are persisted with the table fragments, so that
+        // the actors are rebuilt with their splits in recovery.
+        log::debug!("patching source actors with splits {:?}", split_assignment);
+        table_fragments.update_actor_splits(&split_assignment);
+        let actor_map = table_fragments.actor_map();
 
         // Actors on each stream node will need to know where their upstream lies. `actor_info`
         // includes such information. It contains: 1. actors in the current create
@@ -334,19 +347,19 @@ where
             .start_create_table_fragments(table_fragments.clone())
             .await?;
         self.barrier_manager
-            .run_command(Command::CreateMaterializedView {
+            .start_command(Command::CreateMaterializedView {
                 table_fragments,
                 table_sink_map: ctx.table_sink_map,
                 dispatches,
             })
-            .await?;
-
-        Ok(())
+            .await
     }
 
     /// Dropping materialized view is done by barrier manager. Check
     /// [`Command::DropMaterializedView`] for details.
     pub async fn drop_materialized_view(&self, table_id: &TableId) -> Result<()> {
+        let _reschedule_guard = self.reschedule_lock.lock().await;
+
         self.barrier_manager
             .run_command(Command::DropMaterializedView(*table_id))
             .await?;
@@ -354,6 +367,493 @@ where
         Ok(())
     }
 
+    /// Cancels the creation of the materialized view, whose backfill is not finished yet. Its
+    /// actors are dropped, and the future returned by [`Self::start_create_materialized_view`]
+    /// fails.
+    pub async fn cancel_materialized_view(&self, table_id: &TableId) -> Result<()> {
+        if !self.barrier_manager.is_creating(*table_id).await {
+            return Err(InternalError(format!(
+                "materialized view {} is not being created",
+                table_id
+            ))
+            .into());
+        }
+
+        self.drop_materialized_view(table_id).await
+    }
+
+    /// Reschedule a hash-distributed fragment by removing `removed_actors` and adding an actor on
+    /// each of `added_parallel_units`, it works as follows:
+    /// 1. plan the new mapping from virtual nodes to the actors.
+    /// 2. broadcast the actor info table, notify related nodes to update and build the added
+    /// actors, and create hanging channels to connect them with the upstream and downstream
+    /// actors. 3. add the added actors to meta store with state: `State::Inactive`.
+    /// 4. run [`Command::RescheduleFragment`], which connects the added actors, hands over the
+    /// virtual nodes and stops the removed actors with a barrier.
+    pub async fn reschedule_fragment(
+        &self,
+        table_id: &TableId,
+        fragment_id: FragmentId,
+        removed_actors: Vec<ActorId>,
+        added_parallel_units: Vec<ParallelUnit>,
+    ) -> Result<()> {
+        let _reschedule_guard = self.reschedule_lock.lock().await;
+
+        // The actors of the materialized views being created in background are still reading the
+        // snapshots, which cannot be handed over.
+        if self.barrier_manager.is_creating(*table_id).await {
+            return Err(InternalError(format!(
+                "cannot reschedule materialized view {} being created",
+                table_id
+            ))
+            .into());
+        }
+
+        let node_locations: WorkerLocations = self
+            .cluster_manager
+            .list_worker_node(
+                WorkerType::ComputeNode,
+                Some(risingwave_pb::common::worker_node::State::Running),
+            )
+            .await
+            .into_iter()
+            .map(|node| (node.id, node))
+            .collect();
+        let get_node = |node_id: &WorkerId| {
+            node_locations.get(node_id).ok_or_else(|| {
+                RwError::from(InternalError(format!("node not exist: id={}", node_id)))
+            })
+        };
+
+        let table_fragments = self.fragment_manager.get_table_fragments(table_id).await?;
+        let start_actor_id = self
+            .id_gen_manager
+            .generate_interval::<{ IdCategory::Actor }>(added_parallel_units.len() as i32)
+            .await? as ActorId;
+        let added_actor_nodes: HashMap<ActorId, WorkerId> = added_parallel_units
+            .iter()
+            .enumerate()
+            .map(|(i, parallel_unit)| (start_actor_id + i as ActorId, parallel_unit.worker_node_id))
+            .collect();
+        let added_actors = added_actor_nodes
+            .iter()
+            .map(|(&actor_id, node_id)| {
+                Ok(ActorInfo {
+                    actor_id,
+                    host: get_node(node_id)?.host.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let reschedule =
+            plan_reschedule(&table_fragments, fragment_id, removed_actors, added_actors)?;
+        let actor_map = build_added_actors(
+            &table_fragments.fragments[&fragment_id],
+            &reschedule.added_actor_ids(),
+        )
+        .into_iter()
+        .map(|actor| (actor.actor_id, actor))
+        .collect::<HashMap<_, _>>();
+
+        // The added actors need to know where their upstream and downstream actors lie, and vice
+        // versa.
+        let mut actor_nodes = added_actor_nodes.clone();
+        for actor_id in reschedule
+            .upstream_actors
+            .iter()
+            .chain(reschedule.downstream_actors.iter())
+        {
+            let node_id = table_fragments.actor_status()[actor_id].node_id as WorkerId;
+            actor_nodes.insert(*actor_id, node_id);
+        }
+        let actor_infos_to_broadcast = actor_nodes
+            .iter()
+            .map(|(&actor_id, node_id)| {
+                Ok(ActorInfo {
+                    actor_id,
+                    host: get_node(node_id)?.host.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let actor_host_infos: HashMap<_, _> = actor_infos_to_broadcast
+            .iter()
+            .map(|info| (info.actor_id, info.clone()))
+            .collect();
+
+        // Remote upstream actors send to the added actors through the channels created on the
+        // upstream nodes, and the same for the remote downstream actors.
+        let mut node_hanging_channels: HashMap<WorkerId, Vec<HangingChannel>> = HashMap::new();
+        for (added_id, added_node_id) in &added_actor_nodes {
+            for up_id in &reschedule.upstream_actors {
+                let up_node_id = actor_nodes[up_id];
+                if up_node_id != *added_node_id {
+                    node_hanging_channels
+                        .entry(up_node_id)
+                        .or_default()
+                        .push(HangingChannel {
+                            upstream: Some(ActorInfo {
+                                actor_id: *up_id,
+                                host: None,
+                            }),
+                            downstream: Some(actor_host_infos[added_id].clone()),
+                        });
+                }
+            }
+            for down_id in &reschedule.downstream_actors {
+                let down_node_id = actor_nodes[down_id];
+                if down_node_id != *added_node_id {
+                    node_hanging_channels
+                        .entry(down_node_id)
+                        .or_default()
+                        .push(HangingChannel {
+                            upstream: Some(actor_host_infos[added_id].clone()),
+                            downstream: Some(ActorInfo {
+                                actor_id: *down_id,
+                                host: None,
+                            }),
+                        });
+                }
+            }
+        }
+
+        let mut node_actors: HashMap<WorkerId, Vec<ActorId>> = HashMap::new();
+        for (actor_id, node_id) in &added_actor_nodes {
+            node_actors.entry(*node_id).or_default().push(*actor_id);
+        }
+
+        // Same as creating materialized view, we send RPC requests in two stages. In the first
+        // stage, we broadcast actor info and initialize all channels.
+        for node_id in actor_nodes.values().unique() {
+            let node = get_node(node_id)?;
+            let client = self.clients.get(node).await?;
+
+            client
+                .to_owned()
+                .broadcast_actor_info_table(BroadcastActorInfoTableRequest {
+                    info: actor_infos_to_broadcast.clone(),
+                })
+                .await
+                .to_rw_result_with(|| format!("failed to connect to {}", node_id))?;
+
+            let actors = node_actors.get(node_id).cloned().unwrap_or_default();
+            let hanging_channels = node_hanging_channels.remove(node_id).unwrap_or_default();
+            if actors.is_empty() && hanging_channels.is_empty() {
+                continue;
+            }
+            let stream_actors = actors
+                .iter()
+                .map(|actor_id| actor_map.get(actor_id).cloned().unwrap())
+                .collect::<Vec<_>>();
+
+            let request_id = Uuid::new_v4().to_string();
+            tracing::debug!(request_id = request_id.as_str(), actors = ?actors, "update actors");
+            client
+                .to_owned()
+                .update_actors(UpdateActorsRequest {
+                    request_id,
+                    actors: stream_actors,
+                    hanging_channels,
+                })
+                .await
+                .to_rw_result_with(|| format!("failed to connect to {}", node_id))?;
+        }
+
+        // In the second stage, each [`WorkerNode`] builds the added actors and connects them with
+        // channels.
+        for (node_id, actors) in &node_actors {
+            let node = get_node(node_id)?;
+            let client = self.clients.get(node).await?;
+
+            let request_id = Uuid::new_v4().to_string();
+            tracing::debug!(request_id = request_id.as_str(), actors = ?actors, "build actors");
+            client
+                .to_owned()
+                .build_actors(BuildActorsRequest {
+                    request_id,
+                    actor_id: actors.clone(),
+                })
+                .await
+                .to_rw_result_with(|| format!("failed to connect to {}", node_id))?;
+        }
+
+        let actor_status = added_actor_nodes
+            .iter()
+            .map(|(&actor_id, &node_id)| {
+                (
+                    actor_id,
+                    ActorStatus {
+                        node_id,
+                        state: ActorState::Inactive as i32,
+                    },
+                )
+            })
+            .collect();
+        self.fragment_manager
+            .add_inactive_actors(
+                table_id,
+                fragment_id,
+                actor_map.into_values().collect(),
+                actor_status,
+            )
+            .await?;
+        self.barrier_manager
+            .run_command(Command::RescheduleFragment(reschedule))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Scale out the reschedulable fragments to a new compute node, by adding an actor on each of
+    /// its hash parallel units. Fragments already having actors on the node are skipped.
+    pub async fn scale_out(&self, worker_node: &WorkerNode) -> Result<()> {
+        let parallel_units = worker_node
+            .parallel_units
+            .iter()
+            .filter(|parallel_unit| parallel_unit.r#type == ParallelUnitType::Hash as i32)
+            .cloned()
+            .collect_vec();
+        if parallel_units.is_empty() {
+            return Ok(());
+        }
+
+        for table_fragments in self.fragment_manager.list_table_fragments().await? {
+            for fragment in table_fragments.fragments() {
+                let on_node = fragment.actors.iter().any(|actor| {
+                    table_fragments.actor_status()[&actor.actor_id].node_id == worker_node.id
+                });
+                if on_node || !is_reschedulable(&table_fragments, fragment) {
+                    continue;
+                }
+
+                info!(
+                    "scale out fragment {} of table {} to node {}",
+                    fragment.fragment_id,
+                    table_fragments.table_id(),
+                    worker_node.id
+                );
+                if let Err(err) = self
+                    .reschedule_fragment(
+                        &table_fragments.table_id(),
+                        fragment.fragment_id,
+                        vec![],
+                        parallel_units.clone(),
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to scale out fragment {}: {}",
+                        fragment.fragment_id,
+                        err
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the actors on a deleted compute node from meta store, so that the recovery of
+    /// barrier manager, which is triggered by the failure of the node, can rebuild the stream
+    /// graph without it. This is done without a barrier since the actors are not reachable. The
+    /// actors of reschedulable fragments are removed with their virtual nodes rebalanced to the
+    /// remaining actors, while the others are migrated to the remaining nodes.
+    ///
+    /// Note that `reschedule_lock` is not acquired here: the creation or dropping of materialized
+    /// views holding it may be waiting for the recovery, which in turn waits for this.
+    pub async fn scale_in(&self, worker_node: &WorkerNode) -> Result<()> {
+        let nodes = self
+            .cluster_manager
+            .list_worker_node(WorkerType::ComputeNode, None)
+            .await
+            .into_iter()
+            .map(|node| node.id)
+            .filter(|node_id| *node_id != worker_node.id)
+            .collect_vec();
+        let mut next_node = nodes.iter().cycle();
+
+        for table_fragments in self.fragment_manager.list_table_fragments().await? {
+            let table_id = table_fragments.table_id();
+            let mut migrations = HashMap::new();
+
+            for fragment in table_fragments.fragments() {
+                let actors_on_node = fragment
+                    .actors
+                    .iter()
+                    .map(|actor| actor.actor_id)
+                    .filter(|actor_id| {
+                        table_fragments.actor_status()[actor_id].node_id == worker_node.id
+                    })
+                    .collect_vec();
+                if actors_on_node.is_empty() {
+                    continue;
+                }
+
+                match plan_reschedule(
+                    &table_fragments,
+                    fragment.fragment_id,
+                    actors_on_node.clone(),
+                    vec![],
+                ) {
+                    Ok(reschedule) => {
+                        info!(
+                            "remove actors {:?} of fragment {} on deleted node {}",
+                            actors_on_node, fragment.fragment_id, worker_node.id
+                        );
+                        self.fragment_manager
+                            .apply_reschedule(
+                                &table_id,
+                                &reschedule.upstream_actors,
+                                &reschedule.downstream_actors,
+                                &reschedule.removed_actors,
+                                &[],
+                                ActorMapping {
+                                    hash_mapping: reschedule.vnode_mapping,
+                                },
+                            )
+                            .await?;
+                    }
+                    Err(_) => {
+                        for actor_id in actors_on_node {
+                            let node_id = next_node.next().ok_or_else(|| {
+                                RwError::from(InternalError("no available node exist".to_string()))
+                            })?;
+                            migrations.insert(actor_id, *node_id);
+                        }
+                    }
+                }
+            }
+
+            if !migrations.is_empty() {
+                info!(
+                    "migrate actors of table {} on deleted node {}: {:?}",
+                    table_id, worker_node.id, migrations
+                );
+                self.fragment_manager
+                    .migrate_actors(&table_id, &migrations)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start a task to reschedule the materialized views on cluster membership change. See
+    /// [`Self::scale_out`] and [`Self::scale_in`] for details.
+    pub async fn start_rescheduler(
+        stream_manager: GlobalStreamManagerRef<S>,
+        notification_manager: NotificationManagerRef,
+    ) -> (JoinHandle<()>, UnboundedSender<()>) {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        notification_manager.insert_local_sender(tx).await;
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::unbounded_channel();
+        let join_handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    notification = rx.recv() => {
+                        let result = match notification {
+                            None => {
+                                return;
+                            }
+                            Some(LocalNotification::WorkerActivation(worker_node)) => {
+                                stream_manager.scale_out(&worker_node).await
+                            }
+                            Some(LocalNotification::WorkerDeletion(worker_node)) => {
+                                stream_manager.scale_in(&worker_node).await
+                            }
+                        };
+                        if let Err(err) = result {
+                            tracing::warn!("Failed to reschedule on membership change: {}", err);
+                        }
+                    }
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!("Rescheduler is shutting down");
+                        return;
+                    }
+                }
+            }
+        });
+
+        (join_handle, shutdown_tx)
+    }
+
+    /// Lists the materialized views being created, with the progress of their backfill.
+    pub async fn list_jobs(&self) -> Vec<Job> {
+        self.barrier_manager.list_jobs().await
+    }
+
+    /// Pauses the actors reading the given stream sources, or all stream sources read by
+    /// materialized views if `source_ids` is empty. Barriers keep flowing through the paused
+    /// actors, so checkpoints are not blocked. The actors created for the paused sources later,
+    /// e.g. by creating a materialized view on them or by scaling out, start paused as well.
+    pub async fn pause_sources(&self, source_ids: Vec<SourceId>) -> Result<()> {
+        let source_ids = if source_ids.is_empty() {
+            self.fragment_manager.get_stream_source_ids().await
+        } else {
+            source_ids.into_iter().collect()
+        };
+        if source_ids.is_empty() {
+            return Ok(());
+        }
+        info!("pause sources {:?}", source_ids);
+        self.barrier_manager
+            .run_command(Command::PauseSources(source_ids))
+            .await
+    }
+
+    /// Resumes the sources paused by [`Self::pause_sources`], or all paused sources if
+    /// `source_ids` is empty.
+    pub async fn resume_sources(&self, source_ids: Vec<SourceId>) -> Result<()> {
+        info!("resume sources {:?}", source_ids);
+        self.barrier_manager
+            .run_command(Command::ResumeSources(source_ids.into_iter().collect()))
+            .await
+    }
+
+    async fn resolve_stream_source_actors(&self, source_ids: Vec<SourceId>) -> Vec<ActorId> {
+        let source_ids = source_ids.into_iter().collect::<HashSet<_>>();
+        self.fragment_manager
+            .get_stream_source_actor_ids(&source_ids)
+            .await
+    }
+
+    /// Changes the rate limit of the actors reading the given stream source, where 0 means
+    /// unlimited. The rate limit is applied to each actor separately.
+    pub async fn throttle_source(&self, source_id: SourceId, rate_limit: u32) -> Result<()> {
+        let actors = self.resolve_stream_source_actors(vec![source_id]).await;
+        self.throttle_actors(actors, rate_limit).await
+    }
+
+    /// Changes the rate limit of the snapshot read by the backfill of the materialized view, where
+    /// 0 means unlimited.
+    ///
+    /// Note that only the backfill created with a rate limit can be changed while it's running,
+    /// as the others block the barriers until they finish.
+    pub async fn throttle_materialized_view(
+        &self,
+        table_id: &TableId,
+        rate_limit: u32,
+    ) -> Result<()> {
+        let actors = self
+            .fragment_manager
+            .get_table_chain_actor_ids(table_id)
+            .await?;
+        self.throttle_actors(actors, rate_limit).await
+    }
+
+    async fn throttle_actors(&self, actors: Vec<ActorId>, rate_limit: u32) -> Result<()> {
+        if actors.is_empty() {
+            return Ok(());
+        }
+        info!("throttle actors {:?} to {} rows/s", actors, rate_limit);
+        let actor_rate_limits = actors
+            .into_iter()
+            .map(|actor_id| (actor_id, rate_limit))
+            .collect();
+        self.barrier_manager
+            .run_command(Command::Throttle(actor_rate_limits))
+            .await
+    }
+
     /// Flush means waiting for the next barrier to collect.
     pub async fn flush(&self) -> Result<()> {
         let start = Instant::now();
@@ -575,6 +1075,7 @@ mod tests {
                     cluster_manager.clone(),
                     barrier_manager.clone(),
                     catalog_manager.clone(),
+                    fragment_manager.clone(),
                 )
                 .await?,
             );
@@ -124,6 +124,9 @@ fn make_stream_node() -> StreamNode {
             column_ids: vec![1, 2, 0],
             source_type: SourceType::Table as i32,
             stream_source_state: None,
+            pruned_field_ids: vec![],
+            watermark_descs: vec![],
+            rate_limit: 0,
         })),
         pk_indices: vec![2],
         ..Default::default()
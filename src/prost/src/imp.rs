@@ -104,6 +104,14 @@ impl crate::plan::ColumnDesc {
             column_type: Some(DataType {
                 type_name: TypeName::Struct as i32,
                 is_nullable: true,
+                field_type: fields
+                    .iter()
+                    .map(|f| f.column_type.clone().unwrap_or_default())
+                    .collect(),
+                field_names: fields
+                    .iter()
+                    .map(|f| f.name.rsplit('.').next().unwrap_or_default().to_string())
+                    .collect(),
                 ..Default::default()
             }),
             column_id,
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use std::time::Duration;
 
@@ -21,13 +22,14 @@ use risingwave_common::array::DataChunk;
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, ToRwResult};
 use risingwave_common::util::addr::HostAddr;
+use risingwave_common::util::exchange::decode_batch;
 use risingwave_pb::plan::exchange_info::DistributionMode;
 use risingwave_pb::plan::{ExchangeInfo, PlanFragment, PlanNode, TaskId, TaskOutputId};
 use risingwave_pb::task_service::exchange_service_client::ExchangeServiceClient;
 use risingwave_pb::task_service::task_service_client::TaskServiceClient;
 use risingwave_pb::task_service::{
-    CreateTaskRequest, CreateTaskResponse, GetDataRequest, GetDataResponse, GetStreamRequest,
-    GetStreamResponse,
+    CreateTaskRequest, CreateTaskResponse, DataChunkBatch, ExchangeOptions, GetDataRequest,
+    GetDataResponse, GetStreamRequest, GetStreamResponse,
 };
 use tonic::transport::{Channel, Endpoint};
 use tonic::Streaming;
@@ -56,21 +58,31 @@ impl ComputeClient {
         })
     }
 
-    pub async fn get_data(&self, output_id: TaskOutputId) -> Result<GrpcExchangeSource> {
-        let stream = self.get_data_inner(output_id.clone()).await?;
+    pub async fn get_data(
+        &self,
+        output_id: TaskOutputId,
+        options: ExchangeOptions,
+    ) -> Result<GrpcExchangeSource> {
+        let stream = self.get_data_inner(output_id.clone(), options).await?;
         Ok(GrpcExchangeSource {
             stream,
             task_id: output_id.get_task_id().unwrap().clone(),
             output_id,
+            buffered: VecDeque::new(),
         })
     }
 
-    async fn get_data_inner(&self, output_id: TaskOutputId) -> Result<Streaming<GetDataResponse>> {
+    async fn get_data_inner(
+        &self,
+        output_id: TaskOutputId,
+        options: ExchangeOptions,
+    ) -> Result<Streaming<GetDataResponse>> {
         Ok(self
             .exchange_client
             .to_owned()
             .get_data(GetDataRequest {
                 task_output_id: Some(output_id.clone()),
+                options: Some(options),
             })
             .await
             .to_rw_result()?
@@ -81,6 +93,7 @@ impl ComputeClient {
         &self,
         up_fragment_id: u32,
         down_fragment_id: u32,
+        options: ExchangeOptions,
     ) -> Result<Streaming<GetStreamResponse>> {
         Ok(self
             .exchange_client
@@ -88,6 +101,7 @@ impl ComputeClient {
             .get_stream(GetStreamRequest {
                 up_fragment_id,
                 down_fragment_id,
+                options: Some(options),
             })
             .await
             .to_rw_result_with(|| {
@@ -157,12 +171,19 @@ pub struct GrpcExchangeSource {
 
     output_id: TaskOutputId,
     task_id: TaskId,
+
+    /// The remaining chunks of the last coalesced batch.
+    buffered: VecDeque<DataChunk>,
 }
 
 impl GrpcExchangeSource {
-    pub async fn create(addr: HostAddr, output_id: TaskOutputId) -> Result<Self> {
+    pub async fn create(
+        addr: HostAddr,
+        output_id: TaskOutputId,
+        options: ExchangeOptions,
+    ) -> Result<Self> {
         let client = ComputeClient::new(addr).await?;
-        client.get_data(output_id).await
+        client.get_data(output_id, options).await
     }
 }
 
@@ -177,12 +198,27 @@ impl Debug for GrpcExchangeSource {
 #[async_trait::async_trait]
 impl ExchangeSource for GrpcExchangeSource {
     async fn take_data(&mut self) -> Result<Option<DataChunk>> {
-        let res = match self.stream.next().await {
-            None => return Ok(None),
-            Some(r) => r,
+        let data = loop {
+            if let Some(data) = self.buffered.pop_front() {
+                break data;
+            }
+            let res = match self.stream.next().await {
+                None => return Ok(None),
+                Some(r) => r,
+            };
+            let task_data = res.to_rw_result()?;
+            match task_data.record_batch.as_ref() {
+                Some(record_batch) => break DataChunk::from_protobuf(record_batch)?.compact()?,
+                None => {
+                    let batch: DataChunkBatch =
+                        decode_batch(task_data.compression(), &task_data.batch)?;
+                    for chunk in &batch.chunks {
+                        self.buffered
+                            .push_back(DataChunk::from_protobuf(chunk)?.compact()?);
+                    }
+                }
+            }
         };
-        let task_data = res.to_rw_result()?;
-        let data = DataChunk::from_protobuf(task_data.get_record_batch()?)?.compact()?;
 
         trace!(
             "Receiver task: {:?}, output = {:?}, data = {:?}",
@@ -26,8 +26,10 @@ use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Source as ProstSource, Table as ProstTable,
 };
 use risingwave_pb::common::{WorkerNode, WorkerType};
+use risingwave_pb::ddl_service::alter_rate_limit_request::Relation;
 use risingwave_pb::ddl_service::ddl_service_client::DdlServiceClient;
 use risingwave_pb::ddl_service::{
+    AlterRateLimitRequest, AlterRateLimitResponse, CancelJobRequest, CancelJobResponse,
     CreateDatabaseRequest, CreateDatabaseResponse, CreateMaterializedSourceRequest,
     CreateMaterializedSourceResponse, CreateMaterializedViewRequest,
     CreateMaterializedViewResponse, CreateSchemaRequest, CreateSchemaResponse, CreateSourceRequest,
@@ -48,12 +50,14 @@ use risingwave_pb::hummock::{
 use risingwave_pb::meta::catalog_service_client::CatalogServiceClient;
 use risingwave_pb::meta::cluster_service_client::ClusterServiceClient;
 use risingwave_pb::meta::heartbeat_service_client::HeartbeatServiceClient;
+use risingwave_pb::meta::list_jobs_response::Job;
 use risingwave_pb::meta::notification_service_client::NotificationServiceClient;
 use risingwave_pb::meta::stream_manager_service_client::StreamManagerServiceClient;
 use risingwave_pb::meta::{
     ActivateWorkerNodeRequest, ActivateWorkerNodeResponse, AddWorkerNodeRequest,
     AddWorkerNodeResponse, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse, FlushRequest,
     FlushResponse, HeartbeatRequest, HeartbeatResponse, ListAllNodesRequest, ListAllNodesResponse,
+    ListJobsRequest, ListJobsResponse, PauseRequest, PauseResponse, ResumeRequest, ResumeResponse,
     SubscribeRequest, SubscribeResponse,
 };
 use risingwave_pb::stream_plan::StreamNode;
@@ -156,10 +160,12 @@ impl MetaClient {
         &self,
         table: ProstTable,
         plan: StreamNode,
+        background: bool,
     ) -> Result<(TableId, CatalogVersion)> {
         let request = CreateMaterializedViewRequest {
             materialized_view: Some(table),
             stream_node: Some(plan),
+            background,
         };
         let resp = self.inner.create_materialized_view(request).await?;
         // TODO: handle error in `resp.status` here
@@ -175,6 +181,13 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    /// Cancels the creation of a materialized view running in background.
+    pub async fn cancel_job(&self, table_id: u32) -> Result<()> {
+        let request = CancelJobRequest { table_id };
+        self.inner.cancel_job(request).await?;
+        Ok(())
+    }
+
     pub async fn create_source(&self, source: ProstSource) -> Result<(u32, CatalogVersion)> {
         let request = CreateSourceRequest {
             source: Some(source),
@@ -220,6 +233,21 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    /// Sets the rate limit of a source, or of the backfill of a materialized view. `0` removes
+    /// the limit. The returned catalog version is `0` if the catalog is left unchanged.
+    pub async fn alter_rate_limit(
+        &self,
+        relation: Relation,
+        rate_limit: u32,
+    ) -> Result<CatalogVersion> {
+        let request = AlterRateLimitRequest {
+            relation: Some(relation),
+            rate_limit,
+        };
+        let resp = self.inner.alter_rate_limit(request).await?;
+        Ok(resp.version)
+    }
+
     /// Unregister the current node to the cluster.
     pub async fn unregister(&self, addr: HostAddr) -> Result<()> {
         let request = DeleteWorkerNodeRequest {
@@ -289,6 +317,27 @@ impl MetaClient {
         self.inner.flush(request).await?;
         Ok(())
     }
+
+    /// Pauses the given stream sources, or all stream sources if `source_ids` is empty.
+    pub async fn pause(&self, source_ids: Vec<u32>) -> Result<()> {
+        let request = PauseRequest { source_ids };
+        self.inner.pause(request).await?;
+        Ok(())
+    }
+
+    /// Resumes the given stream sources, or all stream sources if `source_ids` is empty.
+    pub async fn resume(&self, source_ids: Vec<u32>) -> Result<()> {
+        let request = ResumeRequest { source_ids };
+        self.inner.resume(request).await?;
+        Ok(())
+    }
+
+    /// Lists the materialized views being created, with the progress of their backfill.
+    pub async fn list_jobs(&self) -> Result<Vec<Job>> {
+        let request = ListJobsRequest {};
+        let resp = self.inner.list_jobs(request).await?;
+        Ok(resp.jobs)
+    }
 }
 
 #[async_trait]
@@ -457,6 +506,9 @@ macro_rules! for_all_meta_rpc {
             ,{ cluster_client, list_all_nodes, ListAllNodesRequest, ListAllNodesResponse }
             ,{ heartbeat_client, heartbeat, HeartbeatRequest, HeartbeatResponse }
             ,{ stream_client, flush, FlushRequest, FlushResponse }
+            ,{ stream_client, pause, PauseRequest, PauseResponse }
+            ,{ stream_client, resume, ResumeRequest, ResumeResponse }
+            ,{ stream_client, list_jobs, ListJobsRequest, ListJobsResponse }
             ,{ ddl_client, create_materialized_source, CreateMaterializedSourceRequest, CreateMaterializedSourceResponse }
             ,{ ddl_client, create_materialized_view, CreateMaterializedViewRequest, CreateMaterializedViewResponse }
             ,{ ddl_client, create_source, CreateSourceRequest, CreateSourceResponse }
@@ -464,7 +516,9 @@ macro_rules! for_all_meta_rpc {
             ,{ ddl_client, create_database, CreateDatabaseRequest, CreateDatabaseResponse }
             ,{ ddl_client, drop_materialized_source, DropMaterializedSourceRequest, DropMaterializedSourceResponse }
             ,{ ddl_client, drop_materialized_view, DropMaterializedViewRequest, DropMaterializedViewResponse }
+            ,{ ddl_client, cancel_job, CancelJobRequest, CancelJobResponse }
             ,{ ddl_client, drop_source, DropSourceRequest, DropSourceResponse }
+            ,{ ddl_client, alter_rate_limit, AlterRateLimitRequest, AlterRateLimitResponse }
             ,{ hummock_client, pin_version, PinVersionRequest, PinVersionResponse }
             ,{ hummock_client, unpin_version, UnpinVersionRequest, UnpinVersionResponse }
             ,{ hummock_client, pin_snapshot, PinSnapshotRequest, PinSnapshotResponse }
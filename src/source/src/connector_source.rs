@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::marker::Send;
 use std::sync::Arc;
@@ -19,10 +20,10 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use lazy_static::__Deref;
 use risingwave_common::array::StreamChunk;
-use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::ErrorCode::{InternalError, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_connector::base::SourceReader;
-use risingwave_connector::state;
+use risingwave_connector::{new_connector, state, SplitImpl};
 use risingwave_storage::StateStore;
 use tokio::sync::Mutex;
 
@@ -37,6 +38,8 @@ pub struct ConnectorSource {
     pub parser: Arc<dyn SourceParser + Send + Sync>,
     pub reader: Arc<Mutex<Box<dyn SourceReader + Send + Sync>>>,
     pub column_descs: Vec<SourceColumnDesc>,
+    /// The properties of the connector, used to build new readers.
+    pub properties: HashMap<String, String>,
 }
 
 impl SourceChunkBuilder for ConnectorSource {}
@@ -52,15 +55,48 @@ impl ConnectorSource {
         parser: Arc<dyn SourceParser + Send + Sync>,
         reader: Arc<Mutex<Box<dyn SourceReader + Send + Sync>>>,
         column_descs: Vec<SourceColumnDesc>,
+        properties: HashMap<String, String>,
     ) -> Self {
         Self {
             parser,
             reader,
             column_descs,
+            properties,
         }
     }
 
+    /// Builds a source with a new reader of its own, which reads the given splits only.
+    pub async fn with_splits(&self, splits: Vec<SplitImpl>) -> Result<Self> {
+        let mut reader = new_connector(self.properties.clone(), None)
+            .await
+            .map_err(|e| RwError::from(InternalError(e.to_string())))?;
+        reader
+            .assign_splits(splits)
+            .await
+            .map_err(|e| RwError::from(InternalError(e.to_string())))?;
+
+        Ok(Self {
+            reader: Arc::new(Mutex::new(reader)),
+            ..self.clone()
+        })
+    }
+
+    /// Replaces the splits read by the reader.
+    pub async fn assign_splits(&mut self, splits: Vec<SplitImpl>) -> Result<()> {
+        self.reader
+            .lock()
+            .await
+            .assign_splits(splits)
+            .await
+            .map_err(|e| RwError::from(InternalError(e.to_string())))
+    }
+
     pub async fn next(&mut self) -> Result<StreamChunk> {
+        self.next_with_offsets().await.map(|(chunk, _)| chunk)
+    }
+
+    /// Reads the next chunk, along with the offset of the last message read from each split.
+    pub async fn next_with_offsets(&mut self) -> Result<(StreamChunk, HashMap<String, String>)> {
         let payload = self
             .reader
             .lock()
@@ -70,13 +106,15 @@ impl ConnectorSource {
             .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
 
         match payload {
-            None => Ok(StreamChunk::default()),
+            None => Ok((StreamChunk::default(), HashMap::new())),
             Some(batch) => {
                 let mut events = Vec::with_capacity(batch.len());
+                let mut split_offsets = HashMap::new();
                 for msg in batch {
                     if let Some(content) = msg.payload {
                         events.push(self.parser.parse(content.deref(), &self.column_descs)?);
                     }
+                    split_offsets.insert(msg.split_id, msg.offset);
                 }
 
                 let mut ops = Vec::with_capacity(events.iter().map(|e| e.ops.len()).sum());
@@ -86,11 +124,12 @@ impl ConnectorSource {
                     rows.extend(event.rows);
                     ops.extend(event.ops);
                 }
-                Ok(StreamChunk::new(
+                let chunk = StreamChunk::new(
                     ops,
                     Self::build_columns(&self.column_descs, rows.as_ref())?,
                     None,
-                ))
+                );
+                Ok((chunk, split_offsets))
             }
         }
     }
@@ -100,6 +139,9 @@ impl ConnectorSource {
 pub struct ConnectorStreamSource<S: StateStore> {
     pub source_reader: ConnectorSource,
     pub state_store: state::SourceStateHandler<S>,
+    /// The offsets of the last messages read from the splits, since the last
+    /// [`StreamSourceReader::take_split_offsets`].
+    pub split_offsets: HashMap<String, String>,
 }
 
 #[async_trait]
@@ -109,6 +151,16 @@ impl<S: StateStore> StreamSourceReader for ConnectorStreamSource<S> {
     }
 
     async fn next(&mut self) -> Result<StreamChunk> {
-        self.source_reader.next().await
+        let (chunk, split_offsets) = self.source_reader.next_with_offsets().await?;
+        self.split_offsets.extend(split_offsets);
+        Ok(chunk)
+    }
+
+    async fn assign_splits(&mut self, splits: Vec<SplitImpl>) -> Result<()> {
+        self.source_reader.assign_splits(splits).await
+    }
+
+    fn take_split_offsets(&mut self) -> HashMap<String, String> {
+        std::mem::take(&mut self.split_offsets)
     }
 }
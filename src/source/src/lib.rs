@@ -31,6 +31,7 @@
 
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use connector_source::ConnectorSource;
@@ -40,7 +41,9 @@ pub use manager::*;
 pub use parser::*;
 use risingwave_common::array::{DataChunk, StreamChunk};
 use risingwave_common::catalog::ColumnId;
-use risingwave_common::error::Result;
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_connector::SplitImpl;
 pub use table_v2::*;
 
 pub mod parser;
@@ -76,6 +79,34 @@ pub enum SourceImpl {
     Connector(ConnectorSource),
 }
 
+impl SourceImpl {
+    /// Returns a copy of the source whose parser skips the nested struct fields with the given
+    /// column ids, or `None` if the source does not parse its input.
+    pub fn with_pruned_fields(&self, pruned_field_ids: &[ColumnId]) -> Option<SourceImpl> {
+        let prune = |columns: &mut [SourceColumnDesc]| {
+            columns
+                .iter_mut()
+                .for_each(|c| c.prune_fields(pruned_field_ids))
+        };
+        match self {
+            SourceImpl::HighLevelKafka(s) => {
+                let mut columns = s.column_descs.as_ref().clone();
+                prune(&mut columns);
+                Some(SourceImpl::HighLevelKafka(HighLevelKafkaSource {
+                    column_descs: Arc::new(columns),
+                    ..s.clone()
+                }))
+            }
+            SourceImpl::Connector(s) => {
+                let mut s = s.clone();
+                prune(&mut s.column_descs);
+                Some(SourceImpl::Connector(s))
+            }
+            SourceImpl::TableV2(_) => None,
+        }
+    }
+}
+
 #[async_trait]
 pub trait Source: Send + Sync + 'static {
     type ReaderContext;
@@ -117,4 +148,17 @@ pub trait StreamSourceReader: Send + Sync + 'static {
     /// `next` always returns a StreamChunk. If the queue is empty, it will
     /// block until new data coming
     async fn next(&mut self) -> Result<StreamChunk>;
+
+    /// `assign_splits` replaces the splits to read. Only connector sources are split.
+    async fn assign_splits(&mut self, _splits: Vec<SplitImpl>) -> Result<()> {
+        Err(RwError::from(InternalError(
+            "split assignment is not supported by this source".to_string(),
+        )))
+    }
+
+    /// `take_split_offsets` returns the offsets of the last messages read from each split since
+    /// the last call, keyed by split id.
+    fn take_split_offsets(&mut self) -> HashMap<String, String> {
+        HashMap::new()
+    }
 }
@@ -75,6 +75,8 @@ pub struct SourceColumnDesc {
     pub data_type: DataType,
     pub column_id: ColumnId,
     pub skip_parse: bool,
+    /// The nested fields of a struct column.
+    pub fields: Vec<SourceColumnDesc>,
 }
 
 impl From<&ColumnDesc> for SourceColumnDesc {
@@ -84,6 +86,20 @@ impl From<&ColumnDesc> for SourceColumnDesc {
             data_type: c.data_type.clone(),
             column_id: c.column_id,
             skip_parse: false,
+            fields: c.field_descs.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl SourceColumnDesc {
+    /// Marks the nested fields with the given column ids to be skipped by the parser.
+    pub fn prune_fields(&mut self, pruned_field_ids: &[ColumnId]) {
+        for field in &mut self.fields {
+            if pruned_field_ids.contains(&field.column_id) {
+                field.skip_parse = true;
+            } else {
+                field.prune_fields(pruned_field_ids);
+            }
         }
     }
 }
@@ -144,6 +160,7 @@ impl SourceManager for MemSourceManager {
                     parser: parser.clone(),
                     reader: split_reader,
                     column_descs: columns.clone(),
+                    properties: config.clone(),
                 })
             }
         };
@@ -187,12 +204,10 @@ impl SourceManager for MemSourceManager {
             .iter()
             .enumerate()
             .map(|(idx, c)| {
-                let c = c.column_desc.as_ref().unwrap().clone();
+                let c = ColumnDesc::from(c.column_desc.as_ref().unwrap());
                 SourceColumnDesc {
-                    name: c.name.clone(),
-                    data_type: DataType::from(&c.column_type.unwrap()),
-                    column_id: ColumnId::from(c.column_id),
                     skip_parse: idx as i32 == info.row_id_index,
+                    ..(&c).into()
                 }
             })
             .collect::<Vec<SourceColumnDesc>>();
@@ -230,6 +245,7 @@ impl SourceManager for MemSourceManager {
                         parser: parser.clone(),
                         reader: split_reader,
                         column_descs: columns.clone(),
+                        properties: config,
                     })
                 }
             };
@@ -396,6 +412,7 @@ mod tests {
                 data_type: c.data_type.clone(),
                 column_id: c.column_id,
                 skip_parse: false,
+                fields: vec![],
             })
             .collect();
 
@@ -442,6 +459,8 @@ mod tests {
             row_id_index: 0,
             pk_column_ids: vec![0],
             columns,
+            watermark_descs: vec![],
+            rate_limit: 0,
         };
         let source_id = TableId::default();
 
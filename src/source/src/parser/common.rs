@@ -14,6 +14,7 @@
 
 use chrono::NaiveDate;
 use num_traits::FromPrimitive;
+use risingwave_common::array::JsonbVal;
 use risingwave_common::error::ErrorCode::{self, InternalError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{DataType, Decimal, NaiveDateWrapper, ScalarImpl, ScalarRef};
@@ -95,6 +96,9 @@ pub(crate) fn json_parse_value(
                 Err(e) => Err(e),
             },
         },
+        DataType::Jsonb => {
+            make_ScalarImpl!(value.cloned(), |v| ScalarImpl::Jsonb(JsonbVal::new(v)))
+        }
         _ => Err(ErrorCode::NotImplemented(
             "unsupported type for json_parse_value".to_string(),
             None.into(),
@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use protobuf::descriptor::FileDescriptorSet;
 use protobuf::RepeatedField;
-use risingwave_common::array::Op;
+use risingwave_common::array::{Op, StructValue};
 use risingwave_common::error::ErrorCode::{self, InternalError, ItemNotFound, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{DataType, Datum, Decimal, OrderedF32, OrderedF64, ScalarImpl};
@@ -211,7 +212,8 @@ fn protobuf_type_mapping(f: &FieldDescriptor, descriptors: &Descriptors) -> Resu
                 .iter()
                 .map(|f| protobuf_type_mapping(f, descriptors))
                 .collect::<Result<Vec<_>>>()?;
-            DataType::Struct { fields: vec.into() }
+            let names = m.fields().iter().map(|f| f.name().to_string()).collect();
+            DataType::new_struct(vec, names)
         }
         actual_type => {
             return Err(ErrorCode::NotImplemented(
@@ -224,6 +226,76 @@ fn protobuf_type_mapping(f: &FieldDescriptor, descriptors: &Descriptors) -> Resu
     Ok(t)
 }
 
+/// Takes the value of `column` out of a decoded message and converts it into a datum. Nested
+/// messages are converted into structs recursively, where fields marked with `skip_parse` are left
+/// as nulls.
+fn take_field_datum(map: &mut BTreeMap<Value, Value>, column: &SourceColumnDesc) -> Datum {
+    if column.skip_parse {
+        return None;
+    }
+
+    // Nested fields are named with their full path, e.g. `country.city`.
+    let key = Value::String(column.name.rsplit('.').next().unwrap().to_string());
+
+    // Use `remove` instead of `get` to take the ownership of the value
+    let value = map.remove(&key);
+    match column.data_type {
+        DataType::Boolean => {
+            protobuf_match_type!(value, ScalarImpl::Bool, { Bool }, bool)
+        }
+        DataType::Int16 => {
+            protobuf_match_type!(value, ScalarImpl::Int16, { I8, I16, U8 }, i16)
+        }
+        DataType::Int32 => {
+            protobuf_match_type!(value, ScalarImpl::Int32, { I8, I16, I32, U8, U16 }, i32)
+        }
+        DataType::Int64 => {
+            protobuf_match_type!(value, ScalarImpl::Int64, { I8, I16, I32, I64, U8, U16, U32 }, i64)
+        }
+        DataType::Float32 => {
+            protobuf_match_type!(value, ScalarImpl::Float32, { I8, I16, U8, U16, F32 }, OrderedF32)
+        }
+        DataType::Float64 => {
+            protobuf_match_type!(value, ScalarImpl::Float64, { I8, I16, I32, U8, U16, U32, F32, F64}, OrderedF64)
+        }
+        DataType::Decimal => {
+            protobuf_match_type!(value, ScalarImpl::Decimal, { I8, I16, I32, I64, U8, U16, U32, U64}, Decimal)
+        }
+        DataType::Varchar => {
+            protobuf_match_type!(value, ScalarImpl::Utf8, { String }, String)
+        }
+        DataType::Date => value
+            .and_then(|v| match v {
+                Value::String(b) => str_to_date(&b).ok(),
+                Value::Option(Some(boxed_value)) => match *boxed_value {
+                    Value::String(b) => str_to_date(&b).ok(),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .map(ScalarImpl::NaiveDate),
+        DataType::Struct { .. } => {
+            let map = value.and_then(|v| match v {
+                Value::Map(m) => Some(m),
+                Value::Option(Some(boxed_value)) => match *boxed_value {
+                    Value::Map(m) => Some(m),
+                    _ => None,
+                },
+                _ => None,
+            });
+            map.map(|mut m| {
+                let fields = column
+                    .fields
+                    .iter()
+                    .map(|field| take_field_datum(&mut m, field))
+                    .collect();
+                ScalarImpl::Struct(StructValue::new(fields))
+            })
+        }
+        _ => unimplemented!(),
+    }
+}
+
 impl SourceParser for ProtobufParser {
     fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event> {
         let mut map = match self.decode(payload)? {
@@ -231,53 +303,10 @@ impl SourceParser for ProtobufParser {
             _ => return Err(RwError::from(ProtocolError("".to_string()))),
         };
 
-        let row = columns.iter().map(|column| {
-            if column.skip_parse {
-                return None;
-            }
-
-            let key = Value::String(column.name.clone());
-
-            // Use `remove` instead of `get` to take the ownership of the value
-            let value = map.remove(&key);
-            match column.data_type {
-                DataType::Boolean => {
-                    protobuf_match_type!(value, ScalarImpl::Bool, { Bool }, bool)
-                }
-                DataType::Int16 => {
-                    protobuf_match_type!(value, ScalarImpl::Int16, { I8, I16, U8 }, i16)
-                }
-                DataType::Int32 => {
-                    protobuf_match_type!(value, ScalarImpl::Int32, { I8, I16, I32, U8, U16 }, i32)
-                }
-                DataType::Int64 => {
-                    protobuf_match_type!(value, ScalarImpl::Int64, { I8, I16, I32, I64, U8, U16, U32 }, i64)
-                }
-                DataType::Float32 => {
-                    protobuf_match_type!(value, ScalarImpl::Float32, { I8, I16, U8, U16, F32 }, OrderedF32)
-                }
-                DataType::Float64 => {
-                    protobuf_match_type!(value, ScalarImpl::Float64, { I8, I16, I32, U8, U16, U32, F32, F64}, OrderedF64)
-                }
-                DataType::Decimal => {
-                    protobuf_match_type!(value, ScalarImpl::Decimal, { I8, I16, I32, I64, U8, U16, U32, U64}, Decimal)
-                }
-                DataType::Varchar => {
-                    protobuf_match_type!(value, ScalarImpl::Utf8, { String }, String)
-                }
-                DataType::Date => {
-                    value.and_then(|v| match v {
-                        Value::String(b) => str_to_date(&b).ok(),
-                        Value::Option(Some(boxed_value)) => match *boxed_value {
-                            Value::String(b) => str_to_date(&b).ok(),
-                            _ => None,
-                        }
-                        _ => None,
-                    }).map(ScalarImpl::NaiveDate)
-                }
-                _ => unimplemented!(),
-            }
-        }).collect::<Vec<Datum>>();
+        let row = columns
+            .iter()
+            .map(|column| take_field_datum(&mut map, column))
+            .collect::<Vec<Datum>>();
 
         Ok(Event {
             ops: vec![Op::Insert],
@@ -428,36 +457,42 @@ mod tests {
                 data_type: DataType::Int32,
                 column_id: ColumnId::from(0),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "address".to_string(),
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(1),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "city".to_string(),
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(2),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "zipcode".to_string(),
                 data_type: DataType::Int64,
                 column_id: ColumnId::from(3),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "rate".to_string(),
                 data_type: DataType::Float32,
                 column_id: ColumnId::from(4),
                 skip_parse: false,
+                fields: vec![],
             },
             SourceColumnDesc {
                 name: "date".to_string(),
                 data_type: DataType::Date,
                 column_id: ColumnId::from(5),
                 skip_parse: false,
+                fields: vec![],
             },
         ];
 
@@ -501,4 +536,39 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_parser_parse_nested() {
+        use risingwave_common::array::StructValue;
+        use risingwave_common::catalog::ColumnDesc as CatalogColumnDesc;
+
+        let parser = create_parser(PROTO_NESTED_FILE_DATA).unwrap();
+        let mut descs = parser
+            .map_to_columns()
+            .unwrap()
+            .iter()
+            .map(|c| SourceColumnDesc::from(&CatalogColumnDesc::from(c)))
+            .collect::<Vec<_>>();
+        // Prune `country.address`, which should be parsed as null.
+        descs[1].fields[0].skip_parse = true;
+
+        // id: 1, country: { address: "a", city: { address: "b", zipcode: "c" }, zipcode: "z" },
+        // zipcode: 456
+        let payload = b"\x08\x01\x1a\x0e\x0a\x01a\x12\x06\x0a\x01b\x12\x01c\x1a\x01z\x20\xc8\x03";
+        let event = parser.parse(payload, &descs).unwrap();
+        let row = event.rows.first().unwrap();
+        assert_eq!(row[0], Some(ScalarImpl::Int32(1)));
+        assert_eq!(
+            row[1],
+            Some(ScalarImpl::Struct(StructValue::new(vec![
+                None,
+                Some(ScalarImpl::Struct(StructValue::new(vec![
+                    Some(ScalarImpl::Utf8("b".to_string())),
+                    Some(ScalarImpl::Utf8("c".to_string())),
+                ]))),
+                Some(ScalarImpl::Utf8("z".to_string())),
+            ])))
+        );
+        assert_eq!(row[2], Some(ScalarImpl::Int64(456)));
+    }
 }
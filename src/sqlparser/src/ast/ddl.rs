@@ -117,6 +117,53 @@ impl fmt::Display for AlterTableOperation {
     }
 }
 
+/// An `ALTER SOURCE` (`Statement::AlterSource`) operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterSourceOperation {
+    /// `PAUSE`
+    Pause,
+    /// `RESUME`
+    Resume,
+    /// `SET RATE_LIMIT = { <rows per second> | DEFAULT }`
+    SetRateLimit(Option<u64>),
+}
+
+impl fmt::Display for AlterSourceOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterSourceOperation::Pause => write!(f, "PAUSE"),
+            AlterSourceOperation::Resume => write!(f, "RESUME"),
+            AlterSourceOperation::SetRateLimit(rate_limit) => fmt_set_rate_limit(f, rate_limit),
+        }
+    }
+}
+
+/// An `ALTER MATERIALIZED VIEW` (`Statement::AlterMaterializedView`) operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterMaterializedViewOperation {
+    /// `SET RATE_LIMIT = { <rows per second> | DEFAULT }`
+    SetRateLimit(Option<u64>),
+}
+
+impl fmt::Display for AlterMaterializedViewOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterMaterializedViewOperation::SetRateLimit(rate_limit) => {
+                fmt_set_rate_limit(f, rate_limit)
+            }
+        }
+    }
+}
+
+fn fmt_set_rate_limit(f: &mut fmt::Formatter, rate_limit: &Option<u64>) -> fmt::Result {
+    match rate_limit {
+        Some(rate_limit) => write!(f, "SET RATE_LIMIT = {}", rate_limit),
+        None => write!(f, "SET RATE_LIMIT = DEFAULT"),
+    }
+}
+
 /// An `ALTER COLUMN` (`Statement::AlterTable`) operation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -256,6 +303,21 @@ impl fmt::Display for ColumnDef {
     }
 }
 
+/// `WATERMARK FOR <column> AS <expr>` in the column list of `CREATE SOURCE`, e.g.
+/// `WATERMARK FOR ts AS ts - INTERVAL '5' SECOND`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SourceWatermark {
+    pub column: Ident,
+    pub expr: Expr,
+}
+
+impl fmt::Display for SourceWatermark {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WATERMARK FOR {} AS {}", self.column, self.expr)
+    }
+}
+
 /// An optionally-named `ColumnOption`: `[ CONSTRAINT <name> ] <column-option>`.
 ///
 /// Note that implementations are substantially more permissive than the ANSI
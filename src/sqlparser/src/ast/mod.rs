@@ -30,16 +30,17 @@ use itertools::Itertools;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-pub use self::data_type::DataType;
+pub use self::data_type::{DataType, StructField};
 pub use self::ddl::{
-    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
-    ReferentialAction, TableConstraint,
+    AlterColumnOperation, AlterMaterializedViewOperation, AlterSourceOperation,
+    AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef, ReferentialAction,
+    SourceWatermark, TableConstraint,
 };
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
-    Cte, Fetch, Join, JoinConstraint, JoinOperator, LateralView, Offset, OffsetRows, OrderByExpr,
-    Query, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, Top,
-    Values, With,
+    Cte, Distinct, Fetch, Join, JoinConstraint, JoinOperator, LateralView, Offset, OffsetRows,
+    OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor,
+    TableWithJoins, Top, Values, With,
 };
 pub use self::statement::*;
 pub use self::value::{DateTimeField, TrimWhereField, Value};
@@ -304,6 +305,8 @@ pub enum Expr {
     /// The `ARRAY` expr. Alternative syntax for `ARRAY` is by utilizing curly braces, e.g. {1, 2,
     /// 3},
     Array(Vec<Expr>),
+    /// `ANY(<array>)`, only valid as the right operand of a comparison, e.g. `a = ANY(b)`
+    AnyOp(Box<Expr>),
 }
 
 impl fmt::Display for Expr {
@@ -322,7 +325,9 @@ impl fmt::Display for Expr {
                 Ok(())
             }
             Expr::CompoundIdentifier(s) => write!(f, "{}", display_separated(s, ".")),
-            Expr::FieldIdentifier(ast, s) => write!(f, "{}.{}", ast, display_separated(s, ".")),
+            Expr::FieldIdentifier(ast, s) => {
+                write!(f, "({}).{}", ast, display_separated(s, "."))
+            }
             Expr::IsNull(ast) => write!(f, "{} IS NULL", ast),
             Expr::IsNotNull(ast) => write!(f, "{} IS NOT NULL", ast),
             Expr::IsTrue(ast) => write!(f, "{} IS TRUE", ast),
@@ -479,6 +484,7 @@ impl fmt::Display for Expr {
                     .as_slice()
                     .join(", ")
             ),
+            Expr::AnyOp(expr) => write!(f, "ANY({})", expr),
             Expr::Array(exprs) => write!(
                 f,
                 "ARRAY[{}]",
@@ -633,6 +639,7 @@ pub enum ShowObject {
     MaterializedView { schema: Option<Ident> },
     Source { schema: Option<Ident> },
     MaterializedSource { schema: Option<Ident> },
+    Jobs,
 }
 
 impl fmt::Display for ShowObject {
@@ -658,6 +665,7 @@ impl fmt::Display for ShowObject {
             ShowObject::MaterializedSource { schema } => {
                 write!(f, "MATERIALIZED SOURCES{}", fmt_schema(schema))
             }
+            ShowObject::Jobs => f.write_str("JOBS"),
         }
     }
 }
@@ -731,6 +739,8 @@ pub enum Statement {
         columns: Vec<Ident>,
         query: Box<Query>,
         with_options: Vec<SqlOption>,
+        /// `EMIT ON WINDOW CLOSE`, only for materialized views
+        emit_on_window_close: bool,
     },
     /// CREATE TABLE
     CreateTable {
@@ -767,6 +777,18 @@ pub enum Statement {
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// ALTER SOURCE
+    AlterSource {
+        /// Source name
+        name: ObjectName,
+        operation: AlterSourceOperation,
+    },
+    /// ALTER MATERIALIZED VIEW
+    AlterMaterializedView {
+        /// Materialized view name
+        name: ObjectName,
+        operation: AlterMaterializedViewOperation,
+    },
     /// DESCRIBE TABLE OR SOURCE
     Describe {
         /// Table or Source name
@@ -874,6 +896,10 @@ pub enum Statement {
     ///
     /// Note: RisingWave specific statement.
     Flush,
+    /// CANCEL JOB a materialized view being created in background.
+    ///
+    /// Note: RisingWave specific statement.
+    CancelJob { job_id: u32 },
 }
 
 impl fmt::Display for Statement {
@@ -1012,6 +1038,7 @@ impl fmt::Display for Statement {
                 query,
                 materialized,
                 with_options,
+                emit_on_window_close,
             } => {
                 write!(
                     f,
@@ -1026,7 +1053,11 @@ impl fmt::Display for Statement {
                 if !columns.is_empty() {
                     write!(f, " ({})", display_comma_separated(columns))?;
                 }
-                write!(f, " AS {}", query)
+                write!(f, " AS {}", query)?;
+                if *emit_on_window_close {
+                    write!(f, " EMIT ON WINDOW CLOSE")?;
+                }
+                Ok(())
             }
             Statement::CreateTable {
                 name,
@@ -1107,6 +1138,12 @@ impl fmt::Display for Statement {
             Statement::AlterTable { name, operation } => {
                 write!(f, "ALTER TABLE {} {}", name, operation)
             }
+            Statement::AlterSource { name, operation } => {
+                write!(f, "ALTER SOURCE {} {}", name, operation)
+            }
+            Statement::AlterMaterializedView { name, operation } => {
+                write!(f, "ALTER MATERIALIZED VIEW {} {}", name, operation)
+            }
             Statement::Drop(stmt) => write!(f, "DROP {}", stmt),
             Statement::SetVariable {
                 local,
@@ -1244,6 +1281,9 @@ impl fmt::Display for Statement {
             Statement::Flush => {
                 write!(f, "FLUSH")
             }
+            Statement::CancelJob { job_id } => {
+                write!(f, "CANCEL JOB {}", job_id)
+            }
         }
     }
 }
@@ -1464,17 +1504,23 @@ pub struct Function {
     pub over: Option<WindowSpec>,
     // aggregate functions may specify eg `COUNT(DISTINCT x)`
     pub distinct: bool,
+    // aggregate functions may specify eg `FIRST_VALUE(x ORDER BY y)`
+    pub order_by: Vec<OrderByExpr>,
 }
 
 impl fmt::Display for Function {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{}({}{})",
+            "{}({}{}",
             self.name,
             if self.distinct { "DISTINCT " } else { "" },
             display_comma_separated(&self.args),
         )?;
+        if !self.order_by.is_empty() {
+            write!(f, " ORDER BY {}", display_comma_separated(&self.order_by))?;
+        }
+        write!(f, ")")?;
         if let Some(o) = &self.over {
             write!(f, " OVER ({})", o)?;
         }
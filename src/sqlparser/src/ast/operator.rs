@@ -86,6 +86,10 @@ pub enum BinaryOperator {
     PGRegexIMatch,
     PGRegexNotMatch,
     PGRegexNotIMatch,
+    PGJsonAccess,
+    PGJsonAccessAsText,
+    PGJsonPathAccess,
+    PGJsonContains,
 }
 
 impl fmt::Display for BinaryOperator {
@@ -121,6 +125,10 @@ impl fmt::Display for BinaryOperator {
             BinaryOperator::PGRegexIMatch => "~*",
             BinaryOperator::PGRegexNotMatch => "!~",
             BinaryOperator::PGRegexNotIMatch => "!~*",
+            BinaryOperator::PGJsonAccess => "->",
+            BinaryOperator::PGJsonAccessAsText => "->>",
+            BinaryOperator::PGJsonPathAccess => "#>",
+            BinaryOperator::PGJsonContains => "@>",
         })
     }
 }
@@ -147,7 +147,7 @@ impl fmt::Display for SetOperator {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Select {
-    pub distinct: bool,
+    pub distinct: Distinct,
     /// projection expressions
     pub projection: Vec<SelectItem>,
     /// FROM
@@ -164,7 +164,7 @@ pub struct Select {
 
 impl fmt::Display for Select {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "SELECT{}", if self.distinct { " DISTINCT" } else { "" })?;
+        write!(f, "SELECT{}", &self.distinct)?;
         write!(f, " {}", display_comma_separated(&self.projection))?;
         if !self.from.is_empty() {
             write!(f, " FROM {}", display_comma_separated(&self.from))?;
@@ -187,6 +187,30 @@ impl fmt::Display for Select {
     }
 }
 
+/// The `DISTINCT` clause of a `SELECT`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Distinct {
+    /// `SELECT` or `SELECT ALL`
+    All,
+    /// `SELECT DISTINCT`
+    Distinct,
+    /// `SELECT DISTINCT ON (<expr>, ...)`
+    DistinctOn(Vec<Expr>),
+}
+
+impl fmt::Display for Distinct {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Distinct::All => Ok(()),
+            Distinct::Distinct => write!(f, " DISTINCT"),
+            Distinct::DistinctOn(exprs) => {
+                write!(f, " DISTINCT ON ({})", display_comma_separated(exprs))
+            }
+        }
+    }
+}
+
 /// A hive LATERAL VIEW with potential column aliases
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -318,6 +342,9 @@ pub enum TableFactor {
         /// and MSSQL. Note that deprecated MSSQL `FROM foo (NOLOCK)` syntax
         /// will also be parsed as `args`.
         args: Vec<FunctionArg>,
+        /// Whether `FOR SYSTEM_TIME AS OF PROCTIME()` is specified, i.e. the table is joined as of
+        /// the processing time of each row.
+        for_system_time_as_of_proctime: bool,
     },
     Derived {
         lateral: bool,
@@ -341,11 +368,19 @@ pub enum TableFactor {
 impl fmt::Display for TableFactor {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            TableFactor::Table { name, alias, args } => {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                for_system_time_as_of_proctime,
+            } => {
                 write!(f, "{}", name)?;
                 if !args.is_empty() {
                     write!(f, "({})", display_comma_separated(args))?;
                 }
+                if *for_system_time_as_of_proctime {
+                    write!(f, " FOR SYSTEM_TIME AS OF PROCTIME()")?;
+                }
                 if let Some(alias) = alias {
                     write!(f, " AS {}", alias)?;
                 }
@@ -21,7 +21,8 @@ use serde::{Deserialize, Serialize};
 
 use super::ObjectType;
 use crate::ast::{
-    display_comma_separated, ColumnDef, Ident, ObjectName, SqlOption, TableConstraint,
+    display_comma_separated, ColumnDef, Ident, ObjectName, SourceWatermark, SqlOption,
+    TableConstraint,
 };
 use crate::keywords::Keyword;
 use crate::parser::{Parser, ParserError};
@@ -75,6 +76,7 @@ pub struct CreateSourceStatement {
     pub if_not_exists: bool,
     pub columns: Vec<ColumnDef>,
     pub constraints: Vec<TableConstraint>,
+    pub source_watermarks: Vec<SourceWatermark>,
     pub source_name: ObjectName,
     pub with_properties: WithProperties,
     pub source_schema: SourceSchema,
@@ -156,7 +158,7 @@ impl ParseTo for CreateSourceStatement {
         impl_parse_to!(source_name: ObjectName, p);
 
         // parse columns
-        let (columns, constraints) = p.parse_columns()?;
+        let (columns, constraints, source_watermarks) = p.parse_columns_with_watermarks()?;
 
         impl_parse_to!(with_properties: WithProperties, p);
         impl_parse_to!([Keyword::ROW, Keyword::FORMAT], p);
@@ -165,6 +167,7 @@ impl ParseTo for CreateSourceStatement {
             if_not_exists,
             columns,
             constraints,
+            source_watermarks,
             source_name,
             with_properties,
             source_schema,
@@ -177,6 +180,18 @@ impl fmt::Display for CreateSourceStatement {
         let mut v: Vec<String> = vec![];
         impl_fmt_display!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], v, self);
         impl_fmt_display!(source_name, v, self);
+        if !self.columns.is_empty()
+            || !self.constraints.is_empty()
+            || !self.source_watermarks.is_empty()
+        {
+            let elems = self
+                .columns
+                .iter()
+                .map(ToString::to_string)
+                .chain(self.constraints.iter().map(ToString::to_string))
+                .chain(self.source_watermarks.iter().map(ToString::to_string));
+            v.push(format!("({})", elems.format(", ")));
+        }
         impl_fmt_display!(with_properties, v, self);
         impl_fmt_display!([Keyword::ROW, Keyword::FORMAT], v);
         impl_fmt_display!(source_schema, v, self);
@@ -105,6 +105,7 @@ define_keywords!(
     CACHE,
     CALL,
     CALLED,
+    CANCEL,
     CARDINALITY,
     CASCADE,
     CASCADED,
@@ -184,6 +185,7 @@ define_keywords!(
     EACH,
     ELEMENT,
     ELSE,
+    EMIT,
     END,
     END_EXEC = "END-EXEC",
     END_FRAME,
@@ -249,8 +251,11 @@ define_keywords!(
     INTO,
     IS,
     ISOLATION,
+    JOB,
+    JOBS,
     JOIN,
     JSON,
+    JSONB,
     KEY,
     LANGUAGE,
     LARGE,
@@ -321,6 +326,7 @@ define_keywords!(
     PARTITION,
     PARTITIONED,
     PARTITIONS,
+    PAUSE,
     PERCENT,
     PERCENTILE_CONT,
     PERCENTILE_DISC,
@@ -341,6 +347,7 @@ define_keywords!(
     PURGE,
     RANGE,
     RANK,
+    RATE_LIMIT,
     RCFILE,
     READ,
     READS,
@@ -365,6 +372,7 @@ define_keywords!(
     REPEATABLE,
     REPLACE,
     RESTRICT,
+    RESUME,
     RESULT,
     RETURN,
     RETURNS,
@@ -417,6 +425,7 @@ define_keywords!(
     STDIN,
     STORED,
     STRING,
+    STRUCT,
     SUBMULTISET,
     SUBSTRING,
     SUBSTRING_REGEX,
@@ -483,6 +492,7 @@ define_keywords!(
     VIEW,
     VIEWS,
     VIRTUAL,
+    WATERMARK,
     WHEN,
     WHENEVER,
     WHERE,
@@ -520,6 +530,7 @@ pub const RESERVED_FOR_TABLE_ALIAS: &[Keyword] = &[
     Keyword::UNION,
     Keyword::EXCEPT,
     Keyword::INTERSECT,
+    Keyword::EMIT,
     // Reserved only as a table alias in the `FROM`/`JOIN` clauses:
     Keyword::ON,
     Keyword::JOIN,
@@ -559,6 +570,7 @@ pub const RESERVED_FOR_COLUMN_ALIAS: &[Keyword] = &[
     Keyword::EXCEPT,
     Keyword::INTERSECT,
     Keyword::CLUSTER,
+    Keyword::EMIT,
     // Reserved only as a column alias in the `SELECT` clause
     Keyword::FROM,
 ];
@@ -193,6 +193,7 @@ impl Parser {
                 Keyword::PREPARE => Ok(self.parse_prepare()?),
                 Keyword::COMMENT => Ok(self.parse_comment()?),
                 Keyword::FLUSH => Ok(Statement::Flush),
+                Keyword::CANCEL => Ok(self.parse_cancel_job()?),
                 _ => self.expected("an SQL statement", Token::Word(w)),
             },
             Token::LParen => {
@@ -379,6 +380,9 @@ impl Parser {
                     self.prev_token();
                     Ok(Expr::Value(self.parse_value()?))
                 }
+                Keyword::CURRENT_TIMESTAMP => {
+                    self.parse_time_functions(ObjectName(vec![w.to_ident()]))
+                }
                 Keyword::CASE => self.parse_case_expr(),
                 Keyword::CAST => self.parse_cast_expr(),
                 Keyword::TRY_CAST => self.parse_try_cast_expr(),
@@ -555,7 +559,7 @@ impl Parser {
     pub fn parse_function(&mut self, name: ObjectName) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
         let distinct = self.parse_all_or_distinct()?;
-        let args = self.parse_optional_args()?;
+        let (args, order_by) = self.parse_aggregate_args()?;
         let over = if self.parse_keyword(Keyword::OVER) {
             // TBD: support window names (`OVER mywin`) in place of inline specification
             self.expect_token(&Token::LParen)?;
@@ -592,6 +596,24 @@ impl Parser {
             args,
             over,
             distinct,
+            order_by,
+        }))
+    }
+
+    /// Parse SQL standard time functions like `CURRENT_TIMESTAMP`, which may be invoked without
+    /// parentheses.
+    pub fn parse_time_functions(&mut self, name: ObjectName) -> Result<Expr, ParserError> {
+        let args = if self.consume_token(&Token::LParen) {
+            self.parse_optional_args()?
+        } else {
+            vec![]
+        };
+        Ok(Expr::Function(Function {
+            name,
+            args,
+            over: None,
+            distinct: false,
+            order_by: vec![],
         }))
     }
 
@@ -962,6 +984,10 @@ impl Parser {
             Token::TildeAsterisk => Some(BinaryOperator::PGRegexIMatch),
             Token::ExclamationMarkTilde => Some(BinaryOperator::PGRegexNotMatch),
             Token::ExclamationMarkTildeAsterisk => Some(BinaryOperator::PGRegexNotIMatch),
+            Token::Arrow => Some(BinaryOperator::PGJsonAccess),
+            Token::LongArrow => Some(BinaryOperator::PGJsonAccessAsText),
+            Token::HashArrow => Some(BinaryOperator::PGJsonPathAccess),
+            Token::AtArrow => Some(BinaryOperator::PGJsonContains),
             Token::Word(w) => match w.keyword {
                 Keyword::AND => Some(BinaryOperator::And),
                 Keyword::OR => Some(BinaryOperator::Or),
@@ -983,10 +1009,31 @@ impl Parser {
         };
 
         if let Some(op) = regular_binary_operator {
+            let is_comparison = matches!(
+                op,
+                BinaryOperator::Eq
+                    | BinaryOperator::NotEq
+                    | BinaryOperator::Gt
+                    | BinaryOperator::GtEq
+                    | BinaryOperator::Lt
+                    | BinaryOperator::LtEq
+            );
+            let right = if is_comparison
+                && self
+                    .parse_one_of_keywords(&[Keyword::ANY, Keyword::SOME])
+                    .is_some()
+            {
+                self.expect_token(&Token::LParen)?;
+                let array = self.parse_expr()?;
+                self.expect_token(&Token::RParen)?;
+                Expr::AnyOp(Box::new(array))
+            } else {
+                self.parse_subexpr(precedence)?
+            };
             Ok(Expr::BinaryOp {
                 left: Box::new(expr),
                 op,
-                right: Box::new(self.parse_subexpr(precedence)?),
+                right: Box::new(right),
             })
         } else if let Token::Word(w) = &tok {
             match w.keyword {
@@ -1058,13 +1105,10 @@ impl Parser {
             debug!("Tok: {}", tok);
             key_parts.push(key);
         }
-        match expr {
-            e @ Expr::Identifier(_) | e @ Expr::CompoundIdentifier(_) => Ok(Expr::MapAccess {
-                column: Box::new(e),
-                keys: key_parts,
-            }),
-            _ => Ok(expr),
-        }
+        Ok(Expr::MapAccess {
+            column: Box::new(expr),
+            keys: key_parts,
+        })
     }
 
     /// Parses the parens following the `[ NOT ] IN` operator
@@ -1154,6 +1198,7 @@ impl Parser {
             Token::Plus | Token::Minus => Ok(Self::PLUS_MINUS_PREC),
             Token::Mul | Token::Div | Token::Mod | Token::StringConcat => Ok(40),
             Token::DoubleColon => Ok(50),
+            Token::Arrow | Token::LongArrow | Token::HashArrow | Token::AtArrow => Ok(50),
             Token::ExclamationMark => Ok(50),
             Token::LBracket => Ok(10),
             _ => Ok(0),
@@ -1347,6 +1392,21 @@ impl Parser {
         }
     }
 
+    /// Parse the `ALL`, `DISTINCT` or `DISTINCT ON (<expr>, ...)` clause of a `SELECT`.
+    pub fn parse_select_distinct(&mut self) -> Result<Distinct, ParserError> {
+        if !self.parse_all_or_distinct()? {
+            return Ok(Distinct::All);
+        }
+        if self.parse_keyword(Keyword::ON) {
+            self.expect_token(&Token::LParen)?;
+            let exprs = self.parse_comma_separated(Parser::parse_expr)?;
+            self.expect_token(&Token::RParen)?;
+            Ok(Distinct::DistinctOn(exprs))
+        } else {
+            Ok(Distinct::Distinct)
+        }
+    }
+
     /// Parse either `ALL` or `DISTINCT`. Returns `true` if `DISTINCT` is parsed and results in a
     /// `ParserError` if both `ALL` and `DISTINCT` are fround.
     pub fn parse_all_or_distinct(&mut self) -> Result<bool, ParserError> {
@@ -1412,6 +1472,8 @@ impl Parser {
         let with_options = self.parse_options(Keyword::WITH)?;
         self.expect_keyword(Keyword::AS)?;
         let query = Box::new(self.parse_query()?);
+        let emit_on_window_close = materialized
+            && self.parse_keywords(&[Keyword::EMIT, Keyword::ON, Keyword::WINDOW, Keyword::CLOSE]);
         // Optional `WITH [ CASCADED | LOCAL ] CHECK OPTION` is widely supported here.
         Ok(Statement::CreateView {
             name,
@@ -1420,6 +1482,7 @@ impl Parser {
             materialized,
             or_replace,
             with_options,
+            emit_on_window_close,
         })
     }
 
@@ -1507,14 +1570,36 @@ impl Parser {
     }
 
     pub fn parse_columns(&mut self) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>), ParserError> {
+        let (columns, constraints, _) = self.parse_columns_inner(false)?;
+        Ok((columns, constraints))
+    }
+
+    /// Parses the column list of `CREATE SOURCE`, which may also contain
+    /// `WATERMARK FOR <column> AS <expr>` clauses.
+    pub fn parse_columns_with_watermarks(
+        &mut self,
+    ) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>, Vec<SourceWatermark>), ParserError> {
+        self.parse_columns_inner(true)
+    }
+
+    fn parse_columns_inner(
+        &mut self,
+        allow_watermark: bool,
+    ) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>, Vec<SourceWatermark>), ParserError> {
         let mut columns = vec![];
         let mut constraints = vec![];
+        let mut watermarks = vec![];
         if !self.consume_token(&Token::LParen) || self.consume_token(&Token::RParen) {
-            return Ok((columns, constraints));
+            return Ok((columns, constraints, watermarks));
         }
 
         loop {
-            if let Some(constraint) = self.parse_optional_table_constraint()? {
+            if allow_watermark && self.parse_keywords(&[Keyword::WATERMARK, Keyword::FOR]) {
+                let column = self.parse_identifier()?;
+                self.expect_keyword(Keyword::AS)?;
+                let expr = self.parse_expr()?;
+                watermarks.push(SourceWatermark { column, expr });
+            } else if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
             } else if let Token::Word(_) = self.peek_token() {
                 columns.push(self.parse_column_def()?);
@@ -1530,7 +1615,7 @@ impl Parser {
             }
         }
 
-        Ok((columns, constraints))
+        Ok((columns, constraints, watermarks))
     }
 
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
@@ -1715,9 +1800,71 @@ impl Parser {
         Ok(SqlOption { name, value })
     }
 
+    /// Parse a `CANCEL JOB <job_id>` statement, assuming that the `CANCEL` token has already
+    /// been consumed.
+    pub fn parse_cancel_job(&mut self) -> Result<Statement, ParserError> {
+        self.expect_keyword(Keyword::JOB)?;
+        let job_id = self.parse_literal_uint()?;
+        let job_id = u32::try_from(job_id)
+            .map_err(|_| ParserError::ParserError(format!("job id {} out of range", job_id)))?;
+        Ok(Statement::CancelJob { job_id })
+    }
+
     pub fn parse_alter(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword(Keyword::TABLE)?;
-        self.parse_alter_table()
+        match self.expect_one_of_keywords(&[
+            Keyword::TABLE,
+            Keyword::SOURCE,
+            Keyword::MATERIALIZED,
+        ])? {
+            Keyword::TABLE => self.parse_alter_table(),
+            Keyword::SOURCE => self.parse_alter_source(),
+            Keyword::MATERIALIZED => {
+                self.expect_keyword(Keyword::VIEW)?;
+                self.parse_alter_materialized_view()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Parses `RATE_LIMIT = { <rows per second> | DEFAULT }` after `SET`.
+    fn parse_set_rate_limit(&mut self) -> Result<Option<u64>, ParserError> {
+        self.expect_keyword(Keyword::RATE_LIMIT)?;
+        self.expect_token(&Token::Eq)?;
+        if self.parse_keyword(Keyword::DEFAULT) {
+            Ok(None)
+        } else {
+            Ok(Some(self.parse_literal_uint()?))
+        }
+    }
+
+    pub fn parse_alter_materialized_view(&mut self) -> Result<Statement, ParserError> {
+        let view_name = self.parse_object_name()?;
+        let operation = if self.parse_keyword(Keyword::SET) {
+            AlterMaterializedViewOperation::SetRateLimit(self.parse_set_rate_limit()?)
+        } else {
+            return self.expected("SET after ALTER MATERIALIZED VIEW", self.peek_token());
+        };
+        Ok(Statement::AlterMaterializedView {
+            name: view_name,
+            operation,
+        })
+    }
+
+    pub fn parse_alter_source(&mut self) -> Result<Statement, ParserError> {
+        let source_name = self.parse_object_name()?;
+        let operation = if self.parse_keyword(Keyword::PAUSE) {
+            AlterSourceOperation::Pause
+        } else if self.parse_keyword(Keyword::RESUME) {
+            AlterSourceOperation::Resume
+        } else if self.parse_keyword(Keyword::SET) {
+            AlterSourceOperation::SetRateLimit(self.parse_set_rate_limit()?)
+        } else {
+            return self.expected("PAUSE, RESUME or SET after ALTER SOURCE", self.peek_token());
+        };
+        Ok(Statement::AlterSource {
+            name: source_name,
+            operation,
+        })
     }
 
     pub fn parse_alter_table(&mut self) -> Result<Statement, ParserError> {
@@ -1907,10 +2054,18 @@ impl Parser {
     /// Parse a map key string
     pub fn parse_map_key(&mut self) -> Result<Expr, ParserError> {
         match self.next_token() {
-            Token::Word(Word { value, keyword, .. }) if keyword == Keyword::NoKeyword => {
+            Token::Word(Word {
+                value,
+                keyword,
+                quote_style,
+            }) if keyword == Keyword::NoKeyword => {
                 if self.peek_token() == Token::LParen {
                     return self.parse_function(ObjectName(vec![Ident::new(value)]));
                 }
+                // An unquoted word is a column, e.g. the array subscript in `arr[i]`.
+                if quote_style.is_none() {
+                    return Ok(Expr::Identifier(Ident::new(value)));
+                }
                 Ok(Expr::Value(Value::SingleQuotedString(value)))
             }
             Token::SingleQuotedString(s) => Ok(Expr::Value(Value::SingleQuotedString(s))),
@@ -1975,6 +2130,7 @@ impl Parser {
                 // qualifier that we don't currently support. See
                 // parse_interval_literal for a taste.
                 Keyword::INTERVAL => Ok(DataType::Interval),
+                Keyword::JSONB => Ok(DataType::Jsonb),
                 Keyword::REGCLASS => Ok(DataType::Regclass),
                 Keyword::STRING => Ok(DataType::String),
                 Keyword::TEXT => {
@@ -1987,6 +2143,12 @@ impl Parser {
                     }
                 }
                 Keyword::BYTEA => Ok(DataType::Bytea),
+                Keyword::STRUCT => {
+                    self.expect_token(&Token::Lt)?;
+                    let fields = self.parse_comma_separated(Parser::parse_struct_field)?;
+                    self.expect_closing_angle()?;
+                    Ok(DataType::Struct(fields))
+                }
                 Keyword::NUMERIC | Keyword::DECIMAL | Keyword::DEC => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
                     Ok(DataType::Decimal(precision, scale))
@@ -2001,6 +2163,27 @@ impl Parser {
         }
     }
 
+    /// Parse a field of a struct type, e.g. `a INT`
+    pub fn parse_struct_field(&mut self) -> Result<StructField, ParserError> {
+        let name = self.parse_identifier()?;
+        let data_type = self.parse_data_type()?;
+        Ok(StructField { name, data_type })
+    }
+
+    /// Consume a `>` which closes a type parameter list. A `>>` token is split so that the
+    /// remaining `>` closes the enclosing list, e.g. `STRUCT<a STRUCT<b INT>>`.
+    fn expect_closing_angle(&mut self) -> Result<(), ParserError> {
+        match self.next_token() {
+            Token::Gt => Ok(()),
+            Token::ShiftRight => {
+                self.prev_token();
+                self.tokens[self.index] = Token::Gt;
+                Ok(())
+            }
+            unexpected => self.expected("'>'", unexpected),
+        }
+    }
+
     /// Parse `AS identifier` (or simply `identifier` if it's not a reserved keyword)
     /// Some examples with aliases: `SELECT 1 foo`, `SELECT COUNT(*) AS cnt`,
     /// `SELECT ... FROM t1 foo, t2 bar`, `SELECT ... FROM (...) AS bar`
@@ -2371,7 +2554,7 @@ impl Parser {
     /// Parse a restricted `SELECT` statement (no CTEs / `UNION` / `ORDER BY`),
     /// assuming the initial `SELECT` was already consumed
     pub fn parse_select(&mut self) -> Result<Select, ParserError> {
-        let distinct = self.parse_all_or_distinct()?;
+        let distinct = self.parse_select_distinct()?;
 
         let projection = self.parse_comma_separated(Parser::parse_select_item)?;
 
@@ -2516,6 +2699,9 @@ impl Parser {
                 Keyword::SCHEMAS => {
                     return Ok(Statement::ShowObjects(ShowObject::Schema));
                 }
+                Keyword::JOBS => {
+                    return Ok(Statement::ShowObjects(ShowObject::Jobs));
+                }
                 Keyword::MATERIALIZED => {
                     if self.parse_keyword(Keyword::VIEWS) {
                         return Ok(Statement::ShowObjects(ShowObject::MaterializedView {
@@ -2684,11 +2870,33 @@ impl Parser {
             } else {
                 vec![]
             };
+            let for_system_time_as_of_proctime = self.parse_for_system_time_as_of_proctime()?;
             let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
-            Ok(TableFactor::Table { name, alias, args })
+            Ok(TableFactor::Table {
+                name,
+                alias,
+                args,
+                for_system_time_as_of_proctime,
+            })
         }
     }
 
+    /// Parse an optional `FOR SYSTEM_TIME AS OF PROCTIME()` after a table name, which makes the
+    /// table the dimension side of a temporal join.
+    fn parse_for_system_time_as_of_proctime(&mut self) -> Result<bool, ParserError> {
+        if !self.parse_keywords(&[Keyword::FOR, Keyword::SYSTEM_TIME, Keyword::AS, Keyword::OF]) {
+            return Ok(false);
+        }
+        let token = self.peek_token();
+        let ident = self.parse_identifier()?;
+        if !ident.value.eq_ignore_ascii_case("proctime") {
+            return self.expected("PROCTIME()", token);
+        }
+        self.expect_token(&Token::LParen)?;
+        self.expect_token(&Token::RParen)?;
+        Ok(true)
+    }
+
     pub fn parse_derived_table_factor(
         &mut self,
         lateral: IsLateral,
@@ -2926,6 +3134,24 @@ impl Parser {
         }
     }
 
+    /// Parse the arguments of a function call that may be an aggregate, i.e. the arguments may be
+    /// followed by `ORDER BY`, and the closing parenthesis.
+    pub fn parse_aggregate_args(
+        &mut self,
+    ) -> Result<(Vec<FunctionArg>, Vec<OrderByExpr>), ParserError> {
+        if self.consume_token(&Token::RParen) {
+            return Ok((vec![], vec![]));
+        }
+        let args = self.parse_comma_separated(Parser::parse_function_args)?;
+        let order_by = if self.parse_keywords(&[Keyword::ORDER, Keyword::BY]) {
+            self.parse_comma_separated(Parser::parse_order_by_expr)?
+        } else {
+            vec![]
+        };
+        self.expect_token(&Token::RParen)?;
+        Ok((args, order_by))
+    }
+
     /// Parse a comma-delimited list of projections after SELECT
     pub fn parse_select_item(&mut self) -> Result<SelectItem, ParserError> {
         match self.parse_wildcard_expr()? {
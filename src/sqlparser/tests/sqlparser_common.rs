@@ -155,6 +155,7 @@ fn parse_update_with_table_alias() {
                             columns: vec![]
                         }),
                         args: vec![],
+                        for_system_time_as_of_proctime: false,
                     },
                     joins: vec![]
                 },
@@ -249,7 +250,7 @@ fn parse_top_level() {
 fn parse_simple_select() {
     let sql = "SELECT id, fname, lname FROM customer WHERE id = 1 LIMIT 5";
     let select = verified_only_select(sql);
-    assert!(!select.distinct);
+    assert_eq!(Distinct::All, select.distinct);
     assert_eq!(3, select.projection.len());
     let select = verified_query(sql);
     assert_eq!(Some(Expr::Value(number("5"))), select.limit);
@@ -269,13 +270,41 @@ fn parse_limit_is_not_an_alias() {
 fn parse_select_distinct() {
     let sql = "SELECT DISTINCT name FROM customer";
     let select = verified_only_select(sql);
-    assert!(select.distinct);
+    assert_eq!(Distinct::Distinct, select.distinct);
     assert_eq!(
         &SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("name"))),
         only(&select.projection)
     );
 }
 
+#[test]
+fn parse_select_distinct_on() {
+    let sql = "SELECT DISTINCT ON (id, lower(name)) id, name FROM customer";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        Distinct::DistinctOn(vec![
+            Expr::Identifier(Ident::new("id")),
+            Expr::Function(Function {
+                name: ObjectName(vec![Ident::new("lower")]),
+                args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                    Expr::Identifier(Ident::new("name"))
+                ))],
+                over: None,
+                distinct: false,
+                order_by: vec![],
+            }),
+        ]),
+        select.distinct
+    );
+    assert_eq!(2, select.projection.len());
+
+    let result = parse_sql_statements("SELECT DISTINCT ON id FROM customer");
+    assert_eq!(
+        ParserError::ParserError("Expected (, found: id".to_string()),
+        result.unwrap_err(),
+    );
+}
+
 #[test]
 fn parse_select_all() {
     one_statement_parses_to("SELECT ALL name FROM customer", "SELECT name FROM customer");
@@ -377,6 +406,7 @@ fn parse_select_count_wildcard() {
             args: vec![FunctionArg::Unnamed(FunctionArgExpr::Wildcard)],
             over: None,
             distinct: false,
+            order_by: vec![],
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -395,6 +425,7 @@ fn parse_select_count_distinct() {
             }))],
             over: None,
             distinct: true,
+            order_by: vec![],
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -412,6 +443,30 @@ fn parse_select_count_distinct() {
     );
 }
 
+#[test]
+fn parse_aggregate_order_by() {
+    let sql = "SELECT FIRST_VALUE(x ORDER BY y DESC) FROM customer";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        &Expr::Function(Function {
+            name: ObjectName(vec![Ident::new("FIRST_VALUE")]),
+            args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                Expr::Identifier(Ident::new("x"))
+            ))],
+            over: None,
+            distinct: false,
+            order_by: vec![OrderByExpr {
+                expr: Expr::Identifier(Ident::new("y")),
+                asc: Some(false),
+                nulls_first: None,
+            }],
+        }),
+        expr_from_projection(only(&select.projection))
+    );
+
+    verified_only_select("SELECT ARRAY_AGG(DISTINCT x ORDER BY x, y NULLS FIRST) FROM customer");
+}
+
 #[test]
 fn parse_not() {
     let sql = "SELECT id FROM customer WHERE NOT salary = ''";
@@ -834,6 +889,30 @@ fn parse_bitwise_ops() {
     }
 }
 
+#[test]
+fn parse_json_ops() {
+    let json_ops = &[
+        ("->", BinaryOperator::PGJsonAccess),
+        ("->>", BinaryOperator::PGJsonAccessAsText),
+        ("#>", BinaryOperator::PGJsonPathAccess),
+        ("@>", BinaryOperator::PGJsonContains),
+    ];
+
+    for (str_op, op) in json_ops {
+        let select = verified_only_select(&format!("SELECT a {} b", &str_op));
+        assert_eq!(
+            SelectItem::UnnamedExpr(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: op.clone(),
+                right: Box::new(Expr::Identifier(Ident::new("b"))),
+            }),
+            select.projection[0]
+        );
+    }
+
+    verified_stmt("CREATE TABLE t (v JSONB)");
+}
+
 #[test]
 fn parse_logical_xor() {
     let sql = "SELECT true XOR true, false XOR false, true XOR false, false XOR true";
@@ -1093,6 +1172,7 @@ fn parse_select_having() {
                 args: vec![FunctionArg::Unnamed(FunctionArgExpr::Wildcard)],
                 over: None,
                 distinct: false,
+                order_by: vec![],
             })),
             op: BinaryOperator::Gt,
             right: Box::new(Expr::Value(number("1")))
@@ -1790,9 +1870,26 @@ fn parse_named_argument_function() {
             ],
             over: None,
             distinct: false,
+            order_by: vec![],
+        }),
+        expr_from_projection(only(&select.projection))
+    );
+}
+
+#[test]
+fn parse_current_timestamp() {
+    let select = verified_only_select("SELECT CURRENT_TIMESTAMP()");
+    assert_eq!(
+        &Expr::Function(Function {
+            name: ObjectName(vec![Ident::new("CURRENT_TIMESTAMP")]),
+            args: vec![],
+            over: None,
+            distinct: false,
+            order_by: vec![],
         }),
         expr_from_projection(only(&select.projection))
     );
+    one_statement_parses_to("SELECT CURRENT_TIMESTAMP", "SELECT CURRENT_TIMESTAMP()");
 }
 
 #[test]
@@ -1823,6 +1920,7 @@ fn parse_window_functions() {
                 window_frame: None,
             }),
             distinct: false,
+            order_by: vec![],
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -2045,7 +2143,9 @@ fn parse_delimited_identifiers() {
     );
     // check FROM
     match only(select.from).relation {
-        TableFactor::Table { name, alias, args } => {
+        TableFactor::Table {
+            name, alias, args, ..
+        } => {
             assert_eq!(vec![Ident::with_quote('"', "a table")], name.0);
             assert_eq!(Ident::with_quote('"', "alias"), alias.unwrap().name);
             assert!(args.is_empty());
@@ -2067,6 +2167,7 @@ fn parse_delimited_identifiers() {
             args: vec![],
             over: None,
             distinct: false,
+            order_by: vec![],
         }),
         expr_from_projection(&select.projection[1]),
     );
@@ -2171,6 +2272,7 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t1".into()]),
                     alias: None,
                     args: vec![],
+                    for_system_time_as_of_proctime: false,
                 },
                 joins: vec![],
             },
@@ -2179,6 +2281,7 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
                     args: vec![],
+                    for_system_time_as_of_proctime: false,
                 },
                 joins: vec![],
             }
@@ -2195,12 +2298,14 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t1a".into()]),
                     alias: None,
                     args: vec![],
+                    for_system_time_as_of_proctime: false,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t1b".into()]),
                         alias: None,
                         args: vec![],
+                        for_system_time_as_of_proctime: false,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2210,12 +2315,14 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t2a".into()]),
                     alias: None,
                     args: vec![],
+                    for_system_time_as_of_proctime: false,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t2b".into()]),
                         alias: None,
                         args: vec![],
+                        for_system_time_as_of_proctime: false,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2235,6 +2342,7 @@ fn parse_cross_join() {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
                 args: vec![],
+                for_system_time_as_of_proctime: false,
             },
             join_operator: JoinOperator::CrossJoin
         },
@@ -2254,6 +2362,7 @@ fn parse_joins_on() {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
                 args: vec![],
+                for_system_time_as_of_proctime: false,
             },
             join_operator: f(JoinConstraint::On(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("c1".into())),
@@ -2294,6 +2403,34 @@ fn parse_joins_on() {
     );
 }
 
+#[test]
+fn parse_join_for_system_time_as_of_proctime() {
+    let select = verified_only_select(
+        "SELECT * FROM t1 LEFT JOIN t2 FOR SYSTEM_TIME AS OF PROCTIME() AS d ON c1 = c2",
+    );
+    match &only(&select.from).joins[0].relation {
+        TableFactor::Table {
+            name,
+            alias,
+            for_system_time_as_of_proctime,
+            ..
+        } => {
+            assert_eq!(name, &ObjectName(vec![Ident::new("t2")]));
+            assert_eq!(alias, &table_alias("d"));
+            assert!(*for_system_time_as_of_proctime);
+        }
+        _ => panic!("Expecting TableFactor::Table"),
+    }
+    one_statement_parses_to(
+        "SELECT * FROM t1 JOIN t2 FOR SYSTEM_TIME AS OF proctime() ON c1 = c2",
+        "SELECT * FROM t1 JOIN t2 FOR SYSTEM_TIME AS OF PROCTIME() ON c1 = c2",
+    );
+    assert!(parse_sql_statements(
+        "SELECT * FROM t1 JOIN t2 FOR SYSTEM_TIME AS OF now() ON c1 = c2"
+    )
+    .is_err());
+}
+
 #[test]
 fn parse_joins_using() {
     fn join_with_constraint(
@@ -2306,6 +2443,7 @@ fn parse_joins_using() {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
                 args: vec![],
+                for_system_time_as_of_proctime: false,
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
         }
@@ -2350,6 +2488,7 @@ fn parse_natural_join() {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
                 args: vec![],
+                for_system_time_as_of_proctime: false,
             },
             join_operator: f(JoinConstraint::Natural),
         }
@@ -2588,6 +2727,7 @@ fn parse_derived_tables() {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
                     args: vec![],
+                    for_system_time_as_of_proctime: false,
                 },
                 join_operator: JoinOperator::Inner(JoinConstraint::Natural),
             }],
@@ -2767,6 +2907,7 @@ fn parse_create_view() {
             or_replace,
             materialized,
             with_options,
+            emit_on_window_close,
         } => {
             assert_eq!("myschema.myview", name.to_string());
             assert_eq!(Vec::<Ident>::new(), columns);
@@ -2774,6 +2915,7 @@ fn parse_create_view() {
             assert!(!materialized);
             assert!(!or_replace);
             assert_eq!(with_options, vec![]);
+            assert!(!emit_on_window_close);
         }
         _ => unreachable!(),
     }
@@ -2813,13 +2955,15 @@ fn parse_create_view_with_columns() {
             with_options,
             query,
             materialized,
+            emit_on_window_close,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![Ident::new("has"), Ident::new("cols")]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1, 2", query.to_string());
             assert!(!materialized);
-            assert!(!or_replace)
+            assert!(!or_replace);
+            assert!(!emit_on_window_close);
         }
         _ => unreachable!(),
     }
@@ -2835,13 +2979,15 @@ fn parse_create_or_replace_view() {
             with_options,
             query,
             materialized,
+            emit_on_window_close,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1", query.to_string());
             assert!(!materialized);
-            assert!(or_replace)
+            assert!(or_replace);
+            assert!(!emit_on_window_close);
         }
         _ => unreachable!(),
     }
@@ -2862,13 +3008,15 @@ fn parse_create_or_replace_materialized_view() {
             with_options,
             query,
             materialized,
+            emit_on_window_close,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1", query.to_string());
             assert!(materialized);
-            assert!(or_replace)
+            assert!(or_replace);
+            assert!(!emit_on_window_close);
         }
         _ => unreachable!(),
     }
@@ -2885,6 +3033,7 @@ fn parse_create_materialized_view() {
             query,
             materialized,
             with_options,
+            emit_on_window_close,
         } => {
             assert_eq!("myschema.myview", name.to_string());
             assert_eq!(Vec::<Ident>::new(), columns);
@@ -2892,9 +3041,34 @@ fn parse_create_materialized_view() {
             assert!(materialized);
             assert_eq!(with_options, vec![]);
             assert!(!or_replace);
+            assert!(!emit_on_window_close);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn parse_create_materialized_view_emit_on_window_close() {
+    let sql = "CREATE MATERIALIZED VIEW mv AS SELECT window_start, COUNT(*) FROM t GROUP BY window_start EMIT ON WINDOW CLOSE";
+    match verified_stmt(sql) {
+        Statement::CreateView {
+            query,
+            materialized,
+            emit_on_window_close,
+            ..
+        } => {
+            assert_eq!(
+                "SELECT window_start, COUNT(*) FROM t GROUP BY window_start",
+                query.to_string()
+            );
+            assert!(materialized);
+            assert!(emit_on_window_close);
         }
         _ => unreachable!(),
     }
+
+    let sql = "CREATE VIEW v AS SELECT * FROM t EMIT ON WINDOW CLOSE";
+    assert!(parse_sql_statements(sql).is_err());
 }
 
 #[test]
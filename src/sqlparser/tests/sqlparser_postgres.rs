@@ -743,6 +743,84 @@ fn parse_map_access_expr() {
     );
 }
 
+#[test]
+fn parse_array_subscript() {
+    let sql = "SELECT (ARRAY[1, 2])[i] FROM foos";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        &MapAccess {
+            column: Box::new(Expr::Nested(Box::new(Expr::Array(vec![
+                Expr::Value(number("1")),
+                Expr::Value(number("2")),
+            ])))),
+            keys: vec![Identifier(Ident::new("i"))]
+        },
+        expr_from_projection(only(&select.projection)),
+    );
+}
+
+#[test]
+fn parse_any_op() {
+    let sql = "SELECT * FROM foos WHERE a = ANY(b)";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        Expr::BinaryOp {
+            left: Box::new(Identifier(Ident::new("a"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::AnyOp(Box::new(Identifier(Ident::new("b"))))),
+        },
+        select.selection.unwrap(),
+    );
+    one_statement_parses_to(
+        "SELECT * FROM foos WHERE a <> SOME(ARRAY[1, 2])",
+        "SELECT * FROM foos WHERE a <> ANY(ARRAY[1, 2])",
+    );
+}
+
+#[test]
+fn parse_struct_field_access() {
+    let select = verified_only_select("SELECT (country).city.address FROM t");
+    assert_eq!(
+        &Expr::FieldIdentifier(
+            Box::new(Identifier(Ident::new("country"))),
+            vec![Ident::new("city"), Ident::new("address")],
+        ),
+        expr_from_projection(only(&select.projection)),
+    );
+    one_statement_parses_to(
+        "SELECT ((t.country).city).address FROM t",
+        "SELECT (t.country).city.address FROM t",
+    );
+}
+
+#[test]
+fn parse_struct_data_type() {
+    let sql = "SELECT CAST(ROW(1, ROW('a')) AS STRUCT<a INT, b STRUCT<c CHARACTER VARYING>>)";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        &Expr::Cast {
+            expr: Box::new(Expr::Row(vec![
+                Expr::Value(number("1")),
+                Expr::Row(vec![Expr::Value(Value::SingleQuotedString("a".into()))]),
+            ])),
+            data_type: DataType::Struct(vec![
+                StructField {
+                    name: Ident::new("a"),
+                    data_type: DataType::Int(None),
+                },
+                StructField {
+                    name: Ident::new("b"),
+                    data_type: DataType::Struct(vec![StructField {
+                        name: Ident::new("c"),
+                        data_type: DataType::Varchar(None),
+                    }]),
+                },
+            ]),
+        },
+        expr_from_projection(only(&select.projection)),
+    );
+}
+
 #[test]
 fn test_transaction_statement() {
     let statement = verified_stmt("SET TRANSACTION SNAPSHOT '000003A1-1'");
@@ -80,6 +80,25 @@ impl SharedBufferBatch {
     pub fn epoch(&self) -> u64 {
         self.epoch
     }
+
+    /// Returns the batch without the items whose user keys start with any of `prefixes`, or
+    /// `None` if no item is left.
+    pub fn without_prefixes(&self, prefixes: &[Vec<u8>]) -> Option<Self> {
+        let items = self
+            .inner
+            .iter()
+            .filter(|(k, _)| {
+                let user_key = key::user_key(k);
+                !prefixes.iter().any(|p| user_key.starts_with(p))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            None
+        } else {
+            Some(Self::new(items, self.epoch))
+        }
+    }
 }
 
 pub struct SharedBufferBatchIterator<const DIRECTION: usize> {
@@ -214,6 +214,25 @@ impl SharedBufferManager {
         self.shared_buffer.write().remove(&epoch);
     }
 
+    /// Discards the writes of all epochs not synced yet whose user keys start with any of
+    /// `prefixes`, so that a later sync won't commit them.
+    pub fn discard_by_prefixes(&self, prefixes: Vec<Vec<u8>>) -> HummockResult<()> {
+        {
+            let mut guard = self.shared_buffer.write();
+            for batches in guard.values_mut() {
+                *batches = batches
+                    .values()
+                    .filter_map(|b| b.without_prefixes(&prefixes))
+                    .map(|b| (b.end_user_key().to_vec(), b))
+                    .collect();
+            }
+            guard.retain(|_, batches| !batches.is_empty());
+        }
+        self.uploader_tx
+            .send(SharedBufferUploaderItem::Discard(prefixes))
+            .map_err(HummockError::shared_buffer_error)
+    }
+
     #[cfg(test)]
     pub fn get_shared_buffer(&self) -> BTreeMap<u64, BTreeMap<Vec<u8>, SharedBufferBatch>> {
         self.shared_buffer.read().clone()
@@ -694,4 +713,36 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_shared_buffer_manager_discard_by_prefixes() {
+        let shared_buffer_manager = new_shared_buffer_manager().await;
+
+        let keys = ["a_1", "a_2", "b_1", "b_2"]
+            .iter()
+            .map(|k| k.as_bytes().to_vec())
+            .collect_vec();
+        let mut idx = 0;
+        let epoch1 = 1;
+        let shared_buffer_items1 =
+            generate_and_write_batch(&keys, &[], epoch1, &mut idx, &shared_buffer_manager);
+        let epoch2 = 2;
+        generate_and_write_batch(&keys[..2], &[], epoch2, &mut idx, &shared_buffer_manager);
+
+        // Discard keys with prefix `a`. Expect only keys with prefix `b` are left in all epochs.
+        shared_buffer_manager
+            .discard_by_prefixes(vec![b"a".to_vec()])
+            .unwrap();
+        for (idx, key) in keys.iter().enumerate() {
+            let value = shared_buffer_manager.get(key.as_slice(), ..=epoch2);
+            if idx < 2 {
+                assert_eq!(value, None);
+            } else {
+                assert_eq!(value.unwrap(), shared_buffer_items1[idx].1);
+            }
+        }
+        let shared_buffer = shared_buffer_manager.get_shared_buffer();
+        assert!(!shared_buffer.contains_key(&epoch2));
+        assert_eq!(shared_buffer[&epoch1].len(), 1);
+    }
 }
@@ -41,6 +41,8 @@ pub enum SharedBufferUploaderItem {
     Batch(SharedBufferBatch),
     Sync(SyncItem),
     Reset(u64),
+    /// Drops the pending writes whose user keys start with any of the prefixes.
+    Discard(Vec<Vec<u8>>),
 }
 
 pub struct SharedBufferUploader {
@@ -88,16 +90,29 @@ impl SharedBufferUploader {
         }
     }
 
-    /// Uploads buffer batches to S3.
+    /// Uploads buffer batches of the epoch and the epochs before it to S3. The epochs not synced
+    /// by a checkpoint are merged into the SSTs of the given epoch.
     async fn sync(&mut self, epoch: u64) -> HummockResult<()> {
+        let epochs = self
+            .batches_to_upload
+            .range(..=epoch)
+            .map(|(&e, _)| e)
+            .collect_vec();
         if let Some(detector) = &self.write_conflict_detector {
+            epochs
+                .iter()
+                .filter(|&&e| e < epoch)
+                .for_each(|&e| detector.archive_epoch(e));
             detector.archive_epoch(epoch);
         }
 
-        let buffers = match self.batches_to_upload.remove(&epoch) {
-            Some(m) => m,
-            None => return Ok(()),
-        };
+        let buffers = epochs
+            .into_iter()
+            .flat_map(|e| self.batches_to_upload.remove(&e).unwrap())
+            .collect_vec();
+        if buffers.is_empty() {
+            return Ok(());
+        }
 
         // Compact buffers into SSTs
         let mem_compactor_ctx = CompactorContext {
@@ -175,12 +190,10 @@ impl SharedBufferUploader {
                     }
                 };
 
-                if let Some(tx) = sync_item.notifier {
-                    tx.send(res).map_err(|_| {
-                        HummockError::shared_buffer_error(
-                            "Failed to notify shared buffer sync because of send drop",
-                        )
-                    })?;
+                // The waiter may have been cancelled, e.g. the barrier collection cancelled by the
+                // meta service during recovery, which should not stop the uploader.
+                if let Some(tx) = sync_item.notifier && tx.send(res).is_err() {
+                    tracing::warn!("Failed to notify shared buffer sync because of send drop");
                 }
                 Ok(())
             }
@@ -188,6 +201,16 @@ impl SharedBufferUploader {
                 self.batches_to_upload.remove(&epoch);
                 Ok(())
             }
+            SharedBufferUploaderItem::Discard(prefixes) => {
+                for batches in self.batches_to_upload.values_mut() {
+                    *batches = batches
+                        .iter()
+                        .filter_map(|b| b.without_prefixes(&prefixes))
+                        .collect_vec();
+                }
+                self.batches_to_upload.retain(|_, batches| !batches.is_empty());
+                Ok(())
+            }
         }
     }
 
@@ -138,6 +138,68 @@ async fn test_snapshot() {
     assert_count_range_scan!(hummock_storage, .., 2, epoch1);
 }
 
+#[tokio::test]
+async fn test_sync_merges_previous_epochs() {
+    let remote_dir = "hummock_001";
+    let object_store = Arc::new(ObjectStoreImpl::Mem(InMemObjectStore::new()));
+    let sstable_store = Arc::new(SstableStore::new(
+        object_store.clone(),
+        remote_dir.to_string(),
+        Arc::new(StateStoreMetrics::unused()),
+        64 << 20,
+        64 << 20,
+    ));
+    let vm = Arc::new(LocalVersionManager::new(sstable_store.clone()));
+    let (_env, hummock_manager_ref, _cluster_manager_ref, worker_node) =
+        setup_compute_env(8080).await;
+    let mock_hummock_meta_client = Arc::new(MockHummockMetaClient::new(
+        hummock_manager_ref.clone(),
+        worker_node.id,
+    ));
+
+    let hummock_options = Arc::new(default_config_for_test());
+    let hummock_storage = HummockStorage::with_default_stats(
+        hummock_options,
+        sstable_store,
+        vm.clone(),
+        mock_hummock_meta_client.clone(),
+        Arc::new(StateStoreMetrics::unused()),
+    )
+    .await
+    .unwrap();
+
+    // Epoch 1 is not a checkpoint, so it's synced and committed along with epoch 2.
+    let epoch1: u64 = 1;
+    hummock_storage
+        .ingest_batch(
+            vec![
+                (Bytes::from("1"), StorageValue::new_default_put("test")),
+                (Bytes::from("2"), StorageValue::new_default_put("test")),
+            ],
+            epoch1,
+        )
+        .await
+        .unwrap();
+    let epoch2 = epoch1 + 1;
+    hummock_storage
+        .ingest_batch(
+            vec![
+                (Bytes::from("1"), StorageValue::new_default_delete()),
+                (Bytes::from("3"), StorageValue::new_default_put("test")),
+            ],
+            epoch2,
+        )
+        .await
+        .unwrap();
+    hummock_storage.sync(Some(epoch2)).await.unwrap();
+    mock_hummock_meta_client.commit_epoch(epoch2).await.unwrap();
+    vm.refresh_version(mock_hummock_meta_client.as_ref()).await;
+
+    let version = hummock_manager_ref.get_current_version().await;
+    assert!(version.uncommitted_epochs.is_empty());
+    assert_count_range_scan!(hummock_storage, .., 2, epoch2);
+}
+
 #[tokio::test]
 async fn test_snapshot_range_scan() {
     let object_store = Arc::new(ObjectStoreImpl::Mem(InMemObjectStore::new()));
@@ -159,6 +159,22 @@ impl<S: StateStore> Keyspace<S> {
         Ok(pairs)
     }
 
+    /// Scans the keys of the keyspace smaller than `[prefix | end_key]`, and then strips the prefix
+    /// of this keyspace.
+    /// The returned values are based on a snapshot corresponding to the given `epoch`
+    pub async fn scan_strip_prefix_before(
+        &self,
+        end_key: &[u8],
+        epoch: u64,
+    ) -> StorageResult<Vec<(Bytes, Bytes)>> {
+        let range = self.prefix.to_owned()..self.prefixed_key(end_key);
+        let mut pairs = self.store.scan(range, None, epoch).await?;
+        pairs
+            .iter_mut()
+            .for_each(|(k, _v)| *k = k.slice(self.prefix.len()..));
+        Ok(pairs)
+    }
+
     /// Gets an iterator with the prefix of this keyspace.
     /// The returned iterator will iterate data from a snapshot corresponding to the given `epoch`
     pub async fn iter(&'_ self, epoch: u64) -> StorageResult<S::Iter<'_>> {
@@ -153,7 +153,7 @@ pub trait StateStore: Send + Sync + 'static + Clone {
 
     /// Syncs buffered data to S3.
     /// If the epoch is None, all buffered data will be synced.
-    /// Otherwise, only data of the provided epoch will be synced.
+    /// Otherwise, data of the provided epoch and the epochs before it will be synced.
     fn sync(&self, epoch: Option<u64>) -> Self::SyncFuture<'_>;
 
     /// Creates a [`MonitoredStateStore`] from this state store, with given `stats`.
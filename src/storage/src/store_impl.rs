@@ -58,6 +58,18 @@ impl StateStoreImpl {
     pub fn shared_in_memory_store(state_store_metrics: Arc<StateStoreMetrics>) -> Self {
         Self::MemoryStateStore(MemoryStateStore::shared().monitored(state_store_metrics))
     }
+
+    /// Discards the writes not synced yet whose keys start with any of `prefixes`. Only Hummock
+    /// buffers writes until sync, while other state stores write through and ignore it.
+    pub fn discard_unsynced(&self, prefixes: Vec<Vec<u8>>) -> StorageResult<()> {
+        if let StateStoreImpl::HummockStateStore(hummock) = self {
+            hummock
+                .inner()
+                .shared_buffer_manager()
+                .discard_by_prefixes(prefixes)?;
+        }
+        Ok(())
+    }
 }
 
 impl Debug for StateStoreImpl {
@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use risingwave_common::error::Result;
+use risingwave_expr::expr::set_stream_epoch;
 use tracing_futures::Instrument;
 
 use super::StreamConsumer;
@@ -63,6 +64,9 @@ impl Actor {
                         .lock_barrier_manager()
                         .collect(self.id, &barrier)?;
 
+                    // bind `NOW()` evaluated in the following epoch to the barrier
+                    set_stream_epoch(barrier.epoch.curr);
+
                     // then stop this actor if asked
                     let to_stop = barrier.is_to_stop_actor(self.id);
                     if to_stop {
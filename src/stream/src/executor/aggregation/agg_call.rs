@@ -59,4 +59,7 @@ pub struct AggCall {
     pub args: AggArgs,
     /// The return type of aggregation function.
     pub return_type: DataType,
+    /// Whether the stream is append-only, so that the states need no retraction, e.g. `MIN` and
+    /// `MAX` can keep a single value instead of all the input values.
+    pub append_only: bool,
 }
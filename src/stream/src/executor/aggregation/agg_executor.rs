@@ -19,7 +19,7 @@ use risingwave_common::array::column::Column;
 use risingwave_common::array::{ArrayBuilderImpl, ArrayImpl, ArrayRef, Op};
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::Result;
-use risingwave_common::types::Datum;
+use risingwave_common::types::{estimated_datum_size, Datum};
 use risingwave_storage::StateStore;
 
 use super::AggCall;
@@ -56,6 +56,19 @@ impl<S: StateStore> AggState<S> {
             .unwrap_or(0))
     }
 
+    /// Estimates the memory held by the states in bytes.
+    pub fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self
+                .managed_states
+                .iter()
+                .map(ManagedStateImpl::estimated_size)
+                .sum::<usize>()
+            + self.prev_states.as_ref().map_or(0, |states| {
+                states.iter().map(estimated_datum_size).sum::<usize>()
+            })
+    }
+
     pub fn prev_row_count(&self) -> i64 {
         match &self.prev_states {
             Some(states) => states[ROW_COUNT_COLUMN]
@@ -88,6 +101,13 @@ impl<S: StateStore> AggState<S> {
         Ok(())
     }
 
+    /// Discard the previous outputs recorded by `may_mark_as_dirty` without building any changes,
+    /// e.g., for aggregations that `EMIT ON WINDOW CLOSE`.
+    /// After calling this function, `self.is_dirty()` will return `false`.
+    pub fn discard_changes(&mut self) {
+        self.prev_states = None;
+    }
+
     /// Build changes into `builders` and `new_ops`, according to previous and current states. Note
     /// that for [`crate::executor_v2::HashAggExecutor`].
     ///
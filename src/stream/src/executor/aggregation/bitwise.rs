@@ -0,0 +1,204 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module implements `StreamingBitAgg`.
+
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::*;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, Datum, ScalarImpl, ScalarRefImpl};
+use risingwave_expr::expr::AggKind;
+
+use super::StreamingAggStateImpl;
+
+const BITS: usize = 64;
+
+/// `StreamingBitAgg` computes `bit_and` and `bit_or` over integers. It counts, for each bit, the
+/// inputs with that bit set, so that inputs can be retracted.
+#[derive(Clone, Debug)]
+pub struct StreamingBitAgg {
+    is_and: bool,
+    return_type: DataType,
+    /// Number of non-null inputs.
+    count: i64,
+    /// Number of non-null inputs with each bit set.
+    bit_counts: [i64; BITS],
+}
+
+impl StreamingBitAgg {
+    pub fn new(kind: &AggKind, return_type: DataType) -> Self {
+        Self {
+            is_and: match kind {
+                AggKind::BitAnd => true,
+                AggKind::BitOr => false,
+                _ => unreachable!("not a bitwise aggregation: {:?}", kind),
+            },
+            return_type,
+            count: 0,
+            bit_counts: [0; BITS],
+        }
+    }
+
+    /// Restores the aggregation from the persisted state, see [`Self::state_type`].
+    pub fn with_state(kind: &AggKind, return_type: DataType, state: Datum) -> Result<Self> {
+        let mut agg = Self::new(kind, return_type);
+        if let Some(state) = state {
+            let counts = match &state {
+                ScalarImpl::List(list) if list.values().len() == BITS + 1 => list
+                    .values()
+                    .iter()
+                    .map(|v| match v {
+                        Some(ScalarImpl::Int64(v)) => Ok(*v),
+                        _ => Err(Self::err_bad_state(&state)),
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                _ => return Err(Self::err_bad_state(&state)),
+            };
+            agg.count = counts[0];
+            agg.bit_counts.copy_from_slice(&counts[1..]);
+        }
+        Ok(agg)
+    }
+
+    fn err_bad_state(state: &ScalarImpl) -> RwError {
+        ErrorCode::InternalError(format!("invalid state of bitwise aggregation: {:?}", state))
+            .into()
+    }
+
+    /// The type of the persisted state: the number of inputs followed by the number of inputs
+    /// with each bit set.
+    pub fn state_type() -> DataType {
+        DataType::List {
+            datatype: Box::new(DataType::Int64),
+        }
+    }
+}
+
+impl StreamingAggStateImpl for StreamingBitAgg {
+    fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> Result<()> {
+        for (row_idx, op) in ops.iter().enumerate() {
+            let visible = visibility
+                .map(|x| x.is_set(row_idx).unwrap())
+                .unwrap_or(true);
+            if !visible {
+                continue;
+            }
+            let value = match data[0].value_at(row_idx) {
+                Some(ScalarRefImpl::Int16(v)) => v as i64,
+                Some(ScalarRefImpl::Int32(v)) => v as i64,
+                Some(ScalarRefImpl::Int64(v)) => v,
+                None => continue,
+                Some(other) => {
+                    return Err(ErrorCode::InternalError(format!(
+                        "bitwise aggregation over non-integer value {:?}",
+                        other
+                    ))
+                    .into())
+                }
+            };
+            let delta = match op {
+                Op::Insert | Op::UpdateInsert => 1,
+                Op::Delete | Op::UpdateDelete => -1,
+            };
+            self.count += delta;
+            for (bit, count) in self.bit_counts.iter_mut().enumerate() {
+                if value & (1 << bit) != 0 {
+                    *count += delta;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_output(&self) -> Result<Datum> {
+        if self.count == 0 {
+            return Ok(None);
+        }
+        let mut result = 0i64;
+        for (bit, count) in self.bit_counts.iter().enumerate() {
+            let set = if self.is_and {
+                *count == self.count
+            } else {
+                *count > 0
+            };
+            if set {
+                result |= 1 << bit;
+            }
+        }
+        let result = match self.return_type {
+            DataType::Int16 => ScalarImpl::Int16(result as i16),
+            DataType::Int32 => ScalarImpl::Int32(result as i32),
+            _ => ScalarImpl::Int64(result),
+        };
+        Ok(Some(result))
+    }
+
+    fn get_state(&self) -> Result<Datum> {
+        let counts = std::iter::once(self.count)
+            .chain(self.bit_counts.iter().copied())
+            .map(|v| Some(ScalarImpl::Int64(v)))
+            .collect();
+        Ok(Some(ScalarImpl::List(ListValue::new(counts))))
+    }
+
+    fn new_builder(&self) -> ArrayBuilderImpl {
+        self.return_type.create_array_builder(0).unwrap()
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.bit_counts = [0; BITS];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_agg() {
+        let mut bit_and = StreamingBitAgg::new(&AggKind::BitAnd, DataType::Int32);
+        let mut bit_or = StreamingBitAgg::new(&AggKind::BitOr, DataType::Int32);
+        let mut apply = |ops: &[Op], values: &[Option<i32>]| {
+            let data = I32Array::from_slice(values).unwrap().into();
+            bit_and.apply_batch(ops, None, &[&data]).unwrap();
+            bit_or.apply_batch(ops, None, &[&data]).unwrap();
+            (bit_and.get_output().unwrap(), bit_or.get_output().unwrap())
+        };
+        let int = |v: i32| Some(ScalarImpl::Int32(v));
+
+        assert_eq!(apply(&[Op::Insert], &[None]), (None, None));
+        assert_eq!(
+            apply(&[Op::Insert, Op::Insert], &[Some(0b1100), Some(0b0110)]),
+            (int(0b0100), int(0b1110))
+        );
+        assert_eq!(apply(&[Op::Insert], &[Some(-1)]), (int(0b0100), int(-1)));
+        assert_eq!(
+            apply(&[Op::Delete, Op::Delete], &[Some(-1), Some(0b0110)]),
+            (int(0b1100), int(0b1100))
+        );
+
+        // Restore from the persisted state.
+        let state = bit_and.get_state().unwrap();
+        let bit_and =
+            StreamingBitAgg::with_state(&AggKind::BitAnd, DataType::Int32, state).unwrap();
+        assert_eq!(bit_and.get_output().unwrap(), int(0b1100));
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module implements `StreamingBoolAgg`.
+
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::*;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, Datum, ScalarImpl, ScalarRefImpl};
+use risingwave_expr::expr::AggKind;
+
+use super::StreamingAggStateImpl;
+
+/// `StreamingBoolAgg` computes `bool_and` and `bool_or` from the number of true and false
+/// inputs, so that inputs can be retracted.
+#[derive(Clone, Debug)]
+pub struct StreamingBoolAgg {
+    is_and: bool,
+    true_count: i64,
+    false_count: i64,
+}
+
+impl StreamingBoolAgg {
+    pub fn new(kind: &AggKind) -> Self {
+        Self {
+            is_and: match kind {
+                AggKind::BoolAnd => true,
+                AggKind::BoolOr => false,
+                _ => unreachable!("not a boolean aggregation: {:?}", kind),
+            },
+            true_count: 0,
+            false_count: 0,
+        }
+    }
+
+    /// Restores the aggregation from the persisted state, see [`Self::state_type`].
+    pub fn with_state(kind: &AggKind, state: Datum) -> Result<Self> {
+        let mut agg = Self::new(kind);
+        if let Some(state) = state {
+            match &state {
+                ScalarImpl::Struct(s) => match s.fields() {
+                    [Some(ScalarImpl::Int64(true_count)), Some(ScalarImpl::Int64(false_count))] => {
+                        agg.true_count = *true_count;
+                        agg.false_count = *false_count;
+                    }
+                    _ => return Err(Self::err_bad_state(&state)),
+                },
+                _ => return Err(Self::err_bad_state(&state)),
+            }
+        }
+        Ok(agg)
+    }
+
+    fn err_bad_state(state: &ScalarImpl) -> RwError {
+        ErrorCode::InternalError(format!("invalid state of boolean aggregation: {:?}", state))
+            .into()
+    }
+
+    /// The type of the persisted state: the number of true and false inputs.
+    pub fn state_type() -> DataType {
+        DataType::new_struct(
+            vec![DataType::Int64, DataType::Int64],
+            vec!["true_count".to_string(), "false_count".to_string()],
+        )
+    }
+}
+
+impl StreamingAggStateImpl for StreamingBoolAgg {
+    fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> Result<()> {
+        for (row_idx, op) in ops.iter().enumerate() {
+            let visible = visibility
+                .map(|x| x.is_set(row_idx).unwrap())
+                .unwrap_or(true);
+            if !visible {
+                continue;
+            }
+            let count = match data[0].value_at(row_idx) {
+                Some(ScalarRefImpl::Bool(true)) => &mut self.true_count,
+                Some(ScalarRefImpl::Bool(false)) => &mut self.false_count,
+                None => continue,
+                Some(other) => {
+                    return Err(ErrorCode::InternalError(format!(
+                        "boolean aggregation over non-boolean value {:?}",
+                        other
+                    ))
+                    .into())
+                }
+            };
+            match op {
+                Op::Insert | Op::UpdateInsert => *count += 1,
+                Op::Delete | Op::UpdateDelete => *count -= 1,
+            }
+        }
+        Ok(())
+    }
+
+    fn get_output(&self) -> Result<Datum> {
+        if self.true_count + self.false_count == 0 {
+            return Ok(None);
+        }
+        let result = if self.is_and {
+            self.false_count == 0
+        } else {
+            self.true_count > 0
+        };
+        Ok(Some(ScalarImpl::Bool(result)))
+    }
+
+    fn get_state(&self) -> Result<Datum> {
+        Ok(Some(ScalarImpl::Struct(StructValue::new(vec![
+            Some(ScalarImpl::Int64(self.true_count)),
+            Some(ScalarImpl::Int64(self.false_count)),
+        ]))))
+    }
+
+    fn new_builder(&self) -> ArrayBuilderImpl {
+        ArrayBuilderImpl::Bool(BoolArrayBuilder::new(0).unwrap())
+    }
+
+    fn reset(&mut self) {
+        self.true_count = 0;
+        self.false_count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bool_agg() {
+        let mut bool_and = StreamingBoolAgg::new(&AggKind::BoolAnd);
+        let mut bool_or = StreamingBoolAgg::new(&AggKind::BoolOr);
+        let mut apply = |ops: &[Op], values: &[Option<bool>]| {
+            let data = BoolArray::from_slice(values).unwrap().into();
+            bool_and.apply_batch(ops, None, &[&data]).unwrap();
+            bool_or.apply_batch(ops, None, &[&data]).unwrap();
+            (
+                bool_and.get_output().unwrap(),
+                bool_or.get_output().unwrap(),
+            )
+        };
+        let t = Some(ScalarImpl::Bool(true));
+        let f = Some(ScalarImpl::Bool(false));
+
+        assert_eq!(apply(&[Op::Insert], &[None]), (None, None));
+        assert_eq!(
+            apply(&[Op::Insert, Op::Insert], &[Some(true), Some(false)]),
+            (f.clone(), t.clone())
+        );
+        assert_eq!(apply(&[Op::Delete], &[Some(false)]), (t.clone(), t));
+        assert_eq!(
+            apply(
+                &[Op::UpdateDelete, Op::UpdateInsert],
+                &[Some(true), Some(false)]
+            ),
+            (f.clone(), f)
+        );
+    }
+}
@@ -21,7 +21,7 @@ pub use foldable::*;
 use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::{
     Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, BoolArray, DecimalArray, F32Array, F64Array,
-    I16Array, I32Array, I64Array, Utf8Array,
+    I16Array, I32Array, I64Array, IntervalArray, Utf8Array,
 };
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::error::{ErrorCode, Result};
@@ -31,13 +31,19 @@ use risingwave_expr::*;
 pub use row_count::*;
 
 pub use super::aggregation::StreamingRowCountAgg;
+use crate::executor::aggregation::bitwise::StreamingBitAgg;
+use crate::executor::aggregation::boolean::StreamingBoolAgg;
 use crate::executor::aggregation::single_value::StreamingSingleValueAgg;
+use crate::executor::aggregation::variance::StreamingVarianceAgg;
 
 mod agg_call;
 mod agg_executor;
+mod bitwise;
+mod boolean;
 mod foldable;
 mod row_count;
 mod single_value;
+mod variance;
 
 /// `StreamingSumAgg` sums data of the same type.
 pub type StreamingSumAgg<R, I> =
@@ -83,6 +89,13 @@ pub trait StreamingAggStateImpl: Any + std::fmt::Debug + DynClone + Send + Sync
     /// Get the output value
     fn get_output(&self) -> Result<Datum>;
 
+    /// Get the state to persist, from which [`create_streaming_agg_state`] restores the
+    /// aggregation. It is the output itself unless the aggregation needs more than its result to
+    /// handle retractions, see [`streaming_agg_state_type`].
+    fn get_state(&self) -> Result<Datum> {
+        self.get_output()
+    }
+
     /// Get the builder of the state output
     fn new_builder(&self) -> ArrayBuilderImpl;
 
@@ -92,6 +105,23 @@ pub trait StreamingAggStateImpl: Any + std::fmt::Debug + DynClone + Send + Sync
 
 dyn_clone::clone_trait_object!(StreamingAggStateImpl);
 
+/// Returns the type of the state persisted by [`StreamingAggStateImpl::get_state`].
+pub fn streaming_agg_state_type(
+    input_types: &[DataType],
+    agg_type: &AggKind,
+    return_type: &DataType,
+) -> DataType {
+    match (agg_type, input_types) {
+        (
+            AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp,
+            [input_type],
+        ) => StreamingVarianceAgg::state_type(input_type),
+        (AggKind::BoolAnd | AggKind::BoolOr, _) => StreamingBoolAgg::state_type(),
+        (AggKind::BitAnd | AggKind::BitOr, _) => StreamingBitAgg::state_type(),
+        _ => return_type.clone(),
+    }
+}
+
 /// [postgresql specification of aggregate functions](https://www.postgresql.org/docs/13/functions-aggregate.html)
 /// Most of the general-purpose aggregate functions have one input except for:
 /// 1. `count(*) -> bigint`. The input type of count(*)
@@ -134,6 +164,33 @@ pub fn create_streaming_agg_state(
     }
 
     let state: Box<dyn StreamingAggStateImpl> = match input_types {
+        // The following aggregations persist an intermediate state instead of the output, which
+        // `datum` holds.
+        [input_type]
+            if input_type.is_numeric()
+                && matches!(
+                    agg_type,
+                    AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp
+                ) =>
+        {
+            Box::new(StreamingVarianceAgg::with_state(
+                agg_type.clone(),
+                input_type,
+                datum.flatten(),
+            )?)
+        }
+        [DataType::Boolean] if matches!(agg_type, AggKind::BoolAnd | AggKind::BoolOr) => {
+            Box::new(StreamingBoolAgg::with_state(agg_type, datum.flatten())?)
+        }
+        [DataType::Int16 | DataType::Int32 | DataType::Int64]
+            if matches!(agg_type, AggKind::BitAnd | AggKind::BitOr) =>
+        {
+            Box::new(StreamingBitAgg::with_state(
+                agg_type,
+                return_type.clone(),
+                datum.flatten(),
+            )?)
+        }
         [input_type] => {
             gen_unary_agg_state_match!(
                 agg_type,
@@ -178,6 +235,9 @@ pub fn create_streaming_agg_state(
                     (Min, decimal, decimal, StreamingMinAgg::<DecimalArray>),
                     (Min, float32, float32, StreamingMinAgg::<F32Array>),
                     (Min, float64, float64, StreamingMinAgg::<F64Array>),
+                    (Min, boolean, boolean, StreamingMinAgg::<BoolArray>),
+                    (Min, varchar, varchar, StreamingMinAgg::<Utf8Array>),
+                    (Min, interval, interval, StreamingMinAgg::<IntervalArray>),
                     // Max
                     (Max, int16, int16, StreamingMaxAgg::<I16Array>),
                     (Max, int32, int32, StreamingMaxAgg::<I32Array>),
@@ -185,6 +245,9 @@ pub fn create_streaming_agg_state(
                     (Max, decimal, decimal, StreamingMaxAgg::<DecimalArray>),
                     (Max, float32, float32, StreamingMaxAgg::<F32Array>),
                     (Max, float64, float64, StreamingMaxAgg::<F64Array>),
+                    (Max, boolean, boolean, StreamingMaxAgg::<BoolArray>),
+                    (Max, varchar, varchar, StreamingMaxAgg::<Utf8Array>),
+                    (Max, interval, interval, StreamingMaxAgg::<IntervalArray>),
                     (
                         SingleValue,
                         int16,
@@ -0,0 +1,257 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module implements `StreamingVarianceAgg`.
+
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::*;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_expr::expr::AggKind;
+use risingwave_expr::vector_op::agg::{VarianceState, VarianceSums};
+
+use super::StreamingAggStateImpl;
+
+/// `StreamingVarianceAgg` computes `var_pop`, `var_samp`, `stddev_pop` and `stddev_samp` from the
+/// count, sum and sum of squares of the non-null inputs, which can all be retracted.
+#[derive(Clone, Debug)]
+pub struct StreamingVarianceAgg {
+    kind: AggKind,
+    state: VarianceState,
+}
+
+impl StreamingVarianceAgg {
+    pub fn new(kind: AggKind, input_type: &DataType) -> Self {
+        Self {
+            kind,
+            state: VarianceState::new(input_type),
+        }
+    }
+
+    /// Restores the aggregation from the persisted state, see [`Self::state_type`].
+    pub fn with_state(kind: AggKind, input_type: &DataType, state: Datum) -> Result<Self> {
+        let mut agg = Self::new(kind, input_type);
+        if let Some(state) = state {
+            let fields = match &state {
+                ScalarImpl::Struct(s) => s.fields(),
+                _ => return Err(Self::err_bad_state(&state)),
+            };
+            agg.state.sums = match (agg.state.sums, fields) {
+                (VarianceSums::Int { .. }, [_, sum_high, sum_low, sum_sq_high, sum_sq_low]) => {
+                    let word = |datum: &Datum| match datum {
+                        Some(ScalarImpl::Int64(v)) => Ok(*v),
+                        _ => Err(Self::err_bad_state(&state)),
+                    };
+                    VarianceSums::Int {
+                        sum: join_i128(word(sum_high)?, word(sum_low)?),
+                        sum_sq: join_i128(word(sum_sq_high)?, word(sum_sq_low)?),
+                    }
+                }
+                (
+                    VarianceSums::Decimal { .. },
+                    [_, Some(ScalarImpl::Decimal(sum)), Some(ScalarImpl::Decimal(sum_sq))],
+                ) => VarianceSums::Decimal {
+                    sum: *sum,
+                    sum_sq: *sum_sq,
+                },
+                (
+                    VarianceSums::Float { .. },
+                    [_, Some(ScalarImpl::Float64(sum)), Some(ScalarImpl::Float64(sum_sq))],
+                ) => VarianceSums::Float {
+                    sum: sum.0,
+                    sum_sq: sum_sq.0,
+                },
+                _ => return Err(Self::err_bad_state(&state)),
+            };
+            agg.state.count = match fields.first() {
+                Some(Some(ScalarImpl::Int64(count))) => *count,
+                _ => return Err(Self::err_bad_state(&state)),
+            };
+        }
+        Ok(agg)
+    }
+
+    fn err_bad_state(state: &ScalarImpl) -> RwError {
+        ErrorCode::InternalError(format!(
+            "invalid state of variance aggregation: {:?}",
+            state
+        ))
+        .into()
+    }
+
+    /// The type of the persisted state: the count, sum and sum of squares. Sums of integers are
+    /// exact `i128`s, each persisted as its high and low 64 bits.
+    pub fn state_type(input_type: &DataType) -> DataType {
+        let (types, names) = match VarianceState::new(input_type).sums {
+            VarianceSums::Int { .. } => (
+                vec![DataType::Int64; 5],
+                vec!["count", "sum_high", "sum_low", "sum_sq_high", "sum_sq_low"],
+            ),
+            VarianceSums::Decimal { .. } => (
+                vec![DataType::Int64, DataType::Decimal, DataType::Decimal],
+                vec!["count", "sum", "sum_sq"],
+            ),
+            VarianceSums::Float { .. } => (
+                vec![DataType::Int64, DataType::Float64, DataType::Float64],
+                vec!["count", "sum", "sum_sq"],
+            ),
+        };
+        DataType::new_struct(types, names.into_iter().map(String::from).collect())
+    }
+}
+
+fn split_i128(v: i128) -> [Datum; 2] {
+    [
+        Some(ScalarImpl::Int64((v >> 64) as i64)),
+        Some(ScalarImpl::Int64(v as i64)),
+    ]
+}
+
+fn join_i128(high: i64, low: i64) -> i128 {
+    ((high as i128) << 64) | (low as u64 as i128)
+}
+
+impl StreamingAggStateImpl for StreamingVarianceAgg {
+    fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> Result<()> {
+        for (row_idx, op) in ops.iter().enumerate() {
+            let visible = visibility
+                .map(|x| x.is_set(row_idx).unwrap())
+                .unwrap_or(true);
+            if !visible {
+                continue;
+            }
+            if let Some(scalar) = data[0].value_at(row_idx) {
+                match op {
+                    Op::Insert | Op::UpdateInsert => self.state.accumulate(scalar)?,
+                    Op::Delete | Op::UpdateDelete => self.state.retract(scalar)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_output(&self) -> Result<Datum> {
+        Ok(self
+            .state
+            .output(&self.kind)?
+            .map(|v| ScalarImpl::Float64(v.into())))
+    }
+
+    fn get_state(&self) -> Result<Datum> {
+        let mut fields = vec![Some(ScalarImpl::Int64(self.state.count))];
+        match self.state.sums {
+            VarianceSums::Int { sum, sum_sq } => {
+                fields.extend(split_i128(sum));
+                fields.extend(split_i128(sum_sq));
+            }
+            VarianceSums::Decimal { sum, sum_sq } => {
+                fields.push(Some(ScalarImpl::Decimal(sum)));
+                fields.push(Some(ScalarImpl::Decimal(sum_sq)));
+            }
+            VarianceSums::Float { sum, sum_sq } => {
+                fields.push(Some(ScalarImpl::Float64(sum.into())));
+                fields.push(Some(ScalarImpl::Float64(sum_sq.into())));
+            }
+        }
+        Ok(Some(ScalarImpl::Struct(StructValue::new(fields))))
+    }
+
+    fn new_builder(&self) -> ArrayBuilderImpl {
+        ArrayBuilderImpl::Float64(F64ArrayBuilder::new(0).unwrap())
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variance_agg() {
+        let mut agg = StreamingVarianceAgg::new(AggKind::StddevSamp, &DataType::Int32);
+        agg.apply_batch(
+            &[Op::Insert, Op::Insert, Op::Insert, Op::Insert],
+            None,
+            &[&I32Array::from_slice(&[Some(1), Some(3), None, Some(100)])
+                .unwrap()
+                .into()],
+        )
+        .unwrap();
+        agg.apply_batch(
+            &[Op::Delete],
+            None,
+            &[&I32Array::from_slice(&[Some(100)]).unwrap().into()],
+        )
+        .unwrap();
+        let expected = Some(ScalarImpl::Float64(2f64.sqrt().into()));
+        assert_eq!(agg.get_output().unwrap(), expected);
+
+        // Restore from the persisted state.
+        let mut agg = StreamingVarianceAgg::with_state(
+            AggKind::StddevSamp,
+            &DataType::Int32,
+            agg.get_state().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(agg.get_output().unwrap(), expected);
+        agg.apply_batch(
+            &[Op::Delete],
+            None,
+            &[&I32Array::from_slice(&[Some(1)]).unwrap().into()],
+        )
+        .unwrap();
+        assert_eq!(agg.get_output().unwrap(), None);
+    }
+
+    #[test]
+    fn test_variance_agg_exact_state() {
+        // Negative sums and sums beyond 64 bits survive persistence.
+        let mut agg = StreamingVarianceAgg::new(AggKind::VarPop, &DataType::Int64);
+        agg.apply_batch(
+            &[Op::Insert, Op::Insert, Op::Insert],
+            None,
+            &[
+                &I64Array::from_slice(&[Some(-(1 << 62) - 1), Some(-(1 << 62) - 3), Some(-7)])
+                    .unwrap()
+                    .into(),
+            ],
+        )
+        .unwrap();
+        let mut agg = StreamingVarianceAgg::with_state(
+            AggKind::VarPop,
+            &DataType::Int64,
+            agg.get_state().unwrap(),
+        )
+        .unwrap();
+        agg.apply_batch(
+            &[Op::Delete],
+            None,
+            &[&I64Array::from_slice(&[Some(-7)]).unwrap().into()],
+        )
+        .unwrap();
+        assert_eq!(
+            agg.get_output().unwrap(),
+            Some(ScalarImpl::Float64(1f64.into()))
+        );
+    }
+}
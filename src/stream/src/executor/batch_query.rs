@@ -24,8 +24,10 @@ use risingwave_storage::monitor::StateStoreMetrics;
 use risingwave_storage::table::cell_based_table::CellBasedTable;
 use risingwave_storage::{Keyspace, StateStore};
 
-use crate::executor::{Executor, ExecutorBuilder};
-use crate::executor_v2::{BatchQueryExecutor as BatchQueryExecutorV2, Executor as ExecutorV2};
+use crate::executor::ExecutorBuilder;
+use crate::executor_v2::{
+    BatchQueryExecutor as BatchQueryExecutorV2, BoxedExecutor, Executor, ExecutorInfo,
+};
 use crate::task::{ExecutorParams, LocalStreamManagerCore};
 
 pub struct BatchQueryExecutorBuilder;
@@ -36,7 +38,7 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
         node: &stream_plan::StreamNode,
         state_store: impl StateStore,
         _stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node().unwrap(), Node::BatchPlanNode)?;
         let table_id = TableId::from(&node.table_ref_id);
         let column_descs = node
@@ -56,13 +58,17 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
             .map(|key| *key as usize)
             .collect::<Vec<_>>();
 
-        let v2 = Box::new(BatchQueryExecutorV2::new_from_v1(
+        let info = ExecutorInfo {
+            schema: table.schema().clone(),
+            pk_indices: params.pk_indices,
+            identity: "BatchQuery".to_owned(),
+        };
+        Ok(BatchQueryExecutorV2::new(
             table,
-            params.pk_indices,
-            params.op_info,
+            BatchQueryExecutorV2::DEFAULT_BATCH_SIZE,
+            info,
             key_indices,
-        ));
-
-        Ok(Box::new(v2.v1_uninited()))
+        )
+        .boxed())
     }
 }
@@ -18,9 +18,11 @@ use risingwave_pb::stream_plan;
 use risingwave_pb::stream_plan::stream_node::Node;
 use risingwave_storage::StateStore;
 
-use super::Executor;
 use crate::executor::ExecutorBuilder;
-use crate::executor_v2::{ChainExecutor as ChainExecutorV2, Executor as ExecutorV2};
+use crate::executor_v2::{
+    BoxedExecutor, ChainExecutor as ChainExecutorV2, Executor, ExecutorInfo,
+    RearrangedChainExecutor,
+};
 use crate::task::{ExecutorParams, LocalStreamManagerCore};
 
 pub struct ChainExecutorBuilder;
@@ -31,7 +33,7 @@ impl ExecutorBuilder for ChainExecutorBuilder {
         node: &stream_plan::StreamNode,
         _store: impl StateStore,
         stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node().unwrap(), Node::ChainNode)?;
         let snapshot = params.input.remove(1);
         let mview = params.input.remove(0);
@@ -49,16 +51,42 @@ impl ExecutorBuilder for ChainExecutorBuilder {
         // The batch query executor scans on a mapped adhoc mview table, thus we should directly use
         // its schema.
         let schema = snapshot.schema().clone();
+        let pk_indices = mview.pk_indices().to_owned();
 
-        let v2 = Box::new(ChainExecutorV2::new_from_v1(
-            snapshot,
-            mview,
-            notifier,
-            schema,
-            column_idxs,
-            params.op_info,
-        ));
-
-        Ok(Box::new(v2.v1()))
+        // Rearrange the barriers of the upstream so that they are not blocked by the snapshot,
+        // otherwise the checkpoints are stalled, and the progress can not be reported. A throttled
+        // snapshot is always rearranged, as its rate limit is changed by the barriers.
+        if params.env.config().rearranged_chain_enabled || node.rate_limit > 0 {
+            let info = ExecutorInfo {
+                schema,
+                pk_indices,
+                identity: "RearrangedChain".to_owned(),
+            };
+            Ok(RearrangedChainExecutor::new(
+                snapshot,
+                mview,
+                column_idxs,
+                notifier,
+                params.actor_id,
+                info,
+                (node.rate_limit > 0).then(|| node.rate_limit),
+            )
+            .boxed())
+        } else {
+            let info = ExecutorInfo {
+                schema,
+                pk_indices,
+                identity: "Chain".to_owned(),
+            };
+            Ok(ChainExecutorV2::new(
+                snapshot,
+                mview,
+                column_idxs,
+                notifier,
+                params.actor_id,
+                info,
+            )
+            .boxed())
+        }
     }
 }
@@ -12,64 +12,56 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::Debug;
+use futures::{StreamExt, TryStreamExt};
 
-use async_trait::async_trait;
-use risingwave_common::error::Result;
+use super::{execute_input, DebugExecutor};
+use crate::executor::Message;
+use crate::executor_v2::{BoxedExecutor, BoxedMessageStream, Executor};
 
-use crate::executor::{Executor, Message};
-
-#[derive(Debug)]
+/// [`EpochCheckExecutor`] checks that the epochs of the barriers passing from `input` executor are
+/// not stale.
 pub struct EpochCheckExecutor {
     /// The input of the current executor.
-    input: Box<dyn Executor>,
-
-    /// Epoch number recorded from last barrier message.
-    last_epoch: Option<u64>,
+    input: BoxedExecutor,
 }
 
 impl EpochCheckExecutor {
-    pub fn new(input: Box<dyn Executor>) -> Self {
-        Self {
-            input,
-            last_epoch: None,
-        }
+    pub fn new(input: BoxedExecutor) -> Self {
+        Self { input }
     }
 }
 
-#[async_trait]
-impl super::DebugExecutor for EpochCheckExecutor {
-    async fn next(&mut self) -> Result<Message> {
-        let message = self.input.next().await?;
-
-        if let Message::Barrier(b) = &message {
-            let new_epoch = b.epoch.curr;
-            let stale = self
-                .last_epoch
-                .map(|last_epoch| last_epoch > new_epoch)
-                .unwrap_or(false);
-
-            if stale {
-                panic!(
-                    "epoch check failed on {}: last epoch is {:?}, while the epoch of incoming barrier is {}.\nstale barrier: {:?}",
-                    self.input.identity(),
-                    self.last_epoch,
-                    new_epoch,
-                    b
-                );
-            }
-            self.last_epoch = Some(new_epoch);
-        }
-
-        Ok(message)
-    }
-
+impl DebugExecutor for EpochCheckExecutor {
     fn input(&self) -> &dyn Executor {
         self.input.as_ref()
     }
 
-    fn input_mut(&mut self) -> &mut dyn Executor {
-        self.input.as_mut()
+    fn execute_inner(self: Box<Self>, epoch: Option<u64>) -> BoxedMessageStream {
+        let identity = self.input.identity().to_owned();
+        // Epoch number recorded from last barrier message.
+        let mut last_epoch = None;
+
+        execute_input(self.input, epoch)
+            .inspect_ok(move |message| {
+                if let Message::Barrier(b) = message {
+                    let new_epoch = b.epoch.curr;
+                    let stale = last_epoch
+                        .map(|last_epoch| last_epoch > new_epoch)
+                        .unwrap_or(false);
+
+                    if stale {
+                        panic!(
+                            "epoch check failed on {}: last epoch is {:?}, while the epoch of incoming barrier is {}.\nstale barrier: {:?}",
+                            identity,
+                            last_epoch,
+                            new_epoch,
+                            b
+                        );
+                    }
+                    last_epoch = Some(new_epoch);
+                }
+            })
+            .boxed()
     }
 }
 
@@ -79,7 +71,7 @@ mod tests {
     use risingwave_common::array::StreamChunk;
 
     use super::*;
-    use crate::executor::test_utils::MockSource;
+    use crate::executor_v2::test_utils::MockSource;
 
     #[tokio::test]
     async fn test_epoch_ok() {
@@ -89,11 +81,11 @@ mod tests {
         source.push_barrier(114, false);
         source.push_barrier(514, false);
 
-        let mut checked = EpochCheckExecutor::new(Box::new(source));
-        assert_matches!(checked.next().await.unwrap(), Message::Chunk(_));
-        assert_matches!(checked.next().await.unwrap(), Message::Barrier(b) if b.epoch.curr == 114);
-        assert_matches!(checked.next().await.unwrap(), Message::Barrier(b) if b.epoch.curr == 114);
-        assert_matches!(checked.next().await.unwrap(), Message::Barrier(b) if b.epoch.curr == 514);
+        let mut checked = Box::new(EpochCheckExecutor::new(Box::new(source))).execute();
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Chunk(_));
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Barrier(b) if b.epoch.curr == 114);
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Barrier(b) if b.epoch.curr == 114);
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Barrier(b) if b.epoch.curr == 514);
     }
 
     #[should_panic]
@@ -105,11 +97,11 @@ mod tests {
         source.push_barrier(514, false);
         source.push_barrier(114, false);
 
-        let mut checked = EpochCheckExecutor::new(Box::new(source));
-        assert_matches!(checked.next().await.unwrap(), Message::Chunk(_));
-        assert_matches!(checked.next().await.unwrap(), Message::Barrier(b) if b.epoch.curr == 514);
-        assert_matches!(checked.next().await.unwrap(), Message::Barrier(b) if b.epoch.curr == 514);
+        let mut checked = Box::new(EpochCheckExecutor::new(Box::new(source))).execute();
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Chunk(_));
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Barrier(b) if b.epoch.curr == 514);
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Barrier(b) if b.epoch.curr == 514);
 
-        checked.next().await.unwrap(); // should panic
+        checked.next().await.unwrap().unwrap(); // should panic
     }
 }
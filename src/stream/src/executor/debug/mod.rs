@@ -12,63 +12,55 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod cache_clear;
 mod epoch_check;
 mod schema_check;
 mod trace;
-use std::fmt::Debug;
 
-use async_trait::async_trait;
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::Result;
 
-pub use self::cache_clear::*;
 pub use self::epoch_check::*;
 pub use self::schema_check::*;
 pub use self::trace::*;
-use super::{Executor, Message};
+use crate::executor_v2::{BoxedExecutor, BoxedMessageStream, Executor, PkIndicesRef};
 
 /// [`DebugExecutor`] is an abstraction of wrapper executors, generally used for debug purpose. Data
 /// related functions are mostly delegated to the `input` executor.
-#[async_trait]
-pub trait DebugExecutor: Send + Debug + 'static {
-    async fn next(&mut self) -> Result<Message>;
-
+pub trait DebugExecutor: Send + 'static {
     fn input(&self) -> &dyn Executor;
 
-    fn input_mut(&mut self) -> &mut dyn Executor;
+    /// Executes the `input` executor, with the epoch if given, and wraps its message stream.
+    fn execute_inner(self: Box<Self>, epoch: Option<u64>) -> BoxedMessageStream;
 }
 
-#[async_trait]
 impl<E> Executor for E
 where
     E: DebugExecutor,
 {
-    async fn next(&mut self) -> Result<Message> {
-        DebugExecutor::next(self).await
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner(None)
+    }
+
+    fn execute_with_epoch(self: Box<Self>, epoch: u64) -> BoxedMessageStream {
+        self.execute_inner(Some(epoch))
     }
 
     fn schema(&self) -> &Schema {
         self.input().schema()
     }
 
-    fn pk_indices(&self) -> super::PkIndicesRef {
+    fn pk_indices(&self) -> PkIndicesRef {
         self.input().pk_indices()
     }
 
     fn identity(&self) -> &str {
         self.input().identity()
     }
+}
 
-    fn logical_operator_info(&self) -> &str {
-        self.input().logical_operator_info()
-    }
-
-    fn clear_cache(&mut self) -> Result<()> {
-        self.input_mut().clear_cache()
-    }
-
-    fn init(&mut self, epoch: u64) -> Result<()> {
-        self.input_mut().init(epoch)
+/// Executes the `input` executor of a [`DebugExecutor`], with the epoch if given.
+pub(crate) fn execute_input(input: BoxedExecutor, epoch: Option<u64>) -> BoxedMessageStream {
+    match epoch {
+        Some(epoch) => input.execute_with_epoch(epoch),
+        None => input.execute(),
     }
 }
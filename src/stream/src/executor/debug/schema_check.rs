@@ -12,86 +12,82 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::Debug;
-
-use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
 use itertools::Itertools;
-use risingwave_common::error::Result;
 use risingwave_common::for_all_variants;
 use tracing::event;
 
-use crate::executor::{Executor, Message};
+use super::{execute_input, DebugExecutor};
+use crate::executor::Message;
+use crate::executor_v2::{BoxedExecutor, BoxedMessageStream, Executor};
 
 /// [`SchemaCheckExecutor`] checks the passing stream chunk against the expected schema.
 ///
 /// Note that currently this only checks the physical type (variant name).
-#[derive(Debug)]
 pub struct SchemaCheckExecutor {
     /// The input of the current executor.
-    input: Box<dyn Executor>,
+    input: BoxedExecutor,
 }
 
 impl SchemaCheckExecutor {
-    pub fn new(input: Box<dyn Executor>) -> Self {
+    pub fn new(input: BoxedExecutor) -> Self {
         Self { input }
     }
 }
 
-#[async_trait]
-impl super::DebugExecutor for SchemaCheckExecutor {
-    async fn next(&mut self) -> Result<Message> {
-        let message = self.input.next().await?;
-
-        if let Message::Chunk(chunk) = &message {
-            event!(
-                tracing::Level::TRACE,
-                "input schema = \n{:#?}\nexpected schema = \n{:#?}",
-                chunk
-                    .columns()
-                    .iter()
-                    .map(|col| col.array_ref().get_ident())
-                    .collect_vec(),
-                self.schema().fields()
-            );
-
-            for (i, pair) in chunk
-                .columns()
-                .iter()
-                .zip_longest(self.schema().fields())
-                .enumerate()
-            {
-                let array = pair.as_ref().left().map(|c| c.array_ref());
-                let builder = pair
-                    .as_ref()
-                    .right()
-                    .map(|f| f.data_type.create_array_builder(0).unwrap()); // TODO: check `data_type` directly
-
-                macro_rules! check_schema {
-                    ([], $( { $variant_name:ident, $suffix_name:ident, $array:ty, $builder:ty } ),*) => {
-                        use risingwave_common::array::ArrayBuilderImpl;
-                        use risingwave_common::array::ArrayImpl;
-
-                        match (array, &builder) {
-                            $( (Some(ArrayImpl::$variant_name(_)), Some(ArrayBuilderImpl::$variant_name(_))) => {} ),*
-                            _ => panic!("schema check failed on {}: column {} should be {:?}, while stream chunk gives {:?}",
-                                                    self.input.logical_operator_info(), i, builder.map(|b| b.get_ident()), array.map(|a| a.get_ident())),
-                        }
-                    };
-                }
-
-                for_all_variants! { check_schema };
-            }
-        }
-
-        Ok(message)
-    }
-
+impl DebugExecutor for SchemaCheckExecutor {
     fn input(&self) -> &dyn Executor {
         self.input.as_ref()
     }
 
-    fn input_mut(&mut self) -> &mut dyn Executor {
-        self.input.as_mut()
+    fn execute_inner(self: Box<Self>, epoch: Option<u64>) -> BoxedMessageStream {
+        let schema = self.input.schema().clone();
+        let identity = self.input.identity().to_owned();
+
+        execute_input(self.input, epoch)
+            .inspect_ok(move |message| {
+                if let Message::Chunk(chunk) = message {
+                    event!(
+                        tracing::Level::TRACE,
+                        "input schema = \n{:#?}\nexpected schema = \n{:#?}",
+                        chunk
+                            .columns()
+                            .iter()
+                            .map(|col| col.array_ref().get_ident())
+                            .collect_vec(),
+                        schema.fields()
+                    );
+
+                    for (i, pair) in chunk
+                        .columns()
+                        .iter()
+                        .zip_longest(schema.fields())
+                        .enumerate()
+                    {
+                        let array = pair.as_ref().left().map(|c| c.array_ref());
+                        let builder = pair
+                            .as_ref()
+                            .right()
+                            .map(|f| f.data_type.create_array_builder(0).unwrap()); // TODO: check `data_type` directly
+
+                        macro_rules! check_schema {
+                            ([], $( { $variant_name:ident, $suffix_name:ident, $array:ty, $builder:ty } ),*) => {
+                                use risingwave_common::array::ArrayBuilderImpl;
+                                use risingwave_common::array::ArrayImpl;
+
+                                match (array, &builder) {
+                                    $( (Some(ArrayImpl::$variant_name(_)), Some(ArrayBuilderImpl::$variant_name(_))) => {} ),*
+                                    _ => panic!("schema check failed on {}: column {} should be {:?}, while stream chunk gives {:?}",
+                                                            identity, i, builder.map(|b| b.get_ident()), array.map(|a| a.get_ident())),
+                                }
+                            };
+                        }
+
+                        for_all_variants! { check_schema };
+                    }
+                }
+            })
+            .boxed()
     }
 }
 
@@ -104,7 +100,7 @@ mod tests {
     use risingwave_common::types::DataType;
 
     use super::*;
-    use crate::executor::test_utils::MockSource;
+    use crate::executor_v2::test_utils::MockSource;
 
     #[tokio::test]
     async fn test_schema_ok() {
@@ -127,9 +123,9 @@ mod tests {
         source.push_chunks([chunk].into_iter());
         source.push_barrier(1, false);
 
-        let mut checked = SchemaCheckExecutor::new(Box::new(source));
-        assert_matches!(checked.next().await.unwrap(), Message::Chunk(_));
-        assert_matches!(checked.next().await.unwrap(), Message::Barrier(_));
+        let mut checked = Box::new(SchemaCheckExecutor::new(Box::new(source))).execute();
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Chunk(_));
+        assert_matches!(checked.next().await.unwrap().unwrap(), Message::Barrier(_));
     }
 
     #[should_panic]
@@ -154,7 +150,7 @@ mod tests {
         source.push_chunks([chunk].into_iter());
         source.push_barrier(1, false);
 
-        let mut checked = SchemaCheckExecutor::new(Box::new(source));
-        checked.next().await.unwrap();
+        let mut checked = Box::new(SchemaCheckExecutor::new(Box::new(source))).execute();
+        checked.next().await.unwrap().unwrap();
     }
 }
@@ -12,16 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
-use async_trait::async_trait;
-use risingwave_common::error::Result;
+use futures::StreamExt;
+use futures_async_stream::try_stream;
 use tracing::event;
 use tracing_futures::Instrument;
 
+use super::{execute_input, DebugExecutor};
 use crate::executor::monitor::StreamingMetrics;
-use crate::executor::{Executor, Message};
+use crate::executor::Message;
+use crate::executor_v2::error::TracedStreamExecutorError;
+use crate::executor_v2::{BoxedExecutor, BoxedMessageStream, Executor};
 use crate::task::ActorId;
 
 /// `TraceExecutor` prints data passing in the stream graph to stdout.
@@ -33,7 +35,7 @@ use crate::task::ActorId;
 /// ```
 pub struct TraceExecutor {
     /// The input of the current executor
-    input: Box<dyn Executor>,
+    input: BoxedExecutor,
     /// Description of input executor
     input_desc: String,
     /// Input position of the input executor
@@ -49,17 +51,9 @@ pub struct TraceExecutor {
     span_name: String,
 }
 
-impl Debug for TraceExecutor {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("FilterExecutor")
-            .field("input", &self.input)
-            .finish()
-    }
-}
-
 impl TraceExecutor {
     pub fn new(
-        input: Box<dyn Executor>,
+        input: BoxedExecutor,
         input_desc: String,
         input_pos: usize,
         actor_id: ActorId,
@@ -77,17 +71,15 @@ impl TraceExecutor {
             span_name,
         }
     }
-}
 
-#[async_trait]
-impl super::DebugExecutor for TraceExecutor {
-    async fn next(&mut self) -> Result<Message> {
+    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
+    async fn trace(self: Box<Self>, epoch: Option<u64>) {
+        let mut input = execute_input(self.input, epoch);
         let span_name = self.span_name.as_str();
         let input_desc = self.input_desc.as_str();
         let input_pos = self.input_pos;
 
-        let input_message = self
-            .input
+        while let Some(message) = input
             .next()
             .instrument(tracing::trace_span!(
                 "next",
@@ -96,29 +88,29 @@ impl super::DebugExecutor for TraceExecutor {
                 next = input_desc,
                 input_pos = input_pos,
             ))
-            .await;
-        match input_message {
-            Ok(message) => {
-                if let Message::Chunk(ref chunk) = message {
-                    if chunk.cardinality() > 0 {
-                        self.metrics
-                            .actor_row_count
-                            .with_label_values(&[self.actor_id_string.as_str()])
-                            .inc_by(chunk.cardinality() as u64);
-                        event!(tracing::Level::TRACE, prev = %input_desc, msg = "chunk", "input = \n{:#?}", chunk);
-                    }
+            .await
+        {
+            let message = message?;
+            if let Message::Chunk(ref chunk) = message {
+                if chunk.cardinality() > 0 {
+                    self.metrics
+                        .actor_row_count
+                        .with_label_values(&[self.actor_id_string.as_str()])
+                        .inc_by(chunk.cardinality() as u64);
+                    event!(tracing::Level::TRACE, prev = %input_desc, msg = "chunk", "input = \n{:#?}", chunk);
                 }
-                Ok(message)
             }
-            Err(e) => Err(e),
+            yield message;
         }
     }
+}
 
+impl DebugExecutor for TraceExecutor {
     fn input(&self) -> &dyn Executor {
         self.input.as_ref()
     }
 
-    fn input_mut(&mut self) -> &mut dyn Executor {
-        self.input.as_mut()
+    fn execute_inner(self: Box<Self>, epoch: Option<u64>) -> BoxedMessageStream {
+        self.trace(epoch).boxed()
     }
 }
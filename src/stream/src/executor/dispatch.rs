@@ -16,18 +16,22 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use futures::channel::mpsc::Sender;
-use futures::SinkExt;
+use futures::{SinkExt, StreamExt};
 use itertools::Itertools;
 use risingwave_common::array::Op;
+use risingwave_common::error::ErrorCode;
 use risingwave_common::hash::VIRTUAL_KEY_COUNT;
 use risingwave_common::util::addr::{is_local_address, HostAddr};
 use risingwave_common::util::hash_util::CRC32FastBuilder;
 use tracing::event;
 
-use super::{Barrier, Executor, Message, Mutation, Result, StreamChunk, StreamConsumer};
+use super::monitor::ChannelMetrics;
+use super::{Barrier, Message, Mutation, Result, StreamChunk, StreamConsumer, Watermark};
+use crate::executor_v2::{BoxedExecutor, BoxedMessageStream, Executor};
 use crate::task::{ActorId, SharedContext};
 
 /// `Output` provides an interface for `Dispatcher` to send data into downstream actors.
@@ -40,11 +44,31 @@ pub trait Output: Debug + Send + Sync + 'static {
 
 type BoxedOutput = Box<dyn Output>;
 
+/// Sends a message to a local channel, which should never fail. The time blocked on the full
+/// channel and the occupancy of the channel are recorded in `metrics`.
+pub(crate) async fn send_to_channel(
+    ch: &mut Sender<Message>,
+    message: Message,
+    metrics: &ChannelMetrics,
+) {
+    if let Err(err) = ch.try_send(message) {
+        assert!(err.is_full(), "local channel closed unexpectedly");
+        let start = Instant::now();
+        ch.send(err.into_inner()).await.unwrap();
+        metrics
+            .blocking_duration_ns
+            .inc_by(start.elapsed().as_nanos() as u64);
+    }
+    metrics.occupancy.inc();
+}
+
 /// `LocalOutput` sends data to a local `mpsc::Channel`
 pub struct LocalOutput {
     actor_id: ActorId,
 
     ch: Sender<Message>,
+
+    metrics: ChannelMetrics,
 }
 
 impl Debug for LocalOutput {
@@ -56,16 +80,19 @@ impl Debug for LocalOutput {
 }
 
 impl LocalOutput {
-    pub fn new(actor_id: ActorId, ch: Sender<Message>) -> Self {
-        Self { actor_id, ch }
+    pub fn new(actor_id: ActorId, ch: Sender<Message>, metrics: ChannelMetrics) -> Self {
+        Self {
+            actor_id,
+            ch,
+            metrics,
+        }
     }
 }
 
 #[async_trait]
 impl Output for LocalOutput {
     async fn send(&mut self, message: Message) -> Result<()> {
-        // local channel should never fail
-        self.ch.send(message).await.unwrap();
+        send_to_channel(&mut self.ch, message, &self.metrics).await;
         Ok(())
     }
 
@@ -79,6 +106,8 @@ pub struct RemoteOutput {
     actor_id: ActorId,
 
     ch: Sender<Message>,
+
+    metrics: ChannelMetrics,
 }
 
 impl Debug for RemoteOutput {
@@ -90,8 +119,12 @@ impl Debug for RemoteOutput {
 }
 
 impl RemoteOutput {
-    pub fn new(actor_id: ActorId, ch: Sender<Message>) -> Self {
-        Self { actor_id, ch }
+    pub fn new(actor_id: ActorId, ch: Sender<Message>, metrics: ChannelMetrics) -> Self {
+        Self {
+            actor_id,
+            ch,
+            metrics,
+        }
     }
 }
 
@@ -102,8 +135,7 @@ impl Output for RemoteOutput {
             Message::Chunk(chk) => Message::Chunk(chk.compact()?),
             _ => message,
         };
-        // local channel should never fail
-        self.ch.send(message).await.unwrap();
+        send_to_channel(&mut self.ch, message, &self.metrics).await;
         Ok(())
     }
 
@@ -116,7 +148,7 @@ impl Output for RemoteOutput {
 /// data chunks will be dispatched with some specified policy, while control message
 /// such as barriers will be distributed to all receivers.
 pub struct DispatchExecutor {
-    input: Box<dyn Executor>,
+    input: BoxedMessageStream,
     inner: DispatcherImpl,
     actor_id: u32,
     context: Arc<SharedContext>,
@@ -129,18 +161,20 @@ pub fn new_output(
     down_id: &u32,
 ) -> Result<Box<dyn Output>> {
     let tx = context.take_sender(&(actor_id, *down_id))?;
+    let metrics = context
+        .streaming_metrics
+        .channel_metrics(actor_id, *down_id);
     if is_local_address(&addr, &context.addr) {
         // if this is a local downstream actor
-        Ok(Box::new(LocalOutput::new(*down_id, tx)) as Box<dyn Output>)
+        Ok(Box::new(LocalOutput::new(*down_id, tx, metrics)) as Box<dyn Output>)
     } else {
-        Ok(Box::new(RemoteOutput::new(*down_id, tx)) as Box<dyn Output>)
+        Ok(Box::new(RemoteOutput::new(*down_id, tx, metrics)) as Box<dyn Output>)
     }
 }
 
 impl std::fmt::Debug for DispatchExecutor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DispatchExecutor")
-            .field("input", &self.input)
             .field("inner", &self.inner)
             .field("actor_id", &self.actor_id)
             .finish()
@@ -149,13 +183,13 @@ impl std::fmt::Debug for DispatchExecutor {
 
 impl DispatchExecutor {
     pub fn new(
-        input: Box<dyn Executor>,
+        input: BoxedExecutor,
         inner: DispatcherImpl,
         actor_id: u32,
         context: Arc<SharedContext>,
     ) -> Self {
         Self {
-            input,
+            input: input.execute(),
             inner,
             actor_id,
             context,
@@ -173,6 +207,9 @@ impl DispatchExecutor {
                 self.inner.dispatch_barrier(barrier).await?;
                 self.post_mutate_outputs(&mutation).await?;
             }
+            Message::Watermark(watermark) => {
+                self.inner.dispatch_watermark(watermark).await?;
+            }
         };
         Ok(())
     }
@@ -203,7 +240,7 @@ impl DispatchExecutor {
                     self.inner.set_outputs(new_outputs)
                 }
             }
-            Some(Mutation::AddOutput(adds)) => {
+            Some(Mutation::AddOutput { adds, .. }) => {
                 if let Some(downstream_actor_infos) = adds.get(&self.actor_id) {
                     let mut outputs_to_add = Vec::with_capacity(downstream_actor_infos.len());
                     for downstream_actor_info in downstream_actor_infos {
@@ -219,15 +256,43 @@ impl DispatchExecutor {
                     self.inner.add_outputs(outputs_to_add);
                 }
             }
+            Some(Mutation::Reschedule(reschedule)) => {
+                if let Some(update) = reschedule.dispatchers.get(&self.actor_id) {
+                    // The added downstream actors should also receive this barrier, so add them
+                    // before dispatching it.
+                    let mut outputs_to_add =
+                        Vec::with_capacity(update.added_downstream_actors.len());
+                    for downstream_actor_info in &update.added_downstream_actors {
+                        let down_id = downstream_actor_info.get_actor_id();
+                        let downstream_addr = downstream_actor_info.get_host()?.into();
+                        outputs_to_add.push(new_output(
+                            &self.context,
+                            downstream_addr,
+                            self.actor_id,
+                            &down_id,
+                        )?);
+                    }
+                    self.inner.add_outputs(outputs_to_add);
+
+                    // The chunks after this barrier are dispatched by the new mapping.
+                    if let Some(hash_mapping) = &update.hash_mapping {
+                        match &mut self.inner {
+                            DispatcherImpl::Hash(inner) => {
+                                inner.set_hash_mapping(hash_mapping.clone())
+                            }
+                            _ => unreachable!("only hash dispatcher has a hash mapping"),
+                        }
+                    }
+                }
+            }
             _ => {}
         };
 
         Ok(())
     }
 
-    /// For `Stop`, update the outputs after we dispatch the barrier.
+    /// For `Stop` and `Reschedule`, update the outputs after we dispatch the barrier.
     async fn post_mutate_outputs(&mut self, mutation: &Option<Arc<Mutation>>) -> Result<()> {
-        #[allow(clippy::single_match)]
         match mutation.as_deref() {
             Some(Mutation::Stop(stops)) => {
                 // Remove outputs only if this actor itself is not to be stopped.
@@ -235,6 +300,17 @@ impl DispatchExecutor {
                     self.inner.remove_outputs(stops);
                 }
             }
+            Some(Mutation::Reschedule(reschedule)) => {
+                if let Some(update) = reschedule.dispatchers.get(&self.actor_id) {
+                    let removed = &update.removed_downstream_actor_ids;
+                    self.inner.remove_outputs(removed);
+
+                    let actor_id = self.actor_id;
+                    self.context.retain(|&(up_id, down_id)| {
+                        up_id != actor_id || !removed.contains(&down_id)
+                    });
+                }
+            }
             _ => {}
         }
 
@@ -242,10 +318,19 @@ impl DispatchExecutor {
     }
 }
 
+/// Pulls the next message from the input stream of a [`StreamConsumer`]. An actor is expected to
+/// be stopped by a barrier, so the end of the stream is reported as [`ErrorCode::Eof`].
+async fn next_message(input: &mut BoxedMessageStream) -> Result<Message> {
+    match input.next().await {
+        Some(message) => Ok(message?),
+        None => Err(ErrorCode::Eof.into()),
+    }
+}
+
 #[async_trait]
 impl StreamConsumer for DispatchExecutor {
     async fn next(&mut self) -> Result<Option<Barrier>> {
-        let msg = self.input.next().await?;
+        let msg = next_message(&mut self.input).await?;
         let barrier = if let Message::Barrier(ref barrier) = msg {
             Some(barrier.clone())
         } else {
@@ -280,6 +365,12 @@ macro_rules! impl_dispatcher {
                 }
             }
 
+            pub async fn dispatch_watermark(&mut self, watermark: Watermark) -> Result<()> {
+                match self {
+                    $( Self::$variant_name(inner) => inner.dispatch_watermark(watermark).await, )*
+                }
+            }
+
             pub fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
                 match self {
                     $( Self::$variant_name(inner) => inner.set_outputs(outputs), )*
@@ -319,6 +410,7 @@ macro_rules! define_dispatcher_associated_types {
     () => {
         type DataFuture<'a> = impl DispatchFuture<'a>;
         type BarrierFuture<'a> = impl DispatchFuture<'a>;
+        type WatermarkFuture<'a> = impl DispatchFuture<'a>;
     };
 }
 
@@ -327,8 +419,11 @@ pub trait DispatchFuture<'a> = Future<Output = Result<()>> + Send;
 pub trait Dispatcher: Debug + 'static {
     type DataFuture<'a>: DispatchFuture<'a>;
     type BarrierFuture<'a>: DispatchFuture<'a>;
+    type WatermarkFuture<'a>: DispatchFuture<'a>;
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_>;
     fn dispatch_barrier(&mut self, barrier: Barrier) -> Self::BarrierFuture<'_>;
+    /// Watermarks are broadcast to all outputs, as every downstream needs to know the progress.
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_>;
 
     fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>);
     fn add_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>);
@@ -376,6 +471,15 @@ impl Dispatcher for RoundRobinDataDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            for output in &mut self.outputs {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
         self.outputs = outputs.into_iter().collect();
         self.cur = self.cur.min(self.outputs.len() - 1);
@@ -393,7 +497,6 @@ impl Dispatcher for RoundRobinDataDispatcher {
 }
 
 pub struct HashDataDispatcher {
-    fragment_ids: Vec<u32>,
     outputs: Vec<BoxedOutput>,
     keys: Vec<usize>,
     /// Mapping from virtual key to actor id, used for hash data dispatcher to dispatch tasks to
@@ -411,19 +514,20 @@ impl Debug for HashDataDispatcher {
 }
 
 impl HashDataDispatcher {
-    pub fn new(
-        fragment_ids: Vec<u32>,
-        outputs: Vec<BoxedOutput>,
-        keys: Vec<usize>,
-        hash_mapping: Vec<ActorId>,
-    ) -> Self {
+    pub fn new(outputs: Vec<BoxedOutput>, keys: Vec<usize>, hash_mapping: Vec<ActorId>) -> Self {
         Self {
-            fragment_ids,
             outputs,
             keys,
             hash_mapping,
         }
     }
+
+    /// Replaces the mapping from virtual key to downstream actor id, e.g., when the downstream
+    /// fragment is rescheduled. Chunks after the replacement will be dispatched by the new mapping.
+    pub fn set_hash_mapping(&mut self, hash_mapping: Vec<ActorId>) {
+        assert_eq!(hash_mapping.len(), self.hash_mapping.len());
+        self.hash_mapping = hash_mapping;
+    }
 }
 
 impl Dispatcher for HashDataDispatcher {
@@ -447,6 +551,15 @@ impl Dispatcher for HashDataDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            for output in &mut self.outputs {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_> {
         async move {
             // A chunk can be shuffled into multiple output chunks that to be sent to downstreams.
@@ -533,11 +646,7 @@ impl Dispatcher for HashDataDispatcher {
             let ops = new_ops;
 
             // individually output StreamChunk integrated with vis_map
-            for ((vis_map, output), downstream) in vis_maps
-                .into_iter()
-                .zip_eq(self.outputs.iter_mut())
-                .zip_eq(self.fragment_ids.iter())
-            {
+            for (vis_map, output) in vis_maps.into_iter().zip_eq(self.outputs.iter_mut()) {
                 let vis_map = vis_map.try_into().unwrap();
                 // columns is not changed in this function
                 let new_stream_chunk =
@@ -546,7 +655,7 @@ impl Dispatcher for HashDataDispatcher {
                     event!(
                         tracing::Level::TRACE,
                         msg = "chunk",
-                        downstream = downstream,
+                        downstream = output.actor_id(),
                         "send = \n{:#?}",
                         new_stream_chunk
                     );
@@ -614,6 +723,15 @@ impl Dispatcher for BroadcastDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            for output in self.outputs.values_mut() {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn set_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
         self.outputs = Self::into_pairs(outputs).collect()
     }
@@ -666,6 +784,13 @@ impl Dispatcher for SimpleDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            self.output.send(Message::Watermark(watermark)).await?;
+            Ok(())
+        }
+    }
+
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_> {
         async move {
             self.output.send(Message::Chunk(chunk)).await?;
@@ -684,22 +809,32 @@ impl Dispatcher for SimpleDispatcher {
 mod sender_consumer {
     use super::*;
     /// `SenderConsumer` consumes data from input executor and send it into a channel.
-    #[derive(Debug)]
     pub struct SenderConsumer {
-        input: Box<dyn Executor>,
+        input: BoxedMessageStream,
         channel: BoxedOutput,
     }
 
+    impl std::fmt::Debug for SenderConsumer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SenderConsumer")
+                .field("channel", &self.channel)
+                .finish()
+        }
+    }
+
     impl SenderConsumer {
-        pub fn new(input: Box<dyn Executor>, channel: BoxedOutput) -> Self {
-            Self { input, channel }
+        pub fn new(input: BoxedExecutor, channel: BoxedOutput) -> Self {
+            Self {
+                input: input.execute(),
+                channel,
+            }
         }
     }
 
     #[async_trait]
     impl StreamConsumer for SenderConsumer {
         async fn next(&mut self) -> Result<Option<Barrier>> {
-            let message = self.input.next().await?;
+            let message = next_message(&mut self.input).await?;
             let barrier = if let Message::Barrier(ref barrier) = message {
                 Some(barrier.clone())
             } else {
@@ -716,7 +851,7 @@ pub use sender_consumer::SenderConsumer;
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::hash::{BuildHasher, Hasher};
     use std::sync::{Arc, Mutex};
 
@@ -731,8 +866,8 @@ mod tests {
     use risingwave_pb::common::{ActorInfo, HostAddress};
 
     use super::*;
+    use crate::executor::{DispatcherUpdate, Reschedule};
     use crate::executor_v2::receiver::ReceiverExecutor;
-    use crate::executor_v2::Executor;
     use crate::task::{LOCAL_OUTPUT_CHANNEL_SIZE, LOCAL_TEST_ADDR};
 
     #[derive(Debug)]
@@ -781,12 +916,8 @@ mod tests {
             .flat_map(|id| vec![id as ActorId; VIRTUAL_KEY_COUNT / num_outputs])
             .collect_vec();
         hash_mapping.resize(VIRTUAL_KEY_COUNT, num_outputs as u32);
-        let mut hash_dispatcher = HashDataDispatcher::new(
-            (0..outputs.len() as u32).collect(),
-            outputs,
-            key_indices.to_vec(),
-            hash_mapping,
-        );
+        let mut hash_dispatcher =
+            HashDataDispatcher::new(outputs, key_indices.to_vec(), hash_mapping);
 
         let chunk = StreamChunk::new(
             vec![
@@ -897,14 +1028,14 @@ mod tests {
     async fn test_configuration_change() {
         let schema = Schema { fields: vec![] };
         let (mut tx, rx) = channel(16);
-        let input = Box::new(ReceiverExecutor::new(schema.clone(), vec![], rx)).v1();
+        let input = ReceiverExecutor::new(schema.clone(), vec![], rx).boxed();
         let data_sink = Arc::new(Mutex::new(vec![]));
         let actor_id = 233;
         let output = Box::new(MockOutput::new(actor_id, data_sink));
         let ctx = Arc::new(SharedContext::for_test());
 
         let mut executor = Box::new(DispatchExecutor::new(
-            Box::new(input),
+            input,
             DispatcherImpl::Simple(SimpleDispatcher::new(output)),
             actor_id,
             ctx.clone(),
@@ -945,14 +1076,17 @@ mod tests {
         add_local_channels(ctx.clone(), vec![(233, 245)]);
         add_remote_channels(ctx.clone(), 233, vec![246]);
         tx.send(Message::Barrier(
-            Barrier::new_test_barrier(1).with_mutation(Mutation::AddOutput({
-                let mut actors = HashMap::default();
-                actors.insert(
-                    233,
-                    vec![helper_make_local_actor(245), helper_make_remote_actor(246)],
-                );
-                actors
-            })),
+            Barrier::new_test_barrier(1).with_mutation(Mutation::AddOutput {
+                adds: {
+                    let mut actors = HashMap::default();
+                    actors.insert(
+                        233,
+                        vec![helper_make_local_actor(245), helper_make_remote_actor(246)],
+                    );
+                    actors
+                },
+                paused_actors: HashSet::new(),
+            }),
         ))
         .await
         .unwrap();
@@ -963,6 +1097,73 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_reschedule() {
+        let schema = Schema { fields: vec![] };
+        let (mut tx, rx) = channel(16);
+        let input = ReceiverExecutor::new(schema.clone(), vec![], rx).boxed();
+        let data_sink_1 = Arc::new(Mutex::new(vec![]));
+        let data_sink_2 = Arc::new(Mutex::new(vec![]));
+        let outputs = vec![
+            Box::new(MockOutput::new(1, data_sink_1.clone())) as BoxedOutput,
+            Box::new(MockOutput::new(2, data_sink_2.clone())) as BoxedOutput,
+        ];
+        let hash_mapping = (0..VIRTUAL_KEY_COUNT)
+            .map(|vnode| (vnode % 2) as ActorId + 1)
+            .collect_vec();
+        let actor_id = 233;
+        let ctx = Arc::new(SharedContext::for_test());
+
+        let mut executor = Box::new(DispatchExecutor::new(
+            input,
+            DispatcherImpl::Hash(HashDataDispatcher::new(outputs, vec![0], hash_mapping)),
+            actor_id,
+            ctx.clone(),
+        ));
+
+        // Replace actor 2 with actor 3, which takes over all virtual keys.
+        add_local_channels(ctx.clone(), vec![(233, 3)]);
+        let mut rx_3 = ctx.take_receiver(&(233, 3)).unwrap();
+        let reschedule = Reschedule {
+            dispatchers: HashMap::from([(
+                actor_id,
+                DispatcherUpdate {
+                    added_downstream_actors: vec![helper_make_local_actor(3)],
+                    removed_downstream_actor_ids: HashSet::from([2]),
+                    hash_mapping: Some(vec![3; VIRTUAL_KEY_COUNT]),
+                },
+            )]),
+            stopped_actors: HashSet::from([2]),
+            vnode_changed_actors: HashSet::from([1, 3]),
+            ..Default::default()
+        };
+        let b1 = Barrier::new_test_barrier(1).with_mutation(Mutation::Reschedule(reschedule));
+        tx.send(Message::Barrier(b1.clone())).await.unwrap();
+        executor.next().await.unwrap();
+
+        // All of the actors receive the barrier.
+        for sink in [&data_sink_1, &data_sink_2] {
+            let messages = sink.lock().unwrap();
+            assert_eq!(messages.len(), 1);
+            assert_eq!(messages[0].as_barrier(), Some(&b1));
+        }
+        let message = rx_3.try_next().unwrap().unwrap();
+        assert_eq!(message.as_barrier(), Some(&b1));
+
+        // The chunks are dispatched by the new mapping.
+        let chunk = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![column_nonnull! { I64Array, [1, 2] }],
+            None,
+        );
+        tx.send(Message::Chunk(chunk)).await.unwrap();
+        executor.next().await.unwrap();
+        assert_eq!(data_sink_1.lock().unwrap().len(), 1);
+        assert_eq!(data_sink_2.lock().unwrap().len(), 1);
+        let chunk_3 = rx_3.try_next().unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(chunk_3.cardinality(), 2);
+    }
+
     #[tokio::test]
     async fn test_hash_dispatcher() {
         let num_outputs = 5; // actor id ranges from 1 to 5
@@ -983,12 +1184,8 @@ mod tests {
             .flat_map(|id| vec![id as ActorId; VIRTUAL_KEY_COUNT / num_outputs])
             .collect_vec();
         hash_mapping.resize(VIRTUAL_KEY_COUNT, num_outputs as u32);
-        let mut hash_dispatcher = HashDataDispatcher::new(
-            (0..outputs.len() as u32).collect(),
-            outputs,
-            key_indices.to_vec(),
-            hash_mapping.clone(),
-        );
+        let mut hash_dispatcher =
+            HashDataDispatcher::new(outputs, key_indices.to_vec(), hash_mapping.clone());
 
         let mut ops = Vec::new();
         for idx in 0..cardinality {
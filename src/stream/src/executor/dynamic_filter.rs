@@ -0,0 +1,53 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::error::Result;
+use risingwave_common::try_match_expand;
+use risingwave_pb::stream_plan;
+use risingwave_pb::stream_plan::stream_node::Node;
+use risingwave_storage::{Keyspace, StateStore};
+
+use crate::executor::ExecutorBuilder;
+use crate::executor_v2::{BoxedExecutor, DynamicFilterExecutor, Executor};
+use crate::task::{ExecutorParams, LocalStreamManagerCore};
+
+pub struct DynamicFilterExecutorBuilder;
+
+impl ExecutorBuilder for DynamicFilterExecutorBuilder {
+    fn new_boxed_executor(
+        mut params: ExecutorParams,
+        node: &stream_plan::StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node().unwrap(), Node::DynamicFilterNode)?;
+        let right = params.input.remove(1);
+        let left = params.input.remove(0);
+        let keyspace = Keyspace::executor_root(store, params.executor_id);
+        let barrier_align_duration = params
+            .executor_stats
+            .barrier_align_duration(params.actor_id, params.executor_id);
+        Ok(DynamicFilterExecutor::new(
+            left,
+            right,
+            node.left_key as usize,
+            node.right_key as usize,
+            node.get_comparator()?,
+            keyspace,
+            params.executor_id,
+        )
+        .with_barrier_align_duration(barrier_align_duration)
+        .boxed())
+    }
+}
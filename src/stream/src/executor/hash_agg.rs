@@ -24,40 +24,42 @@ use risingwave_pb::stream_plan;
 use risingwave_pb::stream_plan::stream_node::Node;
 use risingwave_storage::{Keyspace, StateStore};
 
-use super::{AggCall, Executor};
+use super::AggCall;
 use crate::executor::{ExecutorBuilder, PkIndices};
-use crate::executor_v2::{Executor as ExecutorV2, HashAggExecutor};
-use crate::task::{build_agg_call_from_prost, ExecutorParams, LocalStreamManagerCore};
+use crate::executor_v2::{BoxedExecutor, Executor, HashAggExecutor};
+use crate::task::{
+    build_agg_call_from_prost, ExecutorParams, LocalStreamManagerCore, MemoryUsageReporter,
+};
 
 struct HashAggExecutorDispatcher<S: StateStore>(PhantomData<S>);
 
 struct HashAggExecutorDispatcherArgs<S: StateStore> {
-    input: Box<dyn Executor>,
+    input: BoxedExecutor,
     agg_calls: Vec<AggCall>,
     key_indices: Vec<usize>,
     keyspace: Keyspace<S>,
     pk_indices: PkIndices,
     executor_id: u64,
-    op_info: String,
+    emit_on_window_close: bool,
+    memory_reporter: MemoryUsageReporter,
 }
 
 impl<S: StateStore> HashKeyDispatcher for HashAggExecutorDispatcher<S> {
     type Input = HashAggExecutorDispatcherArgs<S>;
-    type Output = Result<Box<dyn Executor>>;
+    type Output = Result<BoxedExecutor>;
 
     fn dispatch<K: HashKey>(args: Self::Input) -> Self::Output {
-        Ok(Box::new(
-            Box::new(HashAggExecutor::<K, S>::new_from_v1(
-                args.input,
-                args.agg_calls,
-                args.key_indices,
-                args.keyspace,
-                args.pk_indices,
-                args.executor_id,
-                args.op_info,
-            )?)
-            .v1(),
-        ))
+        Ok(HashAggExecutor::<K, S>::new(
+            args.input,
+            args.agg_calls,
+            args.keyspace,
+            args.pk_indices,
+            args.executor_id,
+            args.key_indices,
+        )?
+        .with_emit_on_window_close(args.emit_on_window_close)
+        .with_memory_reporter(args.memory_reporter)
+        .boxed())
     }
 }
 
@@ -69,7 +71,7 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
         node: &stream_plan::StreamNode,
         store: impl StateStore,
         _stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node().unwrap(), Node::HashAggNode)?;
         let key_indices = node
             .get_distribution_keys()
@@ -79,9 +81,13 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
         let agg_calls: Vec<AggCall> = node
             .get_agg_calls()
             .iter()
-            .map(build_agg_call_from_prost)
+            .map(|agg_call| build_agg_call_from_prost(node.is_append_only, agg_call))
             .try_collect()?;
         let keyspace = Keyspace::shared_executor_root(store, params.executor_id);
+        let memory_reporter = params
+            .env
+            .memory_manager()
+            .register(params.actor_id, params.executor_id);
         let input = params.input.remove(0);
         let keys = key_indices
             .iter()
@@ -95,7 +101,8 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
             keyspace,
             pk_indices: params.pk_indices,
             executor_id: params.executor_id,
-            op_info: params.op_info,
+            emit_on_window_close: node.emit_on_window_close,
+            memory_reporter,
         };
         HashAggExecutorDispatcher::dispatch_by_kind(kind, args)
     }
@@ -15,7 +15,7 @@
 use std::sync::{Arc, Mutex};
 
 use futures::channel::mpsc::channel;
-use futures::SinkExt;
+use futures::{SinkExt, StreamExt};
 use risingwave_common::array::column::Column;
 use risingwave_common::array::*;
 use risingwave_common::catalog::Field;
@@ -26,35 +26,39 @@ use super::*;
 use crate::executor::test_utils::create_in_memory_keyspace;
 use crate::executor_v2::receiver::ReceiverExecutor;
 use crate::executor_v2::{
-    Executor as ExecutorV2, LocalSimpleAggExecutor, MergeExecutor, ProjectExecutor,
-    SimpleAggExecutor,
+    BoxedExecutor, BoxedMessageStream, Executor as ExecutorV2, LocalSimpleAggExecutor,
+    MergeExecutor, ProjectExecutor, SimpleAggExecutor,
 };
 use crate::task::SharedContext;
 
 pub struct MockConsumer {
-    input: Box<dyn Executor>,
+    input: BoxedMessageStream,
     data: Arc<Mutex<Vec<StreamChunk>>>,
 }
 impl std::fmt::Debug for MockConsumer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MockConsumer")
-            .field("input", &self.input)
+            .field("data", &self.data)
             .finish()
     }
 }
 
 impl MockConsumer {
-    pub fn new(input: Box<dyn Executor>, data: Arc<Mutex<Vec<StreamChunk>>>) -> Self {
-        Self { input, data }
+    pub fn new(input: BoxedExecutor, data: Arc<Mutex<Vec<StreamChunk>>>) -> Self {
+        Self {
+            input: input.execute(),
+            data,
+        }
     }
 }
 
 #[async_trait]
 impl StreamConsumer for MockConsumer {
     async fn next(&mut self) -> Result<Option<Barrier>> {
-        match self.input.next().await? {
+        match self.input.next().await.unwrap()? {
             Message::Chunk(chunk) => self.data.lock().unwrap().push(chunk),
             Message::Barrier(barrier) => return Ok(Some(barrier)),
+            Message::Watermark(_) => {}
         }
         Ok(None)
     }
@@ -70,33 +74,34 @@ async fn test_merger_sum_aggr() {
         let schema = Schema {
             fields: vec![Field::unnamed(DataType::Int64)],
         };
-        let input = Box::new(ReceiverExecutor::new(schema, vec![], input_rx)).v1();
+        let input = ReceiverExecutor::new(schema, vec![], input_rx).boxed();
         // for the local aggregator, we need two states: row count and sum
-        let aggregator = Box::new(
-            LocalSimpleAggExecutor::new_from_v1(
-                Box::new(input),
-                vec![
-                    AggCall {
-                        kind: AggKind::RowCount,
-                        args: AggArgs::None,
-                        return_type: DataType::Int64,
-                    },
-                    AggCall {
-                        kind: AggKind::Sum,
-                        args: AggArgs::Unary(DataType::Int64, 0),
-                        return_type: DataType::Int64,
-                    },
-                ],
-                vec![],
-                1,
-                "LocalSimpleAggExecutor".to_string(),
-            )
-            .unwrap(),
+        let aggregator = LocalSimpleAggExecutor::new(
+            input,
+            vec![
+                AggCall {
+                    kind: AggKind::RowCount,
+                    args: AggArgs::None,
+                    return_type: DataType::Int64,
+                    append_only: false,
+                },
+                AggCall {
+                    kind: AggKind::Sum,
+                    args: AggArgs::Unary(DataType::Int64, 0),
+                    return_type: DataType::Int64,
+                    append_only: false,
+                },
+            ],
+            vec![],
+            1,
         )
-        .v1();
+        .unwrap()
+        .boxed();
         let (tx, rx) = channel(16);
-        let consumer =
-            SenderConsumer::new(Box::new(aggregator), Box::new(LocalOutput::new(233, tx)));
+        let consumer = SenderConsumer::new(
+            aggregator,
+            Box::new(LocalOutput::new(233, tx, ChannelMetrics::unused())),
+        );
         let context = SharedContext::for_test().into();
         let actor = Actor::new(Box::new(consumer), 0, context);
         (actor, rx)
@@ -117,7 +122,8 @@ async fn test_merger_sum_aggr() {
         let (actor, channel) = make_actor(rx);
         outputs.push(channel);
         handles.push(tokio::spawn(actor.run()));
-        inputs.push(Box::new(LocalOutput::new(233, tx)) as Box<dyn Output>);
+        inputs
+            .push(Box::new(LocalOutput::new(233, tx, ChannelMetrics::unused())) as Box<dyn Output>);
     }
 
     // create a round robin dispatcher, which dispatches messages to the actors
@@ -125,9 +131,9 @@ async fn test_merger_sum_aggr() {
     let schema = Schema {
         fields: vec![Field::unnamed(DataType::Int64)],
     };
-    let receiver_op = Box::new(ReceiverExecutor::new(schema.clone(), vec![], rx)).v1();
+    let receiver_op = ReceiverExecutor::new(schema.clone(), vec![], rx).boxed();
     let dispatcher = DispatchExecutor::new(
-        Box::new(receiver_op),
+        receiver_op,
         DispatcherImpl::RoundRobin(RoundRobinDataDispatcher::new(inputs)),
         0,
         ctx,
@@ -137,47 +143,46 @@ async fn test_merger_sum_aggr() {
     handles.push(tokio::spawn(actor.run()));
 
     // use a merge operator to collect data from dispatchers before sending them to aggregator
-    let merger = Box::new(MergeExecutor::new(schema, vec![], 0, outputs)).v1();
+    let merger = MergeExecutor::new(schema, vec![], 0, outputs).boxed();
 
     // for global aggregator, we need to sum data and sum row count
-    let aggregator = Box::new(
-        SimpleAggExecutor::new_from_v1(
-            Box::new(merger),
-            vec![
-                AggCall {
-                    kind: AggKind::Sum,
-                    args: AggArgs::Unary(DataType::Int64, 0),
-                    return_type: DataType::Int64,
-                },
-                AggCall {
-                    kind: AggKind::Sum,
-                    args: AggArgs::Unary(DataType::Int64, 1),
-                    return_type: DataType::Int64,
-                },
-            ],
-            create_in_memory_keyspace(),
-            vec![],
-            2,
-            "SimpleAggExecutor".to_string(),
-            vec![],
-        )
-        .unwrap(),
+    let aggregator = SimpleAggExecutor::new(
+        merger,
+        vec![
+            AggCall {
+                kind: AggKind::Sum,
+                args: AggArgs::Unary(DataType::Int64, 0),
+                return_type: DataType::Int64,
+                append_only: false,
+            },
+            AggCall {
+                kind: AggKind::Sum,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                append_only: false,
+            },
+        ],
+        create_in_memory_keyspace(),
+        vec![],
+        2,
+        vec![],
     )
-    .v1();
+    .unwrap()
+    .boxed();
 
-    let projection = Box::new(ProjectExecutor::new_from_v1(
-        Box::new(aggregator),
+    let projection = ProjectExecutor::new(
+        aggregator,
         vec![],
         vec![
             // TODO: use the new streaming_if_null expression here, and add `None` tests
             Box::new(InputRefExpression::new(DataType::Int64, 1)),
         ],
         3,
-        "ProjectExecutor".to_string(),
-    ))
-    .v1();
+    )
+    .with_watermark_derivations(vec![(1, 0)])
+    .boxed();
     let items = Arc::new(Mutex::new(vec![]));
-    let consumer = MockConsumer::new(Box::new(projection), items.clone());
+    let consumer = MockConsumer::new(projection, items.clone());
     let context = SharedContext::for_test().into();
     let actor = Actor::new(Box::new(consumer), 0, context);
     handles.push(tokio::spawn(actor.run()));
@@ -0,0 +1,67 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::catalog::{ColumnDesc, TableId};
+use risingwave_common::try_match_expand;
+use risingwave_common::util::sort_util::OrderPair;
+use risingwave_pb::stream_plan;
+use risingwave_pb::stream_plan::stream_node::Node;
+use risingwave_storage::{Keyspace, StateStore};
+
+use crate::executor::{ExecutorBuilder, Result};
+use crate::executor_v2::{BoxedExecutor, Executor, LookupExecutor, LookupExecutorParams};
+use crate::task::{ExecutorParams, LocalStreamManagerCore};
+
+pub struct LookupExecutorBuilder;
+
+impl ExecutorBuilder for LookupExecutorBuilder {
+    fn new_boxed_executor(
+        mut params: ExecutorParams,
+        node: &stream_plan::StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node().unwrap(), Node::LookupNode)?;
+
+        let arrangement = params.input.remove(1);
+        let stream = params.input.remove(0);
+
+        let table_id = TableId::from(&node.arrangement_table_ref_id);
+        let arrangement_col_descs = node
+            .arrangement_column_descs
+            .iter()
+            .map(|column_desc| ColumnDesc::from(column_desc.clone()))
+            .collect_vec();
+        let arrangement_order_rules = node
+            .arrangement_column_orders
+            .iter()
+            .map(OrderPair::from_prost)
+            .collect_vec();
+
+        Ok(LookupExecutor::new(LookupExecutorParams {
+            arrangement,
+            stream,
+            arrangement_keyspace: Keyspace::table_root(store, &table_id),
+            arrangement_col_descs,
+            arrangement_order_rules,
+            pk_indices: params.pk_indices,
+            use_current_epoch: node.use_current_epoch,
+            stream_join_key_indices: node.stream_key.iter().map(|key| *key as usize).collect(),
+            arrange_join_key_indices: node.arrange_key.iter().map(|key| *key as usize).collect(),
+            join_type: node.get_join_type()?,
+        })
+        .boxed())
+    }
+}
@@ -105,6 +105,9 @@ pub trait ManagedTableState<S: StateStore>: Send + Sync + 'static {
     /// Check if this state needs a flush.
     fn is_dirty(&self) -> bool;
 
+    /// Estimates the memory held by the cached entries of the state in bytes.
+    fn estimated_size(&self) -> usize;
+
     /// Flush the internal state to a write batch.
     fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()>;
 }
@@ -377,6 +380,24 @@ where
         !self.flush_buffer.is_empty()
     }
 
+    fn estimated_size(&self) -> usize {
+        // The sort key in the map key holds the same value as the datum.
+        let entry_size = |pk: &ExtremePk, datum: Option<&Datum>| {
+            std::mem::size_of::<(Option<A::OwnedItem>, ExtremePk)>()
+                + pk.iter().map(estimated_datum_size).sum::<usize>()
+                + datum.map_or(0, |datum| 2 * estimated_datum_size(datum))
+        };
+        self.top_n
+            .iter()
+            .map(|((_, pk), datum)| entry_size(pk, Some(datum)))
+            .sum::<usize>()
+            + self
+                .flush_buffer
+                .iter()
+                .map(|((_, pk), status)| entry_size(pk, status.as_option()))
+                .sum::<usize>()
+    }
+
     fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
         self.flush_inner(write_batch)
     }
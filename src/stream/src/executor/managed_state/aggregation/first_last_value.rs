@@ -0,0 +1,316 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use itertools::Itertools;
+use risingwave_common::array::stream_chunk::{Op, Ops};
+use risingwave_common::array::ArrayImpl;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::ordered::OrderedArraysSerializer;
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_common::util::value_encoding::{deserialize_cell, serialize_cell};
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::write_batch::WriteBatch;
+use risingwave_storage::{Keyspace, StateStore};
+
+use crate::executor::managed_state::aggregation::ManagedTableState;
+use crate::executor::managed_state::flush_status::BtreeMapFlushStatus as FlushStatus;
+
+/// The leading byte of the memcomparable encoding of a NULL ordering key.
+const NULL_KEY_TAG: u8 = 0;
+
+/// Managed state for `first_value(x ORDER BY k)` and `last_value(x ORDER BY k)`. Input values are
+/// kept in the state store keyed by the ordering key and the primary key of the input row, so that
+/// the result can be found again after deletions. Like [`super::ManagedArrayAggState`], the cache
+/// is all-or-nothing.
+pub struct ManagedFirstLastValueState<S: StateStore> {
+    cache: BTreeMap<Bytes, FlushStatus<Datum>>,
+
+    /// A cached result.
+    result: Option<Datum>,
+
+    /// Marks whether there are modifications, i.e. cache != storage
+    dirty: bool,
+
+    /// Number of items in the state.
+    total_count: usize,
+
+    /// Whether to output the value with the smallest ordering key or the largest one.
+    is_first: bool,
+
+    /// Type of the aggregated values.
+    value_type: DataType,
+
+    /// The keyspace to operate on.
+    keyspace: Keyspace<S>,
+
+    /// Serializer of the ordering key and the primary key columns, which come after the value
+    /// column in the input.
+    key_serializer: OrderedArraysSerializer,
+}
+
+impl<S: StateStore> ManagedFirstLastValueState<S> {
+    /// Create a managed first/last value state based on `Keyspace`.
+    pub async fn new(
+        keyspace: Keyspace<S>,
+        value_type: DataType,
+        is_first: bool,
+        row_count: usize,
+        pk_len: usize,
+    ) -> Result<Self> {
+        let order_pairs = (1..=pk_len + 1)
+            .map(|idx| OrderPair::new(idx, OrderType::Ascending))
+            .collect_vec();
+        Ok(Self {
+            cache: BTreeMap::new(),
+            result: None,
+            dirty: false,
+            total_count: row_count,
+            is_first,
+            value_type,
+            keyspace,
+            key_serializer: OrderedArraysSerializer::new(order_pairs),
+        })
+    }
+
+    async fn read_all_into_memory(&mut self, epoch: u64) -> Result<()> {
+        // We cannot read from storage into memory when the cache has not been flushed onto the
+        // storage.
+        assert!(!self.is_dirty());
+        let all_data = self.keyspace.scan_strip_prefix(None, epoch).await?;
+        for (raw_key, raw_value) in all_data {
+            let mut deserializer = value_encoding::Deserializer::new(raw_value);
+            let value = deserialize_cell(&mut deserializer, &self.value_type)?;
+            // Values loaded from the storage are marked as `DeleteInsert`, see
+            // `ManagedStringAggState`.
+            self.cache.insert(raw_key, FlushStatus::DeleteInsert(value));
+        }
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Finds the value of the first or last row in the cache. As in an ascending `ORDER BY`, NULL
+    /// ordering keys sort last, while the memcomparable encoding puts them first.
+    fn find_in_cache(&self) -> Datum {
+        let live_entries = || {
+            self.cache
+                .iter()
+                .filter_map(|(key, value)| Some((key[0] == NULL_KEY_TAG, value.as_option()?)))
+        };
+        let found = if self.is_first {
+            live_entries()
+                .find(|(null_key, _)| !null_key)
+                .or_else(|| live_entries().next())
+        } else {
+            live_entries()
+                .rev()
+                .find(|(null_key, _)| *null_key)
+                .or_else(|| live_entries().next_back())
+        };
+        found.and_then(|(_, value)| value.clone())
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> ManagedTableState<S> for ManagedFirstLastValueState<S> {
+    async fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+        epoch: u64,
+    ) -> Result<()> {
+        debug_assert!(super::verify_batch(ops, visibility, data));
+
+        if self.total_count > self.cache.len() {
+            assert_eq!(self.cache.len(), 0);
+            // The cache has been flushed onto the storage. Load everything back.
+            self.read_all_into_memory(epoch).await?;
+        }
+
+        let mut row_keys = vec![];
+        self.key_serializer.serialize(data, &mut row_keys);
+
+        for (row_idx, (op, key_bytes)) in ops.iter().zip_eq(row_keys.into_iter()).enumerate() {
+            let visible = visibility
+                .map(|x| x.is_set(row_idx).unwrap())
+                .unwrap_or(true);
+            if !visible {
+                continue;
+            }
+
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    let value = data[0].datum_at(row_idx);
+                    FlushStatus::do_insert(self.cache.entry(key_bytes.into()), value);
+                    self.total_count += 1;
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    FlushStatus::do_delete(self.cache.entry(key_bytes.into()));
+                    self.total_count -= 1;
+                }
+            }
+            self.dirty = true;
+            self.result = None;
+        }
+        Ok(())
+    }
+
+    async fn get_output(&mut self, epoch: u64) -> Result<Datum> {
+        if self.total_count == 0 {
+            return Ok(None);
+        }
+        if let Some(result) = &self.result {
+            return Ok(result.clone());
+        }
+        // With the all-or-nothing policy, the cache is either complete or empty. It can only be
+        // empty when the state is not dirty.
+        if self.cache.is_empty() {
+            self.read_all_into_memory(epoch).await?;
+        }
+        let result = self.find_in_cache();
+        self.result = Some(result.clone());
+        Ok(result)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn estimated_size(&self) -> usize {
+        self.cache
+            .iter()
+            .map(|(key, status)| {
+                std::mem::size_of::<(Bytes, FlushStatus<Datum>)>()
+                    + key.len()
+                    + status
+                        .as_option()
+                        .and_then(Option::as_ref)
+                        .map_or(0, ScalarImpl::estimated_heap_size)
+            })
+            .sum::<usize>()
+            + self
+                .result
+                .as_ref()
+                .and_then(Option::as_ref)
+                .map_or(0, ScalarImpl::estimated_heap_size)
+    }
+
+    fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
+        if !self.is_dirty() {
+            return Ok(());
+        }
+
+        let mut local = write_batch.prefixify(&self.keyspace);
+
+        for (key, value) in std::mem::take(&mut self.cache) {
+            match value.into_option() {
+                Some(val) => {
+                    local.put(key, StorageValue::new_default_put(serialize_cell(&val)?));
+                }
+                None => {
+                    local.delete(key);
+                }
+            }
+        }
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{I32Array, I64Array, Op};
+    use risingwave_common::types::ScalarImpl;
+
+    use super::*;
+    use crate::executor::test_utils::create_in_memory_keyspace;
+
+    #[tokio::test]
+    async fn test_managed_first_last_value_state() {
+        let keyspace = create_in_memory_keyspace();
+        let store = keyspace.state_store();
+        let epoch: u64 = 0;
+        let values: ArrayImpl = I32Array::from_slice(&[Some(1), Some(2), Some(3)])
+            .unwrap()
+            .into();
+        let order_keys: ArrayImpl = I64Array::from_slice(&[Some(20), None, Some(10)])
+            .unwrap()
+            .into();
+        let pks: ArrayImpl = I64Array::from_slice(&[Some(0), Some(1), Some(2)])
+            .unwrap()
+            .into();
+
+        let mut first_value =
+            ManagedFirstLastValueState::new(keyspace.clone(), DataType::Int32, true, 0, 1)
+                .await
+                .unwrap();
+        let mut last_value =
+            ManagedFirstLastValueState::new(keyspace.clone(), DataType::Int32, false, 0, 1)
+                .await
+                .unwrap();
+        for state in [&mut first_value, &mut last_value] {
+            state
+                .apply_batch(
+                    &[Op::Insert, Op::Insert, Op::Insert],
+                    None,
+                    &[&values, &order_keys, &pks],
+                    epoch,
+                )
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            first_value.get_output(epoch).await.unwrap(),
+            Some(ScalarImpl::Int32(3))
+        );
+        // The NULL ordering key sorts last.
+        assert_eq!(
+            last_value.get_output(epoch).await.unwrap(),
+            Some(ScalarImpl::Int32(2))
+        );
+
+        let mut write_batch = store.start_write_batch();
+        first_value.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+
+        // Recover from the storage and delete the first row.
+        let mut first_value =
+            ManagedFirstLastValueState::new(keyspace.clone(), DataType::Int32, true, 3, 1)
+                .await
+                .unwrap();
+        first_value
+            .apply_batch(
+                &[Op::Delete],
+                None,
+                &[
+                    &I32Array::from_slice(&[Some(3)]).unwrap().into(),
+                    &I64Array::from_slice(&[Some(10)]).unwrap().into(),
+                    &I64Array::from_slice(&[Some(2)]).unwrap().into(),
+                ],
+                epoch,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            first_value.get_output(epoch).await.unwrap(),
+            Some(ScalarImpl::Int32(1))
+        );
+    }
+}
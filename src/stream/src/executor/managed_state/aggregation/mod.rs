@@ -14,7 +14,9 @@
 
 //! Aggregators with state store support
 
+pub use array_agg::*;
 pub use extreme::*;
+pub use first_last_value::*;
 use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::ArrayImpl;
 use risingwave_common::buffer::Bitmap;
@@ -25,10 +27,12 @@ use risingwave_storage::write_batch::WriteBatch;
 use risingwave_storage::{Keyspace, StateStore};
 pub use value::*;
 
-use super::super::{AggCall, PkDataTypes};
+use super::super::{AggArgs, AggCall, PkDataTypes};
 
+mod array_agg;
 mod extreme;
 mod extreme_serializer;
+mod first_last_value;
 mod string_agg;
 mod value;
 
@@ -54,7 +58,7 @@ pub enum ManagedStateImpl<S: StateStore> {
     /// States as single scalar value e.g. `COUNT`, `SUM`
     Value(ManagedValueState<S>),
 
-    /// States as table structure e.g. `MAX`, `STRING_AGG`
+    /// States as table structure e.g. `MAX`, `STRING_AGG`, `ARRAY_AGG`, `FIRST_VALUE`
     Table(Box<dyn ManagedTableState<S>>),
 }
 
@@ -88,6 +92,16 @@ impl<S: StateStore> ManagedStateImpl<S> {
         }
     }
 
+    /// Estimates the memory held by the state in bytes, including the cached entries of table
+    /// states.
+    pub fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + match self {
+                Self::Value(state) => state.estimated_size(),
+                Self::Table(state) => state.estimated_size(),
+            }
+    }
+
     /// Flush the internal state to a write batch.
     pub fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
         match self {
@@ -105,6 +119,16 @@ impl<S: StateStore> ManagedStateImpl<S> {
         is_row_count: bool,
     ) -> Result<Self> {
         match agg_call.kind {
+            // For append-only inputs, `MIN` and `MAX` only need to keep the current extreme value.
+            AggKind::Max | AggKind::Min if agg_call.append_only => {
+                assert!(
+                    row_count.is_some(),
+                    "should set row_count for value states other than AggKind::RowCount"
+                );
+                Ok(Self::Value(
+                    ManagedValueState::new(agg_call, keyspace, row_count).await?,
+                ))
+            }
             AggKind::Max | AggKind::Min => {
                 assert!(
                     row_count.is_some(),
@@ -122,6 +146,57 @@ impl<S: StateStore> ManagedStateImpl<S> {
                     .await?,
                 ))
             }
+            AggKind::ArrayAgg => {
+                assert!(
+                    row_count.is_some(),
+                    "should set row_count for value states other than AggKind::RowCount"
+                );
+                let value_type = match &agg_call.args {
+                    AggArgs::Unary(value_type, _) => value_type.clone(),
+                    args => {
+                        return Err(ErrorCode::InternalError(format!(
+                            "array_agg expects exactly one argument, got {:?}",
+                            args
+                        ))
+                        .into())
+                    }
+                };
+                Ok(Self::Table(Box::new(
+                    ManagedArrayAggState::new(
+                        keyspace,
+                        value_type,
+                        row_count.unwrap(),
+                        pk_data_types.len(),
+                    )
+                    .await?,
+                )))
+            }
+            AggKind::FirstValue | AggKind::LastValue => {
+                assert!(
+                    row_count.is_some(),
+                    "should set row_count for value states other than AggKind::RowCount"
+                );
+                let value_type = match &agg_call.args {
+                    AggArgs::Binary([value_type, _], _) => value_type.clone(),
+                    args => {
+                        return Err(ErrorCode::InternalError(format!(
+                            "{} expects a value and an ordering key, got {:?}",
+                            agg_call.kind, args
+                        ))
+                        .into())
+                    }
+                };
+                Ok(Self::Table(Box::new(
+                    ManagedFirstLastValueState::new(
+                        keyspace,
+                        value_type,
+                        agg_call.kind == AggKind::FirstValue,
+                        row_count.unwrap(),
+                        pk_data_types.len(),
+                    )
+                    .await?,
+                )))
+            }
             AggKind::StringAgg => {
                 // TODO, It seems with `order by`, `StringAgg` needs more stuff from `AggCall`
                 Err(ErrorCode::NotImplemented(
@@ -131,9 +206,17 @@ impl<S: StateStore> ManagedStateImpl<S> {
                 )
                 .into())
             }
-            // TODO: for append-only lists, we can create `ManagedValueState` instead of
-            // `ManagedExtremeState`.
-            AggKind::Avg | AggKind::Count | AggKind::Sum => {
+            AggKind::Avg
+            | AggKind::Count
+            | AggKind::Sum
+            | AggKind::StddevPop
+            | AggKind::StddevSamp
+            | AggKind::VarPop
+            | AggKind::VarSamp
+            | AggKind::BoolAnd
+            | AggKind::BoolOr
+            | AggKind::BitAnd
+            | AggKind::BitOr => {
                 assert!(
                     is_row_count || row_count.is_some(),
                     "should set row_count for value states other than AggKind::RowCount"
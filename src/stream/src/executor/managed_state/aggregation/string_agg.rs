@@ -241,6 +241,20 @@ impl<S: StateStore> ManagedTableState<S> for ManagedStringAggState<S> {
         self.dirty
     }
 
+    fn estimated_size(&self) -> usize {
+        self.cache
+            .iter()
+            .map(|(key, status)| {
+                std::mem::size_of::<(Bytes, FlushStatus<ScalarImpl>)>()
+                    + key.len()
+                    + status
+                        .as_option()
+                        .map_or(0, ScalarImpl::estimated_heap_size)
+            })
+            .sum::<usize>()
+            + self.result.as_ref().map_or(0, String::capacity)
+    }
+
     fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
         if !self.is_dirty() {
             return Ok(());
@@ -22,7 +22,9 @@ use risingwave_storage::storage_value::StorageValue;
 use risingwave_storage::write_batch::WriteBatch;
 use risingwave_storage::{Keyspace, StateStore};
 
-use crate::executor::{create_streaming_agg_state, AggCall, StreamingAggStateImpl};
+use crate::executor::{
+    create_streaming_agg_state, streaming_agg_state_type, AggCall, StreamingAggStateImpl,
+};
 
 /// A wrapper around [`StreamingAggStateImpl`], which fetches data from the state store and helps
 /// update the state. We don't use any trait to wrap around all `ManagedXxxState`, so as to reduce
@@ -55,7 +57,12 @@ impl<S: StateStore> ManagedValueState<S> {
             // Decode the Datum from the value.
             if let Some(raw_data) = raw_data {
                 let mut deserializer = value_encoding::Deserializer::new(raw_data);
-                Some(deserialize_cell(&mut deserializer, &agg_call.return_type)?)
+                let state_type = streaming_agg_state_type(
+                    agg_call.args.arg_types(),
+                    &agg_call.kind,
+                    &agg_call.return_type,
+                );
+                Some(deserialize_cell(&mut deserializer, &state_type)?)
             } else {
                 None
             }
@@ -101,6 +108,11 @@ impl<S: StateStore> ManagedValueState<S> {
         self.is_dirty
     }
 
+    /// Estimates the memory held by the boxed single-value state in bytes.
+    pub fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self.state.as_ref())
+    }
+
     /// Flush the internal state to a write batch.
     pub fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
         // If the managed state is not dirty, the caller should not flush. But forcing a flush won't
@@ -108,7 +120,7 @@ impl<S: StateStore> ManagedValueState<S> {
         debug_assert!(self.is_dirty());
 
         let mut local = write_batch.prefixify(&self.keyspace);
-        let v = self.state.get_output()?;
+        let v = self.state.get_state()?;
         // TODO(Yuanxin): Implement value meta
         local.put_single(StorageValue::new_default_put(serialize_cell(&v)?));
         self.is_dirty = false;
@@ -122,14 +134,16 @@ mod tests {
     use risingwave_common::types::{DataType, ScalarImpl};
 
     use super::*;
+    use crate::executor::managed_state::aggregation::ManagedStateImpl;
     use crate::executor::test_utils::create_in_memory_keyspace;
-    use crate::executor::AggArgs;
+    use crate::executor::{AggArgs, PkDataTypes};
 
     fn create_test_count_state() -> AggCall {
         AggCall {
             kind: risingwave_expr::expr::AggKind::Count,
             args: AggArgs::Unary(DataType::Int64, 0),
             return_type: DataType::Int64,
+            append_only: false,
         }
     }
 
@@ -176,4 +190,57 @@ mod tests {
             Some(ScalarImpl::Int64(3))
         );
     }
+
+    #[tokio::test]
+    async fn test_managed_value_state_append_only_min() {
+        let keyspace = create_in_memory_keyspace();
+        let agg_call = AggCall {
+            kind: risingwave_expr::expr::AggKind::Min,
+            args: AggArgs::Unary(DataType::Int64, 0),
+            return_type: DataType::Int64,
+            append_only: true,
+        };
+        let mut managed_state = ManagedStateImpl::create_managed_state(
+            agg_call,
+            keyspace.clone(),
+            Some(0),
+            PkDataTypes::new(),
+            false,
+        )
+        .await
+        .unwrap();
+        // An append-only `MIN` doesn't need to keep all the values in a table state.
+        assert!(matches!(managed_state, ManagedStateImpl::Value(_)));
+
+        managed_state
+            .apply_batch(
+                &[Op::Insert, Op::Insert, Op::Insert],
+                None,
+                &[&I64Array::from_slice(&[Some(3), Some(1), Some(2)])
+                    .unwrap()
+                    .into()],
+                0,
+            )
+            .await
+            .unwrap();
+        let epoch: u64 = 0;
+        let mut write_batch = keyspace.state_store().start_write_batch();
+        managed_state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+        assert_eq!(
+            managed_state.get_output(epoch).await.unwrap(),
+            Some(ScalarImpl::Int64(1))
+        );
+
+        // Retractions are not supported on an append-only state.
+        assert!(managed_state
+            .apply_batch(
+                &[Op::Delete],
+                None,
+                &[&I64Array::from_slice(&[Some(1)]).unwrap().into()],
+                0,
+            )
+            .await
+            .is_err());
+    }
 }
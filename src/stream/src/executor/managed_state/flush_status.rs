@@ -62,6 +62,14 @@ macro_rules! impl_flush_status {
                     }
                 }
 
+                #[allow(dead_code)]
+                pub fn as_option_mut(&mut self) -> Option<&mut T> {
+                    match self {
+                        Self::DeleteInsert(value) | Self::Insert(value) => Some(value),
+                        Self::Delete => None,
+                    }
+                }
+
                 /// Insert an entry and modify the corresponding flush state
                 pub fn do_insert<K: Ord + std::fmt::Debug>(entry: $entry_type, value: T) {
                     match entry {
@@ -26,18 +26,28 @@ use risingwave_storage::{Keyspace, StateStore};
 use super::*;
 use crate::executor::managed_state::flush_status::BtreeMapFlushStatus as FlushStatus;
 
-type JoinEntryStateIter<'a> = btree_map::Iter<'a, PkType, StateValueType>;
-
-type JoinEntryStateValues<'a> = btree_map::Values<'a, PkType, StateValueType>;
-
-type JoinEntryStateValuesMut<'a> = btree_map::ValuesMut<'a, PkType, StateValueType>;
+/// The number of rows to read from the state store at a time.
+const PAGE_SIZE: usize = 1024;
 
 /// Manages a `BTreeMap` in memory for all entries. When evicted, `BTreeMap` does not hold any
 /// entries.
+///
+/// The cache is only kept if the size of the rows is within `cache_limit`. Otherwise, the rows are
+/// paged from the state store each time they are visited, so that a hot key with lots of rows won't
+/// take up all the memory.
 pub struct JoinEntryState<S: StateStore> {
     /// The full copy of the state. If evicted, it will be `None`.
     cached: Option<BTreeMap<PkType, StateValueType>>,
 
+    /// The estimated size of `cached` in bytes.
+    cached_size: usize,
+
+    /// The max size of `cached` in bytes.
+    cache_limit: usize,
+
+    /// Whether the rows have exceeded `cache_limit`, in which case they are not cached.
+    exceeds_limit: bool,
+
     /// The actions that will be taken on next flush
     flush_buffer: BTreeMap<PkType, FlushStatus<StateValueType>>,
 
@@ -56,9 +66,13 @@ impl<S: StateStore> JoinEntryState<S> {
         keyspace: Keyspace<S>,
         data_types: Arc<[DataType]>,
         pk_data_types: Arc<[DataType]>,
+        cache_limit: usize,
     ) -> Self {
         Self {
             cached: None,
+            cached_size: 0,
+            cache_limit,
+            exceeds_limit: false,
             flush_buffer: BTreeMap::new(),
             data_types,
             pk_data_types,
@@ -70,38 +84,52 @@ impl<S: StateStore> JoinEntryState<S> {
         keyspace: Keyspace<S>,
         data_types: Arc<[DataType]>,
         pk_data_types: Arc<[DataType]>,
+        cache_limit: usize,
         epoch: u64,
     ) -> Result<Option<Self>> {
-        let all_data = keyspace.scan_strip_prefix(None, epoch).await?;
-        if !all_data.is_empty() {
-            // Insert cached states.
-            let cached = Self::fill_cached(all_data, data_types.clone(), pk_data_types.clone())?;
-            Ok(Some(Self {
-                cached: Some(cached),
-                flush_buffer: BTreeMap::new(),
-                data_types,
-                pk_data_types,
-                keyspace,
-            }))
+        let mut state = Self::new(keyspace, data_types, pk_data_types, cache_limit);
+        if state.populate_cache(epoch).await? {
+            Ok(Some(state))
         } else {
             Ok(None)
         }
     }
 
-    fn fill_cached(
-        data: Vec<(Bytes, Bytes)>,
-        data_types: Arc<[DataType]>,
-        pk_data_types: Arc<[DataType]>,
-    ) -> Result<BTreeMap<PkType, StateValueType>> {
-        let mut cached = BTreeMap::new();
-        for (raw_key, raw_value) in data {
-            let pk_deserializer = RowDeserializer::new(pk_data_types.to_vec());
-            let key = pk_deserializer.deserialize_not_null(&raw_key)?;
-            let deserializer = JoinRowDeserializer::new(data_types.to_vec());
-            let value = deserializer.deserialize(&raw_value)?;
-            cached.insert(key, value);
-        }
-        Ok(cached)
+    fn entry_size(pk: &PkType, value: &StateValueType) -> usize {
+        pk.estimated_size() + value.estimated_size()
+    }
+
+    /// The estimated memory held by the state in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.cached_size
+    }
+
+    fn deserialize_entry(&self, raw_key: &[u8], raw_value: &[u8]) -> Result<(PkType, JoinRow)> {
+        let pk_deserializer = RowDeserializer::new(self.pk_data_types.to_vec());
+        let key = pk_deserializer.deserialize_not_null(&raw_key[self.keyspace.key().len()..])?;
+        let deserializer = JoinRowDeserializer::new(self.data_types.to_vec());
+        let value = deserializer.deserialize(raw_value)?;
+        Ok((key, value))
+    }
+
+    /// Scans a page of at most [`PAGE_SIZE`] rows from `start_key`, and returns the start key of
+    /// the next page if there may be more rows.
+    async fn scan_page(
+        &self,
+        start_key: Vec<u8>,
+        epoch: u64,
+    ) -> Result<(Vec<(Bytes, Bytes)>, Option<Vec<u8>>)> {
+        let page = self
+            .keyspace
+            .scan_with_start_key(start_key, Some(PAGE_SIZE), epoch)
+            .await?;
+        let next_start_key = match page.last() {
+            Some((last_key, _)) if page.len() == PAGE_SIZE => {
+                Some([&last_key[..], &[0u8][..]].concat())
+            }
+            _ => None,
+        };
+        Ok((page, next_start_key))
     }
 
     /// The state is dirty means there are unflush
@@ -110,10 +138,22 @@ impl<S: StateStore> JoinEntryState<S> {
         !self.flush_buffer.is_empty()
     }
 
+    fn drop_cache_if_exceeds_limit(&mut self) {
+        if self.cached_size > self.cache_limit {
+            self.cached = None;
+            self.cached_size = 0;
+            self.exceeds_limit = true;
+        }
+    }
+
     // Insert into the cache and flush buffer.
     pub fn insert(&mut self, key: PkType, value: StateValueType) {
         if let Some(cached) = self.cached.as_mut() {
-            cached.insert(key.clone(), value.clone());
+            self.cached_size += Self::entry_size(&key, &value);
+            if let Some(old) = cached.insert(key.clone(), value.clone()) {
+                self.cached_size -= Self::entry_size(&key, &old);
+            }
+            self.drop_cache_if_exceeds_limit();
         }
         // If no cache maintained, only update the flush buffer.
         FlushStatus::do_insert(self.flush_buffer.entry(key), value);
@@ -121,12 +161,32 @@ impl<S: StateStore> JoinEntryState<S> {
 
     pub fn remove(&mut self, pk: PkType) {
         if let Some(cached) = self.cached.as_mut() {
-            cached.remove(&pk);
+            if let Some(old) = cached.remove(&pk) {
+                self.cached_size -= Self::entry_size(&pk, &old);
+            }
         }
         // If no cache maintained, only update the flush buffer.
         FlushStatus::do_delete(self.flush_buffer.entry(pk));
     }
 
+    /// Records that a row visited by [`JoinEntryState::for_each_mut`] has been changed, so that
+    /// it will be written on next flush.
+    fn record_update(
+        flush_buffer: &mut BTreeMap<PkType, FlushStatus<StateValueType>>,
+        pk: PkType,
+        value: StateValueType,
+    ) {
+        match flush_buffer.entry(pk) {
+            btree_map::Entry::Vacant(e) => {
+                e.insert(FlushStatus::DeleteInsert(value));
+            }
+            btree_map::Entry::Occupied(mut e) => match e.get_mut() {
+                FlushStatus::Insert(v) | FlushStatus::DeleteInsert(v) => *v = value,
+                FlushStatus::Delete => unreachable!("a deleted row can not be updated"),
+            },
+        }
+    }
+
     // Flush data to the state store
     pub fn flush(&mut self, write_batch: &mut WriteBatch<S>) -> Result<()> {
         let mut local = write_batch.prefixify(&self.keyspace);
@@ -148,35 +208,110 @@ impl<S: StateStore> JoinEntryState<S> {
         Ok(())
     }
 
-    // Fetch cache from the state store.
-    async fn populate_cache(&mut self, epoch: u64) -> Result<()> {
+    /// Fetch cache from the state store, unless the rows exceed the cache limit. Returns whether
+    /// there are any rows in the state store.
+    async fn populate_cache(&mut self, epoch: u64) -> Result<bool> {
         assert!(self.cached.is_none());
 
-        let all_data = self.keyspace.scan_strip_prefix(None, epoch).await?;
-
-        // Insert cached states.
-        let mut cached = Self::fill_cached(
-            all_data,
-            self.data_types.clone(),
-            self.pk_data_types.clone(),
-        )?;
+        let mut cached = BTreeMap::new();
+        let mut cached_size = 0;
+        let mut start_key = Some(self.keyspace.key().to_vec());
+        while let Some(key) = start_key {
+            let (page, next_start_key) = self.scan_page(key, epoch).await?;
+            for (raw_key, raw_value) in page {
+                let (pk, value) = self.deserialize_entry(&raw_key, &raw_value)?;
+                cached_size += Self::entry_size(&pk, &value);
+                cached.insert(pk, value);
+            }
+            if cached_size > self.cache_limit {
+                self.exceeds_limit = true;
+                return Ok(true);
+            }
+            start_key = next_start_key;
+        }
+        let exists = !cached.is_empty();
 
         // Apply current flush buffer to cached states.
         for (pk, row) in &self.flush_buffer {
-            match row.as_option() {
+            let old = match row.as_option() {
                 Some(row) => {
-                    cached.insert(pk.clone(), row.clone());
-                }
-                None => {
-                    cached.remove(pk);
+                    cached_size += Self::entry_size(pk, row);
+                    cached.insert(pk.clone(), row.clone())
                 }
+                None => cached.remove(pk),
+            };
+            if let Some(old) = old {
+                cached_size -= Self::entry_size(pk, &old);
             }
         }
 
         self.cached = Some(cached);
+        self.cached_size = cached_size;
+        self.drop_cache_if_exceeds_limit();
+        Ok(exists)
+    }
+
+    /// Calls `f` on each row. If the rows are not cached, they are fetched into the cache, or
+    /// paged from the state store if they exceed the cache limit. The changes made by `f`, i.e.
+    /// the degrees, will be written on next flush.
+    pub async fn for_each_mut(
+        &mut self,
+        epoch: u64,
+        mut f: impl FnMut(&mut JoinRow) -> Result<()>,
+    ) -> Result<()> {
+        if self.cached.is_none() && !self.exceeds_limit {
+            self.populate_cache(epoch).await?;
+        }
+
+        if let Some(cached) = self.cached.as_mut() {
+            for (pk, row) in cached.iter_mut() {
+                let degree = row.degree;
+                f(row)?;
+                if row.degree != degree {
+                    Self::record_update(&mut self.flush_buffer, pk.clone(), row.clone());
+                }
+            }
+            return Ok(());
+        }
+
+        // The rows in the flush buffer are newer than the ones in the state store, so they are
+        // visited in place, and then skipped when paging from the state store.
+        for status in self.flush_buffer.values_mut() {
+            if let Some(row) = status.as_option_mut() {
+                f(row)?;
+            }
+        }
+        let mut start_key = Some(self.keyspace.key().to_vec());
+        while let Some(key) = start_key {
+            let (page, next_start_key) = self.scan_page(key, epoch).await?;
+            for (raw_key, raw_value) in page {
+                let (pk, mut row) = self.deserialize_entry(&raw_key, &raw_value)?;
+                if self.flush_buffer.contains_key(&pk) {
+                    continue;
+                }
+                let degree = row.degree;
+                f(&mut row)?;
+                if row.degree != degree {
+                    Self::record_update(&mut self.flush_buffer, pk, row);
+                }
+            }
+            start_key = next_start_key;
+        }
         Ok(())
     }
 
+    /// Retains only the cached rows whose pk satisfies `f`. Note that this doesn't touch the
+    /// state store.
+    pub fn retain_cached(&mut self, mut f: impl FnMut(&PkType) -> bool) {
+        if let Some(cached) = self.cached.as_mut() {
+            cached.retain(|pk, _| f(pk));
+            self.cached_size = cached
+                .iter()
+                .map(|(pk, value)| Self::entry_size(pk, value))
+                .sum();
+        }
+    }
+
     #[allow(dead_code)]
     pub fn clear_cache(&mut self) {
         assert!(
@@ -184,29 +319,8 @@ impl<S: StateStore> JoinEntryState<S> {
             "cannot clear cache while all or none state is dirty"
         );
         self.cached = None;
-    }
-
-    #[allow(dead_code)]
-    pub async fn iter(&mut self, epoch: u64) -> JoinEntryStateIter<'_> {
-        if self.cached.is_none() {
-            self.populate_cache(epoch).await.unwrap();
-        }
-        self.cached.as_ref().unwrap().iter()
-    }
-
-    #[allow(dead_code)]
-    pub async fn values(&mut self, epoch: u64) -> JoinEntryStateValues<'_> {
-        if self.cached.is_none() {
-            self.populate_cache(epoch).await.unwrap();
-        }
-        self.cached.as_ref().unwrap().values()
-    }
-
-    pub async fn values_mut(&mut self, epoch: u64) -> JoinEntryStateValuesMut<'_> {
-        if self.cached.is_none() {
-            self.populate_cache(epoch).await.unwrap();
-        }
-        self.cached.as_mut().unwrap().values_mut()
+        self.cached_size = 0;
+        self.exceeds_limit = false;
     }
 }
 
@@ -219,23 +333,12 @@ mod tests {
 
     use super::*;
 
-    #[tokio::test]
-    async fn test_managed_all_or_none_state() {
-        let store = MemoryStateStore::new();
-        let keyspace = Keyspace::executor_root(store.clone(), 0x2333);
-        let mut managed_state = JoinEntryState::new(
-            keyspace,
-            vec![DataType::Int64, DataType::Int64].into(),
-            vec![DataType::Int64].into(),
-        );
-        assert!(!managed_state.is_dirty());
+    fn insert_chunk<S: StateStore>(managed_state: &mut JoinEntryState<S>) {
         let columns = vec![
             column_nonnull! { I64Array, [3, 2, 1] },
             column_nonnull! { I64Array, [4, 5, 6] },
         ];
         let pk_indices = [0];
-        let col1 = [1, 2, 3];
-        let col2 = [6, 5, 4];
         let data_chunk_builder = DataChunk::builder().columns(columns);
         let data_chunk = data_chunk_builder.build();
 
@@ -246,14 +349,42 @@ mod tests {
             let join_row = JoinRow { row, degree: 0 };
             managed_state.insert(pk, join_row);
         }
+    }
 
-        let epoch = 0;
-        for state in managed_state
-            .iter(epoch)
+    async fn collect_rows<S: StateStore>(
+        managed_state: &mut JoinEntryState<S>,
+        epoch: u64,
+    ) -> Vec<JoinRow> {
+        let mut rows = vec![];
+        managed_state
+            .for_each_mut(epoch, |row| {
+                rows.push(row.clone());
+                Ok(())
+            })
             .await
-            .zip_eq(col1.iter().zip_eq(col2.iter()))
-        {
-            let ((key, value), (d1, d2)) = state;
+            .unwrap();
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_managed_all_or_none_state() {
+        let store = MemoryStateStore::new();
+        let keyspace = Keyspace::executor_root(store.clone(), 0x2333);
+        let mut managed_state = JoinEntryState::new(
+            keyspace,
+            vec![DataType::Int64, DataType::Int64].into(),
+            vec![DataType::Int64].into(),
+            usize::MAX,
+        );
+        assert!(!managed_state.is_dirty());
+        insert_chunk(&mut managed_state);
+
+        let col1 = [1, 2, 3];
+        let col2 = [6, 5, 4];
+        let epoch = 0;
+        let rows = collect_rows(&mut managed_state, epoch).await;
+        let keys = managed_state.cached.as_ref().unwrap().keys();
+        for ((key, value), (d1, d2)) in keys.zip_eq(rows).zip_eq(col1.iter().zip_eq(col2.iter())) {
             assert_eq!(key.0[0], Some(ScalarImpl::Int64(*d1)));
             assert_eq!(value.row[0], Some(ScalarImpl::Int64(*d1)));
             assert_eq!(value.row[1], Some(ScalarImpl::Int64(*d2)));
@@ -267,4 +398,127 @@ mod tests {
 
         assert!(!managed_state.is_dirty());
     }
+
+    #[tokio::test]
+    async fn test_managed_state_exceeding_cache_limit() {
+        let store = MemoryStateStore::new();
+        let keyspace = Keyspace::executor_root(store.clone(), 0x2333);
+        let mut managed_state = JoinEntryState::new(
+            keyspace.clone(),
+            vec![DataType::Int64, DataType::Int64].into(),
+            vec![DataType::Int64].into(),
+            0,
+        );
+        insert_chunk(&mut managed_state);
+        let epoch = 0;
+        let mut write_batch = store.start_write_batch();
+        managed_state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+
+        // The rows are paged from the state store, and the degrees are updated.
+        managed_state
+            .for_each_mut(epoch, |row| {
+                row.inc_degree();
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert!(managed_state.cached.is_none());
+        assert_eq!(managed_state.estimated_size(), 0);
+        assert!(managed_state.is_dirty());
+
+        let epoch = 1;
+        let mut write_batch = store.start_write_batch();
+        managed_state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+
+        // A new state with enough cache limit sees the updated degrees.
+        let mut managed_state = JoinEntryState::with_cached_state(
+            keyspace,
+            vec![DataType::Int64, DataType::Int64].into(),
+            vec![DataType::Int64].into(),
+            usize::MAX,
+            epoch,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let rows = collect_rows(&mut managed_state, epoch).await;
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.degree == 1));
+    }
+
+    #[tokio::test]
+    async fn test_managed_state_evicted_by_insert() {
+        let store = MemoryStateStore::new();
+        let keyspace = Keyspace::executor_root(store.clone(), 0x2333);
+        let mut managed_state = JoinEntryState::new(
+            keyspace.clone(),
+            vec![DataType::Int64, DataType::Int64].into(),
+            vec![DataType::Int64].into(),
+            usize::MAX,
+        );
+        insert_chunk(&mut managed_state);
+        let epoch = 0;
+        let mut write_batch = store.start_write_batch();
+        managed_state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+        collect_rows(&mut managed_state, epoch).await;
+        let size = managed_state.estimated_size();
+        assert!(size > 0);
+
+        // The cache fits the 3 rows exactly.
+        let mut managed_state = JoinEntryState::with_cached_state(
+            keyspace,
+            vec![DataType::Int64, DataType::Int64].into(),
+            vec![DataType::Int64].into(),
+            size,
+            epoch,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(managed_state.cached.is_some());
+        assert_eq!(managed_state.estimated_size(), size);
+
+        // Inserting one more row exceeds the limit, so the cache is dropped.
+        let row = Row(vec![Some(ScalarImpl::Int64(4)), Some(ScalarImpl::Int64(3))]);
+        managed_state.insert(Row(vec![Some(ScalarImpl::Int64(4))]), JoinRow::new(row, 0));
+        assert!(managed_state.cached.is_none());
+        assert_eq!(managed_state.estimated_size(), 0);
+
+        // The rows are visited from both the state store and the flush buffer, without caching.
+        let rows = collect_rows(&mut managed_state, epoch).await;
+        assert_eq!(rows.len(), 4);
+        assert!(managed_state.cached.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_managed_state_clear_cache() {
+        let store = MemoryStateStore::new();
+        let keyspace = Keyspace::executor_root(store.clone(), 0x2333);
+        let mut managed_state = JoinEntryState::new(
+            keyspace,
+            vec![DataType::Int64, DataType::Int64].into(),
+            vec![DataType::Int64].into(),
+            usize::MAX,
+        );
+        insert_chunk(&mut managed_state);
+        let epoch = 0;
+        let mut write_batch = store.start_write_batch();
+        managed_state.flush(&mut write_batch).unwrap();
+        write_batch.ingest(epoch).await.unwrap();
+        collect_rows(&mut managed_state, epoch).await;
+        assert!(managed_state.cached.is_some());
+
+        managed_state.clear_cache();
+        assert!(managed_state.cached.is_none());
+        assert_eq!(managed_state.estimated_size(), 0);
+
+        // The cache is populated from the state store again on the next visit.
+        let rows = collect_rows(&mut managed_state, epoch).await;
+        assert_eq!(rows.len(), 3);
+        assert!(managed_state.cached.is_some());
+        assert!(managed_state.estimated_size() > 0);
+    }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod join_entry_state;
+use std::collections::HashSet;
 use std::ops::{Deref, DerefMut, Index};
 use std::sync::Arc;
 
@@ -21,8 +22,9 @@ pub use join_entry_state::JoinEntryState;
 use risingwave_common::array::Row;
 use risingwave_common::collection::evictable::EvictableHashMap;
 use risingwave_common::error::Result as RwResult;
-use risingwave_common::types::{DataType, Datum};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
 use risingwave_common::util::value_encoding::{deserialize_cell, serialize_cell};
+use risingwave_storage::write_batch::WriteBatch;
 use risingwave_storage::{Keyspace, StateStore};
 use serde::{Deserialize, Serialize};
 
@@ -51,6 +53,11 @@ impl JoinRow {
         self.row.size()
     }
 
+    /// Estimates the memory held by the row in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.row.estimated_size() + std::mem::size_of::<u64>()
+    }
+
     pub fn is_zero_degree(&self) -> bool {
         self.degree == 0
     }
@@ -115,23 +122,34 @@ pub type StateValueType = JoinRow;
 pub type HashKeyType = Row;
 pub type HashValueType<S> = JoinEntryState<S>;
 
+/// A single join key can take at most `1 / ENTRY_CACHE_LIMIT_DIVISOR` of the cache. The rows of a
+/// larger key are paged from the state store on each probe.
+const ENTRY_CACHE_LIMIT_DIVISOR: usize = 4;
+
 pub struct JoinHashMap<S: StateStore> {
     /// Store the join states.
     inner: EvictableHashMap<HashKeyType, HashValueType<S>>,
+    /// The memory budget of the join states in bytes.
+    cache_capacity: usize,
     /// Data types of the columns
     data_types: Arc<[DataType]>,
     /// Data types of primary keys
     pk_data_types: Arc<[DataType]>,
     /// The keyspace to operate on.
     keyspace: Keyspace<S>,
+    /// The join keys whose states are written or loaded by this executor, including the evicted
+    /// ones. The keyspace is shared with the other actors of the fragment, so the cleaning of
+    /// expired states is bounded to these keys.
+    state_keys: HashSet<HashKeyType>,
     /// Current epoch
     current_epoch: u64,
 }
 
 impl<S: StateStore> JoinHashMap<S> {
-    /// Create a [`JoinHashMap`] with the given LRU capacity.
+    /// Create a [`JoinHashMap`] which keeps at most `cache_capacity` bytes of join states in memory
+    /// after eviction.
     pub fn new(
-        target_cap: usize,
+        cache_capacity: usize,
         pk_indices: Vec<usize>,
         data_types: Vec<DataType>,
         keyspace: Keyspace<S>,
@@ -142,10 +160,13 @@ impl<S: StateStore> JoinHashMap<S> {
             .collect_vec();
 
         Self {
-            inner: EvictableHashMap::new(target_cap),
+            // The cache is evicted by size instead of the number of keys.
+            inner: EvictableHashMap::new(usize::MAX),
+            cache_capacity,
             data_types: data_types.into(),
             pk_data_types: pk_data_types.into(),
             keyspace,
+            state_keys: HashSet::new(),
             current_epoch: 0,
         }
     }
@@ -154,6 +175,49 @@ impl<S: StateStore> JoinHashMap<S> {
         self.current_epoch = epoch;
     }
 
+    pub fn cache_capacity(&self) -> usize {
+        self.cache_capacity
+    }
+
+    /// Forgets the tracked join keys, e.g. when the vnodes of the actor change and some keys are
+    /// moved to other actors. The keys are tracked again once they're accessed.
+    pub fn clear_state_keys(&mut self) {
+        self.state_keys.clear();
+    }
+
+    fn put_state(&mut self, key: &HashKeyType, state: HashValueType<S>) {
+        self.state_keys.insert(key.clone());
+        self.inner.put(key.clone(), state);
+    }
+
+    fn entry_cache_limit(&self) -> usize {
+        self.cache_capacity / ENTRY_CACHE_LIMIT_DIVISOR
+    }
+
+    fn entry_size(key: &HashKeyType, state: &HashValueType<S>) -> usize {
+        key.estimated_size() + state.estimated_size()
+    }
+
+    /// The estimated memory held by the cached join states in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.inner
+            .iter()
+            .map(|(key, state)| Self::entry_size(key, state))
+            .sum()
+    }
+
+    /// Evicts the least recently used join keys until the estimated size of the states is within
+    /// `size`. Should only be called when no state is dirty.
+    pub fn evict_to(&mut self, size: usize) {
+        self.inner.evict_to_size(size, Self::entry_size);
+    }
+
+    /// Evicts the least recently used join keys until the estimated size of the states is within
+    /// the cache capacity. Should only be called when no state is dirty.
+    pub fn evict_to_capacity(&mut self) {
+        self.evict_to(self.cache_capacity);
+    }
+
     fn get_state_keyspace(&self, key: &HashKeyType) -> Keyspace<S> {
         // TODO: in pure in-memory engine, we should not do this serialization.
         let key_encoded = key.serialize().unwrap();
@@ -171,7 +235,7 @@ impl<S: StateStore> JoinHashMap<S> {
             None => {
                 let remote_state = self.fetch_cached_state(key).await.unwrap();
                 remote_state.map(|rv| {
-                    self.inner.put(key.clone(), rv);
+                    self.put_state(key, rv);
                     self.inner.get(key).unwrap()
                 })
             }
@@ -188,7 +252,7 @@ impl<S: StateStore> JoinHashMap<S> {
             None => {
                 let remote_state = self.fetch_cached_state(key).await.unwrap();
                 remote_state.map(|rv| {
-                    self.inner.put(key.clone(), rv);
+                    self.put_state(key, rv);
                     self.inner.get_mut(key).unwrap()
                 })
             }
@@ -208,18 +272,19 @@ impl<S: StateStore> JoinHashMap<S> {
             Some(_) => self.inner.get_mut(key),
             None => {
                 let keyspace = self.get_state_keyspace(key);
-                let all_data = keyspace
-                    .scan_strip_prefix(None, self.current_epoch)
+                let exists = !keyspace
+                    .scan(Some(1), self.current_epoch)
                     .await
-                    .unwrap();
-                let total_count = all_data.len();
-                if total_count > 0 {
+                    .unwrap()
+                    .is_empty();
+                if exists {
                     let state = JoinEntryState::new(
                         keyspace,
                         self.data_types.clone(),
                         self.pk_data_types.clone(),
+                        self.entry_cache_limit(),
                     );
-                    self.inner.put(key.clone(), state);
+                    self.put_state(key, state);
                     Some(self.inner.get_mut(key).unwrap())
                 } else {
                     None
@@ -238,7 +303,7 @@ impl<S: StateStore> JoinHashMap<S> {
             let remote_state = self.fetch_cached_state(key).await.unwrap();
             match remote_state {
                 Some(rv) => {
-                    self.inner.put(key.clone(), rv);
+                    self.put_state(key, rv);
                     true
                 }
                 None => false,
@@ -253,6 +318,7 @@ impl<S: StateStore> JoinHashMap<S> {
             keyspace,
             self.data_types.clone(),
             self.pk_data_types.clone(),
+            self.entry_cache_limit(),
             self.current_epoch,
         )
         .await
@@ -266,8 +332,9 @@ impl<S: StateStore> JoinHashMap<S> {
             keyspace,
             self.data_types.clone(),
             self.pk_data_types.clone(),
+            self.entry_cache_limit(),
         );
-        self.inner.put(key.clone(), state);
+        self.put_state(key, state);
         Ok(())
     }
 
@@ -286,6 +353,68 @@ impl<S: StateStore> JoinHashMap<S> {
             Ok(self.inner.get_mut(key).unwrap())
         }
     }
+
+    /// Removes the states of the join keys whose `key_col`-th column is smaller than `watermark`,
+    /// both from the cache and from the state store. Such keys will never be matched again once
+    /// both sides have passed the watermark. Only the tracked join keys are cleaned. Should only
+    /// be called when no state is dirty.
+    pub async fn clean_below_watermark(
+        &mut self,
+        key_col: usize,
+        watermark: &ScalarImpl,
+        write_batch: &mut WriteBatch<S>,
+    ) -> RwResult<()> {
+        let expired = |key: &HashKeyType| matches!(&key[key_col], Some(v) if v < watermark);
+
+        self.inner.retain_keys(|key| !expired(key));
+
+        let expired_keys = self
+            .state_keys
+            .iter()
+            .filter(|key| expired(key))
+            .cloned()
+            .collect_vec();
+        for key in expired_keys {
+            let keyspace = self.get_state_keyspace(&key);
+            let mut local = write_batch.prefixify(&keyspace);
+            for (raw_key, _) in keyspace.scan_strip_prefix(None, self.current_epoch).await? {
+                local.delete(&raw_key);
+            }
+            self.state_keys.remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Removes the rows whose time column is smaller than `threshold`, both from the cache and from
+    /// the state store. Used by interval joins, where the time column is the first column of the
+    /// state pk, so that the expired rows of each join key are in a bounded range. Only the
+    /// tracked join keys are cleaned. Should only be called when no state is dirty.
+    pub async fn clean_rows_before(
+        &mut self,
+        threshold: &ScalarImpl,
+        write_batch: &mut WriteBatch<S>,
+    ) -> RwResult<()> {
+        let expired = |datum: &Datum| matches!(datum, Some(v) if v < threshold);
+
+        for state in self.inner.values_mut() {
+            state.retain_cached(|pk| !expired(&pk[0]));
+        }
+
+        // The state keys are encoded as `[join key | time | pk]`, so the expired rows of a join key
+        // are the ones before `[join key | threshold]`.
+        let end_key = Row(vec![Some(threshold.clone())]).serialize_not_null()?;
+        for key in &self.state_keys {
+            let keyspace = self.get_state_keyspace(key);
+            let mut local = write_batch.prefixify(&keyspace);
+            for (raw_key, _) in keyspace
+                .scan_strip_prefix_before(&end_key, self.current_epoch)
+                .await?
+            {
+                local.delete(&raw_key);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<S: StateStore> Deref for JoinHashMap<S> {
@@ -301,3 +430,76 @@ impl<S: StateStore> DerefMut for JoinHashMap<S> {
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+
+    fn join_key(key: i64) -> HashKeyType {
+        Row(vec![Some(ScalarImpl::Int64(key))])
+    }
+
+    async fn row_count<S: StateStore>(state: &mut JoinEntryState<S>, epoch: u64) -> usize {
+        let mut count = 0;
+        state
+            .for_each_mut(epoch, |_| {
+                count += 1;
+                Ok(())
+            })
+            .await
+            .unwrap();
+        count
+    }
+
+    #[tokio::test]
+    async fn test_evict_to() {
+        let store = MemoryStateStore::new();
+        let keyspace = Keyspace::executor_root(store.clone(), 0x2333);
+        let mut map = JoinHashMap::new(
+            usize::MAX,
+            vec![1],
+            vec![DataType::Int64, DataType::Int64],
+            keyspace,
+        );
+
+        // Write a row for each of the join keys 1 and 2.
+        let epoch = 0;
+        map.update_epoch(epoch);
+        for key in [1, 2] {
+            let row = Row(vec![
+                Some(ScalarImpl::Int64(key)),
+                Some(ScalarImpl::Int64(key)),
+            ]);
+            let state = map.get_or_init_without_cache(&join_key(key)).await.unwrap();
+            state.insert(join_key(key), JoinRow::new(row, 0));
+        }
+        let mut write_batch = store.start_write_batch();
+        for state in map.values_mut() {
+            state.flush(&mut write_batch).unwrap();
+        }
+        write_batch.ingest(epoch).await.unwrap();
+
+        // Load the rows into the cache, where the key 1 is the least recently used.
+        for key in [1, 2] {
+            let state = map.get_mut(&join_key(key)).await.unwrap();
+            assert_eq!(row_count(state, epoch).await, 1);
+        }
+        let size = map.estimated_size();
+
+        map.evict_to(size - 1);
+        assert!(!map.inner.contains(&join_key(1)));
+        assert!(map.inner.contains(&join_key(2)));
+        assert!(map.estimated_size() < size);
+
+        // The evicted key is loaded from the state store again.
+        let state = map.get_mut(&join_key(1)).await.unwrap();
+        assert_eq!(row_count(state, epoch).await, 1);
+        assert_eq!(map.estimated_size(), size);
+
+        map.evict_to(0);
+        assert!(map.inner.is_empty());
+        assert_eq!(map.estimated_size(), 0);
+    }
+}
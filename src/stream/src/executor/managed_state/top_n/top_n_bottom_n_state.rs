@@ -92,6 +92,22 @@ impl<S: StateStore> ManagedTopNBottomNState<S> {
         !self.flush_buffer.is_empty()
     }
 
+    /// Estimates the memory held by the cached rows in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.top_n
+            .iter()
+            .chain(self.bottom_n.iter())
+            .map(|(key, row)| key.estimated_size() + row.estimated_size())
+            .sum::<usize>()
+            + self
+                .flush_buffer
+                .iter()
+                .map(|(key, status)| {
+                    key.estimated_size() + status.as_option().map_or(0, Row::estimated_size)
+                })
+                .sum::<usize>()
+    }
+
     // May have weird cache policy in the future, reserve an `n`.
     pub fn retain_top_n(&mut self, n: usize) {
         while self.top_n.len() > n {
@@ -88,6 +88,21 @@ impl<S: StateStore, const TOP_N_TYPE: usize> ManagedTopNState<S, TOP_N_TYPE> {
         !self.flush_buffer.is_empty()
     }
 
+    /// Estimates the memory held by the cached rows in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.top_n
+            .iter()
+            .map(|(key, row)| key.estimated_size() + row.estimated_size())
+            .sum::<usize>()
+            + self
+                .flush_buffer
+                .iter()
+                .map(|(key, status)| {
+                    key.estimated_size() + status.as_option().map_or(0, Row::estimated_size)
+                })
+                .sum::<usize>()
+    }
+
     pub fn retain_top_n(&mut self) {
         if let Some(count) = self.top_n_count {
             while self.top_n.len() > count {
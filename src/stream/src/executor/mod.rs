@@ -23,14 +23,18 @@ use async_trait::async_trait;
 pub use batch_query::*;
 pub use chain::*;
 pub use debug::*;
+pub use dedup::*;
 pub use dispatch::*;
+pub use dynamic_filter::*;
 use enum_as_inner::EnumAsInner;
 pub use filter::*;
 use futures::Stream;
 pub use global_simple_agg::*;
 pub use hash_agg::*;
 pub use hash_join::*;
+pub use hop_window::*;
 pub use local_simple_agg::*;
+pub use lookup::*;
 pub use merge::*;
 pub use monitor::*;
 pub use mview::*;
@@ -39,14 +43,20 @@ use risingwave_common::array::column::Column;
 use risingwave_common::array::{ArrayImpl, ArrayRef, DataChunk, StreamChunk};
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::types::DataType;
+use risingwave_common::error::{ErrorCode, Result, RwError, ToRwResult};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_common::util::value_encoding::{deserialize_cell_not_null, serialize_cell_not_null};
+use risingwave_connector::SplitImpl;
 use risingwave_pb::common::ActorInfo;
 use risingwave_pb::data::barrier::Mutation as ProstMutation;
 use risingwave_pb::data::stream_message::StreamMessage;
 use risingwave_pb::data::{
-    Actors as MutationActors, AddMutation, Barrier as ProstBarrier, Epoch as ProstEpoch,
-    NothingMutation, StopMutation, StreamMessage as ProstStreamMessage, UpdateMutation,
+    Actors as MutationActors, AddMutation, Barrier as ProstBarrier, ConnectorSplit,
+    ConnectorSplits, DispatcherUpdate as ProstDispatcherUpdate, Epoch as ProstEpoch,
+    MergeUpdate as ProstMergeUpdate, NothingMutation, PauseMutation,
+    RescheduleMutation as ProstRescheduleMutation, ResumeMutation, SourceChangeSplitMutation,
+    StopMutation, StreamMessage as ProstStreamMessage, ThrottleMutation, UpdateMutation,
+    Watermark as ProstWatermark,
 };
 use risingwave_pb::stream_plan;
 use risingwave_pb::stream_plan::stream_node::Node;
@@ -57,20 +67,24 @@ pub use top_n::*;
 pub use top_n_appendonly::*;
 use tracing::trace_span;
 
+use crate::executor_v2::BoxedExecutor;
 use crate::task::{ActorId, ExecutorParams, LocalStreamManagerCore, ENABLE_BARRIER_AGGREGATION};
 
 mod actor;
 mod aggregation;
-mod barrier_align;
 mod batch_query;
 mod chain;
 mod debug;
+mod dedup;
 mod dispatch;
+mod dynamic_filter;
 mod filter;
 mod global_simple_agg;
 mod hash_agg;
 mod hash_join;
+mod hop_window;
 mod local_simple_agg;
+mod lookup;
 pub(crate) mod managed_state;
 mod merge;
 pub mod monitor;
@@ -97,7 +111,57 @@ pub type BoxedExecutorStream = Pin<Box<dyn Stream<Item = Result<Message>> + Send
 pub enum Mutation {
     Stop(HashSet<ActorId>),
     UpdateOutputs(HashMap<ActorId, Vec<ActorInfo>>),
-    AddOutput(HashMap<ActorId, Vec<ActorInfo>>),
+    /// Adds outputs to the upstream actors. The source actors in `paused_actors` are added by this
+    /// barrier and start paused.
+    AddOutput {
+        adds: HashMap<ActorId, Vec<ActorInfo>>,
+        paused_actors: HashSet<ActorId>,
+    },
+    Reschedule(Reschedule),
+    /// Pauses the sources of the actors until a [`Mutation::Resume`] of them.
+    Pause(HashSet<ActorId>),
+    Resume(HashSet<ActorId>),
+    /// Changes the splits read by the source actors to the given ones.
+    SourceChangeSplit(HashMap<ActorId, Vec<SplitImpl>>),
+    /// Changes the rate limits (rows per second) of the source and chain actors, where `None`
+    /// removes the limit.
+    Throttle(HashMap<ActorId, Option<u32>>),
+}
+
+/// Updates of the dispatcher of an upstream actor when its downstream fragment is rescheduled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatcherUpdate {
+    /// Downstream actors to add to the dispatcher before the barrier.
+    pub added_downstream_actors: Vec<ActorInfo>,
+    /// Downstream actors to remove from the dispatcher after the barrier.
+    pub removed_downstream_actor_ids: HashSet<ActorId>,
+    /// The new mapping from virtual key to downstream actor id, if the dispatcher is hash.
+    pub hash_mapping: Option<Vec<ActorId>>,
+}
+
+/// Updates of the merger of a downstream actor when its upstream fragment is rescheduled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeUpdate {
+    /// Upstream actors to add to the merger after the barrier.
+    pub added_upstream_actors: Vec<ActorInfo>,
+    /// Upstream actors to remove from the merger after the barrier.
+    pub removed_upstream_actor_ids: HashSet<ActorId>,
+}
+
+/// Adds or removes actors of a fragment, and moves the virtual keys between them. See
+/// [`Mutation::Reschedule`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Reschedule {
+    /// Dispatcher updates of the upstream actors.
+    pub dispatchers: HashMap<ActorId, DispatcherUpdate>,
+    /// Merger updates of the downstream actors.
+    pub merges: HashMap<ActorId, MergeUpdate>,
+    /// Actors of the rescheduled fragment to stop after this barrier.
+    pub stopped_actors: HashSet<ActorId>,
+    /// Actors of the rescheduled fragment whose virtual keys are changed.
+    pub vnode_changed_actors: HashSet<ActorId>,
+    /// Actors added to the rescheduled fragment that read paused sources, which start paused.
+    pub paused_actors: HashSet<ActorId>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -176,13 +240,63 @@ impl Barrier {
     }
 
     pub fn is_to_stop_actor(&self, actor_id: ActorId) -> bool {
-        matches!(self.mutation.as_deref(), Some(Mutation::Stop(actors)) if actors.contains(&actor_id))
+        match self.mutation.as_deref() {
+            Some(Mutation::Stop(actors)) => actors.contains(&actor_id),
+            Some(Mutation::Reschedule(reschedule)) => reschedule.stopped_actors.contains(&actor_id),
+            _ => false,
+        }
+    }
+
+    /// Whether the virtual keys owned by the actor are changed by this barrier. If so, the states
+    /// cached by the actor may be stale, as the newly owned keys were written by other actors.
+    pub fn is_vnode_changed(&self, actor_id: ActorId) -> bool {
+        matches!(
+            self.mutation.as_deref(),
+            Some(Mutation::Reschedule(reschedule)) if reschedule.vnode_changed_actors.contains(&actor_id)
+        )
+    }
+
+    /// Returns `Some(true)` if the sources of the actor should be paused by this barrier,
+    /// `Some(false)` if they should be resumed, and `None` if the barrier does not change it.
+    pub fn is_to_pause(&self, actor_id: ActorId) -> Option<bool> {
+        match self.mutation.as_deref() {
+            Some(Mutation::Pause(actors)) if actors.contains(&actor_id) => Some(true),
+            Some(Mutation::Resume(actors)) if actors.contains(&actor_id) => Some(false),
+            Some(Mutation::AddOutput { paused_actors, .. })
+                if paused_actors.contains(&actor_id) =>
+            {
+                Some(true)
+            }
+            Some(Mutation::Reschedule(reschedule))
+                if reschedule.paused_actors.contains(&actor_id) =>
+            {
+                Some(true)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the new splits of the source actor if they are changed by this barrier.
+    pub fn as_split_changes(&self, actor_id: ActorId) -> Option<&Vec<SplitImpl>> {
+        match self.mutation.as_deref() {
+            Some(Mutation::SourceChangeSplit(actor_splits)) => actor_splits.get(&actor_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the new rate limit of the actor if it's changed by this barrier, where `None` means
+    /// unlimited.
+    pub fn as_throttle(&self, actor_id: ActorId) -> Option<Option<u32>> {
+        match self.mutation.as_deref() {
+            Some(Mutation::Throttle(actor_throttle)) => actor_throttle.get(&actor_id).copied(),
+            _ => None,
+        }
     }
 
     pub fn is_to_add_output(&self, actor_id: ActorId) -> bool {
         matches!(
             self.mutation.as_deref(),
-            Some(Mutation::AddOutput(map)) if map
+            Some(Mutation::AddOutput { adds, .. }) if adds
                 .values()
                 .flatten()
                 .any(|info| info.actor_id == actor_id)
@@ -236,7 +350,10 @@ impl Barrier {
                             .collect(),
                     }))
                 }
-                Some(Mutation::AddOutput(adds)) => Some(ProstMutation::Add(AddMutation {
+                Some(Mutation::AddOutput {
+                    adds,
+                    paused_actors,
+                }) => Some(ProstMutation::Add(AddMutation {
                     actors: adds
                         .iter()
                         .map(|(&id, actors)| {
@@ -248,7 +365,49 @@ impl Barrier {
                             )
                         })
                         .collect(),
+                    paused_actors: paused_actors.iter().cloned().collect(),
+                })),
+                Some(Mutation::Reschedule(reschedule)) => {
+                    Some(ProstMutation::Reschedule(reschedule.to_protobuf()))
+                }
+                Some(Mutation::Pause(actors)) => Some(ProstMutation::Pause(PauseMutation {
+                    actors: actors.iter().cloned().collect::<Vec<_>>(),
                 })),
+                Some(Mutation::Resume(actors)) => Some(ProstMutation::Resume(ResumeMutation {
+                    actors: actors.iter().cloned().collect::<Vec<_>>(),
+                })),
+                Some(Mutation::SourceChangeSplit(actor_splits)) => {
+                    Some(ProstMutation::Splits(SourceChangeSplitMutation {
+                        actor_splits: actor_splits
+                            .iter()
+                            .map(|(&actor_id, splits)| {
+                                (
+                                    actor_id,
+                                    ConnectorSplits {
+                                        splits: splits
+                                            .iter()
+                                            .map(|split| ConnectorSplit {
+                                                split_type: split.get_type(),
+                                                encoded_split: split
+                                                    .to_string()
+                                                    .unwrap()
+                                                    .into_bytes(),
+                                            })
+                                            .collect(),
+                                    },
+                                )
+                            })
+                            .collect(),
+                    }))
+                }
+                Some(Mutation::Throttle(actor_throttle)) => {
+                    Some(ProstMutation::Throttle(ThrottleMutation {
+                        actor_throttle: actor_throttle
+                            .iter()
+                            .map(|(&actor_id, rate_limit)| (actor_id, rate_limit.unwrap_or(0)))
+                            .collect(),
+                    }))
+                }
             },
             span: vec![],
         }
@@ -271,11 +430,57 @@ impl Barrier {
                 .into(),
             ),
             ProstMutation::Add(adds) => Some(
-                Mutation::AddOutput(
-                    adds.actors
+                Mutation::AddOutput {
+                    adds: adds
+                        .actors
                         .iter()
                         .map(|(&id, actors)| (id, actors.get_info().clone()))
                         .collect::<HashMap<ActorId, Vec<ActorInfo>>>(),
+                    paused_actors: HashSet::from_iter(adds.paused_actors.iter().cloned()),
+                }
+                .into(),
+            ),
+            ProstMutation::Reschedule(reschedule) => {
+                Some(Mutation::Reschedule(Reschedule::from_protobuf(reschedule)).into())
+            }
+            ProstMutation::Pause(pause) => {
+                Some(Mutation::Pause(HashSet::from_iter(pause.get_actors().clone())).into())
+            }
+            ProstMutation::Resume(resume) => {
+                Some(Mutation::Resume(HashSet::from_iter(resume.get_actors().clone())).into())
+            }
+            ProstMutation::Splits(change_split) => Some(
+                Mutation::SourceChangeSplit(
+                    change_split
+                        .actor_splits
+                        .iter()
+                        .map(|(&actor_id, splits)| {
+                            let splits = splits
+                                .splits
+                                .iter()
+                                .map(|split| {
+                                    SplitImpl::restore_from_bytes(
+                                        split.split_type.clone(),
+                                        &split.encoded_split,
+                                    )
+                                })
+                                .collect::<anyhow::Result<Vec<_>>>()
+                                .to_rw_result()?;
+                            Ok((actor_id, splits))
+                        })
+                        .collect::<Result<HashMap<_, _>>>()?,
+                )
+                .into(),
+            ),
+            ProstMutation::Throttle(throttle) => Some(
+                Mutation::Throttle(
+                    throttle
+                        .actor_throttle
+                        .iter()
+                        .map(|(&actor_id, &rate_limit)| {
+                            (actor_id, (rate_limit > 0).then(|| rate_limit))
+                        })
+                        .collect(),
                 )
                 .into(),
             ),
@@ -293,10 +498,140 @@ impl Barrier {
     }
 }
 
+impl Reschedule {
+    pub fn to_protobuf(&self) -> ProstRescheduleMutation {
+        ProstRescheduleMutation {
+            dispatchers: self
+                .dispatchers
+                .iter()
+                .map(|(&id, update)| {
+                    (
+                        id,
+                        ProstDispatcherUpdate {
+                            added_downstream_actors: update.added_downstream_actors.clone(),
+                            removed_downstream_actor_id: update
+                                .removed_downstream_actor_ids
+                                .iter()
+                                .cloned()
+                                .collect(),
+                            hash_mapping: update.hash_mapping.clone().unwrap_or_default(),
+                        },
+                    )
+                })
+                .collect(),
+            merges: self
+                .merges
+                .iter()
+                .map(|(&id, update)| {
+                    (
+                        id,
+                        ProstMergeUpdate {
+                            added_upstream_actors: update.added_upstream_actors.clone(),
+                            removed_upstream_actor_id: update
+                                .removed_upstream_actor_ids
+                                .iter()
+                                .cloned()
+                                .collect(),
+                        },
+                    )
+                })
+                .collect(),
+            stopped_actors: self.stopped_actors.iter().cloned().collect(),
+            vnode_changed_actors: self.vnode_changed_actors.iter().cloned().collect(),
+            paused_actors: self.paused_actors.iter().cloned().collect(),
+        }
+    }
+
+    pub fn from_protobuf(prost: &ProstRescheduleMutation) -> Self {
+        Self {
+            dispatchers: prost
+                .dispatchers
+                .iter()
+                .map(|(&id, update)| {
+                    (
+                        id,
+                        DispatcherUpdate {
+                            added_downstream_actors: update.added_downstream_actors.clone(),
+                            removed_downstream_actor_ids: HashSet::from_iter(
+                                update.removed_downstream_actor_id.iter().cloned(),
+                            ),
+                            hash_mapping: (!update.hash_mapping.is_empty())
+                                .then(|| update.hash_mapping.clone()),
+                        },
+                    )
+                })
+                .collect(),
+            merges: prost
+                .merges
+                .iter()
+                .map(|(&id, update)| {
+                    (
+                        id,
+                        MergeUpdate {
+                            added_upstream_actors: update.added_upstream_actors.clone(),
+                            removed_upstream_actor_ids: HashSet::from_iter(
+                                update.removed_upstream_actor_id.iter().cloned(),
+                            ),
+                        },
+                    )
+                })
+                .collect(),
+            stopped_actors: HashSet::from_iter(prost.stopped_actors.iter().cloned()),
+            vnode_changed_actors: HashSet::from_iter(prost.vnode_changed_actors.iter().cloned()),
+            paused_actors: HashSet::from_iter(prost.paused_actors.iter().cloned()),
+        }
+    }
+}
+
+/// A watermark tells the downstream that no row whose value of column `col_idx` is smaller than
+/// `val` will arrive any more, so the states of closed windows can be cleaned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watermark {
+    pub col_idx: usize,
+    pub data_type: DataType,
+    pub val: ScalarImpl,
+}
+
+impl Watermark {
+    pub fn new(col_idx: usize, data_type: DataType, val: ScalarImpl) -> Self {
+        Self {
+            col_idx,
+            data_type,
+            val,
+        }
+    }
+
+    /// Returns the same watermark on another column, e.g., after a projection.
+    pub fn with_idx(self, col_idx: usize) -> Self {
+        Self { col_idx, ..self }
+    }
+
+    pub fn to_protobuf(&self) -> Result<ProstWatermark> {
+        Ok(ProstWatermark {
+            col_idx: self.col_idx as u32,
+            data_type: Some(self.data_type.to_protobuf()),
+            val: serialize_cell_not_null(&Some(self.val.clone()))?,
+        })
+    }
+
+    pub fn from_protobuf(prost: &ProstWatermark) -> Result<Self> {
+        let data_type = DataType::from(prost.get_data_type()?);
+        let mut deserializer = value_encoding::Deserializer::new(prost.val.as_slice());
+        let val = deserialize_cell_not_null(&mut deserializer, data_type.clone())?
+            .ok_or_else(|| ErrorCode::InternalError("watermark cannot be null".into()))?;
+        Ok(Self {
+            col_idx: prost.col_idx as usize,
+            data_type,
+            val,
+        })
+    }
+}
+
 #[derive(Debug, EnumAsInner)]
 pub enum Message {
     Chunk(StreamChunk),
     Barrier(Barrier),
+    Watermark(Watermark),
 }
 
 impl<'a> TryFrom<&'a Message> for &'a Barrier {
@@ -304,7 +639,7 @@ impl<'a> TryFrom<&'a Message> for &'a Barrier {
 
     fn try_from(m: &'a Message) -> std::result::Result<Self, Self::Error> {
         match m {
-            Message::Chunk(_) => Err(()),
+            Message::Chunk(_) | Message::Watermark(_) => Err(()),
             Message::Barrier(b) => Ok(b),
         }
     }
@@ -333,6 +668,7 @@ impl Message {
                 StreamMessage::StreamChunk(prost_stream_chunk)
             }
             Self::Barrier(barrier) => StreamMessage::Barrier(barrier.clone().to_protobuf()),
+            Self::Watermark(watermark) => StreamMessage::Watermark(watermark.to_protobuf()?),
         };
         let prost_stream_msg = ProstStreamMessage {
             stream_message: Some(prost),
@@ -348,6 +684,9 @@ impl Message {
             StreamMessage::Barrier(ref barrier) => {
                 Message::Barrier(Barrier::from_protobuf(barrier)?)
             }
+            StreamMessage::Watermark(ref watermark) => {
+                Message::Watermark(Watermark::from_protobuf(watermark)?)
+            }
         };
         Ok(res)
     }
@@ -464,7 +803,7 @@ pub trait ExecutorBuilder {
         node: &stream_plan::StreamNode,
         store: impl StateStore,
         stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>>;
+    ) -> Result<BoxedExecutor>;
 }
 
 #[macro_export]
@@ -491,7 +830,7 @@ pub fn create_executor(
     stream: &mut LocalStreamManagerCore,
     node: &stream_plan::StreamNode,
     store: impl StateStore,
-) -> Result<Box<dyn Executor>> {
+) -> Result<BoxedExecutor> {
     let real_executor = build_executor! { executor_params,node,store,stream,
         Node::SourceNode => SourceExecutorBuilder,
         Node::ProjectNode => ProjectExecutorBuilder,
@@ -505,7 +844,11 @@ pub fn create_executor(
         Node::BatchPlanNode => BatchQueryExecutorBuilder,
         Node::MergeNode => MergeExecutorBuilder,
         Node::MaterializeNode => MaterializeExecutorBuilder,
-        Node::FilterNode => FilterExecutorBuilder
+        Node::FilterNode => FilterExecutorBuilder,
+        Node::HopWindowNode => HopWindowExecutorBuilder,
+        Node::LookupNode => LookupExecutorBuilder,
+        Node::AppendOnlyDedupNode => AppendOnlyDedupExecutorBuilder,
+        Node::DynamicFilterNode => DynamicFilterExecutorBuilder
     }?;
     Ok(real_executor)
 }
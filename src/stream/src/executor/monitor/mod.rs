@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod row_count;
 pub mod streaming_stats;
+pub use row_count::*;
 pub use streaming_stats::*;
@@ -0,0 +1,105 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::{StreamExt, TryStreamExt};
+use prometheus::IntCounter;
+
+use crate::executor::{execute_input, DebugExecutor, Message};
+use crate::executor_v2::{BoxedExecutor, BoxedMessageStream, Executor};
+
+/// [`RowCountExecutor`] counts the rows passing from an executor to its downstream executor, which
+/// are both the output of the former and the input of the latter.
+pub struct RowCountExecutor {
+    /// The input of the current executor.
+    input: BoxedExecutor,
+
+    /// Rows output from the input executor.
+    output_row_count: IntCounter,
+
+    /// Rows consumed by the downstream executor. `None` if the input is the head of the actor.
+    downstream_input_row_count: Option<IntCounter>,
+}
+
+impl RowCountExecutor {
+    pub fn new(
+        input: BoxedExecutor,
+        output_row_count: IntCounter,
+        downstream_input_row_count: Option<IntCounter>,
+    ) -> Self {
+        Self {
+            input,
+            output_row_count,
+            downstream_input_row_count,
+        }
+    }
+}
+
+impl DebugExecutor for RowCountExecutor {
+    fn input(&self) -> &dyn Executor {
+        self.input.as_ref()
+    }
+
+    fn execute_inner(self: Box<Self>, epoch: Option<u64>) -> BoxedMessageStream {
+        let output_row_count = self.output_row_count;
+        let downstream_input_row_count = self.downstream_input_row_count;
+
+        execute_input(self.input, epoch)
+            .inspect_ok(move |message| {
+                if let Message::Chunk(chunk) = message {
+                    let cardinality = chunk.cardinality() as u64;
+                    output_row_count.inc_by(cardinality);
+                    if let Some(counter) = &downstream_input_row_count {
+                        counter.inc_by(cardinality);
+                    }
+                }
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use risingwave_common::array::{Op, StreamChunk};
+
+    use super::*;
+    use crate::executor_v2::test_utils::MockSource;
+
+    #[tokio::test]
+    async fn test_row_count() {
+        let mut source = MockSource::new(Default::default(), vec![]);
+        source.push_chunks(
+            [
+                StreamChunk::new(vec![Op::Insert; 3], vec![], None),
+                StreamChunk::new(vec![Op::Delete; 2], vec![], None),
+            ]
+            .into_iter(),
+        );
+        source.push_barrier(1, false);
+
+        let output_row_count = IntCounter::new("output", "output").unwrap();
+        let input_row_count = IntCounter::new("input", "input").unwrap();
+        let mut counted = Box::new(RowCountExecutor::new(
+            Box::new(source),
+            output_row_count.clone(),
+            Some(input_row_count.clone()),
+        ))
+        .execute();
+        assert_matches!(counted.next().await.unwrap().unwrap(), Message::Chunk(_));
+        assert_matches!(counted.next().await.unwrap().unwrap(), Message::Chunk(_));
+        assert_matches!(counted.next().await.unwrap().unwrap(), Message::Barrier(_));
+        assert_eq!(output_row_count.get(), 5);
+        assert_eq!(input_row_count.get(), 5);
+    }
+}
@@ -13,13 +13,53 @@
 // limitations under the License.
 
 use prometheus::core::{AtomicU64, GenericCounterVec};
-use prometheus::{register_int_counter_vec_with_registry, Registry};
+use prometheus::{
+    exponential_buckets, histogram_opts, register_histogram_vec_with_registry,
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Registry,
+};
 
 pub struct StreamingMetrics {
     pub registry: Registry,
     pub actor_row_count: GenericCounterVec<AtomicU64>,
 
     pub source_output_row_count: GenericCounterVec<AtomicU64>,
+
+    pub memory_usage: IntGauge,
+    pub actor_memory_usage: IntGaugeVec,
+
+    /// Number of messages buffered in the channel between two actors.
+    pub actor_channel_occupancy: IntGaugeVec,
+    /// Time an actor has been blocked on sending to a full channel.
+    pub actor_output_blocking_duration_ns: IntCounterVec,
+    /// Rows consumed from the inputs of each executor.
+    pub executor_input_row_count: IntCounterVec,
+    /// Rows produced by each executor.
+    pub executor_output_row_count: IntCounterVec,
+    /// Time between receiving a barrier from one side of a two-input executor and receiving it
+    /// from the other side.
+    pub barrier_align_duration: HistogramVec,
+}
+
+/// Metrics of the channel from an upstream actor to a downstream actor.
+#[derive(Clone)]
+pub struct ChannelMetrics {
+    /// Number of messages buffered in the channel. Increased by the sender and decreased by the
+    /// receiver.
+    pub occupancy: IntGauge,
+    /// Time the upstream actor has been blocked on sending to the channel.
+    pub blocking_duration_ns: IntCounter,
+}
+
+impl ChannelMetrics {
+    /// Create a new `ChannelMetrics` instance which is not registered, used in tests.
+    pub fn unused() -> Self {
+        Self {
+            occupancy: IntGauge::new("unused", "unused").unwrap(),
+            blocking_duration_ns: IntCounter::new("unused", "unused").unwrap(),
+        }
+    }
 }
 
 impl StreamingMetrics {
@@ -40,15 +80,203 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let memory_usage = register_int_gauge_with_registry!(
+            "stream_memory_usage",
+            "Estimated memory used by the caches of all streaming executors",
+            registry
+        )
+        .unwrap();
+
+        let actor_memory_usage = register_int_gauge_vec_with_registry!(
+            "stream_actor_memory_usage",
+            "Estimated memory used by the caches of the executors of each actor",
+            &["actor_id"],
+            registry
+        )
+        .unwrap();
+
+        let actor_channel_occupancy = register_int_gauge_vec_with_registry!(
+            "stream_actor_channel_occupancy",
+            "Number of messages buffered in the channel between two actors",
+            &["up_actor_id", "down_actor_id"],
+            registry
+        )
+        .unwrap();
+
+        let actor_output_blocking_duration_ns = register_int_counter_vec_with_registry!(
+            "stream_actor_output_blocking_duration_ns",
+            "Total time an actor has been blocked on sending to a full channel",
+            &["actor_id", "down_actor_id"],
+            registry
+        )
+        .unwrap();
+
+        let executor_input_row_count = register_int_counter_vec_with_registry!(
+            "stream_executor_input_row_count",
+            "Total number of rows that have been consumed by each executor",
+            &["actor_id", "executor_id"],
+            registry
+        )
+        .unwrap();
+
+        let executor_output_row_count = register_int_counter_vec_with_registry!(
+            "stream_executor_output_row_count",
+            "Total number of rows that have been output from each executor",
+            &["actor_id", "executor_id"],
+            registry
+        )
+        .unwrap();
+
+        let opts = histogram_opts!(
+            "stream_barrier_align_duration_seconds",
+            "Time spent waiting for a barrier from the other input of each executor",
+            exponential_buckets(0.0001, 2.0, 16).unwrap()
+        );
+        let barrier_align_duration =
+            register_histogram_vec_with_registry!(opts, &["actor_id", "executor_id"], registry)
+                .unwrap();
+
         Self {
             registry,
             actor_row_count,
             source_output_row_count,
+            memory_usage,
+            actor_memory_usage,
+            actor_channel_occupancy,
+            actor_output_blocking_duration_ns,
+            executor_input_row_count,
+            executor_output_row_count,
+            barrier_align_duration,
         }
     }
 
+    /// Get the histogram of the barrier alignment time of the executor.
+    pub fn barrier_align_duration(&self, actor_id: u32, executor_id: u64) -> Histogram {
+        self.barrier_align_duration
+            .with_label_values(&[&actor_id.to_string(), &format!("{:X}", executor_id)])
+    }
+
+    /// Get the metrics of the channel from `up_actor_id` to `down_actor_id`.
+    pub fn channel_metrics(&self, up_actor_id: u32, down_actor_id: u32) -> ChannelMetrics {
+        let up_actor_id = up_actor_id.to_string();
+        let down_actor_id = down_actor_id.to_string();
+        ChannelMetrics {
+            occupancy: self
+                .actor_channel_occupancy
+                .with_label_values(&[&up_actor_id, &down_actor_id]),
+            blocking_duration_ns: self
+                .actor_output_blocking_duration_ns
+                .with_label_values(&[&up_actor_id, &down_actor_id]),
+        }
+    }
+
+    /// Remove the metrics of a dropped actor: the row counts and the barrier alignment time of its
+    /// executors, and the channels from its upstream actors and to its downstream actors.
+    pub fn remove_actor_metrics(
+        &self,
+        actor_id: u32,
+        executor_ids: &[u64],
+        up_actor_ids: &[u32],
+        down_actor_ids: &[u32],
+    ) {
+        let actor_id_str = actor_id.to_string();
+        self.actor_row_count
+            .remove_label_values(&[&actor_id_str])
+            .ok();
+        for executor_id in executor_ids {
+            let executor_id = format!("{:X}", executor_id);
+            let labels = [actor_id_str.as_str(), executor_id.as_str()];
+            self.executor_input_row_count
+                .remove_label_values(&labels)
+                .ok();
+            self.executor_output_row_count
+                .remove_label_values(&labels)
+                .ok();
+            self.barrier_align_duration
+                .remove_label_values(&labels)
+                .ok();
+        }
+        for up_actor_id in up_actor_ids {
+            self.remove_channel_metrics(*up_actor_id, actor_id);
+        }
+        for down_actor_id in down_actor_ids {
+            self.remove_channel_metrics(actor_id, *down_actor_id);
+        }
+    }
+
+    fn remove_channel_metrics(&self, up_actor_id: u32, down_actor_id: u32) {
+        let up_actor_id = up_actor_id.to_string();
+        let down_actor_id = down_actor_id.to_string();
+        let labels = [up_actor_id.as_str(), down_actor_id.as_str()];
+        self.actor_channel_occupancy
+            .remove_label_values(&labels)
+            .ok();
+        self.actor_output_blocking_duration_ns
+            .remove_label_values(&labels)
+            .ok();
+    }
+
     /// Create a new `StreamingMetrics` instance used in tests or other places.
     pub fn unused() -> Self {
         Self::new(prometheus::Registry::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the number of label value sets reported for the metric family `name`.
+    fn label_values_count(metrics: &StreamingMetrics, name: &str) -> usize {
+        metrics
+            .registry
+            .gather()
+            .iter()
+            .find(|family| family.get_name() == name)
+            .map_or(0, |family| family.get_metric().len())
+    }
+
+    #[test]
+    fn test_remove_actor_metrics() {
+        let metrics = StreamingMetrics::unused();
+        // The actor, one of its executors and its downstream actor.
+        let actors: [(u32, u64, u32); 2] = [(1, 0x100000001, 2), (2, 0x200000001, 3)];
+        for (actor_id, executor_id, down_actor_id) in actors {
+            metrics
+                .actor_row_count
+                .with_label_values(&[&actor_id.to_string()])
+                .inc();
+            let executor_id = format!("{:X}", executor_id);
+            metrics
+                .executor_output_row_count
+                .with_label_values(&[&actor_id.to_string(), &executor_id])
+                .inc();
+            metrics
+                .barrier_align_duration
+                .with_label_values(&[&actor_id.to_string(), &executor_id])
+                .observe(1.0);
+            metrics
+                .channel_metrics(actor_id, down_actor_id)
+                .occupancy
+                .set(1);
+        }
+
+        metrics.remove_actor_metrics(1, &[0x100000001], &[], &[2]);
+        for name in [
+            "stream_actor_row_count",
+            "stream_executor_output_row_count",
+            "stream_barrier_align_duration_seconds",
+            "stream_actor_channel_occupancy",
+            "stream_actor_output_blocking_duration_ns",
+        ] {
+            assert_eq!(label_values_count(&metrics, name), 1, "{}", name);
+        }
+
+        // The channel from actor 2 is also removed with its downstream actor.
+        metrics.remove_actor_metrics(3, &[], &[2], &[]);
+        assert_eq!(
+            label_values_count(&metrics, "stream_actor_channel_occupancy"),
+            0
+        );
+    }
+}
@@ -19,8 +19,8 @@ use risingwave_pb::stream_plan;
 use risingwave_pb::stream_plan::stream_node::Node;
 use risingwave_storage::{Keyspace, StateStore};
 
-use crate::executor::{Executor, ExecutorBuilder, Result};
-use crate::executor_v2::{Executor as ExecutorV2, MaterializeExecutor as MaterializeExecutorV2};
+use crate::executor::{ExecutorBuilder, Result};
+use crate::executor_v2::{BoxedExecutor, Executor, MaterializeExecutor as MaterializeExecutorV2};
 use crate::task::{ExecutorParams, LocalStreamManagerCore};
 
 pub struct MaterializeExecutorBuilder;
@@ -30,8 +30,8 @@ impl ExecutorBuilder for MaterializeExecutorBuilder {
         mut params: ExecutorParams,
         node: &stream_plan::StreamNode,
         store: impl StateStore,
-        _stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>> {
+        stream: &mut LocalStreamManagerCore,
+    ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node().unwrap(), Node::MaterializeNode)?;
 
         let table_id = TableId::from(&node.table_ref_id);
@@ -54,16 +54,19 @@ impl ExecutorBuilder for MaterializeExecutorBuilder {
             .map(|key| *key as usize)
             .collect::<Vec<_>>();
 
-        let v2 = Box::new(MaterializeExecutorV2::new_from_v1(
+        Ok(MaterializeExecutorV2::new(
             params.input.remove(0),
             keyspace,
             keys,
             column_ids,
             params.executor_id,
-            params.op_info,
             key_indices,
-        ));
-
-        Ok(Box::new(v2.v1()))
+            Some(
+                stream
+                    .context
+                    .register_table_row_count_reporter(table_id.table_id()),
+            ),
+        )
+        .boxed())
     }
 }
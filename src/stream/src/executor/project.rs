@@ -15,13 +15,14 @@
 use risingwave_common::error::Result;
 use risingwave_common::try_match_expand;
 use risingwave_expr::expr::build_from_prost;
+use risingwave_pb::expr::expr_node::{RexNode, Type};
+use risingwave_pb::expr::ExprNode;
 use risingwave_pb::stream_plan;
 use risingwave_pb::stream_plan::stream_node::Node;
 use risingwave_storage::StateStore;
 
-use super::Executor;
 use crate::executor::ExecutorBuilder;
-use crate::executor_v2::{Executor as ExecutorV2, ProjectExecutor as ProjectExecutorV2};
+use crate::executor_v2::{BoxedExecutor, Executor, ProjectExecutor as ProjectExecutorV2};
 use crate::task::{ExecutorParams, LocalStreamManagerCore};
 
 pub struct ProjectExecutorBuilder;
@@ -32,22 +33,46 @@ impl ExecutorBuilder for ProjectExecutorBuilder {
         node: &stream_plan::StreamNode,
         _store: impl StateStore,
         _stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node().unwrap(), Node::ProjectNode)?;
         let project_exprs = node
             .get_select_list()
             .iter()
             .map(build_from_prost)
             .collect::<Result<Vec<_>>>()?;
-        Ok(Box::new(
-            Box::new(ProjectExecutorV2::new_from_v1(
-                params.input.remove(0),
-                params.pk_indices,
-                project_exprs,
-                params.executor_id,
-                params.op_info,
-            ))
-            .v1(),
-        ))
+        let watermark_derivations = watermark_derivations(node.get_select_list());
+        Ok(ProjectExecutorV2::new(
+            params.input.remove(0),
+            params.pk_indices,
+            project_exprs,
+            params.executor_id,
+        )
+        .with_watermark_derivations(watermark_derivations)
+        .boxed())
     }
 }
+
+/// Returns the `(input column, output column)` pairs along which a watermark can be derived. Only
+/// input references and `TUMBLE_START` on an input reference are known to be monotonic.
+fn watermark_derivations(select_list: &[ExprNode]) -> Vec<(usize, usize)> {
+    let input_ref_idx = |expr: &ExprNode| match expr.get_rex_node() {
+        Ok(RexNode::InputRef(input_ref)) => Some(input_ref.column_idx as usize),
+        _ => None,
+    };
+
+    select_list
+        .iter()
+        .enumerate()
+        .filter_map(|(output_idx, expr)| {
+            let input_idx = match expr.get_expr_type().ok()? {
+                Type::InputRef => input_ref_idx(expr)?,
+                Type::TumbleStart => match expr.get_rex_node().ok()? {
+                    RexNode::FuncCall(func_call) => input_ref_idx(func_call.children.first()?)?,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            Some((input_idx, output_idx))
+        })
+        .collect()
+}
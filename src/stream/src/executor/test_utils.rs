@@ -16,7 +16,6 @@ use std::collections::VecDeque;
 
 use risingwave_storage::memory::MemoryStateStore;
 use risingwave_storage::Keyspace;
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 use crate::executor::*;
 
@@ -113,95 +112,6 @@ impl Executor for MockSource {
     }
 }
 
-/// This source takes message from users asynchronously
-pub struct MockAsyncSource {
-    schema: Schema,
-    pk_indices: PkIndices,
-    epoch: u64,
-    rx: UnboundedReceiver<Message>,
-}
-
-impl std::fmt::Debug for MockAsyncSource {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("MockAsyncSource")
-            .field("schema", &self.schema)
-            .field("pk_indices", &self.pk_indices)
-            .finish()
-    }
-}
-
-impl MockAsyncSource {
-    #[allow(dead_code)]
-    pub fn new(schema: Schema, rx: UnboundedReceiver<Message>) -> Self {
-        Self {
-            schema,
-            pk_indices: vec![],
-            rx,
-            epoch: 0,
-        }
-    }
-
-    pub fn with_pk_indices(
-        schema: Schema,
-        rx: UnboundedReceiver<Message>,
-        pk_indices: Vec<usize>,
-    ) -> Self {
-        Self {
-            schema,
-            pk_indices,
-            rx,
-            epoch: 0,
-        }
-    }
-
-    pub fn push_chunks(
-        tx: &mut UnboundedSender<Message>,
-        chunks: impl IntoIterator<Item = StreamChunk>,
-    ) {
-        for chunk in chunks {
-            tx.send(Message::Chunk(chunk)).expect("Receiver closed");
-        }
-    }
-
-    pub fn push_barrier(tx: &mut UnboundedSender<Message>, epoch: u64, stop: bool) {
-        let mut barrier = Barrier::new_test_barrier(epoch);
-        if stop {
-            barrier = barrier.with_mutation(Mutation::Stop(HashSet::default()))
-        }
-        tx.send(Message::Barrier(barrier)).expect("Receiver closed");
-    }
-}
-
-#[async_trait]
-impl Executor for MockAsyncSource {
-    async fn next(&mut self) -> Result<Message> {
-        self.epoch += 1;
-        match self.rx.recv().await {
-            Some(msg) => Ok(msg),
-            None => Ok(Message::Barrier(
-                Barrier::new_test_barrier(self.epoch)
-                    .with_mutation(Mutation::Stop(HashSet::default())),
-            )),
-        }
-    }
-
-    fn schema(&self) -> &Schema {
-        &self.schema
-    }
-
-    fn pk_indices(&self) -> PkIndicesRef {
-        &self.pk_indices
-    }
-
-    fn identity(&self) -> &'static str {
-        "MockAsyncSource"
-    }
-
-    fn logical_operator_info(&self) -> &str {
-        self.identity()
-    }
-}
-
 pub fn create_in_memory_keyspace() -> Keyspace<MemoryStateStore> {
     Keyspace::executor_root(MemoryStateStore::new(), 0x2333)
 }
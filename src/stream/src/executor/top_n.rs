@@ -20,8 +20,8 @@ use risingwave_pb::stream_plan;
 use risingwave_pb::stream_plan::stream_node::Node;
 use risingwave_storage::{Keyspace, StateStore};
 
-use crate::executor::{Executor, ExecutorBuilder};
-use crate::executor_v2::{Executor as ExecutorV2, TopNExecutor as TopNExecutorV2};
+use crate::executor::ExecutorBuilder;
+use crate::executor_v2::{BoxedExecutor, Executor, TopNExecutor as TopNExecutorV2};
 use crate::task::{ExecutorParams, LocalStreamManagerCore};
 
 pub struct TopNExecutorBuilder {}
@@ -32,7 +32,7 @@ impl ExecutorBuilder for TopNExecutorBuilder {
         node: &stream_plan::StreamNode,
         store: impl StateStore,
         _stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node().unwrap(), Node::TopNNode)?;
         let order_types: Vec<_> = node
             .get_order_types()
@@ -49,25 +49,27 @@ impl ExecutorBuilder for TopNExecutorBuilder {
         let cache_size = Some(1024);
         let total_count = (0, 0, 0);
         let keyspace = Keyspace::executor_root(store, params.executor_id);
+        let memory_reporter = params
+            .env
+            .memory_manager()
+            .register(params.actor_id, params.executor_id);
         let key_indices = node
             .get_distribution_keys()
             .iter()
             .map(|key| *key as usize)
             .collect::<Vec<_>>();
-        Ok(Box::new(
-            Box::new(TopNExecutorV2::new_from_v1(
-                params.input.remove(0),
-                order_types,
-                (node.offset as usize, limit),
-                params.pk_indices,
-                keyspace,
-                cache_size,
-                total_count,
-                params.executor_id,
-                params.op_info,
-                key_indices,
-            )?)
-            .v1(),
-        ))
+        Ok(TopNExecutorV2::new(
+            params.input.remove(0),
+            order_types,
+            (node.offset as usize, limit),
+            params.pk_indices,
+            keyspace,
+            cache_size,
+            total_count,
+            params.executor_id,
+            key_indices,
+        )?
+        .with_memory_reporter(memory_reporter)
+        .boxed())
     }
 }
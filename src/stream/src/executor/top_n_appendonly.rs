@@ -20,9 +20,9 @@ use risingwave_pb::stream_plan;
 use risingwave_pb::stream_plan::stream_node::Node;
 use risingwave_storage::{Keyspace, StateStore};
 
-use crate::executor::{Executor, ExecutorBuilder};
+use crate::executor::ExecutorBuilder;
 use crate::executor_v2::{
-    AppendOnlyTopNExecutor as AppendOnlyTopNExecutorV2, Executor as ExecutorV2,
+    AppendOnlyTopNExecutor as AppendOnlyTopNExecutorV2, BoxedExecutor, Executor,
 };
 use crate::task::{ExecutorParams, LocalStreamManagerCore};
 
@@ -34,7 +34,7 @@ impl ExecutorBuilder for AppendOnlyTopNExecutorBuilder {
         node: &stream_plan::StreamNode,
         store: impl StateStore,
         _stream: &mut LocalStreamManagerCore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node().unwrap(), Node::AppendOnlyTopNNode)?;
         let order_types: Vec<_> = node
             .get_order_types()
@@ -51,25 +51,27 @@ impl ExecutorBuilder for AppendOnlyTopNExecutorBuilder {
         let cache_size = Some(1024);
         let total_count = (0, 0);
         let keyspace = Keyspace::executor_root(store, params.executor_id);
+        let memory_reporter = params
+            .env
+            .memory_manager()
+            .register(params.actor_id, params.executor_id);
         let key_indices = node
             .get_distribution_keys()
             .iter()
             .map(|key| *key as usize)
             .collect::<Vec<_>>();
-        Ok(Box::new(
-            Box::new(AppendOnlyTopNExecutorV2::new_from_v1(
-                params.input.remove(0),
-                order_types,
-                (node.offset as usize, limit),
-                params.pk_indices,
-                keyspace,
-                cache_size,
-                total_count,
-                params.executor_id,
-                params.op_info,
-                key_indices,
-            )?)
-            .v1(),
-        ))
+        Ok(AppendOnlyTopNExecutorV2::new(
+            params.input.remove(0),
+            order_types,
+            (node.offset as usize, limit),
+            params.pk_indices,
+            keyspace,
+            cache_size,
+            total_count,
+            params.executor_id,
+            key_indices,
+        )?
+        .with_memory_reporter(memory_reporter)
+        .boxed())
     }
 }
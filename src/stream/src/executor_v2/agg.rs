@@ -25,7 +25,9 @@ use crate::executor::{AggCall, AggState, PkDataTypes, ROW_COUNT_COLUMN};
 use crate::executor_v2::error::{
     StreamExecutorError, StreamExecutorResult, TracedStreamExecutorError,
 };
-use crate::executor_v2::{BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndicesRef};
+use crate::executor_v2::{
+    BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndicesRef, Watermark,
+};
 
 /// Trait for [`crate::executor_v2::LocalSimpleAggExecutor`], providing
 /// an implementation of [`Executor::execute`] by [`AggExecutorWrapper::agg_executor_execute`].
@@ -38,6 +40,25 @@ pub trait AggExecutor: Send + 'static {
     /// no dirty states to flush, return `Ok(None)`.
     async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<Option<StreamChunk>>;
 
+    /// Buffer a watermark from the input. The states closed by it will be cleaned on the next
+    /// flush. Watermarks are ignored by default.
+    fn apply_watermark(&mut self, _watermark: Watermark) {}
+
+    /// Emit the final results of the windows closed by the buffered watermark after flushing, for
+    /// aggregations that `EMIT ON WINDOW CLOSE`. Returns `Ok(None)` by default.
+    async fn emit_closed_windows(
+        &mut self,
+        _epoch: u64,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        Ok(None)
+    }
+
+    /// Clean the states closed by the buffered watermark after flushing, and return the watermark
+    /// to emit to the downstream, if any.
+    async fn clean_by_watermark(&mut self, _epoch: u64) -> StreamExecutorResult<Option<Watermark>> {
+        Ok(None)
+    }
+
     /// See [`Executor::schema`].
     fn schema(&self) -> &Schema;
 
@@ -104,12 +125,21 @@ where
             let msg = msg?;
             match msg {
                 Message::Chunk(chunk) => self.inner.apply_chunk(chunk, epoch).await?,
+                Message::Watermark(watermark) => self.inner.apply_watermark(watermark),
                 Message::Barrier(barrier) => {
                     let next_epoch = barrier.epoch.curr;
                     if let Some(chunk) = self.inner.flush_data(epoch).await? {
                         assert_eq!(epoch, barrier.epoch.prev);
                         yield Message::Chunk(chunk);
                     }
+                    if let Some(chunk) = self.inner.emit_closed_windows(epoch).await? {
+                        yield Message::Chunk(chunk);
+                    }
+                    // The results of the closed windows have been emitted above, so it's safe to
+                    // emit the watermark now.
+                    if let Some(watermark) = self.inner.clean_by_watermark(epoch).await? {
+                        yield Message::Watermark(watermark);
+                    }
                     yield Message::Barrier(barrier);
                     epoch = next_epoch;
                 }
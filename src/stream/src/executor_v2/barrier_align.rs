@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Instant;
+
 use futures::StreamExt;
+use prometheus::Histogram;
 use tokio::select;
 
 use crate::executor_v2::error::StreamExecutorResult;
-use crate::executor_v2::{Barrier, BoxedMessageStream, Executor, Message, StreamChunk};
+use crate::executor_v2::{Barrier, BoxedMessageStream, Executor, Message, StreamChunk, Watermark};
 
 #[derive(Debug, PartialEq)]
 enum BarrierWaitState {
@@ -29,6 +32,8 @@ enum BarrierWaitState {
 pub enum AlignedMessage {
     Left(StreamExecutorResult<StreamChunk>),
     Right(StreamExecutorResult<StreamChunk>),
+    WatermarkLeft(Watermark),
+    WatermarkRight(Watermark),
     Barrier(Barrier),
 }
 
@@ -50,6 +55,11 @@ pub struct BarrierAligner {
     input_r: BoxedMessageStream,
     /// The barrier state
     state: BarrierWaitState,
+    /// Observes the time between receiving the barrier from one side and from the other side, if
+    /// set.
+    align_duration: Option<Histogram>,
+    /// When the barrier to align is received from the first side.
+    align_start: Option<Instant>,
 }
 
 impl BarrierAligner {
@@ -59,6 +69,29 @@ impl BarrierAligner {
             input_l: Box::pin(input_l.execute()),
             input_r: Box::pin(input_r.execute()),
             state: BarrierWaitState::Either,
+            align_duration: None,
+            align_start: None,
+        }
+    }
+
+    /// Reports the time waiting for the barrier from the other side to `align_duration`.
+    pub fn with_align_duration(mut self, align_duration: Option<Histogram>) -> Self {
+        self.align_duration = align_duration;
+        self
+    }
+
+    /// Called when the barrier is received from the first side.
+    fn start_align(&mut self) {
+        if self.align_duration.is_some() {
+            self.align_start = Some(Instant::now());
+        }
+    }
+
+    /// Called when the barrier is received from both sides.
+    fn finish_align(&mut self) {
+        if let (Some(align_duration), Some(start)) = (&self.align_duration, self.align_start.take())
+        {
+            align_duration.observe(start.elapsed().as_secs_f64());
         }
     }
 
@@ -69,14 +102,17 @@ impl BarrierAligner {
                 match message.unwrap() {
                     Ok(message) => match message {
                         Message::Chunk(chunk) => break AlignedMessage::Left(Ok(chunk)),
+                            Message::Watermark(watermark) => break AlignedMessage::WatermarkLeft(watermark),
                             Message::Barrier(barrier) => {
                                 match self.state {
                                     BarrierWaitState::Left => {
                                         self.state = BarrierWaitState::Either;
+                                        self.finish_align();
                                         break AlignedMessage::Barrier(barrier);
                                     }
                                     BarrierWaitState::Either => {
                                         self.state = BarrierWaitState::Right;
+                                        self.start_align();
                                     }
                                     _ => unreachable!("Should not reach this barrier state: {:?}", self.state),
                                 };
@@ -89,13 +125,16 @@ impl BarrierAligner {
                     match message.unwrap() {
                         Ok(message) => match message {
                             Message::Chunk(chunk) => break AlignedMessage::Right(Ok(chunk)),
+                            Message::Watermark(watermark) => break AlignedMessage::WatermarkRight(watermark),
                             Message::Barrier(barrier) => match self.state {
                                 BarrierWaitState::Right => {
                                     self.state = BarrierWaitState::Either;
+                                    self.finish_align();
                                     break AlignedMessage::Barrier(barrier);
                                 }
                                 BarrierWaitState::Either => {
                                     self.state = BarrierWaitState::Left;
+                                    self.start_align();
                                 }
                                 _ => unreachable!("Should not reach this barrier state: {:?}", self.state),
                             },
@@ -107,3 +146,29 @@ impl BarrierAligner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use prometheus::HistogramOpts;
+
+    use super::*;
+    use crate::executor_v2::test_utils::MockSource;
+
+    #[tokio::test]
+    async fn test_barrier_align_duration() {
+        let mut left = MockSource::new(Default::default(), vec![]);
+        left.push_barrier(1, false);
+        let mut right = MockSource::new(Default::default(), vec![]);
+        right.push_chunks([StreamChunk::default()].into_iter());
+        right.push_barrier(1, false);
+
+        let align_duration = Histogram::with_opts(HistogramOpts::new("test", "test")).unwrap();
+        let mut aligner = BarrierAligner::new(Box::new(left), Box::new(right))
+            .with_align_duration(Some(align_duration.clone()));
+        assert_matches!(aligner.next().await, AlignedMessage::Right(Ok(_)));
+        assert_eq!(align_duration.get_sample_count(), 0);
+        assert_matches!(aligner.next().await, AlignedMessage::Barrier(b) if b.epoch.curr == 1);
+        assert_eq!(align_duration.get_sample_count(), 1);
+    }
+}
@@ -39,7 +39,7 @@ pub struct ChainExecutor {
     info: ExecutorInfo,
 }
 
-fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
+fn mapping(upstream_indices: &[usize], msg: Message) -> Option<Message> {
     match msg {
         Message::Chunk(chunk) => {
             let (ops, columns, visibility) = chunk.into_inner();
@@ -47,9 +47,17 @@ fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
                 .iter()
                 .map(|&i| columns[i].clone())
                 .collect();
-            Message::Chunk(StreamChunk::new(ops, mapped_columns, visibility))
+            Some(Message::Chunk(StreamChunk::new(
+                ops,
+                mapped_columns,
+                visibility,
+            )))
         }
-        _ => msg,
+        Message::Watermark(watermark) => upstream_indices
+            .iter()
+            .position(|&i| i == watermark.col_idx)
+            .map(|idx| Message::Watermark(watermark.with_idx(idx))),
+        _ => Some(msg),
     }
 }
 
@@ -97,10 +105,19 @@ impl ChainExecutor {
             // Init the snapshot with reading epoch.
             let snapshot = self.snapshot.execute_with_epoch(epoch.prev);
 
+            let mut consumed_rows = 0;
             #[for_await]
             for msg in snapshot {
-                yield msg?;
+                let msg = msg?;
+                if let Message::Chunk(chunk) = &msg {
+                    consumed_rows += chunk.cardinality() as u64;
+                }
+                yield msg;
             }
+
+            // The barriers are held until the snapshot is consumed, so the progress can only be
+            // reported along with the finish.
+            self.notifier.update_progress(epoch.curr, consumed_rows);
         }
 
         // 3. Report that we've finished the creation (for a workaround).
@@ -109,7 +126,9 @@ impl ChainExecutor {
         // 4. Continuously consume the upstream.
         #[for_await]
         for msg in upstream {
-            yield mapping(&self.upstream_indices, msg?);
+            if let Some(msg) = mapping(&self.upstream_indices, msg?) {
+                yield msg;
+            }
         }
     }
 }
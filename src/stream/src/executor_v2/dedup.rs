@@ -0,0 +1,287 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::collection::evictable::EvictableHashMap;
+use risingwave_common::error::{ErrorCode, RwError};
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::error::{StreamExecutorError, StreamExecutorResult, TracedStreamExecutorError};
+use super::{BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndicesRef};
+
+/// [`AppendOnlyDedupExecutor`] drops the rows whose dedup key has been seen before, which
+/// implements `DISTINCT ON` over an append-only stream. Only the first row of each key is emitted,
+/// and the seen keys are persisted in the state store on each barrier.
+pub struct AppendOnlyDedupExecutor<S: StateStore> {
+    input: BoxedExecutor,
+    info: ExecutorInfo,
+    state: DedupState<S>,
+}
+
+struct DedupState<S: StateStore> {
+    /// Indices of the columns to deduplicate on.
+    dedup_col_indices: Vec<usize>,
+
+    /// The keyspace to persist the seen keys.
+    keyspace: Keyspace<S>,
+
+    /// Cache of the seen keys. Keys not in the cache are looked up in the state store.
+    cache: EvictableHashMap<Vec<u8>, ()>,
+
+    /// Keys seen in the current epoch which have not been written to the state store.
+    pending_keys: Vec<Vec<u8>>,
+}
+
+impl<S: StateStore> AppendOnlyDedupExecutor<S> {
+    pub fn new(
+        input: BoxedExecutor,
+        dedup_col_indices: Vec<usize>,
+        keyspace: Keyspace<S>,
+        executor_id: u64,
+    ) -> Self {
+        let info = ExecutorInfo {
+            schema: input.schema().clone(),
+            pk_indices: input.pk_indices().to_vec(),
+            identity: format!("AppendOnlyDedupExecutor {:X}", executor_id),
+        };
+        Self {
+            input,
+            info,
+            state: DedupState {
+                dedup_col_indices,
+                keyspace,
+                cache: EvictableHashMap::new(1 << 16),
+                pending_keys: vec![],
+            },
+        }
+    }
+
+    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
+    async fn execute_inner(self: Box<Self>) {
+        let Self {
+            input, mut state, ..
+        } = *self;
+        let mut input = input.execute();
+        let first_msg = input.next().await.unwrap()?;
+        let barrier = first_msg
+            .as_barrier()
+            .expect("the first message received by dedup executor must be a barrier");
+        let mut epoch = barrier.epoch.curr;
+        yield first_msg;
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Chunk(chunk) => {
+                    let vis = state.dedup_chunk(&chunk, epoch).await?;
+                    if vis.iter().any(|v| *v) {
+                        let (data_chunk, ops) = chunk.into_parts();
+                        let (columns, _) = data_chunk.into_parts();
+                        let vis = Bitmap::try_from(vis).map_err(StreamExecutorError::eval_error)?;
+                        yield Message::Chunk(StreamChunk::new(ops, columns, Some(vis)));
+                    }
+                }
+                // Rows are only dropped, so watermarks can be passed through.
+                Message::Watermark(watermark) => yield Message::Watermark(watermark),
+                Message::Barrier(barrier) => {
+                    state.flush_data(epoch).await?;
+                    epoch = barrier.epoch.curr;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> DedupState<S> {
+    /// Returns the visibility of the rows in `chunk` after deduplication.
+    async fn dedup_chunk(
+        &mut self,
+        chunk: &StreamChunk,
+        epoch: u64,
+    ) -> StreamExecutorResult<Vec<bool>> {
+        let mut vis = match chunk.visibility() {
+            Some(bitmap) => bitmap.iter().collect::<Vec<_>>(),
+            None => vec![true; chunk.capacity()],
+        };
+
+        for (idx, op) in chunk.ops().iter().enumerate() {
+            if !vis[idx] {
+                continue;
+            }
+            if *op != Op::Insert {
+                return Err(StreamExecutorError::input_error(RwError::from(
+                    ErrorCode::InternalError(format!(
+                        "append-only dedup received a non-insert operation: {:?}",
+                        op
+                    )),
+                )));
+            }
+
+            let key = Row(self
+                .dedup_col_indices
+                .iter()
+                .map(|&col_idx| chunk.column_at(col_idx).array_ref().datum_at(idx))
+                .collect());
+            let key = key.serialize().map_err(StreamExecutorError::eval_error)?;
+
+            if self.cache.contains(&key) {
+                vis[idx] = false;
+                continue;
+            }
+            let seen = self
+                .keyspace
+                .get(&key, epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?
+                .is_some();
+            if seen {
+                vis[idx] = false;
+            } else {
+                self.pending_keys.push(key.clone());
+            }
+            self.cache.put(key, ());
+        }
+
+        Ok(vis)
+    }
+
+    async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        if !self.pending_keys.is_empty() {
+            let mut write_batch = self.keyspace.state_store().start_write_batch();
+            let mut local = write_batch.prefixify(&self.keyspace);
+            for key in self.pending_keys.drain(..) {
+                local.put(key, StorageValue::new_default_put(vec![]));
+            }
+            write_batch
+                .ingest(epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+        }
+        self.cache.evict_to_target_cap();
+        Ok(())
+    }
+}
+
+impl<S: StateStore> Executor for AppendOnlyDedupExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use risingwave_common::array::{I64Array, Op, StreamChunk};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::column_nonnull;
+    use risingwave_common::types::DataType;
+
+    use super::AppendOnlyDedupExecutor;
+    use crate::executor::{Barrier, Epoch, Message};
+    use crate::executor_v2::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor_v2::Executor;
+
+    #[tokio::test]
+    async fn test_append_only_dedup_executor() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let chunk1 = StreamChunk::new(
+            vec![Op::Insert; 4],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 1, 3] },
+                column_nonnull! { I64Array, [10, 20, 30, 40] },
+            ],
+            None,
+        );
+        let chunk2 = StreamChunk::new(
+            vec![Op::Insert; 3],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 4] },
+                column_nonnull! { I64Array, [50, 60, 70] },
+            ],
+            None,
+        );
+        let source = MockSource::with_messages(
+            schema,
+            vec![1],
+            vec![
+                Message::Barrier(Barrier {
+                    epoch: Epoch::new_test_epoch(1),
+                    ..Barrier::default()
+                }),
+                Message::Chunk(chunk1),
+                Message::Barrier(Barrier {
+                    epoch: Epoch::new_test_epoch(2),
+                    ..Barrier::default()
+                }),
+                Message::Chunk(chunk2),
+            ],
+        );
+
+        let dedup = Box::new(AppendOnlyDedupExecutor::new(
+            Box::new(source),
+            vec![0],
+            create_in_memory_keyspace(),
+            1,
+        ));
+        let mut dedup = dedup.execute();
+
+        assert_matches!(dedup.next().await.unwrap().unwrap(), Message::Barrier(_));
+        let chunk = dedup.next().await.unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(
+            chunk
+                .visibility()
+                .as_ref()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![true, true, false, true]
+        );
+        assert_matches!(dedup.next().await.unwrap().unwrap(), Message::Barrier(_));
+        // Key 2 was seen in the previous epoch, and key 4 is duplicated within the chunk.
+        let chunk = dedup.next().await.unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(
+            chunk
+                .visibility()
+                .as_ref()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![false, true, false]
+        );
+    }
+}
@@ -0,0 +1,610 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use prometheus::Histogram;
+use risingwave_common::array::{Op, Row, RowDeserializer, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DataType, Datum};
+use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
+use risingwave_pb::expr::expr_node::Type as ExprNodeType;
+use risingwave_storage::storage_value::StorageValue;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::barrier_align::{AlignedMessage, BarrierAligner};
+use super::error::{StreamExecutorError, StreamExecutorResult, TracedStreamExecutorError};
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndices, PkIndicesRef,
+};
+use crate::common::StreamChunkBuilder;
+
+/// [`DynamicFilterExecutor`] keeps the rows from the left input whose `left_key` compared with
+/// the value of `right_key` from the right input is true. The right input has at most one row.
+///
+/// The left rows are kept in the state, sorted by `left_key`. Left rows are evaluated against the
+/// right value of the last barrier, and a change of the right value is applied on the next
+/// barrier, where only the left rows crossing the threshold are scanned and emitted.
+pub struct DynamicFilterExecutor<S: StateStore> {
+    left: BoxedExecutor,
+    right: BoxedExecutor,
+    info: ExecutorInfo,
+    state: DynamicFilterState<S>,
+
+    /// Observes the time waiting for the barrier from the other side, if set.
+    barrier_align_duration: Option<Histogram>,
+}
+
+struct DynamicFilterState<S: StateStore> {
+    left_key: usize,
+    right_key: usize,
+    comparator: ExprNodeType,
+    pk_indices: PkIndices,
+    data_types: Vec<DataType>,
+
+    /// The keyspace of the left rows, whose keys are the memcomparable `left_key` followed by the
+    /// primary key. Rows with a null `left_key` never pass the filter and are not stored.
+    left_keyspace: Keyspace<S>,
+
+    /// The keyspace of the right value, whose key is the keyspace itself.
+    right_keyspace: Keyspace<S>,
+
+    /// Left rows written in the current epoch. `None` means a deletion.
+    pending_left: HashMap<Vec<u8>, Option<Vec<u8>>>,
+
+    /// The memcomparable right value of the last barrier. `None` if the right input has no row or
+    /// a null value, in which case no row passes the filter.
+    threshold: Option<Vec<u8>>,
+
+    /// The latest right value received in the current epoch, if it has changed.
+    staged_right: Option<Datum>,
+}
+
+impl<S: StateStore> DynamicFilterExecutor<S> {
+    pub fn new(
+        left: BoxedExecutor,
+        right: BoxedExecutor,
+        left_key: usize,
+        right_key: usize,
+        comparator: ExprNodeType,
+        keyspace: Keyspace<S>,
+        executor_id: u64,
+    ) -> Self {
+        assert!(
+            matches!(
+                comparator,
+                ExprNodeType::LessThan
+                    | ExprNodeType::LessThanOrEqual
+                    | ExprNodeType::GreaterThan
+                    | ExprNodeType::GreaterThanOrEqual
+            ),
+            "invalid comparator {:?} for dynamic filter",
+            comparator
+        );
+        let info = ExecutorInfo {
+            schema: left.schema().clone(),
+            pk_indices: left.pk_indices().to_vec(),
+            identity: format!("DynamicFilterExecutor {:X}", executor_id),
+        };
+        let state = DynamicFilterState {
+            left_key,
+            right_key,
+            comparator,
+            pk_indices: info.pk_indices.clone(),
+            data_types: info.schema.data_types(),
+            left_keyspace: keyspace.append_u8(b'l'),
+            right_keyspace: keyspace.append_u8(b'r'),
+            pending_left: HashMap::new(),
+            threshold: None,
+            staged_right: None,
+        };
+        Self {
+            left,
+            right,
+            info,
+            state,
+            barrier_align_duration: None,
+        }
+    }
+
+    /// Reports the time waiting for the barrier from the other side on each barrier.
+    pub fn with_barrier_align_duration(mut self, barrier_align_duration: Histogram) -> Self {
+        self.barrier_align_duration = Some(barrier_align_duration);
+        self
+    }
+
+    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
+    async fn execute_inner(self: Box<Self>) {
+        let Self {
+            left,
+            right,
+            mut state,
+            barrier_align_duration,
+            ..
+        } = *self;
+        let mut input =
+            BarrierAligner::new(left, right).with_align_duration(barrier_align_duration);
+
+        let barrier = match input.next().await {
+            AlignedMessage::Barrier(barrier) => barrier,
+            _ => panic!("the first message received by dynamic filter executor must be a barrier"),
+        };
+        let mut epoch = barrier.epoch.curr;
+        state.init(barrier.epoch.prev).await?;
+        yield Message::Barrier(barrier);
+
+        loop {
+            match input.next().await {
+                AlignedMessage::Left(chunk) => {
+                    if let Some(chunk) = state.apply_left_chunk(chunk?)? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                AlignedMessage::Right(chunk) => state.apply_right_chunk(chunk?),
+                // Rows below a watermark may still be emitted once the right value changes, so
+                // watermarks can not be propagated.
+                AlignedMessage::WatermarkLeft(_) | AlignedMessage::WatermarkRight(_) => {}
+                AlignedMessage::Barrier(barrier) => {
+                    for chunk in state.flush_data(epoch).await? {
+                        yield Message::Chunk(chunk);
+                    }
+                    epoch = barrier.epoch.curr;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> DynamicFilterState<S> {
+    /// Recovers the right value from the state store.
+    async fn init(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        self.threshold = self
+            .right_keyspace
+            .value(epoch)
+            .await
+            .map_err(StreamExecutorError::storage)?
+            .map(|value| value.to_vec());
+        Ok(())
+    }
+
+    fn serialize_datum(datum: Datum) -> StreamExecutorResult<Vec<u8>> {
+        Row(vec![datum])
+            .serialize()
+            .map_err(StreamExecutorError::eval_error)
+    }
+
+    fn is_lower_bound(&self) -> bool {
+        matches!(
+            self.comparator,
+            ExprNodeType::GreaterThan | ExprNodeType::GreaterThanOrEqual
+        )
+    }
+
+    /// Returns the key range `[start, end)` of the left rows passing the filter with the given
+    /// threshold, where `end` is `None` if unbounded.
+    ///
+    /// A left key starts with the memcomparable `left_key`, followed by the memcomparable primary
+    /// key, whose first byte is always less than `0xff`. So the keys with `left_key` equal to the
+    /// threshold are within `[threshold, threshold ++ 0xff)`.
+    fn key_range(&self, threshold: &[u8]) -> (Vec<u8>, Option<Vec<u8>>) {
+        let after_threshold = [threshold, &[u8::MAX]].concat();
+        match self.comparator {
+            ExprNodeType::GreaterThan => (after_threshold, None),
+            ExprNodeType::GreaterThanOrEqual => (threshold.to_vec(), None),
+            ExprNodeType::LessThan => (vec![], Some(threshold.to_vec())),
+            ExprNodeType::LessThanOrEqual => (vec![], Some(after_threshold)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn passes(&self, key: &[u8]) -> bool {
+        match &self.threshold {
+            Some(threshold) => {
+                let (start, end) = self.key_range(threshold);
+                key >= start.as_slice() && end.map_or(true, |end| key < end.as_slice())
+            }
+            None => false,
+        }
+    }
+
+    /// Applies the left chunk to the state, and returns the rows passing the current threshold.
+    fn apply_left_chunk(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        let (data_chunk, ops) = chunk.into_parts();
+        let (columns, visibility) = data_chunk.into_parts();
+        let mut vis = match visibility {
+            Some(bitmap) => bitmap.iter().collect::<Vec<_>>(),
+            None => vec![true; ops.len()],
+        };
+
+        for (idx, op) in ops.iter().enumerate() {
+            if !vis[idx] {
+                continue;
+            }
+            let datum = columns[self.left_key].array_ref().datum_at(idx);
+            if datum.is_none() {
+                vis[idx] = false;
+                continue;
+            }
+            let mut key = Self::serialize_datum(datum)?;
+            let pk = Row(self
+                .pk_indices
+                .iter()
+                .map(|&pk_idx| columns[pk_idx].array_ref().datum_at(idx))
+                .collect());
+            key.extend(pk.serialize().map_err(StreamExecutorError::eval_error)?);
+
+            let value = match op {
+                Op::Insert | Op::UpdateInsert => {
+                    let row = Row(columns
+                        .iter()
+                        .map(|column| column.array_ref().datum_at(idx))
+                        .collect());
+                    Some(row.serialize().map_err(StreamExecutorError::eval_error)?)
+                }
+                Op::Delete | Op::UpdateDelete => None,
+            };
+            vis[idx] = self.passes(&key);
+            self.pending_left.insert(key, value);
+        }
+
+        if !vis.iter().any(|v| *v) {
+            return Ok(None);
+        }
+        // An update pair may be split by the filter, so updates are emitted as deletes and inserts.
+        let ops = ops
+            .into_iter()
+            .map(|op| match op {
+                Op::Insert | Op::UpdateInsert => Op::Insert,
+                Op::Delete | Op::UpdateDelete => Op::Delete,
+            })
+            .collect();
+        let vis = Bitmap::try_from(vis).map_err(StreamExecutorError::eval_error)?;
+        Ok(Some(StreamChunk::new(ops, columns, Some(vis))))
+    }
+
+    fn apply_right_chunk(&mut self, chunk: StreamChunk) {
+        for row in chunk.rows() {
+            let datum = row
+                .value_at(self.right_key)
+                .map(|scalar| scalar.into_scalar_impl());
+            self.staged_right = match row.op() {
+                Op::Insert | Op::UpdateInsert => Some(datum),
+                Op::Delete | Op::UpdateDelete => Some(None),
+            };
+        }
+    }
+
+    /// Writes the changes of this epoch, and applies the change of the right value. Returns the
+    /// left rows crossing the threshold, in chunks of at most `DEFAULT_CHUNK_BUFFER_SIZE` rows.
+    async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<Vec<StreamChunk>> {
+        let new_threshold = match self.staged_right.take() {
+            Some(datum) => Some(match datum {
+                Some(scalar) => Some(Self::serialize_datum(Some(scalar))?),
+                None => None,
+            }),
+            None => None,
+        };
+
+        let mut write_batch = self.left_keyspace.state_store().start_write_batch();
+        {
+            let mut local = write_batch.prefixify(&self.left_keyspace);
+            for (key, value) in self.pending_left.drain() {
+                match value {
+                    Some(value) => local.put(key, StorageValue::new_default_put(value)),
+                    None => local.delete(key),
+                }
+            }
+        }
+        if let Some(threshold) = &new_threshold {
+            let mut local = write_batch.prefixify(&self.right_keyspace);
+            match threshold {
+                Some(threshold) => {
+                    local.put_single(StorageValue::new_default_put(threshold.clone()))
+                }
+                None => local.delete_single(),
+            }
+        }
+        if !write_batch.is_empty() {
+            write_batch
+                .ingest(epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+        }
+
+        let new_threshold = match new_threshold {
+            Some(threshold) if threshold != self.threshold => threshold,
+            _ => return Ok(vec![]),
+        };
+        let old_range = self.threshold.as_ref().map(|t| self.key_range(t));
+        let new_range = new_threshold.as_ref().map(|t| self.key_range(t));
+        self.threshold = new_threshold;
+
+        let (op, (start, end)) = match (old_range, new_range) {
+            (None, None) => return Ok(vec![]),
+            (None, Some(range)) => (Op::Insert, range),
+            (Some(range), None) => (Op::Delete, range),
+            (Some((old_start, old_end)), Some((new_start, new_end))) => {
+                if self.is_lower_bound() {
+                    if new_start < old_start {
+                        (Op::Insert, (new_start, Some(old_start)))
+                    } else {
+                        (Op::Delete, (old_start, Some(new_start)))
+                    }
+                } else {
+                    let (old_end, new_end) = (old_end.unwrap(), new_end.unwrap());
+                    if new_end > old_end {
+                        (Op::Insert, (old_end, Some(new_end)))
+                    } else {
+                        (Op::Delete, (new_end, Some(old_end)))
+                    }
+                }
+            }
+        };
+
+        let start = self.left_keyspace.prefixed_key(start);
+        let pairs = match end {
+            Some(end) => {
+                let end = self.left_keyspace.prefixed_key(end);
+                self.left_keyspace
+                    .state_store()
+                    .scan(start..end, None, epoch)
+                    .await
+            }
+            None => {
+                self.left_keyspace
+                    .scan_with_start_key(start, None, epoch)
+                    .await
+            }
+        }
+        .map_err(StreamExecutorError::storage)?;
+
+        let deserializer = RowDeserializer::new(self.data_types.clone());
+        let mut chunks = Vec::with_capacity(pairs.len() / DEFAULT_CHUNK_BUFFER_SIZE + 1);
+        for pairs in pairs.chunks(DEFAULT_CHUNK_BUFFER_SIZE) {
+            let mut builder = StreamChunkBuilder::new(pairs.len(), &self.data_types, 0, 0)
+                .map_err(StreamExecutorError::eval_error)?;
+            for (_, value) in pairs {
+                let row = deserializer
+                    .deserialize(value)
+                    .map_err(StreamExecutorError::eval_error)?;
+                builder
+                    .append_row_matched(op, &row)
+                    .map_err(StreamExecutorError::eval_error)?;
+            }
+            chunks.push(builder.finish().map_err(StreamExecutorError::eval_error)?);
+        }
+        Ok(chunks)
+    }
+}
+
+impl<S: StateStore> Executor for DynamicFilterExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::{I64Array, Op, StreamChunk};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::column_nonnull;
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
+    use risingwave_pb::expr::expr_node::Type as ExprNodeType;
+
+    use super::DynamicFilterExecutor;
+    use crate::executor::{Barrier, Epoch, Message};
+    use crate::executor_v2::test_utils::{create_in_memory_keyspace, MockSource};
+    use crate::executor_v2::Executor;
+
+    fn barrier(epoch: u64) -> Message {
+        Message::Barrier(Barrier {
+            epoch: Epoch::new_test_epoch(epoch),
+            ..Barrier::default()
+        })
+    }
+
+    /// Returns the ops and the values of the second column of the visible rows.
+    fn visible_rows(chunk: StreamChunk) -> (Vec<Op>, Vec<Option<i64>>) {
+        let chunk = chunk.compact().unwrap();
+        let ids = chunk.column_at(1).array_ref().as_int64().iter().collect();
+        (chunk.ops().to_vec(), ids)
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_filter() {
+        let left_schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let right_schema = Schema {
+            fields: vec![Field::unnamed(DataType::Int64)],
+        };
+        let left = MockSource::with_messages(
+            left_schema,
+            vec![1],
+            vec![
+                barrier(1),
+                Message::Chunk(StreamChunk::new(
+                    vec![Op::Insert; 3],
+                    vec![
+                        column_nonnull! { I64Array, [1, 5, 10] },
+                        column_nonnull! { I64Array, [1, 2, 3] },
+                    ],
+                    None,
+                )),
+                barrier(2),
+                barrier(3),
+                Message::Chunk(StreamChunk::new(
+                    vec![Op::Insert],
+                    vec![
+                        column_nonnull! { I64Array, [8] },
+                        column_nonnull! { I64Array, [4] },
+                    ],
+                    None,
+                )),
+                barrier(4),
+            ],
+        );
+        let right = MockSource::with_messages(
+            right_schema,
+            vec![],
+            vec![
+                barrier(1),
+                barrier(2),
+                Message::Chunk(StreamChunk::new(
+                    vec![Op::Insert],
+                    vec![column_nonnull! { I64Array, [4] }],
+                    None,
+                )),
+                barrier(3),
+                Message::Chunk(StreamChunk::new(
+                    vec![Op::UpdateDelete, Op::UpdateInsert],
+                    vec![column_nonnull! { I64Array, [4, 7] }],
+                    None,
+                )),
+                barrier(4),
+            ],
+        );
+
+        let filter = Box::new(DynamicFilterExecutor::new(
+            Box::new(left),
+            Box::new(right),
+            0,
+            0,
+            ExprNodeType::GreaterThan,
+            create_in_memory_keyspace(),
+            1,
+        ));
+        let mut filter = filter.execute();
+
+        assert_matches!(filter.next().await.unwrap().unwrap(), Message::Barrier(_));
+        // No right value yet, so no left row passes.
+        assert_matches!(filter.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        // The right value 4 is applied on the barrier.
+        let chunk = filter.next().await.unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(
+            visible_rows(chunk),
+            (vec![Op::Insert, Op::Insert], vec![Some(2), Some(3)])
+        );
+        assert_matches!(filter.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        // The new left row is filtered by the right value 4 of the last barrier.
+        let chunk = filter.next().await.unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(visible_rows(chunk), (vec![Op::Insert], vec![Some(4)]));
+
+        // The right value changes from 4 to 7, so the row with value 5 is deleted.
+        let chunk = filter.next().await.unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(visible_rows(chunk), (vec![Op::Delete], vec![Some(2)]));
+        assert_matches!(filter.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_filter_split_chunks() {
+        let left_schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let right_schema = Schema {
+            fields: vec![Field::unnamed(DataType::Int64)],
+        };
+        let values = (0..DEFAULT_CHUNK_BUFFER_SIZE as i64 + 1)
+            .map(Some)
+            .collect::<Vec<_>>();
+        let left = MockSource::with_messages(
+            left_schema,
+            vec![1],
+            vec![
+                barrier(1),
+                Message::Chunk(StreamChunk::new(
+                    vec![Op::Insert; values.len()],
+                    vec![
+                        Column::new(Arc::new(I64Array::from_slice(&values).unwrap().into())),
+                        Column::new(Arc::new(I64Array::from_slice(&values).unwrap().into())),
+                    ],
+                    None,
+                )),
+                barrier(2),
+            ],
+        );
+        let right = MockSource::with_messages(
+            right_schema,
+            vec![],
+            vec![
+                barrier(1),
+                Message::Chunk(StreamChunk::new(
+                    vec![Op::Insert],
+                    vec![column_nonnull! { I64Array, [-1] }],
+                    None,
+                )),
+                barrier(2),
+            ],
+        );
+
+        let filter = Box::new(DynamicFilterExecutor::new(
+            Box::new(left),
+            Box::new(right),
+            0,
+            0,
+            ExprNodeType::GreaterThan,
+            create_in_memory_keyspace(),
+            1,
+        ));
+        let mut filter = filter.execute();
+
+        assert_matches!(filter.next().await.unwrap().unwrap(), Message::Barrier(_));
+
+        // All left rows pass the right value -1, and are emitted in chunks of the usual size.
+        let chunk = filter.next().await.unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(chunk.cardinality(), DEFAULT_CHUNK_BUFFER_SIZE);
+        let chunk = filter.next().await.unwrap().unwrap().into_chunk().unwrap();
+        assert_eq!(
+            visible_rows(chunk),
+            (
+                vec![Op::Insert],
+                vec![Some(DEFAULT_CHUNK_BUFFER_SIZE as i64)]
+            )
+        );
+        assert_matches!(filter.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+}
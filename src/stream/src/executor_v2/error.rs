@@ -45,6 +45,12 @@ pub enum StreamExecutorError {
     #[error("TopN state error: {0}")]
     TopNStateError(RwError),
 
+    #[error("Hash join error: {0}")]
+    HashJoinError(RwError),
+
+    #[error("Source error: {0}")]
+    SourceError(RwError),
+
     #[error("Channel `{0}` closed")]
     ChannelClosed(String),
 }
@@ -54,10 +60,6 @@ impl StreamExecutorError {
         Self::Storage(error.into()).into()
     }
 
-    pub fn executor_v1(error: impl Into<RwError>) -> TracedStreamExecutorError {
-        Self::ExecutorV1(error.into()).into()
-    }
-
     pub fn eval_error(error: impl Into<RwError>) -> TracedStreamExecutorError {
         Self::EvalError(error.into()).into()
     }
@@ -74,6 +76,14 @@ impl StreamExecutorError {
         Self::TopNStateError(error.into()).into()
     }
 
+    pub fn hash_join_error(error: impl Into<RwError>) -> TracedStreamExecutorError {
+        Self::HashJoinError(error.into()).into()
+    }
+
+    pub fn source_error(error: impl Into<RwError>) -> TracedStreamExecutorError {
+        Self::SourceError(error.into()).into()
+    }
+
     pub fn channel_closed(name: impl Into<String>) -> TracedStreamExecutorError {
         Self::ChannelClosed(name.into()).into()
     }
@@ -126,7 +136,7 @@ mod tests {
     use super::*;
 
     fn func_return_error() -> StreamExecutorResult<()> {
-        Err(ErrorCode::InternalError("test_error".into())).map_err(StreamExecutorError::executor_v1)
+        Err(ErrorCode::InternalError("test_error".into())).map_err(StreamExecutorError::input_error)
     }
 
     #[test]
@@ -12,30 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures::{stream, StreamExt};
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::StreamChunk;
+use risingwave_common::array::{Op, Row, RowDeserializer, StreamChunk};
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
 use risingwave_common::collection::evictable::EvictableHashMap;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::hash::HashKey;
+use risingwave_common::types::{Datum, ScalarImpl};
 use risingwave_storage::{Keyspace, StateStore};
 
-use super::{Executor, ExecutorInfo, StreamExecutorResult};
+use super::{Executor, ExecutorInfo, StreamExecutorResult, Watermark};
 use crate::executor::{
     agg_input_arrays, pk_input_arrays, AggCall, AggState, PkDataTypes, PkIndicesRef,
+    ROW_COUNT_COLUMN,
 };
 use crate::executor_v2::agg::{
     generate_agg_schema, generate_agg_state, AggExecutor, AggExecutorWrapper,
 };
 use crate::executor_v2::error::StreamExecutorError;
 use crate::executor_v2::PkIndices;
+use crate::task::MemoryUsageReporter;
 
 /// [`HashAggExecutor`] could process large amounts of data using a state backend. It works as
 /// follows:
@@ -50,6 +54,20 @@ use crate::executor_v2::PkIndices;
 pub type HashAggExecutor<K, S> = AggExecutorWrapper<AggHashAggExecutor<K, S>>;
 
 impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
+    /// Only emit the final result of each group as an insertion, once its window is closed by the
+    /// watermark on the group key. The output of the executor will be append-only.
+    pub fn with_emit_on_window_close(mut self, emit_on_window_close: bool) -> Self {
+        self.inner.emit_on_window_close = emit_on_window_close;
+        self
+    }
+
+    /// Reports the memory used by the cached states to the memory manager, which may ask the
+    /// executor to evict the states on barriers.
+    pub fn with_memory_reporter(mut self, memory_reporter: MemoryUsageReporter) -> Self {
+        self.inner.memory_reporter = Some(memory_reporter);
+        self
+    }
+
     pub fn new(
         input: Box<dyn Executor>,
         agg_calls: Vec<AggCall>,
@@ -97,6 +115,26 @@ pub struct AggHashAggExecutor<K: HashKey, S: StateStore> {
     /// Indices of the columns
     /// all of the aggregation functions in this executor should depend on same group of keys
     key_indices: Vec<usize>,
+
+    /// The latest watermark on one of the group key columns, which is not applied yet. Its
+    /// `col_idx` is the position in the group key, i.e. the column index in the output.
+    buffered_watermark: Option<Watermark>,
+
+    /// The latest watermark applied on each group key column. The rows falling behind it arrive
+    /// late, and are dropped since their groups have been closed.
+    applied_watermarks: Vec<Option<ScalarImpl>>,
+
+    /// The group keys with states, which bounds the cleaning by watermarks to these groups.
+    /// They're loaded from the row count states when a watermark is applied for the first
+    /// time, and tracked since then.
+    group_keys: Option<HashSet<Row>>,
+
+    /// Whether to emit the results only after the windows are closed. See
+    /// [`HashAggExecutor::with_emit_on_window_close`].
+    emit_on_window_close: bool,
+
+    /// Reports the memory used by the cached states, if set.
+    memory_reporter: Option<MemoryUsageReporter>,
 }
 
 impl<K: HashKey, S: StateStore> AggHashAggExecutor<K, S> {
@@ -120,10 +158,44 @@ impl<K: HashKey, S: StateStore> AggHashAggExecutor<K, S> {
             keyspace,
             state_map: EvictableHashMap::new(1 << 16),
             agg_calls,
+            applied_watermarks: vec![None; key_indices.len()],
             key_indices,
+            buffered_watermark: None,
+            group_keys: None,
+            emit_on_window_close: false,
+            memory_reporter: None,
         })
     }
 
+    /// Estimates the memory held by a group in `state_map`, including the rows cached by its
+    /// table states, e.g. of `MAX`.
+    fn estimated_group_size(_key: &K, state: &Option<Box<AggState<S>>>) -> usize {
+        size_of::<K>()
+            + size_of::<Option<Box<AggState<S>>>>()
+            + state.as_ref().map_or(0, |state| state.estimated_size())
+    }
+
+    fn estimated_size(&self) -> usize {
+        self.state_map
+            .iter()
+            .map(|(key, state)| Self::estimated_group_size(key, state))
+            .sum()
+    }
+
+    /// Evicts the cached states to the target capacity, and further if the memory manager asks
+    /// to.
+    fn evict(&mut self) {
+        self.state_map.evict_to_target_cap();
+
+        if let Some(memory_reporter) = &self.memory_reporter {
+            if let Some(target) = memory_reporter.report(self.estimated_size()) {
+                self.state_map
+                    .evict_to_size(target, Self::estimated_group_size);
+                memory_reporter.report(self.estimated_size());
+            }
+        }
+    }
+
     /// Get unique keys and visibility map of each key in a batch.
     ///
     /// The returned order is the same as how we get distinct final columns from original columns.
@@ -173,11 +245,44 @@ impl<K: HashKey, S: StateStore> AggHashAggExecutor<K, S> {
         Ok(result)
     }
 
+    fn input_pk_data_types(&self) -> PkDataTypes {
+        self.info
+            .pk_indices
+            .iter()
+            .map(|idx| self.info.schema.fields[*idx].data_type.clone())
+            .collect()
+    }
+
     fn is_dirty(&self) -> bool {
         self.state_map
             .values()
             .any(|state| state.as_ref().unwrap().is_dirty())
     }
+
+    /// Loads the group keys from the row count states, whose keys are exactly the group keys, if
+    /// they're not tracked yet. Should only be called when all the states have been flushed.
+    async fn load_group_keys(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        if self.group_keys.is_some() {
+            return Ok(());
+        }
+        let key_data_types = self.schema.data_types()[..self.key_indices.len()].to_vec();
+        let deserializer = RowDeserializer::new(key_data_types);
+        let mut group_keys = HashSet::new();
+        for (raw_key, _) in self
+            .keyspace
+            .append_u16(ROW_COUNT_COLUMN as u16)
+            .scan_strip_prefix(None, epoch)
+            .await
+            .map_err(StreamExecutorError::agg_state_error)?
+        {
+            let key = deserializer
+                .deserialize(&raw_key)
+                .map_err(StreamExecutorError::agg_state_error)?;
+            group_keys.insert(key);
+        }
+        self.group_keys = Some(group_keys);
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -190,20 +295,36 @@ impl<K: HashKey, S: StateStore> AggExecutor for AggHashAggExecutor<K, S> {
 
         // --- Find unique keys in this batch and generate visibility map for each key ---
         // TODO: this might be inefficient if there are not too many duplicated keys in one batch.
-        let unique_keys = self
+        let mut unique_keys = self
             .get_unique_keys(keys, &visibility)
             .map_err(StreamExecutorError::eval_error)?;
 
+        // Once watermarks are applied, the rows of the closed groups arriving late are dropped
+        // instead of creating the groups again, and the new groups are tracked.
+        let key_data_types = &self.schema.data_types()[..self.key_indices.len()];
+        if let Some(group_keys) = &mut self.group_keys {
+            let mut live_keys = Vec::with_capacity(unique_keys.len());
+            for (key, vis_map) in unique_keys {
+                let row = key
+                    .clone()
+                    .deserialize(key_data_types.iter())
+                    .map_err(StreamExecutorError::eval_error)?;
+                let late = row.0.iter().zip_eq(self.applied_watermarks.iter()).any(
+                    |(datum, watermark)| matches!((datum, watermark), (Some(v), Some(w)) if v < w),
+                );
+                if !late {
+                    group_keys.insert(row);
+                    live_keys.push((key, vis_map));
+                }
+            }
+            unique_keys = live_keys;
+        }
+
         // --- Retrieve all aggregation inputs in advance ---
         // Previously, this is done in `unique_keys` inner loop, which is very inefficient.
         let all_agg_input_arrays = agg_input_arrays(&self.agg_calls, &columns);
         let pk_input_arrays = pk_input_arrays(&self.info.pk_indices, &columns);
-        let input_pk_data_types: PkDataTypes = self
-            .info
-            .pk_indices
-            .iter()
-            .map(|idx| self.info.schema.fields[*idx].data_type.clone())
-            .collect();
+        let input_pk_data_types = self.input_pk_data_types();
 
         // When applying batch, we will send columns of primary keys to the last N columns.
         let all_agg_data = all_agg_input_arrays
@@ -214,7 +335,6 @@ impl<K: HashKey, S: StateStore> AggExecutor for AggHashAggExecutor<K, S> {
             })
             .collect_vec();
 
-        let key_data_types = &self.schema.data_types()[..self.key_indices.len()];
         let mut futures = vec![];
         for (key, vis_map) in unique_keys {
             // Retrieve previous state from the KeyedState.
@@ -310,6 +430,15 @@ impl<K: HashKey, S: StateStore> AggExecutor for AggHashAggExecutor<K, S> {
             .await
             .map_err(StreamExecutorError::agg_state_error)?;
 
+        // The results will be emitted once the windows are closed, in `emit_closed_windows`.
+        if self.emit_on_window_close {
+            for states in self.state_map.values_mut() {
+                states.as_mut().unwrap().discard_changes();
+            }
+            self.evict();
+            return Ok(None);
+        }
+
         // --- Produce the stream chunk ---
 
         // --- Create array builders ---
@@ -341,7 +470,7 @@ impl<K: HashKey, S: StateStore> AggExecutor for AggHashAggExecutor<K, S> {
         // In current implementation, we need to fetch the RowCount from the state store once a key
         // is deleted and added again. We should find a way to eliminate this extra fetch.
         assert!(!self.is_dirty());
-        self.state_map.evict_to_target_cap();
+        self.evict();
 
         let columns: Vec<Column> = builders
             .into_iter()
@@ -355,6 +484,158 @@ impl<K: HashKey, S: StateStore> AggExecutor for AggHashAggExecutor<K, S> {
         Ok(Some(chunk))
     }
 
+    fn apply_watermark(&mut self, watermark: Watermark) {
+        if let Some(pos) = self
+            .key_indices
+            .iter()
+            .position(|&idx| idx == watermark.col_idx)
+        {
+            self.buffered_watermark = Some(watermark.with_idx(pos));
+        }
+    }
+
+    async fn emit_closed_windows(
+        &mut self,
+        epoch: u64,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        if !self.emit_on_window_close || self.buffered_watermark.is_none() {
+            return Ok(None);
+        }
+
+        // All the states have been flushed, so we find the closed groups from the group keys
+        // tracked.
+        self.load_group_keys(epoch).await?;
+        let watermark = self.buffered_watermark.as_ref().unwrap();
+        let closed_keys = self
+            .group_keys
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|key| matches!(&key[watermark.col_idx], Some(v) if *v < watermark.val))
+            .cloned()
+            .collect_vec();
+        if closed_keys.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builders = self
+            .schema
+            .create_array_builders(closed_keys.len())
+            .map_err(StreamExecutorError::eval_error)?;
+        let mut ops = Vec::with_capacity(closed_keys.len());
+        let (key_builders, agg_builders) = builders.split_at_mut(self.key_indices.len());
+        for key in closed_keys {
+            let mut states = generate_agg_state(
+                Some(&key),
+                &self.agg_calls,
+                &self.keyspace,
+                self.input_pk_data_types(),
+                epoch,
+            )
+            .await?;
+            if states
+                .row_count(epoch)
+                .await
+                .map_err(StreamExecutorError::agg_state_error)?
+                == 0
+            {
+                continue;
+            }
+
+            ops.push(Op::Insert);
+            for (builder, datum) in key_builders.iter_mut().zip_eq(key.0.iter()) {
+                builder
+                    .append_datum(datum)
+                    .map_err(StreamExecutorError::eval_error)?;
+            }
+            for (builder, state) in agg_builders
+                .iter_mut()
+                .zip_eq(states.managed_states.iter_mut())
+            {
+                let output = state
+                    .get_output(epoch)
+                    .await
+                    .map_err(StreamExecutorError::agg_state_error)?;
+                builder
+                    .append_datum(&output)
+                    .map_err(StreamExecutorError::eval_error)?;
+            }
+        }
+        if ops.is_empty() {
+            return Ok(None);
+        }
+
+        let columns: Vec<Column> = builders
+            .into_iter()
+            .map(|builder| -> Result<_> { Ok(Column::new(Arc::new(builder.finish()?))) })
+            .try_collect()
+            .map_err(StreamExecutorError::eval_error)?;
+
+        Ok(Some(StreamChunk::new(ops, columns, None)))
+    }
+
+    async fn clean_by_watermark(&mut self, epoch: u64) -> StreamExecutorResult<Option<Watermark>> {
+        let watermark = match self.buffered_watermark.take() {
+            Some(watermark) => watermark,
+            None => return Ok(None),
+        };
+        assert!(!self.is_dirty());
+
+        // The groups behind the applied watermark have been cleaned.
+        if matches!(&self.applied_watermarks[watermark.col_idx], Some(v) if *v >= watermark.val) {
+            return Ok(Some(watermark));
+        }
+        self.load_group_keys(epoch).await?;
+        self.applied_watermarks[watermark.col_idx] = Some(watermark.val.clone());
+
+        let key_data_types = &self.schema.data_types()[..self.key_indices.len()];
+        let expired = |datum: &Datum| matches!(datum, Some(v) if *v < watermark.val);
+
+        // Groups closed by the watermark will never be updated again, so remove them from both the
+        // cache and the state store.
+        self.state_map.retain_keys(|key| {
+            key.clone()
+                .deserialize(key_data_types.iter())
+                .map(|row| !expired(&row[watermark.col_idx]))
+                .unwrap_or(true)
+        });
+
+        let group_keys = self.group_keys.as_mut().unwrap();
+        let closed_keys = group_keys
+            .iter()
+            .filter(|key| expired(&key[watermark.col_idx]))
+            .cloned()
+            .collect_vec();
+        let mut write_batch = self.keyspace.state_store().start_write_batch();
+        for key in closed_keys {
+            let key_bytes = key
+                .serialize()
+                .map_err(StreamExecutorError::agg_state_error)?;
+            for idx in 0..self.agg_calls.len() {
+                // The prefix of the state is `agg_call_idx / [group_key]`
+                let keyspace = self
+                    .keyspace
+                    .append_u16(idx as u16)
+                    .append(key_bytes.clone());
+                let mut local = write_batch.prefixify(&keyspace);
+                for (raw_key, _) in keyspace
+                    .scan_strip_prefix(None, epoch)
+                    .await
+                    .map_err(StreamExecutorError::agg_state_error)?
+                {
+                    local.delete(&raw_key);
+                }
+            }
+            group_keys.remove(&key);
+        }
+        write_batch
+            .ingest(epoch)
+            .await
+            .map_err(StreamExecutorError::agg_state_error)?;
+
+        Ok(Some(watermark))
+    }
+
     fn schema(&self) -> &Schema {
         &self.schema
     }
@@ -389,14 +670,14 @@ mod tests {
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::column_nonnull;
     use risingwave_common::error::Result;
-    use risingwave_common::hash::{calc_hash_key_kind, HashKey, HashKeyDispatcher};
-    use risingwave_common::types::DataType;
+    use risingwave_common::hash::{calc_hash_key_kind, HashKey, HashKeyDispatcher, Key64};
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_expr::expr::*;
     use risingwave_storage::{Keyspace, StateStore};
 
     use crate::executor::{AggArgs, AggCall};
     use crate::executor_v2::test_utils::*;
-    use crate::executor_v2::{Executor, HashAggExecutor, Message, PkIndices};
+    use crate::executor_v2::{Executor, HashAggExecutor, Message, PkIndices, Watermark};
     use crate::row_nonnull;
 
     struct HashAggExecutorDispatcher<S: StateStore>(PhantomData<S>);
@@ -495,16 +776,19 @@ mod tests {
                 kind: AggKind::RowCount,
                 args: AggArgs::None,
                 return_type: DataType::Int64,
+                append_only: false,
             },
             AggCall {
                 kind: AggKind::Count,
                 args: AggArgs::Unary(DataType::Int64, 0),
                 return_type: DataType::Int64,
+                append_only: false,
             },
             AggCall {
                 kind: AggKind::Count,
                 args: AggArgs::None,
                 return_type: DataType::Int64,
+                append_only: false,
             },
         ];
 
@@ -604,17 +888,20 @@ mod tests {
                 kind: AggKind::RowCount,
                 args: AggArgs::None,
                 return_type: DataType::Int64,
+                append_only: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
+                append_only: false,
             },
             // This is local hash aggregation, so we add another sum state
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 2),
                 return_type: DataType::Int64,
+                append_only: false,
             },
         ];
 
@@ -728,11 +1015,13 @@ mod tests {
                 kind: AggKind::RowCount,
                 args: AggArgs::None,
                 return_type: DataType::Int64,
+                append_only: false,
             },
             AggCall {
                 kind: AggKind::Min,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
+                append_only: false,
             },
         ];
 
@@ -794,4 +1083,107 @@ mod tests {
             unreachable!("unexpected message {:?}", msg);
         }
     }
+
+    #[tokio::test]
+    async fn test_hash_aggregation_emit_on_window_close() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let mut source = MockSource::new(schema, PkIndices::new());
+        source.push_barrier(1, false);
+        source.push_chunks(
+            [StreamChunk::new(
+                vec![Op::Insert, Op::Insert, Op::Insert],
+                vec![
+                    column_nonnull! { I64Array, [1, 1, 2] },
+                    column_nonnull! { I64Array, [10, 20, 5] },
+                ],
+                None,
+            )]
+            .into_iter(),
+        );
+        source.push_barrier(2, false);
+        source.push_watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(2)));
+        source.push_barrier(3, false);
+        // The row of the closed window `1` arrives late.
+        source.push_chunks(
+            [StreamChunk::new(
+                vec![Op::Insert, Op::Insert],
+                vec![
+                    column_nonnull! { I64Array, [1, 2] },
+                    column_nonnull! { I64Array, [30, 6] },
+                ],
+                None,
+            )]
+            .into_iter(),
+        );
+        source.push_watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(3)));
+        source.push_barrier(4, false);
+
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::RowCount,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                append_only: false,
+            },
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                append_only: false,
+            },
+        ];
+        let hash_agg = HashAggExecutor::<Key64, _>::new(
+            Box::new(source),
+            agg_calls,
+            create_in_memory_keyspace(),
+            vec![],
+            1,
+            vec![0],
+        )
+        .unwrap()
+        .with_emit_on_window_close(true);
+        let mut hash_agg = Box::new(hash_agg).execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+
+        // Nothing is emitted before the window is closed.
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // Only the closed window `1` is emitted, as an insertion.
+        assert_matches!(hash_agg.next().await.unwrap().unwrap(), Message::Chunk(chunk) => {
+            let (data_chunk, ops) = chunk.into_parts();
+            assert_eq!(ops, vec![Op::Insert]);
+            assert_eq!(
+                data_chunk.rows().map(Row::from).collect_vec(),
+                vec![row_nonnull![1i64, 2i64, 2i64]]
+            );
+        });
+
+        assert_matches!(hash_agg.next().await.unwrap().unwrap(), Message::Watermark(watermark) => {
+            assert_eq!(watermark, Watermark::new(0, DataType::Int64, ScalarImpl::Int64(2)));
+        });
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The late row is dropped, and only the window `2` is emitted.
+        assert_matches!(hash_agg.next().await.unwrap().unwrap(), Message::Chunk(chunk) => {
+            let (data_chunk, ops) = chunk.into_parts();
+            assert_eq!(ops, vec![Op::Insert]);
+            assert_eq!(
+                data_chunk.rows().map(Row::from).collect_vec(),
+                vec![row_nonnull![2i64, 2i64, 2i64]]
+            );
+        });
+    }
 }
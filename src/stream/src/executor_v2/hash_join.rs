@@ -0,0 +1,2620 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use prometheus::Histogram;
+use risingwave_common::array::{Array, ArrayRef, Op, Row, RowRef, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl, ToOwnedDatum};
+use risingwave_expr::expr::RowExpression;
+use risingwave_expr::vector_op::arithmetic_op::timestamp_interval_add;
+use risingwave_storage::{Keyspace, StateStore};
+
+use super::barrier_align::{AlignedMessage, BarrierAligner};
+use super::error::{StreamExecutorError, StreamExecutorResult, TracedStreamExecutorError};
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef, Watermark,
+};
+use crate::common::StreamChunkBuilder;
+use crate::executor::managed_state::join::*;
+use crate::task::{ActorId, MemoryUsageReporter};
+
+/// The `JoinType` and `SideType` are to mimic a enum, because currently
+/// enum is not supported in const generic.
+// TODO: Use enum to replace this once [feature(adt_const_params)](https://github.com/rust-lang/rust/issues/95174) get completed.
+pub type JoinTypePrimitive = u8;
+#[allow(non_snake_case, non_upper_case_globals)]
+pub mod JoinType {
+    use super::JoinTypePrimitive;
+    pub const Inner: JoinTypePrimitive = 0;
+    pub const LeftOuter: JoinTypePrimitive = 1;
+    pub const RightOuter: JoinTypePrimitive = 2;
+    pub const FullOuter: JoinTypePrimitive = 3;
+}
+
+type SideTypePrimitive = u8;
+#[allow(non_snake_case, non_upper_case_globals)]
+mod SideType {
+    use super::SideTypePrimitive;
+    pub const Left: SideTypePrimitive = 0;
+    pub const Right: SideTypePrimitive = 1;
+}
+
+const JOIN_LEFT_PATH: u8 = b'l';
+const JOIN_RIGHT_PATH: u8 = b'r';
+
+const fn outer_side_keep(join_type: JoinTypePrimitive, side_type: SideTypePrimitive) -> bool {
+    join_type == JoinType::FullOuter
+        || (join_type == JoinType::LeftOuter && side_type == SideType::Left)
+        || (join_type == JoinType::RightOuter && side_type == SideType::Right)
+}
+
+const fn outer_side_null(join_type: JoinTypePrimitive, side_type: SideTypePrimitive) -> bool {
+    join_type == JoinType::FullOuter
+        || (join_type == JoinType::LeftOuter && side_type == SideType::Right)
+        || (join_type == JoinType::RightOuter && side_type == SideType::Left)
+}
+
+/// The time bounds of an interval join, i.e. the join condition implies
+/// `left[left_time_col] BETWEEN right[right_time_col] + lower_bound AND right[right_time_col] +
+/// upper_bound`. With the bounds, rows can be cleaned from the states once the watermark of the
+/// other side has passed.
+#[derive(Clone, Debug)]
+pub struct IntervalJoinBound {
+    pub left_time_col: usize,
+    pub right_time_col: usize,
+    pub lower_bound: IntervalUnit,
+    pub upper_bound: IntervalUnit,
+}
+
+/// Returns `val + interval` for a watermark on a time column.
+fn add_interval(val: &ScalarImpl, interval: IntervalUnit) -> Result<ScalarImpl> {
+    match val {
+        ScalarImpl::NaiveDateTime(ts) => Ok(ScalarImpl::NaiveDateTime(timestamp_interval_add::<
+            (),
+            (),
+            (),
+        >(*ts, interval)?)),
+        _ => Err(InternalError(format!("cannot add interval to {:?}", val)).into()),
+    }
+}
+
+pub struct JoinParams {
+    /// Indices of the join columns
+    key_indices: Vec<usize>,
+}
+
+impl JoinParams {
+    pub fn new(key_indices: Vec<usize>) -> Self {
+        Self { key_indices }
+    }
+}
+
+struct JoinSide<S: StateStore> {
+    /// Store all data from a one side stream
+    ht: JoinHashMap<S>,
+    /// Indices of the join key columns
+    key_indices: Vec<usize>,
+    /// The primary key indices of this side, used for state store
+    pk_indices: Vec<usize>,
+    /// The date type of each columns to join on
+    col_types: Vec<DataType>,
+    /// The start position for the side in output new columns
+    start_pos: usize,
+    /// The join side operates on this keyspace.
+    keyspace: Keyspace<S>,
+    /// The latest watermark received on each join key column of this side.
+    watermarks: Vec<Option<ScalarImpl>>,
+    /// The time column of an interval join, which the rows of each join key are ordered by.
+    time_col: Option<usize>,
+    /// The latest watermark received on the time column.
+    time_watermark: Option<ScalarImpl>,
+    /// The threshold that the rows of this side were last cleaned by in an interval join.
+    cleaned_time_threshold: Option<ScalarImpl>,
+}
+
+impl<S: StateStore> std::fmt::Debug for JoinSide<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JoinSide")
+            .field("key_indices", &self.key_indices)
+            .field("pk_indices", &self.pk_indices)
+            .field("col_types", &self.col_types)
+            .field("start_pos", &self.start_pos)
+            .finish()
+    }
+}
+
+impl<S: StateStore> JoinSide<S> {
+    /// Records the watermark if it is on the time column or one of the join key columns.
+    fn update_watermark(&mut self, watermark: Watermark) {
+        if self.time_col == Some(watermark.col_idx) {
+            self.time_watermark = Some(watermark.val.clone());
+        }
+        if let Some(pos) = self
+            .key_indices
+            .iter()
+            .position(|&idx| idx == watermark.col_idx)
+        {
+            self.watermarks[pos] = Some(watermark.val);
+        }
+    }
+
+    /// Prepends the time column to the pk of the states, so that the rows of each join key are
+    /// ordered by time and can be cleaned by the watermark.
+    fn set_time_col(&mut self, time_col: usize) {
+        self.pk_indices.insert(0, time_col);
+        self.ht = JoinHashMap::new(
+            self.ht.cache_capacity(),
+            self.pk_indices.clone(),
+            self.col_types.clone(),
+            self.keyspace.clone(),
+        );
+        self.time_col = Some(time_col);
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.ht.values().any(|state| state.is_dirty())
+    }
+
+    fn clear_cache(&mut self) {
+        assert!(
+            !self.is_dirty(),
+            "cannot clear cache while states of hash join are dirty"
+        );
+
+        // TODO: not working with rearranged chain
+        // self.ht.clear();
+    }
+}
+
+/// `HashJoinExecutor` takes two input streams and runs equal hash join on them.
+/// The output columns are the concatenation of left and right columns.
+pub struct HashJoinExecutor<S: StateStore, const T: JoinTypePrimitive> {
+    /// Left input executor.
+    input_l: Option<BoxedExecutor>,
+    /// Right input executor.
+    input_r: Option<BoxedExecutor>,
+    /// the data types of the formed new columns
+    output_data_types: Vec<DataType>,
+    /// The schema of the hash join executor
+    schema: Schema,
+    /// The primary key indices of the schema
+    pk_indices: PkIndices,
+    /// The parameters of the left join executor
+    side_l: JoinSide<S>,
+    /// The parameters of the right join executor
+    side_r: JoinSide<S>,
+    /// Optional non-equi join conditions
+    cond: Option<RowExpression>,
+    /// The time bounds if this is an interval join
+    interval_bound: Option<IntervalJoinBound>,
+    /// The watermark on each join key column that the states were last cleaned by. The rows
+    /// falling behind it arrive late, and are dropped since the rows they may match are cleaned.
+    cleaned_watermarks: Vec<Option<ScalarImpl>>,
+    /// Identity string
+    identity: String,
+    /// Id of the actor this executor belongs to
+    actor_id: ActorId,
+
+    /// Reports the memory used by the join states, if set.
+    memory_reporter: Option<MemoryUsageReporter>,
+
+    /// Observes the time waiting for the barrier from the other side, if set.
+    barrier_align_duration: Option<Histogram>,
+
+    #[allow(dead_code)]
+    /// Indices of the columns on which key distribution depends.
+    key_indices: Vec<usize>,
+}
+
+impl<S: StateStore, const T: JoinTypePrimitive> std::fmt::Debug for HashJoinExecutor<S, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HashJoinExecutor")
+            .field("join_type", &T)
+            .field("side_l", &self.side_l)
+            .field("side_r", &self.side_r)
+            .field("pk_indices", &self.pk_indices)
+            .field("schema", &self.schema)
+            .field("output_data_types", &self.output_data_types)
+            .finish()
+    }
+}
+
+impl<S: StateStore, const T: JoinTypePrimitive> Executor for HashJoinExecutor<S, T> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    fn clear_cache(&mut self) -> Result<()> {
+        self.side_l.clear_cache();
+        self.side_r.clear_cache();
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+impl<S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<S, T> {
+    pub fn new(
+        input_l: BoxedExecutor,
+        input_r: BoxedExecutor,
+        params_l: JoinParams,
+        params_r: JoinParams,
+        pk_indices: PkIndices,
+        keyspace: Keyspace<S>,
+        executor_id: u64,
+        actor_id: ActorId,
+        cond: Option<RowExpression>,
+        key_indices: Vec<usize>,
+        cache_capacity: usize,
+    ) -> Self {
+        let new_column_n = input_l.schema().len() + input_r.schema().len();
+        let side_l_column_n = input_l.schema().len();
+
+        let schema_fields = [
+            input_l.schema().fields.clone(),
+            input_r.schema().fields.clone(),
+        ]
+        .concat();
+
+        assert_eq!(schema_fields.len(), new_column_n);
+
+        let output_data_types = schema_fields
+            .iter()
+            .map(|field| field.data_type.clone())
+            .collect();
+        let col_l_datatypes = input_l
+            .schema()
+            .fields
+            .iter()
+            .map(|field| field.data_type.clone())
+            .collect_vec();
+        let col_r_datatypes = input_r
+            .schema()
+            .fields
+            .iter()
+            .map(|field| field.data_type.clone())
+            .collect_vec();
+        let pk_indices_l = input_l.pk_indices().to_vec();
+        let pk_indices_r = input_r.pk_indices().to_vec();
+
+        let cleaned_watermarks = vec![None; params_l.key_indices.len()];
+        let ks_l = keyspace.append_u8(JOIN_LEFT_PATH);
+        let ks_r = keyspace.append_u8(JOIN_RIGHT_PATH);
+        Self {
+            input_l: Some(input_l),
+            input_r: Some(input_r),
+            output_data_types,
+            schema: Schema {
+                fields: schema_fields,
+            },
+            // Each side takes half of the cache capacity.
+            side_l: JoinSide {
+                ht: JoinHashMap::new(
+                    cache_capacity / 2,
+                    pk_indices_l.clone(),
+                    col_l_datatypes.clone(),
+                    ks_l.clone(),
+                ),
+                watermarks: vec![None; params_l.key_indices.len()],
+                time_col: None,
+                time_watermark: None,
+                cleaned_time_threshold: None,
+                key_indices: params_l.key_indices,
+                col_types: col_l_datatypes,
+                pk_indices: pk_indices_l,
+                start_pos: 0,
+                keyspace: ks_l,
+            },
+            side_r: JoinSide {
+                ht: JoinHashMap::new(
+                    cache_capacity / 2,
+                    pk_indices_r.clone(),
+                    col_r_datatypes.clone(),
+                    ks_r.clone(),
+                ),
+                watermarks: vec![None; params_r.key_indices.len()],
+                time_col: None,
+                time_watermark: None,
+                cleaned_time_threshold: None,
+                key_indices: params_r.key_indices,
+                col_types: col_r_datatypes,
+                pk_indices: pk_indices_r,
+                start_pos: side_l_column_n,
+                keyspace: ks_r,
+            },
+            pk_indices,
+            cond,
+            interval_bound: None,
+            cleaned_watermarks,
+            identity: format!("HashJoinExecutor {:X}", executor_id),
+            actor_id,
+            key_indices,
+            memory_reporter: None,
+            barrier_align_duration: None,
+        }
+    }
+
+    /// Reports the memory used by the join states to the memory manager, which may ask the
+    /// executor to evict the states on barriers.
+    pub fn with_memory_reporter(mut self, memory_reporter: MemoryUsageReporter) -> Self {
+        self.memory_reporter = Some(memory_reporter);
+        self
+    }
+
+    /// Reports the time waiting for the barrier from the other side on each barrier.
+    pub fn with_barrier_align_duration(mut self, barrier_align_duration: Histogram) -> Self {
+        self.barrier_align_duration = Some(barrier_align_duration);
+        self
+    }
+
+    /// Makes this an interval join. Only inner join is supported, since the rows cleaned from the
+    /// states are never emitted with nulls.
+    pub fn with_interval_bound(mut self, interval_bound: IntervalJoinBound) -> Self {
+        assert_eq!(T, JoinType::Inner, "interval join only supports inner join");
+        self.side_l.set_time_col(interval_bound.left_time_col);
+        self.side_r.set_time_col(interval_bound.right_time_col);
+        self.interval_bound = Some(interval_bound);
+        self
+    }
+
+    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
+    async fn execute_inner(mut self: Box<Self>) {
+        let mut aligner =
+            BarrierAligner::new(self.input_l.take().unwrap(), self.input_r.take().unwrap())
+                .with_align_duration(self.barrier_align_duration.take());
+
+        let barrier = match aligner.next().await {
+            AlignedMessage::Barrier(barrier) => barrier,
+            _ => panic!("the first message received by hash join executor must be a barrier"),
+        };
+        let mut epoch = barrier.epoch.curr;
+        self.side_l.ht.update_epoch(epoch);
+        self.side_r.ht.update_epoch(epoch);
+        yield Message::Barrier(barrier);
+
+        loop {
+            match aligner.next().await {
+                AlignedMessage::Left(chunk) => {
+                    let chunk = self
+                        .eq_join_oneside::<{ SideType::Left }>(chunk?, epoch)
+                        .await
+                        .map_err(StreamExecutorError::hash_join_error)?;
+                    yield Message::Chunk(chunk);
+                }
+                AlignedMessage::Right(chunk) => {
+                    let chunk = self
+                        .eq_join_oneside::<{ SideType::Right }>(chunk?, epoch)
+                        .await
+                        .map_err(StreamExecutorError::hash_join_error)?;
+                    yield Message::Chunk(chunk);
+                }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    self.side_l.update_watermark(watermark);
+                }
+                AlignedMessage::WatermarkRight(watermark) => {
+                    self.side_r.update_watermark(watermark);
+                }
+                AlignedMessage::Barrier(barrier) => {
+                    self.flush_data(epoch).await?;
+                    self.clean_states_by_watermark(epoch).await?;
+                    self.clean_states_by_interval(epoch).await?;
+
+                    // The states of the join keys moved in were written by other actors, so the
+                    // cached ones may be stale. They'll be read from the shared keyspace once the
+                    // previous epoch is committed.
+                    let vnode_changed = barrier.is_vnode_changed(self.actor_id);
+                    if vnode_changed {
+                        self.side_l.ht.evict_to(0);
+                        self.side_r.ht.evict_to(0);
+                        self.side_l.ht.clear_state_keys();
+                        self.side_r.ht.clear_state_keys();
+                    }
+
+                    epoch = barrier.epoch.curr;
+                    let prev_epoch = barrier.epoch.prev;
+                    self.side_l.ht.update_epoch(epoch);
+                    self.side_r.ht.update_epoch(epoch);
+                    yield Message::Barrier(barrier);
+
+                    if vnode_changed {
+                        self.side_l
+                            .keyspace
+                            .state_store()
+                            .wait_epoch(prev_epoch)
+                            .await
+                            .map_err(StreamExecutorError::storage)?;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        for side in [&mut self.side_l, &mut self.side_r] {
+            let mut write_batch = side.keyspace.state_store().start_write_batch();
+            for state in side.ht.values_mut() {
+                state
+                    .flush(&mut write_batch)
+                    .map_err(StreamExecutorError::hash_join_error)?;
+            }
+            write_batch
+                .ingest(epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+        }
+
+        // evict the LRU cache
+        assert!(!self.side_l.is_dirty());
+        self.side_l.ht.evict_to_capacity();
+        assert!(!self.side_r.is_dirty());
+        self.side_r.ht.evict_to_capacity();
+
+        if let Some(memory_reporter) = &self.memory_reporter {
+            let size_l = self.side_l.ht.estimated_size();
+            let size_r = self.side_r.ht.estimated_size();
+            if let Some(target) = memory_reporter.report(size_l + size_r) {
+                // Evict both sides proportionally.
+                let ratio = target as f64 / (size_l + size_r) as f64;
+                self.side_l.ht.evict_to((size_l as f64 * ratio) as usize);
+                self.side_r.ht.evict_to((size_r as f64 * ratio) as usize);
+                memory_reporter
+                    .report(self.side_l.ht.estimated_size() + self.side_r.ht.estimated_size());
+            }
+        }
+        Ok(())
+    }
+
+    /// Once both sides have passed a watermark on the same join key column, rows with a smaller
+    /// key can never be matched again, so their states are removed. Only the watermarks advanced
+    /// since the last cleaning are applied.
+    async fn clean_states_by_watermark(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        let watermarks = self
+            .side_l
+            .watermarks
+            .iter()
+            .zip_eq(self.side_r.watermarks.iter())
+            .zip_eq(self.cleaned_watermarks.iter())
+            .enumerate()
+            .filter_map(|(pos, ((l, r), cleaned))| {
+                let watermark = l.as_ref()?.min(r.as_ref()?);
+                match cleaned {
+                    Some(cleaned) if cleaned >= watermark => None,
+                    _ => Some((pos, watermark.clone())),
+                }
+            })
+            .collect_vec();
+        if watermarks.is_empty() {
+            return Ok(());
+        }
+
+        for side in [&mut self.side_l, &mut self.side_r] {
+            let mut write_batch = side.keyspace.state_store().start_write_batch();
+            for (pos, watermark) in &watermarks {
+                side.ht
+                    .clean_below_watermark(*pos, watermark, &mut write_batch)
+                    .await
+                    .map_err(StreamExecutorError::hash_join_error)?;
+            }
+            write_batch
+                .ingest(epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+        }
+        for (pos, watermark) in watermarks {
+            self.cleaned_watermarks[pos] = Some(watermark);
+        }
+        Ok(())
+    }
+
+    /// For interval joins, a left row `l` can only match the right rows in `[l - upper_bound, l -
+    /// lower_bound]`, and a right row `r` can only match the left rows in `[r + lower_bound, r +
+    /// upper_bound]`. So the rows falling behind the watermark of the other side are removed.
+    async fn clean_states_by_interval(&mut self, epoch: u64) -> StreamExecutorResult<()> {
+        let bound = match &self.interval_bound {
+            Some(bound) => bound,
+            None => return Ok(()),
+        };
+        let threshold_l = self
+            .side_r
+            .time_watermark
+            .as_ref()
+            .map(|watermark| add_interval(watermark, bound.lower_bound))
+            .transpose()
+            .map_err(StreamExecutorError::eval_error)?;
+        let threshold_r = self
+            .side_l
+            .time_watermark
+            .as_ref()
+            .map(|watermark| add_interval(watermark, bound.upper_bound.negative()))
+            .transpose()
+            .map_err(StreamExecutorError::eval_error)?;
+
+        for (side, threshold) in [
+            (&mut self.side_l, threshold_l),
+            (&mut self.side_r, threshold_r),
+        ] {
+            // Only clean the states when the threshold advances.
+            let threshold = match threshold {
+                Some(threshold)
+                    if side
+                        .cleaned_time_threshold
+                        .as_ref()
+                        .map_or(true, |cleaned| *cleaned < threshold) =>
+                {
+                    threshold
+                }
+                _ => continue,
+            };
+            let mut write_batch = side.keyspace.state_store().start_write_batch();
+            side.ht
+                .clean_rows_before(&threshold, &mut write_batch)
+                .await
+                .map_err(StreamExecutorError::hash_join_error)?;
+            write_batch
+                .ingest(epoch)
+                .await
+                .map_err(StreamExecutorError::storage)?;
+            side.cleaned_time_threshold = Some(threshold);
+        }
+        Ok(())
+    }
+
+    /// the data the hash table and match the coming
+    /// data chunk with the executor state
+    async fn hash_eq_match<'a>(
+        key: &Row,
+        ht: &'a mut JoinHashMap<S>,
+    ) -> Option<&'a mut HashValueType<S>> {
+        ht.get_mut(key).await
+    }
+
+    fn hash_key_from_row_ref(row: &RowRef, key_indices: &[usize]) -> HashKeyType {
+        let key = key_indices
+            .iter()
+            .map(|idx| row[*idx].to_owned_datum())
+            .collect_vec();
+        Row(key)
+    }
+
+    fn row_from_row_ref(row: &RowRef) -> Row {
+        let value = (0..row.size())
+            .map(|idx| row[idx].to_owned_datum())
+            .collect_vec();
+        Row(value)
+    }
+
+    fn pk_from_row_ref(row: &RowRef, pk_indices: &[usize]) -> Row {
+        row.row_by_slice(pk_indices)
+    }
+
+    fn row_concat(
+        row_update: &RowRef<'_>,
+        update_start_pos: usize,
+        row_matched: &Row,
+        matched_start_pos: usize,
+    ) -> Row {
+        let mut new_row = vec![None; row_update.size() + row_matched.size()];
+        for i in 0..row_update.size() {
+            new_row[i + update_start_pos] = row_update[i].to_owned_datum();
+        }
+        for i in 0..row_matched.size() {
+            new_row[i + matched_start_pos] = row_matched[i].clone();
+        }
+        Row(new_row)
+    }
+
+    fn bool_from_array_ref(array_ref: ArrayRef) -> bool {
+        let bool_array = array_ref.as_ref().as_bool();
+        bool_array.value_at(0).unwrap_or_else(|| {
+            panic!(
+                "Some thing wrong with the expression result. Bool array: {:?}",
+                bool_array
+            )
+        })
+    }
+
+    async fn eq_join_oneside<const SIDE: SideTypePrimitive>(
+        &mut self,
+        chunk: StreamChunk,
+        epoch: u64,
+    ) -> Result<StreamChunk> {
+        let chunk = chunk.compact()?;
+        let (data_chunk, ops) = chunk.into_parts();
+
+        let (side_update, side_match) = if SIDE == SideType::Left {
+            (&mut self.side_l, &mut self.side_r)
+        } else {
+            (&mut self.side_r, &mut self.side_l)
+        };
+
+        // TODO: find a better capacity number, the actual array length
+        // is likely to be larger than the current capacity
+        let capacity = data_chunk.capacity();
+
+        let mut stream_chunk_builder = StreamChunkBuilder::new(
+            capacity,
+            &self.output_data_types,
+            side_update.start_pos,
+            side_match.start_pos,
+        )?;
+
+        for (row, op) in data_chunk.rows().zip_eq(ops.iter()) {
+            // Rows with null time never match in an interval join, and can't be ordered by time.
+            if let Some(time_col) = side_update.time_col && row[time_col].is_none() {
+                continue;
+            }
+            // Rows behind the cleaned watermarks arrive late, and their matches have been cleaned.
+            let late = side_update
+                .key_indices
+                .iter()
+                .zip_eq(self.cleaned_watermarks.iter())
+                .any(|(&idx, watermark)| {
+                    watermark.as_ref().map_or(false, |watermark| {
+                        matches!(row.value_at(idx).to_owned_datum(), Some(v) if v < *watermark)
+                    })
+                });
+            if late {
+                continue;
+            }
+            let key = Self::hash_key_from_row_ref(&row, &side_update.key_indices);
+            let value = Self::row_from_row_ref(&row);
+            let pk = Self::pk_from_row_ref(&row, &side_update.pk_indices);
+            let matched_rows = Self::hash_eq_match(&key, &mut side_match.ht).await;
+            if let Some(matched_rows) = matched_rows {
+                let update_start_pos = side_update.start_pos;
+                let match_start_pos = side_match.start_pos;
+                let cond = &mut self.cond;
+                let output_data_types = &self.output_data_types;
+                match *op {
+                    Op::Insert | Op::UpdateInsert => {
+                        let entry_value = side_update.ht.get_or_init_without_cache(&key).await?;
+                        let mut degree = 0;
+                        matched_rows
+                            .for_each_mut(epoch, |matched_row| {
+                                // TODO(yuhao-su): We should find a better way to eval the
+                                // expression without concat
+                                // two rows.
+                                let new_row = Self::row_concat(
+                                    &row,
+                                    update_start_pos,
+                                    &matched_row.row,
+                                    match_start_pos,
+                                );
+                                let mut cond_match = true;
+                                // if there are non-equi expressions
+                                if let Some(cond) = cond.as_mut() {
+                                    cond_match = Self::bool_from_array_ref(
+                                        cond.eval(&new_row, output_data_types)?,
+                                    );
+                                }
+                                if cond_match {
+                                    degree += 1;
+                                    if matched_row.is_zero_degree() && outer_side_null(T, SIDE) {
+                                        // if the matched_row does not have any current matches
+                                        stream_chunk_builder.append_row_matched(
+                                            Op::UpdateDelete,
+                                            &matched_row.row,
+                                        )?;
+                                        stream_chunk_builder.append_row(
+                                            Op::UpdateInsert,
+                                            &row,
+                                            &matched_row.row,
+                                        )?;
+                                    } else {
+                                        // concat with the matched_row and append the new row
+                                        stream_chunk_builder.append_row(
+                                            *op,
+                                            &row,
+                                            &matched_row.row,
+                                        )?;
+                                    }
+                                    matched_row.inc_degree();
+                                } else {
+                                    // not matched
+                                    if outer_side_keep(T, SIDE) {
+                                        stream_chunk_builder.append_row_update(*op, &row)?;
+                                    }
+                                }
+                                Ok(())
+                            })
+                            .await?;
+                        entry_value.insert(pk, JoinRow::new(value, degree));
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        if let Some(v) = side_update.ht.get_mut_without_cached(&key).await {
+                            // remove the row by it's primary key
+                            v.remove(pk);
+
+                            matched_rows
+                                .for_each_mut(epoch, |matched_row| {
+                                    let new_row = Self::row_concat(
+                                        &row,
+                                        update_start_pos,
+                                        &matched_row.row,
+                                        match_start_pos,
+                                    );
+
+                                    let mut cond_match = true;
+                                    // if there are non-equi expressions
+                                    if let Some(cond) = cond.as_mut() {
+                                        cond_match = Self::bool_from_array_ref(
+                                            cond.eval(&new_row, output_data_types)?,
+                                        );
+                                    }
+                                    if cond_match {
+                                        if matched_row.is_zero_degree() && outer_side_null(T, SIDE)
+                                        {
+                                            // if the matched_row does not have any current matches
+                                            stream_chunk_builder.append_row(
+                                                Op::UpdateDelete,
+                                                &row,
+                                                &matched_row.row,
+                                            )?;
+                                            stream_chunk_builder.append_row_matched(
+                                                Op::UpdateInsert,
+                                                &matched_row.row,
+                                            )?;
+                                        } else {
+                                            // concat with the matched_row and append the new row
+                                            stream_chunk_builder.append_row(
+                                                *op,
+                                                &row,
+                                                &matched_row.row,
+                                            )?;
+                                        }
+                                        matched_row.dec_degree();
+                                    } else {
+                                        // not matched
+                                        if outer_side_keep(T, SIDE) {
+                                            stream_chunk_builder.append_row_update(*op, &row)?;
+                                        }
+                                    }
+                                    Ok(())
+                                })
+                                .await?;
+                        }
+                    }
+                };
+            } else {
+                // if there are no matched rows, just update the hash table
+                //
+                // FIXME: matched rows can still be there but just evicted from the memory cache, we
+                // should handle this!
+                match *op {
+                    Op::Insert | Op::UpdateInsert => {
+                        let state = side_update.ht.get_or_init_without_cache(&key).await?;
+                        state.insert(pk, JoinRow::new(value, 0));
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        if let Some(v) = side_update.ht.get_mut_without_cached(&key).await {
+                            v.remove(pk);
+                        }
+                    }
+                };
+                // if it's outer join and the side needs maintained.
+                if outer_side_keep(T, SIDE) {
+                    stream_chunk_builder.append_row_update(*op, &row)?;
+                }
+            }
+        }
+
+        stream_chunk_builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use itertools::Itertools;
+    use risingwave_common::array::*;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::column_nonnull;
+    use risingwave_common::types::NaiveDateTimeWrapper;
+    use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
+    use risingwave_expr::expr::{InputRefExpression, RowExpression};
+    use risingwave_pb::expr::expr_node::Type;
+    use risingwave_storage::storage_value::StorageValue;
+
+    use super::{HashJoinExecutor, JoinParams, JoinType, *};
+    use crate::executor::{Barrier, Epoch};
+    use crate::executor_v2::test_utils::{create_in_memory_keyspace, MockSource};
+
+    fn create_cond() -> Option<RowExpression> {
+        let left_expr = InputRefExpression::new(DataType::Int64, 1);
+        let right_expr = InputRefExpression::new(DataType::Int64, 3);
+        let cond = new_binary_expr(
+            Type::LessThan,
+            DataType::Boolean,
+            Box::new(left_expr),
+            Box::new(right_expr),
+        );
+        Some(RowExpression::new(cond))
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join() {
+        let chunk_l1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 3] },
+                column_nonnull! { I64Array, [4, 5, 6] },
+            ],
+            None,
+        );
+        let chunk_l2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [3, 3] },
+                column_nonnull! { I64Array, [8, 8] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let chunk_r1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 6] },
+                column_nonnull! { I64Array, [7, 8, 9] },
+            ],
+            None,
+        );
+        let chunk_r2 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [3, 6] },
+                column_nonnull! { I64Array, [10, 11] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        let keyspace = create_in_memory_keyspace();
+
+        let params_l = JoinParams::new(vec![0]);
+        let params_r = JoinParams::new(vec![0]);
+
+        let hash_join = HashJoinExecutor::<_, { JoinType::Inner }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            params_l,
+            params_r,
+            vec![],
+            keyspace,
+            1,
+            0,
+            None,
+            vec![],
+            1 << 20,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops().len(), 0);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops().len(), 0);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(7)]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(10)]
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join_with_barrier() {
+        test_streaming_hash_inner_join_with_barrier_impl(1 << 20).await;
+    }
+
+    /// The join keys exceed the cache limit, so their rows are paged from the state store.
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join_with_barrier_without_cache() {
+        test_streaming_hash_inner_join_with_barrier_impl(0).await;
+    }
+
+    async fn test_streaming_hash_inner_join_with_barrier_impl(cache_capacity: usize) {
+        let chunk_l1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 3] },
+                column_nonnull! { I64Array, [4, 5, 6] },
+            ],
+            None,
+        );
+        let chunk_l2 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [6, 3] },
+                column_nonnull! { I64Array, [8, 8] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let chunk_r1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 6] },
+                column_nonnull! { I64Array, [7, 8, 9] },
+            ],
+            None,
+        );
+        let chunk_r2 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [3, 6] },
+                column_nonnull! { I64Array, [10, 11] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        let keyspace = create_in_memory_keyspace();
+
+        let params_l = JoinParams::new(vec![0]);
+        let params_r = JoinParams::new(vec![0]);
+
+        let hash_join = HashJoinExecutor::<_, { JoinType::Inner }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            params_l,
+            params_r,
+            vec![],
+            keyspace,
+            1,
+            0,
+            None,
+            vec![],
+            cache_capacity,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops().len(), 0);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push a barrier to left side
+        tx_l.push_barrier(2, false);
+
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+
+        // join the first right chunk
+        tx_r.push_chunk(chunk_r1);
+
+        // Consume stream chunk
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(7)]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push a barrier to right side
+        tx_r.push_barrier(2, false);
+
+        // get the aligned barrier here
+        let expected_epoch = Epoch::new_test_epoch(2);
+        assert!(matches!(
+            hash_join.next().await.unwrap().unwrap(),
+            Message::Barrier(Barrier {
+                epoch,
+                mutation: None,
+                ..
+            }) if epoch == expected_epoch
+        ));
+
+        // join the 2nd left chunk
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(8)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(9)]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Insert, Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3), Some(3), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(6), Some(8), Some(8)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3), Some(3), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(10), Some(10), Some(11)]
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_left_join() {
+        let chunk_l1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 3] },
+                column_nonnull! { I64Array, [4, 5, 6] },
+            ],
+            None,
+        );
+        let chunk_l2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [3, 3] },
+                column_nonnull! { I64Array, [8, 8] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let chunk_r1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 6] },
+                column_nonnull! { I64Array, [7, 8, 9] },
+            ],
+            None,
+        );
+        let chunk_r2 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [3, 6] },
+                column_nonnull! { I64Array, [10, 11] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        let keyspace = create_in_memory_keyspace();
+
+        let params_l = JoinParams::new(vec![0]);
+        let params_r = JoinParams::new(vec![0]);
+
+        let hash_join = HashJoinExecutor::<_, { JoinType::LeftOuter }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            params_l,
+            params_r,
+            vec![],
+            keyspace,
+            1,
+            0,
+            None,
+            vec![],
+            1 << 20,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Insert, Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(1), Some(2), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(4), Some(5), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None, None]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Delete]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(8), Some(8)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::UpdateDelete, Op::UpdateInsert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2), Some(2)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5), Some(5)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(2)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(7)]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::UpdateDelete, Op::UpdateInsert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(6), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(10)]
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_right_join() {
+        let chunk_l1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 3] },
+                column_nonnull! { I64Array, [4, 5, 6] },
+            ],
+            None,
+        );
+        let chunk_l2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [3, 3] },
+                column_nonnull! { I64Array, [8, 8] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let chunk_r1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 6] },
+                column_nonnull! { I64Array, [7, 8, 9] },
+            ],
+            None,
+        );
+        let chunk_r2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [5, 5] },
+                column_nonnull! { I64Array, [10, 10] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        let keyspace = create_in_memory_keyspace();
+
+        let params_l = JoinParams::new(vec![0]);
+        let params_r = JoinParams::new(vec![0]);
+
+        let hash_join = HashJoinExecutor::<_, { JoinType::RightOuter }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            params_l,
+            params_r,
+            vec![],
+            keyspace,
+            1,
+            0,
+            None,
+            vec![],
+            1 << 20,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops().len(), 0);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops().len(), 0);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Insert, Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2), None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5), None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2), Some(4), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(7), Some(8), Some(9)]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Delete]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5), Some(5)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(10), Some(10)]
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_full_outer_join() {
+        let chunk_l1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 3] },
+                column_nonnull! { I64Array, [4, 5, 6] },
+            ],
+            None,
+        );
+        let chunk_l2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [3, 3] },
+                column_nonnull! { I64Array, [8, 8] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let chunk_r1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 6] },
+                column_nonnull! { I64Array, [7, 8, 9] },
+            ],
+            None,
+        );
+        let chunk_r2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [5, 5] },
+                column_nonnull! { I64Array, [10, 10] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        let keyspace = create_in_memory_keyspace();
+
+        let params_l = JoinParams::new(vec![0]);
+        let params_r = JoinParams::new(vec![0]);
+
+        let hash_join = HashJoinExecutor::<_, { JoinType::FullOuter }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            params_l,
+            params_r,
+            vec![],
+            keyspace,
+            1,
+            0,
+            None,
+            vec![],
+            1 << 20,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Insert, Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(1), Some(2), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(4), Some(5), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None, None]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Delete]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(8), Some(8)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(
+                chunk.ops(),
+                vec![Op::UpdateDelete, Op::UpdateInsert, Op::Insert, Op::Insert]
+            );
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2), Some(2), None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5), Some(5), None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(2), Some(4), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(7), Some(8), Some(9)]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Delete]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5), Some(5)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(10), Some(10)]
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_full_outer_join_with_nonequi_condition() {
+        let chunk_l1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 3] },
+                column_nonnull! { I64Array, [4, 5, 6] },
+            ],
+            None,
+        );
+        let chunk_l2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [3, 3] },
+                column_nonnull! { I64Array, [8, 8] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let chunk_r1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 3] },
+                column_nonnull! { I64Array, [6, 8, 4] },
+            ],
+            None,
+        );
+        let chunk_r2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [5, 5] },
+                column_nonnull! { I64Array, [10, 10] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        let keyspace = create_in_memory_keyspace();
+
+        let cond = create_cond();
+
+        let params_l = JoinParams::new(vec![0]);
+        let params_r = JoinParams::new(vec![0]);
+
+        let hash_join = HashJoinExecutor::<_, { JoinType::FullOuter }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            params_l,
+            params_r,
+            vec![],
+            keyspace,
+            1,
+            0,
+            cond,
+            vec![],
+            1 << 20,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Insert, Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(1), Some(2), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(4), Some(5), Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None, None]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Delete]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(8), Some(8)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(
+                chunk.ops(),
+                vec![Op::UpdateDelete, Op::UpdateInsert, Op::Insert, Op::Insert]
+            );
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(2), Some(2), None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5), Some(5), None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(2), Some(4), Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, Some(6), Some(8), Some(4)]
+            );
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert, Op::Delete]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![None, None]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(5), Some(5)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(10), Some(10)]
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join_with_nonequi_condition() {
+        let chunk_l1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [1, 2, 3] },
+                column_nonnull! { I64Array, [4, 10, 6] },
+            ],
+            None,
+        );
+        let chunk_l2 = StreamChunk::new(
+            vec![Op::Insert, Op::Delete],
+            vec![
+                column_nonnull! { I64Array, [3, 3] },
+                column_nonnull! { I64Array, [8, 8] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let chunk_r1 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [2, 4, 6] },
+                column_nonnull! { I64Array, [7, 8, 9] },
+            ],
+            None,
+        );
+        let chunk_r2 = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I64Array, [3, 6] },
+                column_nonnull! { I64Array, [10, 11] },
+            ],
+            Some((vec![true, true]).try_into().unwrap()),
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        let keyspace = create_in_memory_keyspace();
+
+        let params_l = JoinParams::new(vec![0]);
+        let params_r = JoinParams::new(vec![0]);
+
+        let cond = create_cond();
+
+        let hash_join = HashJoinExecutor::<_, { JoinType::Inner }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            params_l,
+            params_r,
+            vec![],
+            keyspace,
+            1,
+            0,
+            cond,
+            vec![],
+            1 << 20,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops().len(), 0);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops().len(), 0);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![]);
+            assert_eq!(chunk.columns().len(), 4);
+            for i in 0..4 {
+                assert_eq!(
+                    chunk
+                        .column_at(i)
+                        .array_ref()
+                        .as_int64()
+                        .iter()
+                        .collect_vec(),
+                    vec![]
+                );
+            }
+        } else {
+            unreachable!();
+        }
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        if let Message::Chunk(chunk) = hash_join.next().await.unwrap().unwrap() {
+            assert_eq!(chunk.ops(), vec![Op::Insert]);
+            assert_eq!(chunk.columns().len(), 4);
+            assert_eq!(
+                chunk
+                    .column_at(0)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(1)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(6)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(2)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(3)]
+            );
+            assert_eq!(
+                chunk
+                    .column_at(3)
+                    .array_ref()
+                    .as_int64()
+                    .iter()
+                    .collect_vec(),
+                vec![Some(10)]
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_interval_join_clean_by_watermark() {
+        let t = |minutes| {
+            let date = NaiveDate::from_ymd(2022, 2, 2);
+            let time = NaiveTime::from_hms(10, minutes, 0);
+            ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper(NaiveDateTime::new(date, time)))
+        };
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Timestamp),
+            ],
+        };
+        let chunk = |minutes: &[u32]| {
+            let rows = minutes
+                .iter()
+                .map(|m| {
+                    (
+                        Op::Insert,
+                        Row(vec![Some(ScalarImpl::Int64(1)), Some(t(*m))]),
+                    )
+                })
+                .collect_vec();
+            StreamChunk::from_rows(&rows, &schema.data_types()).unwrap()
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+
+        // A left row of join key 2 written by another actor sharing the keyspace, which should not
+        // be cleaned by this executor.
+        let keyspace = create_in_memory_keyspace();
+        let ks_l = keyspace.append_u8(JOIN_LEFT_PATH);
+        let other_row = Row(vec![Some(ScalarImpl::Int64(2)), Some(t(0))]);
+        let other_pk = Row(vec![Some(t(0)), Some(ScalarImpl::Int64(2)), Some(t(0))]);
+        let mut write_batch = keyspace.state_store().start_write_batch();
+        write_batch.prefixify(&ks_l).put(
+            [
+                Row(other_row.0[..1].to_vec()).serialize().unwrap(),
+                other_pk.serialize_not_null().unwrap(),
+            ]
+            .concat(),
+            StorageValue::new_default_put(JoinRow::new(other_row, 0).serialize().unwrap()),
+        );
+        write_batch.ingest(1).await.unwrap();
+
+        // `l.ts BETWEEN r.ts - INTERVAL '5' MINUTE AND r.ts`
+        let hash_join = HashJoinExecutor::<_, { JoinType::Inner }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            JoinParams::new(vec![0]),
+            JoinParams::new(vec![0]),
+            vec![],
+            keyspace.clone(),
+            1,
+            0,
+            None,
+            vec![],
+            1 << 20,
+        )
+        .with_interval_bound(IntervalJoinBound {
+            left_time_col: 1,
+            right_time_col: 1,
+            lower_bound: IntervalUnit::from_minutes(-5),
+            upper_bound: IntervalUnit::from_minutes(0),
+        });
+        let mut hash_join = Box::new(hash_join).execute();
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(chunk(&[0, 8]));
+        assert_matches!(hash_join.next().await.unwrap().unwrap(), Message::Chunk(chunk) => {
+            assert_eq!(chunk.cardinality(), 0);
+        });
+
+        // The left row at 10:00 can't be matched by any right rows after 10:10.
+        tx_r.push_watermark(Watermark::new(1, DataType::Timestamp, t(10)));
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert_matches!(
+            hash_join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+
+        // Only the left row at 10:00 of join key 1 is removed from the state store.
+        assert_eq!(ks_l.scan(None, u64::MAX).await.unwrap().len(), 2);
+
+        tx_r.push_chunk(chunk(&[12]));
+        assert_matches!(hash_join.next().await.unwrap().unwrap(), Message::Chunk(chunk) => {
+            assert_eq!(chunk.cardinality(), 1);
+            let row = chunk.rows().next().unwrap();
+            assert_eq!(row.value_at(1).to_owned_datum(), Some(t(8)));
+            assert_eq!(row.value_at(3).to_owned_datum(), Some(t(12)));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_streaming_hash_join_clean_by_watermark() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let chunk = |keys: &[i64]| {
+            let rows = keys
+                .iter()
+                .map(|k| {
+                    (
+                        Op::Insert,
+                        Row(vec![
+                            Some(ScalarImpl::Int64(*k)),
+                            Some(ScalarImpl::Int64(0)),
+                        ]),
+                    )
+                })
+                .collect_vec();
+            StreamChunk::from_rows(&rows, &schema.data_types()).unwrap()
+        };
+
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema.clone(), vec![0, 1]);
+        let keyspace = create_in_memory_keyspace();
+        let ks_l = keyspace.append_u8(JOIN_LEFT_PATH);
+        let hash_join = HashJoinExecutor::<_, { JoinType::Inner }>::new(
+            Box::new(source_l),
+            Box::new(source_r),
+            JoinParams::new(vec![0]),
+            JoinParams::new(vec![0]),
+            vec![],
+            keyspace,
+            1,
+            0,
+            None,
+            vec![],
+            1 << 20,
+        );
+        let mut hash_join = Box::new(hash_join).execute();
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(chunk(&[1, 3]));
+        assert_matches!(hash_join.next().await.unwrap().unwrap(), Message::Chunk(chunk) => {
+            assert_eq!(chunk.cardinality(), 0);
+        });
+
+        // Both sides have passed key 2, so the left row of key 1 is cleaned.
+        tx_l.push_watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(2)));
+        tx_r.push_watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(2)));
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert_matches!(
+            hash_join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+        assert_eq!(ks_l.scan(None, u64::MAX).await.unwrap().len(), 1);
+
+        // The right row of key 1 arrives late and is dropped, while key 3 is still matched.
+        tx_r.push_chunk(chunk(&[1, 3]));
+        assert_matches!(hash_join.next().await.unwrap().unwrap(), Message::Chunk(chunk) => {
+            assert_eq!(chunk.cardinality(), 1);
+            let row = chunk.rows().next().unwrap();
+            assert_eq!(row.value_at(0).to_owned_datum(), Some(ScalarImpl::Int64(3)));
+        });
+    }
+}
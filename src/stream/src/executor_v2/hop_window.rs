@@ -18,16 +18,15 @@ use futures::StreamExt;
 use futures_async_stream::try_stream;
 use num_traits::CheckedSub;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::{DataChunk, StreamChunk};
+use risingwave_common::array::{DataChunk, Row, StreamChunk};
 use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
 use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
 use risingwave_expr::expr::{Expression, InputRefExpression, LiteralExpression};
 use risingwave_pb::expr::expr_node;
 
 use super::error::{StreamExecutorError, TracedStreamExecutorError};
-use super::{BoxedExecutor, Executor, ExecutorInfo, Message};
+use super::{BoxedExecutor, Executor, ExecutorInfo, Message, Watermark};
 
-#[allow(unused)]
 pub struct HopWindowExecutor {
     pub input: BoxedExecutor,
     pub info: ExecutorInfo,
@@ -129,9 +128,44 @@ impl HopWindowExecutor {
             window_slide_expr,
         );
 
+        // The window bounds are monotonic on the time column, so a watermark on the time column
+        // can be derived to `window_start` and `window_end`.
+        let input_data_types = schema.data_types()[..schema.len() - 2].to_vec();
+        let window_end_expr = new_binary_expr(
+            expr_node::Type::Add,
+            DataType::Timestamp,
+            InputRefExpression::new(DataType::Timestamp, 0).boxed(),
+            LiteralExpression::new(DataType::Interval, Some(ScalarImpl::Interval(window_size)))
+                .boxed(),
+        );
+
         #[for_await]
         for msg in input.execute() {
             let msg = msg?;
+            if let Message::Watermark(watermark) = &msg && watermark.col_idx == time_col_idx {
+                let mut row = Row(vec![None; input_data_types.len()]);
+                row.0[time_col_idx] = Some(watermark.val.clone());
+                let data_chunk = DataChunk::from_rows(&[row], &input_data_types)
+                    .map_err(StreamExecutorError::EvalError)?;
+                let window_start = hop_start
+                    .eval(&data_chunk)
+                    .map_err(StreamExecutorError::EvalError)?;
+                let window_end = window_end_expr
+                    .eval(&DataChunk::new(vec![Column::new(window_start.clone())], None))
+                    .map_err(StreamExecutorError::EvalError)?;
+
+                yield msg;
+                for (col_idx, bound) in [window_start, window_end].into_iter().enumerate() {
+                    if let Some(val) = bound.datum_at(0) {
+                        yield Message::Watermark(Watermark::new(
+                            input_data_types.len() + col_idx,
+                            DataType::Timestamp,
+                            val,
+                        ));
+                    }
+                }
+                continue;
+            }
             let Message::Chunk(chunk) = msg else {
                 // TODO: syn has not supported `let_else`, we desugar here manually.
                 yield std::task::Poll::Ready(msg);
@@ -198,6 +198,7 @@ mod tests {
             kind: AggKind::RowCount,
             args: AggArgs::None,
             return_type: DataType::Int64,
+            append_only: false,
         }];
 
         let simple_agg = Box::new(LocalSimpleAggExecutor::new(
@@ -261,16 +262,19 @@ mod tests {
                 kind: AggKind::RowCount,
                 args: AggArgs::None,
                 return_type: DataType::Int64,
+                append_only: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 0),
                 return_type: DataType::Int64,
+                append_only: false,
             },
             AggCall {
                 kind: AggKind::Sum,
                 args: AggArgs::Unary(DataType::Int64, 1),
                 return_type: DataType::Int64,
+                append_only: false,
             },
         ];
 
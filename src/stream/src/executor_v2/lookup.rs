@@ -26,6 +26,7 @@ use crate::executor_v2::{Barrier, BoxedMessageStream, Executor, PkIndices, PkInd
 mod sides;
 use self::sides::*;
 mod impl_;
+pub use impl_::LookupExecutorParams;
 
 #[cfg(test)]
 mod tests;
@@ -33,8 +34,8 @@ mod tests;
 type BoxedArrangeStream = Pin<Box<dyn Stream<Item = Result<ArrangeMessage>> + Send>>;
 
 /// `LookupExecutor` takes one input stream and one arrangement. It joins the input stream with the
-/// arrangement. Currently, it only supports inner join. See `LookupExecutorParams` for more
-/// information.
+/// arrangement. Currently, it only supports inner join and left outer join. See
+/// `LookupExecutorParams` for more information.
 ///
 /// The output schema is `| stream columns | arrangement columns |`.
 pub struct LookupExecutor<S: StateStore> {
@@ -58,6 +59,9 @@ pub struct LookupExecutor<S: StateStore> {
 
     /// The last received barrier.
     last_barrier: Option<Barrier>,
+
+    /// Whether to output the stream rows with nulls if no rows are matched in the arrangement.
+    left_outer: bool,
 }
 
 #[async_trait]
@@ -19,6 +19,7 @@ use risingwave_common::catalog::{ColumnDesc, Schema};
 use risingwave_common::error::Result;
 use risingwave_common::util::ordered::OrderedRowSerializer;
 use risingwave_common::util::sort_util::OrderPair;
+use risingwave_pb::plan::JoinType;
 use risingwave_storage::cell_based_row_deserializer::CellBasedRowDeserializer;
 use risingwave_storage::{Keyspace, StateStore};
 
@@ -95,8 +96,13 @@ pub struct LookupExecutorParams<S: StateStore> {
     /// The join keys on the stream side.
     pub stream_join_key_indices: Vec<usize>,
 
-    /// The join keys on the arrangement side.
+    /// The join keys on the arrangement side, as the positions in `arrangement_order_rules`. The
+    /// i-th stream join key is looked up as the `arrange_join_key_indices[i]`-th arrange key.
     pub arrange_join_key_indices: Vec<usize>,
+
+    /// Either [`JoinType::Inner`] or [`JoinType::LeftOuter`]. For left outer join, the stream rows
+    /// without any matched rows are output with nulls on the arrangement side.
+    pub join_type: JoinType,
 }
 
 impl<S: StateStore> LookupExecutor<S> {
@@ -111,16 +117,23 @@ impl<S: StateStore> LookupExecutor<S> {
             use_current_epoch,
             stream_join_key_indices,
             arrange_join_key_indices,
+            join_type,
         } = params;
 
+        let left_outer = match join_type {
+            JoinType::Inner => false,
+            JoinType::LeftOuter => true,
+            _ => panic!("lookup join does not support join type {:?}", join_type),
+        };
+
         let output_column_length = stream.schema().len() + arrangement.schema().len();
 
-        // output schema: | arrange | stream |
-        let schema_fields = arrangement
+        // output schema: | stream | arrange |
+        let schema_fields = stream
             .schema()
             .fields
             .iter()
-            .chain(stream.schema().fields.iter())
+            .chain(arrangement.schema().fields.iter())
             .cloned()
             .collect_vec();
 
@@ -170,6 +183,7 @@ impl<S: StateStore> LookupExecutor<S> {
             schema,
             pk_indices,
             last_barrier: None,
+            left_outer,
             input: if use_current_epoch {
                 Some(Box::pin(stream_lookup_arrange_this_epoch(
                     stream,
@@ -265,10 +279,13 @@ impl<S: StateStore> LookupExecutor<S> {
         )?;
 
         for (op, row) in ops.iter().zip_eq(chunk.rows()) {
-            for matched_row in self.lookup_one_row(&row, lookup_epoch).await? {
+            let matched_rows = self.lookup_one_row(&row, lookup_epoch).await?;
+            if matched_rows.is_empty() && self.left_outer {
+                builder.append_row_update(*op, &row)?;
+            }
+            for matched_row in matched_rows {
                 builder.append_row(*op, &row, &matched_row)?;
             }
-            // TODO: support outer join (return null if no rows are matched)
         }
 
         builder.finish()
@@ -278,13 +295,15 @@ impl<S: StateStore> LookupExecutor<S> {
     async fn lookup_one_row(&mut self, row: &RowRef<'_>, lookup_epoch: u64) -> Result<Vec<Row>> {
         // TODO: add a cache for arrangement in an upstream executor
 
-        // Serialize join key to a state store key.
+        // Serialize join key to a state store key, in the order of the arrange keys.
         let key_prefix = {
             let row = RowRef(
-                self.arrangement
-                    .join_key_indices
+                self.stream
+                    .key_indices
                     .iter()
-                    .map(|x| row.0[*x])
+                    .zip_eq(self.arrangement.join_key_indices.iter())
+                    .sorted_by_key(|(_, arrange_idx)| **arrange_idx)
+                    .map(|(stream_idx, _)| row.0[*stream_idx])
                     .collect_vec(),
             );
             tracing::trace!(target: "events::stream::lookup::one_row", "{:?}", row);
@@ -121,6 +121,9 @@ pub async fn stream_lookup_arrange_prev_epoch(
                 // For message from the arrangement side, we always send in batch.
                 arrange_updates.push(msg?);
             }
+            AlignedMessage::WatermarkLeft(_) | AlignedMessage::WatermarkRight(_) => {
+                // Lookup join does not clean its states by watermark, so simply drop them.
+            }
             AlignedMessage::Barrier(barrier) => {
                 yield ArrangeMessage::Arrange(std::mem::take(&mut arrange_updates));
                 yield ArrangeMessage::Barrier(barrier);
@@ -159,6 +162,9 @@ pub async fn stream_lookup_arrange_this_epoch(
                 // For message from the arrangement side, we always send in batch.
                 arrange_updates.push(msg?);
             }
+            AlignedMessage::WatermarkLeft(_) | AlignedMessage::WatermarkRight(_) => {
+                // Lookup join does not clean its states by watermark, so simply drop them.
+            }
             AlignedMessage::Barrier(barrier) => {
                 yield ArrangeMessage::Arrange(std::mem::take(&mut arrange_updates));
                 for msg in std::mem::take(&mut stream_buf) {
@@ -18,8 +18,9 @@ use itertools::Itertools;
 use risingwave_common::array::{I32Array, Op, StreamChunk};
 use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema, TableId};
 use risingwave_common::column_nonnull;
-use risingwave_common::types::{deserialize_datum_from, DataType};
+use risingwave_common::types::{deserialize_datum_from, DataType, ScalarImpl, ToOwnedDatum};
 use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_pb::plan::JoinType;
 use risingwave_storage::memory::MemoryStateStore;
 use risingwave_storage::{Keyspace, StateStore};
 
@@ -129,6 +130,7 @@ async fn create_arrangement(
         column_ids,
         1,
         vec![],
+        None,
     ))
 }
 
@@ -227,8 +229,9 @@ async fn test_lookup_this_epoch() {
         arrangement_order_rules: arrangement_col_arrange_rules(),
         pk_indices: vec![1, 2],
         use_current_epoch: true,
-        stream_join_key_indices: vec![1],
+        stream_join_key_indices: vec![0],
         arrange_join_key_indices: vec![0],
+        join_type: JoinType::Inner,
     }));
     let mut lookup_executor = lookup_executor.execute();
 
@@ -299,8 +302,9 @@ async fn test_lookup_last_epoch() {
         arrangement_order_rules: arrangement_col_arrange_rules(),
         pk_indices: vec![1, 2],
         use_current_epoch: false,
-        stream_join_key_indices: vec![1],
+        stream_join_key_indices: vec![0],
         arrange_join_key_indices: vec![0],
+        join_type: JoinType::Inner,
     }));
     let mut lookup_executor = lookup_executor.execute();
 
@@ -336,3 +340,73 @@ async fn test_lookup_last_epoch() {
     );
     check_chunk_eq(chunk2, &expected_chunk2);
 }
+
+#[tokio::test]
+async fn test_lookup_left_outer() {
+    let store = MemoryStateStore::new();
+    let table_id = TableId::new(1);
+    let arrangement = create_arrangement(table_id, store.clone()).await;
+
+    // The stream has a row with join key 7, which can't be matched in the arrangement.
+    let stream = {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+        ]);
+        let chunk = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                column_nonnull! { I32Array, [6, 7] },
+                column_nonnull! { I32Array, [1, 2] },
+            ],
+            None,
+        );
+        Box::new(MockSource::with_messages(
+            schema,
+            PkIndices::new(),
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(chunk),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+            ],
+        ))
+    };
+    let lookup_executor = Box::new(LookupExecutor::new(LookupExecutorParams {
+        arrangement,
+        stream,
+        arrangement_keyspace: Keyspace::table_root(store.clone(), &table_id),
+        arrangement_col_descs: arrangement_col_descs(),
+        arrangement_order_rules: arrangement_col_arrange_rules(),
+        pk_indices: vec![1, 2],
+        use_current_epoch: true,
+        stream_join_key_indices: vec![0],
+        arrange_join_key_indices: vec![0],
+        join_type: JoinType::LeftOuter,
+    }));
+    let mut lookup_executor = lookup_executor.execute();
+
+    let mut msgs = vec![];
+    next_msg(&mut msgs, &mut lookup_executor).await;
+    next_msg(&mut msgs, &mut lookup_executor).await;
+    assert_matches!(msgs[0], Message::Barrier(_));
+
+    let chunk = msgs[1].as_chunk().unwrap();
+    let rows = chunk
+        .rows()
+        .map(|row| {
+            row.values
+                .iter()
+                .map(|datum| datum.to_owned_datum())
+                .collect_vec()
+        })
+        .collect_vec();
+    let int = |v| Some(ScalarImpl::Int32(v));
+    assert_eq!(
+        rows,
+        vec![
+            vec![int(6), int(1), int(2333), int(6)],
+            vec![int(6), int(1), int(2334), int(6)],
+            vec![int(7), int(2), None, None],
+        ]
+    );
+}
@@ -12,29 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
 use async_trait::async_trait;
 use futures::channel::mpsc::{Receiver, Sender};
 use futures::future::select_all;
-use futures::{SinkExt, StreamExt};
+use futures::{ready, Stream, StreamExt};
 use futures_async_stream::{for_await, try_stream};
 use itertools::Itertools;
+use prometheus::IntGauge;
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::Result;
-use risingwave_pb::task_service::GetStreamResponse;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::ScalarImpl;
+use risingwave_common::util::addr::{is_local_address, HostAddr};
+use risingwave_common::util::exchange::decode_batch;
+use risingwave_pb::task_service::{ExchangeOptions, GetStreamResponse, StreamMessageBatch};
 use risingwave_rpc_client::ComputeClient;
 use tonic::Streaming;
 use tracing_futures::Instrument;
 
-use super::{Executor, Message, PkIndicesRef};
-use crate::executor::PkIndices;
-use crate::executor_v2::error::TracedStreamExecutorError;
+use super::{Executor, Message, PkIndicesRef, Watermark};
+use crate::executor::monitor::ChannelMetrics;
+use crate::executor::{send_to_channel, MergeUpdate, Mutation, PkIndices};
+use crate::executor_v2::error::{
+    StreamExecutorError, StreamExecutorResult, TracedStreamExecutorError,
+};
 use crate::executor_v2::{BoxedMessageStream, ExecutorInfo};
-use crate::task::UpDownActorIds;
+use crate::task::{ActorId, ComputeClientPool, SharedContext, UpDownActorIds};
 
 /// Receive data from `gRPC` and forwards to `MergerExecutor`/`ReceiverExecutor`
 pub struct RemoteInput {
     stream: Streaming<GetStreamResponse>,
     sender: Sender<Message>,
+    metrics: ChannelMetrics,
 }
 
 impl RemoteInput {
@@ -44,9 +57,29 @@ impl RemoteInput {
         client: ComputeClient,
         up_down_ids: UpDownActorIds,
         sender: Sender<Message>,
+        options: ExchangeOptions,
+        metrics: ChannelMetrics,
     ) -> Result<Self> {
-        let stream = client.get_stream(up_down_ids.0, up_down_ids.1).await?;
-        Ok(Self { stream, sender })
+        let stream = client
+            .get_stream(up_down_ids.0, up_down_ids.1, options)
+            .await?;
+        Ok(Self {
+            stream,
+            sender,
+            metrics,
+        })
+    }
+
+    /// Decodes the messages of a response, which are either sent alone or coalesced into a
+    /// compressed batch.
+    fn decode_response(resp: GetStreamResponse) -> Result<Vec<Message>> {
+        match resp.message.as_ref() {
+            Some(msg) => Ok(vec![Message::from_protobuf(msg)?]),
+            None => {
+                let batch: StreamMessageBatch = decode_batch(resp.compression(), &resp.batch)?;
+                batch.messages.iter().map(Message::from_protobuf).collect()
+            }
+        }
     }
 
     pub async fn run(mut self) {
@@ -54,14 +87,12 @@ impl RemoteInput {
         for data_res in self.stream {
             match data_res {
                 Ok(stream_msg) => {
-                    let msg_res = Message::from_protobuf(
-                        stream_msg
-                            .get_message()
-                            .expect("no message in stream response!"),
-                    );
+                    let msg_res = Self::decode_response(stream_msg);
                     match msg_res {
-                        Ok(msg) => {
-                            self.sender.send(msg).await.unwrap();
+                        Ok(msgs) => {
+                            for msg in msgs {
+                                send_to_channel(&mut self.sender, msg, &self.metrics).await;
+                            }
                         }
                         Err(e) => {
                             error!("RemoteInput forward message error:{}", e);
@@ -78,18 +109,102 @@ impl RemoteInput {
     }
 }
 
+/// Create a receiver of the messages from the upstream actor. If the upstream actor is on another
+/// node, a [`RemoteInput`] will be spawned to forward the messages to the receiver.
+pub fn new_input(
+    context: &SharedContext,
+    compute_client_pool: ComputeClientPool,
+    upstream_addr: HostAddr,
+    up_down_ids: UpDownActorIds,
+    exchange_options: ExchangeOptions,
+) -> Result<Receiver<Message>> {
+    if !is_local_address(&upstream_addr, &context.addr) {
+        // Get the sender for `RemoteInput` to forward received messages to receivers in
+        // `ReceiverExecutor` or `MergerExecutor`.
+        let sender = context.take_sender(&up_down_ids)?;
+        let metrics = context
+            .streaming_metrics
+            .channel_metrics(up_down_ids.0, up_down_ids.1);
+        // spawn the `RemoteInput`
+        tokio::spawn(async move {
+            let init_client = async move {
+                let remote_input = RemoteInput::create(
+                    compute_client_pool
+                        .get_client_for_addr(upstream_addr)
+                        .await?,
+                    up_down_ids,
+                    sender,
+                    exchange_options,
+                    metrics,
+                )
+                .await?;
+                Ok::<_, RwError>(remote_input)
+            };
+            match init_client.await {
+                Ok(remote_input) => remote_input.run().await,
+                Err(e) => {
+                    error!("Spawn remote input fails:{}", e);
+                }
+            }
+        });
+    }
+    context.take_receiver(&up_down_ids)
+}
+
+/// An upstream channel of [`MergeExecutor`], whose messages are tagged with the upstream actor id.
+struct Upstream {
+    actor_id: ActorId,
+    rx: Receiver<Message>,
+    /// For an upstream added by [`Mutation::Reschedule`], the epoch of the rescheduling barrier.
+    /// The newly-created upstream actor also forwards this barrier, which has already been
+    /// aligned and yielded by the merger, so it should be skipped.
+    skip_epoch: Option<u64>,
+    /// The occupancy of the channel, decreased on each received message.
+    occupancy: Option<IntGauge>,
+}
+
+impl Stream for Upstream {
+    type Item = (ActorId, Message);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = ready!(self.rx.poll_next_unpin(cx));
+            if let Some(occupancy) = &self.occupancy && message.is_some() {
+                occupancy.dec();
+            }
+            if let Some(epoch) = self.skip_epoch.take() {
+                if let Some(Message::Barrier(barrier)) = &message && barrier.epoch.curr == epoch {
+                    continue;
+                }
+            }
+            let actor_id = self.actor_id;
+            return Poll::Ready(message.map(|message| (actor_id, message)));
+        }
+    }
+}
+
+/// Creates the channels of the upstreams added by [`Mutation::Reschedule`].
+struct InputContext {
+    context: Arc<SharedContext>,
+    compute_client_pool: ComputeClientPool,
+    exchange_options: ExchangeOptions,
+}
+
 /// `MergeExecutor` merges data from multiple channels. Dataflow from one channel
 /// will be stopped on barrier.
 pub struct MergeExecutor {
-    /// Number of inputs.
-    num_inputs: usize,
-
     /// Upstream channels.
     upstreams: Vec<Receiver<Message>>,
 
+    /// Actor ids of the upstreams, in the same order as `upstreams`.
+    upstream_actor_ids: Vec<ActorId>,
+
     /// Belonged actor id.
     actor_id: u32,
 
+    /// Used to create the channels of the added upstreams, if set.
+    input_context: Option<InputContext>,
+
     info: ExecutorInfo,
 }
 
@@ -98,7 +213,7 @@ impl std::fmt::Debug for MergeExecutor {
         f.debug_struct("MergeExecutor")
             .field("schema", &self.info.schema)
             .field("pk_indices", &self.info.pk_indices)
-            .field("num_inputs", &self.num_inputs)
+            .field("upstream_actor_ids", &self.upstream_actor_ids)
             .finish()
     }
 }
@@ -111,9 +226,10 @@ impl MergeExecutor {
         inputs: Vec<Receiver<Message>>,
     ) -> Self {
         Self {
-            num_inputs: inputs.len(),
+            upstream_actor_ids: (0..inputs.len() as ActorId).collect(),
             upstreams: inputs,
             actor_id,
+            input_context: None,
             info: ExecutorInfo {
                 schema,
                 pk_indices,
@@ -121,6 +237,77 @@ impl MergeExecutor {
             },
         }
     }
+
+    /// Allows the upstreams to be added or removed by [`Mutation::Reschedule`].
+    /// `upstream_actor_ids` should be in the same order as the inputs.
+    pub fn with_upstream_updates(
+        mut self,
+        upstream_actor_ids: Vec<ActorId>,
+        context: Arc<SharedContext>,
+        compute_client_pool: ComputeClientPool,
+        exchange_options: ExchangeOptions,
+    ) -> Self {
+        assert_eq!(upstream_actor_ids.len(), self.upstreams.len());
+        self.upstream_actor_ids = upstream_actor_ids;
+        self.input_context = Some(InputContext {
+            context,
+            compute_client_pool,
+            exchange_options,
+        });
+        self
+    }
+
+    /// Removes and adds the upstreams after a barrier of [`Mutation::Reschedule`] is yielded.
+    fn update_upstreams(
+        &mut self,
+        upstreams: &mut Vec<Upstream>,
+        update: &MergeUpdate,
+        epoch: u64,
+    ) -> StreamExecutorResult<()> {
+        let removed = &update.removed_upstream_actor_ids;
+        upstreams.retain(|upstream| !removed.contains(&upstream.actor_id));
+        self.upstream_actor_ids.retain(|id| !removed.contains(id));
+
+        if !update.added_upstream_actors.is_empty() {
+            let input_context = self
+                .input_context
+                .as_ref()
+                .expect("upstreams of the merger cannot be updated");
+            for info in &update.added_upstream_actors {
+                let upstream_addr = info
+                    .get_host()
+                    .map_err(StreamExecutorError::input_error)?
+                    .into();
+                let rx = new_input(
+                    &input_context.context,
+                    input_context.compute_client_pool.clone(),
+                    upstream_addr,
+                    (info.actor_id, self.actor_id),
+                    input_context.exchange_options.clone(),
+                )
+                .map_err(StreamExecutorError::input_error)?;
+                upstreams.push(Upstream {
+                    actor_id: info.actor_id,
+                    rx,
+                    skip_epoch: Some(epoch),
+                    occupancy: Some(self.channel_occupancy(info.actor_id)),
+                });
+                self.upstream_actor_ids.push(info.actor_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The occupancy gauge of the channel from the upstream actor.
+    fn channel_occupancy(&self, upstream_actor_id: ActorId) -> IntGauge {
+        let input_context = self.input_context.as_ref().unwrap();
+        input_context
+            .context
+            .streaming_metrics
+            .channel_metrics(upstream_actor_id, self.actor_id)
+            .occupancy
+    }
 }
 
 #[async_trait]
@@ -144,8 +331,23 @@ impl Executor for MergeExecutor {
 
 impl MergeExecutor {
     #[try_stream(ok = Message, error = TracedStreamExecutorError)]
-    async fn execute_inner(self) {
-        let mut upstreams = self.upstreams;
+    async fn execute_inner(mut self) {
+        // Tag the messages with the upstream actor id, so that we can track the watermarks of each
+        // upstream, and find the upstreams to remove on rescheduling.
+        let mut upstreams = std::mem::take(&mut self.upstreams)
+            .into_iter()
+            .zip_eq(self.upstream_actor_ids.iter().cloned())
+            .map(|(rx, actor_id)| Upstream {
+                actor_id,
+                rx,
+                skip_epoch: None,
+                occupancy: self
+                    .input_context
+                    .is_some()
+                    .then(|| self.channel_occupancy(actor_id)),
+            })
+            .collect_vec();
+        let mut watermarks = BufferedWatermarks::new(upstreams.len());
 
         loop {
             // Futures of all active upstreams.
@@ -166,7 +368,7 @@ impl MergeExecutor {
                     .await;
 
                 // Panic on channel close.
-                let message = message.expect(
+                let (upstream_actor_id, message) = message.expect(
                     "upstream channel closed unexpectedly, please check error in upstream executors"
                 );
                 // Put back the remainings.
@@ -178,6 +380,19 @@ impl MergeExecutor {
                         active.push(from.into_future());
                         yield message;
                     }
+                    Message::Watermark(watermark) => {
+                        active.push(from.into_future());
+                        let upstream_idx = self
+                            .upstream_actor_ids
+                            .iter()
+                            .position(|&id| id == upstream_actor_id)
+                            .unwrap();
+                        if let Some(watermark) =
+                            watermarks.handle_watermark(upstream_idx, watermark)
+                        {
+                            yield Message::Watermark(watermark);
+                        }
+                    }
                     Message::Barrier(barrier) => {
                         // Align the barrier.
                         if let Some(current_barrier) = current_barrier.as_ref() {
@@ -194,6 +409,13 @@ impl MergeExecutor {
             // 2. Yield the barrier to downstream once all barriers collected from upstream.
             let barrier = current_barrier.unwrap();
             let to_stop = barrier.is_to_stop_actor(self.actor_id);
+            let epoch = barrier.epoch.curr;
+            let update = match barrier.mutation.as_deref() {
+                Some(Mutation::Reschedule(reschedule)) => {
+                    reschedule.merges.get(&self.actor_id).cloned()
+                }
+                _ => None,
+            };
             yield Message::Barrier(barrier);
 
             // 3. Put back the upstreams, or close the stream.
@@ -202,6 +424,66 @@ impl MergeExecutor {
             } else {
                 upstreams = blocked;
             }
+
+            // 4. Update the upstreams if the upstream fragment is rescheduled.
+            if let Some(update) = update {
+                self.update_upstreams(&mut upstreams, &update, epoch)?;
+                watermarks.reset_upstreams(upstreams.len());
+            }
+        }
+    }
+}
+
+/// Buffers the watermarks from all upstreams. The merged watermark of a column is the minimum
+/// among all upstreams, and is only emitted when it advances.
+struct BufferedWatermarks {
+    num_upstreams: usize,
+
+    /// `col_idx` -> the latest watermark of each upstream.
+    upstream_watermarks: HashMap<usize, Vec<Option<Watermark>>>,
+
+    /// `col_idx` -> the last emitted watermark.
+    emitted: HashMap<usize, ScalarImpl>,
+}
+
+impl BufferedWatermarks {
+    fn new(num_upstreams: usize) -> Self {
+        Self {
+            num_upstreams,
+            upstream_watermarks: HashMap::new(),
+            emitted: HashMap::new(),
+        }
+    }
+
+    /// Forgets the watermarks of the upstreams when they are added or removed. The merged
+    /// watermark will be emitted again after all upstreams send a watermark beyond the emitted one.
+    fn reset_upstreams(&mut self, num_upstreams: usize) {
+        self.num_upstreams = num_upstreams;
+        self.upstream_watermarks.clear();
+    }
+
+    /// Returns the merged watermark to emit, if any.
+    fn handle_watermark(&mut self, upstream_idx: usize, watermark: Watermark) -> Option<Watermark> {
+        let col_idx = watermark.col_idx;
+        let watermarks = self
+            .upstream_watermarks
+            .entry(col_idx)
+            .or_insert_with(|| vec![None; self.num_upstreams]);
+        watermarks[upstream_idx] = Some(watermark);
+
+        let min_watermark = watermarks
+            .iter()
+            .map(Option::as_ref)
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .min_by(|a, b| a.val.cmp(&b.val))?
+            .clone();
+        match self.emitted.get(&col_idx) {
+            Some(emitted) if *emitted >= min_watermark.val => None,
+            _ => {
+                self.emitted.insert(col_idx, min_watermark.val.clone());
+                Some(min_watermark)
+            }
         }
     }
 }
@@ -219,6 +501,8 @@ mod tests {
     use futures::SinkExt;
     use itertools::Itertools;
     use risingwave_common::array::{Op, StreamChunk};
+    use risingwave_common::types::DataType;
+    use risingwave_pb::common::{ActorInfo, HostAddress};
     use risingwave_pb::data::StreamMessage;
     use risingwave_pb::task_service::exchange_service_server::{
         ExchangeService, ExchangeServiceServer,
@@ -231,9 +515,10 @@ mod tests {
     use tonic::{Request, Response, Status};
 
     use super::*;
-    use crate::executor::{Barrier, Executor, Mutation};
+    use crate::executor::{Barrier, Executor, Mutation, Reschedule};
     use crate::executor_v2::merge::RemoteInput;
     use crate::executor_v2::Executor as ExecutorV2;
+    use crate::task::LOCAL_TEST_ADDR;
 
     fn build_test_chunk(epoch: u64) -> StreamChunk {
         // The number of items in `ops` is the epoch count.
@@ -241,6 +526,20 @@ mod tests {
         StreamChunk::new(ops, vec![], None)
     }
 
+    #[test]
+    fn test_buffered_watermarks() {
+        let watermark = |val: i64| Watermark::new(0, DataType::Int64, ScalarImpl::Int64(val));
+        let mut buffer = BufferedWatermarks::new(2);
+
+        // Not all upstreams have sent a watermark yet.
+        assert_eq!(buffer.handle_watermark(0, watermark(5)), None);
+        assert_eq!(buffer.handle_watermark(1, watermark(3)), Some(watermark(3)));
+        assert_eq!(buffer.handle_watermark(1, watermark(8)), Some(watermark(5)));
+        // The minimum does not advance.
+        assert_eq!(buffer.handle_watermark(1, watermark(9)), None);
+        assert_eq!(buffer.handle_watermark(0, watermark(7)), Some(watermark(7)));
+    }
+
     #[tokio::test]
     async fn test_merger() {
         const CHANNEL_NUMBER: usize = 10;
@@ -304,6 +603,78 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_merger_reschedule() {
+        let actor_id = 233;
+        let ctx = Arc::new(SharedContext::for_test());
+        let (mut tx_1, rx_1) = channel(16);
+        let (mut tx_2, rx_2) = channel(16);
+        let (tx_3, rx_3) = channel(16);
+        ctx.add_channel_pairs((3, actor_id), (Some(tx_3), Some(rx_3)));
+        let mut tx_3 = ctx.take_sender(&(3, actor_id)).unwrap();
+
+        let merger = MergeExecutor::new(Schema::default(), vec![], actor_id, vec![rx_1, rx_2])
+            .with_upstream_updates(
+                vec![1, 2],
+                ctx.clone(),
+                ComputeClientPool::new(1),
+                ExchangeOptions::default(),
+            );
+        let mut merger = Box::new(merger).execute();
+
+        // Replace upstream 2 with upstream 3.
+        let reschedule = Reschedule {
+            merges: HashMap::from([(
+                actor_id,
+                MergeUpdate {
+                    added_upstream_actors: vec![ActorInfo {
+                        actor_id: 3,
+                        host: Some(HostAddress {
+                            host: LOCAL_TEST_ADDR.host.clone(),
+                            port: LOCAL_TEST_ADDR.port as i32,
+                        }),
+                    }],
+                    removed_upstream_actor_ids: HashSet::from([2]),
+                },
+            )]),
+            stopped_actors: HashSet::from([2]),
+            ..Default::default()
+        };
+        let barrier = Barrier::new_test_barrier(1).with_mutation(Mutation::Reschedule(reschedule));
+        for tx in [&mut tx_1, &mut tx_2] {
+            tx.send(Message::Barrier(barrier.clone())).await.unwrap();
+        }
+        assert_matches!(merger.next().await.unwrap().unwrap(), Message::Barrier(b) => {
+            assert_eq!(b, barrier);
+        });
+
+        // The barriers from upstream 1 and 3 are aligned, while upstream 2 is removed. The
+        // rescheduling barrier forwarded by upstream 3 is skipped.
+        tx_3.send(Message::Barrier(barrier.clone())).await.unwrap();
+        let barrier = Barrier::new_test_barrier(2);
+        tx_1.send(Message::Chunk(build_test_chunk(1)))
+            .await
+            .unwrap();
+        tx_3.send(Message::Chunk(build_test_chunk(2)))
+            .await
+            .unwrap();
+        for tx in [&mut tx_1, &mut tx_3] {
+            tx.send(Message::Barrier(barrier.clone())).await.unwrap();
+        }
+        let mut cardinalities = vec![];
+        for _ in 0..2 {
+            assert_matches!(merger.next().await.unwrap().unwrap(), Message::Chunk(chunk) => {
+                cardinalities.push(chunk.ops().len());
+            });
+        }
+        cardinalities.sort_unstable();
+        assert_eq!(cardinalities, vec![1, 2]);
+        assert_matches!(merger.next().await.unwrap().unwrap(), Message::Barrier(b) => {
+            assert_eq!(b, barrier);
+        });
+        assert!(tx_2.is_closed());
+    }
+
     struct FakeExchangeService {
         rpc_called: Arc<AtomicBool>,
     }
@@ -336,6 +707,7 @@ mod tests {
                         ),
                     ),
                 }),
+                ..Default::default()
             }))
             .await
             .unwrap();
@@ -349,6 +721,7 @@ mod tests {
                         ),
                     ),
                 }),
+                ..Default::default()
             }))
             .await
             .unwrap();
@@ -383,10 +756,15 @@ mod tests {
         assert!(server_run.load(Ordering::SeqCst));
         let (tx, mut rx) = channel(16);
         let input_handle = tokio::spawn(async move {
-            let remote_input =
-                RemoteInput::create(ComputeClient::new(addr.into()).await.unwrap(), (0, 0), tx)
-                    .await
-                    .unwrap();
+            let remote_input = RemoteInput::create(
+                ComputeClient::new(addr.into()).await.unwrap(),
+                (0, 0),
+                tx,
+                ExchangeOptions::default(),
+                ChannelMetrics::unused(),
+            )
+            .await
+            .unwrap();
             remote_input.run().await
         });
         assert_matches!(rx.next().await.unwrap(), Message::Chunk(chunk) => {
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod error;
+pub(crate) mod error;
 
 use error::StreamExecutorResult;
 use futures::stream::BoxStream;
@@ -21,28 +21,32 @@ use risingwave_common::catalog::Schema;
 use risingwave_common::error::Result;
 
 pub use super::executor::{
-    Barrier, Executor as ExecutorV1, Message, Mutation, PkIndices, PkIndicesRef,
+    Barrier, Executor as ExecutorV1, Message, Mutation, PkIndices, PkIndicesRef, Watermark,
 };
 
 mod agg;
 mod barrier_align;
 mod batch_query;
 mod chain;
+mod dedup;
+mod dynamic_filter;
 mod filter;
 mod global_simple_agg;
 mod hash_agg;
+mod hash_join;
 mod hop_window;
 mod local_simple_agg;
 mod lookup;
 pub mod merge;
 pub(crate) mod mview;
 mod project;
-#[allow(dead_code)]
+mod rate_limiter;
 mod rearranged_chain;
 pub mod receiver;
 mod simple;
+mod source;
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
 mod top_n;
 mod top_n_appendonly;
 mod top_n_executor;
@@ -50,16 +54,21 @@ mod v1_compat;
 
 pub use batch_query::BatchQueryExecutor;
 pub use chain::ChainExecutor;
+pub use dedup::AppendOnlyDedupExecutor;
+pub use dynamic_filter::DynamicFilterExecutor;
 pub use filter::FilterExecutor;
 pub use global_simple_agg::SimpleAggExecutor;
 pub use hash_agg::HashAggExecutor;
+pub use hash_join::{HashJoinExecutor, IntervalJoinBound, JoinParams, JoinType};
 pub use hop_window::HopWindowExecutor;
 pub use local_simple_agg::LocalSimpleAggExecutor;
 pub use lookup::*;
 pub use merge::MergeExecutor;
 pub use mview::*;
 pub use project::ProjectExecutor;
+pub use rearranged_chain::RearrangedChainExecutor;
 pub(crate) use simple::{SimpleExecutor, SimpleExecutorWrapper};
+pub use source::SourceExecutor;
 pub use top_n::TopNExecutor;
 pub use top_n_appendonly::AppendOnlyTopNExecutor;
 pub use v1_compat::StreamExecutorV1;
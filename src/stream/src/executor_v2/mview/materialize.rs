@@ -26,6 +26,7 @@ use crate::executor_v2::mview::ManagedMViewState;
 use crate::executor_v2::{
     BoxedExecutor, BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndicesRef,
 };
+use crate::task::TableRowCountReporter;
 
 /// `MaterializeExecutor` materializes changes in stream into a materialized view on storage.
 pub struct MaterializeExecutor<S: StateStore> {
@@ -41,6 +42,9 @@ pub struct MaterializeExecutor<S: StateStore> {
     key_indices: Vec<usize>,
 
     info: ExecutorInfo,
+
+    /// Reports the net number of rows written on each barrier, if set.
+    row_count_reporter: Option<TableRowCountReporter>,
 }
 
 impl<S: StateStore> MaterializeExecutor<S> {
@@ -51,6 +55,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
         column_ids: Vec<ColumnId>,
         executor_id: u64,
         key_indices: Vec<usize>,
+        row_count_reporter: Option<TableRowCountReporter>,
     ) -> Self {
         let arrange_columns: Vec<usize> = keys.iter().map(|k| k.column_idx).collect();
         let arrange_order_types = keys.iter().map(|k| k.order_type).collect();
@@ -65,12 +70,15 @@ impl<S: StateStore> MaterializeExecutor<S> {
                 identity: format!("MaterializeExecutor {:X}", executor_id),
             },
             key_indices,
+            row_count_reporter,
         }
     }
 
     #[try_stream(ok = Message, error = TracedStreamExecutorError)]
     async fn execute_inner(mut self) {
         let input = self.input.execute();
+        // The net number of rows written since the last barrier.
+        let mut row_count_delta = 0i64;
         #[for_await]
         for msg in input {
             let msg = msg?;
@@ -104,9 +112,11 @@ impl<S: StateStore> MaterializeExecutor<S> {
                         match op {
                             Insert | UpdateInsert => {
                                 self.local_state.put(arrange_row, row);
+                                row_count_delta += 1;
                             }
                             Delete | UpdateDelete => {
                                 self.local_state.delete(arrange_row);
+                                row_count_delta -= 1;
                             }
                         }
                     }
@@ -119,8 +129,12 @@ impl<S: StateStore> MaterializeExecutor<S> {
                         .flush(b.epoch.prev)
                         .await
                         .map_err(StreamExecutorError::ExecutorV1)?;
+                    if let Some(reporter) = &self.row_count_reporter {
+                        reporter.report(std::mem::take(&mut row_count_delta));
+                    }
                     Message::Barrier(b)
                 }
+                Message::Watermark(watermark) => Message::Watermark(watermark),
             }
         }
     }
@@ -222,6 +236,7 @@ mod tests {
             column_ids,
             1,
             vec![],
+            None,
         ))
         .execute();
 
@@ -16,25 +16,47 @@ use std::fmt::{Debug, Formatter};
 
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::{DataChunk, StreamChunk};
+use risingwave_common::array::{DataChunk, Row, StreamChunk};
 use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::DataType;
 use risingwave_expr::expr::BoxedExpression;
 
-use super::{Executor, ExecutorInfo, SimpleExecutor, SimpleExecutorWrapper, StreamExecutorResult};
-use crate::executor::PkIndicesRef;
+use super::{
+    Executor, ExecutorInfo, SimpleExecutor, SimpleExecutorWrapper, StreamExecutorResult, Watermark,
+};
+use crate::executor::{PkIndices, PkIndicesRef};
 use crate::executor_v2::error::StreamExecutorError;
 
 pub type ProjectExecutor = SimpleExecutorWrapper<SimpleProjectExecutor>;
 
 impl ProjectExecutor {
-    pub fn new(input: Box<dyn Executor>, exprs: Vec<BoxedExpression>, execuotr_id: u64) -> Self {
-        let info = input.info();
+    pub fn new(
+        input: Box<dyn Executor>,
+        pk_indices: PkIndices,
+        exprs: Vec<BoxedExpression>,
+        executor_id: u64,
+    ) -> Self {
+        let info = ExecutorInfo {
+            schema: input.schema().to_owned(),
+            pk_indices,
+            identity: "Project".to_owned(),
+        };
 
         SimpleExecutorWrapper {
             input,
-            inner: SimpleProjectExecutor::new(info, exprs, execuotr_id),
+            inner: SimpleProjectExecutor::new(info, exprs, executor_id),
         }
     }
+
+    /// Derives the watermarks on the input columns to the output columns along the
+    /// `(input column, output column)` pairs.
+    pub fn with_watermark_derivations(
+        mut self,
+        watermark_derivations: Vec<(usize, usize)>,
+    ) -> Self {
+        self.inner = self.inner.with_watermark_derivations(watermark_derivations);
+        self
+    }
 }
 
 /// `ProjectExecutor` project data with the `expr`. The `expr` takes a chunk of data,
@@ -45,6 +67,13 @@ pub struct SimpleProjectExecutor {
 
     /// Expressions of the current projection.
     exprs: Vec<BoxedExpression>,
+
+    /// Data types of the input columns, used to evaluate the watermarks.
+    input_data_types: Vec<DataType>,
+
+    /// `(input column, output column)` pairs where the output is monotonic on the input, so that a
+    /// watermark on the input column can be derived to the output column.
+    watermark_derivations: Vec<(usize, usize)>,
 }
 
 impl SimpleProjectExecutor {
@@ -62,8 +91,18 @@ impl SimpleProjectExecutor {
                 identity: format!("ProjectExecutor {:X}", executor_id),
             },
             exprs,
+            input_data_types: input_info.schema.data_types(),
+            watermark_derivations: vec![],
         }
     }
+
+    pub fn with_watermark_derivations(
+        mut self,
+        watermark_derivations: Vec<(usize, usize)>,
+    ) -> Self {
+        self.watermark_derivations = watermark_derivations;
+        self
+    }
 }
 
 impl Debug for SimpleProjectExecutor {
@@ -105,6 +144,29 @@ impl SimpleExecutor for SimpleProjectExecutor {
         Ok(Some(new_chunk))
     }
 
+    fn map_watermark(&mut self, watermark: Watermark) -> StreamExecutorResult<Vec<Watermark>> {
+        let mut row = Row(vec![None; self.input_data_types.len()]);
+        row.0[watermark.col_idx] = Some(watermark.val.clone());
+        let data_chunk = DataChunk::from_rows(&[row], &self.input_data_types)
+            .map_err(StreamExecutorError::eval_error)?;
+
+        let mut watermarks = vec![];
+        for &(input_idx, output_idx) in &self.watermark_derivations {
+            if input_idx != watermark.col_idx {
+                continue;
+            }
+            let expr = &self.exprs[output_idx];
+            let val = expr
+                .eval(&data_chunk)
+                .map_err(StreamExecutorError::eval_error)?
+                .datum_at(0);
+            if let Some(val) = val {
+                watermarks.push(Watermark::new(output_idx, expr.return_type(), val));
+            }
+        }
+        Ok(watermarks)
+    }
+
     fn schema(&self) -> &Schema {
         &self.info.schema
     }
@@ -169,7 +231,12 @@ mod tests {
             Box::new(right_expr),
         );
 
-        let project = Box::new(ProjectExecutor::new(Box::new(source), vec![test_expr], 1));
+        let project = Box::new(ProjectExecutor::new(
+            Box::new(source),
+            vec![],
+            vec![test_expr],
+            1,
+        ));
         let mut project = project.execute();
 
         if let Message::Chunk(chunk) = project.next().await.unwrap().unwrap() {
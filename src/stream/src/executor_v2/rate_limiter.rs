@@ -0,0 +1,172 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use futures_async_stream::try_stream;
+use tokio::select;
+use tokio::sync::watch;
+use tokio::time::{sleep, Instant};
+
+use super::error::TracedStreamExecutorError;
+use super::{BoxedMessageStream, Message};
+
+/// [`RateLimiter`] limits the rows emitted per second with a token bucket, which holds at most
+/// the rows of one second.
+///
+/// A chunk is emitted as soon as the bucket is not in debt, even if it's larger than the tokens
+/// left. The debt is paid off by waiting before the next chunk, so that the rate limit is obeyed
+/// in the long run without splitting chunks.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// The maximum rows per second, or `None` if unlimited.
+    rate_limit: Option<u32>,
+    /// The rows that can be emitted without waiting. Negative if the bucket is in debt.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(rate_limit: Option<u32>) -> Self {
+        Self {
+            rate_limit,
+            tokens: 0.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limit
+    }
+
+    /// Changes the rate limit. The debt of the bucket is kept, but paid off at the new rate.
+    pub fn set_rate_limit(&mut self, rate_limit: Option<u32>) {
+        self.refill();
+        if rate_limit.is_none() {
+            self.tokens = 0.0;
+        }
+        self.rate_limit = rate_limit;
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        if let Some(rate_limit) = self.rate_limit {
+            let refilled = (now - self.last_refill).as_secs_f64() * rate_limit as f64;
+            self.tokens = (self.tokens + refilled).min(rate_limit as f64);
+        }
+        self.last_refill = now;
+    }
+
+    /// Takes the tokens of the rows emitted.
+    pub fn consume(&mut self, rows: usize) {
+        if self.rate_limit.is_some() {
+            self.refill();
+            self.tokens -= rows as f64;
+        }
+    }
+
+    /// Returns how long to wait until the bucket is out of debt, or `None` if it's not in debt.
+    fn delay(&mut self) -> Option<Duration> {
+        let rate_limit = self.rate_limit?;
+        self.refill();
+        (self.tokens < 0.0).then(|| Duration::from_secs_f64(-self.tokens / rate_limit as f64))
+    }
+
+    /// Waits until the bucket is out of debt. The rate limit is updated from `rate_limit` while
+    /// waiting, so that raising or removing the limit takes effect immediately. The rate limit is
+    /// kept if the sender of `rate_limit` is dropped.
+    pub async fn wait(&mut self, rate_limit: &mut watch::Receiver<Option<u32>>) {
+        let mut closed = false;
+        loop {
+            let new_rate_limit = *rate_limit.borrow_and_update();
+            if new_rate_limit != self.rate_limit {
+                self.set_rate_limit(new_rate_limit);
+            }
+            let Some(delay) = self.delay() else {
+                return;
+            };
+            if closed {
+                sleep(delay).await;
+                continue;
+            }
+            select! {
+                biased;
+                changed = rate_limit.changed() => closed = changed.is_err(),
+                _ = sleep(delay) => {}
+            }
+        }
+    }
+}
+
+/// Throttles the chunks of `input` to the rate limit received from `rate_limit`. Other messages
+/// are passed through without waiting.
+#[try_stream(ok = Message, error = TracedStreamExecutorError)]
+pub async fn throttle(input: BoxedMessageStream, mut rate_limit: watch::Receiver<Option<u32>>) {
+    let mut limiter = RateLimiter::new(*rate_limit.borrow());
+
+    #[for_await]
+    for msg in input {
+        let msg = msg?;
+        if let Message::Chunk(chunk) = &msg {
+            limiter.wait(&mut rate_limit).await;
+            limiter.consume(chunk.cardinality());
+        }
+        yield msg;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::watch;
+    use tokio::time::Instant;
+
+    use super::RateLimiter;
+
+    #[tokio::test]
+    async fn test_rate_limiter() {
+        let (rate_limit_tx, mut rate_limit_rx) = watch::channel(Some(1000));
+        let mut limiter = RateLimiter::new(Some(1000));
+
+        // The first chunk is emitted immediately, and each of the following waits for the debt of
+        // the previous one, i.e. 100 ms.
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.wait(&mut rate_limit_rx).await;
+            limiter.consume(100);
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(190), "{:?}", elapsed);
+
+        // Removing the limit takes effect immediately.
+        limiter.consume(1_000_000);
+        rate_limit_tx.send(None).unwrap();
+        let start = Instant::now();
+        limiter.wait(&mut rate_limit_rx).await;
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(limiter.rate_limit(), None);
+
+        // The limit is kept after the sender is dropped.
+        rate_limit_tx.send(Some(10_000)).unwrap();
+        drop(rate_limit_tx);
+        limiter.wait(&mut rate_limit_rx).await;
+        limiter.consume(500);
+        let start = Instant::now();
+        limiter.wait(&mut rate_limit_rx).await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(45), "{:?}", elapsed);
+        assert_eq!(limiter.rate_limit(), Some(10_000));
+    }
+}
@@ -17,12 +17,14 @@ use std::sync::Arc;
 use either::Either;
 use futures::channel::{mpsc, oneshot};
 use futures::stream::select_with_strategy;
-use futures::{stream, FutureExt, Stream, StreamExt};
+use futures::{future, stream, FutureExt, Stream, StreamExt};
 use futures_async_stream::{for_await, try_stream};
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::Schema;
+use tokio::sync::watch;
 
 use super::error::{StreamExecutorResult, TracedStreamExecutorError};
+use super::rate_limiter::throttle;
 use super::{Barrier, BoxedExecutor, Executor, ExecutorInfo, Message};
 use crate::executor_v2::error::StreamExecutorError;
 use crate::task::{ActorId, FinishCreateMviewNotifier};
@@ -34,6 +36,9 @@ use crate::task::{ActorId, FinishCreateMviewNotifier};
 ///
 /// [`RearrangedChainExecutor`] resolves the latency problem when creating MV with a huge amount of
 /// existing data, by rearranging the barrier from the upstream. Check the design doc for details.
+///
+/// The snapshot can be throttled to a rate limit, which is changed by the `Throttle` mutations of
+/// the rearranged barriers.
 pub struct RearrangedChainExecutor {
     snapshot: BoxedExecutor,
 
@@ -46,9 +51,12 @@ pub struct RearrangedChainExecutor {
     actor_id: ActorId,
 
     info: ExecutorInfo,
+
+    /// The maximum rows per second read from the snapshot, or `None` if unlimited.
+    rate_limit: Option<u32>,
 }
 
-fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
+fn mapping(upstream_indices: &[usize], msg: Message) -> Option<Message> {
     match msg {
         Message::Chunk(chunk) => {
             let (ops, columns, visibility) = chunk.into_inner();
@@ -56,9 +64,17 @@ fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
                 .iter()
                 .map(|&i| columns[i].clone())
                 .collect();
-            Message::Chunk(StreamChunk::new(ops, mapped_columns, visibility))
+            Some(Message::Chunk(StreamChunk::new(
+                ops,
+                mapped_columns,
+                visibility,
+            )))
         }
-        _ => msg,
+        Message::Watermark(watermark) => upstream_indices
+            .iter()
+            .position(|&i| i == watermark.col_idx)
+            .map(|idx| Message::Watermark(watermark.with_idx(idx))),
+        _ => Some(msg),
     }
 }
 
@@ -84,6 +100,7 @@ impl From<Message> for RearrangedMessage {
         match msg {
             Message::Chunk(chunk) => RearrangedMessage::Chunk(chunk),
             Message::Barrier(barrier) => RearrangedMessage::RearrangedBarrier(barrier),
+            Message::Watermark(_) => unreachable!("there should be no watermark in the snapshot"),
         }
     }
 }
@@ -96,6 +113,7 @@ impl RearrangedChainExecutor {
         notifier: FinishCreateMviewNotifier,
         actor_id: ActorId,
         info: ExecutorInfo,
+        rate_limit: Option<u32>,
     ) -> Self {
         Self {
             snapshot,
@@ -104,6 +122,7 @@ impl RearrangedChainExecutor {
             notifier,
             actor_id,
             info,
+            rate_limit,
         }
     }
 
@@ -111,10 +130,13 @@ impl RearrangedChainExecutor {
     async fn execute_inner(self) {
         // 0. Project the upstream with `upstream_indices`.
         let upstream_indices = self.upstream_indices.clone();
-        let mut upstream = self
-            .upstream
-            .execute()
-            .map(move |result| result.map(|msg| mapping(&upstream_indices, msg)));
+        let mut upstream = self.upstream.execute().filter_map(move |result| {
+            future::ready(
+                result
+                    .map(|msg| mapping(&upstream_indices, msg))
+                    .transpose(),
+            )
+        });
 
         // 1. Poll the upstream to get the first barrier.
         let first_msg = upstream.next().await.unwrap()?;
@@ -157,8 +179,13 @@ impl RearrangedChainExecutor {
                 stop_rearrange_rx,
             ));
 
-            // 4. Init the snapshot with reading epoch.
-            let snapshot = self.snapshot.execute_with_epoch(create_epoch.prev);
+            // 4. Init the snapshot with reading epoch, and throttle it to the rate limit.
+            let (rate_limit_tx, rate_limit_rx) = watch::channel(self.rate_limit);
+            let snapshot = throttle(
+                self.snapshot.execute_with_epoch(create_epoch.prev),
+                rate_limit_rx,
+            )
+            .boxed();
 
             // Chain the `snapshot` and `upstream_rx` to get a unified `rearranged_chunks` stream.
             let rearranged_chunks = snapshot
@@ -176,6 +203,11 @@ impl RearrangedChainExecutor {
             let mut last_rearranged_epoch = create_epoch;
             let mut stop_rearrange_tx = Some(stop_rearrange_tx);
 
+            // The rows consumed from the snapshot, which is reported with each rearranged barrier.
+            // The first phantom barrier is always after the snapshot.
+            let mut consumed_rows = 0;
+            let mut snapshot_consumed = false;
+
             // 6. Consume the merged `rearranged` stream.
             #[for_await]
             for rearranged_msg in &mut rearranged {
@@ -186,6 +218,7 @@ impl RearrangedChainExecutor {
                     // Note that there's no phantom barrier in the snapshot. So we must have already
                     // consumed the whole snapshot and be on the upstream now.
                     RearrangedMessage::PhantomBarrier(barrier) => {
+                        snapshot_consumed = true;
                         if barrier.epoch.curr >= last_rearranged_epoch.curr {
                             // Stop the background rearrangement task.
                             stop_rearrange_tx.take().unwrap().send(()).map_err(|_| {
@@ -197,10 +230,20 @@ impl RearrangedChainExecutor {
 
                     // If we received a message, yield it.
                     RearrangedMessage::RearrangedBarrier(barrier) => {
+                        if let Some(rate_limit) = barrier.as_throttle(self.actor_id) {
+                            // The snapshot may have been consumed, so the receiver could be gone.
+                            let _ = rate_limit_tx.send(rate_limit);
+                        }
                         last_rearranged_epoch = barrier.epoch;
+                        self.notifier.update_progress(create_epoch.curr, consumed_rows);
                         yield Message::Barrier(barrier);
                     }
-                    RearrangedMessage::Chunk(chunk) => yield Message::Chunk(chunk),
+                    RearrangedMessage::Chunk(chunk) => {
+                        if !snapshot_consumed {
+                            consumed_rows += chunk.cardinality() as u64;
+                        }
+                        yield Message::Chunk(chunk)
+                    }
                 }
             }
 
@@ -289,6 +332,10 @@ impl RearrangedChainExecutor {
                             })?;
                     }
 
+                    // The watermarks of the upstream do not hold for the snapshot which is still
+                    // being consumed, so simply drop them.
+                    Message::Watermark(_) => {}
+
                     // If we polled a barrier, rearrange it to `rearranged_barrier_tx` and leave
                     // a phantom barrier in-place.
                     Message::Barrier(barrier) => {
@@ -16,6 +16,7 @@ use futures::channel::mpsc::Receiver;
 use futures::StreamExt;
 use risingwave_common::catalog::Schema;
 
+use crate::executor::monitor::ChannelMetrics;
 use crate::executor_v2::{
     BoxedMessageStream, Executor, ExecutorInfo, Message, PkIndices, PkIndicesRef,
 };
@@ -27,6 +28,8 @@ pub struct ReceiverExecutor {
     receiver: Receiver<Message>,
     /// Logical Operator Info
     info: ExecutorInfo,
+    /// Metrics of the channel, whose occupancy is decreased on each received message.
+    channel_metrics: Option<ChannelMetrics>,
 }
 
 impl std::fmt::Debug for ReceiverExecutor {
@@ -47,13 +50,28 @@ impl ReceiverExecutor {
                 pk_indices,
                 identity: "ReceiverExecutor".to_string(),
             },
+            channel_metrics: None,
         }
     }
+
+    pub fn with_channel_metrics(mut self, channel_metrics: ChannelMetrics) -> Self {
+        self.channel_metrics = Some(channel_metrics);
+        self
+    }
 }
 
 impl Executor for ReceiverExecutor {
     fn execute(self: Box<Self>) -> BoxedMessageStream {
-        self.receiver.map(Ok).boxed()
+        match self.channel_metrics {
+            Some(metrics) => self
+                .receiver
+                .map(move |message| {
+                    metrics.occupancy.dec();
+                    Ok(message)
+                })
+                .boxed(),
+            None => self.receiver.map(Ok).boxed(),
+        }
     }
 
     fn schema(&self) -> &Schema {
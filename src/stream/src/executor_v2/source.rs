@@ -0,0 +1,1105 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use either::Either;
+use futures::stream::{select_with_strategy, PollNext};
+use futures::{Stream, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{ArrayBuilder, ArrayImpl, I64ArrayBuilder, StreamChunk};
+use risingwave_common::catalog::{ColumnId, Schema, TableId};
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::{Result, RwError, ToRwResult};
+use risingwave_common::types::ScalarImpl;
+use risingwave_connector::state::SourceStateHandler;
+use risingwave_connector::SplitImpl;
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_source::connector_source::ConnectorStreamSource;
+use risingwave_source::*;
+use risingwave_storage::{Keyspace, StateStore};
+use tokio::select;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::watch;
+
+use super::error::{StreamExecutorError, TracedStreamExecutorError};
+use super::rate_limiter::RateLimiter;
+use super::{BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef, Watermark};
+use crate::executor::monitor::StreamingMetrics;
+use crate::task::ActorId;
+
+/// The offsets of the last messages read from the splits, keyed by split id.
+type SplitOffsets = HashMap<String, String>;
+
+/// The splits assigned to the reader, along with the generation of the assignment, which is bumped
+/// on every split change. The chunks read are tagged with the generation they are read in.
+type SplitGeneration = (u64, Vec<SplitImpl>);
+
+/// [`SourceExecutor`] is a streaming source, from risingwave's batch table, or external systems
+/// such as Kafka.
+pub struct SourceExecutor<S: StateStore> {
+    actor_id: ActorId,
+    operator_id: u64,
+    source_id: TableId,
+    source_desc: SourceDesc,
+    column_ids: Vec<ColumnId>,
+    schema: Schema,
+    pk_indices: PkIndices,
+
+    /// current allocated row id
+    next_row_id: AtomicU64,
+
+    /// Identity string
+    identity: String,
+
+    /// The receiver of barriers, which are injected by the barrier manager.
+    barrier_receiver: Option<UnboundedReceiver<Message>>,
+
+    // monitor
+    metrics: Arc<StreamingMetrics>,
+
+    /// Split info for stream source. The start offsets of the splits are moved forward as the
+    /// messages are read.
+    stream_source_splits: Vec<SplitImpl>,
+
+    /// The ids of the splits read since the last barrier, whose states should be persisted.
+    updated_split_ids: HashSet<String>,
+
+    /// The generation of the split assignment. The chunks read in the previous generations are
+    /// dropped, as they may come from the splits no longer assigned.
+    split_generation: u64,
+
+    /// Persists the states of the splits, which are shared by all actors of the operator, so that
+    /// a split resumes from its offset when it's reassigned to another actor.
+    split_state_handler: SourceStateHandler<S>,
+
+    source_identify: String,
+
+    /// Watermark generators, each with the output column index it applies to and the expression
+    /// that derives the watermark from the rows of a chunk.
+    watermark_descs: Vec<(usize, BoxedExpression)>,
+
+    /// The largest watermark emitted on each watermark column so far.
+    current_watermarks: Vec<Option<ScalarImpl>>,
+
+    /// The maximum rows per second read from the source, or `None` if unlimited. It can be
+    /// changed by `Throttle` barriers.
+    rate_limit: Option<u32>,
+}
+
+async fn build_stream_reader<S: StateStore>(
+    source: Arc<SourceImpl>,
+    operator_id: u64,
+    column_ids: Vec<ColumnId>,
+    split_state_handler: SourceStateHandler<S>,
+    splits: Vec<SplitImpl>,
+) -> Result<Box<dyn StreamSourceReader>> {
+    let stream_reader: Box<dyn StreamSourceReader> = match source.as_ref() {
+        SourceImpl::HighLevelKafka(s) => Box::new(s.stream_reader(
+            HighLevelKafkaSourceReaderContext {
+                query_id: Some(format!("source-operator-{}", operator_id)),
+                bound_timestamp_ms: None,
+            },
+            column_ids,
+        )?),
+        SourceImpl::TableV2(s) => Box::new(s.stream_reader(TableV2ReaderContext, column_ids)?),
+        SourceImpl::Connector(s) => Box::new(ConnectorStreamSource {
+            source_reader: s.with_splits(splits).await?,
+            state_store: split_state_handler,
+            split_offsets: HashMap::new(),
+        }),
+    };
+
+    Ok(stream_reader)
+}
+
+impl<S: StateStore> SourceExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        source_id: TableId,
+        source_desc: SourceDesc,
+        keyspace: Keyspace<S>,
+        column_ids: Vec<ColumnId>,
+        schema: Schema,
+        pk_indices: PkIndices,
+        barrier_receiver: UnboundedReceiver<Message>,
+        executor_id: u64,
+        actor_id: ActorId,
+        operator_id: u64,
+        streaming_metrics: Arc<StreamingMetrics>,
+        stream_source_splits: Vec<SplitImpl>,
+        watermark_descs: Vec<(usize, BoxedExpression)>,
+        rate_limit: Option<u32>,
+    ) -> Self {
+        Self {
+            actor_id,
+            operator_id,
+            source_id,
+            source_desc,
+            column_ids,
+            schema,
+            pk_indices,
+            next_row_id: AtomicU64::from(0u64),
+            identity: format!("SourceExecutor {:X}", executor_id),
+            barrier_receiver: Some(barrier_receiver),
+            metrics: streaming_metrics,
+            stream_source_splits,
+            updated_split_ids: HashSet::new(),
+            split_generation: 0,
+            split_state_handler: SourceStateHandler::new(keyspace),
+            source_identify: "Table_".to_string() + &source_id.table_id().to_string(),
+            current_watermarks: vec![None; watermark_descs.len()],
+            watermark_descs,
+            rate_limit,
+        }
+    }
+
+    /// Evaluates the watermark expressions on the chunk, and returns a watermark for each column
+    /// whose watermark advances.
+    fn generate_watermarks(&mut self, chunk: &StreamChunk) -> Result<Vec<Watermark>> {
+        let mut watermarks = vec![];
+        if self.watermark_descs.is_empty() {
+            return Ok(watermarks);
+        }
+        let (data_chunk, _) = chunk.clone().into_parts();
+        for ((col_idx, expr), current) in self
+            .watermark_descs
+            .iter()
+            .zip_eq(self.current_watermarks.iter_mut())
+        {
+            let candidate = expr
+                .eval(&data_chunk)?
+                .iter()
+                .flatten()
+                .map(|datum| datum.into_scalar_impl())
+                .max();
+            if let Some(candidate) = candidate {
+                if current
+                    .as_ref()
+                    .map_or(true, |current| candidate > *current)
+                {
+                    *current = Some(candidate.clone());
+                    watermarks.push(Watermark::new(*col_idx, expr.return_type(), candidate));
+                }
+            }
+        }
+        Ok(watermarks)
+    }
+
+    /// Restores the splits from their latest states, so that they resume from the offsets read
+    /// before, possibly by other actors. The meta service only hands over a split after the states
+    /// persisted by its previous actor are committed.
+    async fn restore_splits(&self, splits: Vec<SplitImpl>) -> Result<Vec<SplitImpl>> {
+        let mut restored = Vec::with_capacity(splits.len());
+        for split in splits {
+            let state = self
+                .split_state_handler
+                .get_latest_state(&split, u64::MAX)
+                .await
+                .to_rw_result()?;
+            restored.push(state.unwrap_or(split));
+        }
+        Ok(restored)
+    }
+
+    /// Moves the splits past the messages read from them.
+    fn update_split_offsets(&mut self, split_offsets: SplitOffsets) -> Result<()> {
+        for (split_id, offset) in split_offsets {
+            if let Some(split) = self
+                .stream_source_splits
+                .iter_mut()
+                .find(|split| split.id() == split_id)
+            {
+                split.update_offset(offset).to_rw_result()?;
+                self.updated_split_ids.insert(split_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Persists the states of the splits read since the last barrier.
+    async fn take_split_snapshot(&mut self, epoch: u64) -> Result<()> {
+        let updated_split_ids = std::mem::take(&mut self.updated_split_ids);
+        let states = self
+            .stream_source_splits
+            .iter()
+            .filter(|split| updated_split_ids.contains(&split.id()))
+            .cloned()
+            .collect_vec();
+        if states.is_empty() {
+            return Ok(());
+        }
+        self.split_state_handler
+            .put_latest_states(states, epoch)
+            .await
+            .to_rw_result()
+    }
+
+    /// Replaces the splits of this actor with the given ones. The splits kept continue from the
+    /// offsets read, while the new ones are restored from their states.
+    async fn apply_split_change(&mut self, splits: &[SplitImpl]) -> Result<Vec<SplitImpl>> {
+        let mut new_splits = Vec::with_capacity(splits.len());
+        for split in splits {
+            match self
+                .stream_source_splits
+                .iter()
+                .find(|current| current.id() == split.id())
+            {
+                Some(current) => new_splits.push(current.clone()),
+                None => new_splits.extend(self.restore_splits(vec![split.clone()]).await?),
+            }
+        }
+        self.stream_source_splits = new_splits.clone();
+        Ok(new_splits)
+    }
+
+    fn gen_row_column(&mut self, len: usize) -> Column {
+        let mut builder = I64ArrayBuilder::new(len).unwrap();
+
+        for _ in 0..len {
+            builder
+                .append(Some(self.next_row_id.fetch_add(1, Ordering::Relaxed) as i64))
+                .unwrap();
+        }
+
+        Column::new(Arc::new(ArrayImpl::from(builder.finish().unwrap())))
+    }
+
+    fn refill_row_id_column(&mut self, chunk: StreamChunk) -> StreamChunk {
+        if let Some(row_id_index) = self.source_desc.row_id_index {
+            let row_id_column_id = self.source_desc.columns[row_id_index as usize].column_id;
+
+            if let Some(idx) = self
+                .column_ids
+                .iter()
+                .position(|column_id| *column_id == row_id_column_id)
+            {
+                let (ops, mut columns, bitmap) = chunk.into_inner();
+                columns[idx] = self.gen_row_column(columns[idx].array().len());
+                return StreamChunk::new(ops, columns, bitmap);
+            }
+        }
+        chunk
+    }
+
+    /// Waits until the source is not paused. Returns false if the sender of the flag is dropped.
+    async fn wait_until_resumed(paused: &mut watch::Receiver<bool>) -> bool {
+        while *paused.borrow() {
+            if paused.changed().await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Reads chunks from the source, along with the generation of the splits they are read in and
+    /// the offsets of the splits read.
+    #[try_stream(ok = (u64, StreamChunk, SplitOffsets), error = RwError)]
+    async fn stream_reader(
+        mut stream_reader: Box<dyn StreamSourceReader>,
+        mut paused: watch::Receiver<bool>,
+        mut splits: watch::Receiver<SplitGeneration>,
+        mut rate_limit: watch::Receiver<Option<u32>>,
+    ) {
+        let mut rate_limiter = RateLimiter::new(*rate_limit.borrow());
+        let mut generation = splits.borrow().0;
+        loop {
+            if !Self::wait_until_resumed(&mut paused).await {
+                break;
+            }
+            // Stop reading until the rows read so far are within the rate limit, so that the
+            // upstream is not consumed faster than that.
+            rate_limiter.wait(&mut rate_limit).await;
+            let chunk = select! {
+                biased;
+                // Reassign the splits once they are changed by a barrier.
+                changed = splits.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let (new_generation, splits) = splits.borrow().clone();
+                    generation = new_generation;
+                    match stream_reader.assign_splits(splits).await {
+                        Ok(()) => continue,
+                        Err(e) => Err(e),
+                    }
+                }
+                chunk = stream_reader.next() => chunk,
+            };
+            // The source may be paused while reading. Hold the chunk until it's resumed, so that
+            // no chunk is emitted between the pause barrier and the resume barrier.
+            if !Self::wait_until_resumed(&mut paused).await {
+                break;
+            }
+            match chunk {
+                Err(e) => {
+                    // TODO: report this error to meta service to mark the actors failed.
+                    error!("hang up stream reader due to polling error: {}", e);
+
+                    // Drop the reader, then the error might be caught by the writer side.
+                    drop(stream_reader);
+                    // Then hang up this stream by breaking the loop.
+                    break;
+                }
+                Ok(chunk) => {
+                    rate_limiter.consume(chunk.cardinality());
+                    yield (generation, chunk, stream_reader.take_split_offsets());
+                }
+            }
+        }
+
+        futures::future::pending().await
+    }
+
+    #[try_stream(ok = Message, error = RwError)]
+    async fn barrier_receiver(mut barrier_receiver: UnboundedReceiver<Message>) {
+        while let Some(msg) = barrier_receiver.recv().await {
+            yield msg;
+        }
+        return Err(RwError::from(InternalError(
+            "barrier reader closed unexpectedly".to_string(),
+        )));
+    }
+
+    fn prio_left(_: &mut ()) -> PollNext {
+        PollNext::Left
+    }
+
+    /// Merges the barriers and the chunks read from the source, where barriers are preferred.
+    fn reader_stream(
+        stream_reader: Box<dyn StreamSourceReader>,
+        barrier_receiver: UnboundedReceiver<Message>,
+        paused: watch::Receiver<bool>,
+        splits: watch::Receiver<SplitGeneration>,
+        rate_limit: watch::Receiver<Option<u32>>,
+    ) -> impl Stream<Item = Either<Result<Message>, Result<(u64, StreamChunk, SplitOffsets)>>> {
+        select_with_strategy(
+            Self::barrier_receiver(barrier_receiver).map(Either::Left),
+            Self::stream_reader(stream_reader, paused, splits, rate_limit).map(Either::Right),
+            Self::prio_left,
+        )
+    }
+
+    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
+    async fn execute_inner(mut self: Box<Self>) {
+        // Resume the splits from the offsets persisted, as they might have been read before the
+        // actor is (re)built.
+        let splits = std::mem::take(&mut self.stream_source_splits);
+        self.stream_source_splits = self
+            .restore_splits(splits)
+            .await
+            .map_err(StreamExecutorError::source_error)?;
+
+        // The reader is built here instead of in `new`, so that the source does not establish
+        // connections to the upstream before the executor is polled.
+        let mut stream_reader = build_stream_reader(
+            self.source_desc.source.clone(),
+            self.operator_id,
+            self.column_ids.clone(),
+            self.split_state_handler.clone(),
+            self.stream_source_splits.clone(),
+        )
+        .await
+        .map_err(StreamExecutorError::source_error)?;
+        stream_reader
+            .open()
+            .await
+            .map_err(StreamExecutorError::source_error)?;
+        let barrier_receiver = self.barrier_receiver.take().unwrap();
+        let (pause_tx, pause_rx) = watch::channel(false);
+        let (split_tx, split_rx) =
+            watch::channel((self.split_generation, self.stream_source_splits.clone()));
+        let (rate_limit_tx, rate_limit_rx) = watch::channel(self.rate_limit);
+
+        #[for_await]
+        for msg in Self::reader_stream(
+            stream_reader,
+            barrier_receiver,
+            pause_rx,
+            split_rx,
+            rate_limit_rx,
+        ) {
+            match msg {
+                // This branch will be preferred.
+                Either::Left(msg) => {
+                    let msg = msg.map_err(StreamExecutorError::source_error)?;
+                    if let Message::Barrier(barrier) = &msg {
+                        self.take_split_snapshot(barrier.epoch.prev)
+                            .await
+                            .map_err(StreamExecutorError::source_error)?;
+
+                        if let Some(paused) = barrier.is_to_pause(self.actor_id) {
+                            // The reader holds the flag until this executor is dropped.
+                            pause_tx.send(paused).unwrap();
+                        }
+
+                        if let Some(rate_limit) = barrier.as_throttle(self.actor_id) {
+                            self.rate_limit = rate_limit;
+                            rate_limit_tx.send(rate_limit).unwrap();
+                        }
+
+                        if let Some(splits) = barrier.as_split_changes(self.actor_id) {
+                            if matches!(self.source_desc.source.as_ref(), SourceImpl::Connector(_))
+                            {
+                                let splits = self
+                                    .apply_split_change(splits)
+                                    .await
+                                    .map_err(StreamExecutorError::source_error)?;
+                                // The chunks read from the previous splits are dropped after
+                                // this barrier, and the splits kept are read again from the
+                                // offsets emitted so far.
+                                self.split_generation += 1;
+                                split_tx.send((self.split_generation, splits)).unwrap();
+                            } else {
+                                warn!(
+                                    "ignore split change of non-connector source {:?}",
+                                    self.source_id
+                                );
+                            }
+                        }
+                    }
+                    yield msg;
+                }
+
+                // If there's barrier, this branch will be deferred.
+                Either::Right(chunk) => {
+                    let (generation, mut chunk, split_offsets) =
+                        chunk.map_err(StreamExecutorError::source_error)?;
+                    if generation != self.split_generation {
+                        continue;
+                    }
+                    self.update_split_offsets(split_offsets)
+                        .map_err(StreamExecutorError::source_error)?;
+
+                    // Refill row id only if not a table source.
+                    // Note(eric): Currently, rows from external sources are filled with row_ids
+                    // here, but rows from tables (by insert statements) are filled in
+                    // InsertExecutor.
+                    //
+                    // TODO: in the future, we may add row_id column here for TableV2 as well
+                    if !matches!(self.source_desc.source.as_ref(), SourceImpl::TableV2(_)) {
+                        chunk = self.refill_row_id_column(chunk);
+                    }
+
+                    self.metrics
+                        .source_output_row_count
+                        .with_label_values(&[self.source_identify.as_str()])
+                        .inc_by(chunk.cardinality() as u64);
+                    let watermarks = self
+                        .generate_watermarks(&chunk)
+                        .map_err(StreamExecutorError::eval_error)?;
+                    yield Message::Chunk(chunk);
+                    for watermark in watermarks {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for SourceExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+impl<S: StateStore> Debug for SourceExecutor<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceExecutor")
+            .field("source_id", &self.source_id)
+            .field("column_ids", &self.column_ids)
+            .field("pk_indices", &self.pk_indices)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use itertools::Itertools;
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::{ArrayImpl, I32Array, I64Array, Op, StreamChunk, Utf8Array};
+    use risingwave_common::array_nonnull;
+    use risingwave_common::catalog::{ColumnDesc, Field, Schema};
+    use risingwave_common::types::DataType;
+    use risingwave_source::*;
+    use risingwave_storage::memory::MemoryStateStore;
+    use tokio::sync::mpsc::unbounded_channel;
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::executor::{Barrier, Epoch, Mutation};
+
+    #[tokio::test]
+    async fn test_table_source() -> Result<()> {
+        let table_id = TableId::default();
+
+        let rowid_type = DataType::Int64;
+        let col1_type = DataType::Int32;
+        let col2_type = DataType::Varchar;
+
+        let table_columns = vec![
+            ColumnDesc {
+                column_id: ColumnId::from(0),
+                data_type: rowid_type.clone(),
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+            ColumnDesc {
+                column_id: ColumnId::from(1),
+                data_type: col1_type.clone(),
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+            ColumnDesc {
+                column_id: ColumnId::from(2),
+                data_type: col2_type.clone(),
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+        ];
+        let source_manager = MemSourceManager::new();
+        source_manager.create_table_source_v2(&table_id, table_columns)?;
+        let source_desc = source_manager.get_source(&table_id)?;
+        let source = source_desc.clone().source;
+
+        // Prepare test data chunks
+        let rowid_arr1: Arc<ArrayImpl> = Arc::new(array_nonnull! { I64Array, [0, 0, 0] }.into());
+        let col1_arr1: Arc<ArrayImpl> = Arc::new(array_nonnull! { I32Array, [1, 2, 3] }.into());
+        let col2_arr1: Arc<ArrayImpl> =
+            Arc::new(array_nonnull! { Utf8Array, ["foo", "bar", "baz"] }.into());
+        let rowid_arr2: Arc<ArrayImpl> = Arc::new(array_nonnull! { I64Array, [0, 0, 0] }.into());
+        let col1_arr2: Arc<ArrayImpl> = Arc::new(array_nonnull! { I32Array, [4, 5, 6] }.into());
+        let col2_arr2: Arc<ArrayImpl> =
+            Arc::new(Utf8Array::from_slice(&[Some("hello"), None, Some("world")])?.into());
+
+        let chunk1 = {
+            let rowid = Column::new(rowid_arr1.clone());
+            let col1 = Column::new(col1_arr1.clone());
+            let col2 = Column::new(col2_arr1.clone());
+            let vis = vec![Op::Insert, Op::Insert, Op::Insert];
+            StreamChunk::new(vis, vec![rowid, col1, col2], None)
+        };
+
+        let chunk2 = {
+            let rowid = Column::new(rowid_arr2.clone());
+            let col1 = Column::new(col1_arr2.clone());
+            let col2 = Column::new(col2_arr2.clone());
+            let vis = vec![Op::Insert, Op::Insert, Op::Insert];
+            StreamChunk::new(vis, vec![rowid, col1, col2], None)
+        };
+
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(rowid_type),
+                Field::unnamed(col1_type),
+                Field::unnamed(col2_type),
+            ],
+        };
+
+        let column_ids = vec![0, 1, 2].into_iter().map(ColumnId::from).collect();
+        let pk_indices = vec![0];
+
+        let (barrier_sender, barrier_receiver) = unbounded_channel();
+        let keyspace = Keyspace::executor_root(MemoryStateStore::new(), 0x2333);
+
+        let source_executor = SourceExecutor::new(
+            table_id,
+            source_desc,
+            keyspace,
+            column_ids,
+            schema,
+            pk_indices,
+            barrier_receiver,
+            1,
+            0,
+            1,
+            Arc::new(StreamingMetrics::new(prometheus::Registry::new())),
+            vec![],
+            vec![],
+            None,
+        );
+        let mut source_executor = Box::new(source_executor).execute();
+
+        let write_chunk = |chunk: StreamChunk| {
+            let source = source.clone();
+            tokio::spawn(async move {
+                let table_source = source.as_table_v2().unwrap();
+                table_source.blocking_write_chunk(chunk).await.unwrap();
+            });
+        };
+
+        barrier_sender
+            .send(Message::Barrier(Barrier {
+                epoch: Epoch::new_test_epoch(1),
+                ..Barrier::default()
+            }))
+            .unwrap();
+
+        // Write 1st chunk
+        write_chunk(chunk1);
+
+        for _ in 0..2 {
+            match source_executor.next().await.unwrap().unwrap() {
+                Message::Chunk(chunk) => {
+                    assert_eq!(3, chunk.columns().len());
+                    assert_eq!(
+                        col1_arr1.iter().collect_vec(),
+                        chunk.column_at(1).array_ref().iter().collect_vec(),
+                    );
+                    assert_eq!(
+                        col2_arr1.iter().collect_vec(),
+                        chunk.column_at(2).array_ref().iter().collect_vec()
+                    );
+                    assert_eq!(vec![Op::Insert; 3], chunk.ops());
+                }
+                Message::Barrier(barrier) => {
+                    assert_eq!(barrier.epoch, Epoch::new_test_epoch(1))
+                }
+                Message::Watermark(_) => unreachable!(),
+            }
+        }
+
+        // Write 2nd chunk
+        write_chunk(chunk2);
+
+        if let Message::Chunk(chunk) = source_executor.next().await.unwrap().unwrap() {
+            assert_eq!(3, chunk.columns().len());
+            assert_eq!(
+                col1_arr2.iter().collect_vec(),
+                chunk.column_at(1).array_ref().iter().collect_vec()
+            );
+            assert_eq!(
+                col2_arr2.iter().collect_vec(),
+                chunk.column_at(2).array_ref().iter().collect_vec()
+            );
+            assert_eq!(vec![Op::Insert; 3], chunk.ops());
+        } else {
+            unreachable!();
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_dropped() -> Result<()> {
+        let table_id = TableId::default();
+
+        let rowid_type = DataType::Int64;
+        let col1_type = DataType::Int32;
+        let col2_type = DataType::Varchar;
+
+        let table_columns = vec![
+            ColumnDesc {
+                column_id: ColumnId::from(0),
+                data_type: rowid_type.clone(),
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+            ColumnDesc {
+                column_id: ColumnId::from(1),
+                data_type: col1_type.clone(),
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+            ColumnDesc {
+                column_id: ColumnId::from(2),
+                data_type: col2_type.clone(),
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+        ];
+        let source_manager = MemSourceManager::new();
+        source_manager.create_table_source_v2(&table_id, table_columns)?;
+        let source_desc = source_manager.get_source(&table_id)?;
+        let source = source_desc.clone().source;
+
+        // Prepare test data chunks
+        let rowid_arr1: Arc<ArrayImpl> = Arc::new(array_nonnull! { I64Array, [0, 0, 0] }.into());
+        let col1_arr1: Arc<ArrayImpl> = Arc::new(array_nonnull! { I32Array, [1, 2, 3] }.into());
+        let col2_arr1: Arc<ArrayImpl> =
+            Arc::new(array_nonnull! { Utf8Array, ["foo", "bar", "baz"] }.into());
+
+        let chunk = {
+            let rowid = Column::new(rowid_arr1.clone());
+            let col1 = Column::new(col1_arr1.clone());
+            let col2 = Column::new(col2_arr1.clone());
+            let vis = vec![Op::Insert, Op::Insert, Op::Insert];
+            StreamChunk::new(vis, vec![rowid, col1, col2], None)
+        };
+
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(rowid_type),
+                Field::unnamed(col1_type),
+                Field::unnamed(col2_type),
+            ],
+        };
+
+        let column_ids = vec![0.into(), 1.into(), 2.into()];
+        let pk_indices = vec![0];
+
+        let (barrier_sender, barrier_receiver) = unbounded_channel();
+        let keyspace = Keyspace::executor_root(MemoryStateStore::new(), 0x2333);
+        let source_executor = SourceExecutor::new(
+            table_id,
+            source_desc,
+            keyspace,
+            column_ids,
+            schema,
+            pk_indices,
+            barrier_receiver,
+            1,
+            0,
+            1,
+            Arc::new(StreamingMetrics::unused()),
+            vec![],
+            vec![],
+            None,
+        );
+        let mut source_executor = Box::new(source_executor).execute();
+
+        let write_chunk = |chunk: StreamChunk| {
+            let source = source.clone();
+            tokio::spawn(async move {
+                let table_source = source.as_table_v2().unwrap();
+                table_source.blocking_write_chunk(chunk).await.unwrap();
+            });
+        };
+
+        write_chunk(chunk.clone());
+
+        barrier_sender
+            .send(Message::Barrier(
+                Barrier::new_test_barrier(1).with_mutation(Mutation::Stop(HashSet::default())),
+            ))
+            .unwrap();
+
+        source_executor.next().await.unwrap().unwrap();
+        source_executor.next().await.unwrap().unwrap();
+        write_chunk(chunk);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_source_pause_resume() -> Result<()> {
+        let table_id = TableId::default();
+
+        let table_columns = vec![
+            ColumnDesc {
+                column_id: ColumnId::from(0),
+                data_type: DataType::Int64,
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+            ColumnDesc {
+                column_id: ColumnId::from(1),
+                data_type: DataType::Int32,
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+        ];
+        let source_manager = MemSourceManager::new();
+        source_manager.create_table_source_v2(&table_id, table_columns)?;
+        let source_desc = source_manager.get_source(&table_id)?;
+        let source = source_desc.clone().source;
+
+        let chunk = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                Column::new(Arc::new(array_nonnull! { I64Array, [0, 0] }.into())),
+                Column::new(Arc::new(array_nonnull! { I32Array, [1, 2] }.into())),
+            ],
+            None,
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int32),
+            ],
+        };
+
+        let (barrier_sender, barrier_receiver) = unbounded_channel();
+        let keyspace = Keyspace::executor_root(MemoryStateStore::new(), 0x2333);
+        let source_executor = SourceExecutor::new(
+            table_id,
+            source_desc,
+            keyspace,
+            vec![0.into(), 1.into()],
+            schema,
+            vec![0],
+            barrier_receiver,
+            1,
+            0,
+            1,
+            Arc::new(StreamingMetrics::unused()),
+            vec![],
+            vec![],
+            None,
+        );
+        let mut source_executor = Box::new(source_executor).execute();
+
+        barrier_sender
+            .send(Message::Barrier(
+                Barrier::new_test_barrier(1).with_mutation(Mutation::Pause(HashSet::from([0]))),
+            ))
+            .unwrap();
+        tokio::spawn(async move {
+            let table_source = source.as_table_v2().unwrap();
+            table_source.blocking_write_chunk(chunk).await.unwrap();
+        });
+
+        // Barriers keep flowing while the source is paused, but the written chunk is held back.
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_barrier().unwrap().epoch, Epoch::new_test_epoch(1));
+        barrier_sender
+            .send(Message::Barrier(Barrier::new_test_barrier(2)))
+            .unwrap();
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_barrier().unwrap().epoch, Epoch::new_test_epoch(2));
+
+        barrier_sender
+            .send(Message::Barrier(
+                Barrier::new_test_barrier(3).with_mutation(Mutation::Resume(HashSet::from([0]))),
+            ))
+            .unwrap();
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_barrier().unwrap().epoch, Epoch::new_test_epoch(3));
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_chunk().unwrap().cardinality(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_source_start_paused() -> Result<()> {
+        let table_id = TableId::default();
+
+        let table_columns = vec![
+            ColumnDesc {
+                column_id: ColumnId::from(0),
+                data_type: DataType::Int64,
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+            ColumnDesc {
+                column_id: ColumnId::from(1),
+                data_type: DataType::Int32,
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+        ];
+        let source_manager = MemSourceManager::new();
+        source_manager.create_table_source_v2(&table_id, table_columns)?;
+        let source_desc = source_manager.get_source(&table_id)?;
+        let source = source_desc.clone().source;
+
+        let chunk = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                Column::new(Arc::new(array_nonnull! { I64Array, [0, 0] }.into())),
+                Column::new(Arc::new(array_nonnull! { I32Array, [1, 2] }.into())),
+            ],
+            None,
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int32),
+            ],
+        };
+
+        let (barrier_sender, barrier_receiver) = unbounded_channel();
+        let keyspace = Keyspace::executor_root(MemoryStateStore::new(), 0x2333);
+        let source_executor = SourceExecutor::new(
+            table_id,
+            source_desc,
+            keyspace,
+            vec![0.into(), 1.into()],
+            schema,
+            vec![0],
+            barrier_receiver,
+            1,
+            0,
+            1,
+            Arc::new(StreamingMetrics::unused()),
+            vec![],
+            vec![],
+            None,
+        );
+        let mut source_executor = Box::new(source_executor).execute();
+
+        // The source actor added by the first barrier reads a paused source, so it starts paused.
+        barrier_sender
+            .send(Message::Barrier(
+                Barrier::new_test_barrier(1).with_mutation(Mutation::AddOutput {
+                    adds: HashMap::new(),
+                    paused_actors: HashSet::from([0]),
+                }),
+            ))
+            .unwrap();
+        tokio::spawn(async move {
+            let table_source = source.as_table_v2().unwrap();
+            table_source.blocking_write_chunk(chunk).await.unwrap();
+        });
+
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_barrier().unwrap().epoch, Epoch::new_test_epoch(1));
+        barrier_sender
+            .send(Message::Barrier(Barrier::new_test_barrier(2)))
+            .unwrap();
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_barrier().unwrap().epoch, Epoch::new_test_epoch(2));
+
+        barrier_sender
+            .send(Message::Barrier(
+                Barrier::new_test_barrier(3).with_mutation(Mutation::Resume(HashSet::from([0]))),
+            ))
+            .unwrap();
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_barrier().unwrap().epoch, Epoch::new_test_epoch(3));
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_chunk().unwrap().cardinality(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_table_source_throttle() -> Result<()> {
+        let table_id = TableId::default();
+
+        let table_columns = vec![
+            ColumnDesc {
+                column_id: ColumnId::from(0),
+                data_type: DataType::Int64,
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+            ColumnDesc {
+                column_id: ColumnId::from(1),
+                data_type: DataType::Int32,
+                name: String::new(),
+                field_descs: vec![],
+                type_name: "".to_string(),
+            },
+        ];
+        let source_manager = MemSourceManager::new();
+        source_manager.create_table_source_v2(&table_id, table_columns)?;
+        let source_desc = source_manager.get_source(&table_id)?;
+        let source = source_desc.clone().source;
+
+        let chunk = StreamChunk::new(
+            vec![Op::Insert, Op::Insert],
+            vec![
+                Column::new(Arc::new(array_nonnull! { I64Array, [0, 0] }.into())),
+                Column::new(Arc::new(array_nonnull! { I32Array, [1, 2] }.into())),
+            ],
+            None,
+        );
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int32),
+            ],
+        };
+
+        let (barrier_sender, barrier_receiver) = unbounded_channel();
+        let keyspace = Keyspace::executor_root(MemoryStateStore::new(), 0x2333);
+        // The actor 0 reads 1 row per second.
+        let source_executor = SourceExecutor::new(
+            table_id,
+            source_desc,
+            keyspace,
+            vec![0.into(), 1.into()],
+            schema,
+            vec![0],
+            barrier_receiver,
+            1,
+            0,
+            1,
+            Arc::new(StreamingMetrics::unused()),
+            vec![],
+            vec![],
+            Some(1),
+        );
+        let mut source_executor = Box::new(source_executor).execute();
+
+        tokio::spawn(async move {
+            let table_source = source.as_table_v2().unwrap();
+            table_source
+                .blocking_write_chunk(chunk.clone())
+                .await
+                .unwrap();
+            table_source.blocking_write_chunk(chunk).await.unwrap();
+        });
+
+        // The first chunk is read at once, but the second one has to wait for 2 seconds.
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_chunk().unwrap().cardinality(), 2);
+        assert!(timeout(Duration::from_millis(500), source_executor.next())
+            .await
+            .is_err());
+
+        // Barriers are not throttled, and the limit removed takes effect immediately.
+        barrier_sender
+            .send(Message::Barrier(
+                Barrier::new_test_barrier(1)
+                    .with_mutation(Mutation::Throttle(HashMap::from([(0, None)]))),
+            ))
+            .unwrap();
+        let msg = source_executor.next().await.unwrap().unwrap();
+        assert_eq!(msg.as_barrier().unwrap().epoch, Epoch::new_test_epoch(1));
+        let msg = timeout(Duration::from_millis(500), source_executor.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.as_chunk().unwrap().cardinality(), 2);
+
+        Ok(())
+    }
+}
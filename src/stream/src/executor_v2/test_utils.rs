@@ -19,15 +19,19 @@ use futures_async_stream::try_stream;
 use risingwave_common::catalog::Schema;
 use risingwave_storage::memory::MemoryStateStore;
 use risingwave_storage::Keyspace;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
 use super::error::TracedStreamExecutorError;
-use super::{Barrier, Executor, Message, Mutation, PkIndices, StreamChunk};
+use super::{Barrier, Executor, Message, Mutation, PkIndices, StreamChunk, Watermark};
 
 pub struct MockSource {
     schema: Schema,
     pk_indices: PkIndices,
     msgs: VecDeque<Message>,
 
+    /// Messages pushed through the [`MessageSender`] after `msgs` are consumed, if any.
+    rx: Option<UnboundedReceiver<Message>>,
+
     /// Whether to send a `Stop` barrier on stream finish.
     stop_on_finish: bool,
 }
@@ -48,6 +52,7 @@ impl MockSource {
             schema,
             pk_indices,
             msgs: VecDeque::default(),
+            rx: None,
             stop_on_finish: true,
         }
     }
@@ -58,6 +63,7 @@ impl MockSource {
             schema,
             pk_indices,
             msgs: msgs.into(),
+            rx: None,
             stop_on_finish: true,
         }
     }
@@ -67,10 +73,26 @@ impl MockSource {
             schema,
             pk_indices,
             msgs: chunks.into_iter().map(Message::Chunk).collect(),
+            rx: None,
             stop_on_finish: true,
         }
     }
 
+    /// Returns a source whose messages are pushed by the returned sender. The stream ends when
+    /// the sender is dropped.
+    #[allow(dead_code)]
+    pub fn channel(schema: Schema, pk_indices: PkIndices) -> (MessageSender, Self) {
+        let (tx, rx) = unbounded_channel();
+        let source = Self {
+            schema,
+            pk_indices,
+            msgs: VecDeque::default(),
+            rx: Some(rx),
+            stop_on_finish: true,
+        };
+        (MessageSender(tx), source)
+    }
+
     #[allow(dead_code)]
     #[must_use]
     pub fn stop_on_finish(self, stop_on_finish: bool) -> Self {
@@ -93,6 +115,38 @@ impl MockSource {
         }
         self.msgs.push_back(Message::Barrier(barrier));
     }
+
+    pub fn push_watermark(&mut self, watermark: Watermark) {
+        self.msgs.push_back(Message::Watermark(watermark));
+    }
+}
+
+/// Pushes messages to a [`MockSource`] created by [`MockSource::channel`].
+pub struct MessageSender(UnboundedSender<Message>);
+
+impl MessageSender {
+    #[allow(dead_code)]
+    pub fn push_chunk(&mut self, chunk: StreamChunk) {
+        self.0.send(Message::Chunk(chunk)).expect("source dropped");
+    }
+
+    #[allow(dead_code)]
+    pub fn push_barrier(&mut self, epoch: u64, stop: bool) {
+        let mut barrier = Barrier::new_test_barrier(epoch);
+        if stop {
+            barrier = barrier.with_mutation(Mutation::Stop(HashSet::default()));
+        }
+        self.0
+            .send(Message::Barrier(barrier))
+            .expect("source dropped");
+    }
+
+    #[allow(dead_code)]
+    pub fn push_watermark(&mut self, watermark: Watermark) {
+        self.0
+            .send(Message::Watermark(watermark))
+            .expect("source dropped");
+    }
 }
 
 impl MockSource {
@@ -105,6 +159,13 @@ impl MockSource {
             yield msg
         }
 
+        if let Some(mut rx) = self.rx {
+            while let Some(msg) = rx.recv().await {
+                epoch += 1;
+                yield msg
+            }
+        }
+
         if self.stop_on_finish {
             yield Message::Barrier(
                 Barrier::new_test_barrier(epoch).with_mutation(Mutation::Stop(HashSet::default())),
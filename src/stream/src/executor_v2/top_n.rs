@@ -27,6 +27,7 @@ use crate::executor::managed_state::top_n::{ManagedTopNBottomNState, ManagedTopN
 use crate::executor_v2::error::{StreamExecutorError, StreamExecutorResult};
 use crate::executor_v2::top_n_executor::{generate_output, TopNExecutorBase, TopNExecutorWrapper};
 use crate::executor_v2::{BoxedMessageStream, Executor, ExecutorInfo, PkIndices, PkIndicesRef};
+use crate::task::MemoryUsageReporter;
 
 /// `TopNExecutor` works with input with modification, it keeps all the data
 /// records/rows that have been seen, and returns topN records overall.
@@ -64,6 +65,11 @@ impl<S: StateStore> TopNExecutor<S> {
             )?,
         })
     }
+
+    pub fn with_memory_reporter(mut self, memory_reporter: MemoryUsageReporter) -> Self {
+        self.inner.memory_reporter = Some(memory_reporter);
+        self
+    }
 }
 
 pub struct InnerTopNExecutor<S: StateStore> {
@@ -95,6 +101,9 @@ pub struct InnerTopNExecutor<S: StateStore> {
     #[allow(dead_code)]
     /// Indices of the columns on which key distribution depends.
     key_indices: Vec<usize>,
+
+    /// Reports the memory used by the cached rows, if set.
+    memory_reporter: Option<MemoryUsageReporter>,
 }
 
 impl<S: StateStore> InnerTopNExecutor<S> {
@@ -173,6 +182,7 @@ impl<S: StateStore> InnerTopNExecutor<S> {
             pk_indices,
             first_execution: true,
             key_indices,
+            memory_reporter: None,
         })
     }
 
@@ -190,6 +200,23 @@ impl<S: StateStore> InnerTopNExecutor<S> {
             .await
             .map_err(StreamExecutorError::top_n_state_error)
     }
+
+    /// Drops the cached rows if the memory manager asks to evict. The caches are all-or-nothing,
+    /// so they're reloaded from the state store on the next chunk.
+    fn evict(&mut self) {
+        if let Some(memory_reporter) = &self.memory_reporter {
+            let size = self.managed_lowest_state.estimated_size()
+                + self.managed_middle_state.estimated_size()
+                + self.managed_highest_state.estimated_size();
+            if memory_reporter.report(size).is_some() {
+                self.managed_lowest_state.clear_cache();
+                self.managed_middle_state.clear_cache();
+                self.managed_highest_state.clear_cache();
+                self.first_execution = true;
+                memory_reporter.report(0);
+            }
+        }
+    }
 }
 
 impl<S: StateStore> Executor for InnerTopNExecutor<S> {
@@ -441,12 +468,16 @@ impl<S: StateStore> TopNExecutorBase for InnerTopNExecutor<S> {
     }
 
     async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<()> {
-        self.flush_inner(epoch).await
+        self.flush_inner(epoch).await?;
+        self.evict();
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use assert_matches::assert_matches;
     use futures::StreamExt;
     use itertools::Itertools;
@@ -457,8 +488,10 @@ mod tests {
     use risingwave_common::util::sort_util::OrderType;
 
     use super::*;
+    use crate::executor::monitor::StreamingMetrics;
     use crate::executor_v2::test_utils::{create_in_memory_keyspace, MockSource};
     use crate::executor_v2::{Barrier, Message};
+    use crate::task::MemoryManager;
 
     fn create_stream_chunks() -> Vec<StreamChunk> {
         let chunk1 = StreamChunk::new(
@@ -927,4 +960,59 @@ mod tests {
             Message::Barrier(_)
         );
     }
+
+    #[tokio::test]
+    async fn test_top_n_executor_evicted_by_memory_manager() {
+        let create_executor = |memory_reporter: Option<MemoryUsageReporter>| {
+            let executor = TopNExecutor::new(
+                create_source() as Box<dyn Executor>,
+                create_order_types(),
+                (1, Some(2)),
+                vec![0, 1],
+                create_in_memory_keyspace(),
+                Some(2),
+                (0, 0, 0),
+                1,
+                vec![],
+            )
+            .unwrap();
+            match memory_reporter {
+                Some(memory_reporter) => executor.with_memory_reporter(memory_reporter),
+                None => executor,
+            }
+        };
+
+        // With no memory budget, the caches are dropped on every barrier.
+        let memory_manager = Arc::new(MemoryManager::new(0, Arc::new(StreamingMetrics::unused())));
+        let evicted: Vec<_> = Box::new(create_executor(Some(memory_manager.register(1, 1))))
+            .execute()
+            .collect()
+            .await;
+        let expected: Vec<_> = Box::new(create_executor(None)).execute().collect().await;
+
+        assert_eq!(evicted.len(), expected.len());
+        for (evicted, expected) in evicted.into_iter().zip_eq(expected) {
+            match (evicted.unwrap(), expected.unwrap()) {
+                (Message::Chunk(evicted), Message::Chunk(expected)) => {
+                    assert_eq!(evicted.ops(), expected.ops());
+                    assert_eq!(
+                        evicted
+                            .column_at(0)
+                            .array_ref()
+                            .as_int64()
+                            .iter()
+                            .collect_vec(),
+                        expected
+                            .column_at(0)
+                            .array_ref()
+                            .as_int64()
+                            .iter()
+                            .collect_vec()
+                    );
+                }
+                (Message::Barrier(_), Message::Barrier(_)) => {}
+                _ => unreachable!(),
+            }
+        }
+    }
 }
@@ -29,6 +29,7 @@ use crate::executor::managed_state::top_n::ManagedTopNState;
 use crate::executor_v2::error::{StreamExecutorError, StreamExecutorResult};
 use crate::executor_v2::top_n_executor::{generate_output, TopNExecutorBase, TopNExecutorWrapper};
 use crate::executor_v2::{BoxedMessageStream, Executor, ExecutorInfo, PkIndices, PkIndicesRef};
+use crate::task::MemoryUsageReporter;
 
 /// If the input contains only append, `AppendOnlyTopNExecutor` does not need
 /// to keep all the data records/rows that have been seen. As long as a record
@@ -69,6 +70,11 @@ impl<S: StateStore> AppendOnlyTopNExecutor<S> {
             )?,
         })
     }
+
+    pub fn with_memory_reporter(mut self, memory_reporter: MemoryUsageReporter) -> Self {
+        self.inner.memory_reporter = Some(memory_reporter);
+        self
+    }
 }
 
 pub struct InnerAppendOnlyTopNExecutor<S: StateStore> {
@@ -99,6 +105,9 @@ pub struct InnerAppendOnlyTopNExecutor<S: StateStore> {
     #[allow(dead_code)]
     /// Indices of the columns on which key distribution depends.
     key_indices: Vec<usize>,
+
+    /// Reports the memory used by the cached rows, if set.
+    memory_reporter: Option<MemoryUsageReporter>,
 }
 
 impl<S: StateStore> InnerAppendOnlyTopNExecutor<S> {
@@ -165,6 +174,7 @@ impl<S: StateStore> InnerAppendOnlyTopNExecutor<S> {
             pk_indices,
             first_execution: true,
             key_indices,
+            memory_reporter: None,
         })
     }
 
@@ -178,6 +188,21 @@ impl<S: StateStore> InnerAppendOnlyTopNExecutor<S> {
             .await
             .map_err(StreamExecutorError::top_n_state_error)
     }
+
+    /// Drops the cached rows if the memory manager asks to evict. The caches are all-or-nothing,
+    /// so they're reloaded from the state store on the next chunk.
+    fn evict(&mut self) {
+        if let Some(memory_reporter) = &self.memory_reporter {
+            let size = self.managed_lower_state.estimated_size()
+                + self.managed_higher_state.estimated_size();
+            if memory_reporter.report(size).is_some() {
+                self.managed_lower_state.clear_cache();
+                self.managed_higher_state.clear_cache();
+                self.first_execution = true;
+                memory_reporter.report(0);
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -330,7 +355,9 @@ impl<S: StateStore> TopNExecutorBase for InnerAppendOnlyTopNExecutor<S> {
     }
 
     async fn flush_data(&mut self, epoch: u64) -> StreamExecutorResult<()> {
-        self.flush_inner(epoch).await
+        self.flush_inner(epoch).await?;
+        self.evict();
+        Ok(())
     }
 }
 
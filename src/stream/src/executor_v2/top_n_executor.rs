@@ -110,6 +110,8 @@ where
                 Message::Chunk(chunk) => {
                     yield Message::Chunk(self.inner.apply_chunk(chunk, epoch).await?)
                 }
+                // Top-N may emit any row at any time, so watermarks can not be propagated.
+                Message::Watermark(_) => {}
                 Message::Barrier(barrier) => {
                     self.inner.flush_data(epoch).await?;
                     epoch = barrier.epoch.curr;
@@ -16,29 +16,11 @@ use std::fmt;
 
 use async_trait::async_trait;
 use futures::StreamExt;
-use futures_async_stream::try_stream;
-use risingwave_common::catalog::ColumnId;
 pub use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_common::hash::HashKey;
-use risingwave_common::util::sort_util::{OrderPair, OrderType};
-use risingwave_expr::expr::BoxedExpression;
-use risingwave_storage::table::cell_based_table::CellBasedTable;
-use risingwave_storage::{Keyspace, StateStore};
 
-use super::error::{StreamExecutorError, TracedStreamExecutorError};
-use super::filter::SimpleFilterExecutor;
-use super::project::SimpleProjectExecutor;
-use super::{
-    BatchQueryExecutor, BoxedExecutor, ChainExecutor, Executor, ExecutorInfo, FilterExecutor,
-    HashAggExecutor, LocalSimpleAggExecutor, MaterializeExecutor, ProjectExecutor,
-};
+use super::{BoxedExecutor, ExecutorInfo};
 pub use super::{BoxedMessageStream, ExecutorV1, Message, PkIndices, PkIndicesRef};
-use crate::executor::AggCall;
-use crate::executor_v2::global_simple_agg::SimpleAggExecutor;
-use crate::executor_v2::top_n::TopNExecutor;
-use crate::executor_v2::top_n_appendonly::AppendOnlyTopNExecutor;
-use crate::task::FinishCreateMviewNotifier;
 
 /// The struct wraps a [`BoxedMessageStream`] and implements the interface of [`ExecutorV1`].
 ///
@@ -95,265 +77,3 @@ impl ExecutorV1 for StreamExecutorV1 {
         Ok(())
     }
 }
-
-pub struct ExecutorV1AsV2(pub Box<dyn ExecutorV1>);
-
-impl Executor for ExecutorV1AsV2 {
-    fn execute(self: Box<Self>) -> BoxedMessageStream {
-        self.execute_inner().boxed()
-    }
-
-    fn schema(&self) -> &Schema {
-        self.0.schema()
-    }
-
-    fn pk_indices(&self) -> PkIndicesRef {
-        self.0.pk_indices()
-    }
-
-    fn identity(&self) -> &str {
-        self.0.identity()
-    }
-
-    fn execute_with_epoch(mut self: Box<Self>, epoch: u64) -> BoxedMessageStream {
-        self.0.init(epoch).expect("failed to init executor epoch");
-        self.execute()
-    }
-}
-
-impl ExecutorV1AsV2 {
-    #[try_stream(ok = Message, error = TracedStreamExecutorError)]
-    async fn execute_inner(mut self: Box<Self>) {
-        loop {
-            let msg = self.0.next().await;
-            match msg {
-                // For snapshot input of `Chain`, we use `Eof` to represent the end of stream.
-                Err(e) if matches!(e.inner(), ErrorCode::Eof) => break,
-                _ => yield msg.map_err(StreamExecutorError::executor_v1)?,
-            }
-        }
-    }
-}
-
-impl FilterExecutor {
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        expr: BoxedExpression,
-        executor_id: u64,
-        _op_info: String,
-    ) -> Self {
-        let info = ExecutorInfo {
-            schema: input.schema().to_owned(),
-            pk_indices: input.pk_indices().to_owned(),
-            identity: "Filter".to_owned(),
-        };
-        let input = Box::new(ExecutorV1AsV2(input));
-        super::SimpleExecutorWrapper {
-            input,
-            inner: SimpleFilterExecutor::new(info, expr, executor_id),
-        }
-    }
-}
-
-impl ProjectExecutor {
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        pk_indices: PkIndices,
-        exprs: Vec<BoxedExpression>,
-        executor_id: u64,
-        _op_info: String,
-    ) -> Self {
-        let info = ExecutorInfo {
-            schema: input.schema().to_owned(),
-            pk_indices,
-            identity: "Project".to_owned(),
-        };
-        let input = Box::new(ExecutorV1AsV2(input));
-        super::SimpleExecutorWrapper {
-            input,
-            inner: SimpleProjectExecutor::new(info, exprs, executor_id),
-        }
-    }
-}
-
-impl ChainExecutor {
-    pub fn new_from_v1(
-        snapshot: Box<dyn ExecutorV1>,
-        mview: Box<dyn ExecutorV1>,
-        notifier: FinishCreateMviewNotifier,
-        schema: Schema,
-        column_idxs: Vec<usize>,
-        _op_info: String,
-    ) -> Self {
-        let info = ExecutorInfo {
-            schema,
-            pk_indices: mview.pk_indices().to_owned(),
-            identity: "Chain".to_owned(),
-        };
-
-        let actor_id = notifier.actor_id;
-
-        Self::new(
-            Box::new(ExecutorV1AsV2(snapshot)),
-            Box::new(ExecutorV1AsV2(mview)),
-            column_idxs,
-            notifier,
-            actor_id,
-            info,
-        )
-    }
-}
-
-impl<S: StateStore> MaterializeExecutor<S> {
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        keyspace: Keyspace<S>,
-        keys: Vec<OrderPair>,
-        column_ids: Vec<ColumnId>,
-        executor_id: u64,
-        _op_info: String,
-        key_indices: Vec<usize>,
-    ) -> Self {
-        Self::new(
-            Box::new(ExecutorV1AsV2(input)),
-            keyspace,
-            keys,
-            column_ids,
-            executor_id,
-            key_indices,
-        )
-    }
-}
-
-impl LocalSimpleAggExecutor {
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        agg_calls: Vec<AggCall>,
-        pk_indices: PkIndices,
-        executor_id: u64,
-        _op_info: String,
-    ) -> Result<Self> {
-        let input = Box::new(ExecutorV1AsV2(input));
-        Self::new(input, agg_calls, pk_indices, executor_id)
-    }
-}
-
-impl<S: StateStore> SimpleAggExecutor<S> {
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        agg_calls: Vec<AggCall>,
-        keyspace: Keyspace<S>,
-        pk_indices: PkIndices,
-        executor_id: u64,
-        _op_info: String,
-        key_indices: Vec<usize>,
-    ) -> Result<Self> {
-        let input = Box::new(ExecutorV1AsV2(input));
-        Self::new(
-            input,
-            agg_calls,
-            keyspace,
-            pk_indices,
-            executor_id,
-            key_indices,
-        )
-    }
-}
-
-impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        agg_calls: Vec<AggCall>,
-        key_indices: Vec<usize>,
-        keyspace: Keyspace<S>,
-        pk_indices: PkIndices,
-        executor_id: u64,
-        _op_info: String,
-    ) -> Result<Self> {
-        let input = Box::new(ExecutorV1AsV2(input));
-        Self::new(
-            input,
-            agg_calls,
-            keyspace,
-            pk_indices,
-            executor_id,
-            key_indices,
-        )
-    }
-}
-
-impl<S: StateStore> BatchQueryExecutor<S> {
-    pub fn new_from_v1(
-        table: CellBasedTable<S>,
-        pk_indices: PkIndices,
-        _op_info: String,
-        key_indices: Vec<usize>,
-    ) -> Self {
-        let schema = table.schema().clone();
-        let info = ExecutorInfo {
-            schema,
-            pk_indices,
-            identity: "BatchQuery".to_owned(),
-        };
-
-        Self::new(table, Self::DEFAULT_BATCH_SIZE, info, key_indices)
-    }
-}
-
-impl<S: StateStore> TopNExecutor<S> {
-    #[allow(clippy::too_many_arguments)]
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        pk_order_types: Vec<OrderType>,
-        offset_and_limit: (usize, Option<usize>),
-        pk_indices: PkIndices,
-        keyspace: Keyspace<S>,
-        cache_size: Option<usize>,
-        total_count: (usize, usize, usize),
-        executor_id: u64,
-        _op_info: String,
-        key_indices: Vec<usize>,
-    ) -> Result<Self> {
-        let input = Box::new(ExecutorV1AsV2(input));
-        Self::new(
-            input,
-            pk_order_types,
-            offset_and_limit,
-            pk_indices,
-            keyspace,
-            cache_size,
-            total_count,
-            executor_id,
-            key_indices,
-        )
-    }
-}
-
-impl<S: StateStore> AppendOnlyTopNExecutor<S> {
-    #[allow(clippy::too_many_arguments)]
-    pub fn new_from_v1(
-        input: Box<dyn ExecutorV1>,
-        pk_order_types: Vec<OrderType>,
-        offset_and_limit: (usize, Option<usize>),
-        pk_indices: PkIndices,
-        keyspace: Keyspace<S>,
-        cache_size: Option<usize>,
-        total_count: (usize, usize),
-        executor_id: u64,
-        _op_info: String,
-        key_indices: Vec<usize>,
-    ) -> Result<Self> {
-        let input = Box::new(ExecutorV1AsV2(input));
-        Self::new(
-            input,
-            pk_order_types,
-            offset_and_limit,
-            pk_indices,
-            keyspace,
-            cache_size,
-            total_count,
-            executor_id,
-            key_indices,
-        )
-    }
-}
@@ -15,8 +15,11 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use risingwave_common::error::Result;
-use risingwave_pb::stream_service::inject_barrier_response::FinishedCreateMview as ProstFinishedCreateMview;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::stream_service::inject_barrier_response::{
+    CreateMviewProgress as ProstCreateMviewProgress,
+    FinishedCreateMview as ProstFinishedCreateMview,
+};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot;
 
@@ -52,7 +55,30 @@ impl From<FinishedCreateMview> for ProstFinishedCreateMview {
     }
 }
 
-/// To notify about the finish of an DDL with the `u64` epoch.
+/// Represents that the actor with `actor_id` has consumed `consumed_rows` rows from the snapshot,
+/// for the Create MV DDL with `epoch`.
+#[derive(Debug)]
+pub struct CreateMviewProgress {
+    /// The epoch of the configuration change barrier for this DDL.
+    pub epoch: u64,
+
+    /// The id of the actor reading the snapshot.
+    pub actor_id: ActorId,
+
+    pub consumed_rows: u64,
+}
+
+impl From<CreateMviewProgress> for ProstCreateMviewProgress {
+    fn from(p: CreateMviewProgress) -> Self {
+        Self {
+            epoch: p.epoch,
+            actor_id: p.actor_id,
+            consumed_rows: p.consumed_rows,
+        }
+    }
+}
+
+/// To notify about the progress and the finish of an DDL with the `u64` epoch.
 pub struct FinishCreateMviewNotifier {
     pub barrier_manager: Arc<parking_lot::Mutex<LocalBarrierManager>>,
     pub actor_id: ActorId,
@@ -64,6 +90,12 @@ impl FinishCreateMviewNotifier {
             .lock()
             .finish_create_mview(ddl_epoch, self.actor_id);
     }
+
+    pub fn update_progress(&self, ddl_epoch: u64, consumed_rows: u64) {
+        self.barrier_manager
+            .lock()
+            .update_create_mview_progress(ddl_epoch, self.actor_id, consumed_rows);
+    }
 }
 
 impl std::fmt::Debug for FinishCreateMviewNotifier {
@@ -74,11 +106,47 @@ impl std::fmt::Debug for FinishCreateMviewNotifier {
     }
 }
 
+/// To report the net number of rows written into a materialized view, which estimates the size
+/// of the snapshot read by the materialized views created on it.
+pub struct TableRowCountReporter {
+    pub barrier_manager: Arc<parking_lot::Mutex<LocalBarrierManager>>,
+    pub table_id: u32,
+}
+
+impl TableRowCountReporter {
+    pub fn report(&self, delta: i64) {
+        if delta != 0 {
+            self.barrier_manager
+                .lock()
+                .report_table_row_count(self.table_id, delta);
+        }
+    }
+}
+
+impl std::fmt::Debug for TableRowCountReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TableRowCountReporter")
+            .field("table_id", &self.table_id)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Collect result of some barrier on current compute node. Will be reported to the meta service.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CollectResult {
     /// Finished Create MV DDLs in current epoch.
     pub finished_create_mviews: Vec<FinishedCreateMview>,
+
+    /// The latest progress of the Create MV DDLs not finished yet.
+    pub create_mview_progress: Vec<CreateMviewProgress>,
+
+    /// table id => the net number of rows written into the materialized view since the last
+    /// collection.
+    pub table_row_count_deltas: HashMap<u32, i64>,
+
+    /// Actors failed before collecting this barrier. If not empty, the barrier is not collected
+    /// and will be collected again by the recovery of the meta service.
+    pub failed_actors: Vec<ActorId>,
 }
 
 enum BarrierState {
@@ -181,11 +249,19 @@ impl LocalBarrierManager {
                 .senders
                 .get(&actor_id)
                 .unwrap_or_else(|| panic!("sender for actor {} does not exist", actor_id));
-            sender.send(Message::Barrier(barrier.clone())).unwrap();
+            // The actor may have failed, whose failure is notified by the collection.
+            if sender.send(Message::Barrier(barrier.clone())).is_err() {
+                warn!("failed to send barrier to failed actor {}", actor_id);
+            }
         }
 
         // Actors to stop should still accept this barrier, but won't get sent to in next times.
-        if let Some(Mutation::Stop(actors)) = barrier.mutation.as_deref() {
+        let actors_to_stop = match barrier.mutation.as_deref() {
+            Some(Mutation::Stop(actors)) => Some(actors),
+            Some(Mutation::Reschedule(reschedule)) => Some(&reschedule.stopped_actors),
+            _ => None,
+        };
+        if let Some(actors) = actors_to_stop {
             trace!("remove actors {:?} from senders", actors);
             for actor in actors {
                 self.senders.remove(actor);
@@ -210,6 +286,38 @@ impl LocalBarrierManager {
         Ok(())
     }
 
+    /// When an actor exits with an error, it should report the failure using this function. The
+    /// failure will be notified to the meta service by the barrier collection.
+    pub fn notify_failure(&mut self, actor_id: ActorId, err: &RwError) {
+        error!("actor {} failed: {}", actor_id, err);
+        self.senders.remove(&actor_id);
+
+        match &mut self.state {
+            #[cfg(test)]
+            BarrierState::Local => {}
+
+            BarrierState::Managed(managed_state) => {
+                managed_state.notify_failure(actor_id);
+            }
+        }
+    }
+
+    /// Remove the dropped actors, so that the barriers are neither sent to nor collected from
+    /// them.
+    pub fn remove_actors(&mut self, actors: &HashSet<ActorId>) {
+        self.senders
+            .retain(|actor_id, _| !actors.contains(actor_id));
+
+        match &mut self.state {
+            #[cfg(test)]
+            BarrierState::Local => {}
+
+            BarrierState::Managed(managed_state) => {
+                managed_state.remove_actors(actors);
+            }
+        }
+    }
+
     /// Report that a Create MV DDL with given `ddl_epoch` is finished on the actor with `actor_id`.
     /// This will be piggybacked by the collection of current/next barrier and then be reported
     /// to the meta service.
@@ -235,6 +343,50 @@ impl LocalBarrierManager {
     }
 }
 
+impl LocalBarrierManager {
+    /// Report that the actor with `actor_id` has consumed `consumed_rows` rows from the snapshot
+    /// for the Create MV DDL with `ddl_epoch`. Only the latest progress is piggybacked by the
+    /// collection of current/next barrier.
+    pub fn update_create_mview_progress(
+        &mut self,
+        ddl_epoch: u64,
+        actor_id: ActorId,
+        consumed_rows: u64,
+    ) {
+        match &mut self.state {
+            #[cfg(test)]
+            BarrierState::Local => {}
+
+            BarrierState::Managed(managed_state) => {
+                managed_state.create_mview_progress.insert(
+                    actor_id,
+                    CreateMviewProgress {
+                        epoch: ddl_epoch,
+                        actor_id,
+                        consumed_rows,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Report that `delta` rows are written into the materialized view with `table_id`, which
+    /// will be piggybacked by the collection of current/next barrier.
+    pub fn report_table_row_count(&mut self, table_id: u32, delta: i64) {
+        match &mut self.state {
+            #[cfg(test)]
+            BarrierState::Local => {}
+
+            BarrierState::Managed(managed_state) => {
+                *managed_state
+                    .table_row_count_deltas
+                    .entry(table_id)
+                    .or_default() += delta;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 impl LocalBarrierManager {
     pub fn for_test() -> Self {
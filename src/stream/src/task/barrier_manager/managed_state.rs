@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::iter::once;
 
+use itertools::Itertools;
 use tokio::sync::oneshot;
 
-use super::{CollectResult, FinishedCreateMview};
+use super::{CollectResult, CreateMviewProgress, FinishedCreateMview};
 use crate::executor::Barrier;
 use crate::task::ActorId;
 
@@ -47,8 +48,9 @@ enum ManagedBarrierStateInner {
         /// Actor ids remaining to be collected.
         remaining_actors: HashSet<ActorId>,
 
-        /// Notify that the collection is finished.
-        collect_notifier: oneshot::Sender<CollectResult>,
+        /// Notify that the collection is finished. `None` if the failure of some actors has been
+        /// notified, and the meta service has not issued the request to collect it again.
+        collect_notifier: Option<oneshot::Sender<CollectResult>>,
     },
 }
 
@@ -57,6 +59,15 @@ pub(super) struct ManagedBarrierState {
     inner: ManagedBarrierStateInner,
 
     pub finished_create_mviews: Vec<FinishedCreateMview>,
+
+    /// The latest progress reported by each actor creating a materialized view.
+    pub create_mview_progress: HashMap<ActorId, CreateMviewProgress>,
+
+    /// The row count deltas of the materialized views since the last collection.
+    pub table_row_count_deltas: HashMap<u32, i64>,
+
+    /// Actors exited with errors, which won't collect any barrier until they're dropped.
+    failed_actors: HashSet<ActorId>,
 }
 
 impl ManagedBarrierState {
@@ -68,6 +79,21 @@ impl ManagedBarrierState {
                 last_epoch: None,
             },
             finished_create_mviews: Default::default(),
+            create_mview_progress: Default::default(),
+            table_row_count_deltas: Default::default(),
+            failed_actors: Default::default(),
+        }
+    }
+
+    /// Take the reports piggybacked by the collection.
+    fn take_collect_result(&mut self) -> CollectResult {
+        CollectResult {
+            finished_create_mviews: std::mem::take(&mut self.finished_create_mviews),
+            create_mview_progress: std::mem::take(&mut self.create_mview_progress)
+                .into_values()
+                .collect(),
+            table_row_count_deltas: std::mem::take(&mut self.table_row_count_deltas),
+            failed_actors: vec![],
         }
     }
 
@@ -75,41 +101,85 @@ impl ManagedBarrierState {
         &mut self.inner
     }
 
-    /// Notify if we have collected barriers from all actor ids. The state must be `Issued`.
+    /// Notify if we have collected barriers from all actor ids, or some of the actors to collect
+    /// have failed. The state must be `Issued`.
     fn may_notify(&mut self) {
-        let (epoch, to_notify) = match self.inner_mut() {
+        let (epoch, remaining_actors, collect_notifier) = match &mut self.inner {
             ManagedBarrierStateInner::Issued {
                 epoch,
                 remaining_actors,
-                ..
-            } => (*epoch, remaining_actors.is_empty()),
+                collect_notifier,
+            } => (*epoch, remaining_actors, collect_notifier),
 
             _ => unreachable!(),
         };
 
-        if to_notify {
-            let state = std::mem::replace(
-                self.inner_mut(),
-                ManagedBarrierStateInner::Pending {
-                    last_epoch: Some(epoch),
-                },
-            );
-            let finished_create_mviews = std::mem::take(&mut self.finished_create_mviews);
-
-            match state {
-                ManagedBarrierStateInner::Issued {
-                    collect_notifier, ..
-                } => {
-                    // Notify about barrier finishing.
-                    let result = CollectResult {
-                        finished_create_mviews,
-                    };
-                    if collect_notifier.send(result).is_err() {
-                        warn!("failed to notify barrier collection with epoch {}", epoch)
-                    }
+        // Notify about the failure at once, so that the meta service can start the recovery without
+        // waiting for the other actors, which may never collect the barrier either.
+        let failed_actors = remaining_actors
+            .iter()
+            .filter(|actor_id| self.failed_actors.contains(actor_id))
+            .cloned()
+            .collect_vec();
+        if !failed_actors.is_empty() {
+            remaining_actors.retain(|actor_id| !self.failed_actors.contains(actor_id));
+            if let Some(collect_notifier) = collect_notifier.take() {
+                let result = CollectResult {
+                    failed_actors,
+                    ..Default::default()
+                };
+                if collect_notifier.send(result).is_err() {
+                    warn!("failed to notify barrier failure with epoch {}", epoch)
                 }
+            }
+        }
+
+        if remaining_actors.is_empty() {
+            let collect_notifier = collect_notifier.take();
+            *self.inner_mut() = ManagedBarrierStateInner::Pending {
+                last_epoch: Some(epoch),
+            };
 
-                _ => unreachable!(),
+            // If the failure has been notified, the finished DDLs will be reported by the next
+            // collection.
+            if let Some(collect_notifier) = collect_notifier {
+                // Notify about barrier finishing.
+                let result = self.take_collect_result();
+                if collect_notifier.send(result).is_err() {
+                    warn!("failed to notify barrier collection with epoch {}", epoch)
+                }
+            }
+        }
+    }
+
+    /// Mark the actor with `actor_id` as failed. The failure is notified by the collection of the
+    /// current or the next barrier.
+    pub(super) fn notify_failure(&mut self, actor_id: ActorId) {
+        self.failed_actors.insert(actor_id);
+        if matches!(self.inner, ManagedBarrierStateInner::Issued { .. }) {
+            self.may_notify();
+        }
+    }
+
+    /// Stop waiting for the dropped `actors` to collect barriers.
+    pub(super) fn remove_actors(&mut self, actors: &HashSet<ActorId>) {
+        self.failed_actors
+            .retain(|actor_id| !actors.contains(actor_id));
+
+        match self.inner_mut() {
+            ManagedBarrierStateInner::Pending { .. } => {}
+
+            ManagedBarrierStateInner::Stashed {
+                collected_actors, ..
+            } => {
+                collected_actors.retain(|actor_id| !actors.contains(actor_id));
+            }
+
+            ManagedBarrierStateInner::Issued {
+                remaining_actors, ..
+            } => {
+                remaining_actors.retain(|actor_id| !actors.contains(actor_id));
+                self.may_notify();
             }
         }
     }
@@ -169,13 +239,27 @@ impl ManagedBarrierState {
         collect_notifier: oneshot::Sender<CollectResult>,
     ) {
         match self.inner_mut() {
+            // The barrier has been collected after the failure was notified, and the meta service
+            // is collecting it again during the recovery.
+            ManagedBarrierStateInner::Pending {
+                last_epoch: Some(last_epoch),
+            } if *last_epoch == barrier.epoch.curr => {
+                let result = self.take_collect_result();
+                if collect_notifier.send(result).is_err() {
+                    warn!(
+                        "failed to notify barrier collection with epoch {}",
+                        barrier.epoch.curr
+                    )
+                }
+            }
+
             ManagedBarrierStateInner::Pending { .. } => {
                 let remaining_actors = actor_ids_to_collect.into_iter().collect();
 
                 *self.inner_mut() = ManagedBarrierStateInner::Issued {
                     epoch: barrier.epoch.curr,
                     remaining_actors,
-                    collect_notifier,
+                    collect_notifier: Some(collect_notifier),
                 };
                 self.may_notify();
             }
@@ -194,11 +278,27 @@ impl ManagedBarrierState {
                 *self.inner_mut() = ManagedBarrierStateInner::Issued {
                     epoch: barrier.epoch.curr,
                     remaining_actors,
-                    collect_notifier,
+                    collect_notifier: Some(collect_notifier),
                 };
                 self.may_notify();
             }
 
+            // The failure has been notified or the previous collection is cancelled, and the meta
+            // service is collecting the barrier again from the remaining actors during the
+            // recovery.
+            ManagedBarrierStateInner::Issued {
+                epoch,
+                collect_notifier: notifier,
+                ..
+            } if *epoch == barrier.epoch.curr
+                && notifier
+                    .as_ref()
+                    .map_or(true, |notifier| notifier.is_closed()) =>
+            {
+                *notifier = Some(collect_notifier);
+                self.may_notify();
+            }
+
             ManagedBarrierStateInner::Issued { .. } => {
                 panic!("barrier state has already been `Issued`")
             }
@@ -12,9 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::iter::once;
+use std::iter::{empty, once};
 
 use itertools::Itertools;
+use risingwave_common::error::ErrorCode;
 use tokio::sync::mpsc::unbounded_channel;
 
 use super::*;
@@ -138,3 +139,89 @@ async fn test_managed_barrier_collection_before_send_request() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_managed_barrier_collection_with_failure() -> Result<()> {
+    let mut manager = LocalBarrierManager::new();
+    assert!(!manager.is_local_mode());
+
+    // Register actors
+    let actor_ids = vec![233, 234, 235];
+    let _rxs = actor_ids
+        .iter()
+        .map(|&actor_id| {
+            let (barrier_tx, barrier_rx) = unbounded_channel();
+            manager.register_sender(actor_id, barrier_tx);
+            barrier_rx
+        })
+        .collect_vec();
+
+    // Send a barrier to all actors
+    let epoch = 114514;
+    let barrier = Barrier::new_test_barrier(epoch);
+    let mut collect_rx = manager
+        .send_barrier(&barrier, actor_ids.clone(), actor_ids)
+        .unwrap()
+        .unwrap();
+
+    // The failure is notified at once, without waiting for the other actors
+    manager.collect(233, &barrier).unwrap();
+    manager.notify_failure(
+        234,
+        &ErrorCode::InternalError("actor failed".to_string()).into(),
+    );
+    let result = collect_rx.try_recv().unwrap();
+    assert_eq!(result.failed_actors, vec![234]);
+
+    // Collect the barrier again from the remaining actors after the failed one is dropped
+    manager.remove_actors(&once(234).collect());
+    let mut collect_rx = manager
+        .send_barrier(&barrier, empty(), vec![233, 235])
+        .unwrap()
+        .unwrap();
+    assert!(collect_rx.try_recv().is_err());
+
+    manager.collect(235, &barrier).unwrap();
+    let result = collect_rx.try_recv().unwrap();
+    assert!(result.failed_actors.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_managed_barrier_collection_with_progress() -> Result<()> {
+    let mut manager = LocalBarrierManager::new();
+    let (barrier_tx, _barrier_rx) = unbounded_channel();
+    manager.register_sender(233, barrier_tx);
+
+    // Only the latest progress of each actor is reported, and the row counts are accumulated.
+    manager.update_create_mview_progress(100, 233, 10);
+    manager.update_create_mview_progress(100, 233, 20);
+    manager.report_table_row_count(1, 5);
+    manager.report_table_row_count(1, -2);
+
+    let barrier = Barrier::new_test_barrier(114514);
+    let mut collect_rx = manager
+        .send_barrier(&barrier, once(233), once(233))
+        .unwrap()
+        .unwrap();
+    manager.collect(233, &barrier).unwrap();
+    let result = collect_rx.try_recv().unwrap();
+    assert_eq!(result.create_mview_progress.len(), 1);
+    assert_eq!(result.create_mview_progress[0].epoch, 100);
+    assert_eq!(result.create_mview_progress[0].consumed_rows, 20);
+    assert_eq!(result.table_row_count_deltas.get(&1), Some(&3));
+
+    // The reports are taken by the collection.
+    let barrier = Barrier::new_test_barrier(114515);
+    let mut collect_rx = manager
+        .send_barrier(&barrier, once(233), once(233))
+        .unwrap()
+        .unwrap();
+    manager.collect(233, &barrier).unwrap();
+    let result = collect_rx.try_recv().unwrap();
+    assert!(result.create_mview_progress.is_empty());
+    assert!(result.table_row_count_deltas.is_empty());
+
+    Ok(())
+}
@@ -19,6 +19,8 @@ use risingwave_common::util::addr::HostAddr;
 use risingwave_source::{SourceManager, SourceManagerRef};
 use risingwave_storage::StateStoreImpl;
 
+use super::MemoryManager;
+
 pub(crate) type WorkerNodeId = u32;
 
 /// The global environment for task execution.
@@ -39,6 +41,9 @@ pub struct StreamEnvironment {
 
     /// State store for table scanning.
     state_store: StateStoreImpl,
+
+    /// Tracks the memory used by the caches of executors.
+    memory_manager: Arc<MemoryManager>,
 }
 
 impl StreamEnvironment {
@@ -48,6 +53,7 @@ impl StreamEnvironment {
         config: Arc<StreamingConfig>,
         worker_id: WorkerNodeId,
         state_store: StateStoreImpl,
+        memory_manager: Arc<MemoryManager>,
     ) -> Self {
         StreamEnvironment {
             server_addr,
@@ -55,6 +61,7 @@ impl StreamEnvironment {
             config,
             worker_id,
             state_store,
+            memory_manager,
         }
     }
 
@@ -72,6 +79,7 @@ impl StreamEnvironment {
             state_store: StateStoreImpl::shared_in_memory_store(Arc::new(
                 StateStoreMetrics::unused(),
             )),
+            memory_manager: Arc::new(MemoryManager::for_test()),
         }
     }
 
@@ -98,4 +106,8 @@ impl StreamEnvironment {
     pub fn state_store(&self) -> StateStoreImpl {
         self.state_store.clone()
     }
+
+    pub fn memory_manager(&self) -> &Arc<MemoryManager> {
+        &self.memory_manager
+    }
 }
@@ -0,0 +1,231 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use super::ActorId;
+use crate::executor::monitor::StreamingMetrics;
+
+/// Once the memory usage exceeds this ratio of the budget, executors are asked to evict their
+/// caches.
+const HIGH_WATERMARK: f64 = 0.9;
+
+/// Executors keep evicting their caches until the memory usage is below this ratio of the budget.
+const LOW_WATERMARK: f64 = 0.7;
+
+#[derive(Default)]
+struct MemoryUsages {
+    /// The memory usage of each executor, by executor id.
+    executors: HashMap<u64, (ActorId, usize)>,
+
+    /// The memory usage of each actor, and the number of its executors.
+    actors: HashMap<ActorId, (usize, usize)>,
+
+    total: usize,
+
+    /// Whether the usage has exceeded the high watermark, and not yet dropped below the low
+    /// watermark.
+    evicting: bool,
+}
+
+/// Tracks the memory used by the caches of the streaming executors on this compute node.
+///
+/// There's no allocator-level accounting, so each stateful executor reports the estimated size of
+/// its caches on barriers, through a [`MemoryUsageReporter`]. Once the total usage exceeds the high
+/// watermark of the budget, the executors are asked to evict their caches proportionally, until
+/// the usage drops below the low watermark.
+pub struct MemoryManager {
+    budget: usize,
+    usages: Mutex<MemoryUsages>,
+    metrics: Arc<StreamingMetrics>,
+}
+
+impl fmt::Debug for MemoryManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryManager")
+            .field("budget", &self.budget)
+            .field("total_usage", &self.total_usage())
+            .finish()
+    }
+}
+
+impl MemoryManager {
+    pub fn new(budget: usize, metrics: Arc<StreamingMetrics>) -> Self {
+        Self {
+            budget,
+            usages: Mutex::new(MemoryUsages::default()),
+            metrics,
+        }
+    }
+
+    /// Create an instance with unlimited budget for testing purpose.
+    pub fn for_test() -> Self {
+        Self::new(usize::MAX, Arc::new(StreamingMetrics::unused()))
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// The total memory usage of all executors.
+    pub fn total_usage(&self) -> usize {
+        self.usages.lock().total
+    }
+
+    /// The memory usage of all executors of the actor.
+    pub fn actor_usage(&self, actor_id: ActorId) -> usize {
+        self.usages
+            .lock()
+            .actors
+            .get(&actor_id)
+            .map_or(0, |(usage, _)| *usage)
+    }
+
+    /// Registers an executor, and returns the reporter of its memory usage.
+    pub fn register(self: &Arc<Self>, actor_id: ActorId, executor_id: u64) -> MemoryUsageReporter {
+        let mut usages = self.usages.lock();
+        if usages
+            .executors
+            .insert(executor_id, (actor_id, 0))
+            .is_none()
+        {
+            usages.actors.entry(actor_id).or_default().1 += 1;
+        }
+        MemoryUsageReporter {
+            manager: self.clone(),
+            actor_id,
+            executor_id,
+        }
+    }
+
+    fn unregister(&self, actor_id: ActorId, executor_id: u64) {
+        self.update(actor_id, executor_id, 0);
+        let mut usages = self.usages.lock();
+        if usages.executors.remove(&executor_id).is_none() {
+            return;
+        }
+        let (_, executor_count) = usages.actors.get_mut(&actor_id).unwrap();
+        *executor_count -= 1;
+        if *executor_count == 0 {
+            usages.actors.remove(&actor_id);
+            self.metrics
+                .actor_memory_usage
+                .remove_label_values(&[&actor_id.to_string()])
+                .ok();
+        }
+    }
+
+    /// Updates the memory usage of the executor, and returns the size its caches should be evicted
+    /// to, if the memory usage is too high.
+    fn update(&self, actor_id: ActorId, executor_id: u64, usage: usize) -> Option<usize> {
+        let mut usages = self.usages.lock();
+        let old_usage = match usages.executors.get_mut(&executor_id) {
+            Some((_, old_usage)) => std::mem::replace(old_usage, usage),
+            None => return None,
+        };
+        let actor_usage = {
+            let (actor_usage, _) = usages.actors.get_mut(&actor_id).unwrap();
+            *actor_usage = *actor_usage + usage - old_usage;
+            *actor_usage
+        };
+        usages.total = usages.total + usage - old_usage;
+
+        let total = usages.total as f64;
+        let budget = self.budget as f64;
+        if total > budget * HIGH_WATERMARK {
+            usages.evicting = true;
+        } else if total <= budget * LOW_WATERMARK {
+            usages.evicting = false;
+        }
+
+        self.metrics
+            .actor_memory_usage
+            .with_label_values(&[&actor_id.to_string()])
+            .set(actor_usage as i64);
+        self.metrics.memory_usage.set(usages.total as i64);
+
+        if !usages.evicting {
+            return None;
+        }
+        let target = (usage as f64 * budget * LOW_WATERMARK / total) as usize;
+        if target < usage {
+            Some(target)
+        } else {
+            None
+        }
+    }
+}
+
+/// Reports the memory usage of an executor to [`MemoryManager`]. The usage is removed once the
+/// reporter is dropped.
+pub struct MemoryUsageReporter {
+    manager: Arc<MemoryManager>,
+    actor_id: ActorId,
+    executor_id: u64,
+}
+
+impl MemoryUsageReporter {
+    /// Reports the estimated memory usage of the executor's caches. Returns the size that the
+    /// caches should be evicted to if the memory usage of the compute node is too high, in which
+    /// case the usage should be reported again after eviction. Should be called on barriers, when
+    /// the caches are not dirty.
+    pub fn report(&self, usage: usize) -> Option<usize> {
+        self.manager.update(self.actor_id, self.executor_id, usage)
+    }
+}
+
+impl Drop for MemoryUsageReporter {
+    fn drop(&mut self) {
+        self.manager.unregister(self.actor_id, self.executor_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_manager() {
+        let manager = Arc::new(MemoryManager::new(
+            1000,
+            Arc::new(StreamingMetrics::unused()),
+        ));
+        let reporter_1 = manager.register(1, 1);
+        let reporter_2 = manager.register(1, 2);
+        let reporter_3 = manager.register(2, 3);
+
+        assert_eq!(reporter_1.report(300), None);
+        assert_eq!(reporter_2.report(300), None);
+        assert_eq!(manager.actor_usage(1), 600);
+
+        // The usage exceeds the high watermark, so the executor is asked to evict its cache to
+        // 400 * 700 / 1000.
+        assert_eq!(reporter_3.report(400), Some(280));
+        assert_eq!(reporter_3.report(280), Some(280 * 700 / 880));
+        assert_eq!(manager.total_usage(), 880);
+
+        // Below the high watermark but still evicting, until reaching the low watermark.
+        assert_eq!(reporter_1.report(100), None);
+        assert_eq!(manager.total_usage(), 680);
+        assert_eq!(reporter_2.report(300), None);
+
+        drop(reporter_3);
+        assert_eq!(manager.total_usage(), 400);
+        assert_eq!(manager.actor_usage(2), 0);
+    }
+}
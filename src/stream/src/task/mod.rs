@@ -20,16 +20,19 @@ use parking_lot::{Mutex, MutexGuard};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::util::addr::HostAddr;
 
+use crate::executor::monitor::StreamingMetrics;
 use crate::executor::Message;
 
 mod barrier_manager;
 mod compute_client_pool;
 mod env;
+mod memory_manager;
 mod stream_manager;
 
 pub use barrier_manager::*;
 pub use compute_client_pool::*;
 pub use env::*;
+pub use memory_manager::*;
 pub use stream_manager::*;
 
 #[cfg(test)]
@@ -72,14 +75,18 @@ pub struct SharedContext {
     pub(crate) addr: HostAddr,
 
     pub(crate) barrier_manager: Arc<Mutex<LocalBarrierManager>>,
+
+    /// Used to report the occupancy of the channels and the time blocked on sending.
+    pub(crate) streaming_metrics: Arc<StreamingMetrics>,
 }
 
 impl SharedContext {
-    pub fn new(addr: HostAddr) -> Self {
+    pub fn new(addr: HostAddr, streaming_metrics: Arc<StreamingMetrics>) -> Self {
         Self {
             channel_map: Mutex::new(HashMap::new()),
             addr,
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new())),
+            streaming_metrics,
         }
     }
 
@@ -89,6 +96,7 @@ impl SharedContext {
             channel_map: Mutex::new(HashMap::new()),
             addr: LOCAL_TEST_ADDR.clone(),
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::for_test())),
+            streaming_metrics: Arc::new(StreamingMetrics::unused()),
         }
     }
 
@@ -118,6 +126,17 @@ impl SharedContext {
         }
     }
 
+    /// Create a reporter for the rows written by a
+    /// [`crate::executor_v2::MaterializeExecutor`] into the materialized view with `table_id`.
+    /// The row counts are aggregated by the meta service, to estimate the progress of the
+    /// materialized views created on it.
+    pub fn register_table_row_count_reporter(&self, table_id: u32) -> TableRowCountReporter {
+        TableRowCountReporter {
+            barrier_manager: self.barrier_manager.clone(),
+            table_id,
+        }
+    }
+
     pub fn lock_barrier_manager(&self) -> MutexGuard<LocalBarrierManager> {
         self.barrier_manager.lock()
     }
@@ -20,16 +20,17 @@ use std::sync::Arc;
 use futures::channel::mpsc::{channel, Receiver};
 use itertools::Itertools;
 use parking_lot::Mutex;
-use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::catalog::{Field, Schema, TableId};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::try_match_expand;
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, IntervalUnit};
 use risingwave_common::util::addr::{is_local_address, HostAddr};
-use risingwave_common::util::env_var::env_var_is_true;
-use risingwave_expr::expr::{build_from_prost, AggKind, RowExpression};
+use risingwave_common::util::exchange::exchange_options;
+use risingwave_expr::expr::{build_from_prost, scope_stream_epoch, AggKind, RowExpression};
 use risingwave_pb::common::ActorInfo;
 use risingwave_pb::plan::JoinType as JoinTypeProto;
 use risingwave_pb::stream_plan::stream_node::Node;
+use risingwave_pb::task_service::ExchangeOptions;
 use risingwave_pb::{expr, stream_plan, stream_service};
 use risingwave_storage::{dispatch_state_store, Keyspace, StateStore, StateStoreImpl};
 use tokio::sync::oneshot;
@@ -37,9 +38,12 @@ use tokio::task::JoinHandle;
 
 use super::{CollectResult, ComputeClientPool};
 use crate::executor::*;
-use crate::executor_v2::merge::RemoteInput;
+use crate::executor_v2::merge::new_input;
 use crate::executor_v2::receiver::ReceiverExecutor;
-use crate::executor_v2::{Executor as ExecutorV2, MergeExecutor as MergeExecutorV2};
+use crate::executor_v2::{
+    BoxedExecutor, Executor as ExecutorV2, HashJoinExecutor, IntervalJoinBound, JoinParams,
+    JoinType, MergeExecutor as MergeExecutorV2,
+};
 use crate::task::{
     ActorId, ConsumableChannelPair, SharedContext, StreamEnvironment, UpDownActorIds,
     LOCAL_OUTPUT_CHANNEL_SIZE,
@@ -66,6 +70,13 @@ pub struct LocalStreamManagerCore {
     /// Stores all actor information, taken after actor built.
     actors: HashMap<ActorId, stream_plan::StreamActor>,
 
+    /// Key prefixes of the states that the built actors may write, used to discard their unsynced
+    /// writes when they're dropped for recovery.
+    state_key_prefixes: HashMap<ActorId, Vec<Vec<u8>>>,
+
+    /// Label values of the metrics reported by the built actors, removed when they're dropped.
+    actor_metric_labels: HashMap<ActorId, ActorMetricLabels>,
+
     /// Mock source, `actor_id = 0`.
     /// TODO: remove this
     mock_source: ConsumableChannelPair,
@@ -83,6 +94,13 @@ pub struct LocalStreamManagerCore {
     compute_client_pool: ComputeClientPool,
 }
 
+/// The executors and the upstream and downstream actors of an actor, which label its metrics.
+struct ActorMetricLabels {
+    executor_ids: Vec<u64>,
+    up_actor_ids: Vec<ActorId>,
+    down_actor_ids: Vec<ActorId>,
+}
+
 /// `LocalStreamManager` manages all stream executors in this project.
 pub struct LocalStreamManager {
     core: Mutex<LocalStreamManagerCore>,
@@ -104,7 +122,7 @@ pub struct ExecutorParams {
     pub op_info: String,
 
     /// The input executor.
-    pub input: Vec<Box<dyn Executor>>,
+    pub input: Vec<BoxedExecutor>,
 
     /// Id of the actor.
     pub actor_id: ActorId,
@@ -119,7 +137,14 @@ impl Debug for ExecutorParams {
             .field("executor_id", &self.executor_id)
             .field("operator_id", &self.operator_id)
             .field("op_info", &self.op_info)
-            .field("input", &self.input)
+            .field(
+                "input",
+                &self
+                    .input
+                    .iter()
+                    .map(|input| input.identity())
+                    .collect_vec(),
+            )
             .field("actor_id", &self.actor_id)
             .finish()
     }
@@ -165,18 +190,27 @@ impl LocalStreamManager {
         Ok(rx)
     }
 
-    /// Broadcast a barrier to all senders. Returns when the barrier is fully collected.
+    /// Broadcast a barrier to all senders. Returns when the barrier is fully collected. If
+    /// `checkpoint` is set, the states written until this barrier are synced to the state store.
     pub async fn send_and_collect_barrier(
         &self,
         barrier: &Barrier,
         actor_ids_to_send: impl IntoIterator<Item = ActorId>,
         actor_ids_to_collect: impl IntoIterator<Item = ActorId>,
+        checkpoint: bool,
     ) -> Result<CollectResult> {
         let rx = self.send_barrier(barrier, actor_ids_to_send, actor_ids_to_collect)?;
 
         // Wait for all actors finishing this barrier.
         let collect_result = rx.await.unwrap();
 
+        // The states of the non-checkpoint barriers stay in the shared buffer, and will be synced
+        // along with the next checkpoint. The states are not synced either if some actors failed,
+        // until the barrier is collected again by the recovery.
+        if !checkpoint || !collect_result.failed_actors.is_empty() {
+            return Ok(collect_result);
+        }
+
         // Sync states from shared buffer to S3 before telling meta service we've done.
         dispatch_state_store!(self.state_store(), store, {
             match store.sync(Some(barrier.epoch.prev)).await {
@@ -207,14 +241,46 @@ impl LocalStreamManager {
     }
 
     pub fn drop_actor(&self, actors: &[ActorId]) -> Result<()> {
-        let mut core = self.core.lock();
-        for id in actors {
-            core.drop_actor(*id);
+        Self::drop_actor_inner(&mut self.core.lock(), actors);
+        Ok(())
+    }
+
+    /// Drops the actors, waits for their tasks to exit, and then discards their writes not synced
+    /// to the state store yet, so that a later sync only commits the states of the other actors.
+    pub async fn drop_actor_and_discard_states(&self, actors: &[ActorId]) -> Result<()> {
+        let (handles, prefixes) = {
+            let mut core = self.core.lock();
+            let prefixes = actors
+                .iter()
+                .filter_map(|id| core.state_key_prefixes.get(id))
+                .flatten()
+                .cloned()
+                .collect_vec();
+            (Self::drop_actor_inner(&mut core, actors), prefixes)
+        };
+        for handle in handles {
+            // The task is aborted, so the join error is expected.
+            let _ = handle.await;
         }
-        tracing::debug!(actors = ?actors, "drop actors");
+        if !prefixes.is_empty() {
+            self.state_store().discard_unsynced(prefixes)?;
+        }
+        tracing::debug!(actors = ?actors, "discard unsynced states of dropped actors");
         Ok(())
     }
 
+    fn drop_actor_inner(core: &mut LocalStreamManagerCore, actors: &[ActorId]) -> Vec<ActorHandle> {
+        let handles = actors
+            .iter()
+            .filter_map(|id| core.drop_actor(*id))
+            .collect_vec();
+        core.context
+            .lock_barrier_manager()
+            .remove_actors(&actors.iter().cloned().collect());
+        tracing::debug!(actors = ?actors, "drop actors");
+        handles
+    }
+
     /// Force stop all actors on this worker.
     pub async fn stop_all_actors(&self, epoch: Epoch) -> Result<()> {
         let (actor_ids_to_send, actor_ids_to_collect) = {
@@ -232,7 +298,7 @@ impl LocalStreamManager {
             span: tracing::Span::none(),
         };
 
-        self.send_and_collect_barrier(&barrier, actor_ids_to_send, actor_ids_to_collect)
+        self.send_and_collect_barrier(&barrier, actor_ids_to_send, actor_ids_to_collect, true)
             .await?;
         self.core.lock().drop_all_actors();
 
@@ -244,6 +310,13 @@ impl LocalStreamManager {
         core.context.take_receiver(&ids)
     }
 
+    /// Get the metrics of the channel, whose occupancy is decreased by the exchange service on
+    /// forwarding the messages to the remote downstream actor.
+    pub fn channel_metrics(&self, ids: UpDownActorIds) -> ChannelMetrics {
+        let core = self.core.lock();
+        core.streaming_metrics.channel_metrics(ids.0, ids.1)
+    }
+
     pub fn update_actors(
         &self,
         actors: &[stream_plan::StreamActor],
@@ -305,7 +378,10 @@ impl LocalStreamManager {
     }
 }
 
-pub fn build_agg_call_from_prost(agg_call_proto: &expr::AggCall) -> Result<AggCall> {
+pub fn build_agg_call_from_prost(
+    append_only: bool,
+    agg_call_proto: &expr::AggCall,
+) -> Result<AggCall> {
     let args = {
         let args = &agg_call_proto.get_args()[..];
         match args {
@@ -314,6 +390,16 @@ pub fn build_agg_call_from_prost(agg_call_proto: &expr::AggCall) -> Result<AggCa
                 DataType::from(arg.get_type()?),
                 arg.get_input()?.column_idx as usize,
             ),
+            [arg1, arg2] => AggArgs::Binary(
+                [
+                    DataType::from(arg1.get_type()?),
+                    DataType::from(arg2.get_type()?),
+                ],
+                [
+                    arg1.get_input()?.column_idx as usize,
+                    arg2.get_input()?.column_idx as usize,
+                ],
+            ),
             _ => {
                 return Err(RwError::from(ErrorCode::NotImplemented(
                     "multiple aggregation args".to_string(),
@@ -326,6 +412,7 @@ pub fn build_agg_call_from_prost(agg_call_proto: &expr::AggCall) -> Result<AggCa
         kind: AggKind::try_from(agg_call_proto.get_type()?)?,
         args,
         return_type: DataType::from(agg_call_proto.get_return_type()?),
+        append_only,
     })
 }
 
@@ -344,7 +431,7 @@ impl LocalStreamManagerCore {
         state_store: StateStoreImpl,
         streaming_metrics: Arc<StreamingMetrics>,
     ) -> Self {
-        let context = SharedContext::new(addr);
+        let context = SharedContext::new(addr, streaming_metrics.clone());
         Self::with_store_and_context(state_store, context, streaming_metrics)
     }
 
@@ -360,6 +447,8 @@ impl LocalStreamManagerCore {
             context: Arc::new(context),
             actor_infos: HashMap::new(),
             actors: HashMap::new(),
+            state_key_prefixes: HashMap::new(),
+            actor_metric_labels: HashMap::new(),
             mock_source: (Some(tx), Some(rx)),
             state_store,
             streaming_metrics,
@@ -391,7 +480,7 @@ impl LocalStreamManagerCore {
     /// Create dispatchers with downstream information registered before
     fn create_dispatcher(
         &mut self,
-        input: Box<dyn Executor>,
+        input: BoxedExecutor,
         dispatcher: &stream_plan::Dispatcher,
         actor_id: ActorId,
     ) -> Result<Box<dyn StreamConsumer>> {
@@ -426,7 +515,6 @@ impl LocalStreamManagerCore {
                 Box::new(DispatchExecutor::new(
                     input,
                     DispatcherImpl::Hash(HashDataDispatcher::new(
-                        dispatcher.downstream_actor_id.to_vec(),
                         outputs,
                         column_indices,
                         hash_mapping,
@@ -456,20 +544,29 @@ impl LocalStreamManagerCore {
         Ok(dispatcher)
     }
 
-    /// Create a chain(tree) of nodes, with given `store`.
+    /// Create a chain(tree) of nodes, with given `store`. `downstream_executor_id` is the id of the
+    /// executor consuming the created one, or `None` for the head of the actor.
+    #[allow(clippy::too_many_arguments)]
     fn create_nodes_inner(
         &mut self,
         fragment_id: u32,
         actor_id: ActorId,
         node: &stream_plan::StreamNode,
         input_pos: usize,
+        downstream_executor_id: Option<u64>,
         env: StreamEnvironment,
         store: impl StateStore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let op_info = node.get_identity().clone();
+
+        // We assume that the operator_id of different instances from the same RelNode will be the
+        // same.
+        let executor_id = ((actor_id as u64) << 32) + node.get_operator_id();
+        let operator_id = ((fragment_id as u64) << 32) + node.get_operator_id();
+
         // Create the input executor before creating itself
         // The node with no input must be a `MergeNode`
-        let input: Vec<Box<dyn Executor>> = node
+        let input: Vec<BoxedExecutor> = node
             .input
             .iter()
             .enumerate()
@@ -479,6 +576,7 @@ impl LocalStreamManagerCore {
                     actor_id,
                     input,
                     input_pos,
+                    Some(executor_id),
                     env.clone(),
                     store.clone(),
                 )
@@ -491,11 +589,6 @@ impl LocalStreamManagerCore {
             .map(|idx| *idx as usize)
             .collect::<Vec<_>>();
 
-        // We assume that the operator_id of different instances from the same RelNode will be the
-        // same.
-        let executor_id = ((actor_id as u64) << 32) + node.get_operator_id();
-        let operator_id = ((fragment_id as u64) << 32) + node.get_operator_id();
-
         let executor_params = ExecutorParams {
             env: env.clone(),
             pk_indices,
@@ -513,6 +606,12 @@ impl LocalStreamManagerCore {
             input_pos,
             self.streaming_metrics.clone(),
         )?;
+        let executor = self.wrap_executor_for_row_count(
+            executor,
+            actor_id,
+            executor_id,
+            downstream_executor_id,
+        );
         Ok(executor)
     }
 
@@ -523,18 +622,81 @@ impl LocalStreamManagerCore {
         actor_id: ActorId,
         node: &stream_plan::StreamNode,
         env: StreamEnvironment,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         dispatch_state_store!(self.state_store.clone(), store, {
-            self.create_nodes_inner(fragment_id, actor_id, node, 0, env, store)
+            self.create_nodes_inner(fragment_id, actor_id, node, 0, None, env, store)
+        })
+    }
+
+    /// Returns the key prefixes of the states that the executors of `node` and its inputs may
+    /// write, i.e. the executor roots and the shared executor roots of all nodes, and the table
+    /// roots of the materializations.
+    fn state_key_prefixes(
+        &self,
+        fragment_id: u32,
+        actor_id: ActorId,
+        node: &stream_plan::StreamNode,
+    ) -> Vec<Vec<u8>> {
+        dispatch_state_store!(self.state_store.clone(), store, {
+            let mut prefixes = vec![];
+            let mut nodes = vec![node];
+            while let Some(node) = nodes.pop() {
+                let executor_id = ((actor_id as u64) << 32) + node.get_operator_id();
+                let operator_id = ((fragment_id as u64) << 32) + node.get_operator_id();
+                prefixes.extend([
+                    Keyspace::executor_root(store.clone(), executor_id)
+                        .key()
+                        .to_vec(),
+                    Keyspace::shared_executor_root(store.clone(), executor_id)
+                        .key()
+                        .to_vec(),
+                    Keyspace::shared_executor_root(store.clone(), operator_id)
+                        .key()
+                        .to_vec(),
+                ]);
+                if let Some(Node::MaterializeNode(materialize)) = &node.node {
+                    let table_id = TableId::from(&materialize.table_ref_id);
+                    prefixes.push(
+                        Keyspace::table_root(store.clone(), &table_id)
+                            .key()
+                            .to_vec(),
+                    );
+                }
+                nodes.extend(&node.input);
+            }
+            prefixes
+        })
+    }
+
+    /// Returns the label values of the metrics that the executors of `actor` may report.
+    fn actor_metric_labels(
+        actor_id: ActorId,
+        actor: &stream_plan::StreamActor,
+    ) -> Result<ActorMetricLabels> {
+        let mut executor_ids = vec![];
+        let mut nodes = vec![actor.get_nodes()?];
+        while let Some(node) = nodes.pop() {
+            executor_ids.push(((actor_id as u64) << 32) + node.get_operator_id());
+            nodes.extend(&node.input);
+        }
+        Ok(ActorMetricLabels {
+            executor_ids,
+            up_actor_ids: actor.get_upstream_actor_id().to_vec(),
+            down_actor_ids: actor
+                .get_dispatcher()
+                .iter()
+                .flat_map(|dispatcher| dispatcher.downstream_actor_id.iter())
+                .cloned()
+                .collect(),
         })
     }
 
     fn wrap_executor_for_debug(
-        mut executor: Box<dyn Executor>,
+        mut executor: BoxedExecutor,
         actor_id: ActorId,
         input_pos: usize,
         streaming_metrics: Arc<StreamingMetrics>,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         if !cfg!(debug_assertions) {
             return Ok(executor);
         }
@@ -552,20 +714,42 @@ impl LocalStreamManagerCore {
         executor = Box::new(SchemaCheckExecutor::new(executor));
         // Epoch check
         executor = Box::new(EpochCheckExecutor::new(executor));
-        // Cache clear
-        if env_var_is_true(CACHE_CLEAR_ENABLED_ENV_VAR_KEY) {
-            executor = Box::new(CacheClearExecutor::new(executor));
-        }
 
         Ok(executor)
     }
 
+    /// Count the rows output from the executor, which are also the input of the downstream
+    /// executor in the same actor.
+    fn wrap_executor_for_row_count(
+        &self,
+        executor: BoxedExecutor,
+        actor_id: ActorId,
+        executor_id: u64,
+        downstream_executor_id: Option<u64>,
+    ) -> BoxedExecutor {
+        let actor_id = actor_id.to_string();
+        let output_row_count = self
+            .streaming_metrics
+            .executor_output_row_count
+            .with_label_values(&[&actor_id, &format!("{:X}", executor_id)]);
+        let downstream_input_row_count = downstream_executor_id.map(|id| {
+            self.streaming_metrics
+                .executor_input_row_count
+                .with_label_values(&[&actor_id, &format!("{:X}", id)])
+        });
+        Box::new(RowCountExecutor::new(
+            executor,
+            output_row_count,
+            downstream_input_row_count,
+        ))
+    }
+
     pub(crate) fn create_hash_join_node(
         &mut self,
         mut params: ExecutorParams,
         node: &stream_plan::HashJoinNode,
         store: impl StateStore,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let source_r = params.input.remove(1);
         let source_l = params.input.remove(0);
         let params_l = JoinParams::new(
@@ -593,21 +777,60 @@ impl LocalStreamManagerCore {
             .map(|key| *key as usize)
             .collect::<Vec<_>>();
 
+        let interval_bound = node
+            .interval_bound
+            .as_ref()
+            .map(|bound| -> Result<_> {
+                Ok(IntervalJoinBound {
+                    left_time_col: bound.left_time_col as usize,
+                    right_time_col: bound.right_time_col as usize,
+                    lower_bound: IntervalUnit::from(bound.get_lower_bound()?),
+                    upper_bound: IntervalUnit::from(bound.get_upper_bound()?),
+                })
+            })
+            .transpose()?;
+        let cache_capacity = params.env.config().join_cache_capacity;
+        let memory_reporter = params
+            .env
+            .memory_manager()
+            .register(params.actor_id, params.executor_id);
+        let barrier_align_duration = params
+            .executor_stats
+            .barrier_align_duration(params.actor_id, params.executor_id);
+        let join_type_proto = node.get_join_type()?;
+        if interval_bound.is_some() && join_type_proto != JoinTypeProto::Inner {
+            return Err(ErrorCode::NotImplemented(
+                format!("interval join of type {:?}", join_type_proto),
+                None.into(),
+            )
+            .into());
+        }
+
         macro_rules! impl_create_hash_join_executor {
             ($( { $join_type_proto:ident, $join_type:ident } ),*) => {
                 |typ| match typ {
-                    $( JoinTypeProto::$join_type_proto => Box::new(HashJoinExecutor::<_, { JoinType::$join_type }>::new(
-                        source_l,
-                        source_r,
-                        params_l,
-                        params_r,
-                        params.pk_indices,
-                        Keyspace::shared_executor_root(store.clone(), params.operator_id),
-                        params.executor_id,
-                        condition,
-                        params.op_info,
-                        key_indices,
-                    )) as Box<dyn Executor>, )*
+                    $( JoinTypeProto::$join_type_proto => {
+                        let executor = HashJoinExecutor::<_, { JoinType::$join_type }>::new(
+                            source_l,
+                            source_r,
+                            params_l,
+                            params_r,
+                            params.pk_indices,
+                            Keyspace::shared_executor_root(store.clone(), params.operator_id),
+                            params.executor_id,
+                            params.actor_id,
+                            condition,
+                            key_indices,
+                            cache_capacity,
+                        )
+                        .with_memory_reporter(memory_reporter)
+                        .with_barrier_align_duration(barrier_align_duration);
+                        let executor = match interval_bound {
+                            Some(bound) => executor.with_interval_bound(bound),
+                            None => executor,
+                        };
+                        executor.boxed()
+                    }, )*
                     _ => todo!("Join type {:?} not implemented", typ),
                 }
             }
@@ -624,7 +847,6 @@ impl LocalStreamManagerCore {
             };
         }
         let create_hash_join_executor = for_all_join_types! { impl_create_hash_join_executor };
-        let join_type_proto = node.get_join_type()?;
         let executor = create_hash_join_executor(join_type_proto);
         Ok(executor)
     }
@@ -633,31 +855,33 @@ impl LocalStreamManagerCore {
         &mut self,
         params: ExecutorParams,
         node: &stream_plan::MergeNode,
-    ) -> Result<Box<dyn Executor>> {
+    ) -> Result<BoxedExecutor> {
         let upstreams = node.get_upstream_actor_id();
         let fields = node.fields.iter().map(Field::from).collect();
         let schema = Schema::new(fields);
-        let mut rxs = self.get_receive_message(params.actor_id, upstreams)?;
+        let exchange_options = exchange_options(&params.env.config().exchange);
+        let mut rxs = self.get_receive_message(params.actor_id, upstreams, &exchange_options)?;
 
         if upstreams.len() == 1 {
-            Ok(Box::new(
-                Box::new(ReceiverExecutor::new(
-                    schema,
-                    params.pk_indices,
-                    rxs.remove(0),
-                ))
-                .v1(),
-            ))
+            let channel_metrics = self
+                .streaming_metrics
+                .channel_metrics(upstreams[0], params.actor_id);
+            Ok(
+                ReceiverExecutor::new(schema, params.pk_indices, rxs.remove(0))
+                    .with_channel_metrics(channel_metrics)
+                    .boxed(),
+            )
         } else {
-            Ok(Box::new(
-                Box::new(MergeExecutorV2::new(
-                    schema,
-                    params.pk_indices,
-                    params.actor_id,
-                    rxs,
-                ))
-                .v1(),
-            ))
+            Ok(
+                MergeExecutorV2::new(schema, params.pk_indices, params.actor_id, rxs)
+                    .with_upstream_updates(
+                        upstreams.to_vec(),
+                        self.context.clone(),
+                        self.compute_client_pool.clone(),
+                        exchange_options,
+                    )
+                    .boxed(),
+            )
         }
     }
 
@@ -665,6 +889,7 @@ impl LocalStreamManagerCore {
         &mut self,
         actor_id: ActorId,
         upstreams: &[ActorId],
+        exchange_options: &ExchangeOptions,
     ) -> Result<Vec<Receiver<Message>>> {
         assert!(!upstreams.is_empty());
 
@@ -675,35 +900,13 @@ impl LocalStreamManagerCore {
                     Ok(self.mock_source.1.take().unwrap())
                 } else {
                     let upstream_addr = self.get_actor_info(up_id)?.get_host()?.into();
-                    if !is_local_address(&upstream_addr, &self.context.addr) {
-                        // Get the sender for `RemoteInput` to forward received messages to
-                        // receivers in `ReceiverExecutor` or
-                        // `MergerExecutor`.
-                        let sender = self.context.take_sender(&(*up_id, actor_id))?;
-                        // spawn the `RemoteInput`
-                        let up_id = *up_id;
-
-                        let pool = self.compute_client_pool.clone();
-
-                        tokio::spawn(async move {
-                            let init_client = async move {
-                                let remote_input = RemoteInput::create(
-                                    pool.get_client_for_addr(upstream_addr).await?,
-                                    (up_id, actor_id),
-                                    sender,
-                                )
-                                .await?;
-                                Ok::<_, RwError>(remote_input)
-                            };
-                            match init_client.await {
-                                Ok(remote_input) => remote_input.run().await,
-                                Err(e) => {
-                                    error!("Spawn remote input fails:{}", e);
-                                }
-                            }
-                        });
-                    }
-                    Ok::<_, RwError>(self.context.take_receiver(&(*up_id, actor_id))?)
+                    new_input(
+                        &self.context,
+                        self.compute_client_pool.clone(),
+                        upstream_addr,
+                        (*up_id, actor_id),
+                        exchange_options.clone(),
+                    )
                 }
             })
             .collect::<Result<Vec<_>>>()?;
@@ -726,6 +929,10 @@ impl LocalStreamManagerCore {
             let actor = self.actors.remove(&actor_id).unwrap();
             let executor =
                 self.create_nodes(actor.fragment_id, actor_id, actor.get_nodes()?, env.clone())?;
+            let prefixes = self.state_key_prefixes(actor.fragment_id, actor_id, actor.get_nodes()?);
+            self.state_key_prefixes.insert(actor_id, prefixes);
+            let labels = Self::actor_metric_labels(actor_id, &actor)?;
+            self.actor_metric_labels.insert(actor_id, labels);
 
             let dispatchers = actor.get_dispatcher();
             assert_eq!(
@@ -738,11 +945,17 @@ impl LocalStreamManagerCore {
             trace!("build actor: {:#?}", &dispatcher);
 
             let actor = Actor::new(dispatcher, actor_id, self.context.clone());
+            let context = self.context.clone();
             self.handles.insert(
                 actor_id,
                 tokio::spawn(async move {
-                    // unwrap the actor result to panic on error
-                    actor.run().await.expect("actor failed");
+                    // Report the failure to the barrier manager, which fails the barrier collection
+                    // and lets the meta service recover the actor.
+                    if let Err(err) = scope_stream_epoch(actor.run()).await {
+                        context
+                            .lock_barrier_manager()
+                            .notify_failure(actor_id, &err);
+                    }
                 }),
             );
         }
@@ -787,14 +1000,22 @@ impl LocalStreamManagerCore {
 
     /// `drop_actor` is invoked by meta node via RPC once the stop barrier arrives at the
     /// sink. All the actors in the actors should stop themselves before this method is invoked.
-    fn drop_actor(&mut self, actor_id: ActorId) {
-        let handle = self.handles.remove(&actor_id).unwrap();
-        self.context.retain(|&(up_id, _)| up_id != actor_id);
+    /// Drop the actor with `actor_id`. The actor may not exist on this node, since the recovery of
+    /// meta service drops the actors to rebuild on all nodes.
+    /// Returns the aborted handle of the actor task if it's still tracked.
+    fn drop_actor(&mut self, actor_id: ActorId) -> Option<ActorHandle> {
+        self.context
+            .retain(|&(up_id, down_id)| up_id != actor_id && down_id != actor_id);
 
         self.actor_infos.remove(&actor_id);
         self.actors.remove(&actor_id);
-        // Task should have already stopped when this method is invoked.
+        self.state_key_prefixes.remove(&actor_id);
+        self.remove_actor_metrics(actor_id);
+        // Task should have already stopped when this method is invoked, unless it's dropped for
+        // recovery.
+        let handle = self.handles.remove(&actor_id)?;
         handle.abort();
+        Some(handle)
     }
 
     /// `drop_all_actors` is invoked by meta node via RPC once the stop barrier arrives at all the
@@ -807,6 +1028,23 @@ impl LocalStreamManagerCore {
             handle.abort();
         }
         self.actor_infos.clear();
+        self.state_key_prefixes.clear();
+        for actor_id in self.actor_metric_labels.keys().cloned().collect_vec() {
+            self.remove_actor_metrics(actor_id);
+        }
+    }
+
+    /// Removes the label values of the metrics reported by the actor, so that a dropped actor
+    /// doesn't leave its metrics behind.
+    fn remove_actor_metrics(&mut self, actor_id: ActorId) {
+        if let Some(labels) = self.actor_metric_labels.remove(&actor_id) {
+            self.streaming_metrics.remove_actor_metrics(
+                actor_id,
+                &labels.executor_ids,
+                &labels.up_actor_ids,
+                &labels.down_actor_ids,
+            );
+        }
     }
 
     fn build_channel_for_chain_node(
@@ -43,6 +43,9 @@ pub enum StatementType {
     SHOW_PARAMETERS,
     SHOW_COMMAND,
     FLUSH,
+    ALTER_SOURCE,
+    ALTER_MATERIALIZED_VIEW,
+    CANCEL_JOB,
     OTHER,
     // EMPTY is used when query statement is empty (e.g. ";").
     EMPTY,